@@ -0,0 +1,163 @@
+use crate::*;
+
+const STEAM_PLAYER_ACHIEVEMENTS_ENDPOINT: &str =
+    "https://api.steampowered.com/ISteamUserStats/GetPlayerAchievements/v1/";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct AchievementProgress {
+    pub unlocked: i64,
+    pub total: i64,
+}
+
+impl AchievementProgress {
+    /// A game counts as completed once every achievement it has is unlocked.
+    pub(crate) fn is_completed(&self) -> bool {
+        self.total > 0 && self.unlocked >= self.total
+    }
+}
+
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AchievementsRefreshResponse {
+    pub refreshed_games: usize,
+    pub completed_games: usize,
+    pub failed_games: usize,
+}
+
+/// Reads a `GetPlayerAchievements` response. Games without stats answer with
+/// `success: false`, which is reported as no progress rather than an error.
+fn parse_player_achievements(body: &serde_json::Value) -> Option<AchievementProgress> {
+    let stats = body.get("playerstats")?;
+    if stats.get("success").and_then(serde_json::Value::as_bool) == Some(false) {
+        return None;
+    }
+    let achievements = stats.get("achievements")?.as_array()?;
+    let unlocked = achievements
+        .iter()
+        .filter(|achievement| achievement.get("achieved").and_then(serde_json::Value::as_i64) == Some(1))
+        .count();
+    Some(AchievementProgress {
+        unlocked: unlocked as i64,
+        total: achievements.len() as i64,
+    })
+}
+
+fn fetch_steam_player_achievements(
+    client: &Client,
+    api_key: &str,
+    steam_id: &str,
+    app_id: &str,
+) -> Result<Option<AchievementProgress>, String> {
+    let mut request_url = Url::parse(STEAM_PLAYER_ACHIEVEMENTS_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam achievements endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("key", api_key)
+        .append_pair("steamid", steam_id)
+        .append_pair("appid", app_id);
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam achievements request failed: {error}"))?;
+    // Steam answers 400 for apps without stats, with the same `success: false` body.
+    if !response.status().is_success() && response.status() != reqwest::StatusCode::BAD_REQUEST {
+        return Err(format!("Steam achievements request failed with status {}", response.status()));
+    }
+    let body = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam achievements response: {error}"))?;
+    Ok(parse_player_achievements(&body))
+}
+
+fn save_achievement_progress(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    progress: AchievementProgress,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            INSERT INTO game_achievement_progress (user_id, provider, external_id, unlocked_count, total_count, completed, refreshed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+            ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+              unlocked_count = excluded.unlocked_count,
+              total_count = excluded.total_count,
+              completed = excluded.completed,
+              refreshed_at = excluded.refreshed_at
+            ",
+            params![
+                user_id,
+                provider,
+                external_id,
+                progress.unlocked,
+                progress.total,
+                i64::from(progress.is_completed()),
+                Utc::now().to_rfc3339(),
+            ],
+        )
+        .map_err(|error| format!("Failed to store achievement progress: {error}"))?;
+    Ok(())
+}
+
+/// Refreshes achievement progress for the given Steam apps and recalculates their `completed`
+/// flag. A failing app is counted and skipped so one private or delisted game doesn't stop the
+/// rest.
+pub(crate) fn refresh_steam_achievements(
+    connection: &Connection,
+    client: &Client,
+    api_key: &str,
+    user_id: &str,
+    steam_id: &str,
+    app_ids: &[String],
+) -> Result<AchievementsRefreshResponse, String> {
+    let mut response = AchievementsRefreshResponse::default();
+    for app_id in app_ids {
+        match fetch_steam_player_achievements(client, api_key, steam_id, app_id) {
+            Ok(Some(progress)) => {
+                save_achievement_progress(connection, user_id, "steam", app_id, progress)?;
+                response.refreshed_games += 1;
+                if progress.is_completed() {
+                    response.completed_games += 1;
+                }
+            }
+            Ok(None) => {}
+            Err(error) => {
+                eprintln!("Achievements refresh for app {app_id} failed: {error}");
+                response.failed_games += 1;
+            }
+        }
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_player_achievements_counts_unlocked() {
+        let progress = parse_player_achievements(&serde_json::json!({
+            "playerstats": {
+                "steamID": "76561197960287930",
+                "achievements": [
+                    { "apiname": "ACH_WIN", "achieved": 1, "unlocktime": 1700000000 },
+                    { "apiname": "ACH_LOSE", "achieved": 1, "unlocktime": 1700000100 },
+                    { "apiname": "ACH_SECRET", "achieved": 0, "unlocktime": 0 }
+                ],
+                "success": true
+            }
+        }))
+        .expect("progress");
+
+        assert_eq!(progress, AchievementProgress { unlocked: 2, total: 3 });
+        assert!(!progress.is_completed());
+        assert!(AchievementProgress { unlocked: 3, total: 3 }.is_completed());
+        assert!(!AchievementProgress { unlocked: 0, total: 0 }.is_completed());
+        assert_eq!(
+            parse_player_achievements(&serde_json::json!({
+                "playerstats": { "error": "Requested app has no stats", "success": false }
+            })),
+            None
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use crate::*;
+use crate::application::error::{AppError, AppErrorKind, AppResult};
+use crate::application::services::{batch_service, collection_service, game_actions_service, library_service};
+use std::net::SocketAddr;
+use tauri::AppHandle;
+
+pub(crate) const API_SERVER_DEFAULT_PORT: u16 = 47_820;
+const API_SERVER_TOKEN_ACCOUNT: &str = "api-server-token";
+const API_SERVER_POLL_INTERVAL: Duration = Duration::from_millis(100);
+const API_SERVER_READ_TIMEOUT: Duration = Duration::from_secs(5);
+const API_SERVER_MAX_REQUEST_BYTES: usize = 64 * 1024;
+/// The only endpoint other machines may call with `allow_lan`; launching games and batches
+/// stay loopback-only because the token crosses the network in plain HTTP.
+const API_SERVER_LAN_ENDPOINT: (&str, &str) = ("POST", "/api/install");
+
+/// Stored under `APP_SETTING_API_SERVER`. The token lives in the OS keychain instead.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApiServerSettings {
+    pub enabled: bool,
+    pub port: u16,
+    /// Listens on every interface instead of loopback only, so another Catalyst on the network
+    /// (e.g. a laptop) can start downloads here. The token is still required, and other
+    /// machines can only reach `/api/install`.
+    #[serde(default)]
+    pub allow_lan: bool,
+}
+
+impl Default for ApiServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: API_SERVER_DEFAULT_PORT,
+            allow_lan: false,
+        }
+    }
+}
+
+/// What the server runs with, kept in `AppState` so settings changes apply without a restart.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ApiServerConfig {
+    pub settings: ApiServerSettings,
+    pub token: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ApiServerSettingsResponse {
+    pub enabled: bool,
+    pub port: u16,
+    pub allow_lan: bool,
+    pub base_url: String,
+    /// Sent by clients as `Authorization: Bearer <token>`.
+    pub token: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+struct ApiRequest {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    host: Option<String>,
+    bearer_token: Option<String>,
+    body: Vec<u8>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiPlayRequest {
+    provider: String,
+    external_id: String,
+    launch_options: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiInstallRequest {
+    provider: String,
+    external_id: String,
+}
+
+pub(crate) fn load_api_server_settings(connection: &Connection) -> Result<ApiServerSettings, String> {
+    let settings = load_app_settings(connection)?;
+    match settings.get(APP_SETTING_API_SERVER) {
+        Some(value) => serde_json::from_str(value)
+            .map_err(|error| format!("Stored API server settings are invalid: {error}")),
+        None => Ok(ApiServerSettings::default()),
+    }
+}
+
+pub(crate) fn save_api_server_settings(connection: &Connection, settings: &ApiServerSettings) -> Result<(), String> {
+    let serialized = serde_json::to_string(settings)
+        .map_err(|error| format!("Failed to serialize API server settings: {error}"))?;
+    save_app_setting(connection, APP_SETTING_API_SERVER, Some(&serialized))
+}
+
+pub(crate) fn read_api_server_token() -> Result<Option<String>, String> {
+    secret_store::read_secret(API_SERVER_TOKEN_ACCOUNT)
+}
+
+/// Replaces the token, which invalidates every client configured with the old one.
+pub(crate) fn generate_api_server_token() -> Result<String, String> {
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    secret_store::write_secret(API_SERVER_TOKEN_ACCOUNT, &token)?;
+    Ok(token)
+}
+
+pub(crate) fn api_server_config(state: &AppState) -> ApiServerConfig {
+    state
+        .api_server
+        .lock()
+        .map(|config| config.clone())
+        .unwrap_or_default()
+}
+
+pub(crate) fn set_api_server_config(state: &AppState, config: ApiServerConfig) {
+    if let Ok(mut guard) = state.api_server.lock() {
+        *guard = config;
+    }
+}
+
+pub(crate) fn api_server_settings_response(config: &ApiServerConfig) -> ApiServerSettingsResponse {
+    ApiServerSettingsResponse {
+        enabled: config.settings.enabled,
+        port: config.settings.port,
+        allow_lan: config.settings.allow_lan,
+        base_url: format!("http://127.0.0.1:{}/api", config.settings.port),
+        token: config.token.clone(),
+    }
+}
+
+fn parse_api_request(bytes: &[u8]) -> Result<Option<ApiRequest>, String> {
+    let Some(head_end) = bytes.windows(4).position(|window| window == b"\r\n\r\n") else {
+        return Ok(None);
+    };
+    let head = String::from_utf8_lossy(&bytes[..head_end]);
+    let mut lines = head.lines();
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_owned();
+    let target = request_line.next().unwrap_or_default();
+    if method.is_empty() || !target.starts_with('/') {
+        return Err(String::from("Request line is malformed"));
+    }
+    let url = Url::parse(&format!("http://localhost{target}")).map_err(|error| format!("Invalid request target: {error}"))?;
+
+    let mut host = None;
+    let mut bearer_token = None;
+    let mut content_length = 0_usize;
+    for line in lines {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match name.trim().to_ascii_lowercase().as_str() {
+            "host" => host = Some(value.to_ascii_lowercase()),
+            "authorization" => {
+                bearer_token = value
+                    .strip_prefix("Bearer ")
+                    .or_else(|| value.strip_prefix("bearer "))
+                    .map(|token| token.trim().to_owned());
+            }
+            "content-length" => {
+                content_length = value.parse().map_err(|_| String::from("Invalid Content-Length"))?;
+            }
+            _ => {}
+        }
+    }
+    if content_length > API_SERVER_MAX_REQUEST_BYTES {
+        return Err(String::from("Request body is too large"));
+    }
+    let body_start = head_end + 4;
+    if bytes.len() < body_start + content_length {
+        return Ok(None);
+    }
+
+    Ok(Some(ApiRequest {
+        method,
+        path: url.path().trim_end_matches('/').to_owned(),
+        query: url.query_pairs().into_owned().collect(),
+        host,
+        bearer_token,
+        body: bytes[body_start..body_start + content_length].to_vec(),
+    }))
+}
+
+fn read_api_request(stream: &mut TcpStream) -> Result<ApiRequest, String> {
+    let mut request = Vec::new();
+    let mut buffer = [0_u8; 4096];
+    loop {
+        if let Some(parsed) = parse_api_request(&request)? {
+            return Ok(parsed);
+        }
+        if request.len() > API_SERVER_MAX_REQUEST_BYTES {
+            return Err(String::from("Request is too large"));
+        }
+        let bytes_read = stream
+            .read(&mut buffer)
+            .map_err(|error| format!("Failed to read request: {error}"))?;
+        if bytes_read == 0 {
+            return Err(String::from("Connection closed before the request was complete"));
+        }
+        request.extend_from_slice(&buffer[..bytes_read]);
+    }
+}
+
+fn write_api_response(stream: &mut TcpStream, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes());
+    let _ = stream.flush();
+}
+
+fn api_error_status(error: &AppError) -> &'static str {
+    match error.kind {
+        AppErrorKind::Validation => "400 Bad Request",
+        AppErrorKind::Unauthorized => "401 Unauthorized",
+        AppErrorKind::NotFound => "404 Not Found",
+        AppErrorKind::Conflict => "409 Conflict",
+        AppErrorKind::External => "502 Bad Gateway",
+        AppErrorKind::Internal => "500 Internal Server Error",
+    }
+}
+
+fn to_api_value<T: Serialize>(value: T) -> AppResult<serde_json::Value> {
+    serde_json::to_value(value)
+        .map_err(|error| AppError::internal("serialization_failed", format!("Failed to serialize response: {error}")))
+}
+
+fn parse_api_body<T: serde::de::DeserializeOwned>(body: &[u8]) -> AppResult<T> {
+    serde_json::from_slice(body)
+        .map_err(|error| AppError::validation("invalid_request_body", format!("Invalid JSON body: {error}")))
+}
+
+fn query_flag(query: &HashMap<String, String>, key: &str) -> Option<bool> {
+    query.get(key).and_then(|value| parse_app_setting_flag(value))
+}
+
+/// Mirrors the Tauri commands external tools need: reading the library and collections,
+/// launching games, and the read-only commands of `batch`.
+fn route_api_request(state: &AppState, request: &ApiRequest) -> AppResult<serde_json::Value> {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api/library") => to_api_value(library_service::get_library(
+            state,
+            query_flag(&request.query, "merged"),
+            None,
+            query_flag(&request.query, "includeHidden"),
+        )?),
+        ("GET", "/api/collections") => to_api_value(collection_service::list_collections(
+            state,
+            None,
+            None,
+            query_flag(&request.query, "includeHidden"),
+        )?),
+        ("POST", "/api/play") => {
+            let play: ApiPlayRequest = parse_api_body(&request.body)?;
+            game_actions_service::play_game(state, play.provider, play.external_id, play.launch_options)?;
+            Ok(serde_json::json!({ "launched": true }))
+        }
+        ("POST", "/api/install") => {
+            let install: ApiInstallRequest = parse_api_body(&request.body)?;
+            to_api_value(game_actions_service::install_game(
+                state,
+                install.provider,
+                install.external_id,
+                None,
+                None,
+                None,
+                None,
+            )?)
+        }
+        ("POST", "/api/batch") => to_api_value(batch_service::run_batch(state, parse_api_body(&request.body)?)?),
+        _ => Err(AppError::not_found("unknown_endpoint", "No such API endpoint.")),
+    }
+}
+
+/// Token and `Host` are both checked: the token keeps other local programs out and the `Host`
+/// check stops web pages from reaching the server through DNS rebinding. LAN clients address
+/// the machine by its own name or IP, so only the token applies to them, and only on
+/// `API_SERVER_LAN_ENDPOINT`.
+fn authorize_api_request(
+    request: &ApiRequest,
+    from_loopback: bool,
+    port: u16,
+    token: &str,
+    allow_lan: bool,
+) -> AppResult<()> {
+    if !from_loopback {
+        if !allow_lan || (request.method.as_str(), request.path.as_str()) != API_SERVER_LAN_ENDPOINT {
+            return Err(AppError::unauthorized(
+                "lan_request_not_allowed",
+                "Only downloads can be started from another machine.",
+            ));
+        }
+    } else if !request.host.as_deref().is_some_and(|host| {
+        [format!("127.0.0.1:{port}"), format!("localhost:{port}")]
+            .iter()
+            .any(|allowed| allowed == host)
+    }) {
+        return Err(AppError::unauthorized("invalid_host", "Requests must be addressed to localhost."));
+    }
+    let token_matches = request
+        .bearer_token
+        .as_deref()
+        .is_some_and(|provided| constant_time_eq(provided.as_bytes(), token.as_bytes()));
+    if !token_matches {
+        return Err(AppError::unauthorized("invalid_token", "Missing or invalid API token."));
+    }
+    Ok(())
+}
+
+fn handle_api_connection(
+    state: &AppState,
+    stream: &mut TcpStream,
+    peer: SocketAddr,
+    settings: &ApiServerSettings,
+    token: &str,
+) {
+    // Accepted sockets inherit non-blocking mode from the listener on some platforms.
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(API_SERVER_READ_TIMEOUT));
+    let result = read_api_request(stream)
+        .map_err(|error| AppError::validation("invalid_request", error))
+        .and_then(|request| {
+            authorize_api_request(&request, peer.ip().is_loopback(), settings.port, token, settings.allow_lan)?;
+            route_api_request(state, &request)
+        });
+    match result {
+        Ok(data) => write_api_response(stream, "200 OK", &data.to_string()),
+        Err(error) => {
+            let body = serde_json::to_string(&error).unwrap_or_default();
+            write_api_response(stream, api_error_status(&error), &body);
+        }
+    }
+}
+
+/// Runs for the app's lifetime and (re)binds `127.0.0.1:<port>` whenever the settings enable
+/// the server or change its port. Requests are served one at a time.
+pub(crate) fn spawn_api_server(app: AppHandle) {
+    {
+        let state = app.state::<AppState>();
+        let settings = open_connection(&state.db_path).and_then(|connection| load_api_server_settings(&connection));
+        let token = read_api_server_token();
+        match (settings, token) {
+            (Ok(settings), Ok(token)) => set_api_server_config(state.inner(), ApiServerConfig { settings, token }),
+            (Err(error), _) | (_, Err(error)) => eprintln!("Failed to load API server settings: {error}"),
+        }
+    }
+
+    thread::spawn(move || {
+        let mut listener: Option<(ApiServerSettings, TcpListener)> = None;
+        loop {
+            let state = app.state::<AppState>();
+            let config = api_server_config(state.inner());
+            let wanted = match (config.settings.enabled, config.token.as_deref()) {
+                (true, Some(token)) if !token.is_empty() => Some((config.settings.clone(), token.to_owned())),
+                _ => None,
+            };
+
+            let bound_settings = listener.as_ref().map(|(settings, _)| settings.clone());
+            match &wanted {
+                None => listener = None,
+                Some((settings, _)) if bound_settings.as_ref() != Some(settings) => {
+                    let address = if settings.allow_lan { [0, 0, 0, 0] } else { [127, 0, 0, 1] };
+                    let port = settings.port;
+                    // Drop the old socket first so a changed interface can rebind the same port.
+                    drop(listener.take());
+                    listener = TcpListener::bind(SocketAddr::from((address, port)))
+                        .and_then(|bound| bound.set_nonblocking(true).map(|_| bound))
+                        .map(|bound| (settings.clone(), bound))
+                        .map_err(|error| eprintln!("Failed to start API server on port {port}: {error}"))
+                        .ok();
+                    if listener.is_none() {
+                        // Don't retry a taken port every tick; wait for the settings to change.
+                        let mut disabled = config.clone();
+                        disabled.settings.enabled = false;
+                        set_api_server_config(state.inner(), disabled);
+                    }
+                }
+                Some(_) => {}
+            }
+
+            match (&listener, &wanted) {
+                (Some((settings, bound)), Some((_, token))) => match bound.accept() {
+                    Ok((mut stream, peer)) => handle_api_connection(state.inner(), &mut stream, peer, settings, token),
+                    Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
+                        thread::sleep(API_SERVER_POLL_INTERVAL)
+                    }
+                    Err(error) => {
+                        eprintln!("API server failed to accept a connection: {error}");
+                        thread::sleep(API_SERVER_POLL_INTERVAL);
+                    }
+                },
+                _ => thread::sleep(API_SERVER_POLL_INTERVAL * 10),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_api_request_reads_token_host_and_body() {
+        let raw = b"POST /api/play?x=1 HTTP/1.1\r\nHost: 127.0.0.1:47820\r\nAuthorization: Bearer abc\r\nContent-Length: 2\r\n\r\n{}";
+
+        assert_eq!(parse_api_request(&raw[..raw.len() - 1]).expect("parse partial"), None);
+        let request = parse_api_request(raw).expect("parse").expect("complete request");
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/play");
+        assert_eq!(request.query.get("x").map(String::as_str), Some("1"));
+        assert_eq!(request.body, b"{}");
+        assert!(authorize_api_request(&request, true, 47_820, "abc", false).is_ok());
+        assert!(authorize_api_request(&request, true, 47_820, "abd", false).is_err());
+        assert!(authorize_api_request(&request, true, 8080, "abc", false).is_err());
+    }
+
+    #[test]
+    fn authorize_api_request_only_lets_lan_clients_install() {
+        let raw = b"POST /api/install HTTP/1.1\r\nHost: 192.168.1.20:47820\r\nAuthorization: Bearer abc\r\nContent-Length: 0\r\n\r\n";
+        let install = parse_api_request(raw).expect("parse").expect("complete request");
+        assert!(authorize_api_request(&install, false, 47_820, "abc", true).is_ok());
+        assert!(authorize_api_request(&install, false, 47_820, "abd", true).is_err());
+        assert!(authorize_api_request(&install, false, 47_820, "abc", false).is_err());
+        // Loopback clients still have to address localhost, even with LAN access on.
+        assert!(authorize_api_request(&install, true, 47_820, "abc", true).is_err());
+
+        for path in ["/api/play", "/api/batch", "/api/library"] {
+            let mut request = parse_api_request(raw).expect("parse").expect("complete request");
+            request.path = path.to_owned();
+            let error = authorize_api_request(&request, false, 47_820, "abc", true).expect_err("LAN request");
+            assert_eq!(error.code, "lan_request_not_allowed");
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::*;
+use crate::application::error::AppResult;
+
+/// An open database connection and the signed-in user, the starting point of most services.
+/// Expired sessions are purged by `spawn_session_cleanup_scheduler`, not here; session lookups
+/// already ignore them.
+pub(crate) struct AuthedContext {
+    pub(crate) connection: Connection,
+    pub(crate) user: UserRow,
+}
+
+impl AuthedContext {
+    pub(crate) fn new(state: &AppState) -> AppResult<Self> {
+        let connection = open_connection(&state.db_path)?;
+        let user = get_authenticated_user(state, &connection)?;
+        Ok(Self { connection, user })
+    }
+}
+
+/// `AuthedContext` for a game the user owns, with the provider and external ID normalized.
+pub(crate) struct AuthedGameContext {
+    pub(crate) connection: Connection,
+    pub(crate) user: UserRow,
+    pub(crate) provider: String,
+    pub(crate) external_id: String,
+}
+
+impl AuthedGameContext {
+    pub(crate) fn new(state: &AppState, provider: &str, external_id: &str) -> AppResult<Self> {
+        let AuthedContext { connection, user } = AuthedContext::new(state)?;
+        let (provider, external_id) = normalize_game_identity_input(provider, external_id)?;
+        ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+        Ok(Self {
+            connection,
+            user,
+            provider,
+            external_id,
+        })
+    }
+}
+
+/// For services that only need a signed-in user, not their data.
+pub(crate) fn require_authenticated_user(state: &AppState) -> AppResult<()> {
+    AuthedContext::new(state).map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn authed_game_context_normalizes_and_checks_ownership() {
+        let database = test_support::test_database();
+        let user = &database.user;
+        assert!(AuthedContext::new(&database.app_state()).is_err());
+
+        database.insert_game("steam", "620", "Portal 2");
+        let state = database.signed_in_state();
+
+        let context = AuthedGameContext::new(&state, " Steam ", " 620 ").expect("owned game");
+        assert_eq!(context.user.id, user.id);
+        assert_eq!((context.provider.as_str(), context.external_id.as_str()), ("steam", "620"));
+        assert!(AuthedGameContext::new(&state, "steam", "400").is_err());
+    }
+
+    #[test]
+    fn authed_context_rejects_expired_sessions_before_cleanup_runs() {
+        let database = test_support::test_database();
+        let state = database.signed_in_state();
+        database
+            .connection
+            .execute(
+                "UPDATE sessions SET expires_at = ?1 WHERE user_id = ?2",
+                params![(Utc::now() - ChronoDuration::minutes(1)).to_rfc3339(), database.user.id],
+            )
+            .expect("expire session");
+
+        let error = AuthedContext::new(&state).err().expect("expired session");
+        assert_eq!(error.message, "Session expired or invalid");
+        // The stale token is signed out of the app state as well.
+        assert!(state.current_session_token.lock().expect("session lock").is_none());
+
+        cleanup_expired_sessions(&database.connection).expect("clean up sessions");
+        let remaining_sessions: i64 = database
+            .connection
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
+            .expect("count sessions");
+        assert_eq!(remaining_sessions, 0);
+    }
+
+    #[test]
+    fn authed_game_context_rejects_blank_identities_and_other_users_games() {
+        let database = test_support::test_database();
+        let state = database.signed_in_state();
+        let other_user = create_user(&database.connection, "friend@example.com", "hash", None).expect("create user");
+        database
+            .connection
+            .execute(
+                "
+                INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, last_synced_at)
+                VALUES (?1, 'steam', '620', 'Portal 2', 'game', 0, '2024-01-01T00:00:00Z')
+                ",
+                params![other_user.id],
+            )
+            .expect("insert other user's game");
+
+        for (provider, external_id) in [(" ", "620"), ("steam", "  ")] {
+            assert!(AuthedGameContext::new(&state, provider, external_id).is_err());
+        }
+        assert!(AuthedGameContext::new(&state, "steam", "620").is_err());
+        assert!(AuthedGameContext::new(&database.app_state(), "steam", "620").is_err());
+    }
+}
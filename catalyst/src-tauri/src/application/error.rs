@@ -1,4 +1,7 @@
 use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::i18n::LocalizedMessage;
 
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -17,57 +20,49 @@ pub(crate) struct AppError {
     pub(crate) kind: AppErrorKind,
     pub(crate) code: &'static str,
     pub(crate) message: String,
+    /// Catalog code and parameters of `message`, so the UI can translate it itself. Only set
+    /// for messages created with `i18n::localized`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) message_code: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub(crate) message_params: BTreeMap<String, String>,
 }
 
 pub(crate) type AppResult<T> = Result<T, AppError>;
 
 impl AppError {
-    pub(crate) fn validation(code: &'static str, message: impl Into<String>) -> Self {
+    fn new(kind: AppErrorKind, code: &'static str, message: LocalizedMessage) -> Self {
         Self {
-            kind: AppErrorKind::Validation,
+            kind,
             code,
-            message: message.into(),
+            message: message.message,
+            message_code: message.message_code,
+            message_params: message.message_params,
         }
     }
 
-    pub(crate) fn unauthorized(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            kind: AppErrorKind::Unauthorized,
-            code,
-            message: message.into(),
-        }
+    pub(crate) fn validation(code: &'static str, message: impl Into<LocalizedMessage>) -> Self {
+        Self::new(AppErrorKind::Validation, code, message.into())
     }
 
-    pub(crate) fn not_found(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            kind: AppErrorKind::NotFound,
-            code,
-            message: message.into(),
-        }
+    pub(crate) fn unauthorized(code: &'static str, message: impl Into<LocalizedMessage>) -> Self {
+        Self::new(AppErrorKind::Unauthorized, code, message.into())
     }
 
-    pub(crate) fn conflict(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            kind: AppErrorKind::Conflict,
-            code,
-            message: message.into(),
-        }
+    pub(crate) fn not_found(code: &'static str, message: impl Into<LocalizedMessage>) -> Self {
+        Self::new(AppErrorKind::NotFound, code, message.into())
     }
 
-    pub(crate) fn external(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            kind: AppErrorKind::External,
-            code,
-            message: message.into(),
-        }
+    pub(crate) fn conflict(code: &'static str, message: impl Into<LocalizedMessage>) -> Self {
+        Self::new(AppErrorKind::Conflict, code, message.into())
     }
 
-    pub(crate) fn internal(code: &'static str, message: impl Into<String>) -> Self {
-        Self {
-            kind: AppErrorKind::Internal,
-            code,
-            message: message.into(),
-        }
+    pub(crate) fn external(code: &'static str, message: impl Into<LocalizedMessage>) -> Self {
+        Self::new(AppErrorKind::External, code, message.into())
+    }
+
+    pub(crate) fn internal(code: &'static str, message: impl Into<LocalizedMessage>) -> Self {
+        Self::new(AppErrorKind::Internal, code, message.into())
     }
 }
 
@@ -99,6 +94,10 @@ pub(crate) fn map_app_error_message(message: impl Into<String>) -> AppError {
         return AppError::not_found("not_found_error", message);
     }
 
+    if normalized.contains("offline mode") {
+        return AppError::conflict("offline_mode", crate::i18n::localized("offline_mode", &[]));
+    }
+
     if normalized.contains("steam")
         || normalized.contains("request failed")
         || normalized.contains("timed out")
@@ -120,3 +119,21 @@ impl From<&str> for AppError {
         map_app_error_message(value.to_owned())
     }
 }
+
+impl From<String> for LocalizedMessage {
+    fn from(value: String) -> Self {
+        LocalizedMessage::untranslated(value)
+    }
+}
+
+impl From<&String> for LocalizedMessage {
+    fn from(value: &String) -> Self {
+        LocalizedMessage::untranslated(value.clone())
+    }
+}
+
+impl From<&str> for LocalizedMessage {
+    fn from(value: &str) -> Self {
+        LocalizedMessage::untranslated(value)
+    }
+}
@@ -0,0 +1,401 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+use crate::application::context::AuthedContext;
+
+/// What accounts without a usable password type to confirm deletion.
+const DELETE_ACCOUNT_CONFIRMATION_PHRASE: &str = "delete my account";
+
+pub(crate) fn get_session(state: &AppState) -> AppResult<Option<PublicUser>> {
+	let connection = open_connection(&state.db_path)?;
+
+	let Some(session_token) = get_state_session_token(state)? else {
+		return Ok(None);
+	};
+
+	let user = find_user_by_session_token(&connection, &session_token)?;
+	if user.is_none() {
+		clear_active_session(state)?;
+	}
+
+	Ok(user.map(|row| public_user_from_row(&row)))
+}
+
+pub(crate) fn list_sessions(state: &AppState) -> AppResult<Vec<SessionResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let current_session_token = get_state_session_token(state)?;
+	Ok(list_sessions_by_user(
+		&connection,
+		&user.id,
+		current_session_token.as_deref(),
+	)?)
+}
+
+pub(crate) fn revoke_session(state: &AppState, session_id: String) -> AppResult<()> {
+	let trimmed_session_id = session_id.trim();
+	if trimmed_session_id.is_empty() {
+		return Err(AppError::validation(
+			"session_id_required",
+			"Session ID is required",
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	revoke_user_session(&connection, &user.id, trimmed_session_id)?;
+
+	let revoked_current_session = get_state_session_token(state)?
+		.map(|token| hash_session_token(&token) == trimmed_session_id)
+		.unwrap_or(false);
+	if revoked_current_session {
+		clear_active_session(state)?;
+	}
+
+	Ok(())
+}
+
+pub(crate) fn delete_account(state: &AppState, password: String) -> AppResult<()> {
+	if password.trim().is_empty() {
+		return Err(AppError::validation(
+			"password_required",
+			"Password confirmation is required to delete this account",
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let password_hash = find_user_password_hash(&connection, &user.id)?
+		.ok_or_else(|| AppError::not_found("user_not_found", "User not found"))?;
+
+	// Accounts created through Steam SSO only have a random placeholder password. Their public
+	// Steam ID is no confirmation at all, so they type a fixed phrase instead. Only the phrase is
+	// trimmed; a password is checked exactly as typed.
+	if is_placeholder_steam_account(&user) {
+		if !password.trim().eq_ignore_ascii_case(DELETE_ACCOUNT_CONFIRMATION_PHRASE) {
+			return Err(AppError::unauthorized(
+				"invalid_delete_confirmation",
+				format!("Type \"{DELETE_ACCOUNT_CONFIRMATION_PHRASE}\" to delete this account"),
+			));
+		}
+	} else if !verify(&password, &password_hash).unwrap_or(false) {
+		return Err(AppError::unauthorized(
+			"invalid_password_confirmation",
+			"Password confirmation did not match",
+		));
+	}
+
+	delete_user_by_id(&connection, &user.id)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(clear_active_session(state)?)
+}
+
+/// Registers a Steam sign-in as the one in progress for as long as it is alive, so
+/// `cancel_steam_auth` can reach it and a second sign-in is refused meanwhile.
+pub(crate) struct SteamAuthFlow<'a> {
+	state: &'a AppState,
+	cancel_requested: Arc<AtomicBool>,
+}
+
+impl<'a> SteamAuthFlow<'a> {
+	pub(crate) fn start(state: &'a AppState) -> AppResult<Self> {
+		let mut active_flow = state
+			.steam_auth_cancel_requested
+			.lock()
+			.map_err(|_| String::from("Failed to lock Steam sign-in state"))?;
+		if active_flow.is_some() {
+			return Err(AppError::conflict(
+				"steam_auth_in_progress",
+				"Steam sign-in is already in progress",
+			));
+		}
+		let cancel_requested = Arc::new(AtomicBool::new(false));
+		*active_flow = Some(Arc::clone(&cancel_requested));
+		Ok(Self {
+			state,
+			cancel_requested,
+		})
+	}
+
+	pub(crate) fn cancel_requested(&self) -> &Arc<AtomicBool> {
+		&self.cancel_requested
+	}
+}
+
+impl Drop for SteamAuthFlow<'_> {
+	fn drop(&mut self) {
+		let mut active_flow = self
+			.state
+			.steam_auth_cancel_requested
+			.lock()
+			.unwrap_or_else(std::sync::PoisonError::into_inner);
+		*active_flow = None;
+	}
+}
+
+/// Aborts the Steam sign-in in progress. Returns `false` when no sign-in was running.
+pub(crate) fn cancel_steam_auth(state: &AppState) -> AppResult<bool> {
+	let active_flow = state
+		.steam_auth_cancel_requested
+		.lock()
+		.map_err(|_| String::from("Failed to lock Steam sign-in state"))?;
+	let Some(cancel_requested) = active_flow.as_ref() else {
+		return Ok(false);
+	};
+	cancel_requested.store(true, Ordering::SeqCst);
+	Ok(true)
+}
+
+pub(crate) fn enable_two_factor(state: &AppState) -> AppResult<two_factor::TwoFactorSetupResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	if two_factor::is_two_factor_enabled(&connection, &user.id)? {
+		return Err(AppError::conflict(
+			"two_factor_already_enabled",
+			"Two-factor authentication is already enabled",
+		));
+	}
+
+	let account_name = user.steam_persona_name.as_deref().unwrap_or(&user.email);
+	Ok(two_factor::begin_two_factor_setup(
+		&connection,
+		&user.id,
+		account_name,
+	)?)
+}
+
+pub(crate) fn verify_two_factor_setup(
+	state: &AppState,
+	code: String,
+) -> AppResult<two_factor::TwoFactorRecoveryCodesResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	if !two_factor::has_pending_two_factor_setup(&connection, &user.id)? {
+		return Err(AppError::conflict(
+			"two_factor_setup_not_started",
+			"Start two-factor setup before verifying a code",
+		));
+	}
+	if !two_factor::check_two_factor_code(&connection, &user.id, &code, false)? {
+		return Err(AppError::unauthorized(
+			"invalid_two_factor_code",
+			"The authentication code is incorrect",
+		));
+	}
+
+	Ok(two_factor::TwoFactorRecoveryCodesResponse {
+		recovery_codes: two_factor::enable_two_factor(&connection, &user.id)?,
+	})
+}
+
+pub(crate) fn disable_two_factor(state: &AppState, code: String) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	if !two_factor::is_two_factor_enabled(&connection, &user.id)? {
+		return Err(AppError::conflict(
+			"two_factor_not_enabled",
+			"Two-factor authentication is not enabled",
+		));
+	}
+	if !two_factor::check_two_factor_code(&connection, &user.id, &code, true)? {
+		return Err(AppError::unauthorized(
+			"invalid_two_factor_code",
+			"The authentication code is incorrect",
+		));
+	}
+
+	Ok(two_factor::disable_two_factor(&connection, &user.id)?)
+}
+
+/// Finishes a Steam sign-in that was held back by two-factor authentication. Accepts either an
+/// authenticator code or a recovery code; too many wrong codes cancel the sign-in.
+pub(crate) fn verify_two_factor_login(state: &AppState, code: String) -> AppResult<SteamAuthResponse> {
+	let mut pending_guard = state
+		.pending_two_factor_login
+		.lock()
+		.map_err(|_| String::from("Failed to lock pending two-factor login"))?;
+	let pending = pending_guard
+		.as_mut()
+		.filter(|pending| pending.expires_at > Instant::now())
+		.ok_or_else(|| {
+			AppError::unauthorized(
+				"two_factor_login_expired",
+				"The sign-in has expired. Sign in with Steam again.",
+			)
+		})?;
+
+	let connection = open_connection(&state.db_path)?;
+	if !two_factor::check_two_factor_code(&connection, &pending.user_id, &code, true)? {
+		pending.failed_attempts += 1;
+		if pending.failed_attempts >= two_factor::TWO_FACTOR_LOGIN_MAX_ATTEMPTS {
+			*pending_guard = None;
+		}
+		return Err(AppError::unauthorized(
+			"invalid_two_factor_code",
+			"The authentication code is incorrect",
+		));
+	}
+
+	let user = find_user_by_id(&connection, &pending.user_id)?
+		.ok_or_else(|| AppError::not_found("user_not_found", "User not found"))?;
+	let synced_games = pending.synced_games;
+	*pending_guard = None;
+	drop(pending_guard);
+
+	let session_token = create_session(&connection, &user.id)?;
+	persist_active_session(state, &session_token)?;
+	Ok(SteamAuthResponse {
+		user: public_user_from_row(&user),
+		synced_games,
+		two_factor_required: false,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use rusqlite::params;
+	use tempfile::tempdir;
+
+	#[test]
+	fn delete_account_removes_user_data_and_session() {
+		let dir = tempdir().unwrap();
+		let db_path = dir.path().join("test.db");
+		let session_path = dir.path().join("session");
+		let state = AppState::new(
+			db_path.clone(),
+			session_path.clone(),
+			None,
+			false,
+			false,
+			None,
+		);
+
+		initialize_database(&db_path).expect("init db");
+
+		let conn = open_connection(&db_path).expect("open conn");
+		let password_hash = hash("correct horse", 4).expect("hash password");
+		let user = create_user(&conn, "test@example.com", &password_hash, None).expect("create user");
+		let token = create_session(&conn, &user.id).expect("create session");
+		persist_active_session(&state, &token).expect("persist session");
+		create_user_collection(&conn, &user.id, "Favorites").expect("create collection");
+
+		let rejected = delete_account(&state, "wrong".to_string());
+		assert!(rejected.is_err(), "wrong password must be rejected");
+
+		delete_account(&state, "correct horse".to_string()).expect("delete account");
+
+		assert!(find_user_by_id(&conn, &user.id).expect("query user").is_none());
+		let remaining_collections: i64 = conn
+			.query_row(
+				"SELECT COUNT(*) FROM collections WHERE user_id = ?1",
+				params![&user.id],
+				|row| row.get(0),
+			)
+			.expect("count collections");
+		assert_eq!(remaining_collections, 0);
+		assert!(get_state_session_token(&state).expect("session lock").is_none());
+		assert!(!session_path.exists());
+	}
+
+	#[test]
+	fn delete_account_checks_the_password_exactly_as_typed() {
+		let database = test_support::test_database();
+		let password_hash = hash(" correct horse ", 4).expect("hash password");
+		let user = create_user(&database.connection, "spaces@example.com", &password_hash, None).expect("create user");
+		let state = database.app_state();
+		let token = create_session(&database.connection, &user.id).expect("create session");
+		persist_active_session(&state, &token).expect("persist session");
+
+		let error = delete_account(&state, String::from("correct horse")).expect_err("trimmed password");
+		assert_eq!(error.code, "invalid_password_confirmation");
+		assert!(find_user_by_id(&database.connection, &user.id).expect("query user").is_some());
+
+		delete_account(&state, String::from(" correct horse ")).expect("delete account");
+		assert!(find_user_by_id(&database.connection, &user.id).expect("query user").is_none());
+	}
+
+	#[test]
+	fn delete_account_requires_the_phrase_for_steam_placeholder_accounts() {
+		let database = test_support::test_database();
+		let steam_user = create_steam_user(&database.connection, "76561197960287930").expect("create steam user");
+		let state = database.app_state();
+		let token = create_session(&database.connection, &steam_user.id).expect("create session");
+		persist_active_session(&state, &token).expect("persist session");
+
+		let rejected = delete_account(&state, String::from("76561197960287930"));
+		assert!(rejected.is_err(), "the public Steam ID must not confirm deletion");
+		assert!(delete_account(&state, String::from("  ")).is_err());
+		assert!(find_user_by_id(&database.connection, &steam_user.id).expect("query user").is_some());
+
+		delete_account(&state, String::from(" Delete my account ")).expect("delete account");
+		assert!(find_user_by_id(&database.connection, &steam_user.id).expect("query user").is_none());
+	}
+
+	fn start_pending_two_factor_login(state: &AppState, user_id: &str, expires_in: Duration) {
+		*state.pending_two_factor_login.lock().expect("pending login lock") =
+			Some(two_factor::PendingTwoFactorLogin {
+				user_id: user_id.to_owned(),
+				synced_games: 3,
+				expires_at: Instant::now() + expires_in,
+				failed_attempts: 0,
+			});
+	}
+
+	#[test]
+	fn verify_two_factor_login_drops_the_sign_in_after_too_many_wrong_codes() {
+		let database = test_support::test_database();
+		let (connection, user) = (&database.connection, &database.user);
+		two_factor::begin_two_factor_setup(connection, &user.id, &user.email).expect("begin setup");
+		let recovery_codes = two_factor::enable_two_factor(connection, &user.id).expect("enable");
+		let state = database.app_state();
+		start_pending_two_factor_login(&state, &user.id, two_factor::TWO_FACTOR_LOGIN_TTL);
+
+		for attempt in 1..=two_factor::TWO_FACTOR_LOGIN_MAX_ATTEMPTS {
+			let error = verify_two_factor_login(&state, String::from("not-a-code")).expect_err("wrong code");
+			assert_eq!(error.code, "invalid_two_factor_code");
+			let pending_cleared = state.pending_two_factor_login.lock().expect("pending login lock").is_none();
+			assert_eq!(pending_cleared, attempt == two_factor::TWO_FACTOR_LOGIN_MAX_ATTEMPTS);
+		}
+
+		let error = verify_two_factor_login(&state, recovery_codes[0].clone()).expect_err("sign-in dropped");
+		assert_eq!(error.code, "two_factor_login_expired");
+		assert!(state.current_session_token.lock().expect("session lock").is_none());
+	}
+
+	#[test]
+	fn verify_two_factor_login_consumes_recovery_codes_and_rejects_expired_sign_ins() {
+		let database = test_support::test_database();
+		let (connection, user) = (&database.connection, &database.user);
+		two_factor::begin_two_factor_setup(connection, &user.id, &user.email).expect("begin setup");
+		let recovery_codes = two_factor::enable_two_factor(connection, &user.id).expect("enable");
+		let state = database.app_state();
+
+		start_pending_two_factor_login(&state, &user.id, two_factor::TWO_FACTOR_LOGIN_TTL);
+		let response = verify_two_factor_login(&state, recovery_codes[0].clone()).expect("recovery code");
+		assert_eq!(response.synced_games, 3);
+		assert!(!response.two_factor_required);
+		assert!(state.current_session_token.lock().expect("session lock").is_some());
+		assert!(state.pending_two_factor_login.lock().expect("pending login lock").is_none());
+
+		start_pending_two_factor_login(&state, &user.id, two_factor::TWO_FACTOR_LOGIN_TTL);
+		let error = verify_two_factor_login(&state, recovery_codes[0].clone()).expect_err("used recovery code");
+		assert_eq!(error.code, "invalid_two_factor_code");
+
+		start_pending_two_factor_login(&state, &user.id, Duration::ZERO);
+		let error = verify_two_factor_login(&state, recovery_codes[1].clone()).expect_err("expired sign-in");
+		assert_eq!(error.code, "two_factor_login_expired");
+	}
+
+	#[test]
+	fn cancel_steam_auth_only_reaches_the_running_sign_in() {
+		let database = test_support::test_database();
+		let state = database.app_state();
+		assert!(!cancel_steam_auth(&state).expect("nothing to cancel"));
+
+		let flow = SteamAuthFlow::start(&state).expect("start sign-in");
+		let error = SteamAuthFlow::start(&state).err().expect("second sign-in");
+		assert_eq!(error.code, "steam_auth_in_progress");
+		assert!(cancel_steam_auth(&state).expect("cancel sign-in"));
+		assert!(flow.cancel_requested().load(Ordering::SeqCst));
+		drop(flow);
+
+		// The cancelled sign-in released its slot, and a retry starts uncancelled.
+		assert!(!cancel_steam_auth(&state).expect("nothing left to cancel"));
+		let retry = SteamAuthFlow::start(&state).expect("retry sign-in");
+		assert!(!retry.cancel_requested().load(Ordering::SeqCst));
+	}
+}
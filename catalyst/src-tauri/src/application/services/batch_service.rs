@@ -0,0 +1,254 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+use crate::application::services::{
+	auth_service, collection_service, game_settings_service, library_service, steam_service,
+};
+use std::collections::BTreeMap;
+
+const BATCH_MAX_COMMANDS: usize = 32;
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchCommandRequest {
+	pub command: String,
+	#[serde(default)]
+	pub args: serde_json::Value,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BatchCommandResultResponse {
+	pub ok: bool,
+	pub data: Option<serde_json::Value>,
+	pub error: Option<AppError>,
+}
+
+pub(crate) fn run_batch(
+	state: &AppState,
+	commands: Vec<BatchCommandRequest>,
+) -> AppResult<BTreeMap<usize, BatchCommandResultResponse>> {
+	if commands.len() > BATCH_MAX_COMMANDS {
+		return Err(AppError::validation(
+			"batch_too_large",
+			format!("A batch may contain at most {BATCH_MAX_COMMANDS} commands"),
+		));
+	}
+
+	let mut results = BTreeMap::new();
+	for (index, request) in commands.into_iter().enumerate() {
+		let result = match dispatch_batch_command(state, request.command.trim(), &request.args) {
+			Ok(data) => BatchCommandResultResponse {
+				ok: true,
+				data: Some(data),
+				error: None,
+			},
+			Err(error) => BatchCommandResultResponse {
+				ok: false,
+				data: None,
+				error: Some(error),
+			},
+		};
+		results.insert(index, result);
+	}
+
+	Ok(results)
+}
+
+// Only read-only commands are whitelisted so a batch can never mutate state.
+fn dispatch_batch_command(
+	state: &AppState,
+	command: &str,
+	args: &serde_json::Value,
+) -> AppResult<serde_json::Value> {
+	match command {
+		"get_session" => to_batch_value(auth_service::get_session(state)?),
+		"list_sessions" => to_batch_value(auth_service::list_sessions(state)?),
+		"get_library" => to_batch_value(library_service::get_library(
+			state,
+			batch_optional_bool_arg(args, "merged")?,
+			batch_optional_library_filter_arg(args)?,
+			batch_optional_bool_arg(args, "includeHidden")?,
+		)?),
+		"list_hidden_games" => to_batch_value(library_service::list_hidden_games(state)?),
+		"list_steam_downloads" => to_batch_value(library_service::list_steam_downloads(state)?),
+		"list_collections" => to_batch_value(collection_service::list_collections(
+			state,
+			batch_optional_string_arg(args, "provider", "provider")?,
+			batch_optional_string_arg(args, "externalId", "external_id")?,
+			batch_optional_bool_arg(args, "includeHidden")?,
+		)?),
+		"get_steam_points_balance" => to_batch_value(steam_service::get_steam_points_balance(state)?),
+		"get_game_store_metadata" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(library_service::get_game_store_metadata(state, provider, external_id)?)
+		}
+		"get_game_privacy_settings" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(game_settings_service::get_game_privacy_settings(state, provider, external_id)?)
+		}
+		"get_game_properties_settings" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(game_settings_service::get_game_properties_settings(state, provider, external_id)?)
+		}
+		"get_game_customization_artwork" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(game_settings_service::get_game_customization_artwork(state, provider, external_id)?)
+		}
+		"get_game_installation_details" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(game_settings_service::get_game_installation_details(state, provider, external_id)?)
+		}
+		"get_pending_update_size" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(game_settings_service::get_pending_update_size(state, provider, external_id)?)
+		}
+		"list_game_languages" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(game_settings_service::list_game_languages(state, provider, external_id)?)
+		}
+		"list_game_install_locations" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(game_settings_service::list_game_install_locations(state, provider, external_id)?)
+		}
+		"list_game_versions_betas" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(steam_service::list_game_versions_betas(state, provider, external_id)?)
+		}
+		"list_game_points_shop_items" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(steam_service::list_game_points_shop_items(state, provider, external_id)?)
+		}
+		"get_game_card_status" => {
+			let (provider, external_id) = batch_game_identity_args(args)?;
+			to_batch_value(steam_service::get_game_card_status(state, provider, external_id)?)
+		}
+		_ => Err(AppError::validation(
+			"batch_command_not_allowed",
+			format!("Command '{command}' cannot be used in a batch"),
+		)),
+	}
+}
+
+fn to_batch_value<T: serde::Serialize>(value: T) -> AppResult<serde_json::Value> {
+	serde_json::to_value(value).map_err(|error| {
+		AppError::internal(
+			"batch_serialization_failed",
+			format!("Failed to encode batch command result: {error}"),
+		)
+	})
+}
+
+fn batch_optional_string_arg(
+	args: &serde_json::Value,
+	camel_case_key: &str,
+	snake_case_key: &str,
+) -> AppResult<Option<String>> {
+	let value = args.get(camel_case_key).or_else(|| args.get(snake_case_key));
+	match value {
+		None | Some(serde_json::Value::Null) => Ok(None),
+		Some(serde_json::Value::String(text)) => Ok(Some(text.clone())),
+		Some(_) => Err(AppError::validation(
+			"invalid_batch_argument",
+			format!("Batch argument '{camel_case_key}' must be a string"),
+		)),
+	}
+}
+
+fn batch_optional_bool_arg(args: &serde_json::Value, key: &str) -> AppResult<Option<bool>> {
+	match args.get(key) {
+		None | Some(serde_json::Value::Null) => Ok(None),
+		Some(serde_json::Value::Bool(value)) => Ok(Some(*value)),
+		Some(_) => Err(AppError::validation(
+			"invalid_batch_argument",
+			format!("Batch argument '{key}' must be a boolean"),
+		)),
+	}
+}
+
+fn batch_optional_library_filter_arg(
+	args: &serde_json::Value,
+) -> AppResult<Option<library_service::LibraryQueryFilter>> {
+	match args.get("filter") {
+		None | Some(serde_json::Value::Null) => Ok(None),
+		Some(value) => serde_json::from_value(value.clone()).map(Some).map_err(|error| {
+			AppError::validation(
+				"invalid_batch_argument",
+				format!("Batch argument 'filter' is invalid: {error}"),
+			)
+		}),
+	}
+}
+
+fn batch_game_identity_args(args: &serde_json::Value) -> AppResult<(String, String)> {
+	let provider = batch_optional_string_arg(args, "provider", "provider")?;
+	let external_id = batch_optional_string_arg(args, "externalId", "external_id")?;
+	match (provider, external_id) {
+		(Some(provider), Some(external_id)) => Ok((provider, external_id)),
+		_ => Err(AppError::validation(
+			"missing_identity_pair",
+			"provider and external_id must be supplied together",
+		)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn batch_request(command: &str, args: serde_json::Value) -> BatchCommandRequest {
+		BatchCommandRequest {
+			command: command.to_owned(),
+			args,
+		}
+	}
+
+	#[test]
+	fn run_batch_rejects_batches_over_the_size_cap() {
+		let database = test_support::test_database();
+		let state = database.signed_in_state();
+		let commands = (0..=BATCH_MAX_COMMANDS)
+			.map(|_| batch_request("get_session", serde_json::Value::Null))
+			.collect();
+
+		let error = run_batch(&state, commands).expect_err("oversized batch");
+		assert_eq!(error.code, "batch_too_large");
+		let commands = (0..BATCH_MAX_COMMANDS)
+			.map(|_| batch_request("get_session", serde_json::Value::Null))
+			.collect();
+		assert_eq!(run_batch(&state, commands).expect("full batch").len(), BATCH_MAX_COMMANDS);
+	}
+
+	#[test]
+	fn run_batch_only_dispatches_whitelisted_commands() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let state = database.signed_in_state();
+
+		let results = run_batch(
+			&state,
+			vec![
+				batch_request(" get_session ", serde_json::Value::Null),
+				batch_request("delete_account", serde_json::json!({ "password": "hash" })),
+				batch_request("play_game", serde_json::json!({ "provider": "steam", "externalId": "620" })),
+				batch_request("get_game_privacy_settings", serde_json::json!({ "provider": "steam" })),
+				batch_request("get_library", serde_json::json!({ "merged": "yes" })),
+			],
+		)
+		.expect("run batch");
+
+		assert!(results[&0].ok);
+		let error_codes = (1..5)
+			.map(|index| results[&index].error.as_ref().map(|error| error.code))
+			.collect::<Vec<_>>();
+		assert_eq!(
+			error_codes,
+			vec![
+				Some("batch_command_not_allowed"),
+				Some("batch_command_not_allowed"),
+				Some("missing_identity_pair"),
+				Some("invalid_batch_argument"),
+			]
+		);
+		assert!(find_user_by_id(&database.connection, &database.user.id).expect("query user").is_some());
+	}
+}
@@ -1,14 +1,15 @@
 use crate::*;
 use crate::application::error::{AppError, AppResult};
+use crate::application::context::{AuthedContext, AuthedGameContext};
+use crate::application::services::library_service::LibraryGameIdentityInput;
 
 pub(crate) fn list_collections(
 	state: &AppState,
 	provider: Option<String>,
 	external_id: Option<String>,
+	include_hidden: Option<bool>,
 ) -> AppResult<Vec<CollectionResponse>> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
 
 	let target = match (provider.as_deref(), external_id.as_deref()) {
 		(None, None) => None,
@@ -31,15 +32,17 @@ pub(crate) fn list_collections(
 		}
 	};
 
+	let include_hidden = include_hidden.unwrap_or(false);
 	let list = if let Some((target_provider, target_external_id)) = target {
 		list_collections_by_user(
 			&connection,
 			&user.id,
 			Some(target_provider.as_str()),
 			Some(target_external_id.as_str()),
+			include_hidden,
 		)?
 	} else {
-		list_collections_by_user(&connection, &user.id, None, None)?
+		list_collections_by_user(&connection, &user.id, None, None, include_hidden)?
 	};
 
 	Ok(list)
@@ -49,9 +52,7 @@ pub(crate) fn create_collection(
 	state: &AppState,
 	name: String,
 ) -> AppResult<CollectionResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
 	Ok(create_user_collection(&connection, &user.id, &name)?)
 }
 
@@ -68,10 +69,60 @@ pub(crate) fn rename_collection(
 		));
 	}
 
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	Ok(rename_user_collection(&connection, &user.id, trimmed_collection_id, &name)?)
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let collection = rename_user_collection(&connection, &user.id, trimmed_collection_id, &name)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(collection)
+}
+
+pub(crate) fn set_collection_cover_image(
+	state: &AppState,
+	collection_id: String,
+	path: Option<String>,
+) -> AppResult<CollectionResponse> {
+	let trimmed_collection_id = collection_id.trim();
+	if trimmed_collection_id.is_empty() {
+		return Err(AppError::validation(
+			"collection_id_required",
+			"Collection ID is required",
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let source_path = path
+		.as_deref()
+		.map(str::trim)
+		.filter(|path| !path.is_empty())
+		.map(Path::new);
+	Ok(set_user_collection_cover_image(
+		&connection,
+		&state.db_path,
+		&user.id,
+		trimmed_collection_id,
+		source_path,
+	)?)
+}
+
+pub(crate) fn set_collection_accent_color(
+	state: &AppState,
+	collection_id: String,
+	accent_color: Option<String>,
+) -> AppResult<CollectionResponse> {
+	let trimmed_collection_id = collection_id.trim();
+	if trimmed_collection_id.is_empty() {
+		return Err(AppError::validation(
+			"collection_id_required",
+			"Collection ID is required",
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(set_user_collection_accent_color(
+		&connection,
+		&user.id,
+		trimmed_collection_id,
+		accent_color.as_deref(),
+	)?)
 }
 
 pub(crate) fn delete_collection(
@@ -86,10 +137,10 @@ pub(crate) fn delete_collection(
 		));
 	}
 
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	Ok(delete_user_collection(&connection, &user.id, trimmed_collection_id)?)
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	delete_user_collection(&connection, &user.id, trimmed_collection_id)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(())
 }
 
 pub(crate) fn add_game_to_collection(
@@ -106,11 +157,8 @@ pub(crate) fn add_game_to_collection(
 		));
 	}
 
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
 	ensure_owned_collection_exists(&connection, &user.id, trimmed_collection_id)?;
 	add_game_to_collection_membership(
 		&connection,
@@ -119,6 +167,34 @@ pub(crate) fn add_game_to_collection(
 		&provider,
 		&external_id,
 	)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(())
+}
+
+/// Saves a hand-picked order for the collection; members left out of `games` are listed after
+/// the ordered ones, by name.
+pub(crate) fn set_collection_game_order(
+	state: &AppState,
+	collection_id: String,
+	games: Vec<LibraryGameIdentityInput>,
+) -> AppResult<()> {
+	let trimmed_collection_id = collection_id.trim();
+	if trimmed_collection_id.is_empty() {
+		return Err(AppError::validation(
+			"collection_id_required",
+			"Collection ID is required",
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	ensure_owned_collection_exists(&connection, &user.id, trimmed_collection_id)?;
+	let games = games
+		.iter()
+		.map(|game| normalize_game_identity_input(&game.provider, &game.external_id))
+		.collect::<Result<Vec<_>, _>>()?;
+	set_collection_game_positions(&connection, &user.id, trimmed_collection_id, &games)
+		.map_err(|error| AppError::validation("invalid_collection_order", error))?;
+	state.library_lookup_cache.invalidate_user(&user.id);
 	Ok(())
 }
 
@@ -162,5 +238,43 @@ mod tests {
 		drop(conn);
 		fs::remove_file(db_path).ok();
 	}
-}
 
+	#[test]
+	fn set_collection_game_order_checks_the_collection_and_reaches_the_library() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		let state = database.signed_in_state();
+		let collection = create_collection(&state, String::from("Play next")).expect("create collection");
+		for external_id in ["620", "400"] {
+			add_game_to_collection(&state, "steam".into(), external_id.into(), collection.id.clone())
+				.expect("add game");
+		}
+		let game = |external_id: &str| LibraryGameIdentityInput {
+			provider: String::from(" Steam "),
+			external_id: external_id.to_owned(),
+		};
+
+		let error = set_collection_game_order(&state, String::from(" "), vec![game("620")])
+			.err()
+			.expect("blank collection");
+		assert_eq!(error.code, "collection_id_required");
+		assert!(set_collection_game_order(&state, String::from("missing"), vec![game("620")]).is_err());
+		let error = set_collection_game_order(&state, collection.id.clone(), vec![game("620"), game("620")])
+			.err()
+			.expect("duplicate game");
+		assert_eq!(error.code, "invalid_collection_order");
+
+		set_collection_game_order(&state, collection.id.clone(), vec![game("400"), game("620")]).expect("set order");
+		let games = crate::application::services::library_service::get_library(&state, None, None, None)
+			.expect("library")
+			.games;
+		let position = |external_id: &str| {
+			games
+				.iter()
+				.find(|game| game.external_id == external_id)
+				.and_then(|game| game.collection_positions.get("Play next").copied())
+		};
+		assert_eq!((position("400"), position("620")), (Some(0), Some(1)));
+	}
+}
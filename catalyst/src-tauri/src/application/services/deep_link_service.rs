@@ -0,0 +1,110 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+use crate::application::context::AuthedContext;
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeepLinkActionResponse {
+	pub action: String,
+	pub provider: String,
+	pub external_id: String,
+}
+
+/// Dispatches a `catalyst://<action>/<provider>/<external_id>` URL. `game` resolves the game for
+/// the detail page. `play` never launches on its own: any page can open a link, so it resolves
+/// to `confirm_play` and the launch waits for the user to confirm it in the window. Both only act
+/// on games the signed-in user owns.
+pub(crate) fn handle_deep_link(state: &AppState, url: String) -> AppResult<DeepLinkActionResponse> {
+	let parsed_url = Url::parse(url.trim())
+		.map_err(|_| AppError::validation("invalid_deep_link", "Deep link URL is invalid"))?;
+	if parsed_url.scheme() != DEEP_LINK_SCHEME {
+		return Err(AppError::validation(
+			"unsupported_deep_link_scheme",
+			format!("Deep links must use the {DEEP_LINK_SCHEME}:// scheme"),
+		));
+	}
+
+	let action = parsed_url.host_str().unwrap_or_default().to_ascii_lowercase();
+	let segments = parsed_url
+		.path_segments()
+		.map(|segments| {
+			segments
+				.filter(|segment| !segment.is_empty())
+				.map(|segment| percent_encoding::percent_decode_str(segment).decode_utf8())
+				.collect::<Result<Vec<_>, _>>()
+		})
+		.transpose()
+		.map_err(|_| AppError::validation("invalid_deep_link", "Deep link URL is invalid"))?
+		.unwrap_or_default();
+	let [provider, external_id] = segments.as_slice() else {
+		return Err(AppError::validation(
+			"invalid_deep_link_target",
+			format!("Deep links must look like {DEEP_LINK_SCHEME}://<action>/<provider>/<external_id>"),
+		));
+	};
+	let (provider, external_id) = normalize_game_identity_input(provider, external_id)?;
+
+	let action = match action.as_str() {
+		"play" => "confirm_play",
+		"game" => "open_game",
+		_ => {
+			return Err(AppError::validation(
+				"unsupported_deep_link_action",
+				format!("Unsupported deep link action: {action}"),
+			))
+		}
+	};
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+
+	Ok(DeepLinkActionResponse {
+		action: action.to_owned(),
+		provider,
+		external_id,
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn handle_deep_link_rejects_malformed_and_unknown_links() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let state = database.signed_in_state();
+
+		for (url, code) in [
+			("not a url", "invalid_deep_link"),
+			("https://store.steampowered.com/app/620", "unsupported_deep_link_scheme"),
+			("catalyst://play/steam", "invalid_deep_link_target"),
+			("catalyst://play/steam/620/extra", "invalid_deep_link_target"),
+			("catalyst://uninstall/steam/620", "unsupported_deep_link_action"),
+			("catalyst://game/steam/%FF", "invalid_deep_link"),
+		] {
+			let error = handle_deep_link(&state, url.to_owned()).expect_err(url);
+			assert_eq!(error.code, code, "{url}");
+		}
+		assert!(handle_deep_link(&state, String::from("catalyst://game/steam/730")).is_err());
+	}
+
+	#[test]
+	fn handle_deep_link_decodes_segments_and_defers_play_to_a_confirmation() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("gog", "Portal 2", "Portal 2");
+		let state = database.signed_in_state();
+
+		let response = handle_deep_link(&state, String::from(" catalyst://GAME/Steam/6%32%30 ")).expect("game link");
+		assert_eq!(
+			(response.action.as_str(), response.provider.as_str(), response.external_id.as_str()),
+			("open_game", "steam", "620")
+		);
+		// The game isn't installed, so launching it here would have failed the link.
+		let response = handle_deep_link(&state, String::from("catalyst://play/gog/Portal%202")).expect("play link");
+		assert_eq!(
+			(response.action.as_str(), response.provider.as_str(), response.external_id.as_str()),
+			("confirm_play", "gog", "Portal 2")
+		);
+	}
+}
@@ -0,0 +1,74 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+use crate::application::context::AuthedContext;
+use crate::developer_releases::DeveloperReleasesResponse;
+use crate::discovery_feed::{
+	DiscoveryFeedResponse, DiscoveryFollowResponse, DiscoveryRefreshResponse, DiscoverySourceKind,
+};
+
+pub(crate) fn get_discovery_feed(state: &AppState, limit: Option<usize>) -> AppResult<DiscoveryFeedResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(discovery_feed::load_discovery_feed(&connection, &user.id, limit)?)
+}
+
+/// Refetches followed curators and franchises, wishlist discounts and new releases, and checks
+/// owned developers for new games, now instead of waiting for the background refresh.
+pub(crate) fn refresh_discovery_feed(state: &AppState) -> AppResult<DiscoveryRefreshResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let client = state.http_client()?;
+	let mut response = discovery_feed::refresh_discovery_feed(
+		&connection,
+		&client,
+		&user.id,
+		user.steam_id.as_deref(),
+	)?;
+	let scan = developer_releases::refresh_developer_releases(&connection, &client, &user.id)?;
+	response.refreshed_sources += scan.scanned;
+	response.failed_sources += scan.failed;
+	response.new_developer_releases = scan.new_releases;
+	Ok(response)
+}
+
+/// New games from the developers and publishers the user owns several games of, as found by
+/// the discovery refresh.
+pub(crate) fn get_developer_releases(
+	state: &AppState,
+	limit: Option<usize>,
+) -> AppResult<DeveloperReleasesResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(developer_releases::load_developer_releases(&connection, &user.id, limit)?)
+}
+
+/// Follows a Steam curator or franchise by ID, slug or store URL. Its picks show up in the feed
+/// after the next refresh.
+pub(crate) fn follow_discovery_source(
+	state: &AppState,
+	kind: DiscoverySourceKind,
+	source: String,
+	name: Option<String>,
+) -> AppResult<DiscoveryFollowResponse> {
+	let source_id = discovery_feed::parse_discovery_source_id(kind, &source)
+		.map_err(|error| AppError::validation("invalid_discovery_source", error))?;
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(discovery_feed::follow_discovery_source(
+		&connection,
+		&user.id,
+		kind,
+		&source_id,
+		name.as_deref(),
+	)?)
+}
+
+pub(crate) fn unfollow_discovery_source(
+	state: &AppState,
+	kind: DiscoverySourceKind,
+	source_id: String,
+) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(discovery_feed::unfollow_discovery_source(
+		&connection,
+		&user.id,
+		kind,
+		source_id.trim(),
+	)?)
+}
@@ -1,5 +1,9 @@
 use crate::*;
 use crate::application::error::{AppError, AppResult};
+use crate::application::context::{AuthedContext, AuthedGameContext, require_authenticated_user};
+use crate::game_shortcuts::GameShortcutResponse;
+use crate::game_verification::GameVerificationStatusResponse;
+use crate::install_jobs::InstallJobResponse;
 use rusqlite::params;
 
 pub(crate) fn play_game(
@@ -8,34 +12,274 @@ pub(crate) fn play_game(
 	external_id: String,
 	launch_options: Option<String>,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+	launch_game(state, provider, external_id, launch_options).map(|_| ())
+}
+
+/// Launches a game like `play_game` and hands back the thread that watches the session, if one
+/// was started, so a caller that exits right away can wait for post-exit hooks and presence
+/// cleanup first.
+pub(crate) fn launch_game(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	launch_options: Option<String>,
+) -> AppResult<Option<thread::JoinHandle<()>>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	if private_games::is_locked_private_game(state, &connection, &user.id, &provider, &external_id)? {
+		return Err(AppError::unauthorized(
+			"private_games_locked",
+			"Unlock private games with your PIN to launch this game.",
+		));
+	}
+	let game_settings = load_game_properties_settings(&connection, &user.id, &provider, &external_id).ok();
 	let resolved_launch_options = match launch_options
 		.as_deref()
 		.map(str::trim)
 		.filter(|value| !value.is_empty())
 	{
 		Some(value) => Some(value.to_owned()),
-		None => load_game_properties_settings(&connection, &user.id, &provider, &external_id)
-			.ok()
-			.and_then(|settings| {
-				let trimmed_value = settings.general.launch_options.trim();
-				if trimmed_value.is_empty() {
-					None
-				} else {
-					Some(trimmed_value.to_owned())
-				}
-			}),
+		None => game_settings.as_ref().and_then(|settings| {
+			let trimmed_value = settings.general.launch_options.trim();
+			if trimmed_value.is_empty() {
+				None
+			} else {
+				Some(trimmed_value.to_owned())
+			}
+		}),
 	};
-	Ok(open_provider_game_uri(
+	let game_id = format!("{provider}:{external_id}");
+	if state.running_games.is_running(&game_id) {
+		return Err(AppError::conflict("game_already_running", "This game is already running."));
+	}
+	let game_name = find_game_name(&connection, &user.id, &provider, &external_id)?
+		.unwrap_or_else(|| external_id.clone());
+	// Hooks only run once the user has acknowledged that they execute arbitrary commands.
+	let hook_settings = game_settings
+		.as_ref()
+		.map(|settings| &settings.general)
+		.filter(|general| general.launch_hooks_confirmed);
+	let pre_launch_hook = hook_settings.and_then(|general| {
+		LaunchHook::new(&general.pre_launch_command, &provider, &external_id, &game_name)
+	});
+	let post_exit_hook = hook_settings.and_then(|general| {
+		LaunchHook::new(&general.post_exit_command, &provider, &external_id, &game_name)
+	});
+	if let Some(hook) = pre_launch_hook.as_ref() {
+		hook.run()
+			.map_err(|error| AppError::conflict("pre_launch_hook_failed", error))?;
+	}
+
+	let environment_variables = game_settings
+		.as_ref()
+		.map(|settings| settings.general.environment_variables.clone())
+		.unwrap_or_default();
+
+	if provider == "rom" {
+		let mut emulator = rom_library::launch_rom_game(
+			&connection,
+			&user.id,
+			&external_id,
+			resolved_launch_options.as_deref(),
+			&environment_variables,
+		)?;
+		state.running_games.track_process(&game_id, emulator.id());
+		// Always reap the emulator so the process watcher sees it exit.
+		let session = thread::Builder::new()
+			.name(format!("rom-session-{external_id}"))
+			.spawn(move || {
+				let _ = emulator.wait();
+				run_post_exit_hook(post_exit_hook.as_ref());
+			})
+			.ok();
+		return Ok(session);
+	}
+	if provider == library_import::CUSTOM_PROVIDER {
+		let game_process = library_import::launch_custom_game(
+			&connection,
+			&user.id,
+			&external_id,
+			resolved_launch_options.as_deref(),
+			&environment_variables,
+		)?;
+		let session = game_process.and_then(|mut game_process| {
+			state.running_games.track_process(&game_id, game_process.id());
+			thread::Builder::new()
+				.name(format!("custom-session-{external_id}"))
+				.spawn(move || {
+					let _ = game_process.wait();
+					run_post_exit_hook(post_exit_hook.as_ref());
+				})
+				.ok()
+		});
+		return Ok(session);
+	}
+	open_provider_game_uri(
 		&provider,
 		&external_id,
 		"play",
 		resolved_launch_options.as_deref(),
-	)?)
+	)?;
+
+	let discord_client_id = state.discord_presence_client_id().filter(|_| {
+		game_settings
+			.as_ref()
+			.map(|settings| settings.general.discord_presence_enabled)
+			.unwrap_or(true)
+	});
+	// Exit detection relies on Steam's running-app registry, so other launchers skip post-exit hooks.
+	if let Ok(app_id) = external_id.parse::<u64>() {
+		if provider == "steam" && (discord_client_id.is_some() || post_exit_hook.is_some()) {
+			return Ok(spawn_steam_game_session_tracker(
+				app_id,
+				game_name,
+				discord_client_id,
+				post_exit_hook,
+			));
+		}
+	}
+
+	Ok(None)
+}
+
+pub(crate) fn list_remote_play_hosts(state: &AppState) -> AppResult<Vec<remote_play::RemotePlayHostResponse>> {
+	require_authenticated_user(state)?;
+	Ok(remote_play::read_remote_play_hosts(state.steam_root_override().as_deref())?)
+}
+
+/// Streams a Steam game from another machine with Remote Play. Steam picks the host itself;
+/// `host_id` only checks that the host the user chose is still known to Steam.
+pub(crate) fn play_game_remote(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	host_id: Option<String>,
+) -> AppResult<()> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Remote Play is only supported for Steam games.",
+		));
+	}
+	if private_games::is_locked_private_game(state, &connection, &user.id, &provider, &external_id)? {
+		return Err(AppError::unauthorized(
+			"private_games_locked",
+			"Unlock private games with your PIN to launch this game.",
+		));
+	}
+	let app_id = external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
+	let steam_root_override = state.steam_root_override();
+	if resolve_steam_manifest_path_for_app_id(steam_root_override.as_deref(), app_id).is_ok() {
+		return Err(AppError::conflict(
+			"installed_locally",
+			"Steam runs installed games on this machine; use Play instead of streaming.",
+		));
+	}
+
+	let hosts = remote_play::read_remote_play_hosts(steam_root_override.as_deref())?;
+	let host_known = match host_id.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+		Some(host_id) => hosts.iter().any(|host| host.client_id == host_id),
+		None => !hosts.is_empty(),
+	};
+	if !host_known {
+		return Err(AppError::not_found(
+			"remote_play_host_not_found",
+			"No Remote Play host found. Sign in to Steam on your gaming PC on the same network.",
+		));
+	}
+
+	Ok(remote_play::launch_steam_remote_play(app_id)?)
+}
+
+fn load_configured_gaming_pc(state: &AppState) -> AppResult<gaming_pc::GamingPcSettings> {
+	let AuthedContext { connection, .. } = AuthedContext::new(state)?;
+	gaming_pc::load_gaming_pc_settings(&connection)?
+		.ok_or_else(|| AppError::not_found("gaming_pc_not_configured", "Set up your gaming PC in settings first."))
+}
+
+/// Sends a Wake-on-LAN magic packet to the configured gaming PC.
+pub(crate) fn wake_gaming_pc(state: &AppState) -> AppResult<()> {
+	let settings = load_configured_gaming_pc(state)?;
+	gaming_pc::send_wake_on_lan(&settings).map_err(|error| AppError::external("wake_on_lan_failed", error))
+}
+
+/// Starts installing a game on the gaming PC through its Catalyst API server, so the download
+/// can run before the user gets home. A PC woken with `wake_gaming_pc` needs to finish booting
+/// and start Catalyst before it accepts the request.
+pub(crate) fn start_gaming_pc_download(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<()> {
+	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
+	let settings = load_configured_gaming_pc(state)?;
+	let token = gaming_pc::read_gaming_pc_api_token()?.ok_or_else(|| {
+		AppError::validation(
+			"gaming_pc_token_missing",
+			"Enter the API token shown in the gaming PC's API server settings.",
+		)
+	})?;
+	let client = state.http_client()?;
+	gaming_pc::request_gaming_pc_install(&client, &settings, &token, &provider, &external_id)
+		.map_err(|error| AppError::external("gaming_pc_request_failed", error))
+}
+
+/// Stops a running game. Steam has no per-app exit URI (`steam://exit` quits the client), so
+/// processes are located from the install folder, or from the PID Catalyst recorded when it
+/// spawned the game itself. Without `force` the game is asked to close; `force` kills it and
+/// should only be sent after the user confirms, since unsaved progress is lost.
+pub(crate) fn stop_game(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	force: bool,
+) -> AppResult<()> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let game_id = format!("{provider}:{external_id}");
+	if !state.running_games.is_running(&game_id) {
+		return Err(AppError::conflict("game_not_running", "This game is not running."));
+	}
+
+	let process_ids = if let Some(pid) = state.running_games.tracked_process(&game_id) {
+		vec![pid]
+	} else {
+		let install_directory = match provider.as_str() {
+			"steam" => {
+				let app_id = external_id.parse::<u64>().map_err(|_| {
+					AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID")
+				})?;
+				Some(resolve_steam_install_directory_for_app_id(
+					state.steam_root_override().as_deref(),
+					app_id,
+				)?)
+			}
+			"ea" => store_providers::detect_ea_installed_games()?
+				.into_iter()
+				.find(|game| game.external_id == external_id)
+				.and_then(|game| game.install_path),
+			"ubisoft" => store_providers::detect_ubisoft_installed_games()?
+				.into_iter()
+				.find(|game| game.external_id == external_id)
+				.and_then(|game| game.install_path),
+			_ => None,
+		};
+		install_directory
+			.map(|directory| process_watcher::find_install_directory_process_ids(&directory))
+			.unwrap_or_default()
+	};
+	if process_ids.is_empty() {
+		return Err(AppError::not_found(
+			"game_process_not_found",
+			"Could not find the game's process to stop.",
+		));
+	}
+
+	Ok(process_watcher::terminate_processes(&process_ids, force)?)
 }
 
 pub(crate) fn install_game(
@@ -43,35 +287,418 @@ pub(crate) fn install_game(
 	provider: String,
 	external_id: String,
 	install_path: Option<String>,
+	source_path: Option<String>,
 	create_desktop_shortcut: Option<bool>,
 	create_application_shortcut: Option<bool>,
+) -> AppResult<Option<InstallJobResponse>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+
+	if provider == library_import::CUSTOM_PROVIDER {
+		let source_path = source_path
+			.as_deref()
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+			.ok_or_else(|| {
+				AppError::validation("missing_source_path", "Choose an installer or game folder to install from.")
+			})?;
+		let install_path = install_path
+			.as_deref()
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+			.ok_or_else(|| AppError::validation("missing_install_path", "Choose a folder to install the game into."))?;
+		let job = install_jobs::enqueue_install_job(
+			&connection,
+			&user.id,
+			&external_id,
+			source_path,
+			install_path,
+			create_desktop_shortcut.unwrap_or(false),
+			create_application_shortcut.unwrap_or(false),
+		)
+		.map_err(|error| AppError::validation("invalid_install_job", error))?;
+		return Ok(Some(job));
+	}
+
+	// Launchers such as Steam choose the install destination and shortcuts in their own flow.
+	open_provider_game_uri(&provider, &external_id, "install", None)?;
+	Ok(None)
+}
+
+pub(crate) fn list_install_jobs(state: &AppState) -> AppResult<Vec<InstallJobResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(install_jobs::list_install_jobs(&connection, &user.id)?)
+}
+
+pub(crate) fn uninstall_game(
+	state: &AppState,
+	provider: String,
+	external_id: String,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
-	// Steam currently controls install destination and shortcut behavior from its own flow.
-	// Keep receiving these values so the UI can evolve without breaking command contracts.
-	let _ = (
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	Ok(open_provider_game_uri(&provider, &external_id, "uninstall", None)?)
+}
+
+fn parse_steam_storage_app_id(provider: &str, external_id: &str) -> AppResult<u64> {
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Storage details are only available for Steam games.",
+		));
+	}
+	external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))
+}
+
+pub(crate) fn get_game_storage_breakdown(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<GameStorageBreakdownResponse> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_storage_app_id(&provider, &external_id)?;
+	let storage_paths = resolve_steam_app_storage_paths(state.steam_root_override().as_deref(), app_id)?;
+
+	let measure = |path: &Path| {
+		let size_bytes = measure_directory_size_bytes(path);
+		let display_path = size_bytes.map(|_| path.display().to_string());
+		(size_bytes, display_path)
+	};
+	let (install_bytes, install_path) = measure(&storage_paths.install_directory);
+	let (shader_cache_bytes, shader_cache_path) = measure(&storage_paths.shader_cache_directory);
+	let (compatdata_bytes, compatdata_path) = measure(&storage_paths.compatdata_directory);
+	let (workshop_bytes, workshop_path) = measure(&storage_paths.workshop_directory);
+
+	Ok(GameStorageBreakdownResponse {
+		install_bytes,
+		shader_cache_bytes,
+		compatdata_bytes,
+		workshop_bytes,
+		total_bytes: [install_bytes, shader_cache_bytes, compatdata_bytes, workshop_bytes]
+			.into_iter()
+			.flatten()
+			.sum(),
 		install_path,
-		create_desktop_shortcut,
-		create_application_shortcut,
+		shader_cache_path,
+		compatdata_path,
+		workshop_path,
+	})
+}
+
+/// Deletes one of the regenerable per-app Steam folders. Callers must pass `confirm` because
+/// the compatdata folder also holds Proton save games that are not synced to Steam Cloud.
+fn delete_steam_app_storage_directory(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	confirm: bool,
+	select_directory: fn(SteamAppStoragePaths) -> PathBuf,
+) -> AppResult<()> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_storage_app_id(&provider, &external_id)?;
+	if !confirm {
+		return Err(AppError::validation(
+			"confirmation_required",
+			"Confirm the cleanup before deleting game data.",
+		));
+	}
+	if detect_running_steam_app_id() == Some(app_id) {
+		return Err(AppError::conflict(
+			"game_running",
+			"Close the game before deleting its data.",
+		));
+	}
+
+	let directory = select_directory(resolve_steam_app_storage_paths(
+		state.steam_root_override().as_deref(),
+		app_id,
+	)?);
+	if !directory.is_dir() {
+		return Ok(());
+	}
+	fs::remove_dir_all(&directory).map_err(|error| {
+		AppError::internal(
+			"delete_failed",
+			format!("Failed to delete {}: {error}", directory.display()),
+		)
+	})
+}
+
+pub(crate) fn clear_game_shader_cache(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	confirm: bool,
+) -> AppResult<()> {
+	delete_steam_app_storage_directory(state, provider, external_id, confirm, |paths| {
+		paths.shader_cache_directory
+	})
+}
+
+pub(crate) fn delete_game_compatdata(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	confirm: bool,
+) -> AppResult<()> {
+	delete_steam_app_storage_directory(state, provider, external_id, confirm, |paths| {
+		paths.compatdata_directory
+	})
+}
+
+fn resolve_owned_proton_prefix(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<(u64, PathBuf)> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_storage_app_id(&provider, &external_id)?;
+	let prefix_directory =
+		proton_prefix::resolve_proton_prefix_directory(state.steam_root_override().as_deref(), app_id)
+			.map_err(|error| AppError::not_found("proton_prefix_missing", error))?;
+	Ok((app_id, prefix_directory))
+}
+
+pub(crate) fn open_game_proton_prefix(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<()> {
+	let (_, prefix_directory) = resolve_owned_proton_prefix(state, provider, external_id)?;
+	Ok(open_path_in_file_manager(&prefix_directory)?)
+}
+
+/// Runs winetricks verbs against the game's Proton prefix and waits for them to finish, which
+/// can take minutes for runtimes such as `dotnet48`.
+pub(crate) fn run_game_prefix_tool(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	verbs: Vec<String>,
+) -> AppResult<proton_prefix::ProtonPrefixToolResponse> {
+	let (app_id, prefix_directory) = resolve_owned_proton_prefix(state, provider, external_id)?;
+	if detect_running_steam_app_id() == Some(app_id) {
+		return Err(AppError::conflict(
+			"game_running",
+			"Close the game before changing its Proton prefix.",
+		));
+	}
+	proton_prefix::run_proton_prefix_tool(app_id, &prefix_directory, &verbs)
+		.map_err(|error| AppError::validation("prefix_tool_failed", error))
+}
+
+/// Moves an installed Steam game to another library folder. Steam only notices the new location
+/// after it rescans its libraries, so the game must not be running while its files move.
+pub(crate) fn move_game_install<F>(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	target_library_path: String,
+	on_progress: F,
+) -> AppResult<GameMoveResponse>
+where
+	F: Fn(GameMoveProgressPayload),
+{
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Moving installs is only supported for Steam games.",
+		));
+	}
+	let app_id = external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
+	let target_library_path = target_library_path.trim();
+	if target_library_path.is_empty() {
+		return Err(AppError::validation("invalid_target_library", "Target library is required."));
+	}
+	if detect_running_steam_app_id() == Some(app_id) {
+		return Err(AppError::conflict("game_running", "Close the game before moving it."));
+	}
+
+	let plan = resolve_steam_app_move_plan(
+		state.steam_root_override().as_deref(),
+		app_id,
+		Path::new(target_library_path),
+	)?;
+	// Steam keeps writing into the install folder while it updates or downloads the app.
+	let manifest_contents = fs::read_to_string(&plan.source_manifest_path)
+		.map_err(|error| format!("Failed to read Steam app manifest: {error}"))?;
+	if parse_steam_manifest_u64_field(&manifest_contents, "StateFlags")
+		.is_some_and(|state_flags| state_flags != STEAM_APP_STATE_FULLY_INSTALLED)
+	{
+		return Err(AppError::conflict(
+			"game_updating",
+			"Wait for Steam to finish updating or downloading the game before moving it.",
+		));
+	}
+	let total_bytes = plan.total_bytes();
+	if let Some(free_space_bytes) = detect_available_disk_space_bytes(&plan.target_library_path) {
+		if free_space_bytes < total_bytes {
+			return Err(AppError::validation(
+				"insufficient_disk_space",
+				format!(
+					"The target library needs {total_bytes} bytes free but only {free_space_bytes} are available."
+				),
+			));
+		}
+	}
+
+	let emit_progress = |phase: &'static str, copied_bytes: u64| {
+		on_progress(GameMoveProgressPayload {
+			provider: provider.clone(),
+			external_id: external_id.clone(),
+			phase,
+			copied_bytes,
+			total_bytes,
+		});
+	};
+	emit_progress("copying", 0);
+	execute_steam_app_move(&plan, |copied_bytes| emit_progress("copying", copied_bytes))?;
+	emit_progress("complete", total_bytes);
+
+	Ok(GameMoveResponse {
+		library_path: plan.target_library_path.display().to_string(),
+		install_path: plan.target_install_directory.display().to_string(),
+		moved_bytes: total_bytes,
+	})
+}
+
+/// Installs a Steam game with steamcmd into a chosen library folder instead of going through
+/// the Steam client, which gives no say over the destination. The `steamcmd` settings must
+/// point at a steamcmd install unless one is on `PATH`.
+pub(crate) fn install_game_with_steamcmd<F>(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	options: steamcmd::SteamCmdInstallOptions,
+	on_progress: F,
+) -> AppResult<steamcmd::SteamCmdInstallResponse>
+where
+	F: Fn(steamcmd::SteamCmdInstallProgressPayload),
+{
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"steamcmd installs are only supported for Steam games.",
+		));
+	}
+	let app_id = external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
+	if options.library_path.trim().is_empty() {
+		return Err(AppError::validation("invalid_target_library", "Target library is required."));
+	}
+	if state.is_offline() {
+		return Err(AppError::conflict("offline", "steamcmd installs need a network connection."));
+	}
+	let game_name = find_game_name(&connection, &user.id, &provider, &external_id)?.unwrap_or_default();
+	let settings = steamcmd::load_steamcmd_settings(&connection)?;
+	drop(connection);
+
+	if state.steamcmd_install_running.swap(true, Ordering::SeqCst) {
+		return Err(AppError::conflict(
+			"steamcmd_busy",
+			"Another steamcmd install is already running.",
+		));
+	}
+	let result = steamcmd::install_steam_app_with_steamcmd(
+		state.steam_root_override().as_deref(),
+		&settings,
+		app_id,
+		&game_name,
+		&options,
+		&on_progress,
 	);
-	Ok(open_provider_game_uri(&provider, &external_id, "install", None)?)
+	state.steamcmd_install_running.store(false, Ordering::SeqCst);
+
+	result.map_err(|error| {
+		if error == steamcmd::STEAMCMD_STEAM_GUARD_REQUIRED_MESSAGE {
+			AppError::unauthorized("steam_guard_required", error)
+		} else {
+			AppError::external("steamcmd_failed", error)
+		}
+	})
 }
 
-pub(crate) fn uninstall_game(
+pub(crate) fn list_game_workshop_items(
 	state: &AppState,
 	provider: String,
 	external_id: String,
+) -> AppResult<Vec<workshop::SteamWorkshopItemResponse>> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_workshop_app_id(&provider, &external_id)?;
+
+	let mut items = workshop::read_steam_workshop_items(state.steam_root_override().as_deref(), app_id)?;
+	if items.is_empty() || state.is_offline() {
+		return Ok(items);
+	}
+
+	// Titles are cosmetic; the local manifest is still useful when the lookup fails.
+	let published_file_ids = items
+		.iter()
+		.map(|item| item.published_file_id.clone())
+		.collect::<Vec<_>>();
+	match state
+		.http_client()
+		.and_then(|client| workshop::fetch_steam_workshop_titles(&client, &published_file_ids))
+	{
+		Ok(titles) => {
+			for item in &mut items {
+				item.title = titles.get(&item.published_file_id).cloned();
+			}
+		}
+		Err(error) => eprintln!("Could not fetch Steam workshop titles for app {app_id}: {error}"),
+	}
+
+	Ok(items)
+}
+
+pub(crate) fn open_workshop_page(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	published_file_id: Option<String>,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
-	Ok(open_provider_game_uri(&provider, &external_id, "uninstall", None)?)
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_workshop_app_id(&provider, &external_id)?;
+
+	let published_file_id = published_file_id
+		.map(|value| value.trim().to_owned())
+		.filter(|value| !value.is_empty());
+	if let Some(published_file_id) = published_file_id.as_deref() {
+		if !published_file_id.chars().all(|character| character.is_ascii_digit()) {
+			return Err(AppError::validation(
+				"invalid_published_file_id",
+				"Workshop item ID must be numeric.",
+			));
+		}
+	}
+
+	Ok(workshop::open_steam_workshop_page(app_id, published_file_id.as_deref())?)
+}
+
+fn parse_steam_workshop_app_id(provider: &str, external_id: &str) -> AppResult<u64> {
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Steam Workshop is only available for Steam games.",
+		));
+	}
+	external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))
 }
 
 pub(crate) fn browse_game_installed_files(
@@ -79,11 +706,8 @@ pub(crate) fn browse_game_installed_files(
 	provider: String,
 	external_id: String,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if provider != "steam" {
 		return Err(AppError::validation(
@@ -96,7 +720,7 @@ pub(crate) fn browse_game_installed_files(
 		.parse::<u64>()
 		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
 	let install_directory =
-		resolve_steam_install_directory_for_app_id(state.steam_root_override.as_deref(), app_id)?;
+		resolve_steam_install_directory_for_app_id(state.steam_root_override().as_deref(), app_id)?;
 	if !install_directory.is_dir() {
 		return Err(AppError::not_found(
 			"install_directory_missing",
@@ -112,25 +736,255 @@ pub(crate) fn backup_game_files(
 	provider: String,
 	external_id: String,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
 	Ok(open_provider_game_uri(&provider, &external_id, "backup", None)?)
 }
 
+pub(crate) fn get_game_save_locations(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<Vec<String>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	Ok(save_backups::list_save_location_templates(&connection, &user.id, &provider, &external_id)?)
+}
+
+pub(crate) fn set_game_save_locations(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	templates: Vec<String>,
+) -> AppResult<Vec<String>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	Ok(save_backups::replace_save_location_templates(
+		&connection,
+		&user.id,
+		&provider,
+		&external_id,
+		&templates,
+	)?)
+}
+
+pub(crate) fn backup_game_saves(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<save_backups::SaveBackupResponse> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+
+	let mut templates = save_backups::default_save_location_templates(&provider, &external_id);
+	templates.extend(save_backups::list_save_location_templates(
+		&connection,
+		&user.id,
+		&provider,
+		&external_id,
+	)?);
+	let mut seen_templates = HashSet::new();
+	templates.retain(|template| seen_templates.insert(template.clone()));
+	let context = save_backups::build_save_location_context(
+		state.steam_root_override().as_deref(),
+		user.steam_id.as_deref(),
+		&provider,
+		&external_id,
+	);
+	let locations = templates
+		.into_iter()
+		.filter_map(|template| {
+			save_backups::expand_save_location_template(&template, &context).map(|path| (template, path))
+		})
+		.collect::<Vec<_>>();
+
+	let backups_directory = save_backups::save_backups_directory(&state.db_path, &provider, &external_id);
+	save_backups::create_save_backup(&backups_directory, &provider, &external_id, &locations).map_err(|error| {
+		if error.starts_with("No save files") {
+			AppError::not_found("save_files_not_found", error)
+		} else {
+			AppError::from(error)
+		}
+	})
+}
+
+pub(crate) fn list_save_backups(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<Vec<save_backups::SaveBackupResponse>> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let backups_directory = save_backups::save_backups_directory(&state.db_path, &provider, &external_id);
+	Ok(save_backups::list_save_backups(&backups_directory)?)
+}
+
+pub(crate) fn restore_game_saves(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	backup_id: String,
+) -> AppResult<save_backups::SaveRestoreResponse> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let backups_directory = save_backups::save_backups_directory(&state.db_path, &provider, &external_id);
+	Ok(save_backups::restore_save_backup(&backups_directory, &backup_id)?)
+}
+
+pub(crate) fn list_game_screenshots(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<Vec<screenshots::GameScreenshotResponse>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	collect_game_screenshots(state, &connection, &user, &provider, &external_id)
+}
+
+/// Steam screenshots plus those in the game's custom screenshot folder, newest first.
+fn collect_game_screenshots(
+	state: &AppState,
+	connection: &Connection,
+	user: &UserRow,
+	provider: &str,
+	external_id: &str,
+) -> AppResult<Vec<screenshots::GameScreenshotResponse>> {
+	let mut game_screenshots = Vec::new();
+	if let (Some(steam_id), Ok(app_id), "steam") =
+		(user.steam_id.as_deref(), external_id.parse::<u64>(), provider)
+	{
+		match screenshots::list_steam_screenshots(state.steam_root_override().as_deref(), steam_id, app_id) {
+			Ok(steam_screenshots) => game_screenshots.extend(steam_screenshots),
+			Err(error) => eprintln!("Failed to list Steam screenshots for app {app_id}: {error}"),
+		}
+	}
+	if let Some(directory) =
+		screenshots::find_game_screenshot_directory(connection, &user.id, provider, external_id)?
+	{
+		game_screenshots.extend(screenshots::list_directory_screenshots(Path::new(&directory), "custom"));
+	}
+	screenshots::sort_screenshots_newest_first(&mut game_screenshots);
+
+	Ok(game_screenshots)
+}
+
+/// The game's play sessions, newest first, each with its notes, rating and the screenshots
+/// taken while it ran.
+pub(crate) fn get_game_sessions(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	limit: Option<usize>,
+) -> AppResult<Vec<play_sessions::GameSessionResponse>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let mut sessions = play_sessions::list_game_sessions(&connection, &user.id, &provider, &external_id, limit)?;
+	if !sessions.is_empty() {
+		let game_screenshots = collect_game_screenshots(state, &connection, &user, &provider, &external_id)?;
+		play_sessions::attach_session_screenshots(&mut sessions, game_screenshots);
+	}
+	Ok(sessions)
+}
+
+pub(crate) fn annotate_game_session(
+	state: &AppState,
+	session_id: String,
+	notes: Option<String>,
+	rating: Option<i64>,
+) -> AppResult<play_sessions::GameSessionResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let session =
+		play_sessions::annotate_play_session(&connection, &user.id, session_id.trim(), notes.as_deref(), rating)?;
+	let game_screenshots =
+		collect_game_screenshots(state, &connection, &user, &session.provider, &session.external_id)?;
+	let mut sessions = [session];
+	play_sessions::attach_session_screenshots(&mut sessions, game_screenshots);
+	let [session] = sessions;
+	Ok(session)
+}
+
+pub(crate) fn open_screenshots_folder(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<()> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+
+	if let Some(directory) =
+		screenshots::find_game_screenshot_directory(&connection, &user.id, &provider, &external_id)?
+	{
+		return Ok(open_path_in_file_manager(Path::new(&directory))?);
+	}
+	if provider != "steam" {
+		return Err(AppError::not_found(
+			"screenshot_directory_missing",
+			"No screenshot folder is configured for this game.",
+		));
+	}
+
+	let app_id = external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
+	let steam_id = user
+		.steam_id
+		.as_deref()
+		.ok_or_else(|| AppError::unauthorized("steam_not_linked", "Steam is not linked for this account"))?;
+	let screenshots_directory =
+		screenshots::resolve_steam_screenshots_directory(state.steam_root_override().as_deref(), steam_id, app_id)?;
+	if !screenshots_directory.is_dir() {
+		return Err(AppError::not_found(
+			"screenshot_directory_missing",
+			"Steam has not saved any screenshots for this game yet.",
+		));
+	}
+	Ok(open_path_in_file_manager(&screenshots_directory)?)
+}
+
+pub(crate) fn set_game_screenshot_directory(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	directory: Option<String>,
+) -> AppResult<()> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	Ok(screenshots::save_game_screenshot_directory(
+		&connection,
+		&user.id,
+		&provider,
+		&external_id,
+		directory.as_deref(),
+	)?)
+}
+
 pub(crate) fn verify_game_files(
 	state: &AppState,
 	provider: String,
 	external_id: String,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
-	Ok(open_provider_game_uri(&provider, &external_id, "validate", None)?)
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	open_provider_game_uri(&provider, &external_id, "validate", None)?;
+	if provider == "steam" {
+		game_verification::start_game_verification(&connection, &user.id, &provider, &external_id)?;
+	}
+	Ok(())
+}
+
+pub(crate) fn get_game_verification_status(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<GameVerificationStatusResponse> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	Ok(game_verification::find_game_verification_status(
+		&connection,
+		&user.id,
+		&provider,
+		&external_id,
+	)?)
 }
 
 pub(crate) fn add_game_desktop_shortcut(
@@ -138,11 +992,8 @@ pub(crate) fn add_game_desktop_shortcut(
 	provider: String,
 	external_id: String,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
 
 	let fallback_name = format!("Game {}", external_id);
 	let game_name = connection
@@ -162,16 +1013,53 @@ pub(crate) fn add_game_desktop_shortcut(
 	Ok(create_provider_game_desktop_shortcut(&provider, &external_id, &game_name)?)
 }
 
+/// Creates a shortcut to any library game on the desktop, or in the application menu when
+/// `location` is `applications`.
+pub(crate) fn create_game_shortcut(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	location: Option<String>,
+) -> AppResult<GameShortcutResponse> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let shortcuts_directory = match location.as_deref().map(str::trim).unwrap_or("desktop") {
+		"desktop" => resolve_desktop_shortcuts_directory()?,
+		"applications" => install_jobs::resolve_application_shortcuts_directory()?,
+		other => {
+			return Err(AppError::validation(
+				"invalid_shortcut_location",
+				format!("Unsupported shortcut location: {other}"),
+			))
+		}
+	};
+
+	let (game_name, artwork_url) = connection
+		.query_row(
+			"SELECT name, artwork_url FROM games WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+			params![&user.id, &provider, &external_id],
+			|record| Ok((record.get::<_, String>(0)?, record.get::<_, Option<String>>(1)?)),
+		)
+		.map_err(|error| format!("Failed to query game for shortcut: {error}"))?;
+	let target_uri = game_shortcuts::game_shortcut_target_uri(&provider, &external_id);
+	let icon_path =
+		game_shortcuts::resolve_game_shortcut_icon(state, &provider, &external_id, artwork_url.as_deref());
+	let shortcut_path =
+		game_shortcuts::create_uri_shortcut(&shortcuts_directory, &game_name, &target_uri, icon_path.as_deref())?;
+
+	Ok(GameShortcutResponse {
+		shortcut_path: shortcut_path.to_string_lossy().into_owned(),
+		target_uri,
+		icon_path: icon_path.map(|icon_path| icon_path.to_string_lossy().into_owned()),
+	})
+}
+
 pub(crate) fn open_game_recording_settings(
 	state: &AppState,
 	provider: String,
 	external_id: String,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+	let AuthedGameContext { provider, .. } = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if provider != "steam" {
 		return Err(AppError::validation(
@@ -183,3 +1071,319 @@ pub(crate) fn open_game_recording_settings(
 	Ok(open_steam_game_recording_settings()?)
 }
 
+
+pub(crate) fn play_soundtrack(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<()> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+
+	let kind = connection
+		.query_row(
+			"
+			SELECT kind
+			FROM games
+			WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3
+			",
+			params![&user.id, &provider, &external_id],
+			|record| record.get::<_, String>(0),
+		)
+		.map_err(|error| format!("Failed to query game kind for soundtrack playback: {error}"))?;
+	if kind != "music" {
+		return Err(AppError::validation(
+			"not_a_soundtrack",
+			"Soundtrack playback is only available for soundtrack apps.",
+		));
+	}
+
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Soundtrack playback is only supported for Steam soundtracks.",
+		));
+	}
+
+	let app_id = external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
+	let soundtrack_directory = resolve_steam_soundtrack_directory_for_app_id(
+		state.steam_root_override().as_deref(),
+		app_id,
+	)
+	.map_err(|_| {
+		AppError::not_found(
+			"soundtrack_not_installed",
+			"Install this soundtrack in Steam before playing it.",
+		)
+	})?;
+	if !soundtrack_directory.is_dir() {
+		return Err(AppError::not_found(
+			"soundtrack_not_installed",
+			format!("Soundtrack directory is unavailable: {}", soundtrack_directory.display()),
+		));
+	}
+
+	let mut tracks = Vec::new();
+	collect_soundtrack_audio_files(&soundtrack_directory, &mut tracks)?;
+	if tracks.is_empty() {
+		return Err(AppError::not_found(
+			"soundtrack_tracks_missing",
+			format!("No audio files were found in {}", soundtrack_directory.display()),
+		));
+	}
+	tracks.sort();
+
+	let playlist_path = write_soundtrack_playlist(app_id, &tracks)?;
+	Ok(open_path_in_file_manager(&playlist_path)?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::application::services::game_settings_service;
+
+	fn save_pre_launch_hook(database: &test_support::TestDatabase, command: &str, confirmed: bool) {
+		let mut settings = default_game_properties_settings_payload();
+		settings.general.pre_launch_command = command.to_owned();
+		settings.general.launch_hooks_confirmed = confirmed;
+		save_game_properties_settings(
+			&database.connection,
+			&database.user.id,
+			library_import::CUSTOM_PROVIDER,
+			"hooked",
+			&settings,
+		)
+		.expect("save settings");
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn play_game_only_runs_confirmed_launch_hooks() {
+		let database = test_support::test_database();
+		// Without a launch target the launch itself fails right after the pre-launch hook.
+		database.insert_game(library_import::CUSTOM_PROVIDER, "hooked", "Hooked Game");
+		let state = database.signed_in_state();
+		let marker = database.dir.path().join("pre-launch-ran");
+		let hook = format!("touch '{}'", marker.display());
+
+		save_pre_launch_hook(&database, &hook, false);
+		let error = play_game(&state, library_import::CUSTOM_PROVIDER.to_owned(), "hooked".to_owned(), None)
+			.expect_err("launch without a target");
+		assert_ne!(error.code, "pre_launch_hook_failed");
+		assert!(!marker.exists(), "an unconfirmed hook ran");
+
+		save_pre_launch_hook(&database, &hook, true);
+		let _ = play_game(&state, library_import::CUSTOM_PROVIDER.to_owned(), "hooked".to_owned(), None);
+		assert!(marker.exists(), "the confirmed hook did not run");
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn play_game_stops_when_a_confirmed_pre_launch_hook_fails() {
+		let database = test_support::test_database();
+		database.insert_game(library_import::CUSTOM_PROVIDER, "hooked", "Hooked Game");
+		let state = database.signed_in_state();
+
+		save_pre_launch_hook(&database, "exit 3", true);
+		let error = play_game(&state, library_import::CUSTOM_PROVIDER.to_owned(), "hooked".to_owned(), None)
+			.expect_err("failing hook");
+		assert_eq!(error.code, "pre_launch_hook_failed");
+	}
+
+	#[test]
+	fn launch_hooks_cannot_be_saved_without_confirmation() {
+		let database = test_support::test_database();
+		database.insert_game(library_import::CUSTOM_PROVIDER, "hooked", "Hooked Game");
+		let state = database.signed_in_state();
+		let mut settings = default_game_properties_settings_payload();
+		settings.general.post_exit_command = String::from("echo done");
+
+		let error = game_settings_service::set_game_properties_settings(
+			&state,
+			library_import::CUSTOM_PROVIDER.to_owned(),
+			"hooked".to_owned(),
+			settings,
+		)
+		.expect_err("unconfirmed hooks");
+		assert_eq!(error.code, "launch_hooks_confirmation_required");
+	}
+
+	#[test]
+	fn get_game_storage_breakdown_reports_only_existing_folders() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("gog", "1207658924", "Portal");
+		let install_directory = database.write_steam_app_manifest(620, "Portal 2");
+		fs::write(install_directory.join("portal2.exe"), vec![0_u8; 4096]).expect("write install file");
+		let shader_cache = database.steam_root().join("steamapps").join("shadercache").join("620");
+		fs::create_dir_all(&shader_cache).expect("create shader cache");
+		fs::write(shader_cache.join("cache.bin"), vec![0_u8; 1024]).expect("write shader cache");
+		let state = database.signed_in_state_with_steam_root();
+
+		let breakdown =
+			get_game_storage_breakdown(&state, String::from("steam"), String::from("620")).expect("breakdown");
+		assert!(breakdown.install_bytes.is_some_and(|bytes| bytes >= 4096));
+		assert!(breakdown.shader_cache_bytes.is_some_and(|bytes| bytes >= 1024));
+		assert_eq!((breakdown.compatdata_bytes, breakdown.compatdata_path), (None, None));
+		assert_eq!(breakdown.workshop_bytes, None);
+		assert_eq!(
+			breakdown.total_bytes,
+			breakdown.install_bytes.unwrap_or_default() + breakdown.shader_cache_bytes.unwrap_or_default()
+		);
+
+		let error = get_game_storage_breakdown(&state, String::from("gog"), String::from("1207658924"))
+			.expect_err("non-Steam game");
+		assert_eq!(error.code, "unsupported_provider");
+	}
+
+	#[test]
+	fn storage_cleanup_requires_confirmation_and_tolerates_missing_folders() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.write_steam_app_manifest(620, "Portal 2");
+		let shader_cache = database.steam_root().join("steamapps").join("shadercache").join("620");
+		fs::create_dir_all(&shader_cache).expect("create shader cache");
+		let state = database.signed_in_state_with_steam_root();
+
+		let error = clear_game_shader_cache(&state, String::from("steam"), String::from("620"), false)
+			.expect_err("unconfirmed cleanup");
+		assert_eq!(error.code, "confirmation_required");
+		assert!(shader_cache.is_dir());
+
+		clear_game_shader_cache(&state, String::from("steam"), String::from("620"), true).expect("clear shader cache");
+		assert!(!shader_cache.exists());
+		delete_game_compatdata(&state, String::from("steam"), String::from("620"), true).expect("no compatdata");
+		assert!(database.steam_root().join("steamapps").join("common").join("Portal 2").is_dir());
+	}
+
+	/// Adds a second, empty Steam library to the fixture's `libraryfolders.vdf` and returns it.
+	fn add_second_steam_library(database: &test_support::TestDatabase) -> PathBuf {
+		let library_path = database.dir.path().join("SecondLibrary");
+		fs::create_dir_all(library_path.join("steamapps")).expect("create second library");
+		let escaped_path = |path: &Path| path.display().to_string().replace('\\', "\\\\");
+		fs::write(
+			database.steam_root().join("steamapps").join("libraryfolders.vdf"),
+			format!(
+				"\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+				escaped_path(&database.steam_root()),
+				escaped_path(&library_path),
+			),
+		)
+		.expect("write library folders");
+		library_path
+	}
+
+	#[test]
+	fn move_game_install_relocates_the_install_and_manifest() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let install_directory = database.write_steam_app_manifest(620, "Portal 2");
+		fs::create_dir_all(install_directory.join("bin")).expect("create bin dir");
+		fs::write(install_directory.join("bin").join("portal2.exe"), vec![0_u8; 2048]).expect("write install file");
+		let target_library = add_second_steam_library(&database);
+		let state = database.signed_in_state_with_steam_root();
+		let phases = std::cell::RefCell::new(Vec::new());
+
+		let response = move_game_install(
+			&state,
+			String::from("steam"),
+			String::from("620"),
+			target_library.display().to_string(),
+			|progress| phases.borrow_mut().push((progress.phase, progress.copied_bytes)),
+		)
+		.expect("move install");
+
+		let target_steamapps = target_library.join("steamapps");
+		assert!(target_steamapps.join("common").join("Portal 2").join("bin").join("portal2.exe").is_file());
+		assert!(target_steamapps.join("appmanifest_620.acf").is_file());
+		assert!(!install_directory.exists());
+		assert!(!database.steam_root().join("steamapps").join("appmanifest_620.acf").exists());
+		assert_eq!(phases.borrow().first(), Some(&("copying", 0)));
+		assert_eq!(phases.borrow().last(), Some(&("complete", response.moved_bytes)));
+	}
+
+	#[test]
+	fn move_game_install_rejects_unknown_current_and_occupied_targets() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.write_steam_app_manifest(620, "Portal 2");
+		let target_library = add_second_steam_library(&database);
+		let unknown_library = database.dir.path().join("Unknown");
+		fs::create_dir_all(unknown_library.join("steamapps")).expect("create unknown library");
+		let state = database.signed_in_state_with_steam_root();
+		let move_to = |library: &Path| {
+			move_game_install(&state, String::from("steam"), String::from("620"), library.display().to_string(), |_| {})
+		};
+
+		for library in [unknown_library.as_path(), database.steam_root().as_path(), Path::new("  ")] {
+			assert!(move_to(library).is_err(), "{}", library.display());
+		}
+		fs::create_dir_all(target_library.join("steamapps").join("common").join("Portal 2"))
+			.expect("occupy target folder");
+		let error = move_to(&target_library).expect_err("occupied target");
+		assert!(error.message.contains("already exists"), "{}", error.message);
+		assert!(database.steam_root().join("steamapps").join("appmanifest_620.acf").is_file());
+	}
+
+	#[cfg(unix)]
+	#[test]
+	fn stop_game_terminates_the_tracked_process_and_kills_it_only_when_forced() {
+		use std::os::unix::process::ExitStatusExt;
+
+		let database = test_support::test_database();
+		database.insert_game(library_import::CUSTOM_PROVIDER, "stubborn", "Stubborn Game");
+		let state = database.signed_in_state();
+		let stop = |force: bool| stop_game(&state, library_import::CUSTOM_PROVIDER.to_owned(), "stubborn".to_owned(), force);
+
+		let error = stop(false).expect_err("game not running");
+		assert_eq!(error.code, "game_not_running");
+
+		// Ignored signals survive `exec`, so this process shrugs off a polite stop request.
+		let mut game_process = Command::new("sh")
+			.args(["-c", "trap '' TERM; exec sleep 30"])
+			.spawn()
+			.expect("spawn game process");
+		state.running_games.track_process("custom:stubborn", game_process.id());
+		// Give the shell time to install the trap before it is signalled.
+		thread::sleep(Duration::from_millis(200));
+
+		stop(false).expect("request stop");
+		thread::sleep(Duration::from_millis(200));
+		assert!(game_process.try_wait().expect("poll game process").is_none());
+
+		stop(true).expect("force stop");
+		let status = game_process.wait().expect("wait for game process");
+		assert_eq!(status.signal(), Some(9));
+	}
+
+	#[test]
+	fn move_game_install_refuses_updating_games_and_keeps_the_manifest_with_the_files() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let install_directory = database.write_steam_app_manifest(620, "Portal 2");
+		fs::write(install_directory.join("portal2.exe"), vec![0_u8; 64]).expect("write install file");
+		let source_manifest = database.steam_root().join("steamapps").join("appmanifest_620.acf");
+		let target_library = add_second_steam_library(&database);
+		let target_manifest = target_library.join("steamapps").join("appmanifest_620.acf");
+		let state = database.signed_in_state_with_steam_root();
+		let move_install = || {
+			move_game_install(&state, String::from("steam"), String::from("620"), target_library.display().to_string(), |_| {})
+		};
+
+		let manifest = fs::read_to_string(&source_manifest).expect("read manifest");
+		fs::write(&source_manifest, manifest.replace("\"StateFlags\"\t\"4\"", "\"StateFlags\"\t\"1026\""))
+			.expect("mark update running");
+		let error = move_install().expect_err("updating game");
+		assert_eq!(error.code, "game_updating");
+		fs::write(&source_manifest, &manifest).expect("mark fully installed");
+
+		// A file where the target's `common` folder belongs makes the install folder move fail.
+		fs::write(target_library.join("steamapps").join("common"), "").expect("block target folder");
+		assert!(move_install().is_err());
+		assert!(install_directory.join("portal2.exe").is_file());
+		assert!(source_manifest.is_file());
+		assert!(!target_manifest.exists());
+	}
+}
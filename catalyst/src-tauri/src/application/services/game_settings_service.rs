@@ -1,23 +1,55 @@
 use crate::*;
 use crate::application::error::{AppError, AppResult};
+use crate::application::context::{AuthedContext, AuthedGameContext};
+use crate::application::services::library_service::{
+	self, game_matches_library_filter, LibraryGameIdentityInput, LibraryQueryFilter,
+};
 use chrono::Duration as ChronoDuration;
 
+const BULK_UPDATE_POLICY_MAX_GAMES: usize = 5000;
+
+/// Selects games either explicitly or by library filter; with neither, every Steam game in the
+/// library is targeted. Omitted modes are left unchanged.
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BulkUpdatePolicyInput {
+	pub games: Option<Vec<LibraryGameIdentityInput>>,
+	pub filter: Option<LibraryQueryFilter>,
+	pub installed_only: Option<bool>,
+	pub automatic_updates_mode: Option<String>,
+	pub background_downloads_mode: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SetGamesHiddenResponse {
+	pub updated_games: usize,
+	/// Set when the games were hidden in Catalyst but not every Steam game could be hidden in
+	/// Steam's config files.
+	pub steam_config_error: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BulkUpdatePolicyResponse {
+	pub updated_games: usize,
+	/// Set when the settings were saved but could not be mirrored into Steam's config files.
+	pub steam_config_error: Option<String>,
+	/// Steam was running, so its config files will be updated after it exits.
+	pub steam_config_queued: bool,
+}
+
 pub(crate) fn list_game_languages(
 	state: &AppState,
 	provider: String,
 	external_id: String,
 ) -> AppResult<Vec<String>> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" {
 		return Ok(Vec::new());
@@ -31,12 +63,16 @@ pub(crate) fn list_game_languages(
 	let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_LANGUAGES_CACHE_TTL_HOURS);
 	let cached_languages_entry = find_cached_steam_app_languages(&connection, app_id)?;
 	if let Some((cached_languages, fetched_at)) = cached_languages_entry.as_ref() {
-		if *fetched_at >= stale_before {
+		if *fetched_at >= stale_before || state.is_offline() {
 			return Ok(cached_languages.clone());
 		}
 	}
 
-	let client = build_http_client()?;
+	if state.is_offline() {
+		return Ok(Vec::new());
+	}
+
+	let client = state.http_client()?;
 	match fetch_steam_supported_languages(&connection, &client, app_id) {
 		Ok(fetched_languages) => {
 			cache_steam_app_languages(&connection, app_id, &fetched_languages)?;
@@ -57,17 +93,12 @@ pub(crate) fn list_game_compatibility_tools(
 	provider: String,
 	external_id: String,
 ) -> AppResult<Vec<GameCompatibilityToolResponse>> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" {
 		return Ok(Vec::new());
@@ -77,7 +108,8 @@ pub(crate) fn list_game_compatibility_tools(
 		Ok(parsed) => parsed,
 		Err(_) => return Ok(Vec::new()),
 	};
-	let include_linux_runtime_tools = match build_http_client()
+	let include_linux_runtime_tools = match state
+		.http_client()
 		.and_then(|client| fetch_steam_app_linux_platform_support_from_store(&connection, &client, app_id))
 	{
 		Ok(Some(supported)) => supported,
@@ -92,27 +124,28 @@ pub(crate) fn list_game_compatibility_tools(
 	};
 
 	Ok(resolve_steam_compatibility_tools(
-		state.steam_root_override.as_deref(),
+		state.steam_root_override().as_deref(),
 		include_linux_runtime_tools,
 	)?)
 }
 
+/// Lists the Linux launch wrappers Catalyst can compose into Steam launch options and whether
+/// each one is installed.
+pub(crate) fn list_launch_wrappers() -> AppResult<Vec<LaunchWrapperResponse>> {
+	Ok(detect_launch_wrappers())
+}
+
 pub(crate) fn get_game_privacy_settings(
 	state: &AppState,
 	provider: String,
 	external_id: String,
 ) -> AppResult<GamePrivacySettingsResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	Ok(load_game_privacy_settings(
 		&connection,
@@ -129,17 +162,12 @@ pub(crate) fn set_game_privacy_settings(
 	hide_in_library: bool,
 	mark_as_private: bool,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	let mut settings = load_game_privacy_settings(
 		&connection,
@@ -166,22 +194,139 @@ pub(crate) fn set_game_privacy_settings(
 	)?)
 }
 
+/// Hides or shows many games in one transaction, leaving their private flag as it is. Steam games
+/// are then hidden in Steam too, as with `set_game_privacy_settings`.
+pub(crate) fn set_games_hidden(
+	state: &AppState,
+	games: Vec<LibraryGameIdentityInput>,
+	hidden: bool,
+) -> AppResult<SetGamesHiddenResponse> {
+	let games = library_service::normalize_batch_game_identities(&games)?;
+	let AuthedContext { mut connection, user } = AuthedContext::new(state)?;
+	ensure_owned_games_exist(&connection, &user.id, &games)?;
+
+	let transaction = connection
+		.transaction()
+		.map_err(|error| format!("Failed to start privacy settings transaction: {error}"))?;
+	let mut updated_steam_settings = Vec::new();
+	for (provider, external_id) in &games {
+		let mut settings = load_game_privacy_settings(&transaction, &user.id, provider, external_id)?;
+		settings.hide_in_library = hidden;
+		if provider == "steam" {
+			if let Ok(app_id) = external_id.parse::<u64>() {
+				updated_steam_settings.push((app_id, settings));
+			}
+		}
+		save_game_privacy_settings(&transaction, &user.id, provider, external_id, settings)?;
+	}
+	transaction
+		.commit()
+		.map_err(|error| format!("Failed to commit privacy settings: {error}"))?;
+
+	let mut steam_config_error = None;
+	for (app_id, settings) in &updated_steam_settings {
+		if let Err(error) = apply_steam_game_privacy_settings(state, &user, *app_id, settings) {
+			eprintln!("Could not apply Steam privacy settings for app {app_id}: {error}");
+			steam_config_error.get_or_insert(error);
+		}
+	}
+
+	Ok(SetGamesHiddenResponse {
+		updated_games: games.len(),
+		steam_config_error,
+	})
+}
+
+pub(crate) fn get_private_games_lock_status(
+	state: &AppState,
+) -> AppResult<private_games::PrivateGamesLockStatusResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+
+	Ok(private_games::private_games_lock_status(state, &connection, &user.id)?)
+}
+
+fn ensure_private_games_pin(connection: &Connection, user_id: &str, pin: &str) -> AppResult<()> {
+	match private_games::check_private_games_pin(connection, user_id, pin)? {
+		private_games::PrivateGamesPinCheck::Accepted => Ok(()),
+		private_games::PrivateGamesPinCheck::Rejected => {
+			Err(AppError::unauthorized("invalid_private_games_pin", "Incorrect PIN"))
+		}
+		private_games::PrivateGamesPinCheck::CoolingDown { retry_after } => Err(AppError::unauthorized(
+			"private_games_pin_cooldown",
+			format!(
+				"Too many incorrect PINs. Try again in {} minute(s).",
+				retry_after.as_secs().div_ceil(60).max(1)
+			),
+		)),
+	}
+}
+
+/// Sets or changes the PIN that guards games marked private. Changing an existing PIN needs
+/// the current one.
+pub(crate) fn set_private_games_pin(
+	state: &AppState,
+	pin: String,
+	current_pin: Option<String>,
+) -> AppResult<private_games::PrivateGamesLockStatusResponse> {
+	let pin = private_games::normalize_private_games_pin(&pin)
+		.map_err(|error| AppError::validation("invalid_private_games_pin", error))?;
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	ensure_private_games_pin(&connection, &user.id, current_pin.as_deref().unwrap_or_default())?;
+
+	private_games::save_private_games_pin(&connection, &user.id, &pin)?;
+	private_games::lock_private_games(state);
+	Ok(private_games::private_games_lock_status(state, &connection, &user.id)?)
+}
+
+pub(crate) fn clear_private_games_pin(
+	state: &AppState,
+	current_pin: String,
+) -> AppResult<private_games::PrivateGamesLockStatusResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	ensure_private_games_pin(&connection, &user.id, &current_pin)?;
+
+	private_games::delete_private_games_pin(&connection, &user.id)?;
+	private_games::lock_private_games(state);
+	Ok(private_games::private_games_lock_status(state, &connection, &user.id)?)
+}
+
+/// Shows private games in library responses and allows launching them until the relock
+/// timeout passes.
+pub(crate) fn unlock_private_games(
+	state: &AppState,
+	pin: String,
+) -> AppResult<private_games::PrivateGamesLockStatusResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	if !private_games::has_private_games_pin(&connection, &user.id)? {
+		return Err(AppError::validation(
+			"private_games_pin_not_set",
+			"Set a PIN before locking private games",
+		));
+	}
+	ensure_private_games_pin(&connection, &user.id, &pin)?;
+
+	private_games::unlock_private_games(state, &user.id);
+	Ok(private_games::private_games_lock_status(state, &connection, &user.id)?)
+}
+
+pub(crate) fn lock_private_games(state: &AppState) -> AppResult<private_games::PrivateGamesLockStatusResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+
+	private_games::lock_private_games(state);
+	Ok(private_games::private_games_lock_status(state, &connection, &user.id)?)
+}
+
 pub(crate) fn clear_game_overlay_data(
 	state: &AppState,
 	provider: String,
 	external_id: String,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider == "steam" {
 		let app_id = normalized_external_id
@@ -206,22 +351,117 @@ pub(crate) fn clear_game_overlay_data(
 	)?)
 }
 
+pub(crate) fn get_game_cloud_status(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<SteamCloudStatusResponse> {
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_cloud_app_id(&normalized_provider, &normalized_external_id)?;
+	Ok(read_steam_app_cloud_status(state, &connection, &user, app_id)?)
+}
+
+pub(crate) fn set_game_cloud_enabled(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	enabled: bool,
+) -> AppResult<SteamCloudStatusResponse> {
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_cloud_app_id(&normalized_provider, &normalized_external_id)?;
+	apply_steam_app_cloud_enabled(state, &user, app_id, enabled)?;
+	Ok(read_steam_app_cloud_status(state, &connection, &user, app_id)?)
+}
+
+fn parse_steam_cloud_app_id(provider: &str, external_id: &str) -> AppResult<u64> {
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Steam Cloud status is only available for Steam games.",
+		));
+	}
+	external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))
+}
+
+pub(crate) fn list_controller_layouts(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<controller_layouts::ControllerLayoutsResponse> {
+	let AuthedGameContext { user, provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_controller_app_id(&provider, &external_id)?;
+	let steam_id = user
+		.steam_id
+		.as_deref()
+		.ok_or_else(|| AppError::conflict("steam_not_linked", "Steam is not linked for this account"))?;
+
+	Ok(controller_layouts::list_controller_layouts(
+		state.steam_root_override().as_deref(),
+		steam_id,
+		app_id,
+	)?)
+}
+
+pub(crate) fn set_game_controller_layout(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	controller_type: String,
+	layout_id: Option<String>,
+) -> AppResult<()> {
+	let AuthedGameContext { user, provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let app_id = parse_steam_controller_app_id(&provider, &external_id)?;
+	let steam_id = user
+		.steam_id
+		.as_deref()
+		.ok_or_else(|| AppError::conflict("steam_not_linked", "Steam is not linked for this account"))?;
+
+	Ok(controller_layouts::save_controller_layout_selection(
+		state.steam_root_override().as_deref(),
+		steam_id,
+		app_id,
+		&controller_type,
+		layout_id.as_deref(),
+	)?)
+}
+
+fn parse_steam_controller_app_id(provider: &str, external_id: &str) -> AppResult<u64> {
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Controller layouts are only available for Steam games.",
+		));
+	}
+	external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))
+}
+
 pub(crate) fn get_game_properties_settings(
 	state: &AppState,
 	provider: String,
 	external_id: String,
 ) -> AppResult<GamePropertiesSettingsPayload> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	Ok(load_game_properties_settings(
 		&connection,
@@ -237,37 +477,155 @@ pub(crate) fn set_game_properties_settings(
 	external_id: String,
 	settings: GamePropertiesSettingsPayload,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	let normalized_settings = normalize_game_properties_settings_payload(settings);
-	save_game_properties_settings(
+	let has_launch_hooks = !normalized_settings.general.pre_launch_command.is_empty()
+		|| !normalized_settings.general.post_exit_command.is_empty();
+	if has_launch_hooks && !normalized_settings.general.launch_hooks_confirmed {
+		return Err(AppError::validation(
+			"launch_hooks_confirmation_required",
+			"Launch hooks run arbitrary commands on this computer. Confirm before enabling them.",
+		));
+	}
+	persist_game_properties_settings(
+		state,
 		&connection,
-		&user.id,
+		&user,
 		&normalized_provider,
 		&normalized_external_id,
 		&normalized_settings,
+	)
+}
+
+fn normalize_bulk_update_mode(
+	value: Option<String>,
+	allowed_modes: &[&str],
+	code: &'static str,
+) -> AppResult<Option<String>> {
+	let Some(value) = value.map(|value| value.trim().to_owned()).filter(|value| !value.is_empty()) else {
+		return Ok(None);
+	};
+	allowed_modes
+		.iter()
+		.find(|mode| mode.eq_ignore_ascii_case(&value))
+		.map(|mode| Some((*mode).to_owned()))
+		.ok_or_else(|| {
+			AppError::validation(code, format!("Unsupported mode '{value}'. Use one of: {}", allowed_modes.join(", ")))
+		})
+}
+
+/// Sets the automatic update and background download modes of many Steam games at once. The
+/// stored settings are written in one transaction and Steam's localconfig.vdf is rewritten once.
+pub(crate) fn bulk_set_update_policy(
+	state: &AppState,
+	input: BulkUpdatePolicyInput,
+) -> AppResult<BulkUpdatePolicyResponse> {
+	let automatic_updates_mode = normalize_bulk_update_mode(
+		input.automatic_updates_mode,
+		&STEAM_AUTOMATIC_UPDATES_MODES,
+		"invalid_automatic_updates_mode",
+	)?;
+	let background_downloads_mode = normalize_bulk_update_mode(
+		input.background_downloads_mode,
+		&STEAM_BACKGROUND_DOWNLOADS_MODES,
+		"invalid_background_downloads_mode",
 	)?;
+	if automatic_updates_mode.is_none() && background_downloads_mode.is_none() {
+		return Err(AppError::validation(
+			"no_update_policy",
+			"Choose an automatic updates or background downloads mode.",
+		));
+	}
 
-	if normalized_provider == "steam" {
-		let app_id = normalized_external_id
+	let AuthedContext { mut connection, user } = AuthedContext::new(state)?;
+	let selected_ids = input.games.map(|games| {
+		games
+			.iter()
+			.map(|game| format!("{}:{}", game.provider.trim().to_lowercase(), game.external_id.trim()))
+			.collect::<HashSet<_>>()
+	});
+	let installed_only = input.installed_only.unwrap_or(false);
+	let targets = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?
+		.into_iter()
+		.filter(|game| game.provider == "steam")
+		.filter(|game| selected_ids.as_ref().map_or(true, |ids| ids.contains(&game.id)))
+		.filter(|game| input.filter.as_ref().map_or(true, |filter| game_matches_library_filter(game, filter)))
+		.filter(|game| !installed_only || game.installed)
+		.filter_map(|game| game.external_id.parse::<u64>().ok().map(|app_id| (app_id, game.external_id)))
+		.collect::<Vec<_>>();
+	if targets.len() > BULK_UPDATE_POLICY_MAX_GAMES {
+		return Err(AppError::validation(
+			"too_many_games",
+			format!("At most {BULK_UPDATE_POLICY_MAX_GAMES} games can be updated at once"),
+		));
+	}
+
+	let transaction = connection
+		.transaction()
+		.map_err(|error| format!("Failed to start update policy transaction: {error}"))?;
+	let mut updated_settings = Vec::with_capacity(targets.len());
+	for (app_id, external_id) in targets {
+		let mut settings = load_game_properties_settings(&transaction, &user.id, "steam", &external_id)?;
+		if let Some(mode) = &automatic_updates_mode {
+			settings.updates.automatic_updates_mode = mode.clone();
+		}
+		if let Some(mode) = &background_downloads_mode {
+			settings.updates.background_downloads_mode = mode.clone();
+		}
+		save_game_properties_settings(&transaction, &user.id, "steam", &external_id, &settings)?;
+		updated_settings.push((app_id, settings));
+	}
+	transaction
+		.commit()
+		.map_err(|error| format!("Failed to commit update policies: {error}"))?;
+
+	if process_watcher::is_steam_client_running() {
+		for (app_id, _) in &updated_settings {
+			steam_config_writer::queue_pending_steam_config_write(&connection, &user.id, *app_id)?;
+		}
+		return Ok(BulkUpdatePolicyResponse {
+			updated_games: updated_settings.len(),
+			steam_config_error: None,
+			steam_config_queued: true,
+		});
+	}
+	let steam_config_error = apply_steam_update_policies(state, &user, &updated_settings).err();
+	if let Some(error) = &steam_config_error {
+		eprintln!("Could not apply Steam update policies: {error}");
+	}
+	Ok(BulkUpdatePolicyResponse {
+		updated_games: updated_settings.len(),
+		steam_config_error,
+		steam_config_queued: false,
+	})
+}
+
+/// Stores normalized settings and, for Steam games, mirrors them into Steam's localconfig.
+pub(crate) fn persist_game_properties_settings(
+	state: &AppState,
+	connection: &Connection,
+	user: &UserRow,
+	provider: &str,
+	external_id: &str,
+	settings: &GamePropertiesSettingsPayload,
+) -> AppResult<()> {
+	save_game_properties_settings(connection, &user.id, provider, external_id, settings)?;
+
+	if provider == "steam" {
+		let app_id = external_id
 			.parse::<u64>()
 			.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
-		if let Err(error) = apply_steam_game_properties_settings(
-			state,
-			&user,
-			app_id,
-			&normalized_settings,
-		) {
+		if process_watcher::is_steam_client_running() {
+			steam_config_writer::queue_pending_steam_config_write(connection, &user.id, app_id)?;
+			return Ok(());
+		}
+		if let Err(error) = apply_steam_game_properties_settings(state, user, app_id, settings) {
 			eprintln!(
 				"Could not apply Steam game properties for app {}: {}",
 				app_id, error
@@ -283,17 +641,12 @@ pub(crate) fn get_game_customization_artwork(
 	provider: String,
 	external_id: String,
 ) -> AppResult<GameCustomizationArtworkResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" || normalized_external_id.parse::<u64>().is_err() {
 		return Ok(empty_game_customization_artwork_response());
@@ -309,28 +662,59 @@ pub(crate) fn get_game_customization_artwork(
 	};
 
 	Ok(resolve_steam_customization_artwork(
-		state.steam_root_override.as_deref(),
+		state.steam_root_override().as_deref(),
 		steam_id,
 		&normalized_external_id,
 	))
 }
 
-pub(crate) fn get_game_installation_details(
+/// Replaces the game's artwork with a local image. Returns the managed copy's path.
+pub(crate) fn set_game_custom_artwork(
 	state: &AppState,
 	provider: String,
 	external_id: String,
-) -> AppResult<GameInstallationDetailsResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
+	path: String,
+) -> AppResult<String> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let source_path = path.trim();
+	if source_path.is_empty() {
+		return Err(AppError::validation("invalid_artwork_path", "Artwork path is required"));
+	}
+
+	let artwork_path = custom_artwork::store_custom_artwork(
 		&connection,
+		&state.db_path,
 		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+		&provider,
+		&external_id,
+		Path::new(source_path),
+	)
+	.map_err(|error| AppError::validation("invalid_custom_artwork", error))?;
+	Ok(artwork_path.display().to_string())
+}
+
+pub(crate) fn clear_game_custom_artwork(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<()> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	custom_artwork::clear_custom_artwork(&connection, &user.id, &provider, &external_id)?;
+	Ok(())
+}
+
+pub(crate) fn get_game_installation_details(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<GameInstallationDetailsResponse> {
+	let AuthedGameContext {
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" {
 		return Ok(GameInstallationDetailsResponse {
@@ -350,7 +734,7 @@ pub(crate) fn get_game_installation_details(
 	};
 
 	let manifest_path =
-		match resolve_steam_manifest_path_for_app_id(state.steam_root_override.as_deref(), app_id)
+		match resolve_steam_manifest_path_for_app_id(state.steam_root_override().as_deref(), app_id)
 		{
 			Ok(path) => path,
 			Err(_) => {
@@ -384,17 +768,12 @@ pub(crate) fn get_game_install_size_estimate(
 	provider: String,
 	external_id: String,
 ) -> AppResult<Option<u64>> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" {
 		return Ok(None);
@@ -406,7 +785,7 @@ pub(crate) fn get_game_install_size_estimate(
 	};
 
 	if let Ok(manifest_path) =
-		resolve_steam_manifest_path_for_app_id(state.steam_root_override.as_deref(), app_id)
+		resolve_steam_manifest_path_for_app_id(state.steam_root_override().as_deref(), app_id)
 	{
 		if let Ok(manifest_contents) = fs::read_to_string(&manifest_path) {
 			if let Some(size_on_disk_bytes) = parse_steam_manifest_size_on_disk_bytes(&manifest_contents)
@@ -416,8 +795,44 @@ pub(crate) fn get_game_install_size_estimate(
 		}
 	}
 
-	let client = build_http_client()?;
-	Ok(fetch_steam_install_size_estimate_from_store(&connection, &client, app_id)?)
+	let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_SIZE_ESTIMATE_CACHE_TTL_HOURS);
+	if let Some(cached_size_bytes) =
+		find_cached_steam_app_size_estimate(&connection, app_id, stale_before)?
+	{
+		return Ok(cached_size_bytes);
+	}
+
+	if state.is_offline() {
+		return Ok(None);
+	}
+
+	let client = state.http_client()?;
+	let size_bytes = fetch_steam_install_size_estimate_from_store(&connection, &client, app_id)?;
+	// Best-effort cache for the next time the install dialog opens.
+	let _ = cache_steam_app_size_estimate(&connection, app_id, size_bytes);
+	Ok(size_bytes)
+}
+
+/// Sizes the update waiting for an installed Steam game, before the user lets Steam fetch it.
+pub(crate) fn get_pending_update_size(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<steam_update_size::PendingUpdateSizeResponse> {
+	let AuthedGameContext { provider, external_id, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	if provider != "steam" {
+		return Err(AppError::validation(
+			"unsupported_provider",
+			"Update sizes are only available for Steam games.",
+		));
+	}
+	let app_id = external_id
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_external_id", "Steam external_id must be a numeric app ID"))?;
+
+	steam_update_size::read_pending_steam_update_size(state.steam_root_override().as_deref(), app_id)
+		.map_err(|error| AppError::not_found("game_not_installed", error))
 }
 
 pub(crate) fn list_game_install_locations(
@@ -425,23 +840,14 @@ pub(crate) fn list_game_install_locations(
 	provider: String,
 	external_id: String,
 ) -> AppResult<Vec<GameInstallLocationResponse>> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext { provider: normalized_provider, .. } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" {
 		return Ok(Vec::new());
 	}
 
-	let steam_roots = resolve_steam_root_paths(state.steam_root_override.as_deref());
+	let steam_roots = resolve_steam_root_paths(state.steam_root_override().as_deref());
 	if steam_roots.is_empty() {
 		return Ok(Vec::new());
 	}
@@ -496,3 +902,172 @@ pub(crate) fn list_game_install_locations(
 	Ok(locations)
 }
 
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn bulk_update_policy_input(value: serde_json::Value) -> BulkUpdatePolicyInput {
+		serde_json::from_value(value).expect("bulk update policy input")
+	}
+
+	fn automatic_updates_mode(database: &test_support::TestDatabase, external_id: &str) -> String {
+		load_game_properties_settings(&database.connection, &database.user.id, "steam", external_id)
+			.expect("load settings")
+			.updates
+			.automatic_updates_mode
+	}
+
+	#[test]
+	fn bulk_set_update_policy_rejects_missing_and_unknown_modes() {
+		let database = test_support::test_database();
+		let state = database.signed_in_state();
+
+		let error = bulk_set_update_policy(&state, bulk_update_policy_input(serde_json::json!({})))
+			.err()
+			.expect("no mode");
+		assert_eq!(error.code, "no_update_policy");
+		let error = bulk_set_update_policy(
+			&state,
+			bulk_update_policy_input(serde_json::json!({ "automaticUpdatesMode": "sometimes" })),
+		)
+		.err()
+		.expect("unknown mode");
+		assert_eq!(error.code, "invalid_automatic_updates_mode");
+	}
+
+	#[test]
+	fn bulk_set_update_policy_updates_selected_steam_games_and_localconfig() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		database
+			.connection
+			.execute(
+				"UPDATE games SET installed = 1 WHERE user_id = ?1 AND external_id = '620'",
+				params![database.user.id],
+			)
+			.expect("mark installed");
+		set_user_steam_id(&database.connection, &database.user.id, "76561197960287930").expect("link Steam");
+		let config_directory = database.steam_root().join("userdata").join("76561197960287930").join("config");
+		fs::create_dir_all(&config_directory).expect("create userdata");
+		let localconfig_path = config_directory.join("localconfig.vdf");
+		fs::write(&localconfig_path, "\"UserLocalConfigStore\"\n{\n}\n").expect("write localconfig");
+		let state = database.signed_in_state_with_steam_root();
+
+		let response = bulk_set_update_policy(
+			&state,
+			bulk_update_policy_input(serde_json::json!({
+				"installedOnly": true,
+				"automaticUpdatesMode": "Immediately-Download",
+			})),
+		)
+		.expect("bulk update");
+		assert_eq!(response.updated_games, 1);
+		assert_eq!(response.steam_config_error, None);
+		assert_eq!(automatic_updates_mode(&database, "620"), "immediately-download");
+		assert_ne!(automatic_updates_mode(&database, "400"), "immediately-download");
+
+		let localconfig = parse_vdf_document(&fs::read_to_string(&localconfig_path).expect("read localconfig"))
+			.expect("parse localconfig");
+		let app_settings = ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", "620"]
+			.iter()
+			.try_fold(&localconfig, |value, key| vdf_find_object_value(value, key))
+			.expect("app settings");
+		assert_eq!(vdf_get_text_entry(app_settings, "AutoUpdateBehavior"), Some("2"));
+	}
+
+	#[test]
+	fn bulk_set_update_policy_keeps_saved_settings_when_steam_is_not_linked() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		database.insert_game("epic", "fortnite", "Fortnite");
+		let state = database.signed_in_state();
+
+		let response = bulk_set_update_policy(
+			&state,
+			bulk_update_policy_input(serde_json::json!({ "automaticUpdatesMode": "wait-until-launch" })),
+		)
+		.expect("bulk update");
+		assert_eq!(response.updated_games, 2);
+		assert!(response.steam_config_error.is_some());
+		assert_eq!(automatic_updates_mode(&database, "620"), "wait-until-launch");
+		assert_eq!(automatic_updates_mode(&database, "400"), "wait-until-launch");
+	}
+
+	#[test]
+	fn set_game_custom_artwork_validates_the_path_and_shows_in_the_library() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let state = database.signed_in_state();
+		let png_file = database.dir.path().join("cover.png");
+		std::fs::write(&png_file, b"\x89PNG\r\n\x1a\nrest").expect("write png");
+		let png_path = png_file.display().to_string();
+
+		let error = set_game_custom_artwork(&state, "steam".into(), "620".into(), String::from("  "))
+			.err()
+			.expect("blank path");
+		assert_eq!(error.code, "invalid_artwork_path");
+		assert!(set_game_custom_artwork(&state, "steam".into(), "400".into(), png_path.clone()).is_err());
+
+		let artwork_path = set_game_custom_artwork(&state, "steam".into(), "620".into(), png_path).expect("set artwork");
+		let custom_artwork_path = |state: &AppState| {
+			library_service::get_library(state, None, None, None).expect("library").games[0]
+				.custom_artwork_path
+				.clone()
+		};
+		assert_eq!(custom_artwork_path(&state), Some(artwork_path));
+
+		clear_game_custom_artwork(&state, "steam".into(), "620".into()).expect("clear artwork");
+		assert_eq!(custom_artwork_path(&state), None);
+	}
+
+	#[test]
+	fn set_games_hidden_keeps_the_private_flag_and_rejects_unowned_batches() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("epic", "fortnite", "Fortnite");
+		save_game_privacy_settings(
+			&database.connection,
+			&database.user.id,
+			"steam",
+			"620",
+			GamePrivacySettingsResponse {
+				hide_in_library: false,
+				mark_as_private: true,
+				overlay_data_deleted: false,
+			},
+		)
+		.expect("save privacy settings");
+		let state = database.signed_in_state();
+		let games = |games: &[(&str, &str)]| {
+			games
+				.iter()
+				.map(|(provider, external_id)| LibraryGameIdentityInput {
+					provider: String::from(*provider),
+					external_id: String::from(*external_id),
+				})
+				.collect::<Vec<_>>()
+		};
+		let privacy = |provider: &str, external_id: &str| {
+			let settings = load_game_privacy_settings(&database.connection, &database.user.id, provider, external_id)
+				.expect("load privacy settings");
+			(settings.hide_in_library, settings.mark_as_private)
+		};
+
+		assert!(set_games_hidden(&state, games(&[("steam", "620"), ("gog", "witcher")]), true).is_err());
+		assert_eq!(privacy("steam", "620"), (false, true));
+		assert!(set_games_hidden(&state, Vec::new(), true).is_err());
+
+		// Steam isn't linked, so the games are hidden in Catalyst and the Steam error is reported.
+		let response = set_games_hidden(&state, games(&[("steam", "620"), ("epic", "fortnite")]), true).expect("hide games");
+		assert_eq!(response.updated_games, 2);
+		assert!(response.steam_config_error.is_some());
+		assert_eq!((privacy("steam", "620"), privacy("epic", "fortnite")), ((true, true), (true, false)));
+
+		let response = set_games_hidden(&state, games(&[("epic", "fortnite")]), false).expect("show game");
+		assert!(response.steam_config_error.is_none());
+		assert_eq!(privacy("epic", "fortnite"), (false, false));
+	}
+}
@@ -0,0 +1,112 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+use crate::application::context::AuthedContext;
+use crate::application::services::game_settings_service;
+use crate::launch_templates::LaunchTemplateResponse;
+
+const LAUNCH_TEMPLATE_MAX_TARGETS: usize = 500;
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchTemplateInput {
+	pub id: Option<String>,
+	pub name: String,
+	pub launch_options: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchTemplateTargetInput {
+	pub provider: String,
+	pub external_id: String,
+}
+
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchTemplateApplyResultResponse {
+	pub provider: String,
+	pub external_id: String,
+	pub launch_options: Option<String>,
+	pub error: Option<AppError>,
+}
+
+pub(crate) fn list_launch_templates(state: &AppState) -> AppResult<Vec<LaunchTemplateResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(launch_templates::list_launch_templates(&connection, &user.id)?)
+}
+
+pub(crate) fn save_launch_template(
+	state: &AppState,
+	template: LaunchTemplateInput,
+) -> AppResult<LaunchTemplateResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(launch_templates::save_launch_template(
+		&connection,
+		&user.id,
+		template.id.as_deref(),
+		&template.name,
+		&template.launch_options,
+	)?)
+}
+
+pub(crate) fn delete_launch_template(state: &AppState, template_id: String) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(launch_templates::delete_launch_template(&connection, &user.id, template_id.trim())?)
+}
+
+/// Composes a template into the launch options of every target game. Each game is saved
+/// independently, so one failure is reported in its result without aborting the rest.
+pub(crate) fn apply_launch_template(
+	state: &AppState,
+	template_id: String,
+	games: Vec<LaunchTemplateTargetInput>,
+) -> AppResult<Vec<LaunchTemplateApplyResultResponse>> {
+	if games.is_empty() {
+		return Err(AppError::validation("no_games_selected", "Select at least one game."));
+	}
+	if games.len() > LAUNCH_TEMPLATE_MAX_TARGETS {
+		return Err(AppError::validation(
+			"too_many_games",
+			format!("A template can be applied to at most {LAUNCH_TEMPLATE_MAX_TARGETS} games at once"),
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let template = launch_templates::find_launch_template(&connection, &user.id, template_id.trim())?;
+
+	Ok(games
+		.into_iter()
+		.map(|game| {
+			let outcome = apply_launch_template_to_game(state, &connection, &user, &template, &game);
+			LaunchTemplateApplyResultResponse {
+				provider: game.provider,
+				external_id: game.external_id,
+				launch_options: outcome.as_ref().ok().cloned(),
+				error: outcome.err(),
+			}
+		})
+		.collect())
+}
+
+fn apply_launch_template_to_game(
+	state: &AppState,
+	connection: &Connection,
+	user: &UserRow,
+	template: &LaunchTemplateResponse,
+	game: &LaunchTemplateTargetInput,
+) -> AppResult<String> {
+	let (provider, external_id) = normalize_game_identity_input(&game.provider, &game.external_id)?;
+	ensure_owned_game_exists(connection, &user.id, &provider, &external_id)?;
+	let mut settings = load_game_properties_settings(connection, &user.id, &provider, &external_id)?;
+	settings.general.launch_options =
+		launch_templates::compose_launch_options(&settings.general.launch_options, &template.launch_options);
+	game_settings_service::persist_game_properties_settings(
+		state,
+		connection,
+		user,
+		&provider,
+		&external_id,
+		&settings,
+	)?;
+	Ok(settings.general.launch_options)
+}
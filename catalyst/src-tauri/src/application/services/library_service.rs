@@ -1,7 +1,16 @@
 use crate::*;
-use crate::application::error::AppResult;
+use crate::application::error::{AppError, AppResult};
+use crate::application::context::{AuthedContext, AuthedGameContext, require_authenticated_user};
+use crate::backlog_picker::{GameSuggestionResponse, SuggestGameWeights};
+use crate::library_stats::{LibraryStatsResponse, ProviderDiskUsageEntry};
+use crate::play_sessions::{
+	PlaytimeTimelineResponse, PLAYTIME_TIMELINE_GRANULARITY_MONTH, PLAYTIME_TIMELINE_GRANULARITY_WEEK,
+};
 use std::process::Command;
 
+const LIBRARY_STATS_CACHE_TTL_SECS: i64 = 300;
+const BATCH_GAME_UPDATE_MAX_GAMES: usize = 500;
+
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct GameStoreMetadataResponse {
@@ -17,57 +26,973 @@ pub(crate) struct GameStoreMetadataResponse {
 	pub cloud_details: Option<String>,
 	pub controller_support: Option<String>,
 	pub features: Option<Vec<FeatureResponse>>,
+	pub metacritic_score: Option<i64>,
+	pub recommendations_total: Option<i64>,
+	pub review_score_description: Option<String>,
+	pub review_positive_percent: Option<i64>,
+	pub review_total: Option<i64>,
+	pub stale: bool,
+}
+
+/// A Steam Store tag and how many of the user's visible games carry it.
+#[derive(serde::Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamTagCountResponse {
+	pub name: String,
+	pub game_count: usize,
 }
 
 // FeatureResponse is defined in crate root (`lib.rs`) so it can be shared across responses.
 
-pub(crate) fn get_library(state: &AppState) -> AppResult<LibraryResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let games = list_games_by_user(&connection, &user.id)?;
+/// Narrows `get_library` to games matching every given field. Matching ignores case; developer
+/// and publisher match on any part of the name. `completed` is the built-in smart filter for games
+/// with every achievement unlocked. `controller_support` is `full`, or `partial` for games with at
+/// least partial support. `vr` keeps games that support VR headsets, or those that don't.
+/// `steam_tag` matches a Steam Store tag such as those from `list_steam_tags`, and `user_tag` one
+/// of the user's own tags.
+#[derive(serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibraryQueryFilter {
+	pub genre: Option<String>,
+	pub category: Option<String>,
+	pub developer: Option<String>,
+	pub publisher: Option<String>,
+	pub completed: Option<bool>,
+	pub controller_support: Option<String>,
+	pub vr: Option<bool>,
+	pub steam_tag: Option<String>,
+	pub user_tag: Option<String>,
+}
+
+pub(crate) fn game_matches_library_filter(game: &GameResponse, filter: &LibraryQueryFilter) -> bool {
+	let normalized = |value: &Option<String>| {
+		value
+			.as_deref()
+			.map(str::trim)
+			.filter(|value| !value.is_empty())
+			.map(str::to_lowercase)
+	};
+	let matches_exact = |values: &[String], expected: &Option<String>| match normalized(expected) {
+		Some(expected) => values.iter().any(|value| {
+			let value = value.to_lowercase();
+			value == expected || value.replace(' ', "-") == expected
+		}),
+		None => true,
+	};
+	let matches_partial = |values: &[String], expected: &Option<String>| match normalized(expected) {
+		Some(expected) => values.iter().any(|value| value.to_lowercase().contains(&expected)),
+		None => true,
+	};
+
+	matches_exact(&game.genres, &filter.genre)
+		&& matches_exact(&game.categories, &filter.category)
+		&& matches_exact(&game.steam_tags, &filter.steam_tag)
+		&& matches_exact(&game.user_tags, &filter.user_tag)
+		&& matches_partial(&game.developers, &filter.developer)
+		&& matches_partial(&game.publishers, &filter.publisher)
+		&& filter.completed.map_or(true, |completed| game.completed == completed)
+		&& match normalized(&filter.controller_support).as_deref() {
+			Some("full") => game.controller_support.as_deref() == Some("Full"),
+			Some(_) => game.controller_support.is_some(),
+			None => true,
+		}
+		&& filter.vr.map_or(true, |vr| game.vr_support.is_some() == vr)
+}
+
+/// Games the user hid from the library are left out unless `include_hidden` is set, and
+/// non-game kinds such as soundtracks and tools unless the `show_non_game_kinds` setting is on.
+pub(crate) fn get_library(
+	state: &AppState,
+	merged: Option<bool>,
+	filter: Option<LibraryQueryFilter>,
+	include_hidden: Option<bool>,
+) -> AppResult<LibraryResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let mut games = list_visible_library_games(state, &connection, &user.id, include_hidden.unwrap_or(false))?;
+	if let Some(filter) = filter {
+		games.retain(|game| game_matches_library_filter(game, &filter));
+	}
+	process_watcher::mark_running_games(&state.running_games, &mut games);
 
 	// (removed debug log)
 
+	let merged = if merged.unwrap_or(false) {
+		Some(build_merged_library(&connection, &user.id, &games)?)
+	} else {
+		None
+	};
+
 	Ok(LibraryResponse {
 		user_id: user.id,
 		total: games.len(),
 		games,
+		merged,
 	})
 }
 
-pub(crate) fn sync_steam_library(state: &AppState) -> AppResult<SteamSyncResponse> {
+fn list_visible_library_games(
+	state: &AppState,
+	connection: &Connection,
+	user_id: &str,
+	include_hidden: bool,
+) -> AppResult<Vec<GameResponse>> {
+	let mut games = list_games_by_user(connection, user_id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, connection, user_id, &mut games)?;
+	if !include_hidden {
+		games.retain(|game| !game.hide_in_library);
+	}
+	retain_shown_game_kinds(state, &mut games);
+	Ok(games)
+}
+
+/// Leaves out soundtracks, tools and other non-game kinds unless `show_non_game_kinds` is on.
+fn retain_shown_game_kinds(state: &AppState, games: &mut Vec<GameResponse>) {
+	if !state.show_non_game_kinds() {
+		games.retain(|game| !NON_GAME_KINDS.contains(&game.kind.as_str()));
+	}
+}
+
+/// Steam Store tags across the games `get_library` would return, most used first, for browsing
+/// the library by tag.
+pub(crate) fn list_steam_tags(state: &AppState, include_hidden: Option<bool>) -> AppResult<Vec<SteamTagCountResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let games = list_visible_library_games(state, &connection, &user.id, include_hidden.unwrap_or(false))?;
+	Ok(count_steam_tags(&games))
+}
+
+fn count_steam_tags(games: &[GameResponse]) -> Vec<SteamTagCountResponse> {
+	let mut counts: HashMap<&str, usize> = HashMap::new();
+	for game in games {
+		for tag in &game.steam_tags {
+			*counts.entry(tag.as_str()).or_default() += 1;
+		}
+	}
+	let mut tags = counts
+		.into_iter()
+		.map(|(name, game_count)| SteamTagCountResponse { name: name.to_owned(), game_count })
+		.collect::<Vec<_>>();
+	tags.sort_by(|left, right| {
+		right
+			.game_count
+			.cmp(&left.game_count)
+			.then_with(|| left.name.to_lowercase().cmp(&right.name.to_lowercase()))
+	});
+	tags
+}
+
+/// The games the user hid from the library through the privacy settings.
+pub(crate) fn list_hidden_games(state: &AppState) -> AppResult<Vec<GameResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let mut games = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, &connection, &user.id, &mut games)?;
+	games.retain(|game| game.hide_in_library);
+	process_watcher::mark_running_games(&state.running_games, &mut games);
+
+	Ok(games)
+}
+
+fn build_merged_library(
+	connection: &Connection,
+	user_id: &str,
+	games: &[GameResponse],
+) -> Result<Vec<library_merge::MergedGameResponse>, String> {
+	let links = library_merge::load_game_identity_links(connection, user_id)?;
+	let preferences = library_merge::load_game_merge_preferences(connection, user_id)?;
+	Ok(library_merge::merge_library_games(games, &links, &preferences))
+}
+
+pub(crate) fn import_playnite_library(
+	state: &AppState,
+	path: String,
+	dry_run: Option<bool>,
+) -> AppResult<library_import::LibraryImportResponse> {
+	let path = path.trim();
+	if path.is_empty() {
+		return Err(AppError::validation(
+			"import_path_required",
+			"Choose a Playnite library export to import",
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let entries = library_import::read_playnite_library_export(Path::new(path))
+		.map_err(|error| AppError::validation("invalid_import_file", error))?;
+	let response = library_import::import_library_entries(
+		&connection,
+		&user.id,
+		"playnite",
+		&entries,
+		dry_run.unwrap_or(false),
+	)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(response)
+}
+
+/// Imports from the given `pga.db`, or from Lutris' default data directory when no path is given.
+pub(crate) fn import_lutris_library(
+	state: &AppState,
+	path: Option<String>,
+	dry_run: Option<bool>,
+) -> AppResult<library_import::LibraryImportResponse> {
+	let database_path = path
+		.map(|path| path.trim().to_owned())
+		.filter(|path| !path.is_empty())
+		.map(PathBuf::from)
+		.or_else(library_import::default_lutris_database_path)
+		.filter(|path| path.is_file())
+		.ok_or_else(|| AppError::not_found("lutris_library_not_found", "Lutris library database not found"))?;
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let entries = library_import::read_lutris_library(&database_path)
+		.map_err(|error| AppError::validation("invalid_import_file", error))?;
+	let response = library_import::import_library_entries(
+		&connection,
+		&user.id,
+		"lutris",
+		&entries,
+		dry_run.unwrap_or(false),
+	)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(response)
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibraryGameIdentityInput {
+	pub provider: String,
+	pub external_id: String,
+}
+
+/// Normalizes the games of a batch command and drops duplicates, keeping the first occurrence.
+pub(crate) fn normalize_batch_game_identities(games: &[LibraryGameIdentityInput]) -> AppResult<Vec<(String, String)>> {
+	if games.is_empty() {
+		return Err(AppError::validation("games_required", "Select at least one game"));
+	}
+	if games.len() > BATCH_GAME_UPDATE_MAX_GAMES {
+		return Err(AppError::validation(
+			"too_many_games",
+			format!("At most {BATCH_GAME_UPDATE_MAX_GAMES} games can be updated at once"),
+		));
+	}
+	let mut seen = HashSet::new();
+	let mut identities = Vec::with_capacity(games.len());
+	for game in games {
+		let identity = normalize_game_identity_input(&game.provider, &game.external_id)?;
+		if seen.insert(identity.clone()) {
+			identities.push(identity);
+		}
+	}
+	Ok(identities)
+}
+
+/// Marks games as copies of the same title when automatic matching missed them, e.g. because
+/// the storefronts use different names.
+pub(crate) fn link_library_games(
+	state: &AppState,
+	games: Vec<LibraryGameIdentityInput>,
+) -> AppResult<Vec<library_merge::MergedGameResponse>> {
+	if games.len() < 2 {
+		return Err(AppError::validation(
+			"link_requires_two_games",
+			"Select at least two games to link",
+		));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let mut identities = Vec::with_capacity(games.len());
+	for game in &games {
+		let identity = normalize_game_identity_input(&game.provider, &game.external_id)?;
+		ensure_owned_game_exists(&connection, &user.id, &identity.0, &identity.1)?;
+		if identities.iter().any(|(provider, _): &(String, String)| *provider == identity.0) {
+			return Err(AppError::validation(
+				"link_duplicate_provider",
+				"Linked games must come from different providers",
+			));
+		}
+		identities.push(identity);
+	}
+
+	let group_key = format!("link:{}", Uuid::new_v4());
+	library_merge::save_game_identity_links(&connection, &user.id, &identities, Some(&group_key))?;
+	let mut games = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, &connection, &user.id, &mut games)?;
+	Ok(build_merged_library(&connection, &user.id, &games)?)
+}
+
+/// Shows a game on its own again, whether it was linked manually or matched by title.
+pub(crate) fn unlink_library_game(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<Vec<library_merge::MergedGameResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let identity = normalize_game_identity_input(&provider, &external_id)?;
+	ensure_owned_game_exists(&connection, &user.id, &identity.0, &identity.1)?;
+
+	library_merge::save_game_identity_links(&connection, &user.id, &[identity], None)?;
+	let mut games = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, &connection, &user.id, &mut games)?;
+	Ok(build_merged_library(&connection, &user.id, &games)?)
+}
+
+/// Makes the given copy the one launched from the merged entry it belongs to.
+pub(crate) fn set_preferred_launch_provider(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<library_merge::MergedGameResponse> {
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
+
+	let game_id = format!("{normalized_provider}:{normalized_external_id}");
+	let mut games = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, &connection, &user.id, &mut games)?;
+	let group_key = build_merged_library(&connection, &user.id, &games)?
+		.into_iter()
+		.find(|merged| merged.games.iter().any(|game| game.id == game_id))
+		.map(|merged| merged.group_key)
+		.ok_or_else(|| AppError::not_found("game_not_found", "Game not found"))?;
+	library_merge::save_game_merge_preference(&connection, &user.id, &group_key, &normalized_provider)?;
+
+	build_merged_library(&connection, &user.id, &games)?
+		.into_iter()
+		.find(|merged| merged.group_key == group_key)
+		.ok_or_else(|| AppError::not_found("game_not_found", "Game not found"))
+}
+
+pub(crate) fn get_startup_snapshot(state: &AppState) -> AppResult<StartupSnapshotResponse> {
 	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let client = build_http_client()?;
-	let synced_games = sync_steam_games_for_user(
+
+	let Some(session_token) = get_state_session_token(state)? else {
+		return Ok(StartupSnapshotResponse {
+			user: None,
+			library_counts: None,
+			shelves: Vec::new(),
+			downloads: Vec::new(),
+			notifications: Vec::new(),
+		});
+	};
+	let Some(user) = find_user_by_session_token(&connection, &session_token)? else {
+		clear_active_session(state)?;
+		return Ok(StartupSnapshotResponse {
+			user: None,
+			library_counts: None,
+			shelves: Vec::new(),
+			downloads: Vec::new(),
+			notifications: Vec::new(),
+		});
+	};
+
+	let mut games = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, &connection, &user.id, &mut games)?;
+	retain_shown_game_kinds(state, &mut games);
+	process_watcher::mark_running_games(&state.running_games, &mut games);
+	let library_counts = LibraryCountsResponse {
+		total: games.len(),
+		installed: games.iter().filter(|game| game.installed).count(),
+		favorites: games.iter().filter(|game| game.favorite).count(),
+		hidden: games.iter().filter(|game| game.hide_in_library).count(),
+	};
+	let shelves = build_home_shelves(&games);
+
+	let owned_games_by_app_id = load_owned_steam_games_by_app_id(&connection, &user.id)
+		.unwrap_or_else(|error| {
+			eprintln!(
+				"Startup snapshot: could not load owned Steam games ({error}); continuing without ownership map."
+			);
+			HashMap::new()
+		});
+	let downloads = collect_steam_downloads(state, &owned_games_by_app_id);
+	let notifications = notifications::list_notifications(&connection, &user.id, true, None)?;
+
+	Ok(StartupSnapshotResponse {
+		user: Some(public_user_from_row(&user)),
+		library_counts: Some(library_counts),
+		shelves,
+		downloads,
+		notifications,
+	})
+}
+
+/// Everything the home screen renders, built from a single library query plus the Steam
+/// download scan.
+pub(crate) fn get_home_data(state: &AppState) -> AppResult<HomeDataResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let mut games = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, &connection, &user.id, &mut games)?;
+	process_watcher::mark_running_games(&state.running_games, &mut games);
+
+	let recently_played = select_recently_played_games(&games)
+		.into_iter()
+		.take(HOME_SHELF_GAME_LIMIT)
+		.cloned()
+		.collect();
+	let favorites = games
+		.iter()
+		.filter(|game| game.favorite && !game.hide_in_library)
+		.take(HOME_SHELF_GAME_LIMIT)
+		.cloned()
+		.collect();
+	let mut newly_added = games
+		.iter()
+		.filter(|game| !game.hide_in_library && game.added_at.is_some())
+		.collect::<Vec<_>>();
+	newly_added.sort_by(|left, right| right.added_at.cmp(&left.added_at));
+
+	let owned_games_by_app_id = games
+		.iter()
+		.filter(|game| game.provider == "steam")
+		.filter_map(|game| {
+			let app_id = game.external_id.parse::<u64>().ok()?;
+			Some((
+				app_id,
+				OwnedSteamGameMetadata {
+					game_id: game.id.clone(),
+					external_id: game.external_id.clone(),
+					name: game.name.clone(),
+				},
+			))
+		})
+		.collect::<HashMap<_, _>>();
+	let continue_installing = collect_steam_downloads(state, &owned_games_by_app_id);
+
+	Ok(HomeDataResponse {
+		recently_played,
+		continue_installing,
+		favorites,
+		newly_added: newly_added
+			.into_iter()
+			.take(HOME_SHELF_GAME_LIMIT)
+			.cloned()
+			.collect(),
+		library_counts: LibraryCountsResponse {
+			total: games.len(),
+			installed: games.iter().filter(|game| game.installed).count(),
+			favorites: games.iter().filter(|game| game.favorite).count(),
+			hidden: games.iter().filter(|game| game.hide_in_library).count(),
+		},
+	})
+}
+
+/// Returns the authenticated user's most recently played visible games, newest first.
+pub(crate) fn list_recently_played_games(state: &AppState, limit: usize) -> AppResult<Vec<GameResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let mut games = list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?;
+	private_games::retain_unlocked_games(state, &connection, &user.id, &mut games)?;
+	process_watcher::mark_running_games(&state.running_games, &mut games);
+
+	Ok(select_recently_played_games(&games)
+		.into_iter()
+		.take(limit)
+		.cloned()
+		.collect())
+}
+
+fn select_recently_played_games(games: &[GameResponse]) -> Vec<&GameResponse> {
+	let mut recently_played = games
+		.iter()
+		.filter(|game| !game.hide_in_library && game.last_played_at.is_some())
+		.collect::<Vec<_>>();
+	recently_played.sort_by(|left, right| right.last_played_at.cmp(&left.last_played_at));
+	recently_played
+}
+
+fn build_home_shelves(games: &[GameResponse]) -> Vec<HomeShelfResponse> {
+	let visible_games = games
+		.iter()
+		.filter(|game| !game.hide_in_library)
+		.collect::<Vec<_>>();
+
+	let recently_played = select_recently_played_games(games);
+
+	let favorites = visible_games
+		.iter()
+		.filter(|game| game.favorite)
+		.copied()
+		.collect::<Vec<_>>();
+
+	let mut shelves = Vec::new();
+	for (id, title, shelf_games) in [
+		("recently-played", "Recently Played", recently_played),
+		("favorites", "Favorites", favorites),
+	] {
+		if shelf_games.is_empty() {
+			continue;
+		}
+		shelves.push(HomeShelfResponse {
+			id: id.to_owned(),
+			title: title.to_owned(),
+			games: shelf_games
+				.into_iter()
+				.take(HOME_SHELF_GAME_LIMIT)
+				.cloned()
+				.collect(),
+		});
+	}
+
+	shelves
+}
+
+/// Marks a Steam sync as running for as long as it is alive. A cancel request only applies to
+/// the run it was made during, so the cancel flag is cleared as that run ends.
+struct SteamSyncRun<'a>(&'a AppState);
+
+impl<'a> SteamSyncRun<'a> {
+	fn start(state: &'a AppState) -> AppResult<Self> {
+		if state.steam_sync_running.swap(true, Ordering::SeqCst) {
+			return Err(AppError::conflict(
+				"steam_sync_in_progress",
+				"A Steam library sync is already running.",
+			));
+		}
+		Ok(Self(state))
+	}
+}
+
+impl Drop for SteamSyncRun<'_> {
+	fn drop(&mut self) {
+		self.0.steam_sync_cancel_requested.store(false, Ordering::SeqCst);
+		self.0.steam_sync_running.store(false, Ordering::SeqCst);
+	}
+}
+
+pub(crate) fn sync_steam_library<F>(
+	state: &AppState,
+	full: Option<bool>,
+	on_progress: F,
+) -> AppResult<SteamSyncResponse>
+where
+	F: Fn(SteamSyncProgressPayload),
+{
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let client = build_http_client(state.http_proxy().as_ref())?;
+	let _run = SteamSyncRun::start(state)?;
+	// Offline syncs skip the Steam Web API and only refresh local install flags.
+	let steam_api_key = state.steam_api_key().filter(|_| !state.is_offline());
+	let sync_result = sync_steam_games_for_user(
 		&connection,
 		&user,
-		state.steam_api_key.as_deref(),
-		state.steam_local_install_detection,
-		state.steam_root_override.as_deref(),
+		steam_api_key.as_deref(),
+		state.steam_local_install_detection(),
+		state.steam_root_override().as_deref(),
 		&client,
-	)?;
+		!full.unwrap_or(false),
+		&on_progress,
+		&state.steam_sync_cancel_requested,
+	);
+	// Even a failed sync may have replaced genres and Store tags before stopping.
+	state.library_lookup_cache.clear();
+	let summary = sync_result.map_err(|error| {
+		if state.steam_sync_cancel_requested.load(Ordering::SeqCst) {
+			AppError::conflict("steam_sync_cancelled", error)
+		} else {
+			record_sync_notification(
+				&connection,
+				&user.id,
+				notifications::NOTIFICATION_KIND_SYNC_FAILED,
+				"Steam sync failed",
+				&error,
+			);
+			AppError::from(error)
+		}
+	})?;
+
+	let warning = summary.profile_private.then(|| String::from(STEAM_PROFILE_PRIVATE_MESSAGE));
+	if let Some(warning) = warning.as_deref() {
+		record_sync_notification(
+			&connection,
+			&user.id,
+			notifications::NOTIFICATION_KIND_SYNC_FAILED,
+			"Steam library is private",
+			warning,
+		);
+	}
+
+	if summary.added > 0 {
+		let noun = if summary.added == 1 { "game" } else { "games" };
+		record_sync_notification(
+			&connection,
+			&user.id,
+			notifications::NOTIFICATION_KIND_SYNC_COMPLETED,
+			"Steam library synced",
+			&format!("{} new {noun} added to your library.", summary.added),
+		);
+	}
 
 	Ok(SteamSyncResponse {
 		user_id: user.id,
 		provider: String::from("steam"),
-		synced_games,
+		synced_games: summary.total,
+		added_games: summary.added,
+		updated_games: summary.updated,
+		removed_games: summary.removed,
+		warning,
 	})
 }
 
+/// Refreshes achievement progress for one Steam game, or for every played Steam game when no game
+/// is given, and recalculates which games count as completed.
+pub(crate) fn refresh_achievements(
+	state: &AppState,
+	provider: Option<String>,
+	external_id: Option<String>,
+) -> AppResult<achievements::AchievementsRefreshResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let steam_id = user
+		.steam_id
+		.as_deref()
+		.ok_or_else(|| AppError::validation("steam_not_linked", "Link a Steam account to refresh achievements."))?;
+	let api_key = state
+		.steam_api_key()
+		.filter(|_| !state.is_offline())
+		.ok_or_else(|| AppError::validation("steam_api_key_missing", "Set a Steam Web API key to refresh achievements."))?;
+
+	let app_ids = match (provider.as_deref(), external_id.as_deref()) {
+		(None, None) => list_games_by_user(&connection, &user.id, &state.library_lookup_cache)?
+			.into_iter()
+			// Achievements can only be unlocked by playing, so unplayed games are skipped.
+			.filter(|game| game.provider == "steam" && game.playtime_minutes > 0)
+			.map(|game| game.external_id)
+			.collect::<Vec<_>>(),
+		(Some(provider), Some(external_id)) => {
+			let (provider, external_id) = normalize_game_identity_input(provider, external_id)?;
+			ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+			if provider != "steam" {
+				return Err(AppError::validation(
+					"unsupported_provider",
+					"Achievements are only available for Steam games.",
+				));
+			}
+			vec![external_id]
+		}
+		_ => {
+			return Err(AppError::validation(
+				"missing_identity_pair",
+				"provider and external_id must be supplied together",
+			))
+		}
+	};
+
+	let client = build_http_client(state.http_proxy().as_ref())?;
+	Ok(achievements::refresh_steam_achievements(
+		&connection,
+		&client,
+		&api_key,
+		&user.id,
+		steam_id,
+		&app_ids,
+	)?)
+}
+
+/// Steam apps whose manifest shows up in more than one library folder, with the copy Steam uses
+/// marked active.
+pub(crate) fn get_library_conflicts(
+	state: &AppState,
+) -> AppResult<Vec<steam_library_conflicts::SteamLibraryConflictResponse>> {
+	require_authenticated_user(state)?;
+	Ok(steam_library_conflicts::collect_steam_library_conflicts(
+		state.steam_root_override().as_deref(),
+	))
+}
+
+/// Locked private games are left out, as in `get_library`; hidden games are still found.
+pub(crate) fn search_library(
+	state: &AppState,
+	query: String,
+	limit: Option<usize>,
+) -> AppResult<Vec<library_search::LibrarySearchMatch>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let matches = library_search::search_library_games(&connection, &user.id, &query, limit)?;
+	let mut visible_matches = Vec::with_capacity(matches.len());
+	for search_match in matches {
+		if !private_games::is_locked_private_game(
+			state,
+			&connection,
+			&user.id,
+			&search_match.provider,
+			&search_match.external_id,
+		)? {
+			visible_matches.push(search_match);
+		}
+	}
+	Ok(visible_matches)
+}
+
+pub(crate) fn get_game_notes(state: &AppState, provider: String, external_id: String) -> AppResult<String> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	Ok(library_search::load_game_notes(&connection, &user.id, &provider, &external_id)?)
+}
+
+pub(crate) fn save_game_notes(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	notes: String,
+) -> AppResult<String> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	library_search::save_game_notes(&connection, &user.id, &provider, &external_id, &notes)
+		.map_err(|error| AppError::validation("invalid_game_notes", error))
+}
+
+/// Sets the title the library shows, sorts and searches by; blank restores the synced name.
+pub(crate) fn set_game_display_name(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	display_name: Option<String>,
+) -> AppResult<Option<String>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	save_game_display_name(&connection, &user.id, &provider, &external_id, display_name.as_deref())
+		.map_err(|error| AppError::validation("invalid_display_name", error))
+}
+
+/// Adds a personal tag to the game and returns all of its tags.
+pub(crate) fn add_game_user_tag(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	tag: String,
+) -> AppResult<Vec<String>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let tags = game_user_tags::add_game_user_tag(&connection, &user.id, &provider, &external_id, &tag)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(tags)
+}
+
+pub(crate) fn remove_game_user_tag(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	tag: String,
+) -> AppResult<Vec<String>> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	let tags = game_user_tags::remove_game_user_tag(&connection, &user.id, &provider, &external_id, &tag)?;
+	state.library_lookup_cache.invalidate_user(&user.id);
+	Ok(tags)
+}
+
+/// Sync notifications are best-effort; failing to store one never fails the sync itself.
+fn record_sync_notification(connection: &Connection, user_id: &str, kind: &str, title: &str, body: &str) {
+	if let Err(error) = notifications::create_notification(connection, user_id, kind, title, body, None) {
+		eprintln!("Failed to record sync notification: {error}");
+	}
+}
+
+/// Refreshes EA App and Ubisoft Connect games from their local install data. These providers
+/// have no account sync, so only installed titles are discovered.
+pub(crate) fn sync_local_store_libraries(state: &AppState) -> AppResult<Vec<LocalProviderSyncResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+
+	let detected_by_provider = [
+		("ea", crate::store_providers::detect_ea_installed_games()?),
+		("ubisoft", crate::store_providers::detect_ubisoft_installed_games()?),
+	];
+	let mut responses = Vec::new();
+	for (provider, detected_games) in detected_by_provider {
+		let summary = sync_local_provider_games(&connection, &user.id, provider, &detected_games)?;
+		responses.push(LocalProviderSyncResponse {
+			provider: provider.to_owned(),
+			synced_games: summary.total,
+			added_games: summary.added,
+			updated_games: summary.updated,
+			uninstalled_games: summary.removed,
+		});
+	}
+
+	Ok(responses)
+}
+
+/// Aggregates capacity, free space and installed-game usage for every Steam library folder,
+/// the folders holding EA and Ubisoft installs, and the configured ROM directories.
+pub(crate) fn get_storage_overview(state: &AppState) -> AppResult<StorageOverviewResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+
+	let mut libraries = collect_steam_library_storage(state.steam_root_override().as_deref());
+
+	let detected_by_provider = [
+		("ea", crate::store_providers::detect_ea_installed_games()?),
+		("ubisoft", crate::store_providers::detect_ubisoft_installed_games()?),
+	];
+	for (provider, detected_games) in detected_by_provider {
+		let mut usage_by_root: Vec<(PathBuf, u64, usize)> = Vec::new();
+		for install_path in detected_games.iter().filter_map(|game| game.install_path.as_ref()) {
+			let install_root = install_path
+				.parent()
+				.map(Path::to_path_buf)
+				.unwrap_or_else(|| install_path.clone());
+			let install_bytes = measure_directory_size_bytes(install_path).unwrap_or(0);
+			match usage_by_root.iter_mut().find(|(root, _, _)| *root == install_root) {
+				Some((_, used_bytes, game_count)) => {
+					*used_bytes = used_bytes.saturating_add(install_bytes);
+					*game_count += 1;
+				}
+				None => usage_by_root.push((install_root, install_bytes, 1)),
+			}
+		}
+
+		for (install_root, games_bytes, game_count) in usage_by_root {
+			let disk_space = crate::disk_space::detect_disk_space(&install_root);
+			libraries.push(StorageLibraryResponse {
+				provider: provider.to_owned(),
+				path: install_root.display().to_string(),
+				total_bytes: disk_space.map(|value| value.total_bytes),
+				free_bytes: disk_space.map(|value| value.available_bytes),
+				games_bytes,
+				game_count,
+			});
+		}
+	}
+
+	for rom_directory in crate::rom_library::list_rom_directories(&connection, &user.id)? {
+		let (games_bytes, game_count) =
+			crate::rom_library::summarize_rom_directory_usage(&connection, &user.id, &rom_directory.path)?;
+		let disk_space = crate::disk_space::detect_disk_space(Path::new(&rom_directory.path));
+		libraries.push(StorageLibraryResponse {
+			provider: String::from("rom"),
+			path: rom_directory.path,
+			total_bytes: disk_space.map(|value| value.total_bytes),
+			free_bytes: disk_space.map(|value| value.available_bytes),
+			games_bytes,
+			game_count,
+		});
+	}
+
+	Ok(StorageOverviewResponse {
+		total_games_bytes: libraries.iter().map(|library| library.games_bytes).sum(),
+		libraries,
+	})
+}
+
+/// Playtime bucketed by `week` or `month` (the default) and by game, optionally limited to one
+/// year, for the year-in-review view.
+pub(crate) fn get_playtime_timeline(
+	state: &AppState,
+	granularity: Option<String>,
+	year: Option<i32>,
+) -> AppResult<PlaytimeTimelineResponse> {
+	let granularity = granularity
+		.map(|value| value.trim().to_ascii_lowercase())
+		.filter(|value| !value.is_empty())
+		.unwrap_or_else(|| String::from(PLAYTIME_TIMELINE_GRANULARITY_MONTH));
+	if granularity != PLAYTIME_TIMELINE_GRANULARITY_WEEK && granularity != PLAYTIME_TIMELINE_GRANULARITY_MONTH {
+		return Err(AppError::validation(
+			"invalid_timeline_granularity",
+			"Timeline granularity must be 'week' or 'month'",
+		));
+	}
+	if year.is_some_and(|year| !(1970..=9999).contains(&year)) {
+		return Err(AppError::validation("invalid_timeline_year", "Timeline year is out of range"));
+	}
+
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let exclude_private = !private_games::private_games_lock_status(state, &connection, &user.id)?.unlocked;
+
+	Ok(crate::play_sessions::compute_playtime_timeline(
+		&connection,
+		&user.id,
+		&granularity,
+		year,
+		exclude_private,
+	)?)
+}
+
+/// Picks a few games from the backlog at random, favoring short, well reviewed and installed
+/// games and ones tagged with any of `moods`. Locked private games are never suggested.
+pub(crate) fn suggest_game(
+	state: &AppState,
+	weights: Option<SuggestGameWeights>,
+	moods: Option<Vec<String>>,
+	count: Option<usize>,
+) -> AppResult<Vec<GameSuggestionResponse>> {
+	let weights = crate::backlog_picker::normalize_suggest_game_weights(weights.unwrap_or_default())
+		.map_err(|error| AppError::validation("invalid_suggestion_weights", error))?;
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let exclude_private = !private_games::private_games_lock_status(state, &connection, &user.id)?.unlocked;
+
+	Ok(crate::backlog_picker::suggest_backlog_games(
+		&connection,
+		&user.id,
+		&weights,
+		&moods.unwrap_or_default(),
+		count,
+		exclude_private,
+	)?)
+}
+
+/// Library totals and playtime breakdowns for the stats dashboard. Results are cached for a
+/// few minutes because the disk usage part walks install folders; `refresh` skips the cache.
+pub(crate) fn get_library_stats(state: &AppState, refresh: Option<bool>) -> AppResult<LibraryStatsResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+
+	// Locked and unlocked stats are cached apart so relocking never serves private games.
+	let exclude_private = !private_games::private_games_lock_status(state, &connection, &user.id)?.unlocked;
+	let cache_key = format!("library_stats:{}:{}", user.id, if exclude_private { "locked" } else { "unlocked" });
+	if !refresh.unwrap_or(false) {
+		let cached_stats = crate::cache::get_cached(&cache_key, LIBRARY_STATS_CACHE_TTL_SECS)
+			.and_then(|value| serde_json::from_value::<LibraryStatsResponse>(value).ok());
+		if let Some(stats) = cached_stats {
+			return Ok(stats);
+		}
+	}
+
+	let mut disk_usage_by_provider: Vec<ProviderDiskUsageEntry> = Vec::new();
+	match get_storage_overview(state) {
+		Ok(overview) => {
+			for library in overview.libraries {
+				match disk_usage_by_provider.iter_mut().find(|entry| entry.provider == library.provider) {
+					Some(entry) => {
+						entry.games_bytes = entry.games_bytes.saturating_add(library.games_bytes);
+						entry.game_count += library.game_count;
+					}
+					None => disk_usage_by_provider.push(ProviderDiskUsageEntry {
+						provider: library.provider,
+						games_bytes: library.games_bytes,
+						game_count: library.game_count,
+					}),
+				}
+			}
+		}
+		Err(error) => eprintln!("Failed to measure disk usage for library stats: {}", error.message),
+	}
+
+	let stats = crate::library_stats::compute_library_stats(
+		&connection,
+		&user.id,
+		disk_usage_by_provider,
+		exclude_private,
+	)?;
+	if let Ok(value) = serde_json::to_value(&stats) {
+		crate::cache::set_cached(&cache_key, value);
+	}
+	Ok(stats)
+}
+
+/// Asks the running Steam sync to stop. Without a running sync there is nothing to cancel, so
+/// the request is dropped instead of cancelling the next sync.
+pub(crate) fn cancel_steam_sync(state: &AppState) -> AppResult<()> {
+	if state.steam_sync_running.load(Ordering::SeqCst) {
+		state.steam_sync_cancel_requested.store(true, Ordering::SeqCst);
+	}
+	Ok(())
+}
+
 pub(crate) fn set_game_favorite(
 	state: &AppState,
 	provider: String,
 	external_id: String,
 	favorite: bool,
 ) -> AppResult<()> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (provider, external_id) = normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(&connection, &user.id, &provider, &external_id)?;
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if favorite {
 		upsert_game_favorite(&connection, &user.id, &provider, &external_id)?;
@@ -78,48 +1003,223 @@ pub(crate) fn set_game_favorite(
 	Ok(())
 }
 
+/// Favorites or unfavorites many games in one transaction. Returns the number of games updated.
+pub(crate) fn set_games_favorite(
+	state: &AppState,
+	games: Vec<LibraryGameIdentityInput>,
+	favorite: bool,
+) -> AppResult<usize> {
+	let games = normalize_batch_game_identities(&games)?;
+	let AuthedContext { mut connection, user } = AuthedContext::new(state)?;
+	ensure_owned_games_exist(&connection, &user.id, &games)?;
+
+	let transaction = connection
+		.transaction()
+		.map_err(|error| format!("Failed to start favorites transaction: {error}"))?;
+	for (provider, external_id) in &games {
+		if favorite {
+			upsert_game_favorite(&transaction, &user.id, provider, external_id)?;
+		} else {
+			remove_game_favorite(&transaction, &user.id, provider, external_id)?;
+		}
+	}
+	transaction
+		.commit()
+		.map_err(|error| format!("Failed to commit favorites: {error}"))?;
+
+	Ok(games.len())
+}
+
 pub(crate) fn list_steam_downloads(state: &AppState) -> AppResult<Vec<SteamDownloadProgressResponse>> {
-	let owned_games_by_app_id = match open_connection(&state.db_path) {
-		Ok(connection) => {
-			if let Err(error) = cleanup_expired_sessions(&connection) {
+	let owned_games_by_app_id = match AuthedContext::new(state) {
+		Ok(AuthedContext { connection, user }) => match load_owned_steam_games_by_app_id(&connection, &user.id) {
+			Ok(games) => games,
+			Err(error) => {
 				eprintln!(
-					"Steam download tracking: failed to cleanup expired sessions ({error}); continuing without ownership map."
+					"Steam download tracking: could not load owned Steam games ({error}); continuing without ownership map."
 				);
 				HashMap::new()
-			} else {
-				match get_authenticated_user(state, &connection) {
-					Ok(user) => match load_owned_steam_games_by_app_id(&connection, &user.id) {
-						Ok(games) => games,
-						Err(error) => {
-							eprintln!(
-								"Steam download tracking: could not load owned Steam games ({error}); continuing without ownership map."
-							);
-							HashMap::new()
-						}
-					},
-					Err(error) => {
-						eprintln!(
-							"Steam download tracking: could not resolve authenticated user metadata ({error}); continuing without ownership map."
-						);
-						HashMap::new()
-					}
-				}
 			}
-		}
+		},
 		Err(error) => {
 			eprintln!(
-				"Steam download tracking: could not open app database ({error}); continuing without ownership map."
+				"Steam download tracking: could not resolve authenticated user metadata ({}); continuing without ownership map.",
+				error.message
 			);
 			HashMap::new()
 		}
 	};
 
-	let steam_roots = resolve_steam_root_paths(state.steam_root_override.as_deref());
+	// The manifest watcher knows when nothing is transferring, which spares reading every manifest.
+	let mut downloads = if state.steam_manifest_index.has_active_downloads() == Some(false) {
+		Vec::new()
+	} else {
+		collect_steam_downloads(state, &owned_games_by_app_id)
+	};
+	if let Err(error) = apply_download_queue_state(state, &owned_games_by_app_id, &mut downloads) {
+		eprintln!("Steam download tracking: could not load the download queue ({}).", error.message);
+	}
+	Ok(downloads)
+}
+
+/// Marks downloads paused through Catalyst, lists paused ones Steam no longer reports as
+/// transferring, and orders the result by the saved queue. Pauses for apps that have since
+/// finished installing are dropped.
+fn apply_download_queue_state(
+	state: &AppState,
+	owned_games_by_app_id: &HashMap<u64, OwnedSteamGameMetadata>,
+	downloads: &mut Vec<SteamDownloadProgressResponse>,
+) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let queue = download_queue::load_download_queue(&connection, &user.id)?;
+	for download in downloads.iter_mut() {
+		if let Some(queue_state) = queue.get(&download.external_id) {
+			download.paused = queue_state.paused;
+			download.queue_position = queue_state.queue_position;
+			if queue_state.paused {
+				download.state = String::from("Paused");
+			}
+		}
+	}
+
+	let steam_root_override = state.steam_root_override();
+	for (app_id, queue_state) in &queue {
+		if !queue_state.paused || downloads.iter().any(|download| &download.external_id == app_id) {
+			continue;
+		}
+		let Ok(numeric_app_id) = app_id.parse::<u64>() else {
+			continue;
+		};
+		if download_queue::is_steam_app_fully_installed(steam_root_override.as_deref(), numeric_app_id) {
+			download_queue::set_download_paused(&connection, &user.id, app_id, false, false)?;
+			continue;
+		}
+		let game_metadata = owned_games_by_app_id.get(&numeric_app_id);
+		downloads.push(SteamDownloadProgressResponse {
+			game_id: game_metadata
+				.map(|game| game.game_id.clone())
+				.unwrap_or_else(|| format!("steam:{app_id}")),
+			provider: String::from("steam"),
+			external_id: app_id.clone(),
+			name: game_metadata
+				.map(|game| game.name.clone())
+				.unwrap_or_else(|| format!("Steam App {app_id}")),
+			state: String::from("Paused"),
+			bytes_downloaded: None,
+			bytes_total: None,
+			progress_percent: None,
+			progress_source: None,
+			paused: true,
+			queue_position: queue_state.queue_position,
+		});
+	}
+
+	downloads.sort_by_cached_key(|download| {
+		(
+			download.queue_position.unwrap_or(usize::MAX),
+			download.name.to_ascii_lowercase(),
+		)
+	});
+	Ok(())
+}
+
+fn parse_steam_download_app_id(external_id: &str) -> AppResult<u64> {
+	external_id
+		.trim()
+		.parse::<u64>()
+		.map_err(|_| AppError::validation("invalid_app_id", "Steam external_id must be a numeric app ID"))
+}
+
+/// Pauses a Steam download. The pause is kept in the download queue so it survives restarts
+/// and `list_steam_downloads` keeps showing the download.
+pub(crate) fn pause_download(state: &AppState, external_id: String) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let app_id = parse_steam_download_app_id(&external_id)?;
+	download_queue::set_download_paused(&connection, &user.id, &app_id.to_string(), true, false)?;
+	download_queue::open_steam_downloads_page()
+		.map_err(|error| AppError::external("steam_launch_failed", error))
+}
+
+pub(crate) fn resume_download(state: &AppState, external_id: String) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let app_id = parse_steam_download_app_id(&external_id)?;
+	download_queue::set_download_paused(&connection, &user.id, &app_id.to_string(), false, false)?;
+	download_queue::resume_steam_download(app_id)
+		.map_err(|error| AppError::external("steam_launch_failed", error))
+}
+
+/// Saves the download order and asks Steam to resume the first unpaused download, which moves
+/// it to the front of Steam's own queue.
+pub(crate) fn reorder_download_queue(
+	state: &AppState,
+	external_ids: Vec<String>,
+) -> AppResult<Vec<SteamDownloadProgressResponse>> {
+	let AuthedContext { mut connection, user } = AuthedContext::new(state)?;
+	let mut app_ids = Vec::with_capacity(external_ids.len());
+	for external_id in &external_ids {
+		let app_id = parse_steam_download_app_id(external_id)?.to_string();
+		if app_ids.contains(&app_id) {
+			return Err(AppError::validation(
+				"duplicate_download",
+				format!("Download {app_id} is listed more than once"),
+			));
+		}
+		app_ids.push(app_id);
+	}
+	download_queue::save_download_queue_order(&mut connection, &user.id, &app_ids)?;
+
+	let queue = download_queue::load_download_queue(&connection, &user.id)?;
+	if let Some(first_app_id) = app_ids
+		.iter()
+		.find(|app_id| !queue.get(*app_id).is_some_and(|queue_state| queue_state.paused))
+		.and_then(|app_id| app_id.parse::<u64>().ok())
+	{
+		download_queue::resume_steam_download(first_app_id)
+			.map_err(|error| AppError::external("steam_launch_failed", error))?;
+	}
+	list_steam_downloads(state)
+}
+
+/// Steam downloads on this machine, newest first, with per-month totals for metered connections.
+/// Steam's content logs are read on every call; without a Steam install only the recorded
+/// history is returned.
+pub(crate) fn get_download_history(
+	state: &AppState,
+	limit: Option<usize>,
+) -> AppResult<download_history::DownloadHistoryResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	if let Some(steam_root) = resolve_steam_root_path(state.steam_root_override().as_deref()) {
+		if let Err(error) = download_history::import_steam_content_logs(&connection, &steam_root) {
+			eprintln!("Could not import Steam download history: {error}");
+		}
+	}
+	Ok(download_history::load_download_history(&connection, &user.id, limit)?)
+}
+
+pub(crate) fn get_download_rate_limit(state: &AppState) -> AppResult<download_queue::DownloadRateLimitResponse> {
+	Ok(download_queue::read_download_rate_limit(state.steam_root_override().as_deref())?)
+}
+
+pub(crate) fn set_download_rate_limit(
+	state: &AppState,
+	kbps: Option<u64>,
+) -> AppResult<download_queue::DownloadRateLimitResponse> {
+	require_authenticated_user(state)?;
+	Ok(download_queue::write_download_rate_limit(state.steam_root_override().as_deref(), kbps)?)
+}
+
+fn collect_steam_downloads(
+	state: &AppState,
+	owned_games_by_app_id: &HashMap<u64, OwnedSteamGameMetadata>,
+) -> Vec<SteamDownloadProgressResponse> {
+	let steam_roots = resolve_steam_root_paths(state.steam_root_override().as_deref());
 	if steam_roots.is_empty() {
-		return Ok(Vec::new());
+		return Vec::new();
 	}
 	let mut downloads = Vec::new();
 	let mut seen_external_ids = HashSet::new();
+	let skipped_manifest_paths =
+		steam_library_conflicts::collect_inactive_duplicate_manifest_paths(state.steam_root_override().as_deref());
 
 	for steam_root in steam_roots {
 		let steamapps_directories = match resolve_steamapps_directories(&steam_root) {
@@ -136,8 +1236,9 @@ pub(crate) fn list_steam_downloads(state: &AppState) -> AppResult<Vec<SteamDownl
 		for steamapps_directory in steamapps_directories {
 			if let Err(error) = collect_steam_download_progress_from_steamapps_dir(
 				&steamapps_directory,
-				&owned_games_by_app_id,
+				owned_games_by_app_id,
 				&mut seen_external_ids,
+				&skipped_manifest_paths,
 				&mut downloads,
 			) {
 				eprintln!(
@@ -154,7 +1255,7 @@ pub(crate) fn list_steam_downloads(state: &AppState) -> AppResult<Vec<SteamDownl
 			.to_ascii_lowercase()
 			.cmp(&right.name.to_ascii_lowercase())
 	});
-	Ok(downloads)
+	downloads
 }
 
 pub(crate) fn get_game_store_metadata(
@@ -162,17 +1263,12 @@ pub(crate) fn get_game_store_metadata(
 	provider: String,
 	external_id: String,
 ) -> AppResult<GameStoreMetadataResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	// Only Steam is supported for rich store metadata at the moment
 	if normalized_provider != "steam" {
@@ -189,6 +1285,12 @@ pub(crate) fn get_game_store_metadata(
 			cloud_details: None,
 			controller_support: None,
 			features: None,
+			metacritic_score: None,
+			recommendations_total: None,
+			review_score_description: None,
+			review_positive_percent: None,
+			review_total: None,
+			stale: false,
 		});
 	}
 
@@ -208,11 +1310,23 @@ pub(crate) fn get_game_store_metadata(
 				cloud_details: None,
 				controller_support: None,
 				features: None,
+				metacritic_score: None,
+				recommendations_total: None,
+				review_score_description: None,
+				review_positive_percent: None,
+				review_total: None,
+				stale: false,
 			})
 		}
 	};
 
-	let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_DETAILS_CACHE_TTL_HOURS);
+	let fresh_after = Utc::now() - ChronoDuration::hours(STEAM_APP_DETAILS_CACHE_TTL_HOURS);
+	// Offline mode serves whatever is cached, however old, and flags it as stale.
+	let stale_before = if state.is_offline() {
+		chrono::DateTime::<Utc>::MIN_UTC
+	} else {
+		fresh_after
+	};
 
 	let mut response = GameStoreMetadataResponse {
 		developers: None,
@@ -227,12 +1341,23 @@ pub(crate) fn get_game_store_metadata(
 		cloud_details: None,
 		controller_support: None,
 		features: None,
+		metacritic_score: None,
+		recommendations_total: None,
+		review_score_description: None,
+		review_positive_percent: None,
+		review_total: None,
+		stale: false,
 	};
 
 	// Keep a reference to parsed store data (if available) to build normalized feature list later.
 	let mut maybe_data: Option<serde_json::Value> = None;
 
 	if let Ok(Some(cached)) = find_cached_steam_app_details(&connection, app_id, stale_before) {
+		response.stale = state.is_offline()
+			&& !matches!(
+				find_cached_steam_app_details(&connection, app_id, fresh_after),
+				Ok(Some(_))
+			);
 		if let Some(data) = cached.get("data") {
 
 			// capture parsed data for normalized feature building
@@ -290,7 +1415,7 @@ pub(crate) fn get_game_store_metadata(
 	}
 
 	// If no cached details were found, attempt a best-effort live fetch from the Steam Store
-	if response.short_description.is_none() || response.developers.is_none() {
+	if !state.is_offline() && (response.short_description.is_none() || response.developers.is_none()) {
 		// Prefer using steamcmd if available for an exact client-style appinfo
 		if let Ok(output) = Command::new("bash").arg("-lc").arg(format!("steamcmd +login anonymous +app_info_print {} +quit", app_id)).output() {
 			if output.status.success() {
@@ -362,7 +1487,7 @@ pub(crate) fn get_game_store_metadata(
 				}
 			}
 		}
-		if let Ok(client) = crate::build_http_client() {
+		if let Ok(client) = crate::build_http_client(state.http_proxy().as_ref()) {
 			// If steamcmd already provided useful fields, skip the HTTP store fetch.
 			if response.short_description.is_some() || response.developers.is_some() || response.publishers.is_some() {
 				// skip HTTP fetch: we prefer steamcmd results when present
@@ -373,7 +1498,7 @@ pub(crate) fn get_game_store_metadata(
 				};
 				// append query
 				request_url.query_pairs_mut().append_pair("appids", &app_id.to_string()).append_pair("l", "english");
-				if let Ok(resp) = client.get(request_url).send() {
+				if let Ok(resp) = crate::send_steam_get_request(&client, request_url) {
 					if resp.status().is_success() {
 						if let Ok(payload) = resp.json::<serde_json::Value>() {
 							if let Some(entry) = payload.get(&app_id.to_string()) {
@@ -588,6 +1713,447 @@ pub(crate) fn get_game_store_metadata(
 		}
 	}
 
+	if let Some(ref data) = maybe_data {
+		response.metacritic_score = data
+			.get("metacritic")
+			.and_then(|value| value.get("score"))
+			.and_then(serde_json::Value::as_i64);
+		response.recommendations_total = data
+			.get("recommendations")
+			.and_then(|value| value.get("total"))
+			.and_then(serde_json::Value::as_i64);
+	}
+
+	let mut review_summary = find_cached_steam_review_summary(&connection, app_id, stale_before)?;
+	if review_summary.is_none() && !state.is_offline() {
+		let client = state.http_client()?;
+		match fetch_steam_review_summary(&client, app_id) {
+			Ok(Some(summary)) => {
+				// Best-effort cache so the library can sort by it too.
+				let _ = cache_steam_review_summary(&connection, app_id, &summary);
+				review_summary = Some(summary);
+			}
+			Ok(None) => {}
+			Err(error) => eprintln!("Could not fetch Steam review summary for app {app_id}: {error}"),
+		}
+	}
+	if let Some(summary) = review_summary {
+		response.review_positive_percent = summary.positive_percent();
+		response.review_total = Some(summary.total_reviews);
+		response.review_score_description = Some(summary.score_description);
+	}
+
 	Ok(response)
 }
 
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_startup_snapshot_includes_unread_notifications() {
+		let database = test_support::test_database();
+		let (connection, user) = (&database.connection, &database.user);
+		assert!(get_startup_snapshot(&database.app_state()).expect("signed-out snapshot").notifications.is_empty());
+
+		let read = notifications::create_notification(
+			connection,
+			&user.id,
+			notifications::NOTIFICATION_KIND_DOWNLOAD_FINISHED,
+			"Download finished",
+			"Portal is ready to play.",
+			Some(("steam", "400")),
+		)
+		.expect("create notification");
+		notifications::mark_notifications_read(connection, &user.id, Some(&read.id)).expect("mark read");
+		let unread = notifications::create_notification(
+			connection,
+			&user.id,
+			notifications::NOTIFICATION_KIND_DOWNLOAD_FINISHED,
+			"Download finished",
+			"Portal 2 is ready to play.",
+			Some(("steam", "620")),
+		)
+		.expect("create notification");
+		let state = database.signed_in_state_with_steam_root();
+
+		let snapshot = get_startup_snapshot(&state).expect("snapshot");
+
+		assert_eq!(
+			snapshot.notifications.iter().map(|notification| notification.id.as_str()).collect::<Vec<_>>(),
+			vec![unread.id.as_str()]
+		);
+	}
+
+	#[test]
+	fn sync_steam_library_runs_one_sync_at_a_time_and_drops_stale_cancels() {
+		let database = test_support::test_database();
+		let state = database.signed_in_state();
+
+		cancel_steam_sync(&state).expect("cancel without a sync");
+		assert!(!state.steam_sync_cancel_requested.load(Ordering::SeqCst));
+
+		state.steam_sync_running.store(true, Ordering::SeqCst);
+		let error = sync_steam_library(&state, None, |_| {}).expect_err("second sync");
+		assert_eq!(error.code, "steam_sync_in_progress");
+		cancel_steam_sync(&state).expect("cancel the running sync");
+		assert!(state.steam_sync_cancel_requested.load(Ordering::SeqCst));
+		state.steam_sync_running.store(false, Ordering::SeqCst);
+
+		// The fixture user isn't linked to Steam, so the run fails, but it still ends cleanly.
+		let error = sync_steam_library(&state, None, |_| {}).expect_err("unlinked sync");
+		assert_ne!(error.code, "steam_sync_in_progress");
+		assert!(!state.steam_sync_running.load(Ordering::SeqCst));
+		assert!(!state.steam_sync_cancel_requested.load(Ordering::SeqCst));
+	}
+
+	#[test]
+	fn get_library_stats_serves_cached_numbers_until_refreshed() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let state = database.signed_in_state_with_steam_root();
+
+		let stats = get_library_stats(&state, None).expect("stats");
+		assert_eq!(stats.total_games, 1);
+		database.insert_game("steam", "400", "Portal");
+
+		let cached = get_library_stats(&state, None).expect("cached stats");
+		assert_eq!((cached.total_games, cached.generated_at.as_str()), (1, stats.generated_at.as_str()));
+		assert_eq!(get_library_stats(&state, Some(true)).expect("refreshed stats").total_games, 2);
+		assert!(get_library_stats(&database.app_state(), None).is_err());
+	}
+
+	#[test]
+	fn get_playtime_timeline_validates_granularity_and_year() {
+		let database = test_support::test_database();
+		let state = database.signed_in_state();
+
+		for (granularity, year, code) in [
+			(Some("daily"), None, "invalid_timeline_granularity"),
+			(None, Some(1969), "invalid_timeline_year"),
+			(Some("week"), Some(10_000), "invalid_timeline_year"),
+		] {
+			let error = get_playtime_timeline(&state, granularity.map(str::to_owned), year)
+				.err()
+				.expect("invalid timeline request");
+			assert_eq!(error.code, code);
+		}
+		let timeline = get_playtime_timeline(&state, Some(String::from(" WEEK ")), Some(2024)).expect("timeline");
+		assert_eq!(timeline.granularity, PLAYTIME_TIMELINE_GRANULARITY_WEEK);
+	}
+
+	fn save_privacy(database: &test_support::TestDatabase, external_id: &str, hide_in_library: bool, mark_as_private: bool) {
+		save_game_privacy_settings(
+			&database.connection,
+			&database.user.id,
+			"steam",
+			external_id,
+			GamePrivacySettingsResponse {
+				hide_in_library,
+				mark_as_private,
+				overlay_data_deleted: false,
+			},
+		)
+		.expect("save privacy settings");
+	}
+
+	fn external_ids(games: &[GameResponse]) -> Vec<&str> {
+		let mut external_ids = games.iter().map(|game| game.external_id.as_str()).collect::<Vec<_>>();
+		external_ids.sort_unstable();
+		external_ids
+	}
+
+	#[test]
+	fn get_library_leaves_hidden_games_out_unless_asked() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		save_privacy(&database, "400", true, false);
+		let state = database.signed_in_state();
+
+		assert_eq!(external_ids(&get_library(&state, None, None, None).expect("library").games), vec!["620"]);
+		let library = get_library(&state, None, None, Some(true)).expect("library with hidden games");
+		assert_eq!((library.total, external_ids(&library.games)), (2, vec!["400", "620"]));
+		assert_eq!(external_ids(&list_hidden_games(&state).expect("hidden games")), vec!["400"]);
+
+		// Unhiding brings the game back and empties the hidden list.
+		save_privacy(&database, "400", false, false);
+		assert_eq!(get_library(&state, None, None, None).expect("library").games.len(), 2);
+		assert!(list_hidden_games(&state).expect("hidden games").is_empty());
+		assert!(list_hidden_games(&database.app_state()).is_err());
+	}
+
+	#[test]
+	fn get_playtime_timeline_and_stats_leave_out_locked_private_games() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		database
+			.connection
+			.execute(
+				"UPDATE games SET playtime_minutes = 90, last_played_at = '2024-03-05T10:00:00Z' WHERE user_id = ?1",
+				params![database.user.id],
+			)
+			.expect("seed playtime");
+		save_privacy(&database, "400", false, true);
+		private_games::save_private_games_pin(&database.connection, &database.user.id, "1234").expect("save PIN");
+		let state = database.signed_in_state_with_steam_root();
+
+		let timeline_ids = |state: &AppState| {
+			let timeline = get_playtime_timeline(state, None, Some(2024)).expect("timeline");
+			let mut external_ids = timeline
+				.buckets
+				.iter()
+				.flat_map(|bucket| bucket.games.iter().map(|game| game.external_id.clone()))
+				.collect::<Vec<_>>();
+			external_ids.sort_unstable();
+			(timeline.total_playtime_minutes, external_ids)
+		};
+		assert_eq!(timeline_ids(&state), (90, vec![String::from("620")]));
+		let stats = get_library_stats(&state, None).expect("locked stats");
+		assert_eq!((stats.total_games, stats.total_playtime_minutes), (1, 90));
+		assert_eq!(stats.most_played_by_year.len(), 1);
+		assert_eq!(stats.most_played_by_year[0].external_id, "620");
+
+		// Unlocking shows the private game; the locked numbers are not served from the cache.
+		private_games::unlock_private_games(&state, &database.user.id);
+		assert_eq!(timeline_ids(&state), (180, vec![String::from("400"), String::from("620")]));
+		let stats = get_library_stats(&state, None).expect("unlocked stats");
+		assert_eq!((stats.total_games, stats.total_playtime_minutes), (2, 180));
+
+		private_games::lock_private_games(&state);
+		assert_eq!(get_library_stats(&state, None).expect("relocked stats").total_games, 1);
+	}
+
+	#[test]
+	fn search_library_leaves_out_locked_private_games_but_finds_hidden_ones() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		database.insert_game("steam", "420", "Portal Stories");
+		save_privacy(&database, "400", false, true);
+		save_privacy(&database, "420", true, false);
+		private_games::save_private_games_pin(&database.connection, &database.user.id, "1234").expect("save PIN");
+		let state = database.signed_in_state();
+		let search_ids = |state: &AppState| {
+			let mut external_ids = search_library(state, String::from("portal"), None)
+				.expect("search")
+				.into_iter()
+				.map(|search_match| search_match.external_id)
+				.collect::<Vec<_>>();
+			external_ids.sort_unstable();
+			external_ids
+		};
+
+		assert_eq!(search_ids(&state), vec![String::from("420"), String::from("620")]);
+		private_games::unlock_private_games(&state, &database.user.id);
+		assert_eq!(search_ids(&state).len(), 3);
+		assert!(search_library(&database.app_state(), String::from("portal"), None).is_err());
+	}
+
+	#[test]
+	fn set_game_display_name_renames_and_resorts_the_library() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		database.insert_game("steam", "70", "Half-Life");
+		let state = database.signed_in_state();
+		let library_names = |state: &AppState| {
+			get_library(state, None, None, None)
+				.expect("library")
+				.games
+				.into_iter()
+				.map(|game| (game.name, game.original_name))
+				.collect::<Vec<_>>()
+		};
+
+		let display_name = set_game_display_name(&state, "steam".into(), "620".into(), Some(" Aperture 2 ".into()))
+			.expect("set display name");
+		assert_eq!(display_name.as_deref(), Some("Aperture 2"));
+		assert_eq!(
+			library_names(&state),
+			vec![
+				(String::from("Aperture 2"), Some(String::from("Portal 2"))),
+				(String::from("Half-Life"), None),
+				(String::from("Portal"), None),
+			]
+		);
+
+		let too_long = "a".repeat(GAME_DISPLAY_NAME_MAX_LENGTH + 1);
+		let error = set_game_display_name(&state, "steam".into(), "620".into(), Some(too_long))
+			.err()
+			.expect("too long display name");
+		assert_eq!(error.code, "invalid_display_name");
+		assert!(set_game_display_name(&state, "steam".into(), "730".into(), Some("Counter-Strike".into())).is_err());
+
+		assert_eq!(set_game_display_name(&state, "steam".into(), "620".into(), Some("  ".into())).expect("clear"), None);
+		assert_eq!(library_names(&state).last(), Some(&(String::from("Portal 2"), None)));
+	}
+
+	#[test]
+	fn list_steam_tags_counts_visible_games_and_filters_the_library_by_tag() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		database.insert_game("steam", "70", "Half-Life");
+		let tags = |names: &[&str]| names.iter().map(|name| String::from(*name)).collect::<Vec<_>>();
+		cache_steam_store_tags(&database.connection, 620, &tags(&["Puzzle", "Co-op"])).expect("cache Portal 2 tags");
+		cache_steam_store_tags(&database.connection, 400, &tags(&["Puzzle"])).expect("cache Portal tags");
+		cache_steam_store_tags(&database.connection, 70, &tags(&["Shooter"])).expect("cache Half-Life tags");
+		save_privacy(&database, "70", true, false);
+		let state = database.signed_in_state();
+		let tag_counts = |include_hidden: Option<bool>| {
+			list_steam_tags(&state, include_hidden)
+				.expect("Steam tags")
+				.into_iter()
+				.map(|tag| (tag.name, tag.game_count))
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(tag_counts(None), vec![(String::from("Puzzle"), 2), (String::from("Co-op"), 1)]);
+		assert_eq!(
+			tag_counts(Some(true)),
+			vec![(String::from("Puzzle"), 2), (String::from("Co-op"), 1), (String::from("Shooter"), 1)]
+		);
+
+		let tagged = |steam_tag: &str| {
+			let filter = LibraryQueryFilter { steam_tag: Some(steam_tag.into()), ..Default::default() };
+			external_ids(&get_library(&state, None, Some(filter), None).expect("filtered library").games)
+				.into_iter()
+				.map(String::from)
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(tagged("puzzle"), vec![String::from("400"), String::from("620")]);
+		assert_eq!(tagged("CO-OP"), vec![String::from("620")]);
+		assert!(tagged("Shooter").is_empty());
+		assert!(list_steam_tags(&database.app_state(), None).is_err());
+	}
+
+	#[test]
+	fn add_game_user_tag_filters_the_library_and_rejects_unknown_games() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("epic", "fortnite", "Fortnite");
+		let state = database.signed_in_state();
+		let tagged = |user_tag: &str| {
+			let filter = LibraryQueryFilter { user_tag: Some(user_tag.into()), ..Default::default() };
+			external_ids(&get_library(&state, None, Some(filter), None).expect("filtered library").games)
+				.into_iter()
+				.map(String::from)
+				.collect::<Vec<_>>()
+		};
+
+		// Load the library first so the new tag has to get past the lookup cache.
+		assert!(tagged("couch co-op").is_empty());
+		add_game_user_tag(&state, "epic".into(), "fortnite".into(), "Couch co-op".into()).expect("add tag");
+		assert_eq!(tagged("couch co-op"), vec![String::from("fortnite")]);
+
+		let error = add_game_user_tag(&state, "steam".into(), "730".into(), "Weekend".into())
+			.err()
+			.expect("unknown game");
+		assert_eq!(error.code, "not_found_error");
+		assert!(add_game_user_tag(&state, "steam".into(), "620".into(), " ".into()).is_err());
+		assert!(add_game_user_tag(&database.app_state(), "steam".into(), "620".into(), "Weekend".into()).is_err());
+
+		remove_game_user_tag(&state, "epic".into(), "fortnite".into(), "COUCH CO-OP".into()).expect("remove tag");
+		assert!(tagged("couch co-op").is_empty());
+	}
+
+	fn game_identities(games: &[(&str, &str)]) -> Vec<LibraryGameIdentityInput> {
+		games
+			.iter()
+			.map(|(provider, external_id)| LibraryGameIdentityInput {
+				provider: String::from(*provider),
+				external_id: String::from(*external_id),
+			})
+			.collect()
+	}
+
+	#[test]
+	fn set_games_favorite_validates_the_batch_and_writes_nothing_for_unowned_games() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		let state = database.signed_in_state();
+		let favorite_ids = |state: &AppState| {
+			let games = get_library(state, None, None, None).expect("library").games;
+			external_ids(&games.into_iter().filter(|game| game.favorite).collect::<Vec<_>>())
+				.into_iter()
+				.map(String::from)
+				.collect::<Vec<_>>()
+		};
+
+		let error = set_games_favorite(&state, Vec::new(), true).err().expect("empty batch");
+		assert_eq!(error.code, "games_required");
+		let too_many = vec![("steam", "620"); BATCH_GAME_UPDATE_MAX_GAMES + 1];
+		let error = set_games_favorite(&state, game_identities(&too_many), true).err().expect("oversized batch");
+		assert_eq!(error.code, "too_many_games");
+		assert!(set_games_favorite(&state, game_identities(&[("steam", " ")]), true).is_err());
+
+		// One unowned game fails the whole batch before anything is written.
+		assert!(set_games_favorite(&state, game_identities(&[("steam", "620"), ("steam", "730")]), true).is_err());
+		assert!(favorite_ids(&state).is_empty());
+
+		let updated = set_games_favorite(&state, game_identities(&[("steam", "620"), ("STEAM", "620"), ("steam", "400")]), true)
+			.expect("favorite games");
+		assert_eq!(updated, 2);
+		assert_eq!(favorite_ids(&state), vec![String::from("400"), String::from("620")]);
+
+		set_games_favorite(&state, game_identities(&[("steam", "400")]), false).expect("unfavorite game");
+		assert_eq!(favorite_ids(&state), vec![String::from("620")]);
+		assert!(set_games_favorite(&database.app_state(), game_identities(&[("steam", "620")]), true).is_err());
+	}
+
+	#[test]
+	fn suggest_game_rejects_bad_weights_and_leaves_out_locked_private_games() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "400", "Portal");
+		save_privacy(&database, "400", false, true);
+		private_games::save_private_games_pin(&database.connection, &database.user.id, "1234").expect("save PIN");
+		let state = database.signed_in_state();
+
+		let weights = SuggestGameWeights { mood: -1.0, ..SuggestGameWeights::default() };
+		let error = suggest_game(&state, Some(weights), None, None).err().expect("negative weight");
+		assert_eq!(error.code, "invalid_suggestion_weights");
+		assert!(suggest_game(&database.app_state(), None, None, None).is_err());
+
+		let suggested_ids = |state: &AppState| {
+			let mut external_ids = suggest_game(state, None, None, Some(5))
+				.expect("suggestions")
+				.into_iter()
+				.map(|suggestion| suggestion.external_id)
+				.collect::<Vec<_>>();
+			external_ids.sort_unstable();
+			external_ids
+		};
+		assert_eq!(suggested_ids(&state), vec![String::from("620")]);
+		private_games::unlock_private_games(&state, &database.user.id);
+		assert_eq!(suggested_ids(&state), vec![String::from("400"), String::from("620")]);
+	}
+
+	#[test]
+	fn get_startup_snapshot_leaves_out_non_game_kinds_unless_shown() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		database.insert_game("steam", "1070560", "Steam Linux Runtime");
+		database
+			.connection
+			.execute("UPDATE games SET kind = 'tool' WHERE external_id = '1070560'", [])
+			.expect("mark tool");
+		let state = database.signed_in_state();
+		let snapshot_total = |state: &AppState| {
+			get_startup_snapshot(state)
+				.expect("snapshot")
+				.library_counts
+				.expect("library counts")
+				.total
+		};
+
+		assert_eq!(snapshot_total(&state), 1);
+		assert_eq!(snapshot_total(&state), get_library(&state, None, None, None).expect("library").total);
+		state.show_non_game_kinds.store(true, Ordering::SeqCst);
+		assert_eq!(snapshot_total(&state), 2);
+	}
+}
@@ -1,6 +1,13 @@
 pub mod auth_service;
+pub mod batch_service;
 pub mod collection_service;
+pub mod deep_link_service;
+pub mod discovery_service;
 pub mod game_actions_service;
 pub mod game_settings_service;
+pub mod launch_template_service;
 pub mod library_service;
+pub mod notification_service;
+pub mod rom_service;
+pub mod settings_service;
 pub mod steam_service;
@@ -0,0 +1,83 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+use crate::application::context::AuthedContext;
+use crate::notifications::NotificationResponse;
+
+pub(crate) fn list_notifications(
+	state: &AppState,
+	unread_only: Option<bool>,
+	limit: Option<usize>,
+) -> AppResult<Vec<NotificationResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(notifications::list_notifications(
+		&connection,
+		&user.id,
+		unread_only.unwrap_or(false),
+		limit,
+	)?)
+}
+
+/// Marks a single notification as read, or all of them when no ID is given.
+pub(crate) fn mark_notification_read(state: &AppState, notification_id: Option<String>) -> AppResult<usize> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let notification_id = notification_id
+		.as_deref()
+		.map(str::trim)
+		.filter(|value| !value.is_empty());
+	let updated = notifications::mark_notifications_read(&connection, &user.id, notification_id)?;
+	if updated == 0 && notification_id.is_some() {
+		let exists = connection
+			.query_row(
+				"SELECT 1 FROM notifications WHERE user_id = ?1 AND id = ?2",
+				params![user.id, notification_id],
+				|_| Ok(()),
+			)
+			.optional()
+			.map_err(|error| format!("Failed to look up notification: {error}"))?
+			.is_some();
+		if !exists {
+			return Err(AppError::not_found("notification_not_found", "Notification not found"));
+		}
+	}
+	Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn mark_notification_read_rejects_unknown_and_foreign_notifications() {
+		let database = test_support::test_database();
+		let state = database.signed_in_state();
+		let other_user = create_user(&database.connection, "other@example.com", "hash", None).expect("create user");
+		let foreign = notifications::create_notification(
+			&database.connection,
+			&other_user.id,
+			notifications::NOTIFICATION_KIND_SYNC_COMPLETED,
+			"Steam sync finished",
+			"Done",
+			None,
+		)
+		.expect("create foreign notification");
+		let own = notifications::create_notification(
+			&database.connection,
+			&database.user.id,
+			notifications::NOTIFICATION_KIND_SYNC_FAILED,
+			"Steam sync failed",
+			"Timed out",
+			None,
+		)
+		.expect("create notification");
+
+		for notification_id in [String::from("missing"), foreign.id] {
+			let error = mark_notification_read(&state, Some(notification_id)).expect_err("not the user's");
+			assert_eq!(error.code, "notification_not_found");
+		}
+		assert_eq!(mark_notification_read(&state, Some(own.id.clone())).expect("mark read"), 1);
+		// Marking it again is not an error, it just changes nothing.
+		assert_eq!(mark_notification_read(&state, Some(own.id)).expect("mark again"), 0);
+		assert_eq!(mark_notification_read(&state, Some(String::from(" "))).expect("mark all"), 0);
+		assert!(list_notifications(&state, Some(true), None).expect("list unread").is_empty());
+	}
+}
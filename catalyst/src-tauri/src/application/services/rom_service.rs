@@ -0,0 +1,85 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::application::context::AuthedContext;
+use crate::rom_library::{EmulatorProfileResponse, RomDirectoryResponse, RomScanResponse};
+use crate::store_providers::LocalProviderGame;
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EmulatorProfileInput {
+	pub id: Option<String>,
+	pub name: String,
+	pub executable_path: String,
+	#[serde(default)]
+	pub args_template: String,
+	pub extensions: Vec<String>,
+}
+
+pub(crate) fn list_emulator_profiles(state: &AppState) -> AppResult<Vec<EmulatorProfileResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(rom_library::list_emulator_profiles(&connection, &user.id)?)
+}
+
+pub(crate) fn save_emulator_profile(
+	state: &AppState,
+	profile: EmulatorProfileInput,
+) -> AppResult<EmulatorProfileResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(rom_library::save_emulator_profile(
+		&connection,
+		&user.id,
+		profile.id.as_deref(),
+		&profile.name,
+		&profile.executable_path,
+		&profile.args_template,
+		&profile.extensions,
+	)?)
+}
+
+pub(crate) fn delete_emulator_profile(state: &AppState, profile_id: String) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(rom_library::delete_emulator_profile(&connection, &user.id, profile_id.trim())?)
+}
+
+pub(crate) fn list_rom_directories(state: &AppState) -> AppResult<Vec<RomDirectoryResponse>> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(rom_library::list_rom_directories(&connection, &user.id)?)
+}
+
+pub(crate) fn add_rom_directory(
+	state: &AppState,
+	path: String,
+	profile_id: String,
+) -> AppResult<RomDirectoryResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(rom_library::add_rom_directory(&connection, &user.id, &path, profile_id.trim())?)
+}
+
+pub(crate) fn remove_rom_directory(state: &AppState, directory_id: String) -> AppResult<()> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	Ok(rom_library::remove_rom_directory(&connection, &user.id, directory_id.trim())?)
+}
+
+pub(crate) fn scan_rom_library(state: &AppState) -> AppResult<RomScanResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+
+	let scanned_files = rom_library::scan_rom_directories(&connection, &user.id)?;
+	rom_library::save_scanned_rom_files(&connection, &user.id, &scanned_files)?;
+	let detected_games = scanned_files
+		.iter()
+		.map(|rom_file| LocalProviderGame {
+			external_id: rom_file.hash.clone(),
+			name: rom_file.name.clone(),
+			install_path: Some(PathBuf::from(&rom_file.path)),
+		})
+		.collect::<Vec<_>>();
+	let summary = sync_local_provider_games(&connection, &user.id, "rom", &detected_games)?;
+
+	Ok(RomScanResponse {
+		scanned_files: scanned_files.len(),
+		synced_games: summary.total,
+		added_games: summary.added,
+		updated_games: summary.updated,
+		uninstalled_games: summary.removed,
+	})
+}
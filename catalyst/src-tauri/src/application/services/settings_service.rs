@@ -0,0 +1,499 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+
+pub(crate) fn get_offline_mode(state: &AppState) -> AppResult<bool> {
+	Ok(state.is_offline())
+}
+
+pub(crate) fn set_offline_mode(state: &AppState, enabled: bool) -> AppResult<bool> {
+	state.offline_mode.store(enabled, Ordering::SeqCst);
+	Ok(state.is_offline())
+}
+
+#[derive(serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct AppSettingsUpdateInput {
+	pub steam_api_key: Option<String>,
+	pub steam_root_override: Option<String>,
+	pub sync_interval_minutes: Option<u64>,
+	pub steam_local_install_detection: Option<bool>,
+	pub steam_settings_debug_logging: Option<bool>,
+	pub minimize_to_tray: Option<bool>,
+	pub discord_rich_presence: Option<bool>,
+	pub discord_client_id: Option<String>,
+	pub native_notifications: Option<bool>,
+	pub show_non_game_kinds: Option<bool>,
+}
+
+pub(crate) fn get_app_settings(state: &AppState) -> AppResult<AppSettingsResponse> {
+	Ok(app_settings_response(state))
+}
+
+/// Persists the supplied settings and applies them to the running app. Omitted fields are left
+/// unchanged; an empty API key or Steam root clears the stored value.
+pub(crate) fn set_app_settings(
+	state: &AppState,
+	input: AppSettingsUpdateInput,
+) -> AppResult<AppSettingsResponse> {
+	let steam_root_override = input
+		.steam_root_override
+		.as_deref()
+		.map(|value| value.trim().to_owned());
+	if let Some(steam_root) = steam_root_override.as_deref().filter(|value| !value.is_empty()) {
+		if !Path::new(steam_root).is_dir() {
+			return Err(AppError::validation(
+				"steam_root_not_found",
+				i18n::localized("steam_root_not_found", &[]),
+			));
+		}
+	}
+
+	if let Some(interval) = input.sync_interval_minutes {
+		if interval != 0
+			&& !(STEAM_SYNC_INTERVAL_MIN_MINUTES..=STEAM_SYNC_INTERVAL_MAX_MINUTES).contains(&interval)
+		{
+			return Err(AppError::validation(
+				"invalid_sync_interval",
+				i18n::localized(
+					"invalid_sync_interval",
+					&[
+						("min", STEAM_SYNC_INTERVAL_MIN_MINUTES.to_string()),
+						("max", STEAM_SYNC_INTERVAL_MAX_MINUTES.to_string()),
+					],
+				),
+			));
+		}
+	}
+
+	let connection = open_connection(&state.db_path)?;
+	let mut updates = Vec::new();
+	if let Some(api_key) = input.steam_api_key.as_deref() {
+		store_steam_api_key(state, &connection, api_key)?;
+	}
+	if let Some(steam_root) = steam_root_override {
+		updates.push((APP_SETTING_STEAM_ROOT_OVERRIDE, steam_root));
+	}
+	if let Some(interval) = input.sync_interval_minutes {
+		updates.push((APP_SETTING_STEAM_SYNC_INTERVAL_MINUTES, interval.to_string()));
+	}
+	if let Some(enabled) = input.steam_local_install_detection {
+		updates.push((APP_SETTING_STEAM_LOCAL_INSTALL_DETECTION, enabled.to_string()));
+	}
+	if let Some(enabled) = input.steam_settings_debug_logging {
+		updates.push((APP_SETTING_STEAM_SETTINGS_DEBUG_LOGGING, enabled.to_string()));
+	}
+	if let Some(enabled) = input.minimize_to_tray {
+		updates.push((APP_SETTING_MINIMIZE_TO_TRAY, enabled.to_string()));
+	}
+	if let Some(enabled) = input.discord_rich_presence {
+		updates.push((APP_SETTING_DISCORD_RICH_PRESENCE, enabled.to_string()));
+	}
+	if let Some(enabled) = input.native_notifications {
+		updates.push((APP_SETTING_NATIVE_NOTIFICATIONS, enabled.to_string()));
+	}
+	if let Some(enabled) = input.show_non_game_kinds {
+		updates.push((APP_SETTING_SHOW_NON_GAME_KINDS, enabled.to_string()));
+	}
+	if let Some(client_id) = input.discord_client_id.as_deref() {
+		let client_id = client_id.trim();
+		if !client_id.is_empty() && !client_id.chars().all(|character| character.is_ascii_digit()) {
+			return Err(AppError::validation(
+				"invalid_discord_client_id",
+				i18n::localized("invalid_discord_client_id", &[]),
+			));
+		}
+		updates.push((APP_SETTING_DISCORD_CLIENT_ID, client_id.to_owned()));
+	}
+
+	for (key, value) in &updates {
+		save_app_setting(
+			&connection,
+			key,
+			Some(value.as_str()).filter(|value| !value.is_empty()),
+		)?;
+	}
+
+	let applied_settings = updates
+		.into_iter()
+		.map(|(key, value)| (key.to_owned(), value))
+		.collect::<HashMap<_, _>>();
+	apply_app_settings(state, &applied_settings);
+	Ok(app_settings_response(state))
+}
+
+pub(crate) fn get_locale(_state: &AppState) -> AppResult<i18n::LocaleResponse> {
+	Ok(i18n::LocaleResponse {
+		locale: i18n::active_locale(),
+		supported_locales: i18n::supported_locales(),
+	})
+}
+
+/// Switches the language of backend messages. Tags like `de-AT` select their language's catalog.
+pub(crate) fn set_locale(state: &AppState, locale: String) -> AppResult<i18n::LocaleResponse> {
+	let Some(locale) = i18n::normalize_locale(&locale) else {
+		return Err(AppError::validation(
+			"invalid_locale",
+			i18n::localized("invalid_locale", &[("supported", i18n::supported_locales().join(", "))]),
+		));
+	};
+	let connection = open_connection(&state.db_path)?;
+	save_app_setting(&connection, APP_SETTING_LOCALE, Some(&locale))?;
+	i18n::set_active_locale(&locale);
+	get_locale(state)
+}
+
+pub(crate) fn get_download_schedule(state: &AppState) -> AppResult<download_queue::DownloadSchedule> {
+	let connection = open_connection(&state.db_path)?;
+	Ok(download_queue::load_download_schedule(&connection)?)
+}
+
+/// Replaces the download schedule. The schedule monitor picks it up on its next tick.
+pub(crate) fn set_download_schedule(
+	state: &AppState,
+	schedule: download_queue::DownloadSchedule,
+) -> AppResult<download_queue::DownloadSchedule> {
+	download_queue::validate_download_schedule(&schedule)
+		.map_err(|error| AppError::validation("invalid_download_schedule", error))?;
+	let connection = open_connection(&state.db_path)?;
+	let serialized = serde_json::to_string(&schedule)
+		.map_err(|error| format!("Failed to serialize download schedule: {error}"))?;
+	save_app_setting(&connection, APP_SETTING_DOWNLOAD_SCHEDULE, Some(&serialized))?;
+	Ok(schedule)
+}
+
+pub(crate) fn get_steamcmd_settings(state: &AppState) -> AppResult<steamcmd::SteamCmdSettings> {
+	let connection = open_connection(&state.db_path)?;
+	Ok(steamcmd::load_steamcmd_settings(&connection)?)
+}
+
+/// Saves where steamcmd lives and which account it logs in with. An empty username switches
+/// back to anonymous logins.
+pub(crate) fn set_steamcmd_settings(
+	state: &AppState,
+	settings: steamcmd::SteamCmdSettings,
+) -> AppResult<steamcmd::SteamCmdSettings> {
+	let trimmed = |value: Option<String>| {
+		value
+			.map(|value| value.trim().to_owned())
+			.filter(|value| !value.is_empty())
+	};
+	let settings = steamcmd::SteamCmdSettings {
+		path: trimmed(settings.path),
+		username: trimmed(settings.username),
+	};
+	if let Some(path) = settings.path.as_deref() {
+		if !Path::new(path).is_file() {
+			return Err(AppError::validation(
+				"invalid_steamcmd_path",
+				format!("steamcmd was not found at {path}"),
+			));
+		}
+	}
+	let connection = open_connection(&state.db_path)?;
+	steamcmd::save_steamcmd_settings(&connection, &settings)?;
+	Ok(settings)
+}
+
+pub(crate) fn get_api_server_settings(state: &AppState) -> AppResult<api_server::ApiServerSettingsResponse> {
+	Ok(api_server::api_server_settings_response(&api_server::api_server_config(state)))
+}
+
+/// Turns the localhost API server on or off, or moves it to another port. A token is generated
+/// the first time the server is enabled.
+pub(crate) fn set_api_server_settings(
+	state: &AppState,
+	settings: api_server::ApiServerSettings,
+) -> AppResult<api_server::ApiServerSettingsResponse> {
+	if settings.port < 1024 {
+		return Err(AppError::validation(
+			"invalid_api_server_port",
+			i18n::localized("invalid_api_server_port", &[]),
+		));
+	}
+	let mut config = api_server::api_server_config(state);
+	if settings.enabled && config.token.is_none() {
+		config.token = Some(api_server::generate_api_server_token()?);
+	}
+	let connection = open_connection(&state.db_path)?;
+	api_server::save_api_server_settings(&connection, &settings)?;
+	config.settings = settings;
+	api_server::set_api_server_config(state, config.clone());
+	Ok(api_server::api_server_settings_response(&config))
+}
+
+/// Issues a new API token; clients using the old one are rejected from the next request on.
+pub(crate) fn regenerate_api_server_token(state: &AppState) -> AppResult<api_server::ApiServerSettingsResponse> {
+	let mut config = api_server::api_server_config(state);
+	config.token = Some(api_server::generate_api_server_token()?);
+	api_server::set_api_server_config(state, config.clone());
+	Ok(api_server::api_server_settings_response(&config))
+}
+
+pub(crate) fn get_gaming_pc_settings(state: &AppState) -> AppResult<gaming_pc::GamingPcSettingsResponse> {
+	let connection = open_connection(&state.db_path)?;
+	Ok(gaming_pc::GamingPcSettingsResponse {
+		settings: gaming_pc::load_gaming_pc_settings(&connection)?,
+		api_token_configured: gaming_pc::read_gaming_pc_api_token()?.is_some(),
+	})
+}
+
+/// Saves the gaming PC entry, or removes it when `settings` is `None`. `api_token` is only
+/// touched when given; an empty string forgets it.
+pub(crate) fn set_gaming_pc_settings(
+	state: &AppState,
+	settings: Option<gaming_pc::GamingPcSettings>,
+	api_token: Option<String>,
+) -> AppResult<gaming_pc::GamingPcSettingsResponse> {
+	if let Some(settings) = settings.as_ref() {
+		gaming_pc::parse_mac_address(&settings.mac_address)
+			.map_err(|error| AppError::validation("invalid_mac_address", error))?;
+		if let Some(api_url) = settings.api_url.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+			gaming_pc::parse_gaming_pc_api_url(api_url)
+				.map_err(|error| AppError::validation("invalid_api_url", error))?;
+		}
+	}
+	let connection = open_connection(&state.db_path)?;
+	gaming_pc::save_gaming_pc_settings(&connection, settings.as_ref())?;
+	if settings.is_none() {
+		gaming_pc::save_gaming_pc_api_token(None)?;
+	} else if let Some(api_token) = api_token.as_deref() {
+		gaming_pc::save_gaming_pc_api_token(Some(api_token))?;
+	}
+	Ok(gaming_pc::GamingPcSettingsResponse {
+		settings,
+		api_token_configured: gaming_pc::read_gaming_pc_api_token()?.is_some(),
+	})
+}
+
+pub(crate) fn get_http_proxy_settings(state: &AppState) -> AppResult<http_proxy::HttpProxySettingsResponse> {
+	let connection = open_connection(&state.db_path)?;
+	Ok(http_proxy::HttpProxySettingsResponse {
+		settings: http_proxy::load_http_proxy_settings(&connection)?,
+		password_configured: http_proxy::read_http_proxy_password()?.is_some(),
+	})
+}
+
+/// Saves the proxy every Steam request goes through, or removes it when `settings` is `None`.
+/// `password` is only touched when given; an empty string forgets it.
+pub(crate) fn set_http_proxy_settings(
+	state: &AppState,
+	settings: Option<http_proxy::HttpProxySettings>,
+	password: Option<String>,
+) -> AppResult<http_proxy::HttpProxySettingsResponse> {
+	let settings = settings
+		.map(http_proxy::normalize_http_proxy_settings)
+		.transpose()
+		.map_err(|error| AppError::validation("invalid_http_proxy", error))?;
+	let connection = open_connection(&state.db_path)?;
+	http_proxy::save_http_proxy_settings(&connection, settings.as_ref())?;
+	if settings.is_none() {
+		http_proxy::save_http_proxy_password(None)?;
+	} else if let Some(password) = password.as_deref() {
+		http_proxy::save_http_proxy_password(Some(password))?;
+	}
+	let password = http_proxy::read_http_proxy_password()?;
+	let password_configured = password.is_some();
+	http_proxy::set_http_proxy_config(
+		state,
+		settings
+			.clone()
+			.map(|settings| http_proxy::HttpProxyConfig { settings, password }),
+	);
+	Ok(http_proxy::HttpProxySettingsResponse {
+		settings,
+		password_configured,
+	})
+}
+
+/// Checks that Steam is reachable through `settings` before they are saved, or through the
+/// current proxy when `settings` is `None`. Without `password` the saved one is used.
+pub(crate) fn test_proxy_connection(
+	state: &AppState,
+	settings: Option<http_proxy::HttpProxySettings>,
+	password: Option<String>,
+) -> AppResult<http_proxy::HttpProxyTestResponse> {
+	if state.is_offline() {
+		return Err(AppError::conflict("offline_mode", i18n::localized("offline_mode", &[])));
+	}
+	let config = match settings {
+		Some(settings) => {
+			let settings = http_proxy::normalize_http_proxy_settings(settings)
+				.map_err(|error| AppError::validation("invalid_http_proxy", error))?;
+			let password = match password {
+				Some(password) => Some(password).filter(|password| !password.is_empty()),
+				None => http_proxy::read_http_proxy_password()?,
+			};
+			Some(http_proxy::HttpProxyConfig { settings, password })
+		}
+		None => state.http_proxy(),
+	};
+	Ok(http_proxy::test_http_proxy_connection(config.as_ref()))
+}
+
+pub(crate) fn get_steam_api_key_status(state: &AppState) -> AppResult<SteamApiKeyStatusResponse> {
+	let connection = open_connection(&state.db_path)?;
+	Ok(steam_api_key_status(state, &connection)?)
+}
+
+/// Saves the Steam Web API key to the OS keychain, or clears it when `api_key` is empty, in
+/// which case `STEAM_API_KEY` applies again if it is set. Without a usable keychain the key is
+/// kept in the settings table, and clearing it there still succeeds.
+pub(crate) fn set_steam_api_key(
+	state: &AppState,
+	api_key: Option<String>,
+) -> AppResult<SteamApiKeyStatusResponse> {
+	let api_key = api_key.as_deref().map(str::trim).unwrap_or_default();
+	if !api_key.is_empty()
+		&& (api_key.len() != 32 || !api_key.chars().all(|character| character.is_ascii_hexdigit()))
+	{
+		return Err(AppError::validation(
+			"invalid_steam_api_key",
+			i18n::localized("invalid_steam_api_key", &[]),
+		));
+	}
+
+	let connection = open_connection(&state.db_path)?;
+	store_steam_api_key(state, &connection, api_key)?;
+	Ok(steam_api_key_status(state, &connection)?)
+}
+
+fn store_steam_api_key(state: &AppState, connection: &Connection, api_key: &str) -> Result<(), String> {
+	let api_key = api_key.trim();
+	if api_key.is_empty() {
+		if let Err(error) = secret_store::delete_secret(secret_store::STEAM_API_KEY_ACCOUNT) {
+			eprintln!("Could not remove the Steam API key from the OS keychain: {error}");
+		}
+		save_app_setting(connection, APP_SETTING_STEAM_API_KEY, None)?;
+		if let Ok(mut guard) = state.steam_api_key.lock() {
+			*guard = read_env_steam_api_key();
+		}
+		return Ok(());
+	}
+
+	// Without a usable keychain the key is kept encrypted in the settings table instead.
+	match secret_store::write_secret(secret_store::STEAM_API_KEY_ACCOUNT, api_key) {
+		Ok(()) => save_app_setting(connection, APP_SETTING_STEAM_API_KEY, None)?,
+		Err(error) => {
+			eprintln!("{error}");
+			let encrypted_api_key = field_encryption::encrypt_field(api_key)?;
+			save_app_setting(connection, APP_SETTING_STEAM_API_KEY, Some(&encrypted_api_key))?;
+		}
+	}
+	if let Ok(mut guard) = state.steam_api_key.lock() {
+		*guard = Some(api_key.to_owned());
+	}
+	Ok(())
+}
+
+fn steam_api_key_status(state: &AppState, connection: &Connection) -> Result<SteamApiKeyStatusResponse, String> {
+	let configured = state.steam_api_key().is_some();
+	let source = if !configured {
+		None
+	} else if matches!(
+		secret_store::read_secret(secret_store::STEAM_API_KEY_ACCOUNT),
+		Ok(Some(_))
+	) {
+		Some("keychain")
+	} else if load_app_settings(connection)?.contains_key(APP_SETTING_STEAM_API_KEY) {
+		Some("settings")
+	} else {
+		Some("environment")
+	};
+
+	Ok(SteamApiKeyStatusResponse {
+		configured,
+		source: source.map(str::to_owned),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn sync_interval_input(minutes: u64) -> AppSettingsUpdateInput {
+		serde_json::from_value(serde_json::json!({ "syncIntervalMinutes": minutes }))
+			.expect("settings input")
+	}
+
+	#[test]
+	fn set_app_settings_validates_and_applies_the_sync_interval() {
+		let database = test_support::test_database();
+		let state = database.signed_in_state();
+
+		for minutes in [STEAM_SYNC_INTERVAL_MIN_MINUTES - 1, STEAM_SYNC_INTERVAL_MAX_MINUTES + 1] {
+			let error = set_app_settings(&state, sync_interval_input(minutes)).expect_err("out of range");
+			assert_eq!(error.code, "invalid_sync_interval");
+		}
+		assert_eq!(state.steam_sync_interval_minutes(), 0);
+
+		set_app_settings(&state, sync_interval_input(30)).expect("valid interval");
+		assert_eq!(state.steam_sync_interval_minutes(), 30);
+		assert_eq!(
+			load_app_settings(&database.connection)
+				.expect("load settings")
+				.get(APP_SETTING_STEAM_SYNC_INTERVAL_MINUTES)
+				.map(String::as_str),
+			Some("30")
+		);
+
+		// Zero turns scheduled syncs off.
+		set_app_settings(&state, sync_interval_input(0)).expect("disable interval");
+		assert_eq!(state.steam_sync_interval_minutes(), 0);
+	}
+
+	#[test]
+	fn apply_app_settings_ignores_an_unparseable_sync_interval() {
+		let database = test_support::test_database();
+		let state = database.app_state();
+		let settings = |value: &str| {
+			HashMap::from([(APP_SETTING_STEAM_SYNC_INTERVAL_MINUTES.to_owned(), value.to_owned())])
+		};
+
+		apply_app_settings(&state, &settings(" 15 "));
+		assert_eq!(state.steam_sync_interval_minutes(), 15);
+		apply_app_settings(&state, &settings("hourly"));
+		assert_eq!(state.steam_sync_interval_minutes(), 15);
+	}
+
+	#[test]
+	fn set_steam_api_key_stores_and_clears_the_key_with_and_without_a_keychain() {
+		let database = test_support::test_database();
+		let state = database.signed_in_state();
+		let api_key = "0123456789abcdef0123456789ABCDEF";
+
+		let error = set_steam_api_key(&state, Some(String::from("not-a-key"))).expect_err("invalid key");
+		assert_eq!(error.code, "invalid_steam_api_key");
+
+		// Both halves share the keychain account, so they stay in one test rather than racing.
+		// Without a keychain the key falls back to the settings table and can be cleared there.
+		secret_store::set_test_secret_unavailable(secret_store::STEAM_API_KEY_ACCOUNT, true);
+		let status = set_steam_api_key(&state, Some(api_key.to_owned())).expect("save to settings");
+		assert_eq!(status.source.as_deref(), Some("settings"));
+		set_steam_api_key(&state, None).expect("clear without a keychain");
+		assert!(!load_app_settings(&database.connection)
+			.expect("load settings")
+			.contains_key(APP_SETTING_STEAM_API_KEY));
+		assert_eq!(state.steam_api_key(), read_env_steam_api_key());
+
+		// A key saved to the settings table moves into the keychain once it is usable.
+		set_steam_api_key(&state, Some(api_key.to_owned())).expect("save to settings");
+
+		secret_store::set_test_secret_unavailable(secret_store::STEAM_API_KEY_ACCOUNT, false);
+		let settings = load_app_settings(&database.connection).expect("load settings");
+		restore_keychain_steam_api_key(&state, &database.connection, &settings);
+		assert_eq!(
+			secret_store::read_secret(secret_store::STEAM_API_KEY_ACCOUNT).expect("read keychain").as_deref(),
+			Some(api_key)
+		);
+		assert!(!load_app_settings(&database.connection)
+			.expect("load settings")
+			.contains_key(APP_SETTING_STEAM_API_KEY));
+
+		// Once cleared, a restart does not bring the key back.
+		set_steam_api_key(&state, Some(String::new())).expect("clear key");
+		assert_eq!(secret_store::read_secret(secret_store::STEAM_API_KEY_ACCOUNT).expect("read keychain"), None);
+		let settings = load_app_settings(&database.connection).expect("load settings");
+		restore_keychain_steam_api_key(&state, &database.connection, &settings);
+		assert_eq!(state.steam_api_key(), read_env_steam_api_key());
+	}
+}
@@ -1,5 +1,6 @@
 use crate::*;
 use crate::application::error::{AppError, AppResult};
+use crate::application::context::{AuthedContext, AuthedGameContext, require_authenticated_user};
 use chrono::Duration as ChronoDuration;
 
 pub(crate) fn list_game_versions_betas(
@@ -7,22 +8,18 @@ pub(crate) fn list_game_versions_betas(
 	provider: String,
 	external_id: String,
 ) -> AppResult<GameVersionBetasResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		connection,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" {
 		return Ok(GameVersionBetasResponse {
 			options: default_game_version_beta_options(),
 			warning: None,
+			stale: false,
 		});
 	}
 
@@ -32,23 +29,50 @@ pub(crate) fn list_game_versions_betas(
 			return Ok(GameVersionBetasResponse {
 				options: default_game_version_beta_options(),
 				warning: Some(String::from("This Steam app ID is invalid.")),
+				stale: false,
 			});
 		}
 	};
 
+	// Steam's local product info cache is current and includes every branch the account can
+	// see, so the web APIs are only consulted for apps it hasn't cached.
+	match steam_appinfo::read_local_steam_beta_branches(state.steam_root_override().as_deref(), app_id) {
+		Ok(Some(options)) => {
+			cache_steam_app_betas(&connection, app_id, &options)?;
+			return Ok(GameVersionBetasResponse {
+				options,
+				warning: None,
+				stale: false,
+			});
+		}
+		Ok(None) => {}
+		Err(error) => {
+			eprintln!("Failed to read beta branches for app {app_id} from appinfo.vdf: {error}");
+		}
+	}
+
 	let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_BETAS_CACHE_TTL_HOURS);
 	let cached_options_entry = find_cached_steam_app_betas(&connection, app_id)?;
 	if let Some((cached_options, fetched_at)) = cached_options_entry.as_ref() {
-		if *fetched_at >= stale_before {
+		if *fetched_at >= stale_before || state.is_offline() {
 			return Ok(GameVersionBetasResponse {
 				options: cached_options.clone(),
 				warning: None,
+				stale: *fetched_at < stale_before,
 			});
 		}
 	}
 
-	let Some(api_key) = state
-		.steam_api_key
+	if state.is_offline() {
+		return Ok(GameVersionBetasResponse {
+			options: default_game_version_beta_options(),
+			warning: Some(String::from(OFFLINE_MODE_MESSAGE)),
+			stale: false,
+		});
+	}
+
+	let steam_api_key = state.steam_api_key();
+	let Some(api_key) = steam_api_key
 		.as_deref()
 		.map(str::trim)
 		.filter(|value| !value.is_empty())
@@ -59,6 +83,7 @@ pub(crate) fn list_game_versions_betas(
 				warning: Some(String::from(
 					"Using cached beta branch data because STEAM_API_KEY is not configured.",
 				)),
+				stale: true,
 			});
 		}
 
@@ -67,10 +92,11 @@ pub(crate) fn list_game_versions_betas(
 			warning: Some(String::from(
 				"Live beta branch data is unavailable because STEAM_API_KEY is not configured.",
 			)),
+			stale: false,
 		});
 	};
 
-	let client = build_http_client()?;
+	let client = state.http_client()?;
 	match fetch_steam_game_version_betas(&client, app_id, api_key) {
 		Ok(options) => {
 			if !options.is_empty() {
@@ -78,6 +104,7 @@ pub(crate) fn list_game_versions_betas(
 				return Ok(GameVersionBetasResponse {
 					options,
 					warning: None,
+					stale: false,
 				});
 			}
 
@@ -87,6 +114,7 @@ pub(crate) fn list_game_versions_betas(
 					warning: Some(String::from(
 						"Steam returned no beta branch data. Showing cached data.",
 					)),
+					stale: true,
 				});
 			}
 
@@ -95,6 +123,7 @@ pub(crate) fn list_game_versions_betas(
 				warning: Some(String::from(
 					"Steam returned no beta branch data for this app.",
 				)),
+				stale: false,
 			})
 		}
 		Err(fetch_error) => {
@@ -108,6 +137,7 @@ pub(crate) fn list_game_versions_betas(
 								warning: Some(String::from(
 									"Using public Steam branch metadata (partner betas API returned 403). Private branch visibility may be limited.",
 								)),
+								stale: false,
 							});
 						}
 					}
@@ -127,11 +157,13 @@ pub(crate) fn list_game_versions_betas(
 						"Could not refresh beta branch data: {} Using cached data.",
 						normalize_backend_warning_message(&fetch_error)
 					)),
+					stale: true,
 				});
 			}
 			Ok(GameVersionBetasResponse {
 				options: default_game_version_beta_options(),
 				warning: Some(normalize_backend_warning_message(&fetch_error)),
+				stale: false,
 			})
 		}
 	}
@@ -143,22 +175,16 @@ pub(crate) fn validate_game_beta_access_code(
 	external_id: String,
 	access_code: String,
 ) -> AppResult<GameBetaAccessCodeValidationResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
-	let (normalized_provider, normalized_external_id) =
-		normalize_game_identity_input(&provider, &external_id)?;
-	ensure_owned_game_exists(
-		&connection,
-		&user.id,
-		&normalized_provider,
-		&normalized_external_id,
-	)?;
+	let AuthedGameContext {
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
 
 	if normalized_provider != "steam" {
 		return Ok(GameBetaAccessCodeValidationResponse {
 			valid: false,
-			message: String::from("Beta access code validation is only available for Steam games."),
+			message: i18n::localized("beta_code_steam_only", &[]),
 			branch_id: None,
 			branch_name: None,
 		});
@@ -168,7 +194,7 @@ pub(crate) fn validate_game_beta_access_code(
 	if trimmed_access_code.is_empty() {
 		return Ok(GameBetaAccessCodeValidationResponse {
 			valid: false,
-			message: String::from("Enter an access code before checking."),
+			message: i18n::localized("beta_code_required", &[]),
 			branch_id: None,
 			branch_name: None,
 		});
@@ -179,42 +205,38 @@ pub(crate) fn validate_game_beta_access_code(
 		Err(_) => {
 			return Ok(GameBetaAccessCodeValidationResponse {
 				valid: false,
-				message: String::from("This Steam app ID is invalid."),
+				message: i18n::localized("beta_code_invalid_app_id", &[]),
 				branch_id: None,
 				branch_name: None,
 			});
 		}
 	};
 
-	let Some(api_key) = state
-		.steam_api_key
+	let steam_api_key = state.steam_api_key();
+	let Some(api_key) = steam_api_key
 		.as_deref()
 		.map(str::trim)
 		.filter(|value| !value.is_empty())
 	else {
 		return Ok(GameBetaAccessCodeValidationResponse {
 			valid: false,
-			message: String::from(
-				"Beta access code validation is unavailable because STEAM_API_KEY is not configured.",
-			),
+			message: i18n::localized("beta_code_api_key_missing", &[]),
 			branch_id: None,
 			branch_name: None,
 		});
 	};
 
-	let client = build_http_client()?;
+	let client = state.http_client()?;
 	match fetch_steam_beta_access_code_validation(&client, app_id, api_key, trimmed_access_code) {
 		Ok(validation) => Ok(validation),
 		Err(fetch_error) => Ok(GameBetaAccessCodeValidationResponse {
 			valid: false,
 			message: if is_forbidden_http_error(&fetch_error) {
-				String::from(
-					"Steam returned 403 for beta code validation. This usually requires publisher-level API access.",
-				)
+				i18n::localized("beta_code_forbidden", &[])
 			} else if fetch_error.trim().is_empty() {
-				String::from("Could not validate this code right now.")
+				i18n::localized("beta_code_check_unavailable", &[])
 			} else {
-				normalize_backend_warning_message(&fetch_error)
+				i18n::LocalizedMessage::untranslated(normalize_backend_warning_message(&fetch_error))
 			},
 			branch_id: None,
 			branch_name: None,
@@ -222,15 +244,18 @@ pub(crate) fn validate_game_beta_access_code(
 	}
 }
 
-pub(crate) fn import_steam_collections(state: &AppState) -> AppResult<SteamCollectionsImportResponse> {
-	let connection = open_connection(&state.db_path)?;
-	cleanup_expired_sessions(&connection)?;
-	let user = get_authenticated_user(state, &connection)?;
+pub(crate) fn import_steam_collections(
+	state: &AppState,
+	mode: Option<SteamCollectionsImportMode>,
+	import_favorites: Option<bool>,
+) -> AppResult<SteamCollectionsImportResponse> {
+	let mode = mode.unwrap_or_default();
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
 	let steam_id = user
 		.steam_id
 		.as_deref()
 		.ok_or_else(|| AppError::unauthorized("steam_not_linked", "Steam is not linked for this account"))?;
-	let steam_root = resolve_steam_root_path(state.steam_root_override.as_deref())
+	let steam_root = resolve_steam_root_path(state.steam_root_override().as_deref())
 		.ok_or_else(|| AppError::not_found("steam_install_not_found", "Could not locate local Steam installation"))?;
 	let userdata_directory = resolve_steam_userdata_directory(&steam_root, steam_id)?;
 	let config_paths = [
@@ -259,6 +284,28 @@ pub(crate) fn import_steam_collections(state: &AppState) -> AppResult<SteamColle
 		loaded_config_paths.push(config_path.display().to_string());
 	}
 
+	let cloud_namespace_path = userdata_directory
+		.join("config")
+		.join("cloudstorage")
+		.join("cloud-storage-namespace-1.json");
+	let mut dynamic_collections_skipped = 0;
+	if cloud_namespace_path.is_file() {
+		let namespace_contents = fs::read_to_string(&cloud_namespace_path).map_err(|error| {
+			format!(
+				"Failed to read Steam cloudstorage namespace file at {}: {error}",
+				cloud_namespace_path.display()
+			)
+		})?;
+		let cloud_collections = steam_cloud_collections::parse_steam_cloud_collections(&namespace_contents)?;
+		let owned_game_names = steam_cloud_collections::load_steam_game_names(&connection, &user.id)?;
+		let (parsed_collections, skipped) =
+			steam_cloud_collections::resolve_steam_cloud_collection_memberships(&cloud_collections, &owned_game_names);
+		merge_collections_by_app_id(&mut combined_collections_by_app_id, parsed_collections);
+		dynamic_collections_skipped = skipped;
+		loaded_any_config_file = true;
+		loaded_config_paths.push(cloud_namespace_path.display().to_string());
+	}
+
 	if !loaded_any_config_file {
 		return Err(AppError::not_found(
 			"steam_collection_config_not_found",
@@ -283,10 +330,549 @@ pub(crate) fn import_steam_collections(state: &AppState) -> AppResult<SteamColle
 		));
 	}
 
-	Ok(import_steam_collections_for_user(
-		&connection,
-		&user.id,
-		combined_collections_by_app_id,
+	let mut response = import_steam_collections_for_user(&connection, &user.id, combined_collections_by_app_id, mode, import_favorites.unwrap_or(false))?;
+	response.dynamic_collections_skipped = dynamic_collections_skipped;
+	if mode != SteamCollectionsImportMode::Preview {
+		state.library_lookup_cache.invalidate_user(&user.id);
+	}
+	Ok(response)
+}
+
+
+pub(crate) fn get_steam_points_balance(state: &AppState) -> AppResult<SteamPointsBalanceResponse> {
+	let AuthedContext { user, .. } = AuthedContext::new(state)?;
+	let steam_id = user
+		.steam_id
+		.as_deref()
+		.ok_or_else(|| AppError::unauthorized("steam_not_linked", "Steam is not linked for this account"))?;
+
+	let cache_key = format!("steam_points_balance:{steam_id}");
+	let cached_balance = crate::cache::get_cached(&cache_key, STEAM_POINTS_BALANCE_CACHE_TTL_SECS)
+		.and_then(|value| serde_json::from_value::<SteamPointsBalanceResponse>(value).ok());
+	if let Some(balance) = cached_balance {
+		return Ok(balance);
+	}
+
+	if state.is_offline() {
+		let stale_balance = crate::cache::get_cached(&cache_key, i64::MAX)
+			.and_then(|value| serde_json::from_value::<SteamPointsBalanceResponse>(value).ok());
+		return Ok(match stale_balance {
+			Some(balance) => SteamPointsBalanceResponse {
+				stale: true,
+				..balance
+			},
+			None => SteamPointsBalanceResponse {
+				points: 0,
+				points_earned: 0,
+				points_spent: 0,
+				warning: Some(String::from(OFFLINE_MODE_MESSAGE)),
+				stale: false,
+			},
+		});
+	}
+
+	let steam_api_key = state.steam_api_key();
+	let Some(api_key) = steam_api_key
+		.as_deref()
+		.map(str::trim)
+		.filter(|value| !value.is_empty())
+	else {
+		return Ok(SteamPointsBalanceResponse {
+			points: 0,
+			points_earned: 0,
+			points_spent: 0,
+			warning: Some(String::from(
+				"Steam Points balance is unavailable because STEAM_API_KEY is not configured.",
+			)),
+			stale: false,
+		});
+	};
+
+	let client = state.http_client()?;
+	let balance = fetch_steam_points_summary(&client, api_key, steam_id)?;
+	if let Ok(value) = serde_json::to_value(&balance) {
+		crate::cache::set_cached(&cache_key, value);
+	}
+	Ok(balance)
+}
+
+pub(crate) fn list_game_points_shop_items(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<SteamPointsShopItemsResponse> {
+	let AuthedGameContext {
+		connection,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+		..
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
+
+	if normalized_provider != "steam" {
+		return Ok(SteamPointsShopItemsResponse {
+			items: Vec::new(),
+			warning: None,
+			stale: false,
+		});
+	}
+
+	let app_id = match normalized_external_id.parse::<u64>() {
+		Ok(parsed) => parsed,
+		Err(_) => {
+			return Ok(SteamPointsShopItemsResponse {
+				items: Vec::new(),
+				warning: Some(String::from("This Steam app ID is invalid.")),
+				stale: false,
+			});
+		}
+	};
+
+	let stale_before = Utc::now() - ChronoDuration::hours(STEAM_POINTS_SHOP_ITEMS_CACHE_TTL_HOURS);
+	let cached_items_entry = find_cached_steam_points_shop_items(&connection, app_id)?;
+	if let Some((cached_items, fetched_at)) = cached_items_entry.as_ref() {
+		if *fetched_at >= stale_before || state.is_offline() {
+			return Ok(SteamPointsShopItemsResponse {
+				items: cached_items.clone(),
+				warning: None,
+				stale: *fetched_at < stale_before,
+			});
+		}
+	}
+
+	if state.is_offline() {
+		return Ok(SteamPointsShopItemsResponse {
+			items: Vec::new(),
+			warning: Some(String::from(OFFLINE_MODE_MESSAGE)),
+			stale: false,
+		});
+	}
+
+	let steam_api_key = state.steam_api_key();
+	let api_key = steam_api_key
+		.as_deref()
+		.map(str::trim)
+		.filter(|value| !value.is_empty());
+	let client = state.http_client()?;
+	match fetch_steam_points_shop_items(&client, app_id, api_key) {
+		Ok(items) => {
+			cache_steam_points_shop_items(&connection, app_id, &items)?;
+			Ok(SteamPointsShopItemsResponse {
+				items,
+				warning: None,
+				stale: false,
+			})
+		}
+		Err(fetch_error) => {
+			eprintln!("Failed to fetch Steam points shop items for app {app_id}: {fetch_error}");
+			if let Some((cached_items, _)) = cached_items_entry {
+				return Ok(SteamPointsShopItemsResponse {
+					items: cached_items,
+					warning: Some(format!(
+						"Could not refresh points shop items: {} Using cached data.",
+						normalize_backend_warning_message(&fetch_error)
+					)),
+					stale: true,
+				});
+			}
+			Ok(SteamPointsShopItemsResponse {
+				items: Vec::new(),
+				warning: Some(normalize_backend_warning_message(&fetch_error)),
+				stale: false,
+			})
+		}
+	}
+}
+
+/// The user's badges, from the in-memory cache when fresh. Shared by the badge overview and
+/// per-game card status so both count against one Web API call.
+fn load_steam_badges(
+	client: &Client,
+	api_key: &str,
+	steam_id: &str,
+	refresh: bool,
+) -> Result<steam_badges::SteamPlayerBadges, String> {
+	let cache_key = format!("steam_badges:{steam_id}");
+	let cached_badges = crate::cache::get_cached(&cache_key, steam_badges::STEAM_BADGES_CACHE_TTL_SECS)
+		.filter(|_| !refresh)
+		.and_then(|value| serde_json::from_value::<steam_badges::SteamPlayerBadges>(value).ok());
+	if let Some(badges) = cached_badges {
+		return Ok(badges);
+	}
+	let badges = steam_badges::fetch_steam_badges(client, api_key, steam_id)?;
+	if let Ok(value) = serde_json::to_value(&badges) {
+		crate::cache::set_cached(&cache_key, value);
+	}
+	Ok(badges)
+}
+
+/// The user's Steam level, XP and badges for the profile stats panel. `refresh` skips the
+/// cache; offline, the last fetched badges are returned as stale.
+pub(crate) fn get_steam_badges(
+	state: &AppState,
+	refresh: Option<bool>,
+) -> AppResult<steam_badges::SteamBadgesResponse> {
+	let AuthedContext { connection, user } = AuthedContext::new(state)?;
+	let steam_id = user
+		.steam_id
+		.as_deref()
+		.ok_or_else(|| AppError::unauthorized("steam_not_linked", "Steam is not linked for this account"))?;
+	let game_names = load_owned_steam_games_by_app_id(&connection, &user.id)?
+		.into_iter()
+		.map(|(app_id, game)| (app_id, game.name))
+		.collect::<HashMap<_, _>>();
+	let empty_response = |warning: &str| {
+		let mut response = steam_badges::summarize_steam_badges(
+			steam_badges::SteamPlayerBadges {
+				player_level: 0,
+				player_xp: 0,
+				xp_needed_to_level_up: 0,
+				xp_needed_current_level: 0,
+				badges: Vec::new(),
+			},
+			&game_names,
+			false,
+		);
+		response.warning = Some(warning.to_owned());
+		response
+	};
+
+	if state.is_offline() {
+		let stale_badges = crate::cache::get_cached(&format!("steam_badges:{steam_id}"), i64::MAX)
+			.and_then(|value| serde_json::from_value::<steam_badges::SteamPlayerBadges>(value).ok());
+		return Ok(match stale_badges {
+			Some(badges) => steam_badges::summarize_steam_badges(badges, &game_names, true),
+			None => empty_response(OFFLINE_MODE_MESSAGE),
+		});
+	}
+
+	let steam_api_key = state.steam_api_key();
+	let Some(api_key) = steam_api_key
+		.as_deref()
+		.map(str::trim)
+		.filter(|value| !value.is_empty())
+	else {
+		return Ok(empty_response(
+			"Steam badges are unavailable because STEAM_API_KEY is not configured.",
+		));
+	};
+	let client = state.http_client()?;
+	let badges = load_steam_badges(&client, api_key, steam_id, refresh.unwrap_or(false))?;
+	Ok(steam_badges::summarize_steam_badges(badges, &game_names, false))
+}
+
+/// Whether a Steam game drops trading cards, the user's badge progress for it and, when the
+/// badge page shows it, how many drops are left. Badge data needs STEAM_API_KEY; without it
+/// only the card availability is reported.
+pub(crate) fn get_game_card_status(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+) -> AppResult<steam_badges::GameCardStatusResponse> {
+	let AuthedGameContext {
+		connection,
+		user,
+		provider: normalized_provider,
+		external_id: normalized_external_id,
+	} = AuthedGameContext::new(state, &provider, &external_id)?;
+
+	if normalized_provider != "steam" {
+		return Ok(steam_badges::GameCardStatusResponse::without_cards(None));
+	}
+	let Ok(app_id) = normalized_external_id.parse::<u64>() else {
+		return Ok(steam_badges::GameCardStatusResponse::without_cards(Some(String::from(
+			"This Steam app ID is invalid.",
+		))));
+	};
+
+	let cache_key = format!("game_card_status:{}:{app_id}", user.id);
+	let cached_status = crate::cache::get_cached(&cache_key, steam_badges::STEAM_GAME_CARD_STATUS_CACHE_TTL_SECS)
+		.and_then(|value| serde_json::from_value::<steam_badges::GameCardStatusResponse>(value).ok());
+	if let Some(status) = cached_status {
+		return Ok(status);
+	}
+
+	let client = state.http_client()?;
+	if state.is_offline() {
+		let stale_status = crate::cache::get_cached(&cache_key, i64::MAX)
+			.and_then(|value| serde_json::from_value::<steam_badges::GameCardStatusResponse>(value).ok());
+		if let Some(status) = stale_status {
+			return Ok(steam_badges::GameCardStatusResponse { stale: true, ..status });
+		}
+		let has_trading_cards = steam_badges::app_has_trading_cards(&connection, &client, app_id, false)?;
+		let mut status = steam_badges::GameCardStatusResponse::without_cards(Some(String::from(OFFLINE_MODE_MESSAGE)));
+		status.has_trading_cards = has_trading_cards;
+		return Ok(status);
+	}
+
+	if !steam_badges::app_has_trading_cards(&connection, &client, app_id, true)? {
+		let status = steam_badges::GameCardStatusResponse::without_cards(None);
+		if let Ok(value) = serde_json::to_value(&status) {
+			crate::cache::set_cached(&cache_key, value);
+		}
+		return Ok(status);
+	}
+
+	let Some(steam_id) = user.steam_id.as_deref() else {
+		return Ok(steam_badges::game_card_status(
+			app_id,
+			None,
+			None,
+			Some(String::from("Link Steam to see badge progress and card drops.")),
+		));
+	};
+	let mut warnings = Vec::new();
+	let steam_api_key = state.steam_api_key();
+	let badges = match steam_api_key.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+		Some(api_key) => match load_steam_badges(&client, api_key, steam_id, false) {
+			Ok(badges) => Some(badges),
+			Err(error) => {
+				warnings.push(normalize_backend_warning_message(&error));
+				None
+			}
+		},
+		None => {
+			warnings.push(String::from("Badge progress is unavailable because STEAM_API_KEY is not configured."));
+			None
+		}
+	};
+	let card_drops_remaining = steam_badges::fetch_card_drops_remaining(&client, steam_id, app_id)
+		.unwrap_or_else(|error| {
+			warnings.push(normalize_backend_warning_message(&error));
+			None
+		});
+
+	let warning = (!warnings.is_empty()).then(|| warnings.join(" "));
+	let status = steam_badges::game_card_status(app_id, badges.as_ref(), card_drops_remaining, warning);
+	if status.warning.is_none() {
+		if let Ok(value) = serde_json::to_value(&status) {
+			crate::cache::set_cached(&cache_key, value);
+		}
+	}
+	Ok(status)
+}
+
+#[derive(serde::Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamShortcutArtworkInput {
+	pub cover_path: Option<String>,
+	pub wide_cover_path: Option<String>,
+	pub hero_path: Option<String>,
+	pub logo_path: Option<String>,
+}
+
+/// Adds a non-Steam library entry to the user's Steam shortcuts. ROMs launch their emulator
+/// directly; launcher games go through Catalyst's `play` deep link.
+pub(crate) fn add_to_steam(
+	state: &AppState,
+	provider: String,
+	external_id: String,
+	artwork: Option<SteamShortcutArtworkInput>,
+) -> AppResult<steam_shortcuts::SteamShortcutResponse> {
+	let AuthedGameContext { connection, user, provider, external_id } =
+		AuthedGameContext::new(state, &provider, &external_id)?;
+	if provider == "steam" {
+		return Err(AppError::validation(
+			"already_steam_game",
+			"Steam games are already in the Steam library",
+		));
+	}
+	let steam_id = user
+		.steam_id
+		.as_deref()
+		.ok_or_else(|| AppError::unauthorized("steam_not_linked", "Steam is not linked for this account"))?;
+	let name = find_game_name(&connection, &user.id, &provider, &external_id)?.unwrap_or_else(|| external_id.clone());
+
+	let target = if provider == "rom" {
+		let (executable_path, launch_options) =
+			rom_library::rom_external_launch_command(&connection, &user.id, &external_id)?;
+		let start_directory = Path::new(&executable_path)
+			.parent()
+			.map(|directory| directory.display().to_string())
+			.unwrap_or_default();
+		steam_shortcuts::SteamShortcutTarget {
+			name,
+			executable_path,
+			start_directory,
+			launch_options,
+			icon_path: None,
+		}
+	} else {
+		let executable_path = std::env::current_exe().map_err(|error| {
+			AppError::internal(
+				"current_exe_unavailable",
+				format!("Failed to resolve the Catalyst executable: {error}"),
+			)
+		})?;
+		let start_directory = executable_path
+			.parent()
+			.map(|directory| directory.display().to_string())
+			.unwrap_or_default();
+		steam_shortcuts::SteamShortcutTarget {
+			name,
+			executable_path: executable_path.display().to_string(),
+			start_directory,
+			launch_options: format!("{DEEP_LINK_SCHEME}://play/{provider}/{external_id}"),
+			icon_path: Some(executable_path.display().to_string()),
+		}
+	};
+
+	let artwork = artwork.unwrap_or_default();
+	let grid_images = [
+		("p", artwork.cover_path),
+		("", artwork.wide_cover_path),
+		("_hero", artwork.hero_path),
+		("_logo", artwork.logo_path),
+	]
+	.into_iter()
+	.filter_map(|(suffix, path)| {
+		path.map(|value| value.trim().to_owned())
+			.filter(|value| !value.is_empty())
+			.map(|value| (suffix, PathBuf::from(value)))
+	})
+	.collect::<Vec<_>>();
+	if let Some((_, missing_path)) = grid_images.iter().find(|(_, path)| !path.is_file()) {
+		return Err(AppError::validation(
+			"artwork_not_found",
+			format!("Artwork file not found at {}", missing_path.display()),
+		));
+	}
+	let grid_image_refs = grid_images
+		.iter()
+		.map(|(suffix, path)| (*suffix, path.as_path()))
+		.collect::<Vec<_>>();
+
+	Ok(steam_shortcuts::add_steam_shortcut(
+		state.steam_root_override().as_deref(),
+		steam_id,
+		&target,
+		&grid_image_refs,
 	)?)
 }
 
+pub(crate) fn list_steam_config_backups(
+	state: &AppState,
+) -> AppResult<Vec<steam_config_writer::SteamConfigBackupResponse>> {
+	require_authenticated_user(state)?;
+	Ok(steam_config_writer::list_steam_config_backups()?)
+}
+
+/// Restores a Steam config file from a backup. Steam must be closed, since it rewrites its
+/// config files on exit and would overwrite the restored copy.
+pub(crate) fn restore_steam_config_backup(
+	state: &AppState,
+	backup_id: String,
+) -> AppResult<steam_config_writer::SteamConfigBackupResponse> {
+	require_authenticated_user(state)?;
+	if process_watcher::is_steam_client_running() {
+		return Err(AppError::conflict(
+			"steam_running",
+			"Close Steam before restoring a config backup.",
+		));
+	}
+	steam_config_writer::restore_steam_config_backup(backup_id.trim())
+		.map_err(|error| AppError::not_found("steam_config_backup_restore_failed", error))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn list_game_versions_betas_flags_cached_data_as_stale() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let state = database.signed_in_state_with_steam_root();
+		let betas = || list_game_versions_betas(&state, String::from("steam"), String::from("620")).expect("list betas");
+
+		cache_steam_app_betas(&database.connection, 620, &default_game_version_beta_options()).expect("cache betas");
+		let fresh = betas();
+		assert!(!fresh.stale);
+		assert_eq!(fresh.warning, None);
+
+		database
+			.connection
+			.execute("UPDATE steam_app_betas SET fetched_at = '2020-01-01T00:00:00Z'", [])
+			.expect("age cache");
+		// Online without an API key the old cache is all there is.
+		let expired = betas();
+		assert!(expired.stale);
+		assert!(expired.warning.is_some_and(|warning| warning.contains("STEAM_API_KEY")));
+
+		state.offline_mode.store(true, Ordering::SeqCst);
+		let offline = betas();
+		assert!(offline.stale);
+		assert_eq!(offline.warning, None);
+		assert!(state.http_client().is_err());
+
+		database.connection.execute("DELETE FROM steam_app_betas", []).expect("clear cache");
+		let uncached = betas();
+		assert!(!uncached.stale);
+		assert_eq!(uncached.warning.as_deref(), Some(OFFLINE_MODE_MESSAGE));
+
+		state.offline_mode.store(false, Ordering::SeqCst);
+		assert!(!betas().stale);
+	}
+
+	#[test]
+	fn get_steam_badges_summarizes_cached_badges_and_reports_why_none_are_shown() {
+		let database = test_support::test_database();
+		database.insert_game("steam", "620", "Portal 2");
+		let state = database.signed_in_state();
+		let error = get_steam_badges(&state, None).err().expect("Steam not linked");
+		assert_eq!(error.code, "steam_not_linked");
+
+		// A Steam ID of its own keeps the process-wide cache apart from other tests.
+		let steam_id = "76561197960287933";
+		set_user_steam_id(&database.connection, &database.user.id, steam_id).expect("link Steam");
+		let without_key = get_steam_badges(&state, None).expect("badges without API key");
+		assert_eq!(without_key.player_level, 0);
+		assert!(without_key.warning.is_some_and(|warning| warning.contains("STEAM_API_KEY")));
+
+		state.offline_mode.store(true, Ordering::SeqCst);
+		let uncached = get_steam_badges(&state, None).expect("offline badges");
+		assert_eq!(uncached.warning.as_deref(), Some(OFFLINE_MODE_MESSAGE));
+
+		let badges = steam_badges::SteamPlayerBadges {
+			player_level: 12,
+			player_xp: 1300,
+			xp_needed_to_level_up: 100,
+			xp_needed_current_level: 1200,
+			badges: vec![
+				steam_badges::SteamBadge {
+					badge_id: 1,
+					app_id: Some(620),
+					level: 5,
+					xp: 500,
+					foil: false,
+					completed_at: Some(String::from("2024-02-01T00:00:00+00:00")),
+				},
+				steam_badges::SteamBadge {
+					badge_id: 1,
+					app_id: Some(400),
+					level: 2,
+					xp: 200,
+					foil: false,
+					completed_at: Some(String::from("2024-03-01T00:00:00+00:00")),
+				},
+			],
+		};
+		crate::cache::set_cached(
+			&format!("steam_badges:{steam_id}"),
+			serde_json::to_value(&badges).expect("serialize badges"),
+		);
+		let offline = get_steam_badges(&state, None).expect("stale badges");
+		assert!(offline.stale);
+		assert_eq!((offline.player_level, offline.game_badge_count, offline.unfinished_game_badge_count), (12, 2, 1));
+		assert_eq!(
+			offline.badges.iter().map(|badge| (badge.app_id, badge.game_name.as_deref())).collect::<Vec<_>>(),
+			vec![(Some(400), None), (Some(620), Some("Portal 2"))]
+		);
+
+		// Online with a key, fresh badges come from the cache without calling the Web API.
+		state.offline_mode.store(false, Ordering::SeqCst);
+		*state.steam_api_key.lock().expect("API key lock") = Some(String::from("test-key"));
+		let cached = get_steam_badges(&state, None).expect("cached badges");
+		assert!(!cached.stale);
+		assert_eq!((cached.player_xp, cached.warning), (1300, None));
+	}
+}
@@ -0,0 +1,432 @@
+use crate::*;
+
+const SUGGEST_GAME_DEFAULT_COUNT: usize = 3;
+const SUGGEST_GAME_MAX_COUNT: usize = 10;
+const SUGGEST_GAME_MAX_WEIGHT: f64 = 10.0;
+/// Review scores from fewer reviews than this are too noisy to favor a game.
+const SUGGEST_GAME_MIN_REVIEWS: i64 = 50;
+/// A review share at or above this is mentioned as a reason.
+const SUGGEST_GAME_GOOD_REVIEW_RATIO: f64 = 0.8;
+/// Store tags that mark a game as quick to finish. Completion times aren't cached, so these
+/// stand in for them.
+const SUGGEST_GAME_SHORT_TAGS: [&str; 2] = ["Short", "Casual"];
+/// Kinds that aren't worth suggesting from the backlog.
+const SUGGEST_GAME_EXCLUDED_KINDS: [&str; 6] = ["music", "tool", "application", "video", "dlc", "demo"];
+
+/// How strongly each property favors a game, from 0 (ignored) to `SUGGEST_GAME_MAX_WEIGHT`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct SuggestGameWeights {
+    pub short: f64,
+    pub review_score: f64,
+    pub installed: f64,
+    pub mood: f64,
+}
+
+impl Default for SuggestGameWeights {
+    fn default() -> Self {
+        Self {
+            short: 1.0,
+            review_score: 1.0,
+            installed: 1.0,
+            mood: 2.0,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GameSuggestionResponse {
+    pub provider: String,
+    pub external_id: String,
+    pub name: String,
+    pub playtime_minutes: i64,
+    pub installed: bool,
+    /// The relative weight the game was drawn with; higher is more likely.
+    pub score: f64,
+    pub reasons: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct BacklogGame {
+    provider: String,
+    external_id: String,
+    name: String,
+    playtime_minutes: i64,
+    installed: bool,
+    tags: Vec<String>,
+    total_positive: i64,
+    total_reviews: i64,
+}
+
+pub(crate) fn normalize_suggest_game_weights(weights: SuggestGameWeights) -> Result<SuggestGameWeights, String> {
+    for (name, weight) in [
+        ("short", weights.short),
+        ("reviewScore", weights.review_score),
+        ("installed", weights.installed),
+        ("mood", weights.mood),
+    ] {
+        if !(0.0..=SUGGEST_GAME_MAX_WEIGHT).contains(&weight) {
+            return Err(format!("The {name} weight must be between 0 and {SUGGEST_GAME_MAX_WEIGHT}"));
+        }
+    }
+    Ok(weights)
+}
+
+/// Owned games with less than the backlog's playtime that aren't hidden, completed or, when
+/// `exclude_private`, marked private. Personal and store tags are merged for mood matching.
+fn load_backlog_games(connection: &Connection, user_id: &str, exclude_private: bool) -> Result<Vec<BacklogGame>, String> {
+    let mut personal_tags: HashMap<String, Vec<String>> = HashMap::new();
+    let mut statement = connection
+        .prepare("SELECT provider, external_id, tag FROM game_user_tags WHERE user_id = ?1")
+        .map_err(|error| format!("Failed to prepare personal tag query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                game_membership_key(&row.get::<_, String>(0)?, &row.get::<_, String>(1)?),
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query personal tags: {error}"))?;
+    for row in rows {
+        let (game_key, tag) = row.map_err(|error| format!("Failed to decode personal tag row: {error}"))?;
+        personal_tags.entry(game_key).or_default().push(tag);
+    }
+
+    let excluded_kinds = serde_json::to_string(&SUGGEST_GAME_EXCLUDED_KINDS)
+        .map_err(|error| format!("Failed to serialize excluded game kinds: {error}"))?;
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT
+              g.provider,
+              g.external_id,
+              COALESCE(display.display_name, g.name),
+              g.playtime_minutes,
+              g.installed,
+              tags.tags_json,
+              COALESCE(reviews.total_positive, 0),
+              COALESCE(reviews.total_reviews, 0)
+            FROM games g
+            LEFT JOIN game_privacy_settings privacy
+              ON privacy.user_id = g.user_id
+              AND privacy.provider = g.provider
+              AND privacy.external_id = g.external_id
+            LEFT JOIN game_display_names display
+              ON display.user_id = g.user_id
+              AND display.provider = g.provider
+              AND display.external_id = g.external_id
+            LEFT JOIN game_achievement_progress achievement_progress
+              ON achievement_progress.user_id = g.user_id
+              AND achievement_progress.provider = g.provider
+              AND achievement_progress.external_id = g.external_id
+            LEFT JOIN steam_app_store_tags tags
+              ON g.provider = 'steam'
+              AND tags.app_id = g.external_id
+            LEFT JOIN steam_app_review_summaries reviews
+              ON g.provider = 'steam'
+              AND reviews.app_id = g.external_id
+            WHERE g.user_id = ?1
+              AND g.playtime_minutes < ?2
+              AND g.kind NOT IN (SELECT value FROM json_each(?3))
+              AND COALESCE(privacy.hide_in_library, 0) = 0
+              AND (?4 = 0 OR COALESCE(privacy.mark_as_private, 0) = 0)
+              AND COALESCE(achievement_progress.completed, 0) = 0
+            ORDER BY g.provider ASC, g.external_id ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare backlog query: {error}"))?;
+    let rows = statement
+        .query_map(
+            params![user_id, library_stats::LIBRARY_STATS_BACKLOG_MAX_MINUTES, excluded_kinds, exclude_private],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)? != 0,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, i64>(6)?,
+                    row.get::<_, i64>(7)?,
+                ))
+            },
+        )
+        .map_err(|error| format!("Failed to query backlog: {error}"))?;
+
+    let mut games = Vec::new();
+    for row in rows {
+        let (provider, external_id, name, playtime_minutes, installed, tags_json, total_positive, total_reviews) =
+            row.map_err(|error| format!("Failed to decode backlog row: {error}"))?;
+        let mut tags = tags_json
+            .and_then(|json| serde_json::from_str::<Vec<String>>(&json).ok())
+            .unwrap_or_default();
+        tags.extend(
+            personal_tags
+                .remove(&game_membership_key(&provider, &external_id))
+                .unwrap_or_default(),
+        );
+        games.push(BacklogGame {
+            provider,
+            external_id,
+            name,
+            playtime_minutes,
+            installed,
+            tags,
+            total_positive,
+            total_reviews,
+        });
+    }
+    Ok(games)
+}
+
+/// The weight a game is drawn with and why. Every game starts at 1 so ones without any
+/// metadata can still come up.
+fn score_backlog_game(game: &BacklogGame, weights: &SuggestGameWeights, moods: &[String]) -> (f64, Vec<String>) {
+    let has_tag = |tag: &str| game.tags.iter().any(|game_tag| game_tag.eq_ignore_ascii_case(tag));
+    let mut score = 1.0;
+    let mut reasons = Vec::new();
+
+    if weights.short > 0.0 && SUGGEST_GAME_SHORT_TAGS.iter().any(|tag| has_tag(tag)) {
+        score += weights.short;
+        reasons.push(String::from("Tagged as short"));
+    }
+    if weights.review_score > 0.0 && game.total_reviews >= SUGGEST_GAME_MIN_REVIEWS {
+        let positive_ratio = game.total_positive as f64 / game.total_reviews as f64;
+        score += weights.review_score * positive_ratio;
+        if positive_ratio >= SUGGEST_GAME_GOOD_REVIEW_RATIO {
+            reasons.push(format!("{}% positive Steam reviews", (positive_ratio * 100.0).round()));
+        }
+    }
+    if weights.installed > 0.0 && game.installed {
+        score += weights.installed;
+        reasons.push(String::from("Already installed"));
+    }
+    if weights.mood > 0.0 && !moods.is_empty() {
+        let matched = moods.iter().filter(|mood| has_tag(mood)).cloned().collect::<Vec<_>>();
+        if !matched.is_empty() {
+            score += weights.mood * matched.len() as f64 / moods.len() as f64;
+            reasons.push(format!("Matches your mood: {}", matched.join(", ")));
+        }
+    }
+    if reasons.is_empty() {
+        reasons.push(if game.playtime_minutes == 0 {
+            String::from("Never played")
+        } else {
+            format!("Only {} minutes played", game.playtime_minutes)
+        });
+    }
+    (score, reasons)
+}
+
+/// Draws `count` games without replacement, each with probability proportional to its score
+/// (Efraimidis–Spirakis: keep the largest `u^(1/score)`).
+fn pick_weighted<T>(scored: Vec<(f64, T)>, count: usize, mut random_unit: impl FnMut() -> f64) -> Vec<(f64, T)> {
+    let mut keyed = scored
+        .into_iter()
+        .map(|(score, item)| (random_unit().powf(1.0 / score), score, item))
+        .collect::<Vec<_>>();
+    keyed.sort_by(|left, right| right.0.total_cmp(&left.0));
+    keyed
+        .into_iter()
+        .take(count)
+        .map(|(_, score, item)| (score, item))
+        .collect()
+}
+
+/// Uniform in `[0, 1)` from a v4 UUID's low 53 bits, which are all random.
+fn random_unit() -> f64 {
+    (Uuid::new_v4().as_u128() as u64 & ((1 << 53) - 1)) as f64 / (1u64 << 53) as f64
+}
+
+/// Picks a few backlog games at random, favoring the ones the weights and moods point at.
+pub(crate) fn suggest_backlog_games(
+    connection: &Connection,
+    user_id: &str,
+    weights: &SuggestGameWeights,
+    moods: &[String],
+    count: Option<usize>,
+    exclude_private: bool,
+) -> Result<Vec<GameSuggestionResponse>, String> {
+    let moods = moods
+        .iter()
+        .map(|mood| mood.trim().to_owned())
+        .filter(|mood| !mood.is_empty())
+        .collect::<Vec<_>>();
+    let scored = load_backlog_games(connection, user_id, exclude_private)?
+        .into_iter()
+        .map(|game| {
+            let (score, reasons) = score_backlog_game(&game, weights, &moods);
+            (score, (game, reasons))
+        })
+        .collect::<Vec<_>>();
+    let count = count
+        .unwrap_or(SUGGEST_GAME_DEFAULT_COUNT)
+        .clamp(1, SUGGEST_GAME_MAX_COUNT);
+
+    Ok(pick_weighted(scored, count, random_unit)
+        .into_iter()
+        .map(|(score, (game, reasons))| GameSuggestionResponse {
+            provider: game.provider,
+            external_id: game.external_id,
+            name: game.name,
+            playtime_minutes: game.playtime_minutes,
+            installed: game.installed,
+            score,
+            reasons,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggest_backlog_games_scores_weights_and_skips_played_games() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for (external_id, name, kind, playtime_minutes, installed) in [
+            ("620", "Portal 2", "game", 0, 1),
+            ("400", "Portal", "game", 30, 0),
+            ("440", "Team Fortress 2", "game", 900, 1),
+            ("1070560", "Steam Linux Runtime", "tool", 0, 1),
+        ] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, last_synced_at)
+                    VALUES (?1, 'steam', ?2, ?3, ?4, ?5, ?6, '2024-01-01T00:00:00Z')
+                    ",
+                    params![user.id, external_id, name, kind, playtime_minutes, installed],
+                )
+                .expect("insert game");
+        }
+        connection
+            .execute_batch(
+                "
+                INSERT INTO steam_app_store_tags (app_id, tags_json, fetched_at)
+                VALUES ('620', '[\"Puzzle\",\"Co-op\"]', '2024-01-01T00:00:00Z');
+                INSERT INTO steam_app_review_summaries (app_id, score_description, total_positive, total_reviews, fetched_at)
+                VALUES ('620', 'Overwhelmingly Positive', 98, 100, '2024-01-01T00:00:00Z');
+                ",
+            )
+            .expect("insert metadata");
+        connection
+            .execute(
+                "
+                INSERT INTO game_user_tags (user_id, provider, external_id, tag, created_at)
+                VALUES (?1, 'steam', '400', 'Short', '2024-01-01T00:00:00Z')
+                ",
+                params![user.id],
+            )
+            .expect("insert personal tag");
+
+        let backlog = load_backlog_games(&connection, &user.id, false).expect("load backlog");
+        assert_eq!(
+            backlog.iter().map(|game| game.external_id.as_str()).collect::<Vec<_>>(),
+            vec!["400", "620"]
+        );
+
+        let weights = SuggestGameWeights::default();
+        let moods = vec![String::from("puzzle"), String::from("cozy")];
+        let (score, reasons) = score_backlog_game(&backlog[1], &weights, &moods);
+        assert!((score - 3.98).abs() < 1e-9, "unexpected score {score}");
+        assert_eq!(
+            reasons,
+            vec!["98% positive Steam reviews", "Already installed", "Matches your mood: puzzle"]
+        );
+        let (score, reasons) = score_backlog_game(&backlog[0], &weights, &moods);
+        assert_eq!((score, reasons), (2.0, vec![String::from("Tagged as short")]));
+
+        let mut draws = [0.5, 0.5].into_iter();
+        let picked = pick_weighted(vec![(1.0, "low"), (4.0, "high")], 1, || draws.next().unwrap_or(0.0));
+        assert_eq!(picked, vec![(4.0, "high")]);
+
+        assert!(normalize_suggest_game_weights(SuggestGameWeights {
+            mood: 11.0,
+            ..SuggestGameWeights::default()
+        })
+        .is_err());
+        let suggestions = suggest_backlog_games(&connection, &user.id, &weights, &moods, Some(5), false)
+            .expect("suggest games");
+        assert_eq!(suggestions.len(), 2);
+    }
+
+    #[test]
+    fn suggest_backlog_games_skips_hidden_completed_and_locked_private_games() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        assert!(suggest_backlog_games(connection, &user.id, &SuggestGameWeights::default(), &[], None, true)
+            .expect("empty backlog")
+            .is_empty());
+
+        for (external_id, name) in [("620", "Portal 2"), ("400", "Portal"), ("420", "Portal Stories"), ("70", "Half-Life")] {
+            database.insert_game("steam", external_id, name);
+        }
+        connection
+            .execute(
+                "
+                INSERT INTO game_privacy_settings (user_id, provider, external_id, hide_in_library, mark_as_private, updated_at)
+                VALUES (?1, 'steam', '400', 1, 0, '2024-01-01T00:00:00Z'), (?1, 'steam', '420', 0, 1, '2024-01-01T00:00:00Z')
+                ",
+                params![user.id],
+            )
+            .expect("insert privacy settings");
+        connection
+            .execute(
+                "
+                INSERT INTO game_achievement_progress (user_id, provider, external_id, unlocked_count, total_count, completed, refreshed_at)
+                VALUES (?1, 'steam', '70', 10, 10, 1, '2024-01-01T00:00:00Z')
+                ",
+                params![user.id],
+            )
+            .expect("insert achievement progress");
+        let backlog_ids = |exclude_private: bool| {
+            load_backlog_games(connection, &user.id, exclude_private)
+                .expect("load backlog")
+                .into_iter()
+                .map(|game| game.external_id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(backlog_ids(true), vec![String::from("620")]);
+        assert_eq!(backlog_ids(false), vec![String::from("420"), String::from("620")]);
+        // A count of zero still suggests one game.
+        let suggestions = suggest_backlog_games(connection, &user.id, &SuggestGameWeights::default(), &[], Some(0), false)
+            .expect("suggest games");
+        assert_eq!(suggestions.len(), 1);
+    }
+
+    #[test]
+    fn score_backlog_game_ignores_zero_weights_and_thin_reviews() {
+        let game = BacklogGame {
+            provider: String::from("steam"),
+            external_id: String::from("620"),
+            name: String::from("Portal 2"),
+            playtime_minutes: 45,
+            installed: true,
+            tags: vec![String::from("Short"), String::from("Puzzle")],
+            total_positive: 40,
+            total_reviews: 40,
+        };
+        let weights = SuggestGameWeights {
+            short: 0.0,
+            review_score: 5.0,
+            installed: 0.0,
+            mood: 0.0,
+        };
+
+        let (score, reasons) = score_backlog_game(&game, &weights, &[String::from("puzzle")]);
+        assert_eq!((score, reasons), (1.0, vec![String::from("Only 45 minutes played")]));
+        let unplayed = BacklogGame { playtime_minutes: 0, ..game };
+        assert_eq!(score_backlog_game(&unplayed, &weights, &[]).1, vec![String::from("Never played")]);
+
+        for weight in [-1.0, f64::NAN, SUGGEST_GAME_MAX_WEIGHT + 0.5] {
+            let weights = SuggestGameWeights { installed: weight, ..SuggestGameWeights::default() };
+            assert!(normalize_suggest_game_weights(weights).is_err(), "weight {weight} should be rejected");
+        }
+        let weights = SuggestGameWeights { short: SUGGEST_GAME_MAX_WEIGHT, ..SuggestGameWeights::default() };
+        assert!(normalize_suggest_game_weights(weights).is_ok());
+    }
+}
@@ -0,0 +1,4 @@
+// Headless entry point for scripting, cron-driven syncs and Steam Deck game mode shortcuts.
+fn main() -> std::process::ExitCode {
+    catalyst_lib::run_cli()
+}
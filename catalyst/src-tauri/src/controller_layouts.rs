@@ -0,0 +1,297 @@
+use crate::*;
+
+const STEAM_CONTROLLER_CONFIGS_APP_ID: &str = "241100";
+const CONFIGSET_FILE_PREFIX: &str = "configset_controller_";
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ControllerLayoutResponse {
+    pub id: String,
+    pub source: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub controller_type: Option<String>,
+    pub path: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ControllerLayoutSelectionResponse {
+    pub controller_type: String,
+    pub layout_id: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ControllerLayoutsResponse {
+    pub layouts: Vec<ControllerLayoutResponse>,
+    pub selections: Vec<ControllerLayoutSelectionResponse>,
+}
+
+/// Steam keeps per-account Steam Input data under `steamapps/common/Steam Controller Configs`,
+/// keyed by the same account ID as the userdata folder.
+fn resolve_controller_config_directory(steam_root: &Path, steam_id: &str) -> Result<PathBuf, String> {
+    let userdata_directory = resolve_steam_userdata_directory(steam_root, steam_id)?;
+    let account_id = userdata_directory
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .ok_or_else(|| String::from("Could not determine Steam account folder"))?;
+    Ok(steam_root
+        .join("steamapps")
+        .join("common")
+        .join("Steam Controller Configs")
+        .join(account_id)
+        .join("config"))
+}
+
+/// Lists the layouts Steam has on disk for an app: Valve's official templates, the user's own
+/// saved configs for the app, and any community configs the app is currently bound to.
+pub(crate) fn list_controller_layouts(
+    steam_root_override: Option<&str>,
+    steam_id: &str,
+    app_id: u64,
+) -> Result<ControllerLayoutsResponse, String> {
+    let steam_root = resolve_steam_root_path(steam_root_override)
+        .ok_or_else(|| String::from("Could not locate local Steam installation"))?;
+    let config_directory = resolve_controller_config_directory(&steam_root, steam_id)?;
+    let app_id_key = app_id.to_string();
+
+    let mut layouts = Vec::new();
+    for path in list_vdf_files(&steam_root.join("controller_base").join("templates")) {
+        if let Some(layout) = read_controller_layout(&path, "template") {
+            layouts.push(layout);
+        }
+    }
+    for path in list_vdf_files(&config_directory.join(&app_id_key)) {
+        if let Some(layout) = read_controller_layout(&path, "personal") {
+            layouts.push(layout);
+        }
+    }
+
+    let selections = read_controller_layout_selections(&config_directory, &app_id_key)?;
+    let workshop_directory = steam_root
+        .join("steamapps")
+        .join("workshop")
+        .join("content")
+        .join(STEAM_CONTROLLER_CONFIGS_APP_ID);
+    for published_file_id in selections
+        .iter()
+        .filter_map(|selection| selection.layout_id.as_deref()?.strip_prefix("workshop:"))
+    {
+        if layouts.iter().any(|layout| layout.id == format!("workshop:{published_file_id}")) {
+            continue;
+        }
+        let item_directory = workshop_directory.join(published_file_id);
+        let layout = list_vdf_files(&item_directory)
+            .first()
+            .and_then(|path| read_controller_layout(path, "workshop"))
+            .map(|layout| ControllerLayoutResponse {
+                id: format!("workshop:{published_file_id}"),
+                ..layout
+            })
+            .unwrap_or_else(|| ControllerLayoutResponse {
+                id: format!("workshop:{published_file_id}"),
+                source: String::from("workshop"),
+                title: format!("Community layout {published_file_id}"),
+                description: None,
+                controller_type: None,
+                path: item_directory.display().to_string(),
+            });
+        layouts.push(layout);
+    }
+
+    Ok(ControllerLayoutsResponse { layouts, selections })
+}
+
+fn list_vdf_files(directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut paths = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .map(|extension| extension.eq_ignore_ascii_case("vdf"))
+                    .unwrap_or(false)
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+fn read_controller_layout(path: &Path, source: &str) -> Option<ControllerLayoutResponse> {
+    let file_name = path.file_name()?.to_string_lossy().into_owned();
+    let contents = fs::read_to_string(path).ok()?;
+    let document = parse_vdf_document(&contents).ok()?;
+    let mappings = vdf_find_object_value(&document, "controller_mappings");
+    let read_text = |key: &str| {
+        mappings
+            .and_then(|value| vdf_get_text_entry(value, key))
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && !value.starts_with('#'))
+            .map(str::to_owned)
+    };
+    let title = read_text("title").unwrap_or_else(|| {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().replace('_', " "))
+            .unwrap_or_else(|| file_name.clone())
+    });
+
+    Some(ControllerLayoutResponse {
+        id: format!("{source}:{file_name}"),
+        source: source.to_owned(),
+        title,
+        description: read_text("description"),
+        controller_type: read_text("controller_type"),
+        path: path.display().to_string(),
+    })
+}
+
+/// Reads the app's binding from each `configset_controller_<type>.vdf`, which is how Steam
+/// records the layout chosen for every controller family.
+fn read_controller_layout_selections(
+    config_directory: &Path,
+    app_id_key: &str,
+) -> Result<Vec<ControllerLayoutSelectionResponse>, String> {
+    let mut selections = Vec::new();
+    for path in list_vdf_files(config_directory) {
+        let Some(controller_type) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.strip_prefix(CONFIGSET_FILE_PREFIX))
+            .map(str::to_owned)
+        else {
+            continue;
+        };
+        let contents = fs::read_to_string(&path)
+            .map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+        let document = parse_vdf_document(&contents)?;
+        let layout_id = vdf_find_object_value(&document, "controller_config")
+            .and_then(|config| vdf_find_object_value(config, app_id_key))
+            .and_then(parse_controller_layout_binding);
+        selections.push(ControllerLayoutSelectionResponse {
+            controller_type,
+            layout_id,
+        });
+    }
+    Ok(selections)
+}
+
+fn parse_controller_layout_binding(binding: &VdfValue) -> Option<String> {
+    if let Some(published_file_id) = vdf_get_text_entry(binding, "workshop").filter(|value| !value.is_empty()) {
+        return Some(format!("workshop:{published_file_id}"));
+    }
+    vdf_get_text_entry(binding, "template")
+        .filter(|value| !value.is_empty())
+        .map(|template| format!("template:{template}"))
+}
+
+/// Binds an app to a template or community layout for one controller family, or clears the
+/// binding when `layout_id` is `None`. Steam rewrites these files on exit, so changes made
+/// while the client is running may be overwritten.
+pub(crate) fn save_controller_layout_selection(
+    steam_root_override: Option<&str>,
+    steam_id: &str,
+    app_id: u64,
+    controller_type: &str,
+    layout_id: Option<&str>,
+) -> Result<(), String> {
+    let controller_type = controller_type.trim();
+    if controller_type.is_empty()
+        || !controller_type
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || character == '_')
+    {
+        return Err(String::from("Controller type must be letters, digits and underscores only"));
+    }
+    let binding = match layout_id.map(str::trim).filter(|value| !value.is_empty()) {
+        None => None,
+        Some(layout_id) => match layout_id.split_once(':') {
+            Some(("template", template)) if !template.is_empty() && !template.contains(['/', '\\']) => {
+                Some(("template", template.to_owned()))
+            }
+            Some(("workshop", published_file_id))
+                if !published_file_id.is_empty()
+                    && published_file_id.chars().all(|character| character.is_ascii_digit()) =>
+            {
+                Some(("workshop", published_file_id.to_owned()))
+            }
+            _ => {
+                return Err(String::from(
+                    "Layout must be an official template or a community layout",
+                ))
+            }
+        },
+    };
+
+    let steam_root = resolve_steam_root_path(steam_root_override)
+        .ok_or_else(|| String::from("Could not locate local Steam installation"))?;
+    let config_directory = resolve_controller_config_directory(&steam_root, steam_id)?;
+    let configset_path = config_directory.join(format!("{CONFIGSET_FILE_PREFIX}{controller_type}.vdf"));
+    let mut document = match fs::read_to_string(&configset_path) {
+        Ok(contents) => parse_vdf_document(&contents)?,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => VdfValue::Object(Vec::new()),
+        Err(error) => {
+            return Err(format!(
+                "Failed to read {}: {error}",
+                configset_path.display()
+            ))
+        }
+    };
+
+    let app_id_key = app_id.to_string();
+    let controller_config = vdf_ensure_object_path_mut(&mut document, &["controller_config"]);
+    match binding {
+        None => vdf_remove_entry(controller_config, &app_id_key),
+        Some((binding_key, binding_value)) => {
+            let app_binding = vdf_ensure_object_path_mut(controller_config, &[app_id_key.as_str()]);
+            vdf_remove_entry(app_binding, "template");
+            vdf_remove_entry(app_binding, "workshop");
+            vdf_set_text_entry(app_binding, binding_key, &binding_value);
+        }
+    }
+
+    fs::create_dir_all(&config_directory)
+        .map_err(|error| format!("Failed to create {}: {error}", config_directory.display()))?;
+    steam_config_writer::write_steam_config_file(&configset_path, serialize_vdf_document(&document))
+        .map_err(|error| format!("Failed to write {}: {error}", configset_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_controller_layout_binding_prefers_workshop_layouts() {
+        let document = parse_vdf_document(
+            r#"
+"controller_config"
+{
+	"620"
+	{
+		"workshop"		"1234567"
+		"template"		"controller_xbox360_gamepad_joystick.vdf"
+	}
+	"400"
+	{
+		"template"		"controller_xbox360_gamepad_joystick.vdf"
+	}
+}
+"#,
+        )
+        .expect("configset should parse");
+        let config = vdf_find_object_value(&document, "controller_config").expect("config should exist");
+
+        assert_eq!(
+            vdf_find_object_value(config, "620").and_then(parse_controller_layout_binding),
+            Some(String::from("workshop:1234567"))
+        );
+        assert_eq!(
+            vdf_find_object_value(config, "400").and_then(parse_controller_layout_binding),
+            Some(String::from("template:controller_xbox360_gamepad_joystick.vdf"))
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use crate::*;
+
+const CUSTOM_ARTWORK_DIRECTORY: &str = "custom-artwork";
+const CUSTOM_ARTWORK_MAX_BYTES: u64 = 20 * 1024 * 1024;
+
+/// Identifies an image by its signature rather than its file name, so a renamed file can't
+/// slip through.
+fn detect_image_extension(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("png")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("jpg")
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some("gif")
+    } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        Some("webp")
+    } else {
+        None
+    }
+}
+
+fn custom_artwork_directory(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(CUSTOM_ARTWORK_DIRECTORY)
+}
+
+/// Removal is best-effort: a leftover file only wastes space.
+pub(crate) fn remove_custom_artwork_file(artwork_path: &str) {
+    match fs::remove_file(artwork_path) {
+        Ok(()) => {}
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+        Err(error) => eprintln!("Could not remove custom artwork {artwork_path}: {error}"),
+    }
+}
+
+/// Copies the image at `source_path` into Catalyst's data directory, so it keeps working after
+/// the original is moved or deleted. `name` identifies the owner in the file name.
+pub(crate) fn copy_custom_artwork_file(db_path: &Path, name: &str, source_path: &Path) -> Result<PathBuf, String> {
+    let metadata = fs::metadata(source_path)
+        .map_err(|error| format!("Could not read {}: {error}", source_path.display()))?;
+    if !metadata.is_file() {
+        return Err(format!("{} is not a file", source_path.display()));
+    }
+    if metadata.len() > CUSTOM_ARTWORK_MAX_BYTES {
+        return Err(format!(
+            "Artwork must be at most {} MB",
+            CUSTOM_ARTWORK_MAX_BYTES / (1024 * 1024)
+        ));
+    }
+    let bytes = fs::read(source_path).map_err(|error| format!("Could not read {}: {error}", source_path.display()))?;
+    let extension = detect_image_extension(&bytes)
+        .ok_or_else(|| String::from("Artwork must be a PNG, JPEG, GIF or WebP image"))?;
+
+    // Each upload gets a new file name so webviews don't keep showing a cached older image.
+    let file_stem = format!("{name}-{}", Uuid::new_v4().simple())
+        .chars()
+        .map(|character| if character.is_ascii_alphanumeric() || character == '-' { character } else { '_' })
+        .collect::<String>();
+    let artwork_directory = custom_artwork_directory(db_path);
+    fs::create_dir_all(&artwork_directory)
+        .map_err(|error| format!("Failed to create {}: {error}", artwork_directory.display()))?;
+    let artwork_path = artwork_directory.join(format!("{file_stem}.{extension}"));
+    fs::write(&artwork_path, &bytes).map_err(|error| format!("Failed to write {}: {error}", artwork_path.display()))?;
+    Ok(artwork_path)
+}
+
+fn remove_stored_artwork(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<(), String> {
+    let stored_path = connection
+        .query_row(
+            "SELECT artwork_path FROM game_custom_artwork WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+            params![user_id, provider, external_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to load custom artwork: {error}"))?;
+    if let Some(stored_path) = stored_path {
+        remove_custom_artwork_file(&stored_path);
+    }
+    Ok(())
+}
+
+/// Makes a copy of `source_path` the game's artwork. Returns the managed path.
+pub(crate) fn store_custom_artwork(
+    connection: &Connection,
+    db_path: &Path,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    source_path: &Path,
+) -> Result<PathBuf, String> {
+    let artwork_path =
+        copy_custom_artwork_file(db_path, &format!("{user_id}-{provider}-{external_id}"), source_path)?;
+    remove_stored_artwork(connection, user_id, provider, external_id)?;
+    connection
+        .execute(
+            "
+            INSERT INTO game_custom_artwork (user_id, provider, external_id, artwork_path, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+              artwork_path = excluded.artwork_path,
+              updated_at = excluded.updated_at
+            ",
+            params![
+                user_id,
+                provider,
+                external_id,
+                artwork_path.display().to_string(),
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(|error| format!("Failed to save custom artwork: {error}"))?;
+    Ok(artwork_path)
+}
+
+pub(crate) fn clear_custom_artwork(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<(), String> {
+    remove_stored_artwork(connection, user_id, provider, external_id)?;
+    connection
+        .execute(
+            "DELETE FROM game_custom_artwork WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+            params![user_id, provider, external_id],
+        )
+        .map_err(|error| format!("Failed to clear custom artwork: {error}"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn store_custom_artwork_copies_images_and_replaces_previous_uploads() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let text_file = database.dir.path().join("cover.png");
+        fs::write(&text_file, "not an image").expect("write text file");
+        assert!(store_custom_artwork(&connection, &database.db_path, &user.id, "steam", "620", &text_file).is_err());
+
+        let png_file = database.dir.path().join("cover-upload");
+        fs::write(&png_file, b"\x89PNG\r\n\x1a\nrest").expect("write png");
+        let first = store_custom_artwork(&connection, &database.db_path, &user.id, "steam", "620", &png_file)
+            .expect("store png");
+        assert_eq!(first.extension().and_then(|extension| extension.to_str()), Some("png"));
+
+        let jpg_file = database.dir.path().join("cover.jpg");
+        fs::write(&jpg_file, [0xFF, 0xD8, 0xFF, 0xE0]).expect("write jpg");
+        let second = store_custom_artwork(&connection, &database.db_path, &user.id, "steam", "620", &jpg_file)
+            .expect("store jpg");
+        assert!(!first.exists());
+        assert!(second.is_file());
+
+        clear_custom_artwork(&connection, &user.id, "steam", "620").expect("clear artwork");
+        assert!(!second.exists());
+    }
+
+    #[test]
+    fn detect_image_extension_reads_signatures() {
+        assert_eq!(detect_image_extension(b"GIF89a..."), Some("gif"));
+        assert_eq!(detect_image_extension(b"RIFF\0\0\0\0WEBPVP8 "), Some("webp"));
+        assert_eq!(detect_image_extension(b"RIFF\0\0\0\0WAVE"), None);
+        assert_eq!(detect_image_extension(b"RIFF"), None);
+        assert_eq!(detect_image_extension(b""), None);
+    }
+
+    #[test]
+    fn store_custom_artwork_rejects_bad_sources_and_keeps_the_current_image() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let png_file = database.dir.path().join("cover.png");
+        fs::write(&png_file, b"\x89PNG\r\n\x1a\nrest").expect("write png");
+        let stored = store_custom_artwork(&connection, &database.db_path, &user.id, "steam", "620", &png_file)
+            .expect("store png");
+
+        let oversized_file = database.dir.path().join("huge.png");
+        fs::File::create(&oversized_file)
+            .and_then(|file| file.set_len(CUSTOM_ARTWORK_MAX_BYTES + 1))
+            .expect("create oversized file");
+        for source_path in [
+            oversized_file,
+            database.dir.path().to_path_buf(),
+            database.dir.path().join("missing.png"),
+        ] {
+            assert!(
+                store_custom_artwork(&connection, &database.db_path, &user.id, "steam", "620", &source_path).is_err(),
+                "{} was accepted",
+                source_path.display()
+            );
+        }
+        assert!(stored.is_file());
+        let stored_path: String = connection
+            .query_row(
+                "SELECT artwork_path FROM game_custom_artwork WHERE user_id = ?1 AND external_id = '620'",
+                params![user.id],
+                |row| row.get(0),
+            )
+            .expect("stored artwork");
+        assert_eq!(PathBuf::from(stored_path), stored);
+
+        // Clearing a game without custom artwork is not an error.
+        clear_custom_artwork(&connection, &user.id, "steam", "400").expect("clear missing artwork");
+    }
+}
@@ -0,0 +1,204 @@
+use crate::*;
+
+const DEMO_USER_EMAIL: &str = "demo@catalyst.local";
+const DEMO_GAME_COUNT: u64 = 300;
+const DEMO_APP_ID_BASE: u64 = 9_000_000;
+const DEMO_DOWNLOADING_GAME_COUNT: u64 = 4;
+const DEMO_TITLE_PREFIXES: [&str; 12] = [
+    "Neon", "Crimson", "Silent", "Iron", "Hollow", "Stellar", "Frozen", "Lost", "Savage",
+    "Ancient", "Quantum", "Shadow",
+];
+const DEMO_TITLE_SUFFIXES: [&str; 10] = [
+    "Frontier", "Protocol", "Kingdom", "Odyssey", "Circuit", "Harbor", "Legacy", "Rift",
+    "Garden", "Tactics",
+];
+const DEMO_COLLECTION_NAMES: [&str; 4] = ["Backlog", "Co-op Night", "Completed", "Cozy"];
+
+/// Small deterministic generator so the demo library looks the same on every launch.
+struct DemoRandom(u64);
+
+impl DemoRandom {
+    fn next_value(&mut self) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        self.0 >> 33
+    }
+
+    fn below(&mut self, upper_bound: u64) -> u64 {
+        self.next_value() % upper_bound.max(1)
+    }
+}
+
+/// Seeds the isolated demo database and fake Steam root, returning a fresh session token
+/// for the synthetic demo user.
+pub(crate) fn seed_demo_environment(db_path: &Path, demo_steam_root: &Path) -> Result<String, String> {
+    let connection = open_connection(db_path)?;
+    let existing_user_id = connection
+        .query_row(
+            "SELECT id FROM users WHERE email = ?1",
+            params![DEMO_USER_EMAIL],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query demo user: {error}"))?;
+
+    let user_id = match existing_user_id {
+        Some(user_id) => user_id,
+        None => {
+            let placeholder_password_hash = hash(Uuid::new_v4().to_string(), DEFAULT_COST)
+                .map_err(|error| format!("Failed to hash demo password: {error}"))?;
+            let user = create_user(&connection, DEMO_USER_EMAIL, &placeholder_password_hash, None)?;
+            seed_demo_library(&connection, &user.id)?;
+            user.id
+        }
+    };
+
+    write_demo_steam_manifests(demo_steam_root)?;
+    create_session(&connection, &user_id)
+}
+
+fn demo_app_id(index: u64) -> u64 {
+    DEMO_APP_ID_BASE + index
+}
+
+fn demo_game_name(random: &mut DemoRandom, index: u64) -> String {
+    let prefix = DEMO_TITLE_PREFIXES[random.below(DEMO_TITLE_PREFIXES.len() as u64) as usize];
+    let suffix = DEMO_TITLE_SUFFIXES[random.below(DEMO_TITLE_SUFFIXES.len() as u64) as usize];
+    if index % 7 == 0 {
+        format!("{prefix} {suffix} {}", index % 5 + 2)
+    } else {
+        format!("{prefix} {suffix}")
+    }
+}
+
+fn seed_demo_library(connection: &Connection, user_id: &str) -> Result<(), String> {
+    let mut random = DemoRandom(0x00C0_FFEE);
+    let now = Utc::now();
+    let mut games = Vec::new();
+    let mut seen_names = HashSet::new();
+
+    for index in 0..DEMO_GAME_COUNT {
+        let mut name = demo_game_name(&mut random, index);
+        if !seen_names.insert(name.to_ascii_lowercase()) {
+            name = format!("{name} ({})", index + 1);
+            seen_names.insert(name.to_ascii_lowercase());
+        }
+
+        let kind = match index % 23 {
+            0 => "demo",
+            5 | 11 => "dlc",
+            17 => "music",
+            _ => "game",
+        };
+        let playtime_minutes = if random.below(4) == 0 {
+            0
+        } else {
+            random.below(12_000) as i64
+        };
+        let playtime_2weeks_minutes = if playtime_minutes > 0 && random.below(6) == 0 {
+            random.below(900) as i64
+        } else {
+            0
+        };
+        let last_played_at = if playtime_2weeks_minutes > 0 {
+            Some((now - ChronoDuration::hours(random.below(24 * 14) as i64)).to_rfc3339())
+        } else if playtime_minutes > 0 {
+            Some((now - ChronoDuration::hours(random.below(24 * 400) as i64)).to_rfc3339())
+        } else {
+            None
+        };
+
+        games.push(LibraryGameInput {
+            external_id: demo_app_id(index).to_string(),
+            name,
+            kind: kind.to_owned(),
+            playtime_minutes,
+            playtime_2weeks_minutes,
+            installed: index % 5 == 0,
+            artwork_url: None,
+            last_synced_at: now.to_rfc3339(),
+            last_played_at,
+        });
+    }
+
+    replace_provider_games(connection, user_id, "steam", &games, false)?;
+
+    for index in (0..DEMO_GAME_COUNT).step_by(17) {
+        upsert_game_favorite(connection, user_id, "steam", &demo_app_id(index).to_string())?;
+    }
+
+    for (collection_index, collection_name) in DEMO_COLLECTION_NAMES.iter().enumerate() {
+        let (collection_id, _) =
+            get_or_create_collection_id_by_name(connection, user_id, collection_name)?;
+        for index in (collection_index as u64..DEMO_GAME_COUNT).step_by(9 + collection_index * 4) {
+            add_game_to_collection_membership(
+                connection,
+                user_id,
+                &collection_id,
+                "steam",
+                &demo_app_id(index).to_string(),
+            )?;
+        }
+    }
+
+    for index in 0..DEMO_GAME_COUNT {
+        if index % 3 != 0 {
+            continue;
+        }
+        let achievements_count = 10 + random.below(90);
+        cache_steam_app_features(
+            connection,
+            demo_app_id(index),
+            true,
+            Some(achievements_count),
+            index % 2 == 0,
+            None,
+            if index % 4 == 0 { Some("Full") } else { None },
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_demo_steam_manifests(demo_steam_root: &Path) -> Result<(), String> {
+    let steamapps_directory = demo_steam_root.join("steamapps");
+    fs::create_dir_all(&steamapps_directory)
+        .map_err(|error| format!("Failed to create demo Steam library: {error}"))?;
+
+    let mut random = DemoRandom(0x0BAD_5EED);
+    for index in 0..DEMO_GAME_COUNT {
+        let installed = index % 5 == 0;
+        let downloading = index % 5 == 1 && index / 5 < DEMO_DOWNLOADING_GAME_COUNT;
+        if !installed && !downloading {
+            continue;
+        }
+
+        let app_id = demo_app_id(index);
+        let size_on_disk = (1 + random.below(80)) * 1024 * 1024 * 1024;
+        let (state_flags, bytes_downloaded) = if downloading {
+            fs::create_dir_all(steamapps_directory.join("downloading").join(app_id.to_string()))
+                .map_err(|error| format!("Failed to create demo download directory: {error}"))?;
+            (
+                STEAM_APP_STATE_DOWNLOADING | STEAM_APP_STATE_UPDATE_RUNNING,
+                size_on_disk / 100 * (10 + random.below(80)),
+            )
+        } else {
+            (STEAM_APP_STATE_FULLY_INSTALLED, size_on_disk)
+        };
+
+        let manifest = format!(
+            "\"AppState\"\n{{\n\t\"appid\"\t\t\"{app_id}\"\n\t\"name\"\t\t\"Demo App {app_id}\"\n\t\"installdir\"\t\t\"demo-{app_id}\"\n\t\"StateFlags\"\t\t\"{state_flags}\"\n\t\"SizeOnDisk\"\t\t\"{size_on_disk}\"\n\t\"BytesToDownload\"\t\t\"{size_on_disk}\"\n\t\"BytesDownloaded\"\t\t\"{bytes_downloaded}\"\n}}\n"
+        );
+        fs::write(
+            steamapps_directory.join(format!("appmanifest_{app_id}.acf")),
+            manifest,
+        )
+        .map_err(|error| format!("Failed to write demo app manifest: {error}"))?;
+        fs::create_dir_all(steamapps_directory.join("common").join(format!("demo-{app_id}")))
+            .map_err(|error| format!("Failed to create demo install directory: {error}"))?;
+    }
+
+    Ok(())
+}
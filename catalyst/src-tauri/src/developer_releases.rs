@@ -0,0 +1,495 @@
+use crate::*;
+
+const STEAM_STORE_SEARCH_ENDPOINT: &str = "https://store.steampowered.com/search/results/";
+/// A developer or publisher is watched once the user owns this many of its games.
+const DEVELOPER_WATCH_MIN_OWNED_GAMES: i64 = 2;
+const DEVELOPER_WATCH_MAX_ENTITIES: usize = 10;
+const DEVELOPER_SEARCH_RESULT_COUNT: usize = 25;
+const DEVELOPER_RELEASES_DEFAULT_LIMIT: usize = 50;
+
+static SEARCH_RESULT_ROW_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(
+        r#"(?s)data-ds-appid="(\d+)".*?<span class="title">(.*?)</span>.*?class="[^"]*search_released[^"]*">(.*?)</div>"#,
+    )
+    .expect("search result row pattern should compile")
+});
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DeveloperRole {
+    Developer,
+    Publisher,
+}
+
+impl DeveloperRole {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Developer => "developer",
+            Self::Publisher => "publisher",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "developer" => Some(Self::Developer),
+            "publisher" => Some(Self::Publisher),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct WatchedDeveloperResponse {
+    pub name: String,
+    pub role: DeveloperRole,
+    pub owned_games: i64,
+    pub last_scanned_at: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeveloperReleaseResponse {
+    pub app_id: String,
+    pub name: String,
+    pub developer: String,
+    pub role: DeveloperRole,
+    /// As the store shows it, e.g. `12 Mar, 2025` or `Coming soon`.
+    pub release_date: Option<String>,
+    pub first_seen_at: String,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeveloperReleasesResponse {
+    pub releases: Vec<DeveloperReleaseResponse>,
+    pub watched: Vec<WatchedDeveloperResponse>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct DeveloperReleaseScan {
+    pub scanned: usize,
+    pub failed: usize,
+    pub new_releases: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct StoreSearchResult {
+    app_id: String,
+    name: String,
+    release_date: Option<String>,
+}
+
+/// Developers and publishers the user owns at least `DEVELOPER_WATCH_MIN_OWNED_GAMES` games
+/// from, by the cached store metadata of their Steam games. A studio that both develops and
+/// publishes is watched once, as a developer.
+fn load_owned_developers(connection: &Connection, user_id: &str) -> Result<Vec<(String, DeveloperRole, i64)>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT m.developers_json, m.publishers_json
+            FROM games g
+            JOIN steam_app_metadata m ON m.app_id = g.external_id
+            WHERE g.user_id = ?1 AND g.provider = 'steam'
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare owned developer query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((row.get::<_, Option<String>>(0)?, row.get::<_, Option<String>>(1)?))
+        })
+        .map_err(|error| format!("Failed to query owned developers: {error}"))?;
+
+    let mut counts: HashMap<(String, DeveloperRole), i64> = HashMap::new();
+    for row in rows {
+        let (developers_json, publishers_json) =
+            row.map_err(|error| format!("Failed to decode owned developers: {error}"))?;
+        for (json, role) in [
+            (developers_json, DeveloperRole::Developer),
+            (publishers_json, DeveloperRole::Publisher),
+        ] {
+            let names = json
+                .and_then(|json| serde_json::from_str::<Vec<String>>(&json).ok())
+                .unwrap_or_default();
+            for name in names.into_iter().map(|name| name.trim().to_owned()).collect::<HashSet<_>>() {
+                if !name.is_empty() {
+                    *counts.entry((name, role)).or_default() += 1;
+                }
+            }
+        }
+    }
+
+    let developer_names = counts
+        .keys()
+        .filter(|(_, role)| *role == DeveloperRole::Developer)
+        .map(|(name, _)| name.to_lowercase())
+        .collect::<HashSet<_>>();
+    let mut developers = counts
+        .into_iter()
+        .filter(|((name, role), owned_games)| {
+            *owned_games >= DEVELOPER_WATCH_MIN_OWNED_GAMES
+                && (*role == DeveloperRole::Developer || !developer_names.contains(&name.to_lowercase()))
+        })
+        .map(|((name, role), owned_games)| (name, role, owned_games))
+        .collect::<Vec<_>>();
+    developers.sort_by(|left, right| right.2.cmp(&left.2).then(left.0.cmp(&right.0)));
+    developers.truncate(DEVELOPER_WATCH_MAX_ENTITIES);
+    Ok(developers)
+}
+
+fn parse_store_search_results(html: &str) -> Vec<StoreSearchResult> {
+    SEARCH_RESULT_ROW_PATTERN
+        .captures_iter(html)
+        .map(|captures| {
+            let release_date = decode_basic_html_entities(captures[3].trim());
+            StoreSearchResult {
+                app_id: captures[1].to_owned(),
+                name: decode_basic_html_entities(captures[2].trim()),
+                release_date: (!release_date.is_empty()).then_some(release_date),
+            }
+        })
+        .collect()
+}
+
+/// The developer's or publisher's newest games on the store, newest first.
+fn search_store_releases(client: &Client, name: &str, role: DeveloperRole) -> Result<Vec<StoreSearchResult>, String> {
+    let mut request_url = Url::parse(STEAM_STORE_SEARCH_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam store search endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair(role.as_str(), name)
+        .append_pair("sort_by", "Released_DESC")
+        // Games only, so soundtracks and DLC of owned games don't count as new releases.
+        .append_pair("category1", "998")
+        .append_pair("count", &DEVELOPER_SEARCH_RESULT_COUNT.to_string())
+        .append_pair("infinite", "1");
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam store search request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam store search request failed with status {}", response.status()));
+    }
+    let body = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam store search response: {error}"))?;
+    let html = body
+        .get("results_html")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| String::from("Steam store search response has no results"))?;
+    Ok(parse_store_search_results(html))
+}
+
+/// Records the games in `results` that weren't seen before and returns the new ones. The first
+/// scan of a developer only records a baseline, so its back catalog isn't reported as new.
+fn record_store_releases(
+    connection: &Connection,
+    user_id: &str,
+    name: &str,
+    role: DeveloperRole,
+    owned_games: i64,
+    results: &[StoreSearchResult],
+) -> Result<Vec<StoreSearchResult>, String> {
+    let owned_app_ids = load_owned_steam_games_by_app_id(connection, user_id)?;
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| format!("Failed to start developer release transaction: {error}"))?;
+    let scanned_at = Utc::now().to_rfc3339();
+    let is_first_scan = transaction
+        .query_row(
+            "SELECT last_scanned_at FROM developer_watches WHERE user_id = ?1 AND name = ?2",
+            params![user_id, name],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query developer watch: {error}"))?
+        .flatten()
+        .is_none();
+    transaction
+        .execute(
+            "
+            INSERT INTO developer_watches (user_id, name, role, owned_games, last_scanned_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, name) DO UPDATE SET
+              role = excluded.role,
+              owned_games = excluded.owned_games,
+              last_scanned_at = excluded.last_scanned_at
+            ",
+            params![user_id, name, role.as_str(), owned_games, scanned_at],
+        )
+        .map_err(|error| format!("Failed to save developer watch: {error}"))?;
+
+    let mut new_releases = Vec::new();
+    for result in results {
+        let is_owned = result
+            .app_id
+            .parse::<u64>()
+            .is_ok_and(|app_id| owned_app_ids.contains_key(&app_id));
+        let inserted = transaction
+            .execute(
+                "
+                INSERT OR IGNORE INTO developer_releases
+                  (user_id, developer, app_id, name, release_date, baseline, first_seen_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                ",
+                params![
+                    user_id,
+                    name,
+                    result.app_id,
+                    result.name,
+                    result.release_date,
+                    is_first_scan || is_owned,
+                    scanned_at
+                ],
+            )
+            .map_err(|error| format!("Failed to save developer release: {error}"))?;
+        if inserted > 0 && !is_first_scan && !is_owned {
+            new_releases.push(result.clone());
+        }
+    }
+    transaction
+        .commit()
+        .map_err(|error| format!("Failed to commit developer releases: {error}"))?;
+    Ok(new_releases)
+}
+
+/// Drops developers that are no longer among the most owned, with their releases, so they start
+/// from a fresh baseline if they come back.
+fn forget_unwatched_developers(
+    connection: &Connection,
+    user_id: &str,
+    developers: &[(String, DeveloperRole, i64)],
+) -> Result<(), String> {
+    let watched_names = developers.iter().map(|(name, _, _)| name.as_str()).collect::<HashSet<_>>();
+    let mut statement = connection
+        .prepare("SELECT name FROM developer_watches WHERE user_id = ?1")
+        .map_err(|error| format!("Failed to prepare developer watch query: {error}"))?;
+    let stale_names = statement
+        .query_map(params![user_id], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to query developer watches: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode developer watch: {error}"))?
+        .into_iter()
+        .filter(|name| !watched_names.contains(name.as_str()))
+        .collect::<Vec<_>>();
+    for name in stale_names {
+        connection
+            .execute(
+                "DELETE FROM developer_releases WHERE user_id = ?1 AND developer = ?2",
+                params![user_id, name],
+            )
+            .map_err(|error| format!("Failed to delete developer releases: {error}"))?;
+        connection
+            .execute(
+                "DELETE FROM developer_watches WHERE user_id = ?1 AND name = ?2",
+                params![user_id, name],
+            )
+            .map_err(|error| format!("Failed to delete developer watch: {error}"))?;
+    }
+    Ok(())
+}
+
+/// Searches the store for the newest games of every watched developer and publisher and notifies
+/// the user about ones that appeared since the last scan. A failing search is counted and the
+/// others continue.
+pub(crate) fn refresh_developer_releases(
+    connection: &Connection,
+    client: &Client,
+    user_id: &str,
+) -> Result<DeveloperReleaseScan, String> {
+    let mut scan = DeveloperReleaseScan::default();
+    let developers = load_owned_developers(connection, user_id)?;
+    forget_unwatched_developers(connection, user_id, &developers)?;
+    for (name, role, owned_games) in developers {
+        let results = match search_store_releases(client, &name, role) {
+            Ok(results) => results,
+            Err(error) => {
+                eprintln!("Steam store search for {} {name} failed: {error}", role.as_str());
+                scan.failed += 1;
+                continue;
+            }
+        };
+        scan.scanned += 1;
+        for release in record_store_releases(connection, user_id, &name, role, owned_games, &results)? {
+            scan.new_releases += 1;
+            if let Err(error) = notifications::create_notification(
+                connection,
+                user_id,
+                notifications::NOTIFICATION_KIND_DEVELOPER_RELEASE,
+                &format!("New from {name}"),
+                &format!("{} is on the Steam store.", release.name),
+                Some(("steam", &release.app_id)),
+            ) {
+                eprintln!("Failed to record developer release notification: {error}");
+            }
+        }
+    }
+    Ok(scan)
+}
+
+/// Games that appeared from watched developers and publishers since watching started, newest
+/// first. Games the user has since bought are left out.
+pub(crate) fn load_developer_releases(
+    connection: &Connection,
+    user_id: &str,
+    limit: Option<usize>,
+) -> Result<DeveloperReleasesResponse, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT name, role, owned_games, last_scanned_at
+            FROM developer_watches
+            WHERE user_id = ?1
+            ORDER BY owned_games DESC, name ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare developer watch query: {error}"))?;
+    let watched = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, Option<String>>(3)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query developer watches: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode developer watch: {error}"))?
+        .into_iter()
+        .filter_map(|(name, role, owned_games, last_scanned_at)| {
+            Some(WatchedDeveloperResponse {
+                name,
+                role: DeveloperRole::parse(&role)?,
+                owned_games,
+                last_scanned_at,
+            })
+        })
+        .collect::<Vec<_>>();
+    let roles = watched
+        .iter()
+        .map(|developer| (developer.name.clone(), developer.role))
+        .collect::<HashMap<_, _>>();
+
+    let owned_app_ids = load_owned_steam_games_by_app_id(connection, user_id)?;
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT app_id, name, developer, release_date, first_seen_at
+            FROM developer_releases
+            WHERE user_id = ?1 AND baseline = 0
+            ORDER BY first_seen_at DESC, developer ASC, app_id ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare developer release query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query developer releases: {error}"))?;
+
+    let limit = limit.unwrap_or(DEVELOPER_RELEASES_DEFAULT_LIMIT);
+    let mut seen_app_ids = HashSet::new();
+    let mut releases = Vec::new();
+    for row in rows {
+        let (app_id, name, developer, release_date, first_seen_at) =
+            row.map_err(|error| format!("Failed to decode developer release: {error}"))?;
+        let is_owned = app_id.parse::<u64>().is_ok_and(|app_id| owned_app_ids.contains_key(&app_id));
+        let Some(role) = roles.get(&developer).copied() else {
+            continue;
+        };
+        if is_owned || !seen_app_ids.insert(app_id.clone()) {
+            continue;
+        }
+        releases.push(DeveloperReleaseResponse {
+            app_id,
+            name,
+            developer,
+            role,
+            release_date,
+            first_seen_at,
+        });
+        if releases.len() >= limit {
+            break;
+        }
+    }
+    Ok(DeveloperReleasesResponse { releases, watched })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn developer_releases_baseline_first_scan_and_report_later_additions() {
+        let html = r#"
+            <a href="https://store.steampowered.com/app/1145350/Hades_II/" data-ds-appid="1145350" class="search_result_row">
+              <div class="col search_name ellipsis"><span class="title">Hades II</span></div>
+              <div class="col search_released responsive_secondrow">Coming soon</div>
+            </a>
+            <a href="https://store.steampowered.com/app/1145360/Hades/" data-ds-appid="1145360" class="search_result_row">
+              <div class="col search_name ellipsis"><span class="title">Hades</span></div>
+              <div class="col search_released responsive_secondrow">17 Sep, 2020</div>
+            </a>
+        "#;
+        let results = parse_store_search_results(html);
+        assert_eq!(
+            results,
+            vec![
+                StoreSearchResult {
+                    app_id: String::from("1145350"),
+                    name: String::from("Hades II"),
+                    release_date: Some(String::from("Coming soon")),
+                },
+                StoreSearchResult {
+                    app_id: String::from("1145360"),
+                    name: String::from("Hades"),
+                    release_date: Some(String::from("17 Sep, 2020")),
+                },
+            ]
+        );
+
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for (app_id, developers, publishers) in [
+            ("1145360", r#"["Supergiant Games"]"#, r#"["Supergiant Games"]"#),
+            ("237930", r#"["Supergiant Games"]"#, r#"["Supergiant Games"]"#),
+            ("620", r#"["Valve"]"#, r#"["Valve"]"#),
+        ] {
+            database.insert_game("steam", app_id, app_id);
+            connection
+                .execute(
+                    "
+                    INSERT INTO steam_app_metadata (app_id, app_type, developers_json, publishers_json, fetched_at)
+                    VALUES (?1, 'game', ?2, ?3, '2024-01-01T00:00:00Z')
+                    ",
+                    params![app_id, developers, publishers],
+                )
+                .expect("insert metadata");
+        }
+        assert_eq!(
+            load_owned_developers(&connection, &user.id).expect("load developers"),
+            vec![(String::from("Supergiant Games"), DeveloperRole::Developer, 2)]
+        );
+
+        let new_releases =
+            record_store_releases(&connection, &user.id, "Supergiant Games", DeveloperRole::Developer, 2, &results[1..])
+                .expect("record baseline");
+        assert!(new_releases.is_empty());
+        let new_releases =
+            record_store_releases(&connection, &user.id, "Supergiant Games", DeveloperRole::Developer, 2, &results)
+                .expect("record releases");
+        assert_eq!(new_releases, results[..1].to_vec());
+
+        let response = load_developer_releases(&connection, &user.id, None).expect("load releases");
+        assert_eq!(
+            response.releases.iter().map(|release| release.name.as_str()).collect::<Vec<_>>(),
+            vec!["Hades II"]
+        );
+        assert_eq!(response.watched.len(), 1);
+    }
+}
@@ -0,0 +1,235 @@
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const DISCORD_IPC_OP_HANDSHAKE: u32 = 0;
+const DISCORD_IPC_OP_FRAME: u32 = 1;
+const DISCORD_IPC_SOCKET_COUNT: u32 = 10;
+const DISCORD_IPC_MAX_FRAME_BYTES: usize = 64 * 1024;
+
+trait DiscordIpcStream: Read + Write + Send {}
+
+impl<T: Read + Write + Send> DiscordIpcStream for T {}
+
+/// Minimal Discord RPC client speaking the local IPC protocol: a little-endian opcode and
+/// length header followed by a JSON payload.
+pub struct DiscordPresence {
+    stream: Box<dyn DiscordIpcStream>,
+}
+
+impl DiscordPresence {
+    pub fn connect(client_id: &str) -> Result<Self, String> {
+        let mut stream = open_discord_ipc_stream()?;
+        write_frame(
+            stream.as_mut(),
+            DISCORD_IPC_OP_HANDSHAKE,
+            &json!({ "v": 1, "client_id": client_id }),
+        )?;
+        read_frame(stream.as_mut())?;
+        Ok(Self { stream })
+    }
+
+    pub fn set_activity(&mut self, details: &str, started_at_unix: i64) -> Result<(), String> {
+        self.send_activity(json!({
+            "details": details,
+            "timestamps": { "start": started_at_unix },
+            "instance": false,
+        }))
+    }
+
+    pub fn clear_activity(&mut self) -> Result<(), String> {
+        self.send_activity(Value::Null)
+    }
+
+    fn send_activity(&mut self, activity: Value) -> Result<(), String> {
+        write_frame(
+            self.stream.as_mut(),
+            DISCORD_IPC_OP_FRAME,
+            &json!({
+                "cmd": "SET_ACTIVITY",
+                "args": { "pid": std::process::id(), "activity": activity },
+                "nonce": Uuid::new_v4().to_string(),
+            }),
+        )?;
+        let response = read_frame(self.stream.as_mut())?;
+        if response.get("evt").and_then(Value::as_str) == Some("ERROR") {
+            let message = response
+                .pointer("/data/message")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error");
+            return Err(format!("Discord rejected the presence update: {message}"));
+        }
+        Ok(())
+    }
+}
+
+fn discord_ipc_socket_paths() -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        return (0..DISCORD_IPC_SOCKET_COUNT)
+            .map(|index| PathBuf::from(format!(r"\\.\pipe\discord-ipc-{index}")))
+            .collect();
+    }
+
+    let mut directories = Vec::new();
+    for variable in ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"] {
+        if let Ok(value) = std::env::var(variable) {
+            let directory = PathBuf::from(value);
+            directories.push(directory.join("app/com.discordapp.Discord"));
+            directories.push(directory.join("snap.discord"));
+            directories.push(directory);
+        }
+    }
+    directories.push(PathBuf::from("/tmp"));
+
+    directories
+        .iter()
+        .flat_map(|directory| {
+            (0..DISCORD_IPC_SOCKET_COUNT)
+                .map(move |index| directory.join(format!("discord-ipc-{index}")))
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn open_discord_ipc_path(path: &Path) -> std::io::Result<Box<dyn DiscordIpcStream>> {
+    Ok(Box::new(std::os::unix::net::UnixStream::connect(path)?))
+}
+
+#[cfg(not(unix))]
+fn open_discord_ipc_path(path: &Path) -> std::io::Result<Box<dyn DiscordIpcStream>> {
+    let pipe = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)?;
+    Ok(Box::new(pipe))
+}
+
+fn open_discord_ipc_stream() -> Result<Box<dyn DiscordIpcStream>, String> {
+    discord_ipc_socket_paths()
+        .iter()
+        .find_map(|path| open_discord_ipc_path(path).ok())
+        .ok_or_else(|| String::from("Discord is not running or its IPC socket could not be found"))
+}
+
+fn write_frame(stream: &mut dyn DiscordIpcStream, opcode: u32, payload: &Value) -> Result<(), String> {
+    let body = serde_json::to_vec(payload)
+        .map_err(|error| format!("Failed to encode Discord IPC payload: {error}"))?;
+    let mut frame = Vec::with_capacity(body.len() + 8);
+    frame.extend_from_slice(&opcode.to_le_bytes());
+    frame.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&body);
+    stream
+        .write_all(&frame)
+        .map_err(|error| format!("Failed to write Discord IPC frame: {error}"))
+}
+
+fn read_frame(stream: &mut dyn DiscordIpcStream) -> Result<Value, String> {
+    let mut header = [0_u8; 8];
+    stream
+        .read_exact(&mut header)
+        .map_err(|error| format!("Failed to read Discord IPC header: {error}"))?;
+    let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+    if length > DISCORD_IPC_MAX_FRAME_BYTES {
+        return Err(format!("Discord IPC frame is too large ({length} bytes)"));
+    }
+
+    let mut body = vec![0_u8; length];
+    stream
+        .read_exact(&mut body)
+        .map_err(|error| format!("Failed to read Discord IPC payload: {error}"))?;
+    serde_json::from_slice(&body).map_err(|error| format!("Failed to decode Discord IPC payload: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Replays `incoming` to the reader and records everything written.
+    #[derive(Default)]
+    struct FakeIpcStream {
+        incoming: Cursor<Vec<u8>>,
+        outgoing: Vec<u8>,
+    }
+
+    impl Read for FakeIpcStream {
+        fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+            self.incoming.read(buffer)
+        }
+    }
+
+    impl Write for FakeIpcStream {
+        fn write(&mut self, buffer: &[u8]) -> std::io::Result<usize> {
+            self.outgoing.write(buffer)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn encoded_frame(opcode: u32, payload: &Value) -> Vec<u8> {
+        let mut stream = FakeIpcStream::default();
+        write_frame(&mut stream, opcode, payload).expect("write frame");
+        stream.outgoing
+    }
+
+    fn stream_reading(bytes: Vec<u8>) -> FakeIpcStream {
+        FakeIpcStream {
+            incoming: Cursor::new(bytes),
+            outgoing: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn frames_carry_a_little_endian_header_and_round_trip() {
+        let payload = json!({ "v": 1, "client_id": "1234" });
+        let frame = encoded_frame(DISCORD_IPC_OP_HANDSHAKE, &payload);
+        let body_length = serde_json::to_vec(&payload).expect("encode").len();
+
+        assert_eq!(frame[..4], DISCORD_IPC_OP_HANDSHAKE.to_le_bytes());
+        assert_eq!(frame[4..8], (body_length as u32).to_le_bytes());
+        assert_eq!(frame.len(), body_length + 8);
+        assert_eq!(read_frame(&mut stream_reading(frame)).expect("read frame"), payload);
+    }
+
+    #[test]
+    fn read_frame_rejects_oversized_truncated_and_malformed_frames() {
+        let mut oversized = DISCORD_IPC_OP_FRAME.to_le_bytes().to_vec();
+        oversized.extend_from_slice(&(DISCORD_IPC_MAX_FRAME_BYTES as u32 + 1).to_le_bytes());
+        let error = read_frame(&mut stream_reading(oversized)).expect_err("oversized frame");
+        assert!(error.contains("too large"), "{error}");
+
+        let mut truncated = encoded_frame(DISCORD_IPC_OP_FRAME, &json!({ "evt": "READY" }));
+        truncated.truncate(truncated.len() - 1);
+        let error = read_frame(&mut stream_reading(truncated)).expect_err("truncated frame");
+        assert!(error.contains("payload"), "{error}");
+
+        let error = read_frame(&mut stream_reading(vec![1, 0, 0])).expect_err("short header");
+        assert!(error.contains("header"), "{error}");
+
+        let mut malformed = DISCORD_IPC_OP_FRAME.to_le_bytes().to_vec();
+        malformed.extend_from_slice(&3_u32.to_le_bytes());
+        malformed.extend_from_slice(b"{no");
+        let error = read_frame(&mut stream_reading(malformed)).expect_err("malformed payload");
+        assert!(error.contains("decode"), "{error}");
+    }
+
+    #[test]
+    fn set_activity_surfaces_discord_errors() {
+        let mut responses = encoded_frame(DISCORD_IPC_OP_FRAME, &json!({ "evt": null, "cmd": "SET_ACTIVITY" }));
+        responses.extend(encoded_frame(
+            DISCORD_IPC_OP_FRAME,
+            &json!({ "evt": "ERROR", "data": { "code": 4000, "message": "child \"activity\" fails" } }),
+        ));
+        let mut presence = DiscordPresence {
+            stream: Box::new(stream_reading(responses)),
+        };
+
+        presence.set_activity("Playing Portal 2", 1_700_000_000).expect("accepted update");
+        let error = presence.clear_activity().expect_err("rejected update");
+        assert!(error.contains("child \"activity\" fails"), "{error}");
+    }
+}
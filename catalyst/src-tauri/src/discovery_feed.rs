@@ -0,0 +1,819 @@
+use crate::*;
+
+const STEAM_STORE_BASE_URL: &str = "https://store.steampowered.com";
+const STEAM_WISHLIST_ENDPOINT: &str = "https://api.steampowered.com/IWishlistService/GetWishlist/v1/";
+const STEAM_FEATURED_CATEGORIES_ENDPOINT: &str = "https://store.steampowered.com/api/featuredcategories";
+const DISCOVERY_FEED_REFRESH_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+const DISCOVERY_FEED_REFRESH_TICK: Duration = Duration::from_secs(5 * 60);
+const DISCOVERY_SOURCE_RECOMMENDATION_COUNT: usize = 30;
+/// `appdetails` only accepts several app IDs at once with `filters=price_overview`.
+const DISCOVERY_PRICE_BATCH_SIZE: usize = 50;
+const DISCOVERY_WISHLIST_MAX_DISCOUNTS: usize = 20;
+const DISCOVERY_NEW_RELEASE_MAX_LOOKUPS: usize = 30;
+const DISCOVERY_OWNED_GENRE_COUNT: usize = 5;
+const DISCOVERY_FEED_DEFAULT_LIMIT: usize = 60;
+const DISCOVERY_SOURCE_WISHLIST: &str = "wishlist";
+const DISCOVERY_SOURCE_NEW_RELEASES: &str = "new_releases";
+
+static STORE_APP_LINK_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r#"store\.steampowered\.com/app/(\d+)/([^/"?#]*)"#).expect("store app link pattern should compile")
+});
+static RECOMMENDATION_DESC_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r#"(?s)class="recommendation_desc"[^>]*>(.*?)</div>"#)
+        .expect("recommendation description pattern should compile")
+});
+static HTML_TAG_PATTERN: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"<[^>]+>").expect("html tag pattern should compile"));
+static CURATOR_URL_PATTERN: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"/curator/(\d+)").expect("curator url pattern should compile"));
+static FRANCHISE_URL_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r"/franchise/([^/?#]+)").expect("franchise url pattern should compile")
+});
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum DiscoverySourceKind {
+    Curator,
+    Franchise,
+}
+
+impl DiscoverySourceKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Curator => "curator",
+            Self::Franchise => "franchise",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "curator" => Some(Self::Curator),
+            "franchise" => Some(Self::Franchise),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiscoveryFollowResponse {
+    pub kind: DiscoverySourceKind,
+    pub source_id: String,
+    pub name: String,
+    pub followed_at: String,
+    pub last_fetched_at: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiscoveryFeedItem {
+    /// `curator`, `franchise`, `wishlist_discount` or `new_release`.
+    pub kind: String,
+    /// The followed curator or franchise, or the owned genre a new release matched.
+    pub source_name: Option<String>,
+    pub app_id: String,
+    pub name: String,
+    /// The curator's blurb, when there is one.
+    pub detail: Option<String>,
+    pub discount_percent: Option<i64>,
+    pub price: Option<String>,
+    pub fetched_at: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiscoveryFeedResponse {
+    pub items: Vec<DiscoveryFeedItem>,
+    pub follows: Vec<DiscoveryFollowResponse>,
+    pub refreshed_at: Option<String>,
+}
+
+#[derive(Serialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DiscoveryRefreshResponse {
+    pub refreshed_sources: usize,
+    pub failed_sources: usize,
+    pub items: usize,
+    /// Games from owned developers and publishers that appeared since the last refresh.
+    pub new_developer_releases: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DiscoveryCandidate {
+    app_id: String,
+    name: String,
+    detail: Option<String>,
+    discount_percent: Option<i64>,
+    price: Option<String>,
+}
+
+fn source_key(kind: DiscoverySourceKind, source_id: &str) -> String {
+    format!("{}:{source_id}", kind.as_str())
+}
+
+/// Accepts a bare curator ID or franchise slug as well as a store URL for either.
+pub(crate) fn parse_discovery_source_id(kind: DiscoverySourceKind, input: &str) -> Result<String, String> {
+    let input = input.trim();
+    let url_pattern = match kind {
+        DiscoverySourceKind::Curator => &*CURATOR_URL_PATTERN,
+        DiscoverySourceKind::Franchise => &*FRANCHISE_URL_PATTERN,
+    };
+    let source_id = url_pattern
+        .captures(input)
+        .map(|captures| captures[1].to_owned())
+        .unwrap_or_else(|| input.trim_matches('/').to_owned());
+    let is_valid = match kind {
+        DiscoverySourceKind::Curator => source_id.chars().all(|character| character.is_ascii_digit()),
+        DiscoverySourceKind::Franchise => source_id
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_')),
+    };
+    if source_id.is_empty() || !is_valid {
+        return Err(format!("'{input}' is invalid as a Steam {} ID or URL", kind.as_str()));
+    }
+    Ok(source_id)
+}
+
+pub(crate) fn follow_discovery_source(
+    connection: &Connection,
+    user_id: &str,
+    kind: DiscoverySourceKind,
+    source_id: &str,
+    name: Option<&str>,
+) -> Result<DiscoveryFollowResponse, String> {
+    let name = name
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .unwrap_or(source_id);
+    connection
+        .execute(
+            "
+            INSERT INTO discovery_follows (user_id, kind, source_id, name, followed_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, kind, source_id) DO UPDATE SET name = excluded.name
+            ",
+            params![user_id, kind.as_str(), source_id, name, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to follow {}: {error}", kind.as_str()))?;
+    list_discovery_follows(connection, user_id)?
+        .into_iter()
+        .find(|follow| follow.kind == kind && follow.source_id == source_id)
+        .ok_or_else(|| format!("Followed {} not found", kind.as_str()))
+}
+
+/// Stops following the source and drops its cached recommendations.
+pub(crate) fn unfollow_discovery_source(
+    connection: &Connection,
+    user_id: &str,
+    kind: DiscoverySourceKind,
+    source_id: &str,
+) -> Result<(), String> {
+    let removed = connection
+        .execute(
+            "DELETE FROM discovery_follows WHERE user_id = ?1 AND kind = ?2 AND source_id = ?3",
+            params![user_id, kind.as_str(), source_id],
+        )
+        .map_err(|error| format!("Failed to unfollow {}: {error}", kind.as_str()))?;
+    if removed == 0 {
+        return Err(format!("Followed {} not found", kind.as_str()));
+    }
+    connection
+        .execute(
+            "DELETE FROM discovery_feed_items WHERE user_id = ?1 AND source = ?2",
+            params![user_id, source_key(kind, source_id)],
+        )
+        .map_err(|error| format!("Failed to clear cached recommendations: {error}"))?;
+    Ok(())
+}
+
+pub(crate) fn list_discovery_follows(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<Vec<DiscoveryFollowResponse>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT kind, source_id, name, followed_at, last_fetched_at
+            FROM discovery_follows
+            WHERE user_id = ?1
+            ORDER BY name COLLATE NOCASE ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare follow query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query follows: {error}"))?;
+
+    let mut follows = Vec::new();
+    for row in rows {
+        let (kind, source_id, name, followed_at, last_fetched_at) =
+            row.map_err(|error| format!("Failed to decode follow row: {error}"))?;
+        let Some(kind) = DiscoverySourceKind::parse(&kind) else {
+            continue;
+        };
+        follows.push(DiscoveryFollowResponse {
+            kind,
+            source_id,
+            name,
+            followed_at,
+            last_fetched_at,
+        });
+    }
+    Ok(follows)
+}
+
+fn clean_html_text(html: &str) -> String {
+    decode_basic_html_entities(&HTML_TAG_PATTERN.replace_all(html, " "))
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Reads the `results_html` of a curator or franchise page. Each recommendation links its app
+/// (usually more than once) with a URL slug that doubles as the name; curators add a blurb.
+fn parse_recommendations_html(html: &str) -> Vec<DiscoveryCandidate> {
+    let mut blocks: Vec<(usize, String, String)> = Vec::new();
+    for captures in STORE_APP_LINK_PATTERN.captures_iter(html) {
+        if blocks.last().is_some_and(|(_, app_id, _)| app_id == &captures[1]) {
+            continue;
+        }
+        let start = captures.get(0).map_or(0, |link| link.start());
+        blocks.push((start, captures[1].to_owned(), captures[2].to_owned()));
+    }
+
+    let mut seen_app_ids = HashSet::new();
+    let mut candidates = Vec::new();
+    for (index, (start, app_id, slug)) in blocks.iter().enumerate() {
+        if !seen_app_ids.insert(app_id.clone()) {
+            continue;
+        }
+        let end = blocks.get(index + 1).map_or(html.len(), |(next_start, _, _)| *next_start);
+        let name = slug.replace('_', " ").trim().to_owned();
+        candidates.push(DiscoveryCandidate {
+            app_id: app_id.clone(),
+            name: if name.is_empty() { format!("App {app_id}") } else { name },
+            detail: RECOMMENDATION_DESC_PATTERN
+                .captures(&html[*start..end])
+                .map(|captures| clean_html_text(&captures[1]))
+                .filter(|detail| !detail.is_empty()),
+            discount_percent: None,
+            price: None,
+        });
+    }
+    candidates
+}
+
+fn fetch_source_recommendations(
+    client: &Client,
+    kind: DiscoverySourceKind,
+    source_id: &str,
+) -> Result<Vec<DiscoveryCandidate>, String> {
+    let mut request_url = Url::parse(&format!(
+        "{STEAM_STORE_BASE_URL}/{}/{source_id}/ajaxgetfilteredrecommendations/",
+        kind.as_str()
+    ))
+    .map_err(|error| format!("Failed to parse Steam {} endpoint: {error}", kind.as_str()))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("query", "")
+        .append_pair("start", "0")
+        .append_pair("count", &DISCOVERY_SOURCE_RECOMMENDATION_COUNT.to_string())
+        .append_pair("sort", "recent");
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam {} request failed: {error}", kind.as_str()))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam {} request failed with status {}", kind.as_str(), response.status()));
+    }
+    let body = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam {} response: {error}", kind.as_str()))?;
+    let results_html = body
+        .get("results_html")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| format!("Steam {} {source_id} was not found", kind.as_str()))?;
+    Ok(parse_recommendations_html(results_html))
+}
+
+fn fetch_wishlist_app_ids(client: &Client, steam_id: &str) -> Result<Vec<String>, String> {
+    let mut request_url = Url::parse(STEAM_WISHLIST_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam wishlist endpoint: {error}"))?;
+    request_url.query_pairs_mut().append_pair("steamid", steam_id);
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam wishlist request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam wishlist request failed with status {}", response.status()));
+    }
+    let body = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam wishlist response: {error}"))?;
+    Ok(body
+        .pointer("/response/items")
+        .and_then(serde_json::Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.get("appid").and_then(serde_json::Value::as_u64))
+                .map(|app_id| app_id.to_string())
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Discount percentage and formatted price by app ID, for discounted apps only.
+fn parse_price_overviews(body: &serde_json::Value) -> HashMap<String, (i64, Option<String>)> {
+    let Some(entries) = body.as_object() else {
+        return HashMap::new();
+    };
+    entries
+        .iter()
+        .filter_map(|(app_id, entry)| {
+            // Free apps answer with an empty `data` array instead of an object.
+            let price_overview = entry.pointer("/data/price_overview")?;
+            let discount_percent = price_overview
+                .get("discount_percent")
+                .and_then(serde_json::Value::as_i64)
+                .filter(|discount_percent| *discount_percent > 0)?;
+            let price = price_overview
+                .get("final_formatted")
+                .and_then(serde_json::Value::as_str)
+                .map(str::to_owned);
+            Some((app_id.clone(), (discount_percent, price)))
+        })
+        .collect()
+}
+
+fn fetch_app_details(client: &Client, app_ids: &[String], filters: &str) -> Result<serde_json::Value, String> {
+    let mut request_url = Url::parse(STEAM_APP_DETAILS_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam app details endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("appids", &app_ids.join(","))
+        .append_pair("filters", filters);
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam app details request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam app details request failed with status {}", response.status()));
+    }
+    response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam app details response: {error}"))
+}
+
+fn fetch_wishlist_discounts(
+    connection: &Connection,
+    client: &Client,
+    user_id: &str,
+    steam_id: &str,
+) -> Result<Vec<DiscoveryCandidate>, String> {
+    let mut discounts = Vec::new();
+    for app_id_batch in fetch_wishlist_app_ids(client, steam_id)?.chunks(DISCOVERY_PRICE_BATCH_SIZE) {
+        let body = fetch_app_details(client, app_id_batch, "price_overview")?;
+        discounts.extend(parse_price_overviews(&body));
+    }
+    discounts.sort_by(|(left_id, (left, _)), (right_id, (right, _))| right.cmp(left).then(left_id.cmp(right_id)));
+
+    let owned_games = load_owned_steam_games_by_app_id(connection, user_id)?;
+    let mut candidates = Vec::new();
+    for (app_id, (discount_percent, price)) in discounts.into_iter().take(DISCOVERY_WISHLIST_MAX_DISCOUNTS) {
+        // Names aren't part of the price overview; `basic` details only work one app at a time.
+        let owned_game = app_id.parse::<u64>().ok().and_then(|app_id| owned_games.get(&app_id));
+        let name = match owned_game {
+            Some(game) => Some(game.name.clone()),
+            None => fetch_app_details(client, std::slice::from_ref(&app_id), "basic")
+                .ok()
+                .and_then(|body| {
+                    body.pointer(&format!("/{app_id}/data/name"))
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::to_owned)
+                }),
+        };
+        candidates.push(DiscoveryCandidate {
+            name: name.unwrap_or_else(|| format!("App {app_id}")),
+            app_id,
+            detail: None,
+            discount_percent: Some(discount_percent),
+            price,
+        });
+    }
+    Ok(candidates)
+}
+
+/// The store genres the user owns most games of, most common first.
+fn load_top_owned_genres(connection: &Connection, user_id: &str) -> Result<Vec<String>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT m.genres_json
+            FROM games g
+            JOIN steam_app_metadata m ON m.app_id = g.external_id
+            WHERE g.user_id = ?1 AND g.provider = 'steam' AND m.genres_json IS NOT NULL
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare owned genre query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to query owned genres: {error}"))?;
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for row in rows {
+        let genres_json = row.map_err(|error| format!("Failed to decode owned genres: {error}"))?;
+        for genre in serde_json::from_str::<Vec<String>>(&genres_json).unwrap_or_default() {
+            *counts.entry(genre).or_default() += 1;
+        }
+    }
+    let mut genres = counts.into_iter().collect::<Vec<_>>();
+    genres.sort_by(|(left_genre, left), (right_genre, right)| right.cmp(left).then(left_genre.cmp(right_genre)));
+    Ok(genres
+        .into_iter()
+        .take(DISCOVERY_OWNED_GENRE_COUNT)
+        .map(|(genre, _)| genre)
+        .collect())
+}
+
+/// Store genres of an app from the metadata cache, fetching and caching them when missing or
+/// stale.
+fn load_steam_app_genres(connection: &Connection, client: &Client, app_id: u64) -> Result<Vec<String>, String> {
+    let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_METADATA_CACHE_TTL_HOURS);
+    let cached = connection
+        .query_row(
+            "SELECT genres_json, fetched_at FROM steam_app_metadata WHERE app_id = ?1",
+            params![app_id.to_string()],
+            |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query cached Steam app metadata: {error}"))?;
+    if let Some((Some(genres_json), fetched_at)) = cached {
+        let is_fresh = chrono::DateTime::parse_from_rfc3339(&fetched_at)
+            .is_ok_and(|timestamp| timestamp.with_timezone(&Utc) >= stale_before);
+        if is_fresh {
+            return Ok(serde_json::from_str(&genres_json).unwrap_or_default());
+        }
+    }
+
+    let Some(metadata) = fetch_steam_app_metadata_batch(client, &[app_id])?.remove(&app_id) else {
+        return Ok(Vec::new());
+    };
+    cache_steam_app_metadata(connection, app_id, &metadata)?;
+    Ok(metadata.genres)
+}
+
+fn fetch_new_releases_in_owned_genres(
+    connection: &Connection,
+    client: &Client,
+    user_id: &str,
+) -> Result<Vec<DiscoveryCandidate>, String> {
+    let owned_genres = load_top_owned_genres(connection, user_id)?;
+    if owned_genres.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let request_url = Url::parse(STEAM_FEATURED_CATEGORIES_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam featured categories endpoint: {error}"))?;
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam featured categories request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam featured categories request failed with status {}", response.status()));
+    }
+    let body = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam featured categories response: {error}"))?;
+
+    let owned_games = load_owned_steam_games_by_app_id(connection, user_id)?;
+    let mut candidates = Vec::new();
+    let new_releases = body
+        .pointer("/new_releases/items")
+        .and_then(serde_json::Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for release in new_releases.iter().take(DISCOVERY_NEW_RELEASE_MAX_LOOKUPS) {
+        let Some(app_id) = release.get("id").and_then(serde_json::Value::as_u64) else {
+            continue;
+        };
+        if owned_games.contains_key(&app_id) {
+            continue;
+        }
+        let genres = load_steam_app_genres(connection, client, app_id).unwrap_or_else(|error| {
+            eprintln!("Could not load genres of Steam app {app_id}: {error}");
+            Vec::new()
+        });
+        let Some(genre) = owned_genres.iter().find(|genre| genres.contains(genre)) else {
+            continue;
+        };
+        candidates.push(DiscoveryCandidate {
+            app_id: app_id.to_string(),
+            name: release
+                .get("name")
+                .and_then(serde_json::Value::as_str)
+                .map_or_else(|| format!("App {app_id}"), str::to_owned),
+            detail: Some(genre.clone()),
+            discount_percent: release
+                .get("discount_percent")
+                .and_then(serde_json::Value::as_i64)
+                .filter(|discount_percent| *discount_percent > 0),
+            price: None,
+        });
+    }
+    Ok(candidates)
+}
+
+fn replace_source_items(
+    connection: &Connection,
+    user_id: &str,
+    source: &str,
+    candidates: &[DiscoveryCandidate],
+) -> Result<(), String> {
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| format!("Failed to start discovery feed transaction: {error}"))?;
+    transaction
+        .execute(
+            "DELETE FROM discovery_feed_items WHERE user_id = ?1 AND source = ?2",
+            params![user_id, source],
+        )
+        .map_err(|error| format!("Failed to clear discovery feed items: {error}"))?;
+    let fetched_at = Utc::now().to_rfc3339();
+    for (position, candidate) in candidates.iter().enumerate() {
+        transaction
+            .execute(
+                "
+                INSERT OR IGNORE INTO discovery_feed_items
+                  (user_id, source, app_id, name, detail, discount_percent, price, position, fetched_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+                ",
+                params![
+                    user_id,
+                    source,
+                    candidate.app_id,
+                    candidate.name,
+                    candidate.detail,
+                    candidate.discount_percent,
+                    candidate.price,
+                    position as i64,
+                    fetched_at
+                ],
+            )
+            .map_err(|error| format!("Failed to save discovery feed item: {error}"))?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| format!("Failed to commit discovery feed items: {error}"))
+}
+
+/// Refetches every followed source, the wishlist's discounts and new releases in the user's
+/// most owned genres. A failing source keeps its previous items and is counted.
+pub(crate) fn refresh_discovery_feed(
+    connection: &Connection,
+    client: &Client,
+    user_id: &str,
+    steam_id: Option<&str>,
+) -> Result<DiscoveryRefreshResponse, String> {
+    let mut response = DiscoveryRefreshResponse::default();
+    let mut record = |source: &str, result: Result<Vec<DiscoveryCandidate>, String>| -> Result<bool, String> {
+        match result {
+            Ok(candidates) => {
+                replace_source_items(connection, user_id, source, &candidates)?;
+                response.refreshed_sources += 1;
+                response.items += candidates.len();
+                Ok(true)
+            }
+            Err(error) => {
+                eprintln!("Discovery source {source} failed: {error}");
+                response.failed_sources += 1;
+                Ok(false)
+            }
+        }
+    };
+
+    for follow in list_discovery_follows(connection, user_id)? {
+        let source = source_key(follow.kind, &follow.source_id);
+        if record(&source, fetch_source_recommendations(client, follow.kind, &follow.source_id))? {
+            connection
+                .execute(
+                    "UPDATE discovery_follows SET last_fetched_at = ?1 WHERE user_id = ?2 AND kind = ?3 AND source_id = ?4",
+                    params![Utc::now().to_rfc3339(), user_id, follow.kind.as_str(), follow.source_id],
+                )
+                .map_err(|error| format!("Failed to update follow: {error}"))?;
+        }
+    }
+    if let Some(steam_id) = steam_id {
+        record(
+            DISCOVERY_SOURCE_WISHLIST,
+            fetch_wishlist_discounts(connection, client, user_id, steam_id),
+        )?;
+    }
+    record(
+        DISCOVERY_SOURCE_NEW_RELEASES,
+        fetch_new_releases_in_owned_genres(connection, client, user_id),
+    )?;
+    Ok(response)
+}
+
+/// Cached feed items, alternating between followed sources, wishlist discounts and new releases
+/// so no single kind crowds out the others. Owned apps are left out.
+pub(crate) fn load_discovery_feed(
+    connection: &Connection,
+    user_id: &str,
+    limit: Option<usize>,
+) -> Result<DiscoveryFeedResponse, String> {
+    let follows = list_discovery_follows(connection, user_id)?;
+    let names_by_source = follows
+        .iter()
+        .map(|follow| (source_key(follow.kind, &follow.source_id), follow.name.clone()))
+        .collect::<HashMap<_, _>>();
+    let owned_games = load_owned_steam_games_by_app_id(connection, user_id)?;
+
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT source, app_id, name, detail, discount_percent, price, fetched_at
+            FROM discovery_feed_items
+            WHERE user_id = ?1
+            ORDER BY source ASC, position ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare discovery feed query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                DiscoveryFeedItem {
+                    kind: String::new(),
+                    source_name: None,
+                    app_id: row.get(1)?,
+                    name: row.get(2)?,
+                    detail: row.get(3)?,
+                    discount_percent: row.get(4)?,
+                    price: row.get(5)?,
+                    fetched_at: row.get(6)?,
+                },
+            ))
+        })
+        .map_err(|error| format!("Failed to query discovery feed: {error}"))?;
+
+    // Followed sources share one lane, so following many curators doesn't push the wishlist out.
+    let mut lanes: [Vec<DiscoveryFeedItem>; 3] = Default::default();
+    let mut seen_app_ids = HashSet::new();
+    let mut refreshed_at: Option<String> = None;
+    for row in rows {
+        let (source, mut item) = row.map_err(|error| format!("Failed to decode discovery feed row: {error}"))?;
+        if refreshed_at.as_deref().map_or(true, |latest| item.fetched_at.as_str() > latest) {
+            refreshed_at = Some(item.fetched_at.clone());
+        }
+        let is_owned = item.app_id.parse::<u64>().is_ok_and(|app_id| owned_games.contains_key(&app_id));
+        if is_owned || !seen_app_ids.insert(item.app_id.clone()) {
+            continue;
+        }
+        let lane = match source.as_str() {
+            DISCOVERY_SOURCE_WISHLIST => {
+                item.kind = String::from("wishlist_discount");
+                1
+            }
+            DISCOVERY_SOURCE_NEW_RELEASES => {
+                item.kind = String::from("new_release");
+                item.source_name = item.detail.take();
+                2
+            }
+            _ => {
+                let Some(name) = names_by_source.get(&source) else {
+                    continue;
+                };
+                item.kind = source.split(':').next().unwrap_or_default().to_owned();
+                item.source_name = Some(name.clone());
+                0
+            }
+        };
+        lanes[lane].push(item);
+    }
+    lanes[1].sort_by(|left, right| right.discount_percent.cmp(&left.discount_percent));
+
+    let limit = limit.unwrap_or(DISCOVERY_FEED_DEFAULT_LIMIT);
+    let mut lanes = lanes.map(Vec::into_iter);
+    let mut items = Vec::new();
+    while items.len() < limit {
+        let mut added = false;
+        for lane in &mut lanes {
+            if let Some(item) = lane.next() {
+                items.push(item);
+                added = true;
+            }
+        }
+        if !added {
+            break;
+        }
+    }
+    items.truncate(limit);
+
+    Ok(DiscoveryFeedResponse {
+        items,
+        follows,
+        refreshed_at,
+    })
+}
+
+/// Refreshes the signed-in user's discovery feed every few hours while online.
+pub(crate) fn spawn_discovery_feed_refresher(app_handle: tauri::AppHandle) {
+    use tauri::Manager;
+
+    let _ = thread::Builder::new()
+        .name("discovery-feed-refresher".into())
+        .spawn(move || {
+            let mut last_refresh_at: Option<Instant> = None;
+            loop {
+                thread::sleep(DISCOVERY_FEED_REFRESH_TICK);
+                if last_refresh_at.is_some_and(|refreshed_at| refreshed_at.elapsed() < DISCOVERY_FEED_REFRESH_INTERVAL) {
+                    continue;
+                }
+                let state = app_handle.state::<AppState>();
+                if state.is_offline() {
+                    continue;
+                }
+                // Not signed in yet; try again on the next tick.
+                let signed_in = open_connection(&state.db_path)
+                    .and_then(|connection| get_authenticated_user(state.inner(), &connection))
+                    .is_ok();
+                if !signed_in {
+                    continue;
+                }
+                last_refresh_at = Some(Instant::now());
+                if let Err(error) = application::services::discovery_service::refresh_discovery_feed(state.inner()) {
+                    eprintln!("Discovery feed refresh failed: {}", error.message);
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery_feed_parses_recommendations_and_interleaves_sources() {
+        let html = r#"
+            <div class="recommendation">
+              <a href="https://store.steampowered.com/app/620/Portal_2/?curator_clanid=1"><img></a>
+              <a href="https://store.steampowered.com/app/620/Portal_2/">Portal 2</a>
+              <div class="recommendation_desc">  A <b>must</b> play &amp; then some </div>
+            </div>
+            <div class="recommendation">
+              <a href="https://store.steampowered.com/app/1145360/Hades/"><img></a>
+            </div>
+        "#;
+        let candidates = parse_recommendations_html(html);
+        assert_eq!(
+            candidates.iter().map(|candidate| (candidate.app_id.as_str(), candidate.name.as_str())).collect::<Vec<_>>(),
+            vec![("620", "Portal 2"), ("1145360", "Hades")]
+        );
+        assert_eq!(candidates[0].detail.as_deref(), Some("A must play & then some"));
+        assert_eq!(candidates[1].detail, None);
+
+        assert_eq!(
+            parse_discovery_source_id(DiscoverySourceKind::Curator, "https://store.steampowered.com/curator/33075774-Rock-Paper-Shotgun/"),
+            Ok(String::from("33075774"))
+        );
+        assert!(parse_discovery_source_id(DiscoverySourceKind::Curator, "rps").is_err());
+
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+        follow_discovery_source(&connection, &user.id, DiscoverySourceKind::Curator, "33075774", Some("RPS"))
+            .expect("follow curator");
+        replace_source_items(&connection, &user.id, "curator:33075774", &candidates).expect("save curator items");
+        let discount = |app_id: &str, discount_percent: i64| DiscoveryCandidate {
+            app_id: app_id.to_owned(),
+            name: format!("App {app_id}"),
+            detail: None,
+            discount_percent: Some(discount_percent),
+            price: None,
+        };
+        replace_source_items(
+            &connection,
+            &user.id,
+            DISCOVERY_SOURCE_WISHLIST,
+            &[discount("10", 25), discount("20", 75), discount("30", 50)],
+        )
+        .expect("save wishlist items");
+
+        let feed = load_discovery_feed(&connection, &user.id, None).expect("load feed");
+        assert_eq!(
+            feed.items.iter().map(|item| (item.kind.as_str(), item.app_id.as_str())).collect::<Vec<_>>(),
+            vec![
+                ("curator", "1145360"),
+                ("wishlist_discount", "20"),
+                ("wishlist_discount", "30"),
+                ("wishlist_discount", "10"),
+            ]
+        );
+        assert_eq!(feed.items[0].source_name.as_deref(), Some("RPS"));
+
+        unfollow_discovery_source(&connection, &user.id, DiscoverySourceKind::Curator, "33075774").expect("unfollow");
+        assert_eq!(load_discovery_feed(&connection, &user.id, None).expect("load feed").items.len(), 3);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DiskSpace {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+/// Queries volume statistics for a path that exists. The platform calls sit behind this trait
+/// so path resolution can be tested without touching real disks.
+pub(crate) trait DiskSpaceProbe {
+    fn query(&self, path: &Path) -> Option<DiskSpace>;
+}
+
+/// `GetDiskFreeSpaceExW` on Windows, `df -Pk` everywhere else.
+pub(crate) struct SystemDiskSpaceProbe;
+
+impl DiskSpaceProbe for SystemDiskSpaceProbe {
+    #[cfg(target_os = "windows")]
+    fn query(&self, path: &Path) -> Option<DiskSpace> {
+        use std::os::windows::ffi::OsStrExt;
+        use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+        let wide_path = path
+            .as_os_str()
+            .encode_wide()
+            .chain(std::iter::once(0))
+            .collect::<Vec<u16>>();
+        let mut available_bytes = 0_u64;
+        let mut total_bytes = 0_u64;
+        let mut total_free_bytes = 0_u64;
+        // SAFETY: `wide_path` is NUL-terminated and the out pointers reference live locals.
+        let succeeded = unsafe {
+            GetDiskFreeSpaceExW(
+                wide_path.as_ptr(),
+                &mut available_bytes,
+                &mut total_bytes,
+                &mut total_free_bytes,
+            )
+        };
+        (succeeded != 0).then_some(DiskSpace {
+            total_bytes,
+            available_bytes,
+        })
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    fn query(&self, path: &Path) -> Option<DiskSpace> {
+        let output = Command::new("df").arg("-Pk").arg(path).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        parse_df_output(&String::from_utf8(output.stdout).ok()?)
+    }
+}
+
+pub(crate) fn detect_disk_space(path: &Path) -> Option<DiskSpace> {
+    detect_disk_space_with(&SystemDiskSpaceProbe, path)
+}
+
+/// Install pickers can point at folders that have not been created yet, which both `df` and
+/// `GetDiskFreeSpaceExW` reject, so the nearest existing ancestor is queried instead.
+pub(crate) fn detect_disk_space_with(probe: &dyn DiskSpaceProbe, path: &Path) -> Option<DiskSpace> {
+    let existing_path = path.ancestors().find(|candidate| candidate.exists())?;
+    probe.query(existing_path)
+}
+
+/// Reads the data row of POSIX `df -Pk` output, which reports sizes in KiB.
+fn parse_df_output(stdout: &str) -> Option<DiskSpace> {
+    let data_row = stdout.lines().nth(1)?;
+    let mut columns = data_row.split_whitespace().skip(1);
+    let total_kib = columns.next()?.parse::<u64>().ok()?;
+    let available_kib = columns.nth(1)?.parse::<u64>().ok()?;
+    Some(DiskSpace {
+        total_bytes: total_kib.saturating_mul(1024),
+        available_bytes: available_kib.saturating_mul(1024),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    struct RecordingProbe {
+        queried_paths: RefCell<Vec<PathBuf>>,
+    }
+
+    impl DiskSpaceProbe for RecordingProbe {
+        fn query(&self, path: &Path) -> Option<DiskSpace> {
+            self.queried_paths.borrow_mut().push(path.to_path_buf());
+            Some(DiskSpace {
+                total_bytes: 100,
+                available_bytes: 40,
+            })
+        }
+    }
+
+    #[test]
+    fn parse_df_output_reads_total_and_available_columns() {
+        let output = "Filesystem     1024-blocks      Used Available Capacity Mounted on\n/dev/nvme0n1p2   488245288 302137400 161226176      66% /\n";
+
+        assert_eq!(
+            parse_df_output(output),
+            Some(DiskSpace {
+                total_bytes: 488_245_288 * 1024,
+                available_bytes: 161_226_176 * 1024,
+            })
+        );
+    }
+
+    #[test]
+    fn detect_disk_space_with_queries_nearest_existing_ancestor() {
+        let root = tempfile::tempdir().expect("temp dir should be created");
+        let missing_library = root.path().join("SteamLibrary").join("steamapps");
+        let probe = RecordingProbe {
+            queried_paths: RefCell::new(Vec::new()),
+        };
+
+        let disk_space = detect_disk_space_with(&probe, &missing_library);
+
+        assert_eq!(disk_space.map(|value| value.available_bytes), Some(40));
+        assert_eq!(probe.queried_paths.borrow().as_slice(), [root.path().to_path_buf()]);
+    }
+}
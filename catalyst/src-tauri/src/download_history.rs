@@ -0,0 +1,359 @@
+use crate::*;
+
+/// Steam rotates `content_log.txt` into `content_log.previous.txt`; both are read, oldest first.
+const STEAM_CONTENT_LOG_FILES: [&str; 2] = ["content_log.previous.txt", "content_log.txt"];
+const DOWNLOAD_HISTORY_DEFAULT_LIMIT: usize = 100;
+const DOWNLOAD_HISTORY_MAX_LIMIT: usize = 1000;
+
+static CONTENT_LOG_UPDATE_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r"^\[(\d{4}-\d{2}-\d{2} \d{2}:\d{2}:\d{2})\] AppID (\d+) update (started|finished|canceled|stopped)\b(.*)$")
+        .expect("content log pattern should compile")
+});
+static CONTENT_LOG_DOWNLOAD_PATTERN: once_cell::sync::Lazy<Regex> =
+    once_cell::sync::Lazy::new(|| Regex::new(r"download (\d+)/(\d+)").expect("download pattern should compile"));
+
+/// One update Steam ran for an app, from `update started` to `update finished` or `canceled`.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SteamContentLogDownload {
+    pub app_id: String,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub bytes_downloaded: u64,
+    pub bytes_total: u64,
+    /// `completed`, `canceled` or `in-progress`.
+    pub status: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DownloadHistoryEntryResponse {
+    pub app_id: String,
+    /// Only known for apps in the user's library.
+    pub name: Option<String>,
+    pub started_at: String,
+    pub finished_at: Option<String>,
+    pub bytes_downloaded: u64,
+    pub bytes_total: u64,
+    pub status: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MonthlyBandwidthResponse {
+    /// `YYYY-MM`, local time.
+    pub month: String,
+    pub bytes_downloaded: u64,
+    pub downloads: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DownloadHistoryResponse {
+    /// Newest first.
+    pub entries: Vec<DownloadHistoryEntryResponse>,
+    /// Every recorded month, newest first.
+    pub monthly_totals: Vec<MonthlyBandwidthResponse>,
+}
+
+/// Content log times are local wall-clock times without an offset.
+fn parse_content_log_timestamp(value: &str) -> Option<String> {
+    let naive = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok()?;
+    chrono::Local
+        .from_local_datetime(&naive)
+        .earliest()
+        .map(|timestamp| timestamp.to_rfc3339())
+}
+
+pub(crate) fn parse_steam_content_log(contents: &str) -> Vec<SteamContentLogDownload> {
+    let mut downloads = Vec::new();
+    let mut open_downloads: HashMap<String, SteamContentLogDownload> = HashMap::new();
+    for line in contents.lines() {
+        let Some(captures) = CONTENT_LOG_UPDATE_PATTERN.captures(line.trim_end()) else {
+            continue;
+        };
+        let Some(timestamp) = parse_content_log_timestamp(&captures[1]) else {
+            continue;
+        };
+        let app_id = captures[2].to_owned();
+        let progress = CONTENT_LOG_DOWNLOAD_PATTERN.captures(&captures[4]).and_then(|progress| {
+            Some((progress[1].parse::<u64>().ok()?, progress[2].parse::<u64>().ok()?))
+        });
+
+        match &captures[3] {
+            "started" => {
+                // A start without an end means Steam exited mid-update.
+                if let Some(mut interrupted) = open_downloads.remove(&app_id) {
+                    interrupted.status = "canceled";
+                    downloads.push(interrupted);
+                }
+                open_downloads.insert(
+                    app_id.clone(),
+                    SteamContentLogDownload {
+                        app_id,
+                        started_at: timestamp,
+                        finished_at: None,
+                        bytes_downloaded: 0,
+                        bytes_total: progress.map_or(0, |(_, total)| total),
+                        status: "in-progress",
+                    },
+                );
+            }
+            outcome => {
+                let Some(mut download) = open_downloads.remove(&app_id) else {
+                    continue;
+                };
+                if let Some((downloaded, total)) = progress {
+                    download.bytes_downloaded = downloaded;
+                    download.bytes_total = download.bytes_total.max(total);
+                }
+                download.finished_at = Some(timestamp);
+                download.status = if outcome == "finished" { "completed" } else { "canceled" };
+                downloads.push(download);
+            }
+        }
+    }
+    downloads.extend(open_downloads.into_values());
+    downloads.sort_by(|left, right| left.started_at.cmp(&right.started_at));
+    downloads
+}
+
+/// Records the updates in Steam's content logs. The logs only reach back a few days, so entries
+/// are kept in SQLite once seen; an update still running is updated on the next import.
+pub(crate) fn import_steam_content_logs(connection: &Connection, steam_root: &Path) -> Result<usize, String> {
+    // Parsed as one log so an update that spans a rotation is still matched up.
+    let mut contents = String::new();
+    for file_name in STEAM_CONTENT_LOG_FILES {
+        let log_path = steam_root.join("logs").join(file_name);
+        match fs::read(&log_path) {
+            Ok(bytes) => {
+                contents.push_str(&String::from_utf8_lossy(&bytes));
+                contents.push('\n');
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(format!("Failed to read {}: {error}", log_path.display())),
+        }
+    }
+    let downloads = parse_steam_content_log(&contents);
+
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| format!("Failed to start download history transaction: {error}"))?;
+    for download in &downloads {
+        transaction
+            .execute(
+                "
+                INSERT INTO steam_download_history (
+                  app_id, started_at, finished_at, bytes_downloaded, bytes_total, status
+                )
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                ON CONFLICT(app_id, started_at) DO UPDATE SET
+                  finished_at = excluded.finished_at,
+                  bytes_downloaded = excluded.bytes_downloaded,
+                  bytes_total = excluded.bytes_total,
+                  status = excluded.status
+                ",
+                params![
+                    download.app_id,
+                    download.started_at,
+                    download.finished_at,
+                    i64::try_from(download.bytes_downloaded).unwrap_or(i64::MAX),
+                    i64::try_from(download.bytes_total).unwrap_or(i64::MAX),
+                    download.status
+                ],
+            )
+            .map_err(|error| format!("Failed to record download history: {error}"))?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| format!("Failed to commit download history: {error}"))?;
+    Ok(downloads.len())
+}
+
+pub(crate) fn load_download_history(
+    connection: &Connection,
+    user_id: &str,
+    limit: Option<usize>,
+) -> Result<DownloadHistoryResponse, String> {
+    let limit = limit
+        .unwrap_or(DOWNLOAD_HISTORY_DEFAULT_LIMIT)
+        .clamp(1, DOWNLOAD_HISTORY_MAX_LIMIT);
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT
+              history.app_id,
+              COALESCE(display.display_name, g.name),
+              history.started_at,
+              history.finished_at,
+              history.bytes_downloaded,
+              history.bytes_total,
+              history.status
+            FROM steam_download_history history
+            LEFT JOIN games g
+              ON g.user_id = ?1
+              AND g.provider = 'steam'
+              AND g.external_id = history.app_id
+            LEFT JOIN game_display_names display
+              ON display.user_id = g.user_id
+              AND display.provider = g.provider
+              AND display.external_id = g.external_id
+            ORDER BY history.started_at DESC
+            LIMIT ?2
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare download history query: {error}"))?;
+    let entries = statement
+        .query_map(params![user_id, limit as i64], |row| {
+            Ok(DownloadHistoryEntryResponse {
+                app_id: row.get(0)?,
+                name: row.get(1)?,
+                started_at: row.get(2)?,
+                finished_at: row.get(3)?,
+                bytes_downloaded: u64::try_from(row.get::<_, i64>(4)?).unwrap_or_default(),
+                bytes_total: u64::try_from(row.get::<_, i64>(5)?).unwrap_or_default(),
+                status: row.get(6)?,
+            })
+        })
+        .map_err(|error| format!("Failed to query download history: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode download history: {error}"))?;
+
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT substr(started_at, 1, 7) AS month, SUM(bytes_downloaded), COUNT(*)
+            FROM steam_download_history
+            GROUP BY month
+            ORDER BY month DESC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare bandwidth totals query: {error}"))?;
+    let monthly_totals = statement
+        .query_map([], |row| {
+            Ok(MonthlyBandwidthResponse {
+                month: row.get(0)?,
+                bytes_downloaded: u64::try_from(row.get::<_, i64>(1)?).unwrap_or_default(),
+                downloads: usize::try_from(row.get::<_, i64>(2)?).unwrap_or_default(),
+            })
+        })
+        .map_err(|error| format!("Failed to query bandwidth totals: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode bandwidth totals: {error}"))?;
+
+    Ok(DownloadHistoryResponse { entries, monthly_totals })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_log_updates_are_recorded_and_totalled_by_month() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+
+        let steam_root = database.dir.path().join("Steam");
+        fs::create_dir_all(steam_root.join("logs")).expect("create logs dir");
+        fs::write(
+            steam_root.join("logs").join("content_log.previous.txt"),
+            "[2024-01-30 20:00:00] AppID 620 update started : download 0/1000, store 0/4000, reuse 0/0, delta 0/0, stage 0/0\n\
+             [2024-01-30 20:05:00] AppID 620 update changed : Running Update,\n\
+             [2024-01-30 20:10:00] AppID 620 update finished : download 1000/1000, store 4000/4000, reuse 0/0, delta 0/0, stage 0/0\n",
+        )
+        .expect("write previous log");
+        fs::write(
+            steam_root.join("logs").join("content_log.txt"),
+            "[2024-02-02 09:00:00] AppID 570 update started : download 0/5000, store 0/5000, reuse 0/0, delta 0/0, stage 0/0\n\
+             [2024-02-02 09:01:00] AppID 570 update canceled : download 200/5000, store 200/5000, reuse 0/0, delta 0/0, stage 0/0\n\
+             [2024-02-03 10:00:00] AppID 570 update started : download 0/4800, store 0/4800, reuse 0/0, delta 0/0, stage 0/0\n",
+        )
+        .expect("write current log");
+
+        assert_eq!(import_steam_content_logs(&connection, &steam_root).expect("import logs"), 3);
+        // Importing again must not duplicate the entries.
+        import_steam_content_logs(&connection, &steam_root).expect("import logs");
+
+        let history = load_download_history(&connection, &user.id, None).expect("load history");
+        assert_eq!(
+            history
+                .entries
+                .iter()
+                .map(|entry| (entry.app_id.as_str(), entry.status.as_str(), entry.bytes_downloaded))
+                .collect::<Vec<_>>(),
+            vec![("570", "in-progress", 0), ("570", "canceled", 200), ("620", "completed", 1000)]
+        );
+        assert_eq!(history.entries[2].name.as_deref(), Some("Portal 2"));
+        assert_eq!(
+            history
+                .monthly_totals
+                .iter()
+                .map(|month| (month.month.as_str(), month.bytes_downloaded, month.downloads))
+                .collect::<Vec<_>>(),
+            vec![("2024-02", 200, 2), ("2024-01", 1000, 1)]
+        );
+    }
+
+    #[test]
+    fn parse_steam_content_log_skips_unmatched_lines_and_closes_interrupted_updates() {
+        let downloads = parse_steam_content_log(
+            "[2024-03-01 08:00:00] AppID 440 update finished : download 10/10, store 10/10\n\
+             [2024-13-01 08:00:00] AppID 440 update started : download 0/10, store 0/10\n\
+             garbage line\n\
+             [2024-03-01 09:00:00] AppID 620 update started : download 0/3000, store 0/3000\n\
+             [2024-03-01 09:30:00] AppID 620 update started : download 0/2000, store 0/2000\n\
+             [2024-03-01 09:40:00] AppID 620 update stopped : download 500/2500, store 500/2500\n",
+        );
+
+        assert_eq!(
+            downloads
+                .iter()
+                .map(|download| (download.status, download.bytes_downloaded, download.bytes_total))
+                .collect::<Vec<_>>(),
+            vec![("canceled", 0, 3000), ("canceled", 500, 2500)]
+        );
+        assert!(downloads[0].finished_at.is_none());
+        assert!(downloads[1].finished_at.is_some());
+        assert!(parse_steam_content_log("").is_empty());
+    }
+
+    #[test]
+    fn download_history_limit_is_clamped_and_display_names_win() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+        connection
+            .execute(
+                "
+                INSERT INTO game_display_names (user_id, provider, external_id, display_name, updated_at)
+                VALUES (?1, 'steam', '620', 'Aperture 2', '2024-01-01T00:00:00Z')
+                ",
+                params![user.id],
+            )
+            .expect("insert display name");
+
+        // Without any content logs there is nothing to import.
+        let steam_root = database.dir.path().join("Steam");
+        assert_eq!(import_steam_content_logs(connection, &steam_root).expect("import missing logs"), 0);
+
+        fs::create_dir_all(steam_root.join("logs")).expect("create logs dir");
+        fs::write(
+            steam_root.join("logs").join("content_log.txt"),
+            "[2024-03-01 09:00:00] AppID 620 update started : download 0/100, store 0/100\n\
+             [2024-03-01 09:10:00] AppID 620 update finished : download 100/100, store 100/100\n\
+             [2024-03-02 09:00:00] AppID 570 update started : download 0/50, store 0/50\n\
+             [2024-03-02 09:10:00] AppID 570 update finished : download 50/50, store 50/50\n",
+        )
+        .expect("write current log");
+        import_steam_content_logs(connection, &steam_root).expect("import logs");
+
+        let history = load_download_history(connection, &user.id, Some(0)).expect("load history");
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!((history.entries[0].app_id.as_str(), history.entries[0].name.as_deref()), ("570", None));
+        // The monthly totals are not cut down by the limit.
+        assert_eq!(history.monthly_totals[0].bytes_downloaded, 150);
+
+        let history = load_download_history(connection, &user.id, None).expect("load history");
+        assert_eq!(history.entries[1].name.as_deref(), Some("Aperture 2"));
+    }
+}
@@ -0,0 +1,424 @@
+use crate::*;
+
+const STEAM_CONFIG_STORE_PATH: [&str; 4] = ["InstallConfigStore", "Software", "Valve", "Steam"];
+const STEAM_DOWNLOAD_THROTTLE_KEY: &str = "DownloadThrottleKbps";
+const DOWNLOAD_SCHEDULE_TICK: Duration = Duration::from_secs(60);
+const DOWNLOAD_SCHEDULE_MAX_WINDOWS: usize = 28;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct DownloadQueueState {
+    pub queue_position: Option<usize>,
+    pub paused: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DownloadRateLimitResponse {
+    /// `None` means downloads are not throttled.
+    pub kbps: Option<u64>,
+    /// Steam reads `config.vdf` on start and rewrites it on exit, so a change made while the
+    /// client is running only takes effect after Steam restarts.
+    pub steam_config_path: String,
+}
+
+/// Days and times when Steam downloads may run. With no windows for a day, downloads are
+/// blocked for that whole day while the schedule is enabled.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DownloadSchedule {
+    pub enabled: bool,
+    pub windows: Vec<DownloadWindow>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DownloadWindow {
+    /// 0 is Monday, 6 is Sunday.
+    pub weekday: u32,
+    /// `HH:MM`, local time. An end before the start runs past midnight into the next day, and
+    /// an end equal to the start covers the whole day.
+    pub start: String,
+    pub end: String,
+}
+
+pub(crate) fn load_download_queue(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<HashMap<String, DownloadQueueState>, String> {
+    let mut statement = connection
+        .prepare("SELECT app_id, queue_position, paused FROM steam_download_queue WHERE user_id = ?1")
+        .map_err(|error| format!("Failed to prepare download queue query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                DownloadQueueState {
+                    queue_position: row.get::<_, Option<i64>>(1)?.map(|position| position.max(0) as usize),
+                    paused: row.get::<_, i64>(2)? != 0,
+                },
+            ))
+        })
+        .map_err(|error| format!("Failed to query download queue: {error}"))?;
+
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|error| format!("Failed to decode download queue row: {error}"))
+}
+
+pub(crate) fn set_download_paused(
+    connection: &Connection,
+    user_id: &str,
+    app_id: &str,
+    paused: bool,
+    by_schedule: bool,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            INSERT INTO steam_download_queue (user_id, app_id, queue_position, paused, paused_by_schedule, updated_at)
+            VALUES (?1, ?2, NULL, ?3, ?4, ?5)
+            ON CONFLICT(user_id, app_id) DO UPDATE SET
+              paused = excluded.paused,
+              paused_by_schedule = excluded.paused_by_schedule,
+              updated_at = excluded.updated_at
+            ",
+            params![
+                user_id,
+                app_id,
+                i64::from(paused),
+                i64::from(paused && by_schedule),
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(|error| format!("Failed to save download pause state: {error}"))?;
+    Ok(())
+}
+
+/// Clears the pauses the download schedule made and returns the affected app IDs. Pauses the
+/// user made themselves are left alone.
+fn take_schedule_paused_downloads(connection: &Connection, user_id: &str) -> Result<Vec<String>, String> {
+    let mut statement = connection
+        .prepare("SELECT app_id FROM steam_download_queue WHERE user_id = ?1 AND paused_by_schedule = 1")
+        .map_err(|error| format!("Failed to prepare scheduled pause query: {error}"))?;
+    let app_ids = statement
+        .query_map(params![user_id], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to query scheduled pauses: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode scheduled pause row: {error}"))?;
+    connection
+        .execute(
+            "
+            UPDATE steam_download_queue
+            SET paused = 0, paused_by_schedule = 0, updated_at = ?2
+            WHERE user_id = ?1 AND paused_by_schedule = 1
+            ",
+            params![user_id, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to clear scheduled pauses: {error}"))?;
+    Ok(app_ids)
+}
+
+/// Stores the queue order; downloads missing from `app_ids` keep their pause state but lose
+/// their position and sort after the ordered ones.
+pub(crate) fn save_download_queue_order(
+    connection: &mut Connection,
+    user_id: &str,
+    app_ids: &[String],
+) -> Result<(), String> {
+    let now = Utc::now().to_rfc3339();
+    let transaction = connection
+        .transaction()
+        .map_err(|error| format!("Failed to start download queue transaction: {error}"))?;
+    transaction
+        .execute(
+            "UPDATE steam_download_queue SET queue_position = NULL WHERE user_id = ?1",
+            params![user_id],
+        )
+        .map_err(|error| format!("Failed to reset download queue order: {error}"))?;
+    for (position, app_id) in app_ids.iter().enumerate() {
+        transaction
+            .execute(
+                "
+                INSERT INTO steam_download_queue (user_id, app_id, queue_position, paused, updated_at)
+                VALUES (?1, ?2, ?3, 0, ?4)
+                ON CONFLICT(user_id, app_id) DO UPDATE SET
+                  queue_position = excluded.queue_position,
+                  updated_at = excluded.updated_at
+                ",
+                params![user_id, app_id, position as i64, now],
+            )
+            .map_err(|error| format!("Failed to save download queue order: {error}"))?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| format!("Failed to commit download queue order: {error}"))
+}
+
+/// Steam has no URI that pauses a single download, so the pause is recorded here and the Steam
+/// downloads page is opened for the transfer to be paused there.
+pub(crate) fn open_steam_downloads_page() -> Result<(), String> {
+    launch_steam_uri("steam://open/downloads", "open")
+}
+
+/// Re-queues the download; `steam://install` resumes a partially downloaded app.
+pub(crate) fn resume_steam_download(app_id: u64) -> Result<(), String> {
+    launch_steam_uri(&format!("steam://install/{app_id}"), "install")
+}
+
+/// Reads the local manifest to tell a finished download apart from a cancelled or paused one.
+pub(crate) fn is_steam_app_fully_installed(steam_root_override: Option<&str>, app_id: u64) -> bool {
+    resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)
+        .ok()
+        .and_then(|manifest_path| fs::read_to_string(manifest_path).ok())
+        .and_then(|contents| parse_steam_manifest_u64_field(&contents, "StateFlags"))
+        .is_some_and(|state_flags| {
+            state_flags & STEAM_APP_STATE_FULLY_INSTALLED != 0
+                && state_flags & STEAM_APP_STATE_UPDATE_REQUIRED == 0
+        })
+}
+
+fn resolve_steam_config_path(steam_root_override: Option<&str>) -> Result<PathBuf, String> {
+    let steam_root = resolve_steam_root_path(steam_root_override)
+        .ok_or_else(|| String::from("Could not locate local Steam installation"))?;
+    let config_path = steam_root.join("config").join("config.vdf");
+    if !config_path.is_file() {
+        return Err(format!(
+            "Could not locate Steam config.vdf at {}",
+            config_path.display()
+        ));
+    }
+    Ok(config_path)
+}
+
+pub(crate) fn read_download_rate_limit(
+    steam_root_override: Option<&str>,
+) -> Result<DownloadRateLimitResponse, String> {
+    let config_path = resolve_steam_config_path(steam_root_override)?;
+    let contents = fs::read_to_string(&config_path).map_err(|error| {
+        format!("Failed to read Steam config at {}: {error}", config_path.display())
+    })?;
+    let config_value = parse_vdf_document(&contents)?;
+    let kbps = STEAM_CONFIG_STORE_PATH
+        .iter()
+        .try_fold(&config_value, |value, key| vdf_find_object_value(value, key))
+        .and_then(|steam_object| vdf_get_text_entry(steam_object, STEAM_DOWNLOAD_THROTTLE_KEY))
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .filter(|kbps| *kbps > 0);
+
+    Ok(DownloadRateLimitResponse {
+        kbps,
+        steam_config_path: config_path.display().to_string(),
+    })
+}
+
+/// Writes Steam's download throttle; `None` or `0` removes the limit.
+pub(crate) fn write_download_rate_limit(
+    steam_root_override: Option<&str>,
+    kbps: Option<u64>,
+) -> Result<DownloadRateLimitResponse, String> {
+    let config_path = resolve_steam_config_path(steam_root_override)?;
+    let contents = fs::read_to_string(&config_path).map_err(|error| {
+        format!("Failed to read Steam config at {}: {error}", config_path.display())
+    })?;
+    let mut config_value = parse_vdf_document(&contents)?;
+    let steam_object = vdf_ensure_object_path_mut(&mut config_value, &STEAM_CONFIG_STORE_PATH);
+    let kbps = kbps.filter(|kbps| *kbps > 0);
+    match kbps {
+        Some(kbps) => vdf_set_text_entry(steam_object, STEAM_DOWNLOAD_THROTTLE_KEY, &kbps.to_string()),
+        None => vdf_remove_entry(steam_object, STEAM_DOWNLOAD_THROTTLE_KEY),
+    }
+    steam_config_writer::write_steam_config_file(&config_path, serialize_vdf_document(&config_value)).map_err(|error| {
+        format!("Failed to write Steam config at {}: {error}", config_path.display())
+    })?;
+
+    Ok(DownloadRateLimitResponse {
+        kbps,
+        steam_config_path: config_path.display().to_string(),
+    })
+}
+
+fn parse_window_minutes(value: &str) -> Option<u32> {
+    let (hours, minutes) = value.trim().split_once(':')?;
+    let hours = hours.parse::<u32>().ok().filter(|hours| *hours < 24)?;
+    let minutes = minutes.parse::<u32>().ok().filter(|minutes| *minutes < 60)?;
+    Some(hours * 60 + minutes)
+}
+
+pub(crate) fn validate_download_schedule(schedule: &DownloadSchedule) -> Result<(), String> {
+    if schedule.windows.len() > DOWNLOAD_SCHEDULE_MAX_WINDOWS {
+        return Err(format!(
+            "A download schedule can have at most {DOWNLOAD_SCHEDULE_MAX_WINDOWS} windows"
+        ));
+    }
+    for window in &schedule.windows {
+        if window.weekday > 6 {
+            return Err(String::from("Weekday must be between 0 (Monday) and 6 (Sunday)"));
+        }
+        if parse_window_minutes(&window.start).is_none() || parse_window_minutes(&window.end).is_none() {
+            return Err(String::from("Download window times must use HH:MM"));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn is_download_allowed(schedule: &DownloadSchedule, weekday: u32, minute_of_day: u32) -> bool {
+    if !schedule.enabled {
+        return true;
+    }
+    schedule.windows.iter().any(|window| {
+        let (Some(start), Some(end)) = (parse_window_minutes(&window.start), parse_window_minutes(&window.end))
+        else {
+            return false;
+        };
+        if start < end {
+            window.weekday == weekday && (start..end).contains(&minute_of_day)
+        } else if start > end {
+            (window.weekday == weekday && minute_of_day >= start)
+                || ((window.weekday + 1) % 7 == weekday && minute_of_day < end)
+        } else {
+            window.weekday == weekday
+        }
+    })
+}
+
+pub(crate) fn load_download_schedule(connection: &Connection) -> Result<DownloadSchedule, String> {
+    let settings = load_app_settings(connection)?;
+    match settings.get(APP_SETTING_DOWNLOAD_SCHEDULE) {
+        Some(value) => serde_json::from_str(value)
+            .map_err(|error| format!("Stored download schedule is invalid: {error}")),
+        None => Ok(DownloadSchedule::default()),
+    }
+}
+
+fn apply_download_schedule(state: &AppState, allowed: bool) -> Result<(), String> {
+    let connection = open_connection(&state.db_path)?;
+    let Ok(user) = get_authenticated_user(state, &connection) else {
+        return Ok(());
+    };
+    if allowed {
+        for app_id in take_schedule_paused_downloads(&connection, &user.id)? {
+            if let Ok(app_id) = app_id.parse::<u64>() {
+                resume_steam_download(app_id)?;
+            }
+        }
+        return Ok(());
+    }
+
+    let downloads = application::services::library_service::list_steam_downloads(state)
+        .map_err(|error| error.message)?;
+    let mut paused_any = false;
+    for download in downloads.iter().filter(|download| !download.paused) {
+        set_download_paused(&connection, &user.id, &download.external_id, true, true)?;
+        paused_any = true;
+    }
+    if paused_any {
+        open_steam_downloads_page()?;
+    }
+    Ok(())
+}
+
+/// Pauses Steam downloads outside the configured download windows and resumes the ones it
+/// paused once a window opens. The schedule is re-read every tick so changes apply without a
+/// restart.
+pub(crate) fn spawn_download_schedule_monitor(app_handle: tauri::AppHandle) {
+    use chrono::{Datelike, Timelike};
+    use tauri::Manager;
+
+    let _ = thread::Builder::new()
+        .name("download-schedule-monitor".into())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+            let schedule = open_connection(&state.db_path).and_then(|connection| load_download_schedule(&connection));
+            match schedule {
+                Ok(schedule) => {
+                    let now = chrono::Local::now();
+                    let allowed = is_download_allowed(
+                        &schedule,
+                        now.weekday().num_days_from_monday(),
+                        now.hour() * 60 + now.minute(),
+                    );
+                    if let Err(error) = apply_download_schedule(state.inner(), allowed) {
+                        eprintln!("Failed to apply download schedule: {error}");
+                    }
+                }
+                Err(error) => eprintln!("Failed to load download schedule: {error}"),
+            }
+
+            thread::sleep(DOWNLOAD_SCHEDULE_TICK);
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn is_download_allowed_handles_overnight_windows() {
+        let schedule = DownloadSchedule {
+            enabled: true,
+            windows: vec![DownloadWindow {
+                weekday: 6,
+                start: String::from("23:00"),
+                end: String::from("07:00"),
+            }],
+        };
+
+        assert!(is_download_allowed(&schedule, 6, 23 * 60 + 30));
+        assert!(is_download_allowed(&schedule, 0, 6 * 60));
+        assert!(!is_download_allowed(&schedule, 0, 7 * 60));
+        assert!(!is_download_allowed(&schedule, 6, 22 * 60));
+        assert!(is_download_allowed(&DownloadSchedule::default(), 2, 12 * 60));
+    }
+
+    #[test]
+    fn save_download_queue_order_keeps_pauses_and_drops_missing_positions() {
+        let mut database = test_support::test_database();
+        let user_id = database.user.id.clone();
+        set_download_paused(&database.connection, &user_id, "400", true, false).expect("pause download");
+
+        let order = [String::from("620"), String::from("400")];
+        save_download_queue_order(&mut database.connection, &user_id, &order).expect("save order");
+        let queue = load_download_queue(&database.connection, &user_id).expect("load queue");
+        assert_eq!(queue["620"].queue_position, Some(0));
+        assert_eq!((queue["400"].queue_position, queue["400"].paused), (Some(1), true));
+
+        save_download_queue_order(&mut database.connection, &user_id, &[String::from("400")]).expect("reorder");
+        let queue = load_download_queue(&database.connection, &user_id).expect("load queue");
+        assert_eq!((queue["620"].queue_position, queue["620"].paused), (None, false));
+        assert_eq!((queue["400"].queue_position, queue["400"].paused), (Some(0), true));
+    }
+
+    #[test]
+    fn take_schedule_paused_downloads_leaves_user_pauses_alone() {
+        let database = test_support::test_database();
+        let (connection, user_id) = (&database.connection, database.user.id.as_str());
+        set_download_paused(connection, user_id, "70", true, true).expect("schedule pause");
+        set_download_paused(connection, user_id, "400", true, false).expect("user pause");
+
+        assert_eq!(take_schedule_paused_downloads(connection, user_id).expect("take"), vec![String::from("70")]);
+        assert!(take_schedule_paused_downloads(connection, user_id).expect("take again").is_empty());
+        let queue = load_download_queue(connection, user_id).expect("load queue");
+        assert!(!queue["70"].paused);
+        assert!(queue["400"].paused);
+    }
+
+    #[test]
+    fn download_rate_limit_roundtrips_through_steam_config() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let steam_root = dir.path().to_string_lossy().into_owned();
+        assert!(read_download_rate_limit(Some(&steam_root)).is_err());
+
+        fs::create_dir_all(dir.path().join("config")).expect("create config dir");
+        fs::write(
+            dir.path().join("config").join("config.vdf"),
+            "\"InstallConfigStore\"\n{\n\t\"Software\"\n\t{\n\t}\n}\n",
+        )
+        .expect("write config");
+        assert_eq!(read_download_rate_limit(Some(&steam_root)).expect("read limit").kbps, None);
+
+        assert_eq!(write_download_rate_limit(Some(&steam_root), Some(512)).expect("limit").kbps, Some(512));
+        assert_eq!(read_download_rate_limit(Some(&steam_root)).expect("read limit").kbps, Some(512));
+        // Zero is the same as no limit.
+        assert_eq!(write_download_rate_limit(Some(&steam_root), Some(0)).expect("unlimit").kbps, None);
+        assert_eq!(read_download_rate_limit(Some(&steam_root)).expect("read limit").kbps, None);
+    }
+}
@@ -0,0 +1,213 @@
+use crate::*;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use once_cell::sync::OnceCell;
+
+const ENCRYPTED_FIELD_PREFIX: &str = "enc:v1:";
+const NONCE_LENGTH: usize = 12;
+const FIELD_KEY_ACCOUNT: &str = "field-encryption-key";
+
+static FIELD_KEY: OnceCell<Key<Aes256Gcm>> = OnceCell::new();
+static FIELD_KEY_FALLBACK_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+/// Sets the file used to hold the key when the OS keychain is unavailable, e.g. on Linux
+/// without a Secret Service provider. Must be called before the first field is encrypted.
+pub(crate) fn configure_field_key_fallback(path: PathBuf) {
+    let _ = FIELD_KEY_FALLBACK_PATH.set(path);
+}
+
+fn field_key() -> Result<&'static Key<Aes256Gcm>, String> {
+    FIELD_KEY.get_or_try_init(|| {
+        load_or_create_field_key(FIELD_KEY_ACCOUNT, FIELD_KEY_FALLBACK_PATH.get().map(PathBuf::as_path))
+    })
+}
+
+/// Prefers the OS keychain. A key found in the fallback file is moved into the keychain once
+/// the keychain becomes usable. When neither holds a key, a new one goes into the keychain, or
+/// into the fallback file if the keychain can't be reached, as on Linux without a Secret
+/// Service provider or in headless CLI runs. Only without a fallback file is the keychain
+/// error returned.
+fn load_or_create_field_key(account: &str, fallback_path: Option<&Path>) -> Result<Key<Aes256Gcm>, String> {
+    let keychain_key = secret_store::read_secret(account);
+    if let Ok(Some(encoded_key)) = &keychain_key {
+        return decode_field_key(encoded_key)
+            .ok_or_else(|| String::from("Encryption key in the OS keychain is invalid"));
+    }
+
+    let existing_file_key = match fallback_path.map(fs::read_to_string) {
+        Some(Ok(contents)) => Some(
+            decode_field_key(contents.trim())
+                .ok_or_else(|| String::from("Encryption key file is invalid"))?,
+        ),
+        Some(Err(error)) if error.kind() != std::io::ErrorKind::NotFound => {
+            return Err(format!("Failed to read encryption key file: {error}"))
+        }
+        _ => None,
+    };
+    let keychain_answered = keychain_key.is_ok();
+    let key = match (existing_file_key, keychain_key) {
+        (Some(key), _) => key,
+        (None, Ok(_)) => Aes256Gcm::generate_key(OsRng),
+        (None, Err(_)) if fallback_path.is_some() => Aes256Gcm::generate_key(OsRng),
+        (None, Err(error)) => return Err(error),
+    };
+    let encoded_key = encode_hex(&key);
+
+    if keychain_answered && secret_store::write_secret(account, &encoded_key).is_ok() {
+        if let Some(path) = fallback_path {
+            clear_session_token_file(path)?;
+        }
+        return Ok(key);
+    }
+
+    let path = fallback_path.ok_or_else(|| {
+        String::from("OS keychain is unavailable and no encryption key file is configured")
+    })?;
+    if existing_file_key.is_none() {
+        // The session token writer already creates owner-only files on Unix.
+        persist_session_token(path, &encoded_key)?;
+    }
+    Ok(key)
+}
+
+fn decode_field_key(encoded_key: &str) -> Option<Key<Aes256Gcm>> {
+    decode_hex(encoded_key)
+        .filter(|bytes| bytes.len() == 32)
+        .map(|bytes| *Key::<Aes256Gcm>::from_slice(&bytes))
+}
+
+pub(crate) fn is_encrypted_field(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_FIELD_PREFIX)
+}
+
+/// Encrypts a single column value with AES-256-GCM. The result is self-describing text so
+/// encrypted values can sit in existing TEXT columns.
+pub(crate) fn encrypt_field(plaintext: &str) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(field_key()?);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| String::from("Failed to encrypt value"))?;
+
+    let mut payload = nonce.to_vec();
+    payload.extend_from_slice(&ciphertext);
+    Ok(format!("{ENCRYPTED_FIELD_PREFIX}{}", encode_hex(&payload)))
+}
+
+/// Decrypts a value written by `encrypt_field`. Values without the prefix predate encryption
+/// and are returned unchanged so existing databases keep working until they are migrated.
+pub(crate) fn decrypt_field(value: &str) -> Result<String, String> {
+    let Some(encoded_payload) = value.strip_prefix(ENCRYPTED_FIELD_PREFIX) else {
+        return Ok(value.to_owned());
+    };
+    let payload = decode_hex(encoded_payload)
+        .filter(|payload| payload.len() > NONCE_LENGTH)
+        .ok_or_else(|| String::from("Encrypted value is invalid"))?;
+    let cipher = Aes256Gcm::new(field_key()?);
+    let (nonce, ciphertext) = payload.split_at(NONCE_LENGTH);
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| String::from("Failed to decrypt value; the encryption key may have changed"))?;
+
+    String::from_utf8(plaintext).map_err(|_| String::from("Decrypted value is not valid UTF-8"))
+}
+
+/// Encrypted values use a random nonce, so equality lookups go through a keyed hash instead.
+pub(crate) fn field_lookup_hash(value: &str) -> Result<String, String> {
+    let digest = Sha256::new()
+        .chain_update(field_key()?)
+        .chain_update(value.as_bytes())
+        .finalize();
+    Ok(format!("{digest:x}"))
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if value.len() % 2 != 0 {
+        return None;
+    }
+    (0..value.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypt_field_roundtrips_and_uses_a_fresh_nonce() {
+        let first = encrypt_field("JBSWY3DPEHPK3PXP").expect("value should encrypt");
+        let second = encrypt_field("JBSWY3DPEHPK3PXP").expect("value should encrypt");
+
+        assert!(is_encrypted_field(&first));
+        assert_ne!(first, second);
+        assert_eq!(
+            decrypt_field(&first).expect("value should decrypt"),
+            "JBSWY3DPEHPK3PXP"
+        );
+        assert_eq!(
+            decrypt_field("76561197960287930").expect("plaintext passes through"),
+            "76561197960287930"
+        );
+        assert_eq!(
+            field_lookup_hash("76561197960287930").expect("hash"),
+            field_lookup_hash("76561197960287930").expect("hash")
+        );
+    }
+
+    #[test]
+    fn load_or_create_field_key_keeps_an_unreachable_keychain_key_safe() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let key_path = dir.path().join("field.key");
+        let account = "test-field-key-unavailable";
+        secret_store::set_test_secret_unavailable(account, true);
+
+        let error = load_or_create_field_key(account, None).expect_err("keychain error");
+        assert!(error.contains("unavailable"), "{error}");
+
+        // Without a keychain the new key goes into the file and is read back from it.
+        let file_key = load_or_create_field_key(account, Some(&key_path)).expect("new file key");
+        assert_eq!(
+            fs::read_to_string(&key_path).expect("read key file"),
+            encode_hex(&file_key)
+        );
+        assert_eq!(load_or_create_field_key(account, Some(&key_path)).expect("file key"), file_key);
+        secret_store::set_test_secret_unavailable(account, false);
+    }
+
+    #[test]
+    fn load_or_create_field_key_generates_a_key_only_for_an_empty_keychain() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let key_path = dir.path().join("field.key");
+        let account = "test-field-key-empty";
+
+        let key = load_or_create_field_key(account, Some(&key_path)).expect("new key");
+        assert_eq!(
+            secret_store::read_secret(account).expect("read keychain"),
+            Some(encode_hex(&key))
+        );
+        assert!(!key_path.exists());
+        assert_eq!(load_or_create_field_key(account, Some(&key_path)).expect("stored key"), key);
+    }
+
+    #[test]
+    fn load_or_create_field_key_moves_a_file_key_into_the_keychain() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let key_path = dir.path().join("field.key");
+        let account = "test-field-key-migration";
+        let file_key = Aes256Gcm::generate_key(OsRng);
+        fs::write(&key_path, encode_hex(&file_key)).expect("write key file");
+
+        assert_eq!(load_or_create_field_key(account, Some(&key_path)).expect("migrated key"), file_key);
+        assert_eq!(
+            secret_store::read_secret(account).expect("read keychain"),
+            Some(encode_hex(&file_key))
+        );
+        assert!(!key_path.exists());
+    }
+}
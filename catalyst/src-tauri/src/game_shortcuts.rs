@@ -0,0 +1,273 @@
+use crate::*;
+
+const SHORTCUT_ICONS_DIRECTORY: &str = "shortcut-icons";
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GameShortcutResponse {
+    pub shortcut_path: String,
+    pub target_uri: String,
+    pub icon_path: Option<String>,
+}
+
+/// Steam games open straight through Steam; everything else goes through Catalyst's deep link
+/// so custom and launcher games start the same way they do from the library.
+pub(crate) fn game_shortcut_target_uri(provider: &str, external_id: &str) -> String {
+    if provider == "steam" {
+        return format!("steam://rungameid/{external_id}");
+    }
+    let mut url = Url::parse(&format!("{DEEP_LINK_SCHEME}://play/")).expect("deep link base URL is valid");
+    url.path_segments_mut()
+        .expect("deep link base URL has a path")
+        .pop_if_empty()
+        .extend([provider, external_id]);
+    url.to_string()
+}
+
+fn shortcut_icons_directory(db_path: &Path) -> PathBuf {
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(SHORTCUT_ICONS_DIRECTORY)
+}
+
+/// Steam keeps each app's icon in `appcache/librarycache`, either as `<appid>_icon.jpg` or, in
+/// newer clients, as a file named after the icon hash inside `<appid>/`.
+fn find_steam_library_cache_icon(steam_root_override: Option<&str>, app_id: &str) -> Option<PathBuf> {
+    let library_cache = resolve_steam_root_path(steam_root_override)?
+        .join("appcache")
+        .join("librarycache");
+    let legacy_icon = library_cache.join(format!("{app_id}_icon.jpg"));
+    if legacy_icon.is_file() {
+        return Some(legacy_icon);
+    }
+    fs::read_dir(library_cache.join(app_id))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.is_file()
+                && path
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .is_some_and(|stem| stem.len() == 40 && stem.chars().all(|character| character.is_ascii_hexdigit()))
+        })
+}
+
+/// Downloads the game's artwork once into Catalyst's data directory and reuses it afterwards.
+fn cache_game_artwork_icon(
+    state: &AppState,
+    provider: &str,
+    external_id: &str,
+    artwork_url: &str,
+) -> Result<PathBuf, String> {
+    let extension = Url::parse(artwork_url)
+        .ok()
+        .and_then(|url| {
+            Path::new(url.path())
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map(str::to_ascii_lowercase)
+        })
+        .filter(|extension| ["png", "jpg", "jpeg", "webp", "ico"].contains(&extension.as_str()))
+        .unwrap_or_else(|| String::from("jpg"));
+    let file_stem = format!("{provider}-{external_id}")
+        .chars()
+        .map(|character| if character.is_ascii_alphanumeric() || character == '-' { character } else { '_' })
+        .collect::<String>();
+    let icons_directory = shortcut_icons_directory(&state.db_path);
+    let icon_path = icons_directory.join(format!("{file_stem}.{extension}"));
+    if icon_path.is_file() {
+        return Ok(icon_path);
+    }
+
+    let client = state.http_client()?;
+    let artwork = client
+        .get(artwork_url)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .map_err(|error| format!("Failed to download artwork for shortcut icon: {error}"))?;
+    fs::create_dir_all(&icons_directory)
+        .map_err(|error| format!("Failed to create {}: {error}", icons_directory.display()))?;
+    fs::write(&icon_path, &artwork).map_err(|error| format!("Failed to write {}: {error}", icon_path.display()))?;
+    Ok(icon_path)
+}
+
+pub(crate) fn resolve_game_shortcut_icon(
+    state: &AppState,
+    provider: &str,
+    external_id: &str,
+    artwork_url: Option<&str>,
+) -> Option<PathBuf> {
+    if provider == "steam" {
+        if let Some(icon_path) = find_steam_library_cache_icon(state.steam_root_override().as_deref(), external_id) {
+            return Some(icon_path);
+        }
+    }
+    let artwork_url = artwork_url.map(str::trim).filter(|value| !value.is_empty())?;
+    match cache_game_artwork_icon(state, provider, external_id, artwork_url) {
+        Ok(icon_path) => Some(icon_path),
+        Err(error) => {
+            eprintln!("Could not cache shortcut icon for {provider}:{external_id}: {error}");
+            None
+        }
+    }
+}
+
+/// Windows shortcuts only take `.ico` icons. An ICO file may hold a PNG image as-is, so PNG
+/// artwork is wrapped rather than converted; other formats are left without an icon.
+fn wrap_png_as_ico(png: &[u8]) -> Option<Vec<u8>> {
+    if !png.starts_with(PNG_SIGNATURE) || png.len() < 24 {
+        return None;
+    }
+    // IHDR is always the first chunk: width and height follow its length and type.
+    let dimension = |offset: usize| {
+        let value = u32::from_be_bytes(png[offset..offset + 4].try_into().ok()?);
+        // 0 means 256 or more in an ICO directory entry.
+        Some(if value >= 256 { 0 } else { value as u8 })
+    };
+    let (width, height) = (dimension(16)?, dimension(20)?);
+
+    let mut ico = Vec::with_capacity(22 + png.len());
+    ico.extend_from_slice(&[0, 0, 1, 0, 1, 0]);
+    ico.extend_from_slice(&[width, height, 0, 0]);
+    ico.extend_from_slice(&1_u16.to_le_bytes());
+    ico.extend_from_slice(&32_u16.to_le_bytes());
+    ico.extend_from_slice(&(png.len() as u32).to_le_bytes());
+    ico.extend_from_slice(&22_u32.to_le_bytes());
+    ico.extend_from_slice(png);
+    Some(ico)
+}
+
+fn write_shortcut_file(path: &Path, content: &str) -> Result<(), String> {
+    fs::write(path, content).map_err(|error| format!("Could not write shortcut at {}: {error}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut permissions = fs::metadata(path)
+            .map_err(|error| format!("Could not read shortcut metadata at {}: {error}", path.display()))?
+            .permissions();
+        permissions.set_mode(0o755);
+        fs::set_permissions(path, permissions)
+            .map_err(|error| format!("Could not make shortcut at {} executable: {error}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// Writes a shortcut that opens `target_uri`: a `.lnk` on Windows, a minimal `.app` bundle on
+/// macOS and a `.desktop` entry elsewhere.
+pub(crate) fn create_uri_shortcut(
+    directory: &Path,
+    game_name: &str,
+    target_uri: &str,
+    icon_path: Option<&Path>,
+) -> Result<PathBuf, String> {
+    let shortcut_name = sanitize_desktop_shortcut_name(game_name);
+
+    if cfg!(target_os = "windows") {
+        let shortcut_path = directory.join(format!("{shortcut_name}.lnk"));
+        let icon_location = icon_path.and_then(|icon_path| {
+            if icon_path
+                .extension()
+                .is_some_and(|extension| extension.eq_ignore_ascii_case("ico"))
+            {
+                return Some(icon_path.to_path_buf());
+            }
+            let ico = wrap_png_as_ico(&fs::read(icon_path).ok()?)?;
+            let ico_path = icon_path.with_extension("ico");
+            fs::write(&ico_path, ico).ok()?;
+            Some(ico_path)
+        });
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        let mut script = format!(
+            "$shortcut = (New-Object -ComObject WScript.Shell).CreateShortcut({}); $shortcut.TargetPath = (Join-Path $env:SystemRoot 'explorer.exe'); $shortcut.Arguments = {};",
+            quote(&shortcut_path.to_string_lossy()),
+            quote(target_uri),
+        );
+        if let Some(icon_location) = icon_location {
+            script.push_str(&format!(" $shortcut.IconLocation = {};", quote(&icon_location.to_string_lossy())));
+        }
+        script.push_str(" $shortcut.Save()");
+        let status = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .status()
+            .map_err(|error| format!("Failed to start PowerShell for shortcut creation: {error}"))?;
+        if !status.success() {
+            return Err(format!("Could not create shortcut at {}", shortcut_path.display()));
+        }
+        return Ok(shortcut_path);
+    }
+
+    if cfg!(target_os = "macos") {
+        let bundle_path = directory.join(format!("{shortcut_name}.app"));
+        let contents_path = bundle_path.join("Contents");
+        let executable_directory = contents_path.join("MacOS");
+        fs::create_dir_all(&executable_directory)
+            .map_err(|error| format!("Could not create {}: {error}", executable_directory.display()))?;
+        let icon_entry = match icon_path {
+            Some(icon_path) => {
+                let resources_directory = contents_path.join("Resources");
+                let extension = icon_path.extension().and_then(|extension| extension.to_str()).unwrap_or("png");
+                let bundled_icon = format!("icon.{extension}");
+                fs::create_dir_all(&resources_directory)
+                    .and_then(|_| fs::copy(icon_path, resources_directory.join(&bundled_icon)))
+                    .map_err(|error| format!("Could not copy shortcut icon: {error}"))?;
+                format!("  <key>CFBundleIconFile</key>\n  <string>{bundled_icon}</string>\n")
+            }
+            None => String::new(),
+        };
+        fs::write(
+            contents_path.join("Info.plist"),
+            format!(
+                r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+  <key>CFBundleExecutable</key>
+  <string>launcher</string>
+  <key>CFBundleName</key>
+  <string>{shortcut_name}</string>
+  <key>CFBundlePackageType</key>
+  <string>APPL</string>
+{icon_entry}</dict>
+</plist>
+"#
+            ),
+        )
+        .map_err(|error| format!("Could not write shortcut bundle at {}: {error}", bundle_path.display()))?;
+        write_shortcut_file(
+            &executable_directory.join("launcher"),
+            &format!("#!/bin/sh\nexec open '{}'\n", target_uri.replace('\'', "'\\''")),
+        )?;
+        return Ok(bundle_path);
+    }
+
+    let shortcut_path = directory.join(format!("{shortcut_name}.desktop"));
+    let icon = icon_path
+        .map(|icon_path| icon_path.to_string_lossy().into_owned())
+        .unwrap_or_else(|| String::from(if target_uri.starts_with("steam:") { "steam" } else { "applications-games" }));
+    write_shortcut_file(
+        &shortcut_path,
+        &format!(
+            "[Desktop Entry]\nType=Application\nVersion=1.0\nName={shortcut_name}\nExec=xdg-open {target_uri}\nIcon={icon}\nTerminal=false\nCategories=Game;\n"
+        ),
+    )?;
+    Ok(shortcut_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn game_shortcut_target_uri_uses_steam_or_deep_link() {
+        assert_eq!(game_shortcut_target_uri("steam", "620"), "steam://rungameid/620");
+        assert_eq!(
+            game_shortcut_target_uri("custom", "doom gzdoom"),
+            "catalyst://play/custom/doom%20gzdoom"
+        );
+    }
+}
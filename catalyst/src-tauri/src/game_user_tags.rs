@@ -0,0 +1,185 @@
+use crate::*;
+
+const GAME_USER_TAG_MAX_LENGTH: usize = 50;
+
+/// Trims the tag and collapses inner whitespace, so "co op  night" and " co op night" are one tag.
+fn normalize_game_user_tag(tag: &str) -> Result<String, String> {
+    let tag = tag.split_whitespace().collect::<Vec<_>>().join(" ");
+    if tag.is_empty() {
+        return Err(String::from("Tag is required"));
+    }
+    if tag.chars().count() > GAME_USER_TAG_MAX_LENGTH {
+        return Err(format!("Tags must be {GAME_USER_TAG_MAX_LENGTH} characters or fewer"));
+    }
+    Ok(tag)
+}
+
+/// Tags the game; tags differing only in case are treated as the same tag.
+pub(crate) fn add_game_user_tag(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    tag: &str,
+) -> Result<Vec<String>, String> {
+    let tag = normalize_game_user_tag(tag)?;
+    connection
+        .execute(
+            "
+            INSERT OR IGNORE INTO game_user_tags (user_id, provider, external_id, tag, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ",
+            params![user_id, provider, external_id, tag, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to add tag: {error}"))?;
+    load_game_user_tags(connection, user_id, provider, external_id)
+}
+
+pub(crate) fn remove_game_user_tag(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    tag: &str,
+) -> Result<Vec<String>, String> {
+    let tag = normalize_game_user_tag(tag)?;
+    connection
+        .execute(
+            "
+            DELETE FROM game_user_tags
+            WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3 AND tag = ?4
+            ",
+            params![user_id, provider, external_id, tag],
+        )
+        .map_err(|error| format!("Failed to remove tag: {error}"))?;
+    load_game_user_tags(connection, user_id, provider, external_id)
+}
+
+fn load_game_user_tags(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<Vec<String>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT tag
+            FROM game_user_tags
+            WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3
+            ORDER BY tag ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare tag query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id, provider, external_id], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to query tags: {error}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode tags: {error}"))
+}
+
+/// Every tagged game's tags, keyed by `game_membership_key`.
+pub(crate) fn load_user_tags_by_game(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<HashMap<String, Vec<String>>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT provider, external_id, tag
+            FROM game_user_tags
+            WHERE user_id = ?1
+            ORDER BY tag ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare tag query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query tags: {error}"))?;
+
+    let mut tags_by_game: HashMap<String, Vec<String>> = HashMap::new();
+    for row in rows {
+        let (provider, external_id, tag) = row.map_err(|error| format!("Failed to decode tag row: {error}"))?;
+        tags_by_game
+            .entry(game_membership_key(&provider, &external_id))
+            .or_default()
+            .push(tag);
+    }
+    Ok(tags_by_game)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_and_remove_game_user_tags_ignore_case_and_spacing() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("epic", "fortnite", "Fortnite");
+
+        add_game_user_tag(&connection, &user.id, "epic", "fortnite", "  Couch   co-op ").expect("add tag");
+        let tags = add_game_user_tag(&connection, &user.id, "epic", "fortnite", "couch co-op").expect("add tag");
+        assert_eq!(tags, vec![String::from("Couch co-op")]);
+        assert!(add_game_user_tag(&connection, &user.id, "epic", "fortnite", "   ").is_err());
+
+        add_game_user_tag(&connection, &user.id, "epic", "fortnite", "Weekend").expect("add tag");
+        let tags_by_game = load_user_tags_by_game(&connection, &user.id).expect("load tags");
+        assert_eq!(
+            tags_by_game.get("epic:fortnite"),
+            Some(&vec![String::from("Couch co-op"), String::from("Weekend")])
+        );
+
+        let tags = remove_game_user_tag(&connection, &user.id, "epic", "fortnite", "COUCH CO-OP").expect("remove tag");
+        assert_eq!(tags, vec![String::from("Weekend")]);
+
+        let matches = library_search::search_library_games(&connection, &user.id, "weekend", None).expect("search");
+        assert_eq!(matches.len(), 1);
+    }
+
+    #[test]
+    fn game_user_tags_enforce_the_length_limit_and_stay_per_user() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+        let friend = create_user(connection, "friend@example.com", "hash", None).expect("create user");
+        connection
+            .execute(
+                "
+                INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, last_synced_at)
+                VALUES (?1, 'steam', '620', 'Portal 2', 'game', 0, '2024-01-01T00:00:00Z')
+                ",
+                params![friend.id],
+            )
+            .expect("insert friend's game");
+
+        let longest = "a".repeat(GAME_USER_TAG_MAX_LENGTH);
+        assert_eq!(
+            add_game_user_tag(connection, &user.id, "steam", "620", &longest).expect("add longest tag"),
+            vec![longest.clone()]
+        );
+        let too_long = "a".repeat(GAME_USER_TAG_MAX_LENGTH + 1);
+        assert!(add_game_user_tag(connection, &user.id, "steam", "620", &too_long).is_err());
+        assert!(remove_game_user_tag(connection, &user.id, "steam", "620", "  ").is_err());
+
+        // Removing a tag the game doesn't have leaves its tags alone.
+        assert_eq!(
+            remove_game_user_tag(connection, &user.id, "steam", "620", "Weekend").expect("remove missing tag"),
+            vec![longest]
+        );
+
+        add_game_user_tag(connection, &friend.id, "steam", "620", "Weekend").expect("add friend's tag");
+        remove_game_user_tag(connection, &user.id, "steam", "620", "Weekend").expect("remove other user's tag");
+        assert_eq!(
+            load_user_tags_by_game(connection, &friend.id).expect("friend's tags").get("steam:620"),
+            Some(&vec![String::from("Weekend")])
+        );
+        assert_eq!(load_user_tags_by_game(connection, &user.id).expect("tags")["steam:620"].len(), 1);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::*;
+use chrono::DateTime;
+
+pub(crate) const GAME_VERIFICATION_PROGRESS_EVENT: &str = "game-verification-progress";
+pub(crate) const GAME_VERIFICATION_COMPLETED_EVENT: &str = "game-verification-completed";
+const GAME_VERIFICATION_STATUS_QUEUED: &str = "queued";
+const GAME_VERIFICATION_STATUS_VALIDATING: &str = "validating";
+const GAME_VERIFICATION_STATUS_COMPLETED: &str = "completed";
+const GAME_VERIFICATION_STATUS_TIMED_OUT: &str = "timed_out";
+const GAME_VERIFICATION_STATUS_IDLE: &str = "idle";
+const GAME_VERIFICATION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Steam may queue a validation behind other downloads, so it gets a while to start.
+const GAME_VERIFICATION_START_TIMEOUT_MINUTES: i64 = 15;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GameVerificationStatusResponse {
+    pub provider: String,
+    pub external_id: String,
+    /// `idle`, `queued`, `validating`, `completed` or `timed_out`.
+    pub status: String,
+    pub started_at: Option<String>,
+    pub last_verified_at: Option<String>,
+}
+
+struct PendingGameVerification {
+    user_id: String,
+    provider: String,
+    external_id: String,
+    started_at: String,
+    validating_seen: bool,
+    last_verified_at: Option<String>,
+}
+
+/// Records that a verification was requested so the monitor can follow it through the
+/// appmanifest state flags.
+pub(crate) fn start_game_verification(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            INSERT INTO game_verifications (user_id, provider, external_id, started_at, validating_seen, last_verified_at)
+            VALUES (?1, ?2, ?3, ?4, 0, NULL)
+            ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+              started_at = excluded.started_at,
+              validating_seen = 0
+            ",
+            params![user_id, provider, external_id, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to record game verification: {error}"))?;
+    Ok(())
+}
+
+pub(crate) fn find_game_verification_status(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<GameVerificationStatusResponse, String> {
+    let row = connection
+        .query_row(
+            "
+            SELECT started_at, validating_seen, last_verified_at
+            FROM game_verifications
+            WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3
+            ",
+            params![user_id, provider, external_id],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, i64>(1)? != 0,
+                    row.get::<_, Option<String>>(2)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query game verification: {error}"))?;
+    let (started_at, validating_seen, last_verified_at) = row.unwrap_or_default();
+    let status = match (&started_at, validating_seen, &last_verified_at) {
+        (Some(_), true, _) => GAME_VERIFICATION_STATUS_VALIDATING,
+        (Some(_), false, _) => GAME_VERIFICATION_STATUS_QUEUED,
+        (None, _, Some(_)) => GAME_VERIFICATION_STATUS_COMPLETED,
+        (None, _, None) => GAME_VERIFICATION_STATUS_IDLE,
+    };
+
+    Ok(GameVerificationStatusResponse {
+        provider: provider.to_owned(),
+        external_id: external_id.to_owned(),
+        status: status.to_owned(),
+        started_at,
+        last_verified_at,
+    })
+}
+
+fn list_pending_game_verifications(connection: &Connection) -> Result<Vec<PendingGameVerification>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT user_id, provider, external_id, started_at, validating_seen, last_verified_at
+            FROM game_verifications
+            WHERE started_at IS NOT NULL
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare pending verification query: {error}"))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok(PendingGameVerification {
+                user_id: row.get(0)?,
+                provider: row.get(1)?,
+                external_id: row.get(2)?,
+                started_at: row.get(3)?,
+                validating_seen: row.get::<_, i64>(4)? != 0,
+                last_verified_at: row.get(5)?,
+            })
+        })
+        .map_err(|error| format!("Failed to query pending verifications: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode pending verification row: {error}"))
+}
+
+/// Where a verification stands given the app's current `StateFlags`. `None` means nothing
+/// changed since the last poll.
+fn next_game_verification_status(
+    verification: &PendingGameVerification,
+    state_flags: Option<u64>,
+    now: DateTime<Utc>,
+) -> Option<&'static str> {
+    let validating = state_flags.is_some_and(|flags| flags & STEAM_APP_STATE_VALIDATING != 0);
+    if validating {
+        return (!verification.validating_seen).then_some(GAME_VERIFICATION_STATUS_VALIDATING);
+    }
+    if verification.validating_seen {
+        return Some(GAME_VERIFICATION_STATUS_COMPLETED);
+    }
+
+    let started_at = DateTime::parse_from_rfc3339(&verification.started_at)
+        .map(|started_at| started_at.with_timezone(&Utc))
+        .unwrap_or(now);
+    (now - started_at >= ChronoDuration::minutes(GAME_VERIFICATION_START_TIMEOUT_MINUTES))
+        .then_some(GAME_VERIFICATION_STATUS_TIMED_OUT)
+}
+
+fn poll_game_verifications(app_handle: &tauri::AppHandle, state: &AppState) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let connection = open_connection(&state.db_path)?;
+    let now = Utc::now();
+    for verification in list_pending_game_verifications(&connection)? {
+        let state_flags = match verification.provider.as_str() {
+            "steam" => verification
+                .external_id
+                .parse::<u64>()
+                .ok()
+                .and_then(|app_id| {
+                    resolve_steam_manifest_path_for_app_id(state.steam_root_override().as_deref(), app_id).ok()
+                })
+                .and_then(|manifest_path| fs::read_to_string(manifest_path).ok())
+                .and_then(|contents| parse_steam_manifest_u64_field(&contents, "StateFlags")),
+            _ => None,
+        };
+        let Some(status) = next_game_verification_status(&verification, state_flags, now) else {
+            continue;
+        };
+
+        let mut last_verified_at = verification.last_verified_at.clone();
+        let (started_at, event) = match status {
+            GAME_VERIFICATION_STATUS_VALIDATING => {
+                (Some(verification.started_at.clone()), GAME_VERIFICATION_PROGRESS_EVENT)
+            }
+            GAME_VERIFICATION_STATUS_COMPLETED => {
+                last_verified_at = Some(now.to_rfc3339());
+                (None, GAME_VERIFICATION_COMPLETED_EVENT)
+            }
+            _ => (None, GAME_VERIFICATION_COMPLETED_EVENT),
+        };
+        connection
+            .execute(
+                "
+                UPDATE game_verifications
+                SET started_at = ?4, validating_seen = ?5, last_verified_at = ?6
+                WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3
+                ",
+                params![
+                    verification.user_id,
+                    verification.provider,
+                    verification.external_id,
+                    started_at,
+                    i64::from(started_at.is_some()),
+                    last_verified_at
+                ],
+            )
+            .map_err(|error| format!("Failed to update game verification: {error}"))?;
+
+        let _ = app_handle.emit(
+            event,
+            GameVerificationStatusResponse {
+                provider: verification.provider,
+                external_id: verification.external_id,
+                status: status.to_owned(),
+                started_at,
+                last_verified_at,
+            },
+        );
+    }
+    Ok(())
+}
+
+/// Follows requested verifications through the appmanifest `StateFlags`: a verification is
+/// done once Steam has set and then cleared the validating flag.
+pub(crate) fn spawn_game_verification_monitor(app_handle: tauri::AppHandle) {
+    use tauri::Manager;
+
+    let _ = thread::Builder::new()
+        .name("game-verification-monitor".into())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+            if let Err(error) = poll_game_verifications(&app_handle, state.inner()) {
+                eprintln!("Failed to poll game verifications: {error}");
+            }
+            thread::sleep(GAME_VERIFICATION_POLL_INTERVAL);
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_game_verification_status_follows_validating_flag() {
+        let now = Utc::now();
+        let mut verification = PendingGameVerification {
+            user_id: String::from("user"),
+            provider: String::from("steam"),
+            external_id: String::from("620"),
+            started_at: now.to_rfc3339(),
+            validating_seen: false,
+            last_verified_at: None,
+        };
+
+        assert_eq!(next_game_verification_status(&verification, Some(4), now), None);
+        assert_eq!(
+            next_game_verification_status(&verification, Some(4 | STEAM_APP_STATE_VALIDATING), now),
+            Some(GAME_VERIFICATION_STATUS_VALIDATING)
+        );
+        verification.validating_seen = true;
+        assert_eq!(
+            next_game_verification_status(&verification, Some(4 | STEAM_APP_STATE_VALIDATING), now),
+            None
+        );
+        assert_eq!(
+            next_game_verification_status(&verification, Some(4), now),
+            Some(GAME_VERIFICATION_STATUS_COMPLETED)
+        );
+        verification.validating_seen = false;
+        assert_eq!(
+            next_game_verification_status(&verification, Some(4), now + ChronoDuration::hours(1)),
+            Some(GAME_VERIFICATION_STATUS_TIMED_OUT)
+        );
+    }
+}
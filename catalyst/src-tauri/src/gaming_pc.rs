@@ -0,0 +1,205 @@
+use crate::*;
+use std::net::{Ipv4Addr, UdpSocket};
+
+const GAMING_PC_API_TOKEN_ACCOUNT: &str = "gaming-pc-api-token";
+const WAKE_ON_LAN_PORT: u16 = 9;
+const WAKE_ON_LAN_PACKET_LEN: usize = 6 + 16 * 6;
+
+/// The main gaming PC a laptop client wakes and sends downloads to. `api_url` points at the
+/// gaming PC's Catalyst API server, which must have LAN access enabled.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GamingPcSettings {
+    pub name: Option<String>,
+    pub mac_address: String,
+    /// Broadcast address of the gaming PC's network, `255.255.255.255` when unset.
+    pub broadcast_address: Option<String>,
+    pub api_url: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GamingPcSettingsResponse {
+    pub settings: Option<GamingPcSettings>,
+    pub api_token_configured: bool,
+}
+
+pub(crate) fn load_gaming_pc_settings(connection: &Connection) -> Result<Option<GamingPcSettings>, String> {
+    let settings = load_app_settings(connection)?;
+    settings
+        .get(APP_SETTING_GAMING_PC)
+        .map(|value| {
+            serde_json::from_str(value).map_err(|error| format!("Stored gaming PC settings are invalid: {error}"))
+        })
+        .transpose()
+}
+
+pub(crate) fn save_gaming_pc_settings(connection: &Connection, settings: Option<&GamingPcSettings>) -> Result<(), String> {
+    let serialized = settings
+        .map(serde_json::to_string)
+        .transpose()
+        .map_err(|error| format!("Failed to serialize gaming PC settings: {error}"))?;
+    save_app_setting(connection, APP_SETTING_GAMING_PC, serialized.as_deref())
+}
+
+pub(crate) fn read_gaming_pc_api_token() -> Result<Option<String>, String> {
+    secret_store::read_secret(GAMING_PC_API_TOKEN_ACCOUNT)
+}
+
+/// Stores the token shown by the gaming PC's API server settings, or forgets it when empty.
+pub(crate) fn save_gaming_pc_api_token(token: Option<&str>) -> Result<(), String> {
+    match token.map(str::trim).filter(|token| !token.is_empty()) {
+        Some(token) => secret_store::write_secret(GAMING_PC_API_TOKEN_ACCOUNT, token),
+        None => secret_store::delete_secret(GAMING_PC_API_TOKEN_ACCOUNT),
+    }
+}
+
+/// Accepts `AA:BB:CC:DD:EE:FF`, `AA-BB-CC-DD-EE-FF` and `AABBCCDDEEFF`.
+pub(crate) fn parse_mac_address(value: &str) -> Result<[u8; 6], String> {
+    let digits = value
+        .trim()
+        .chars()
+        .filter(|character| !matches!(character, ':' | '-' | '.'))
+        .collect::<String>();
+    if digits.len() != 12 || !digits.chars().all(|character| character.is_ascii_hexdigit()) {
+        return Err(format!("'{}' is not a valid MAC address", value.trim()));
+    }
+    let mut mac_address = [0_u8; 6];
+    for (index, byte) in mac_address.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digits[index * 2..index * 2 + 2], 16)
+            .map_err(|_| format!("'{}' is not a valid MAC address", value.trim()))?;
+    }
+    Ok(mac_address)
+}
+
+/// Six `0xFF` bytes followed by the MAC address sixteen times.
+fn build_magic_packet(mac_address: [u8; 6]) -> [u8; WAKE_ON_LAN_PACKET_LEN] {
+    let mut packet = [0xFF_u8; WAKE_ON_LAN_PACKET_LEN];
+    for chunk in packet[6..].chunks_exact_mut(6) {
+        chunk.copy_from_slice(&mac_address);
+    }
+    packet
+}
+
+pub(crate) fn send_wake_on_lan(settings: &GamingPcSettings) -> Result<(), String> {
+    let mac_address = parse_mac_address(&settings.mac_address)?;
+    let broadcast_address = match settings.broadcast_address.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        Some(address) => address
+            .parse::<Ipv4Addr>()
+            .map_err(|_| format!("'{address}' is not a valid broadcast address"))?,
+        None => Ipv4Addr::BROADCAST,
+    };
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .map_err(|error| format!("Failed to open Wake-on-LAN socket: {error}"))?;
+    socket
+        .set_broadcast(true)
+        .map_err(|error| format!("Failed to enable broadcast: {error}"))?;
+    socket
+        .send_to(&build_magic_packet(mac_address), (broadcast_address, WAKE_ON_LAN_PORT))
+        .map_err(|error| format!("Failed to send Wake-on-LAN packet: {error}"))?;
+    Ok(())
+}
+
+fn is_local_network_host(url: &Url) -> bool {
+    match url.host() {
+        Some(url::Host::Ipv4(address)) => address.is_private() || address.is_loopback() || address.is_link_local(),
+        Some(url::Host::Ipv6(address)) => {
+            // Unique local (fc00::/7) and link-local (fe80::/10) addresses.
+            address.is_loopback()
+                || address.segments()[0] & 0xFE00 == 0xFC00
+                || address.segments()[0] & 0xFFC0 == 0xFE80
+        }
+        Some(url::Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost"
+                || !domain.contains('.')
+                || [".local", ".lan", ".home.arpa"].iter().any(|suffix| domain.ends_with(suffix))
+        }
+        None => false,
+    }
+}
+
+/// The API server speaks plain HTTP, so `http://` URLs are only accepted for hosts on the local
+/// network; anything further away needs `https://` (e.g. through a reverse proxy) to keep the
+/// token off the wire.
+pub(crate) fn parse_gaming_pc_api_url(value: &str) -> Result<Url, String> {
+    let url = Url::parse(value.trim()).map_err(|error| format!("Invalid gaming PC API URL: {error}"))?;
+    match url.scheme() {
+        "https" => Ok(url),
+        "http" if is_local_network_host(&url) => Ok(url),
+        "http" => Err(String::from(
+            "Use an https:// URL for a gaming PC outside the local network; http:// would send the API token unencrypted",
+        )),
+        _ => Err(String::from("The gaming PC API URL must start with http:// or https://")),
+    }
+}
+
+/// Asks the gaming PC's Catalyst to start installing a game through its API server.
+pub(crate) fn request_gaming_pc_install(
+    client: &Client,
+    settings: &GamingPcSettings,
+    token: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<(), String> {
+    let api_url = settings
+        .api_url
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .ok_or_else(|| String::from("Set the gaming PC's API URL first"))?;
+    let api_url = parse_gaming_pc_api_url(api_url)?;
+    let install_url = Url::parse(&format!("{}/install", api_url.as_str().trim_end_matches('/')))
+        .map_err(|error| format!("Invalid gaming PC API URL: {error}"))?;
+    let response = send_limited_request(&install_url, || {
+        client
+            .post(install_url.clone())
+            .bearer_auth(token)
+            .json(&serde_json::json!({ "provider": provider, "externalId": external_id }))
+    })
+    .map_err(|error| format!("Could not reach the gaming PC: {error}"))?;
+    if response.status().is_success() {
+        return Ok(());
+    }
+
+    let status = response.status();
+    let message = response
+        .json::<serde_json::Value>()
+        .ok()
+        .and_then(|body| body.get("message").and_then(serde_json::Value::as_str).map(str::to_owned))
+        .unwrap_or_else(|| format!("HTTP {status}"));
+    Err(format!("The gaming PC refused the download: {message}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_magic_packet_repeats_mac_address() {
+        let mac_address = parse_mac_address("00-1a:2B3c4D5e").expect("parse mac");
+        let packet = build_magic_packet(mac_address);
+
+        assert_eq!(mac_address, [0x00, 0x1A, 0x2B, 0x3C, 0x4D, 0x5E]);
+        assert_eq!(&packet[..6], &[0xFF; 6]);
+        assert!(packet[6..].chunks_exact(6).all(|chunk| chunk == mac_address));
+        assert!(parse_mac_address("00:1A:2B:3C:4D").is_err());
+    }
+
+    #[test]
+    fn parse_gaming_pc_api_url_keeps_plain_http_on_the_local_network() {
+        for url in [
+            "http://192.168.1.20:47820/api",
+            "http://10.0.0.5:47820/api",
+            "http://gaming-pc:47820/api",
+            "http://gaming-pc.local:47820/api",
+            "http://[fd00::20]:47820/api",
+            "https://catalyst.example.com/api",
+        ] {
+            assert!(parse_gaming_pc_api_url(url).is_ok(), "{url} was rejected");
+        }
+        for url in ["http://203.0.113.7:47820/api", "http://catalyst.example.com/api", "ftp://192.168.1.20/api"] {
+            assert!(parse_gaming_pc_api_url(url).is_err(), "{url} was accepted");
+        }
+    }
+}
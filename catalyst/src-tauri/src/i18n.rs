@@ -0,0 +1,144 @@
+use crate::*;
+
+pub(crate) const DEFAULT_LOCALE: &str = "en";
+
+/// Bundled message catalogs, keyed by language. Each maps a stable message code to its text,
+/// with `{name}` placeholders for parameters.
+static MESSAGE_CATALOGS: once_cell::sync::Lazy<BTreeMap<&'static str, HashMap<String, String>>> =
+    once_cell::sync::Lazy::new(|| {
+        [
+            ("en", include_str!("../locales/en.json")),
+            ("de", include_str!("../locales/de.json")),
+        ]
+        .into_iter()
+        .map(|(locale, contents)| {
+            let messages = serde_json::from_str(contents)
+                .unwrap_or_else(|error| panic!("Bundled {locale} message catalog is invalid: {error}"));
+            (locale, messages)
+        })
+        .collect()
+    });
+
+// Process-wide rather than in `AppState`: messages are rendered where errors are created, which
+// is often far from any state.
+static ACTIVE_LOCALE: once_cell::sync::Lazy<Mutex<String>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(String::from(DEFAULT_LOCALE)));
+
+/// A message rendered in the active locale, together with the code and parameters the UI can
+/// use to translate it itself. Messages without a catalog entry have no code.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocalizedMessage {
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_code: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub message_params: BTreeMap<String, String>,
+}
+
+impl LocalizedMessage {
+    /// Wraps text that has no catalog entry, such as an error reported by Steam.
+    pub(crate) fn untranslated(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            message_code: None,
+            message_params: BTreeMap::new(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LocaleResponse {
+    pub locale: String,
+    pub supported_locales: Vec<String>,
+}
+
+pub(crate) fn supported_locales() -> Vec<String> {
+    MESSAGE_CATALOGS.keys().map(|locale| (*locale).to_owned()).collect()
+}
+
+/// Reduces a tag like `de-AT` or `de_DE.UTF-8` to its language and returns it when a catalog
+/// exists for it.
+pub(crate) fn normalize_locale(locale: &str) -> Option<String> {
+    let language = locale
+        .trim()
+        .split(['-', '_', '.'])
+        .next()
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    MESSAGE_CATALOGS.contains_key(language.as_str()).then_some(language)
+}
+
+pub(crate) fn active_locale() -> String {
+    ACTIVE_LOCALE
+        .lock()
+        .map(|locale| locale.clone())
+        .unwrap_or_else(|_| String::from(DEFAULT_LOCALE))
+}
+
+/// Switches the language of messages created from now on. Unsupported locales fall back to
+/// English.
+pub(crate) fn set_active_locale(locale: &str) {
+    let locale = normalize_locale(locale).unwrap_or_else(|| String::from(DEFAULT_LOCALE));
+    if let Ok(mut guard) = ACTIVE_LOCALE.lock() {
+        *guard = locale;
+    }
+}
+
+/// Looks `code` up in `locale`, then in English, and fills in `params`. Unknown codes render
+/// as the code itself so a missing entry is visible rather than blank.
+fn render_message(locale: &str, code: &str, params: &BTreeMap<String, String>) -> String {
+    let template = [locale, DEFAULT_LOCALE]
+        .iter()
+        .find_map(|locale| MESSAGE_CATALOGS.get(locale).and_then(|messages| messages.get(code)));
+    let Some(template) = template else {
+        return code.to_owned();
+    };
+    params
+        .iter()
+        .fold(template.clone(), |message, (name, value)| message.replace(&format!("{{{name}}}"), value))
+}
+
+/// Renders catalog entry `code` in the active locale.
+pub(crate) fn localized(code: &str, params: &[(&str, String)]) -> LocalizedMessage {
+    let message_params = params
+        .iter()
+        .map(|(name, value)| ((*name).to_owned(), value.clone()))
+        .collect::<BTreeMap<_, _>>();
+    LocalizedMessage {
+        message: render_message(&active_locale(), code, &message_params),
+        message_code: Some(code.to_owned()),
+        message_params,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_message_fills_params_and_falls_back_to_english() {
+        let params = BTreeMap::from([(String::from("branch"), String::from("public_beta"))]);
+        assert_eq!(
+            render_message("de", "beta_code_accepted_branch", &params),
+            "Code akzeptiert. Freigeschalteter Zweig: public_beta."
+        );
+        assert_eq!(
+            render_message("fr", "beta_code_accepted_branch", &params),
+            "Code accepted. Branch unlocked: public_beta."
+        );
+        assert_eq!(render_message("de", "no_such_message", &params), "no_such_message");
+
+        assert_eq!(normalize_locale(" de_DE.UTF-8 ").as_deref(), Some("de"));
+        assert_eq!(normalize_locale("EN-us").as_deref(), Some("en"));
+        assert_eq!(normalize_locale("fr"), None);
+
+        let english = &MESSAGE_CATALOGS[DEFAULT_LOCALE];
+        for (locale, messages) in MESSAGE_CATALOGS.iter() {
+            let mut missing = english.keys().filter(|code| !messages.contains_key(*code)).collect::<Vec<_>>();
+            missing.sort();
+            assert!(missing.is_empty(), "{locale} catalog is missing {missing:?}");
+        }
+    }
+}
@@ -0,0 +1,607 @@
+use crate::*;
+use crate::library_import::CUSTOM_PROVIDER;
+
+pub(crate) const INSTALL_JOB_PROGRESS_EVENT: &str = "install-job-progress";
+const INSTALL_JOB_STATUS_QUEUED: &str = "queued";
+const INSTALL_JOB_STATUS_INSTALLING: &str = "installing";
+const INSTALL_JOB_STATUS_COMPLETED: &str = "completed";
+const INSTALL_JOB_STATUS_FAILED: &str = "failed";
+const INSTALL_JOB_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Progress events are emitted at most once per this many copied bytes, plus once per job
+/// state change.
+const INSTALL_JOB_PROGRESS_STEP_BYTES: u64 = 8 * 1024 * 1024;
+const INSTALL_JOB_LIST_LIMIT: i64 = 50;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct InstallJobResponse {
+    pub id: String,
+    pub provider: String,
+    pub external_id: String,
+    pub source_path: String,
+    pub install_path: String,
+    pub create_desktop_shortcut: bool,
+    pub create_application_shortcut: bool,
+    /// `queued`, `installing`, `completed` or `failed`.
+    pub status: String,
+    pub bytes_total: u64,
+    pub bytes_done: u64,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+/// How a custom game's files reach its install directory.
+#[derive(Debug, PartialEq, Eq)]
+enum InstallSource {
+    /// A `.zip` archive, extracted into the install directory.
+    Archive(PathBuf),
+    /// A game folder, copied with its contents.
+    Directory(PathBuf),
+    /// A single installer or portable executable, copied into the install directory.
+    File(PathBuf),
+}
+
+const INSTALL_JOB_COLUMNS: &str = "
+    id, user_id, provider, external_id, source_path, install_path, create_desktop_shortcut,
+    create_application_shortcut, status, bytes_total, bytes_done, error, created_at, updated_at
+";
+
+fn map_install_job_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<(String, InstallJobResponse)> {
+    Ok((
+        row.get(1)?,
+        InstallJobResponse {
+            id: row.get(0)?,
+            provider: row.get(2)?,
+            external_id: row.get(3)?,
+            source_path: row.get(4)?,
+            install_path: row.get(5)?,
+            create_desktop_shortcut: row.get::<_, i64>(6)? != 0,
+            create_application_shortcut: row.get::<_, i64>(7)? != 0,
+            status: row.get(8)?,
+            bytes_total: row.get::<_, i64>(9)?.max(0) as u64,
+            bytes_done: row.get::<_, i64>(10)?.max(0) as u64,
+            error: row.get(11)?,
+            created_at: row.get(12)?,
+            updated_at: row.get(13)?,
+        },
+    ))
+}
+
+fn classify_install_source(source_path: &Path) -> Result<InstallSource, String> {
+    if source_path.is_dir() {
+        return Ok(InstallSource::Directory(source_path.to_path_buf()));
+    }
+    if !source_path.is_file() {
+        return Err(format!("Installer not found at {}", source_path.display()));
+    }
+    let is_archive = source_path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"));
+    Ok(if is_archive {
+        InstallSource::Archive(source_path.to_path_buf())
+    } else {
+        InstallSource::File(source_path.to_path_buf())
+    })
+}
+
+/// Queues an install of a custom game. The worker copies or extracts `source_path` into
+/// `install_path`, points the game's launch target at the installed copy and creates the
+/// requested shortcuts.
+pub(crate) fn enqueue_install_job(
+    connection: &Connection,
+    user_id: &str,
+    external_id: &str,
+    source_path: &str,
+    install_path: &str,
+    create_desktop_shortcut: bool,
+    create_application_shortcut: bool,
+) -> Result<InstallJobResponse, String> {
+    let source_path = Path::new(source_path.trim());
+    let install_path = Path::new(install_path.trim());
+    if !install_path.is_absolute() {
+        return Err(String::from("Choose an absolute install folder"));
+    }
+    if let InstallSource::Directory(source_directory) = classify_install_source(source_path)? {
+        if install_path.starts_with(&source_directory) {
+            return Err(String::from("The install folder cannot be inside the game folder being copied"));
+        }
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let job = InstallJobResponse {
+        id: Uuid::new_v4().to_string(),
+        provider: CUSTOM_PROVIDER.to_owned(),
+        external_id: external_id.to_owned(),
+        source_path: source_path.to_string_lossy().into_owned(),
+        install_path: install_path.to_string_lossy().into_owned(),
+        create_desktop_shortcut,
+        create_application_shortcut,
+        status: INSTALL_JOB_STATUS_QUEUED.to_owned(),
+        bytes_total: 0,
+        bytes_done: 0,
+        error: None,
+        created_at: now.clone(),
+        updated_at: now,
+    };
+    connection
+        .execute(
+            "
+            INSERT INTO install_jobs (
+              id, user_id, provider, external_id, source_path, install_path, create_desktop_shortcut,
+              create_application_shortcut, status, bytes_total, bytes_done, error, created_at, updated_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, 0, 0, NULL, ?10, ?10)
+            ",
+            params![
+                job.id,
+                user_id,
+                job.provider,
+                job.external_id,
+                job.source_path,
+                job.install_path,
+                i64::from(create_desktop_shortcut),
+                i64::from(create_application_shortcut),
+                job.status,
+                job.created_at
+            ],
+        )
+        .map_err(|error| format!("Failed to queue install: {error}"))?;
+    Ok(job)
+}
+
+/// Most recent install jobs, newest first.
+pub(crate) fn list_install_jobs(connection: &Connection, user_id: &str) -> Result<Vec<InstallJobResponse>, String> {
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT {INSTALL_JOB_COLUMNS} FROM install_jobs WHERE user_id = ?1 ORDER BY created_at DESC LIMIT ?2"
+        ))
+        .map_err(|error| format!("Failed to prepare install job query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id, INSTALL_JOB_LIST_LIMIT], map_install_job_row)
+        .map_err(|error| format!("Failed to query install jobs: {error}"))?;
+
+    rows.map(|row| row.map(|(_, job)| job))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode install job row: {error}"))
+}
+
+fn take_next_install_job(connection: &Connection) -> Result<Option<(String, InstallJobResponse)>, String> {
+    let next_job = connection
+        .query_row(
+            &format!(
+                "SELECT {INSTALL_JOB_COLUMNS} FROM install_jobs WHERE status = ?1 ORDER BY created_at ASC LIMIT 1"
+            ),
+            params![INSTALL_JOB_STATUS_QUEUED],
+            map_install_job_row,
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query queued install jobs: {error}"))?;
+    let Some((user_id, mut job)) = next_job else {
+        return Ok(None);
+    };
+    job.status = INSTALL_JOB_STATUS_INSTALLING.to_owned();
+    save_install_job_progress(connection, &job)?;
+    Ok(Some((user_id, job)))
+}
+
+fn save_install_job_progress(connection: &Connection, job: &InstallJobResponse) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            UPDATE install_jobs
+            SET status = ?2, bytes_total = ?3, bytes_done = ?4, error = ?5, updated_at = ?6
+            WHERE id = ?1
+            ",
+            params![
+                job.id,
+                job.status,
+                job.bytes_total as i64,
+                job.bytes_done as i64,
+                job.error,
+                job.updated_at
+            ],
+        )
+        .map_err(|error| format!("Failed to update install job: {error}"))?;
+    Ok(())
+}
+
+pub(crate) fn measure_directory_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| {
+            let entry_path = entry.path();
+            if entry_path.is_dir() {
+                measure_directory_size(&entry_path)
+            } else {
+                entry.metadata().map(|metadata| metadata.len()).unwrap_or(0)
+            }
+        })
+        .sum()
+}
+
+fn copy_with_progress(
+    reader: &mut impl std::io::Read,
+    destination_path: &Path,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    if let Some(parent_directory) = destination_path.parent() {
+        fs::create_dir_all(parent_directory)
+            .map_err(|error| format!("Failed to create {}: {error}", parent_directory.display()))?;
+    }
+    let mut destination_file = fs::File::create(destination_path)
+        .map_err(|error| format!("Failed to write {}: {error}", destination_path.display()))?;
+    let mut buffer = vec![0_u8; 256 * 1024];
+    loop {
+        let read_bytes = reader
+            .read(&mut buffer)
+            .map_err(|error| format!("Failed to read installer data: {error}"))?;
+        if read_bytes == 0 {
+            return Ok(());
+        }
+        std::io::Write::write_all(&mut destination_file, &buffer[..read_bytes])
+            .map_err(|error| format!("Failed to write {}: {error}", destination_path.display()))?;
+        on_progress(read_bytes as u64);
+    }
+}
+
+pub(crate) fn copy_directory_with_progress(
+    source_directory: &Path,
+    destination_directory: &Path,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    fs::create_dir_all(destination_directory)
+        .map_err(|error| format!("Failed to create {}: {error}", destination_directory.display()))?;
+    let entries = fs::read_dir(source_directory)
+        .map_err(|error| format!("Failed to read {}: {error}", source_directory.display()))?;
+    for entry in entries.flatten() {
+        let source_path = entry.path();
+        let destination_path = destination_directory.join(entry.file_name());
+        if source_path.is_dir() {
+            copy_directory_with_progress(&source_path, &destination_path, on_progress)?;
+            continue;
+        }
+        let mut source_file = fs::File::open(&source_path)
+            .map_err(|error| format!("Failed to open {}: {error}", source_path.display()))?;
+        copy_with_progress(&mut source_file, &destination_path, on_progress)?;
+    }
+    Ok(())
+}
+
+/// Installs the files of `source` into `install_path`, returning the total size and the path
+/// the launch target should be moved to, if it can be determined.
+fn install_source_files(
+    source: &InstallSource,
+    install_path: &Path,
+    current_target: Option<&str>,
+    on_total: &mut dyn FnMut(u64),
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<Option<PathBuf>, String> {
+    fs::create_dir_all(install_path)
+        .map_err(|error| format!("Failed to create {}: {error}", install_path.display()))?;
+    match source {
+        InstallSource::Archive(archive_path) => {
+            let archive_file = fs::File::open(archive_path)
+                .map_err(|error| format!("Failed to open {}: {error}", archive_path.display()))?;
+            let mut archive =
+                zip::ZipArchive::new(archive_file).map_err(|error| format!("Failed to read installer archive: {error}"))?;
+            let mut total_bytes = 0;
+            for entry_index in 0..archive.len() {
+                if let Ok(entry) = archive.by_index_raw(entry_index) {
+                    total_bytes += entry.size();
+                }
+            }
+            on_total(total_bytes);
+            for entry_index in 0..archive.len() {
+                let mut entry = archive
+                    .by_index(entry_index)
+                    .map_err(|error| format!("Failed to read installer archive entry: {error}"))?;
+                // `enclosed_name` rejects entries that would escape the install folder.
+                let Some(relative_path) = entry.enclosed_name() else {
+                    continue;
+                };
+                let destination_path = install_path.join(relative_path);
+                if entry.is_dir() {
+                    fs::create_dir_all(&destination_path)
+                        .map_err(|error| format!("Failed to create {}: {error}", destination_path.display()))?;
+                    continue;
+                }
+                copy_with_progress(&mut entry, &destination_path, on_progress)?;
+            }
+            Ok(current_target
+                .and_then(|target| Path::new(target).file_name())
+                .map(|file_name| install_path.join(file_name))
+                .filter(|target| target.is_file()))
+        }
+        InstallSource::Directory(source_directory) => {
+            on_total(measure_directory_size(source_directory));
+            copy_directory_with_progress(source_directory, install_path, on_progress)?;
+            Ok(current_target
+                .and_then(|target| Path::new(target).strip_prefix(source_directory).ok())
+                .map(|relative_target| install_path.join(relative_target)))
+        }
+        InstallSource::File(source_file_path) => {
+            let file_name = source_file_path
+                .file_name()
+                .ok_or_else(|| String::from("Installer path has no file name"))?;
+            on_total(fs::metadata(source_file_path).map(|metadata| metadata.len()).unwrap_or(0));
+            let mut source_file = fs::File::open(source_file_path)
+                .map_err(|error| format!("Failed to open {}: {error}", source_file_path.display()))?;
+            let destination_path = install_path.join(file_name);
+            copy_with_progress(&mut source_file, &destination_path, on_progress)?;
+            Ok(Some(destination_path))
+        }
+    }
+}
+
+pub(crate) fn resolve_application_shortcuts_directory() -> Result<PathBuf, String> {
+    let directory = if cfg!(target_os = "windows") {
+        std::env::var("APPDATA")
+            .map(PathBuf::from)
+            .map_err(|_| String::from("Could not resolve the Start menu folder"))?
+            .join("Microsoft")
+            .join("Windows")
+            .join("Start Menu")
+            .join("Programs")
+    } else {
+        let home_directory = std::env::var("HOME")
+            .map(PathBuf::from)
+            .map_err(|_| String::from("Could not resolve user home directory for application shortcut"))?;
+        if cfg!(target_os = "macos") {
+            home_directory.join("Applications")
+        } else {
+            home_directory.join(".local").join("share").join("applications")
+        }
+    };
+    fs::create_dir_all(&directory)
+        .map_err(|error| format!("Could not create shortcut directory at {}: {error}", directory.display()))?;
+    Ok(directory)
+}
+
+/// Writes a shortcut that starts `executable` directly: a `.lnk` on Windows, a `.command`
+/// script on macOS and a `.desktop` entry elsewhere.
+fn create_executable_shortcut(
+    directory: &Path,
+    game_name: &str,
+    executable: &Path,
+    arguments: Option<&str>,
+) -> Result<PathBuf, String> {
+    let shortcut_name = sanitize_desktop_shortcut_name(game_name);
+    let arguments = arguments.map(str::trim).unwrap_or_default();
+    let working_directory = executable.parent().unwrap_or(executable);
+
+    if cfg!(target_os = "windows") {
+        let shortcut_path = directory.join(format!("{shortcut_name}.lnk"));
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        let script = format!(
+            "$shortcut = (New-Object -ComObject WScript.Shell).CreateShortcut({}); $shortcut.TargetPath = {}; $shortcut.Arguments = {}; $shortcut.WorkingDirectory = {}; $shortcut.Save()",
+            quote(&shortcut_path.to_string_lossy()),
+            quote(&executable.to_string_lossy()),
+            quote(arguments),
+            quote(&working_directory.to_string_lossy()),
+        );
+        let status = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .status()
+            .map_err(|error| format!("Failed to start PowerShell for shortcut creation: {error}"))?;
+        if !status.success() {
+            return Err(format!("Could not create shortcut at {}", shortcut_path.display()));
+        }
+        return Ok(shortcut_path);
+    }
+
+    let (shortcut_path, content) = if cfg!(target_os = "macos") {
+        (
+            directory.join(format!("{shortcut_name}.command")),
+            format!(
+                "#!/bin/sh\ncd \"{}\" && exec \"{}\" {arguments}\n",
+                working_directory.display(),
+                executable.display()
+            ),
+        )
+    } else {
+        (
+            directory.join(format!("{shortcut_name}.desktop")),
+            format!(
+                "[Desktop Entry]\nType=Application\nVersion=1.0\nName={shortcut_name}\nExec=\"{}\" {arguments}\nPath={}\nTerminal=false\nCategories=Game;\n",
+                executable.display(),
+                working_directory.display()
+            ),
+        )
+    };
+    fs::write(&shortcut_path, content)
+        .map_err(|error| format!("Could not write shortcut at {}: {error}", shortcut_path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut permissions = fs::metadata(&shortcut_path)
+            .map_err(|error| format!("Could not read shortcut metadata at {}: {error}", shortcut_path.display()))?
+            .permissions();
+        permissions.set_mode(0o755);
+        fs::set_permissions(&shortcut_path, permissions)
+            .map_err(|error| format!("Could not make shortcut at {} executable: {error}", shortcut_path.display()))?;
+    }
+    Ok(shortcut_path)
+}
+
+fn run_install_job(
+    app_handle: &tauri::AppHandle,
+    connection: &Connection,
+    user_id: &str,
+    job: &mut InstallJobResponse,
+) -> Result<(), String> {
+    use tauri::Emitter;
+
+    let source = classify_install_source(Path::new(&job.source_path))?;
+    let install_path = PathBuf::from(&job.install_path);
+    let (current_target, arguments) = library_import::find_custom_game_launch_target(connection, user_id, &job.external_id)?
+        .unwrap_or_default();
+
+    let job_cell = std::cell::RefCell::new(&mut *job);
+    let mut last_reported_bytes = 0;
+    let installed_target = install_source_files(
+        &source,
+        &install_path,
+        Some(current_target.as_str()).filter(|target| !target.is_empty()),
+        &mut |total_bytes| {
+            let mut job = job_cell.borrow_mut();
+            job.bytes_total = total_bytes;
+            job.updated_at = Utc::now().to_rfc3339();
+            let _ = save_install_job_progress(connection, &job);
+            let _ = app_handle.emit(INSTALL_JOB_PROGRESS_EVENT, (*job).clone());
+        },
+        &mut |copied_bytes| {
+            let mut job = job_cell.borrow_mut();
+            job.bytes_done += copied_bytes;
+            if job.bytes_done - last_reported_bytes >= INSTALL_JOB_PROGRESS_STEP_BYTES {
+                last_reported_bytes = job.bytes_done;
+                job.updated_at = Utc::now().to_rfc3339();
+                let _ = save_install_job_progress(connection, &job);
+                let _ = app_handle.emit(INSTALL_JOB_PROGRESS_EVENT, (*job).clone());
+            }
+        },
+    )?;
+
+    let launch_target = installed_target.unwrap_or_else(|| PathBuf::from(&current_target));
+    let launch_target_text = launch_target.to_string_lossy().into_owned();
+    if !launch_target_text.is_empty() {
+        library_import::save_custom_game_launch_target(
+            connection,
+            user_id,
+            &job.external_id,
+            &launch_target_text,
+            arguments.as_deref(),
+        )?;
+    }
+    connection
+        .execute(
+            "UPDATE games SET installed = 1 WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+            params![user_id, job.provider, job.external_id],
+        )
+        .map_err(|error| format!("Failed to mark game as installed: {error}"))?;
+
+    if job.create_desktop_shortcut || job.create_application_shortcut {
+        if !launch_target.is_file() {
+            return Err(String::from("Installed, but no executable was found to create shortcuts for"));
+        }
+        let game_name = connection
+            .query_row(
+                "SELECT name FROM games WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+                params![user_id, job.provider, job.external_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()
+            .map_err(|error| format!("Failed to query game name for shortcut: {error}"))?
+            .unwrap_or_else(|| job.external_id.clone());
+        if job.create_desktop_shortcut {
+            create_executable_shortcut(
+                &resolve_desktop_shortcuts_directory()?,
+                &game_name,
+                &launch_target,
+                arguments.as_deref(),
+            )?;
+        }
+        if job.create_application_shortcut {
+            create_executable_shortcut(
+                &resolve_application_shortcuts_directory()?,
+                &game_name,
+                &launch_target,
+                arguments.as_deref(),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn process_next_install_job(app_handle: &tauri::AppHandle, state: &AppState) -> Result<bool, String> {
+    use tauri::Emitter;
+
+    let connection = open_connection(&state.db_path)?;
+    let Some((user_id, mut job)) = take_next_install_job(&connection)? else {
+        return Ok(false);
+    };
+    let _ = app_handle.emit(INSTALL_JOB_PROGRESS_EVENT, job.clone());
+
+    let outcome = run_install_job(app_handle, &connection, &user_id, &mut job);
+    job.status = match &outcome {
+        Ok(()) => INSTALL_JOB_STATUS_COMPLETED,
+        Err(_) => INSTALL_JOB_STATUS_FAILED,
+    }
+    .to_owned();
+    job.error = outcome.err();
+    job.updated_at = Utc::now().to_rfc3339();
+    save_install_job_progress(&connection, &job)?;
+    let _ = app_handle.emit(INSTALL_JOB_PROGRESS_EVENT, job);
+    Ok(true)
+}
+
+/// Installs queued custom games one at a time. Jobs interrupted by a previous shutdown are
+/// marked failed rather than resumed, since their files may be half-copied.
+pub(crate) fn spawn_install_job_worker(app_handle: tauri::AppHandle) {
+    use tauri::Manager;
+
+    let _ = thread::Builder::new()
+        .name("install-job-worker".into())
+        .spawn(move || {
+            {
+                let state = app_handle.state::<AppState>();
+                if let Ok(connection) = open_connection(&state.db_path) {
+                    let _ = connection.execute(
+                        "UPDATE install_jobs SET status = ?1, error = ?2, updated_at = ?3 WHERE status = ?4",
+                        params![
+                            INSTALL_JOB_STATUS_FAILED,
+                            "Interrupted before it finished",
+                            Utc::now().to_rfc3339(),
+                            INSTALL_JOB_STATUS_INSTALLING
+                        ],
+                    );
+                }
+            }
+            loop {
+                let state = app_handle.state::<AppState>();
+                match process_next_install_job(&app_handle, state.inner()) {
+                    Ok(true) => continue,
+                    Ok(false) => {}
+                    Err(error) => eprintln!("Failed to process install job: {error}"),
+                }
+                thread::sleep(INSTALL_JOB_POLL_INTERVAL);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn install_source_files_copies_folder_and_rebases_target() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let source_directory = dir.path().join("source");
+        fs::create_dir_all(source_directory.join("bin")).expect("create source");
+        fs::write(source_directory.join("bin").join("game.exe"), b"game").expect("write executable");
+        fs::write(source_directory.join("data.pak"), b"data").expect("write data");
+        let install_path = dir.path().join("installed");
+        let current_target = source_directory.join("bin").join("game.exe");
+
+        let mut total_bytes = 0;
+        let mut copied_bytes = 0;
+        let installed_target = install_source_files(
+            &classify_install_source(&source_directory).expect("classify source"),
+            &install_path,
+            current_target.to_str(),
+            &mut |total| total_bytes = total,
+            &mut |copied| copied_bytes += copied,
+        )
+        .expect("install files");
+
+        assert_eq!(installed_target, Some(install_path.join("bin").join("game.exe")));
+        assert_eq!(total_bytes, 8);
+        assert_eq!(copied_bytes, 8);
+        assert_eq!(fs::read(install_path.join("data.pak")).expect("read data"), b"data");
+    }
+}
@@ -0,0 +1,204 @@
+use crate::*;
+use crate::application::error::{AppError, AppResult};
+use crate::application::services::{game_actions_service, library_service};
+use std::process::ExitCode;
+
+/// Must match `identifier` in `tauri.conf.json`, which names the desktop app's data directory.
+const APP_IDENTIFIER: &str = "com.geo.catalyst";
+const CLI_USAGE: &str = "\
+Usage: catalyst-cli <command>
+
+Commands:
+  sync [--full]                         Sync the Steam library of the signed-in account
+  play <provider> <external-id> [opts]  Launch a game, passing any remaining arguments as launch options
+  export [--json]                       Print the library, as JSON with --json
+  help                                  Show this message
+
+Set CATALYST_DATA_DIR to use a data directory other than the desktop app's.";
+
+/// Where the desktop app keeps its database, mirroring Tauri's `app_data_dir` without
+/// starting a Tauri app.
+fn resolve_cli_app_data_dir() -> Result<PathBuf, String> {
+    if let Some(data_dir) = std::env::var_os("CATALYST_DATA_DIR").filter(|value| !value.is_empty()) {
+        return Ok(PathBuf::from(data_dir));
+    }
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let data_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".local").join("share")))
+    };
+    data_dir
+        .map(|data_dir| data_dir.join(APP_IDENTIFIER))
+        .ok_or_else(|| String::from("Could not resolve the app data directory; set CATALYST_DATA_DIR"))
+}
+
+#[derive(Debug, PartialEq)]
+enum CliCommand {
+    Help,
+    Sync { full: bool },
+    Play { provider: String, external_id: String, launch_options: Option<String> },
+    Export { json: bool },
+}
+
+/// Errors are usage errors; `run_cli` prints them above the usage text.
+fn parse_cli_args(args: &[String]) -> Result<CliCommand, String> {
+    let Some((command, args)) = args.split_first() else {
+        return Err(String::from("Missing command"));
+    };
+    let has_flag = |flag: &str| args.iter().any(|arg| arg == flag);
+    match command.as_str() {
+        "help" | "--help" | "-h" => Ok(CliCommand::Help),
+        "sync" => Ok(CliCommand::Sync { full: has_flag("--full") }),
+        "play" => {
+            let [provider, external_id, launch_options @ ..] = args else {
+                return Err(String::from("play needs a provider and an external ID"));
+            };
+            Ok(CliCommand::Play {
+                provider: provider.clone(),
+                external_id: external_id.clone(),
+                launch_options: (!launch_options.is_empty()).then(|| launch_options.join(" ")),
+            })
+        }
+        "export" => Ok(CliCommand::Export { json: has_flag("--json") }),
+        _ => Err(format!("Unknown command '{command}'")),
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) -> AppResult<()> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|error| format!("Failed to serialize output: {error}"))?;
+    println!("{json}");
+    Ok(())
+}
+
+fn run_sync(state: &AppState, full: bool) -> AppResult<()> {
+    let response = library_service::sync_steam_library(state, Some(full), |progress| {
+        if progress.total > 0 {
+            eprintln!("{}: {}/{}", progress.phase, progress.processed, progress.total);
+        }
+    })?;
+    if let Some(warning) = &response.warning {
+        eprintln!("warning: {warning}");
+    }
+    println!(
+        "Synced {} games ({} added, {} updated, {} removed)",
+        response.synced_games, response.added_games, response.updated_games, response.removed_games
+    );
+    Ok(())
+}
+
+/// Stays alive while Catalyst watches the game, since post-exit hooks and Discord presence
+/// cleanup run on a thread that would die with the process.
+fn run_play(
+    state: &AppState,
+    provider: String,
+    external_id: String,
+    launch_options: Option<String>,
+) -> AppResult<()> {
+    let session = game_actions_service::launch_game(state, provider, external_id, launch_options)?;
+    if let Some(session) = session {
+        eprintln!("Waiting for the game to exit...");
+        session
+            .join()
+            .map_err(|_| AppError::internal("game_session_failed", "The game session watcher stopped unexpectedly"))?;
+    }
+    Ok(())
+}
+
+fn run_export(state: &AppState, json: bool) -> AppResult<()> {
+    let library = library_service::get_library(state, None, None, Some(false))?;
+    if json {
+        return print_json(&library.games);
+    }
+    for game in &library.games {
+        println!(
+            "{}\t{}\t{}\t{}",
+            game.provider,
+            game.external_id,
+            if game.installed { "installed" } else { "-" },
+            game.name
+        );
+    }
+    Ok(())
+}
+
+/// Entry point of `catalyst-cli`. Runs against the same database and session as the desktop
+/// app, so commands act on whichever account is signed in there.
+pub(crate) fn run_cli(args: Vec<String>) -> ExitCode {
+    let command = match parse_cli_args(&args) {
+        Ok(CliCommand::Help) => {
+            println!("{CLI_USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(command) => command,
+        Err(error) => {
+            eprintln!("{error}\n\n{CLI_USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let result = resolve_cli_app_data_dir()
+        .and_then(|app_data_dir| initialize_app_state(&app_data_dir))
+        .map_err(AppError::from)
+        .and_then(|state| match command {
+            CliCommand::Help => Ok(()),
+            CliCommand::Sync { full } => run_sync(&state, full),
+            CliCommand::Play { provider, external_id, launch_options } => {
+                run_play(&state, provider, external_id, launch_options)
+            }
+            CliCommand::Export { json } => run_export(&state, json),
+        });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error.message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_owned()).collect()
+    }
+
+    #[test]
+    fn parse_cli_args_reads_commands_and_flags() {
+        assert_eq!(parse_cli_args(&args(&["-h"])), Ok(CliCommand::Help));
+        assert_eq!(parse_cli_args(&args(&["sync"])), Ok(CliCommand::Sync { full: false }));
+        assert_eq!(parse_cli_args(&args(&["sync", "--full"])), Ok(CliCommand::Sync { full: true }));
+        assert_eq!(parse_cli_args(&args(&["export", "--json"])), Ok(CliCommand::Export { json: true }));
+        assert_eq!(
+            parse_cli_args(&args(&["play", "steam", "620", "-novid", "-high"])),
+            Ok(CliCommand::Play {
+                provider: String::from("steam"),
+                external_id: String::from("620"),
+                launch_options: Some(String::from("-novid -high")),
+            })
+        );
+        assert_eq!(
+            parse_cli_args(&args(&["play", "steam", "620"])),
+            Ok(CliCommand::Play {
+                provider: String::from("steam"),
+                external_id: String::from("620"),
+                launch_options: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_cli_args_rejects_missing_or_unknown_commands() {
+        assert!(parse_cli_args(&[]).is_err());
+        assert_eq!(parse_cli_args(&args(&["install"])), Err(String::from("Unknown command 'install'")));
+        assert!(parse_cli_args(&args(&["play", "steam"])).is_err());
+    }
+}
@@ -10,7 +10,6 @@ use tauri::State;
 pub(crate) fn logout(state: State<'_, AppState>) -> AppResult<()> {
     let session_token = get_state_session_token(state.inner())?;
     let connection = open_connection(&state.db_path)?;
-    cleanup_expired_sessions(&connection)?;
 
     if let Some(token) = session_token {
         invalidate_session_by_token(&connection, &token)?;
@@ -21,49 +20,120 @@ pub(crate) fn logout(state: State<'_, AppState>) -> AppResult<()> {
 
 #[tauri::command]
 pub(crate) fn get_session(state: State<'_, AppState>) -> AppResult<Option<PublicUser>> {
-    let connection = open_connection(&state.db_path)?;
-    cleanup_expired_sessions(&connection)?;
-
-    let Some(session_token) = get_state_session_token(state.inner())? else {
-        return Ok(None);
-    };
-
-    let user = find_user_by_session_token(&connection, &session_token)?;
-    if user.is_none() {
-        clear_active_session(state.inner())?;
-    }
-
-    Ok(user.map(|row| public_user_from_row(&row)))
+    crate::application::services::auth_service::get_session(state.inner())
 }
 
 #[tauri::command]
-pub(crate) async fn start_steam_auth(state: State<'_, AppState>) -> AppResult<SteamAuthResponse> {
+pub(crate) async fn start_steam_auth(
+    relink: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<SteamAuthResponse> {
     let db_path = state.db_path.clone();
-    let steam_api_key = state.steam_api_key.clone();
-    let steam_local_install_detection = state.steam_local_install_detection;
-    let steam_root_override = state.steam_root_override.clone();
+    let steam_api_key = state.steam_api_key();
+    let steam_local_install_detection = state.steam_local_install_detection();
+    let steam_root_override = state.steam_root_override();
     let current_session_token = get_state_session_token(state.inner())?;
+    let http_proxy = state.http_proxy();
+    let flow = crate::application::services::auth_service::SteamAuthFlow::start(state.inner())?;
 
-    let outcome = tauri::async_runtime::spawn_blocking(move || {
+    let flow_cancel_requested = Arc::clone(flow.cancel_requested());
+    let flow_result = tauri::async_runtime::spawn_blocking(move || {
         complete_steam_auth_flow(
             &db_path,
             steam_api_key,
             steam_local_install_detection,
             steam_root_override,
             current_session_token,
+            relink.unwrap_or(false),
+            &flow_cancel_requested,
+            http_proxy,
         )
     })
-    .await
-    .map_err(|error| format!("Steam auth task failed: {error}"))??;
-
-    persist_active_session(state.inner(), &outcome.session_token)?;
+    .await;
+    let cancel_requested = flow.cancel_requested().load(Ordering::SeqCst);
+    drop(flow);
+    let outcome = flow_result
+        .map_err(|error| format!("Steam auth task failed: {error}"))?
+        .map_err(|error| {
+            if cancel_requested {
+                AppError::conflict("steam_auth_cancelled", error)
+            } else {
+                AppError::from(error)
+            }
+        })?;
+    // Signing in syncs the Steam library.
+    state.library_lookup_cache.clear();
+
+    let two_factor_required = match outcome.session_token.as_deref() {
+        Some(session_token) => {
+            persist_active_session(state.inner(), session_token)?;
+            false
+        }
+        None => {
+            let mut pending = state
+                .pending_two_factor_login
+                .lock()
+                .map_err(|_| String::from("Failed to lock pending two-factor login"))?;
+            *pending = Some(two_factor::PendingTwoFactorLogin {
+                user_id: outcome.user.id.clone(),
+                synced_games: outcome.synced_games,
+                expires_at: Instant::now() + two_factor::TWO_FACTOR_LOGIN_TTL,
+                failed_attempts: 0,
+            });
+            true
+        }
+    };
 
     Ok(SteamAuthResponse {
         user: public_user_from_row(&outcome.user),
         synced_games: outcome.synced_games,
+        two_factor_required,
     })
 }
 
+#[tauri::command]
+pub(crate) fn cancel_steam_auth(state: State<'_, AppState>) -> AppResult<bool> {
+    crate::application::services::auth_service::cancel_steam_auth(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn verify_2fa_login(code: String, state: State<'_, AppState>) -> AppResult<SteamAuthResponse> {
+    crate::application::services::auth_service::verify_two_factor_login(state.inner(), code)
+}
+
+#[tauri::command]
+pub(crate) fn enable_2fa(state: State<'_, AppState>) -> AppResult<two_factor::TwoFactorSetupResponse> {
+    crate::application::services::auth_service::enable_two_factor(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn verify_2fa_setup(
+    code: String,
+    state: State<'_, AppState>,
+) -> AppResult<two_factor::TwoFactorRecoveryCodesResponse> {
+    crate::application::services::auth_service::verify_two_factor_setup(state.inner(), code)
+}
+
+#[tauri::command]
+pub(crate) fn disable_2fa(code: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::auth_service::disable_two_factor(state.inner(), code)
+}
+
+#[tauri::command]
+pub(crate) fn list_sessions(state: State<'_, AppState>) -> AppResult<Vec<SessionResponse>> {
+    crate::application::services::auth_service::list_sessions(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn revoke_session(session_id: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::auth_service::revoke_session(state.inner(), session_id)
+}
+
+#[tauri::command]
+pub(crate) fn delete_account(password: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::auth_service::delete_account(state.inner(), password)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,13 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::application::services::batch_service::{BatchCommandRequest, BatchCommandResultResponse};
+use std::collections::BTreeMap;
+use tauri::State;
+
+#[tauri::command]
+pub(crate) fn batch(
+    commands: Vec<BatchCommandRequest>,
+    state: State<'_, AppState>,
+) -> AppResult<BTreeMap<usize, BatchCommandResultResponse>> {
+    crate::application::services::batch_service::run_batch(state.inner(), commands)
+}
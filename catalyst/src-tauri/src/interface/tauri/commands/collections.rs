@@ -1,17 +1,20 @@
 use crate::*;
 use crate::application::error::AppResult;
+use crate::application::services::library_service::LibraryGameIdentityInput;
 use tauri::State;
 
 #[tauri::command]
 pub(crate) fn list_collections(
     provider: Option<String>,
     external_id: Option<String>,
+    include_hidden: Option<bool>,
     state: State<'_, AppState>,
 ) -> AppResult<Vec<CollectionResponse>> {
     crate::application::services::collection_service::list_collections(
         state.inner(),
         provider,
         external_id,
+        include_hidden,
     )
 }
 
@@ -33,6 +36,32 @@ pub(crate) fn rename_collection(
     )
 }
 
+#[tauri::command]
+pub(crate) fn set_collection_cover_image(
+    collection_id: String,
+    path: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<CollectionResponse> {
+    crate::application::services::collection_service::set_collection_cover_image(
+        state.inner(),
+        collection_id,
+        path,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn set_collection_accent_color(
+    collection_id: String,
+    accent_color: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<CollectionResponse> {
+    crate::application::services::collection_service::set_collection_accent_color(
+        state.inner(),
+        collection_id,
+        accent_color,
+    )
+}
+
 #[tauri::command]
 pub(crate) fn delete_collection(collection_id: String, state: State<'_, AppState>) -> AppResult<()> {
     crate::application::services::collection_service::delete_collection(state.inner(), collection_id)
@@ -52,3 +81,16 @@ pub(crate) fn add_game_to_collection(
         collection_id,
     )
 }
+
+#[tauri::command]
+pub(crate) fn set_collection_game_order(
+    collection_id: String,
+    games: Vec<LibraryGameIdentityInput>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::collection_service::set_collection_game_order(
+        state.inner(),
+        collection_id,
+        games,
+    )
+}
@@ -0,0 +1,9 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::application::services::deep_link_service::DeepLinkActionResponse;
+use tauri::State;
+
+#[tauri::command]
+pub(crate) fn handle_deep_link(url: String, state: State<'_, AppState>) -> AppResult<DeepLinkActionResponse> {
+    crate::application::services::deep_link_service::handle_deep_link(state.inner(), url)
+}
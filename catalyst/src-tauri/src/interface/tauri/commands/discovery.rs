@@ -0,0 +1,54 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::developer_releases::DeveloperReleasesResponse;
+use crate::discovery_feed::{
+    DiscoveryFeedResponse, DiscoveryFollowResponse, DiscoveryRefreshResponse, DiscoverySourceKind,
+};
+use tauri::{AppHandle, Manager, State};
+
+#[tauri::command]
+pub(crate) fn get_discovery_feed(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<DiscoveryFeedResponse> {
+    crate::application::services::discovery_service::get_discovery_feed(state.inner(), limit)
+}
+
+#[tauri::command]
+pub(crate) async fn refresh_discovery_feed(app_handle: AppHandle) -> AppResult<DiscoveryRefreshResponse> {
+    // One store request per followed source and watched developer, plus wishlist prices and
+    // new release genres.
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        crate::application::services::discovery_service::refresh_discovery_feed(state.inner())
+    })
+    .await
+    .map_err(|error| format!("Discovery feed refresh task failed: {error}"))?
+}
+
+#[tauri::command]
+pub(crate) fn follow_discovery_source(
+    kind: DiscoverySourceKind,
+    source: String,
+    name: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<DiscoveryFollowResponse> {
+    crate::application::services::discovery_service::follow_discovery_source(state.inner(), kind, source, name)
+}
+
+#[tauri::command]
+pub(crate) fn unfollow_discovery_source(
+    kind: DiscoverySourceKind,
+    source_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::discovery_service::unfollow_discovery_source(state.inner(), kind, source_id)
+}
+
+#[tauri::command]
+pub(crate) fn get_developer_releases(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<DeveloperReleasesResponse> {
+    crate::application::services::discovery_service::get_developer_releases(state.inner(), limit)
+}
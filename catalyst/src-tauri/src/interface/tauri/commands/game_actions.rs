@@ -1,6 +1,9 @@
 use crate::*;
 use crate::application::error::AppResult;
-use tauri::State;
+use crate::game_shortcuts::GameShortcutResponse;
+use crate::game_verification::GameVerificationStatusResponse;
+use crate::install_jobs::InstallJobResponse;
+use tauri::{AppHandle, Emitter, Manager, State};
 
 #[tauri::command]
 pub(crate) fn play_game(
@@ -17,25 +20,47 @@ pub(crate) fn play_game(
     )
 }
 
+#[tauri::command]
+pub(crate) fn stop_game(
+    provider: String,
+    external_id: String,
+    force: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::stop_game(
+        state.inner(),
+        provider,
+        external_id,
+        force.unwrap_or(false),
+    )
+}
+
 #[tauri::command]
 pub(crate) fn install_game(
     provider: String,
     external_id: String,
     install_path: Option<String>,
+    source_path: Option<String>,
     create_desktop_shortcut: Option<bool>,
     create_application_shortcut: Option<bool>,
     state: State<'_, AppState>,
-) -> AppResult<()> {
+) -> AppResult<Option<InstallJobResponse>> {
     crate::application::services::game_actions_service::install_game(
         state.inner(),
         provider,
         external_id,
         install_path,
+        source_path,
         create_desktop_shortcut,
         create_application_shortcut,
     )
 }
 
+#[tauri::command]
+pub(crate) fn list_install_jobs(state: State<'_, AppState>) -> AppResult<Vec<InstallJobResponse>> {
+    crate::application::services::game_actions_service::list_install_jobs(state.inner())
+}
+
 #[tauri::command]
 pub(crate) fn uninstall_game(
     provider: String,
@@ -75,6 +100,339 @@ pub(crate) fn backup_game_files(
     )
 }
 
+#[tauri::command]
+pub(crate) fn get_game_save_locations(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<String>> {
+    crate::application::services::game_actions_service::get_game_save_locations(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn set_game_save_locations(
+    provider: String,
+    external_id: String,
+    templates: Vec<String>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<String>> {
+    crate::application::services::game_actions_service::set_game_save_locations(
+        state.inner(),
+        provider,
+        external_id,
+        templates,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn backup_game_saves(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<crate::save_backups::SaveBackupResponse> {
+    crate::application::services::game_actions_service::backup_game_saves(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn list_save_backups(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<crate::save_backups::SaveBackupResponse>> {
+    crate::application::services::game_actions_service::list_save_backups(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn restore_game_saves(
+    provider: String,
+    external_id: String,
+    backup_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<crate::save_backups::SaveRestoreResponse> {
+    crate::application::services::game_actions_service::restore_game_saves(
+        state.inner(),
+        provider,
+        external_id,
+        backup_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn list_game_screenshots(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<crate::screenshots::GameScreenshotResponse>> {
+    crate::application::services::game_actions_service::list_game_screenshots(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn get_game_sessions(
+    provider: String,
+    external_id: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<crate::play_sessions::GameSessionResponse>> {
+    crate::application::services::game_actions_service::get_game_sessions(
+        state.inner(),
+        provider,
+        external_id,
+        limit,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn annotate_game_session(
+    session_id: String,
+    notes: Option<String>,
+    rating: Option<i64>,
+    state: State<'_, AppState>,
+) -> AppResult<crate::play_sessions::GameSessionResponse> {
+    crate::application::services::game_actions_service::annotate_game_session(
+        state.inner(),
+        session_id,
+        notes,
+        rating,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn open_screenshots_folder(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::open_screenshots_folder(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn set_game_screenshot_directory(
+    provider: String,
+    external_id: String,
+    directory: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::set_game_screenshot_directory(
+        state.inner(),
+        provider,
+        external_id,
+        directory,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn move_game_install(
+    provider: String,
+    external_id: String,
+    target_library_path: String,
+    app_handle: AppHandle,
+) -> AppResult<GameMoveResponse> {
+    // Copying across drives can take minutes, so keep it off the main thread.
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        crate::application::services::game_actions_service::move_game_install(
+            state.inner(),
+            provider,
+            external_id,
+            target_library_path,
+            |progress| {
+                let _ = app_handle.emit(GAME_MOVE_PROGRESS_EVENT, progress);
+            },
+        )
+    })
+    .await
+    .map_err(|error| format!("Game move task failed: {error}"))?
+}
+
+#[tauri::command]
+pub(crate) async fn install_game_with_steamcmd(
+    provider: String,
+    external_id: String,
+    options: crate::steamcmd::SteamCmdInstallOptions,
+    app_handle: AppHandle,
+) -> AppResult<crate::steamcmd::SteamCmdInstallResponse> {
+    // steamcmd downloads the whole game, so keep it off the main thread.
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        crate::application::services::game_actions_service::install_game_with_steamcmd(
+            state.inner(),
+            provider,
+            external_id,
+            options,
+            |progress| {
+                let _ = app_handle.emit(crate::steamcmd::STEAMCMD_INSTALL_PROGRESS_EVENT, progress);
+            },
+        )
+    })
+    .await
+    .map_err(|error| format!("steamcmd install task failed: {error}"))?
+}
+
+#[tauri::command]
+pub(crate) fn list_remote_play_hosts(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<crate::remote_play::RemotePlayHostResponse>> {
+    crate::application::services::game_actions_service::list_remote_play_hosts(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn play_game_remote(
+    provider: String,
+    external_id: String,
+    host_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::play_game_remote(
+        state.inner(),
+        provider,
+        external_id,
+        host_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn wake_gaming_pc(state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::game_actions_service::wake_gaming_pc(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn start_gaming_pc_download(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::start_gaming_pc_download(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn list_game_workshop_items(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<crate::workshop::SteamWorkshopItemResponse>> {
+    crate::application::services::game_actions_service::list_game_workshop_items(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn open_workshop_page(
+    provider: String,
+    external_id: String,
+    published_file_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::open_workshop_page(
+        state.inner(),
+        provider,
+        external_id,
+        published_file_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn get_game_storage_breakdown(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<GameStorageBreakdownResponse> {
+    crate::application::services::game_actions_service::get_game_storage_breakdown(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn clear_game_shader_cache(
+    provider: String,
+    external_id: String,
+    confirm: bool,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::clear_game_shader_cache(
+        state.inner(),
+        provider,
+        external_id,
+        confirm,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn delete_game_compatdata(
+    provider: String,
+    external_id: String,
+    confirm: bool,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::delete_game_compatdata(
+        state.inner(),
+        provider,
+        external_id,
+        confirm,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn open_game_proton_prefix(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::open_game_proton_prefix(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) async fn run_game_prefix_tool(
+    provider: String,
+    external_id: String,
+    verbs: Vec<String>,
+    app_handle: AppHandle,
+) -> AppResult<crate::proton_prefix::ProtonPrefixToolResponse> {
+    // Winetricks verbs download and run installers, so keep them off the main thread.
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        crate::application::services::game_actions_service::run_game_prefix_tool(
+            state.inner(),
+            provider,
+            external_id,
+            verbs,
+        )
+    })
+    .await
+    .map_err(|error| format!("Prefix tool task failed: {error}"))?
+}
+
 #[tauri::command]
 pub(crate) fn verify_game_files(
     provider: String,
@@ -88,6 +446,19 @@ pub(crate) fn verify_game_files(
     )
 }
 
+#[tauri::command]
+pub(crate) fn get_game_verification_status(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<GameVerificationStatusResponse> {
+    crate::application::services::game_actions_service::get_game_verification_status(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
 #[tauri::command]
 pub(crate) fn add_game_desktop_shortcut(
     provider: String,
@@ -101,6 +472,21 @@ pub(crate) fn add_game_desktop_shortcut(
     )
 }
 
+#[tauri::command]
+pub(crate) fn create_game_shortcut(
+    provider: String,
+    external_id: String,
+    location: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<GameShortcutResponse> {
+    crate::application::services::game_actions_service::create_game_shortcut(
+        state.inner(),
+        provider,
+        external_id,
+        location,
+    )
+}
+
 #[tauri::command]
 pub(crate) fn open_game_recording_settings(
     provider: String,
@@ -113,3 +499,16 @@ pub(crate) fn open_game_recording_settings(
         external_id,
     )
 }
+
+#[tauri::command]
+pub(crate) fn play_soundtrack(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_actions_service::play_soundtrack(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
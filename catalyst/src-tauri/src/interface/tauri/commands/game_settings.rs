@@ -1,5 +1,11 @@
 use crate::*;
 use crate::application::error::AppResult;
+use crate::application::services::game_settings_service::{
+    BulkUpdatePolicyInput, BulkUpdatePolicyResponse, SetGamesHiddenResponse,
+};
+use crate::application::services::library_service::LibraryGameIdentityInput;
+use crate::private_games::PrivateGamesLockStatusResponse;
+use crate::steam_update_size::PendingUpdateSizeResponse;
 use tauri::State;
 
 #[tauri::command]
@@ -58,6 +64,47 @@ pub(crate) fn set_game_privacy_settings(
     )
 }
 
+#[tauri::command]
+pub(crate) fn set_games_hidden(
+    games: Vec<LibraryGameIdentityInput>,
+    hidden: bool,
+    state: State<'_, AppState>,
+) -> AppResult<SetGamesHiddenResponse> {
+    crate::application::services::game_settings_service::set_games_hidden(state.inner(), games, hidden)
+}
+
+#[tauri::command]
+pub(crate) fn get_private_games_lock_status(state: State<'_, AppState>) -> AppResult<PrivateGamesLockStatusResponse> {
+    crate::application::services::game_settings_service::get_private_games_lock_status(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_private_games_pin(
+    pin: String,
+    current_pin: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<PrivateGamesLockStatusResponse> {
+    crate::application::services::game_settings_service::set_private_games_pin(state.inner(), pin, current_pin)
+}
+
+#[tauri::command]
+pub(crate) fn clear_private_games_pin(
+    current_pin: String,
+    state: State<'_, AppState>,
+) -> AppResult<PrivateGamesLockStatusResponse> {
+    crate::application::services::game_settings_service::clear_private_games_pin(state.inner(), current_pin)
+}
+
+#[tauri::command]
+pub(crate) fn unlock_private_games(pin: String, state: State<'_, AppState>) -> AppResult<PrivateGamesLockStatusResponse> {
+    crate::application::services::game_settings_service::unlock_private_games(state.inner(), pin)
+}
+
+#[tauri::command]
+pub(crate) fn lock_private_games(state: State<'_, AppState>) -> AppResult<PrivateGamesLockStatusResponse> {
+    crate::application::services::game_settings_service::lock_private_games(state.inner())
+}
+
 #[tauri::command]
 pub(crate) fn clear_game_overlay_data(
     provider: String,
@@ -99,6 +146,14 @@ pub(crate) fn set_game_properties_settings(
     )
 }
 
+#[tauri::command]
+pub(crate) fn bulk_set_update_policy(
+    policy: BulkUpdatePolicyInput,
+    state: State<'_, AppState>,
+) -> AppResult<BulkUpdatePolicyResponse> {
+    crate::application::services::game_settings_service::bulk_set_update_policy(state.inner(), policy)
+}
+
 #[tauri::command]
 pub(crate) fn get_game_customization_artwork(
     provider: String,
@@ -112,6 +167,34 @@ pub(crate) fn get_game_customization_artwork(
     )
 }
 
+#[tauri::command]
+pub(crate) fn set_game_custom_artwork(
+    provider: String,
+    external_id: String,
+    path: String,
+    state: State<'_, AppState>,
+) -> AppResult<String> {
+    crate::application::services::game_settings_service::set_game_custom_artwork(
+        state.inner(),
+        provider,
+        external_id,
+        path,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn clear_game_custom_artwork(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_settings_service::clear_game_custom_artwork(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
 #[tauri::command]
 pub(crate) fn get_game_installation_details(
     provider: String,
@@ -138,6 +221,49 @@ pub(crate) fn get_game_install_size_estimate(
     )
 }
 
+#[tauri::command]
+pub(crate) fn get_pending_update_size(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<PendingUpdateSizeResponse> {
+    crate::application::services::game_settings_service::get_pending_update_size(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn list_controller_layouts(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<crate::controller_layouts::ControllerLayoutsResponse> {
+    crate::application::services::game_settings_service::list_controller_layouts(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn set_game_controller_layout(
+    provider: String,
+    external_id: String,
+    controller_type: String,
+    layout_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<()> {
+    crate::application::services::game_settings_service::set_game_controller_layout(
+        state.inner(),
+        provider,
+        external_id,
+        controller_type,
+        layout_id,
+    )
+}
+
 #[tauri::command]
 pub(crate) fn list_game_install_locations(
     provider: String,
@@ -150,3 +276,36 @@ pub(crate) fn list_game_install_locations(
         external_id,
     )
 }
+
+#[tauri::command]
+pub(crate) fn get_game_cloud_status(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<SteamCloudStatusResponse> {
+    crate::application::services::game_settings_service::get_game_cloud_status(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn set_game_cloud_enabled(
+    provider: String,
+    external_id: String,
+    enabled: bool,
+    state: State<'_, AppState>,
+) -> AppResult<SteamCloudStatusResponse> {
+    crate::application::services::game_settings_service::set_game_cloud_enabled(
+        state.inner(),
+        provider,
+        external_id,
+        enabled,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn list_launch_wrappers() -> AppResult<Vec<LaunchWrapperResponse>> {
+    crate::application::services::game_settings_service::list_launch_wrappers()
+}
@@ -0,0 +1,38 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::application::services::launch_template_service::{
+    LaunchTemplateApplyResultResponse, LaunchTemplateInput, LaunchTemplateTargetInput,
+};
+use crate::launch_templates::LaunchTemplateResponse;
+use tauri::State;
+
+#[tauri::command]
+pub(crate) fn list_launch_templates(state: State<'_, AppState>) -> AppResult<Vec<LaunchTemplateResponse>> {
+    crate::application::services::launch_template_service::list_launch_templates(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn save_launch_template(
+    template: LaunchTemplateInput,
+    state: State<'_, AppState>,
+) -> AppResult<LaunchTemplateResponse> {
+    crate::application::services::launch_template_service::save_launch_template(state.inner(), template)
+}
+
+#[tauri::command]
+pub(crate) fn delete_launch_template(template_id: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::launch_template_service::delete_launch_template(state.inner(), template_id)
+}
+
+#[tauri::command]
+pub(crate) fn apply_launch_template(
+    template_id: String,
+    games: Vec<LaunchTemplateTargetInput>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<LaunchTemplateApplyResultResponse>> {
+    crate::application::services::launch_template_service::apply_launch_template(
+        state.inner(),
+        template_id,
+        games,
+    )
+}
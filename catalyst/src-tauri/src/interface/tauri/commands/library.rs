@@ -1,19 +1,253 @@
 use crate::*;
 use crate::application::error::AppResult;
-use crate::application::services::library_service::GameStoreMetadataResponse;
+use crate::application::services::library_service::{
+    GameStoreMetadataResponse, LibraryGameIdentityInput, LibraryQueryFilter, SteamTagCountResponse,
+};
+use crate::backlog_picker::{GameSuggestionResponse, SuggestGameWeights};
+use crate::library_stats::LibraryStatsResponse;
+use crate::play_sessions::PlaytimeTimelineResponse;
 use tauri::{State, AppHandle};
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 
 #[tauri::command]
-pub(crate) fn get_library(state: State<'_, AppState>) -> AppResult<LibraryResponse> {
-    crate::application::services::library_service::get_library(state.inner())
+pub(crate) fn get_library(
+    merged: Option<bool>,
+    filter: Option<LibraryQueryFilter>,
+    include_hidden: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<LibraryResponse> {
+    crate::application::services::library_service::get_library(state.inner(), merged, filter, include_hidden)
+}
+
+#[tauri::command]
+pub(crate) fn list_steam_tags(
+    include_hidden: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<SteamTagCountResponse>> {
+    crate::application::services::library_service::list_steam_tags(state.inner(), include_hidden)
+}
+
+#[tauri::command]
+pub(crate) fn list_hidden_games(state: State<'_, AppState>) -> AppResult<Vec<GameResponse>> {
+    crate::application::services::library_service::list_hidden_games(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn import_playnite_library(
+    path: String,
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<library_import::LibraryImportResponse> {
+    crate::application::services::library_service::import_playnite_library(
+        state.inner(),
+        path,
+        dry_run,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn import_lutris_library(
+    path: Option<String>,
+    dry_run: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<library_import::LibraryImportResponse> {
+    crate::application::services::library_service::import_lutris_library(
+        state.inner(),
+        path,
+        dry_run,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn link_library_games(
+    games: Vec<LibraryGameIdentityInput>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<library_merge::MergedGameResponse>> {
+    crate::application::services::library_service::link_library_games(state.inner(), games)
+}
+
+#[tauri::command]
+pub(crate) fn unlink_library_game(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<library_merge::MergedGameResponse>> {
+    crate::application::services::library_service::unlink_library_game(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn set_preferred_launch_provider(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<library_merge::MergedGameResponse> {
+    crate::application::services::library_service::set_preferred_launch_provider(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn get_home_data(state: State<'_, AppState>) -> AppResult<HomeDataResponse> {
+    crate::application::services::library_service::get_home_data(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn get_startup_snapshot(state: State<'_, AppState>) -> AppResult<StartupSnapshotResponse> {
+    crate::application::services::library_service::get_startup_snapshot(state.inner())
 }
 
 // `get_steam_status` command removed; Steam status is available via server-side
 // logic and no longer exposed directly to the frontend.
 #[tauri::command]
-pub(crate) fn sync_steam_library(state: State<'_, AppState>) -> AppResult<SteamSyncResponse> {
-    crate::application::services::library_service::sync_steam_library(state.inner())
+pub(crate) async fn sync_steam_library(
+    full: Option<bool>,
+    app_handle: AppHandle,
+) -> AppResult<SteamSyncResponse> {
+    // Run on a blocking worker so `cancel_steam_sync` can be handled while the sync is in flight.
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        crate::application::services::library_service::sync_steam_library(
+            state.inner(),
+            full,
+            |progress| {
+                let _ = app_handle.emit(STEAM_SYNC_PROGRESS_EVENT, progress);
+            },
+        )
+    })
+    .await
+    .map_err(|error| format!("Steam sync task failed: {error}"))?
+}
+
+#[tauri::command]
+pub(crate) async fn refresh_achievements(
+    provider: Option<String>,
+    external_id: Option<String>,
+    app_handle: AppHandle,
+) -> AppResult<crate::achievements::AchievementsRefreshResponse> {
+    // A full refresh makes one Steam request per played game.
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        crate::application::services::library_service::refresh_achievements(
+            state.inner(),
+            provider,
+            external_id,
+        )
+    })
+    .await
+    .map_err(|error| format!("Achievements refresh task failed: {error}"))?
+}
+
+#[tauri::command]
+pub(crate) fn sync_local_store_libraries(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<LocalProviderSyncResponse>> {
+    crate::application::services::library_service::sync_local_store_libraries(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn get_library_conflicts(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<crate::steam_library_conflicts::SteamLibraryConflictResponse>> {
+    crate::application::services::library_service::get_library_conflicts(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn search_library(
+    query: String,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<crate::library_search::LibrarySearchMatch>> {
+    crate::application::services::library_service::search_library(state.inner(), query, limit)
+}
+
+#[tauri::command]
+pub(crate) fn get_game_notes(provider: String, external_id: String, state: State<'_, AppState>) -> AppResult<String> {
+    crate::application::services::library_service::get_game_notes(state.inner(), provider, external_id)
+}
+
+#[tauri::command]
+pub(crate) fn save_game_notes(
+    provider: String,
+    external_id: String,
+    notes: String,
+    state: State<'_, AppState>,
+) -> AppResult<String> {
+    crate::application::services::library_service::save_game_notes(state.inner(), provider, external_id, notes)
+}
+
+#[tauri::command]
+pub(crate) fn set_game_display_name(
+    provider: String,
+    external_id: String,
+    display_name: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<Option<String>> {
+    crate::application::services::library_service::set_game_display_name(
+        state.inner(),
+        provider,
+        external_id,
+        display_name,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn add_game_user_tag(
+    provider: String,
+    external_id: String,
+    tag: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<String>> {
+    crate::application::services::library_service::add_game_user_tag(state.inner(), provider, external_id, tag)
+}
+
+#[tauri::command]
+pub(crate) fn remove_game_user_tag(
+    provider: String,
+    external_id: String,
+    tag: String,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<String>> {
+    crate::application::services::library_service::remove_game_user_tag(state.inner(), provider, external_id, tag)
+}
+
+#[tauri::command]
+pub(crate) fn get_storage_overview(state: State<'_, AppState>) -> AppResult<StorageOverviewResponse> {
+    crate::application::services::library_service::get_storage_overview(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn get_library_stats(refresh: Option<bool>, state: State<'_, AppState>) -> AppResult<LibraryStatsResponse> {
+    crate::application::services::library_service::get_library_stats(state.inner(), refresh)
+}
+
+#[tauri::command]
+pub(crate) fn get_playtime_timeline(
+    granularity: Option<String>,
+    year: Option<i32>,
+    state: State<'_, AppState>,
+) -> AppResult<PlaytimeTimelineResponse> {
+    crate::application::services::library_service::get_playtime_timeline(state.inner(), granularity, year)
+}
+
+#[tauri::command]
+pub(crate) fn suggest_game(
+    weights: Option<SuggestGameWeights>,
+    moods: Option<Vec<String>>,
+    count: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<GameSuggestionResponse>> {
+    crate::application::services::library_service::suggest_game(state.inner(), weights, moods, count)
+}
+
+#[tauri::command]
+pub(crate) fn cancel_steam_sync(state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::library_service::cancel_steam_sync(state.inner())
 }
 
 #[tauri::command]
@@ -31,11 +265,61 @@ pub(crate) fn set_game_favorite(
     )
 }
 
+#[tauri::command]
+pub(crate) fn set_games_favorite(
+    games: Vec<LibraryGameIdentityInput>,
+    favorite: bool,
+    state: State<'_, AppState>,
+) -> AppResult<usize> {
+    crate::application::services::library_service::set_games_favorite(state.inner(), games, favorite)
+}
+
 #[tauri::command]
 pub(crate) fn list_steam_downloads(state: State<'_, AppState>) -> AppResult<Vec<SteamDownloadProgressResponse>> {
     crate::application::services::library_service::list_steam_downloads(state.inner())
 }
 
+#[tauri::command]
+pub(crate) fn pause_download(external_id: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::library_service::pause_download(state.inner(), external_id)
+}
+
+#[tauri::command]
+pub(crate) fn resume_download(external_id: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::library_service::resume_download(state.inner(), external_id)
+}
+
+#[tauri::command]
+pub(crate) fn reorder_download_queue(
+    external_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<SteamDownloadProgressResponse>> {
+    crate::application::services::library_service::reorder_download_queue(state.inner(), external_ids)
+}
+
+#[tauri::command]
+pub(crate) fn get_download_history(
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<download_history::DownloadHistoryResponse> {
+    crate::application::services::library_service::get_download_history(state.inner(), limit)
+}
+
+#[tauri::command]
+pub(crate) fn get_download_rate_limit(
+    state: State<'_, AppState>,
+) -> AppResult<download_queue::DownloadRateLimitResponse> {
+    crate::application::services::library_service::get_download_rate_limit(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_download_rate_limit(
+    kbps: Option<u64>,
+    state: State<'_, AppState>,
+) -> AppResult<download_queue::DownloadRateLimitResponse> {
+    crate::application::services::library_service::set_download_rate_limit(state.inner(), kbps)
+}
+
 #[tauri::command]
 pub(crate) fn get_game_store_metadata(
     provider: String,
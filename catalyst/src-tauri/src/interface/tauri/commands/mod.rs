@@ -1,8 +1,15 @@
 pub mod auth;
+pub mod batch;
 pub mod collections;
+pub mod deep_link;
+pub mod discovery;
 pub mod game_actions;
 pub mod game_settings;
+pub mod launch_templates;
 pub mod library;
+pub mod notifications;
+pub mod roms;
+pub mod settings;
 pub mod steam;
 // Deprecated command helpers removed from source tree; keep module list
 // minimal to avoid exposing unused code paths via the invoke handler.
@@ -0,0 +1,21 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::notifications::NotificationResponse;
+use tauri::State;
+
+#[tauri::command]
+pub(crate) fn list_notifications(
+    unread_only: Option<bool>,
+    limit: Option<usize>,
+    state: State<'_, AppState>,
+) -> AppResult<Vec<NotificationResponse>> {
+    crate::application::services::notification_service::list_notifications(state.inner(), unread_only, limit)
+}
+
+#[tauri::command]
+pub(crate) fn mark_notification_read(
+    notification_id: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<usize> {
+    crate::application::services::notification_service::mark_notification_read(state.inner(), notification_id)
+}
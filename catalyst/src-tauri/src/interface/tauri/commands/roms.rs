@@ -0,0 +1,47 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::application::services::rom_service::EmulatorProfileInput;
+use crate::rom_library::{EmulatorProfileResponse, RomDirectoryResponse, RomScanResponse};
+use tauri::State;
+
+#[tauri::command]
+pub(crate) fn list_emulator_profiles(state: State<'_, AppState>) -> AppResult<Vec<EmulatorProfileResponse>> {
+    crate::application::services::rom_service::list_emulator_profiles(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn save_emulator_profile(
+    profile: EmulatorProfileInput,
+    state: State<'_, AppState>,
+) -> AppResult<EmulatorProfileResponse> {
+    crate::application::services::rom_service::save_emulator_profile(state.inner(), profile)
+}
+
+#[tauri::command]
+pub(crate) fn delete_emulator_profile(profile_id: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::rom_service::delete_emulator_profile(state.inner(), profile_id)
+}
+
+#[tauri::command]
+pub(crate) fn list_rom_directories(state: State<'_, AppState>) -> AppResult<Vec<RomDirectoryResponse>> {
+    crate::application::services::rom_service::list_rom_directories(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn add_rom_directory(
+    path: String,
+    profile_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<RomDirectoryResponse> {
+    crate::application::services::rom_service::add_rom_directory(state.inner(), path, profile_id)
+}
+
+#[tauri::command]
+pub(crate) fn remove_rom_directory(directory_id: String, state: State<'_, AppState>) -> AppResult<()> {
+    crate::application::services::rom_service::remove_rom_directory(state.inner(), directory_id)
+}
+
+#[tauri::command]
+pub(crate) fn scan_rom_library(state: State<'_, AppState>) -> AppResult<RomScanResponse> {
+    crate::application::services::rom_service::scan_rom_library(state.inner())
+}
@@ -0,0 +1,140 @@
+use crate::*;
+use crate::application::error::AppResult;
+use crate::api_server::ApiServerSettingsResponse;
+use crate::application::services::settings_service::AppSettingsUpdateInput;
+use crate::gaming_pc::GamingPcSettingsResponse;
+use crate::http_proxy::{HttpProxySettingsResponse, HttpProxyTestResponse};
+use tauri::{AppHandle, Manager, State};
+
+#[tauri::command]
+pub(crate) fn get_offline_mode(state: State<'_, AppState>) -> AppResult<bool> {
+    crate::application::services::settings_service::get_offline_mode(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_offline_mode(enabled: bool, state: State<'_, AppState>) -> AppResult<bool> {
+    crate::application::services::settings_service::set_offline_mode(state.inner(), enabled)
+}
+
+#[tauri::command]
+pub(crate) fn get_app_settings(state: State<'_, AppState>) -> AppResult<AppSettingsResponse> {
+    crate::application::services::settings_service::get_app_settings(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_app_settings(
+    settings: AppSettingsUpdateInput,
+    state: State<'_, AppState>,
+) -> AppResult<AppSettingsResponse> {
+    crate::application::services::settings_service::set_app_settings(state.inner(), settings)
+}
+
+#[tauri::command]
+pub(crate) fn get_locale(state: State<'_, AppState>) -> AppResult<i18n::LocaleResponse> {
+    crate::application::services::settings_service::get_locale(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_locale(locale: String, state: State<'_, AppState>) -> AppResult<i18n::LocaleResponse> {
+    crate::application::services::settings_service::set_locale(state.inner(), locale)
+}
+
+#[tauri::command]
+pub(crate) fn get_download_schedule(state: State<'_, AppState>) -> AppResult<download_queue::DownloadSchedule> {
+    crate::application::services::settings_service::get_download_schedule(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_download_schedule(
+    schedule: download_queue::DownloadSchedule,
+    state: State<'_, AppState>,
+) -> AppResult<download_queue::DownloadSchedule> {
+    crate::application::services::settings_service::set_download_schedule(state.inner(), schedule)
+}
+
+#[tauri::command]
+pub(crate) fn get_steamcmd_settings(state: State<'_, AppState>) -> AppResult<steamcmd::SteamCmdSettings> {
+    crate::application::services::settings_service::get_steamcmd_settings(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_steamcmd_settings(
+    settings: steamcmd::SteamCmdSettings,
+    state: State<'_, AppState>,
+) -> AppResult<steamcmd::SteamCmdSettings> {
+    crate::application::services::settings_service::set_steamcmd_settings(state.inner(), settings)
+}
+
+#[tauri::command]
+pub(crate) fn get_api_server_settings(state: State<'_, AppState>) -> AppResult<ApiServerSettingsResponse> {
+    crate::application::services::settings_service::get_api_server_settings(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_api_server_settings(
+    settings: api_server::ApiServerSettings,
+    state: State<'_, AppState>,
+) -> AppResult<ApiServerSettingsResponse> {
+    crate::application::services::settings_service::set_api_server_settings(state.inner(), settings)
+}
+
+#[tauri::command]
+pub(crate) fn regenerate_api_server_token(state: State<'_, AppState>) -> AppResult<ApiServerSettingsResponse> {
+    crate::application::services::settings_service::regenerate_api_server_token(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn get_gaming_pc_settings(state: State<'_, AppState>) -> AppResult<GamingPcSettingsResponse> {
+    crate::application::services::settings_service::get_gaming_pc_settings(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_gaming_pc_settings(
+    settings: Option<gaming_pc::GamingPcSettings>,
+    api_token: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<GamingPcSettingsResponse> {
+    crate::application::services::settings_service::set_gaming_pc_settings(state.inner(), settings, api_token)
+}
+
+#[tauri::command]
+pub(crate) fn get_http_proxy_settings(state: State<'_, AppState>) -> AppResult<HttpProxySettingsResponse> {
+    crate::application::services::settings_service::get_http_proxy_settings(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_http_proxy_settings(
+    settings: Option<http_proxy::HttpProxySettings>,
+    password: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<HttpProxySettingsResponse> {
+    crate::application::services::settings_service::set_http_proxy_settings(state.inner(), settings, password)
+}
+
+#[tauri::command]
+pub(crate) async fn test_proxy_connection(
+    settings: Option<http_proxy::HttpProxySettings>,
+    password: Option<String>,
+    app_handle: AppHandle,
+) -> AppResult<HttpProxyTestResponse> {
+    // A proxy that drops packets keeps the request open until the client timeout.
+    tauri::async_runtime::spawn_blocking(move || {
+        let state = app_handle.state::<AppState>();
+        crate::application::services::settings_service::test_proxy_connection(state.inner(), settings, password)
+    })
+    .await
+    .map_err(|error| format!("Proxy test task failed: {error}"))?
+}
+
+#[tauri::command]
+pub(crate) fn get_steam_api_key_status(state: State<'_, AppState>) -> AppResult<SteamApiKeyStatusResponse> {
+    crate::application::services::settings_service::get_steam_api_key_status(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn set_steam_api_key(
+    api_key: Option<String>,
+    state: State<'_, AppState>,
+) -> AppResult<SteamApiKeyStatusResponse> {
+    crate::application::services::settings_service::set_steam_api_key(state.inner(), api_key)
+}
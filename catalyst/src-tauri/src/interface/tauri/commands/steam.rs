@@ -31,6 +31,70 @@ pub(crate) fn validate_game_beta_access_code(
 }
 
 #[tauri::command]
-pub(crate) fn import_steam_collections(state: State<'_, AppState>) -> AppResult<SteamCollectionsImportResponse> {
-    crate::application::services::steam_service::import_steam_collections(state.inner())
+pub(crate) fn import_steam_collections(
+    mode: Option<SteamCollectionsImportMode>,
+    import_favorites: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<SteamCollectionsImportResponse> {
+    crate::application::services::steam_service::import_steam_collections(state.inner(), mode, import_favorites)
+}
+
+#[tauri::command]
+pub(crate) fn get_steam_points_balance(state: State<'_, AppState>) -> AppResult<SteamPointsBalanceResponse> {
+    crate::application::services::steam_service::get_steam_points_balance(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn list_game_points_shop_items(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<SteamPointsShopItemsResponse> {
+    crate::application::services::steam_service::list_game_points_shop_items(
+        state.inner(),
+        provider,
+        external_id,
+    )
+}
+
+#[tauri::command]
+pub(crate) fn add_to_steam(
+    provider: String,
+    external_id: String,
+    artwork: Option<crate::application::services::steam_service::SteamShortcutArtworkInput>,
+    state: State<'_, AppState>,
+) -> AppResult<crate::steam_shortcuts::SteamShortcutResponse> {
+    crate::application::services::steam_service::add_to_steam(state.inner(), provider, external_id, artwork)
+}
+
+#[tauri::command]
+pub(crate) fn list_steam_config_backups(
+    state: State<'_, AppState>,
+) -> AppResult<Vec<steam_config_writer::SteamConfigBackupResponse>> {
+    crate::application::services::steam_service::list_steam_config_backups(state.inner())
+}
+
+#[tauri::command]
+pub(crate) fn restore_steam_config_backup(
+    backup_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<steam_config_writer::SteamConfigBackupResponse> {
+    crate::application::services::steam_service::restore_steam_config_backup(state.inner(), backup_id)
+}
+
+#[tauri::command]
+pub(crate) fn get_game_card_status(
+    provider: String,
+    external_id: String,
+    state: State<'_, AppState>,
+) -> AppResult<crate::steam_badges::GameCardStatusResponse> {
+    crate::application::services::steam_service::get_game_card_status(state.inner(), provider, external_id)
+}
+
+#[tauri::command]
+pub(crate) fn get_steam_badges(
+    refresh: Option<bool>,
+    state: State<'_, AppState>,
+) -> AppResult<crate::steam_badges::SteamBadgesResponse> {
+    crate::application::services::steam_service::get_steam_badges(state.inner(), refresh)
 }
@@ -0,0 +1,47 @@
+use crate::*;
+use crate::application::services::deep_link_service;
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_deep_link::DeepLinkExt;
+
+const DEEP_LINK_EVENT: &str = "deep-link";
+const DEEP_LINK_ERROR_EVENT: &str = "deep-link-error";
+
+/// Routes `catalyst://` URLs delivered by the OS, including the one the app was launched with,
+/// through the deep link dispatcher.
+pub(crate) fn setup_deep_links(app: &AppHandle) {
+    // Installed builds register the scheme through the bundle; this covers dev runs.
+    #[cfg(any(windows, target_os = "linux"))]
+    {
+        if let Err(error) = app.deep_link().register_all() {
+            eprintln!("Failed to register {DEEP_LINK_SCHEME}:// deep links: {error}");
+        }
+    }
+
+    if let Ok(Some(urls)) = app.deep_link().get_current() {
+        for url in urls {
+            dispatch_deep_link(app, url.as_str());
+        }
+    }
+
+    let app_handle = app.clone();
+    app.deep_link().on_open_url(move |event| {
+        for url in event.urls() {
+            dispatch_deep_link(&app_handle, url.as_str());
+        }
+    });
+}
+
+fn dispatch_deep_link(app: &AppHandle, url: &str) {
+    let state = app.state::<AppState>();
+    match deep_link_service::handle_deep_link(state.inner(), url.to_owned()) {
+        Ok(response) => {
+            // Both actions finish in the window: the detail page, or the play confirmation.
+            crate::interface::tauri::tray::show_main_window(app);
+            let _ = app.emit(DEEP_LINK_EVENT, response);
+        }
+        Err(error) => {
+            eprintln!("Failed to handle deep link {url}: {}", error.message);
+            let _ = app.emit(DEEP_LINK_ERROR_EVENT, error);
+        }
+    }
+}
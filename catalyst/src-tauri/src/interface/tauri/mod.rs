@@ -1 +1,4 @@
 pub mod commands;
+pub mod tray;
+pub mod deep_link;
+pub mod single_instance;
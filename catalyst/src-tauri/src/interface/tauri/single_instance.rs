@@ -0,0 +1,48 @@
+use crate::*;
+use tauri::{AppHandle, Emitter};
+
+const SECOND_INSTANCE_EVENT: &str = "second-instance";
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SecondInstancePayload {
+    args: Vec<String>,
+    cwd: String,
+}
+
+/// Runs in the first instance when Catalyst is started again; the new process hands over its
+/// arguments and exits before opening a database connection or a Steam callback listener.
+/// `catalyst://` URLs reach the deep link handler through the plugin's `deep-link` feature, so
+/// only the remaining arguments are forwarded here.
+/// Drops the executable path and any `catalyst://` URLs from a second launch's arguments.
+fn forwarded_instance_args(args: Vec<String>) -> Vec<String> {
+    let deep_link_prefix = format!("{DEEP_LINK_SCHEME}://");
+    args.into_iter()
+        .skip(1)
+        .filter(|arg| !arg.starts_with(&deep_link_prefix))
+        .collect()
+}
+
+pub(crate) fn handle_second_instance(app: &AppHandle, args: Vec<String>, cwd: String) {
+    crate::interface::tauri::tray::show_main_window(app);
+
+    let args = forwarded_instance_args(args);
+    if !args.is_empty() {
+        let _ = app.emit(SECOND_INSTANCE_EVENT, SecondInstancePayload { args, cwd });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwarded_instance_args_skip_the_executable_and_deep_links() {
+        let args = ["/usr/bin/catalyst", "--minimized", "catalyst://play/steam/620", "extra"]
+            .map(String::from)
+            .to_vec();
+        assert_eq!(forwarded_instance_args(args), vec![String::from("--minimized"), String::from("extra")]);
+        assert!(forwarded_instance_args(vec![String::from("/usr/bin/catalyst")]).is_empty());
+        assert!(forwarded_instance_args(Vec::new()).is_empty());
+    }
+}
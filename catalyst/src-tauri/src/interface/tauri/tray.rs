@@ -0,0 +1,214 @@
+use crate::*;
+use crate::application::services::{game_actions_service, library_service};
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use std::sync::mpsc;
+use tauri::{AppHandle, Emitter, Listener, Runtime, WindowEvent};
+
+const TRAY_ID: &str = "catalyst-tray";
+const TRAY_MENU_SHOW_ID: &str = "tray-show";
+const TRAY_MENU_SYNC_ID: &str = "tray-sync";
+const TRAY_MENU_QUIT_ID: &str = "tray-quit";
+const TRAY_MENU_PLAY_PREFIX: &str = "tray-play:";
+const TRAY_RECENT_GAMES_LIMIT: usize = 8;
+
+/// Creates the tray icon and starts the background refresher that keeps the recent games
+/// submenu and download tooltip current. It rebuilds when Steam's manifests change, a game
+/// starts or stops, or a sync completes.
+pub(crate) fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_tray_menu(app, &[])?;
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&menu)
+        .tooltip("Catalyst")
+        .on_menu_event(|app, event| handle_tray_menu_event(app, event.id.as_ref()))
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    let tray = builder.build(app)?;
+
+    let (refresh_sender, refresh_receiver) = mpsc::channel();
+    for event in [
+        steam_manifest_watcher::STEAM_LIBRARY_CHANGED_EVENT,
+        process_watcher::GAME_STARTED_EVENT,
+        process_watcher::GAME_STOPPED_EVENT,
+    ] {
+        let refresh_sender = refresh_sender.clone();
+        app.listen_any(event, move |_| {
+            let _ = refresh_sender.send(());
+        });
+    }
+    app.listen_any(STEAM_SYNC_PROGRESS_EVENT, move |event| {
+        if is_steam_sync_complete_payload(event.payload()) {
+            let _ = refresh_sender.send(());
+        }
+    });
+
+    let app_handle = app.clone();
+    let _ = thread::Builder::new()
+        .name("tray-refresh".into())
+        .spawn(move || {
+            let mut recent_game_ids = None;
+            refresh_tray(&app_handle, &tray, &mut recent_game_ids);
+            while refresh_receiver.recv().is_ok() {
+                // A download rewrites its manifest often; one rebuild covers the whole burst.
+                while refresh_receiver.try_recv().is_ok() {}
+                refresh_tray(&app_handle, &tray, &mut recent_game_ids);
+            }
+        });
+
+    Ok(())
+}
+
+/// Hides the main window instead of closing it while minimize-to-tray is enabled.
+pub(crate) fn handle_window_event<R: Runtime>(window: &tauri::Window<R>, event: &WindowEvent) {
+    if let WindowEvent::CloseRequested { api, .. } = event {
+        if window.state::<AppState>().minimize_to_tray() {
+            api.prevent_close();
+            let _ = window.hide();
+        }
+    }
+}
+
+fn build_tray_menu(app: &AppHandle, recent_games: &[GameResponse]) -> tauri::Result<Menu<tauri::Wry>> {
+    let show_item = MenuItem::with_id(app, TRAY_MENU_SHOW_ID, "Open Catalyst", true, None::<&str>)?;
+    let sync_item = MenuItem::with_id(app, TRAY_MENU_SYNC_ID, "Sync now", true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, TRAY_MENU_QUIT_ID, "Quit", true, None::<&str>)?;
+
+    let recent_submenu = Submenu::with_id(app, "tray-recent", "Recent games", !recent_games.is_empty())?;
+    for game in recent_games {
+        let item = MenuItem::with_id(
+            app,
+            format!("{TRAY_MENU_PLAY_PREFIX}{}:{}", game.provider, game.external_id),
+            &game.name,
+            true,
+            None::<&str>,
+        )?;
+        recent_submenu.append(&item)?;
+    }
+
+    Menu::with_items(
+        app,
+        &[
+            &show_item,
+            &sync_item,
+            &recent_submenu,
+            &PredefinedMenuItem::separator(app)?,
+            &quit_item,
+        ],
+    )
+}
+
+fn handle_tray_menu_event(app: &AppHandle, menu_id: &str) {
+    match menu_id {
+        TRAY_MENU_SHOW_ID => show_main_window(app),
+        TRAY_MENU_SYNC_ID => spawn_tray_sync(app.clone()),
+        TRAY_MENU_QUIT_ID => app.exit(0),
+        _ => {
+            let Some((provider, external_id)) = menu_id
+                .strip_prefix(TRAY_MENU_PLAY_PREFIX)
+                .and_then(|identity| identity.split_once(':'))
+            else {
+                return;
+            };
+            let state = app.state::<AppState>();
+            if let Err(error) = game_actions_service::play_game(
+                state.inner(),
+                provider.to_owned(),
+                external_id.to_owned(),
+                None,
+            ) {
+                eprintln!("Tray launch failed for {provider}:{external_id}: {}", error.message);
+            }
+        }
+    }
+}
+
+fn spawn_tray_sync(app: AppHandle) {
+    let _ = thread::Builder::new()
+        .name("tray-steam-sync".into())
+        .spawn(move || {
+            let state = app.state::<AppState>();
+            if let Err(error) =
+                library_service::sync_steam_library(state.inner(), None, |progress| {
+                    let _ = app.emit(STEAM_SYNC_PROGRESS_EVENT, progress);
+                })
+            {
+                eprintln!("Tray Steam sync failed: {}", error.message);
+            }
+        });
+}
+
+pub(crate) fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn refresh_tray(app: &AppHandle, tray: &TrayIcon, recent_game_ids: &mut Option<Vec<String>>) {
+    let state = app.state::<AppState>();
+    let downloads = library_service::list_steam_downloads(state.inner()).unwrap_or_default();
+    let _ = tray.set_tooltip(Some(tray_tooltip(&downloads)));
+
+    // Recent games only change after a launch or sync, so skip rebuilding an identical menu.
+    let recent_games = library_service::list_recently_played_games(state.inner(), TRAY_RECENT_GAMES_LIMIT)
+        .unwrap_or_default();
+    let game_ids = recent_games
+        .iter()
+        .map(|game| game.id.clone())
+        .collect::<Vec<_>>();
+    if recent_game_ids.as_ref() == Some(&game_ids) {
+        return;
+    }
+
+    match build_tray_menu(app, &recent_games) {
+        Ok(menu) => {
+            if tray.set_menu(Some(menu)).is_ok() {
+                *recent_game_ids = Some(game_ids);
+            }
+        }
+        Err(error) => eprintln!("Failed to rebuild tray menu: {error}"),
+    }
+}
+
+fn is_steam_sync_complete_payload(payload: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(payload)
+        .ok()
+        .and_then(|progress| progress.get("phase")?.as_str().map(|phase| phase == "complete"))
+        .unwrap_or(false)
+}
+
+fn tray_tooltip(downloads: &[SteamDownloadProgressResponse]) -> String {
+    match downloads {
+        [] => String::from("Catalyst"),
+        [download] => match download.progress_percent {
+            Some(percent) => format!("Catalyst — Downloading {} ({percent:.0}%)", download.name),
+            None => format!("Catalyst — Downloading {}", download.name),
+        },
+        _ => format!("Catalyst — {} downloads in progress", downloads.len()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_the_final_sync_progress_event_refreshes_the_tray() {
+        assert!(is_steam_sync_complete_payload(r#"{"phase":"complete","processed":3,"total":3}"#));
+        assert!(!is_steam_sync_complete_payload(r#"{"phase":"saving","processed":0,"total":3}"#));
+        assert!(!is_steam_sync_complete_payload("null"));
+        assert!(!is_steam_sync_complete_payload("not json"));
+    }
+}
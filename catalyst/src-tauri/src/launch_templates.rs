@@ -0,0 +1,202 @@
+use crate::*;
+
+const LAUNCH_COMMAND_PLACEHOLDER: &str = "%command%";
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LaunchTemplateResponse {
+    pub id: String,
+    pub name: String,
+    pub launch_options: String,
+}
+
+pub(crate) fn list_launch_templates(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<Vec<LaunchTemplateResponse>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT id, name, launch_options
+            FROM launch_templates
+            WHERE user_id = ?1
+            ORDER BY name COLLATE NOCASE
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare launch template query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok(LaunchTemplateResponse {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                launch_options: row.get(2)?,
+            })
+        })
+        .map_err(|error| format!("Failed to query launch templates: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode launch template row: {error}"))
+}
+
+pub(crate) fn find_launch_template(
+    connection: &Connection,
+    user_id: &str,
+    template_id: &str,
+) -> Result<LaunchTemplateResponse, String> {
+    connection
+        .query_row(
+            "SELECT id, name, launch_options FROM launch_templates WHERE id = ?1 AND user_id = ?2",
+            params![template_id, user_id],
+            |row| {
+                Ok(LaunchTemplateResponse {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    launch_options: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query launch template: {error}"))?
+        .ok_or_else(|| String::from("Launch template not found"))
+}
+
+pub(crate) fn save_launch_template(
+    connection: &Connection,
+    user_id: &str,
+    template_id: Option<&str>,
+    name: &str,
+    launch_options: &str,
+) -> Result<LaunchTemplateResponse, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(String::from("Launch template name is required"));
+    }
+    let launch_options = launch_options.trim();
+    if launch_options.is_empty() {
+        return Err(String::from("Launch template options are required"));
+    }
+    let template_id = template_id.map(str::trim).filter(|value| !value.is_empty());
+
+    let duplicate_id = connection
+        .query_row(
+            "SELECT id FROM launch_templates WHERE user_id = ?1 AND name = ?2 COLLATE NOCASE",
+            params![user_id, name],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query launch templates: {error}"))?;
+    if duplicate_id.is_some() && duplicate_id.as_deref() != template_id {
+        return Err(format!("A launch template named '{name}' already exists"));
+    }
+
+    let now = Utc::now().to_rfc3339();
+    let template_id = match template_id {
+        Some(existing_id) => {
+            let updated_rows = connection
+                .execute(
+                    "
+                    UPDATE launch_templates
+                    SET name = ?1, launch_options = ?2, updated_at = ?3
+                    WHERE id = ?4 AND user_id = ?5
+                    ",
+                    params![name, launch_options, now, existing_id, user_id],
+                )
+                .map_err(|error| format!("Failed to update launch template: {error}"))?;
+            if updated_rows == 0 {
+                return Err(String::from("Launch template not found"));
+            }
+            existing_id.to_owned()
+        }
+        None => {
+            let new_id = Uuid::new_v4().to_string();
+            connection
+                .execute(
+                    "
+                    INSERT INTO launch_templates (id, user_id, name, launch_options, created_at, updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?5)
+                    ",
+                    params![new_id, user_id, name, launch_options, now],
+                )
+                .map_err(|error| format!("Failed to create launch template: {error}"))?;
+            new_id
+        }
+    };
+
+    Ok(LaunchTemplateResponse {
+        id: template_id,
+        name: name.to_owned(),
+        launch_options: launch_options.to_owned(),
+    })
+}
+
+pub(crate) fn delete_launch_template(
+    connection: &Connection,
+    user_id: &str,
+    template_id: &str,
+) -> Result<(), String> {
+    let deleted_rows = connection
+        .execute(
+            "DELETE FROM launch_templates WHERE id = ?1 AND user_id = ?2",
+            params![template_id, user_id],
+        )
+        .map_err(|error| format!("Failed to delete launch template: {error}"))?;
+    if deleted_rows == 0 {
+        return Err(String::from("Launch template not found"));
+    }
+    Ok(())
+}
+
+fn split_launch_options(launch_options: &str) -> (&str, &str) {
+    match launch_options.split_once(LAUNCH_COMMAND_PLACEHOLDER) {
+        Some((prefix, suffix)) => (prefix.trim(), suffix.trim()),
+        None => ("", launch_options.trim()),
+    }
+}
+
+fn join_launch_option_parts(existing: &str, addition: &str) -> String {
+    if addition.is_empty() || existing.contains(addition) {
+        return existing.to_owned();
+    }
+    if existing.is_empty() {
+        return addition.to_owned();
+    }
+    format!("{existing} {addition}")
+}
+
+/// Merges a template into a game's existing launch options. Both sides may use Steam's
+/// `%command%` placeholder: wrapper and environment prefixes are merged before it and
+/// arguments after it, and parts that are already present are not repeated.
+pub(crate) fn compose_launch_options(existing: &str, template: &str) -> String {
+    let (existing_prefix, existing_suffix) = split_launch_options(existing);
+    let (template_prefix, template_suffix) = split_launch_options(template);
+    let prefix = join_launch_option_parts(existing_prefix, template_prefix);
+    let suffix = join_launch_option_parts(existing_suffix, template_suffix);
+
+    if prefix.is_empty() {
+        return suffix;
+    }
+    format!("{prefix} {LAUNCH_COMMAND_PLACEHOLDER} {suffix}")
+        .trim_end()
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compose_launch_options_merges_around_command_placeholder() {
+        assert_eq!(
+            compose_launch_options("-novid", "-windowed -w 2560 -h 1440"),
+            "-novid -windowed -w 2560 -h 1440"
+        );
+        assert_eq!(
+            compose_launch_options("-novid", "PROTON_USE_WINED3D=1 %command% -dx11"),
+            "PROTON_USE_WINED3D=1 %command% -novid -dx11"
+        );
+        assert_eq!(
+            compose_launch_options("PROTON_USE_WINED3D=1 %command% -dx11", "-dx11"),
+            "PROTON_USE_WINED3D=1 %command% -dx11"
+        );
+    }
+}
@@ -1,11 +1,14 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fs,
     io::{Read, Write},
     net::{TcpListener, TcpStream, ToSocketAddrs},
     path::{Path, PathBuf},
     process::Command,
-    sync::Mutex,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
     thread,
     time::{Duration, Instant, SystemTime},
 };
@@ -23,27 +26,133 @@ use uuid::Uuid;
 #[cfg(unix)]
 use std::os::unix::fs::PermissionsExt;
 
+static STEAM_REQUEST_SCHEDULE: once_cell::sync::Lazy<Mutex<HashMap<String, Instant>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
 mod application;
 mod interface;
+mod achievements;
+mod api_server;
+mod backlog_picker;
 mod cache;
+mod controller_layouts;
+mod custom_artwork;
+mod demo;
+mod developer_releases;
+mod discord;
+mod discovery_feed;
+mod disk_space;
+mod download_history;
+mod download_queue;
+mod field_encryption;
+mod game_shortcuts;
+mod game_user_tags;
+mod gaming_pc;
+mod game_verification;
+mod http_proxy;
+mod i18n;
+mod install_jobs;
+mod launch_templates;
+mod library_import;
+mod library_lookup_cache;
+mod library_search;
+mod library_merge;
+mod library_stats;
+mod notifications;
+mod play_sessions;
+mod private_games;
+mod process_watcher;
+mod proton_prefix;
+mod remote_play;
+mod rom_library;
+mod save_backups;
+mod screenshots;
+mod secret_store;
+mod steam_appinfo;
+mod steam_badges;
+mod steam_cloud_collections;
+mod steam_config_writer;
+mod steam_library_conflicts;
+mod steam_manifest_watcher;
+mod steam_shortcuts;
+mod steam_update_size;
+mod steamcmd;
+mod store_providers;
+#[cfg(test)]
+mod test_support;
+mod two_factor;
+mod workshop;
 
 const STEAM_OPENID_ENDPOINT: &str = "https://steamcommunity.com/openid/login";
 const STEAM_WEB_API_ENDPOINT: &str =
     "https://api.steampowered.com/IPlayerService/GetOwnedGames/v1/";
+const STEAM_PLAYER_SUMMARIES_ENDPOINT: &str =
+    "https://api.steampowered.com/ISteamUser/GetPlayerSummaries/v2/";
 const STEAM_APP_DETAILS_ENDPOINT: &str = "https://store.steampowered.com/api/appdetails";
 const STEAM_STORE_APP_ENDPOINT: &str = "https://store.steampowered.com/app";
+const STEAM_STORE_APP_REVIEWS_ENDPOINT: &str = "https://store.steampowered.com/appreviews";
 const STEAM_CALLBACK_PUBLIC_HOST: &str = "catalyst";
 const STEAM_APP_BETAS_ENDPOINT: &str = "https://api.steampowered.com/ISteamApps/GetAppBetas/v1/";
 const STEAM_APP_BETA_CODE_CHECK_ENDPOINT: &str =
     "https://api.steampowered.com/ISteamApps/CheckAppBetaPassword/v1/";
+const STEAM_LOYALTY_SUMMARY_ENDPOINT: &str =
+    "https://api.steampowered.com/ILoyaltyRewardsService/GetSummary/v1/";
+const STEAM_LOYALTY_REWARD_ITEMS_ENDPOINT: &str =
+    "https://api.steampowered.com/ILoyaltyRewardsService/QueryRewardItems/v1/";
+const STEAM_COMMUNITY_ITEM_IMAGE_BASE_URL: &str =
+    "https://cdn.akamai.steamstatic.com/steamcommunity/public/images/items";
 const STEAM_CALLBACK_TIMEOUT: Duration = Duration::from_secs(180);
+const STEAM_CALLBACK_READ_TIMEOUT: Duration = Duration::from_secs(5);
+const STEAM_CALLBACK_MAX_REQUEST_BYTES: usize = 16 * 1024;
+const STEAM_HTTP_MAX_RETRIES: u32 = 4;
+const STEAM_HTTP_BACKOFF_BASE_MILLIS: u64 = 500;
+const STEAM_HTTP_BACKOFF_MAX_MILLIS: u64 = 30_000;
+const STEAM_STORE_MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(1500);
+const STEAM_COMMUNITY_MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(1000);
+const STEAM_WEB_API_MIN_REQUEST_INTERVAL: Duration = Duration::from_millis(200);
 const STEAM_APP_DETAILS_BATCH_SIZE: usize = 75;
 const STEAM_APP_DETAILS_CACHE_TTL_HOURS: i64 = 24 * 7; // 1 week
 const STEAM_APP_METADATA_CACHE_TTL_HOURS: i64 = 24 * 7;
 const STEAM_APP_LANGUAGES_CACHE_TTL_HOURS: i64 = 24 * 7;
 const STEAM_APP_BETAS_CACHE_TTL_HOURS: i64 = 24 * 7;
 const STEAM_APP_STORE_TAGS_CACHE_TTL_HOURS: i64 = 24 * 7;
+const STEAM_APP_SIZE_ESTIMATE_CACHE_TTL_HOURS: i64 = 24 * 7;
+const STEAM_APP_REVIEW_SUMMARY_CACHE_TTL_HOURS: i64 = 24 * 7;
+const STEAM_POINTS_SHOP_ITEMS_CACHE_TTL_HOURS: i64 = 24;
+const STEAM_POINTS_BALANCE_CACHE_TTL_SECS: i64 = 300;
+const STEAM_POINTS_SHOP_MAX_PAGES: usize = 10;
 const SESSION_TTL_DAYS: i64 = 30;
+const HOME_SHELF_GAME_LIMIT: usize = 12;
+const GAME_DISPLAY_NAME_MAX_LENGTH: usize = 200;
+const STEAM_SYNC_PROGRESS_EVENT: &str = "steam-sync-progress";
+const GAME_MOVE_PROGRESS_EVENT: &str = "game-move-progress";
+const STEAM_SYNC_CANCELLED_MESSAGE: &str = "Steam sync was cancelled";
+const OFFLINE_MODE_MESSAGE: &str = "Catalyst is in offline mode; live Steam data is unavailable";
+const STEAM_PROFILE_PRIVATE_MESSAGE: &str = "Your Steam game details are private, so Steam returned no games. Set \"Game details\" to Public in your Steam profile privacy settings, then sync again. Your existing library was kept.";
+const APP_SETTING_STEAM_API_KEY: &str = "steam_api_key";
+const APP_SETTING_STEAM_ROOT_OVERRIDE: &str = "steam_root_override";
+const APP_SETTING_STEAM_SYNC_INTERVAL_MINUTES: &str = "steam_sync_interval_minutes";
+const APP_SETTING_STEAM_LOCAL_INSTALL_DETECTION: &str = "steam_local_install_detection";
+const APP_SETTING_STEAM_SETTINGS_DEBUG_LOGGING: &str = "steam_settings_debug_logging";
+const APP_SETTING_MINIMIZE_TO_TRAY: &str = "minimize_to_tray";
+const APP_SETTING_DISCORD_RICH_PRESENCE: &str = "discord_rich_presence";
+const APP_SETTING_DISCORD_CLIENT_ID: &str = "discord_client_id";
+const APP_SETTING_NATIVE_NOTIFICATIONS: &str = "native_notifications";
+const APP_SETTING_SHOW_NON_GAME_KINDS: &str = "show_non_game_kinds";
+const APP_SETTING_DOWNLOAD_SCHEDULE: &str = "download_schedule";
+const APP_SETTING_STEAMCMD: &str = "steamcmd";
+const APP_SETTING_API_SERVER: &str = "api_server";
+const APP_SETTING_GAMING_PC: &str = "gaming_pc";
+const APP_SETTING_HTTP_PROXY: &str = "http_proxy";
+const APP_SETTING_LOCALE: &str = "locale";
+const DEEP_LINK_SCHEME: &str = "catalyst";
+const GAME_SESSION_START_TIMEOUT: Duration = Duration::from_secs(180);
+const GAME_SESSION_POLL_INTERVAL: Duration = Duration::from_secs(5);
+const STEAM_SYNC_INTERVAL_MIN_MINUTES: u64 = 5;
+const STEAM_SYNC_INTERVAL_MAX_MINUTES: u64 = 24 * 60;
+const STEAM_SYNC_SCHEDULER_TICK: Duration = Duration::from_secs(60);
+/// Expired sessions are already rejected on lookup; purging them is only housekeeping.
+const SESSION_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
 const STEAM_ID64_ACCOUNT_ID_BASE: u64 = 76_561_197_960_265_728;
 const STEAM_CALLBACK_FALLBACK_HOST: &str = "127.0.0.1";
 const STEAM_BUILTIN_COMPATIBILITY_TOOLS: [(&str, &str); 7] = [
@@ -69,15 +178,51 @@ const STEAM_APP_STATE_COMMITTING: u64 = 0x400_000;
 const STEAM_DIRECTORY_PROGRESS_MANIFEST_STALE_SECONDS: u64 = 20;
 const STEAM_DIRECTORY_PROGRESS_MIN_DELTA_BYTES: u64 = 256 * 1024 * 1024;
 const STEAM_DIRECTORY_PROGRESS_BLEND_FACTOR: f64 = 0.5;
+const STEAM_AUTOMATIC_UPDATES_MODES: [&str; 4] = [
+    "use-global-setting",
+    "wait-until-launch",
+    "let-steam-decide",
+    "immediately-download",
+];
+const STEAM_BACKGROUND_DOWNLOADS_MODES: [&str; 3] = ["pause-while-playing-global", "always-allow", "never-allow"];
+const SOUNDTRACK_AUDIO_EXTENSIONS: [&str; 7] = ["mp3", "flac", "ogg", "wav", "m4a", "aac", "opus"];
 
 struct AppState {
     db_path: PathBuf,
     session_token_path: PathBuf,
-    steam_api_key: Option<String>,
-    steam_local_install_detection: bool,
-    steam_settings_debug_logging: bool,
-    steam_root_override: Option<String>,
+    steam_api_key: Mutex<Option<String>>,
+    steam_local_install_detection: AtomicBool,
+    steam_settings_debug_logging: AtomicBool,
+    steam_root_override: Mutex<Option<String>>,
+    steam_sync_interval_minutes: AtomicU64,
+    minimize_to_tray: AtomicBool,
+    discord_rich_presence: AtomicBool,
+    discord_client_id: Mutex<Option<String>>,
+    native_notifications: AtomicBool,
+    /// Keeps soundtracks, tools, applications and videos in the library; off by default.
+    show_non_game_kinds: AtomicBool,
     current_session_token: Mutex<Option<String>>,
+    steam_sync_cancel_requested: AtomicBool,
+    /// Set while a Steam library sync runs, whether it was started manually, from the tray or
+    /// by the scheduler.
+    steam_sync_running: AtomicBool,
+    offline_mode: AtomicBool,
+    running_games: process_watcher::RunningGames,
+    pending_two_factor_login: Mutex<Option<two_factor::PendingTwoFactorLogin>>,
+    /// Cancel flag of the Steam sign-in in progress; `Some` while a flow owns the callback
+    /// listener, which also keeps a second flow from starting.
+    steam_auth_cancel_requested: Mutex<Option<Arc<AtomicBool>>>,
+    /// Set while private games are unlocked with the PIN; cleared again once it expires.
+    private_games_unlock: Mutex<Option<private_games::PrivateGamesUnlock>>,
+    /// Set while steamcmd is installing; steamcmd can't run two instances against one login.
+    steamcmd_install_running: AtomicBool,
+    /// Settings and token of the localhost API server, read by its listener on every tick.
+    api_server: Mutex<api_server::ApiServerConfig>,
+    /// Installed and downloading Steam apps, kept current by the manifest watcher.
+    steam_manifest_index: steam_manifest_watcher::SteamManifestIndex,
+    library_lookup_cache: library_lookup_cache::LibraryLookupCache,
+    /// The configured proxy and its password; `None` connects directly.
+    http_proxy: Mutex<Option<http_proxy::HttpProxyConfig>>,
 }
 
 impl AppState {
@@ -92,12 +237,102 @@ impl AppState {
         Self {
             db_path,
             session_token_path,
-            steam_api_key,
-            steam_local_install_detection,
-            steam_settings_debug_logging,
-            steam_root_override,
+            steam_api_key: Mutex::new(steam_api_key),
+            steam_local_install_detection: AtomicBool::new(steam_local_install_detection),
+            steam_settings_debug_logging: AtomicBool::new(steam_settings_debug_logging),
+            steam_root_override: Mutex::new(steam_root_override),
+            steam_sync_interval_minutes: AtomicU64::new(0),
+            minimize_to_tray: AtomicBool::new(true),
+            discord_rich_presence: AtomicBool::new(false),
+            discord_client_id: Mutex::new(None),
+            native_notifications: AtomicBool::new(true),
+            show_non_game_kinds: AtomicBool::new(false),
             current_session_token: Mutex::new(None),
+            steam_sync_cancel_requested: AtomicBool::new(false),
+            steam_sync_running: AtomicBool::new(false),
+            offline_mode: AtomicBool::new(false),
+            running_games: process_watcher::RunningGames::default(),
+            pending_two_factor_login: Mutex::new(None),
+            steam_auth_cancel_requested: Mutex::new(None),
+            private_games_unlock: Mutex::new(None),
+            steamcmd_install_running: AtomicBool::new(false),
+            api_server: Mutex::new(api_server::ApiServerConfig::default()),
+            steam_manifest_index: steam_manifest_watcher::SteamManifestIndex::default(),
+            library_lookup_cache: library_lookup_cache::LibraryLookupCache::default(),
+            http_proxy: Mutex::new(None),
+        }
+    }
+
+    fn steam_api_key(&self) -> Option<String> {
+        self.steam_api_key
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+    }
+
+    fn steam_root_override(&self) -> Option<String> {
+        self.steam_root_override
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+    }
+
+    fn steam_local_install_detection(&self) -> bool {
+        self.steam_local_install_detection.load(Ordering::SeqCst)
+    }
+
+    fn steam_settings_debug_logging(&self) -> bool {
+        self.steam_settings_debug_logging.load(Ordering::SeqCst)
+    }
+
+    fn steam_sync_interval_minutes(&self) -> u64 {
+        self.steam_sync_interval_minutes.load(Ordering::SeqCst)
+    }
+
+    fn minimize_to_tray(&self) -> bool {
+        self.minimize_to_tray.load(Ordering::SeqCst)
+    }
+
+    fn native_notifications(&self) -> bool {
+        self.native_notifications.load(Ordering::SeqCst)
+    }
+
+    fn show_non_game_kinds(&self) -> bool {
+        self.show_non_game_kinds.load(Ordering::SeqCst)
+    }
+
+    /// Returns the Discord application ID to publish presence with, or `None` when Rich
+    /// Presence is disabled or not configured.
+    fn discord_presence_client_id(&self) -> Option<String> {
+        if !self.discord_rich_presence.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        self.discord_client_id
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+    }
+
+    fn http_proxy(&self) -> Option<http_proxy::HttpProxyConfig> {
+        self.http_proxy
+            .lock()
+            .ok()
+            .and_then(|value| value.clone())
+    }
+
+    fn is_offline(&self) -> bool {
+        self.offline_mode.load(Ordering::SeqCst)
+    }
+
+    /// Builds an HTTP client for Steam requests, refusing while offline mode is enabled so
+    /// callers fall back to cached data instead of waiting on the network.
+    fn http_client(&self) -> Result<Client, String> {
+        if self.is_offline() {
+            return Err(String::from(OFFLINE_MODE_MESSAGE));
         }
+
+        build_http_client(self.http_proxy().as_ref())
     }
 }
 
@@ -106,6 +341,9 @@ struct UserRow {
     id: String,
     email: String,
     steam_id: Option<String>,
+    steam_persona_name: Option<String>,
+    steam_avatar_url: Option<String>,
+    steam_profile_url: Option<String>,
 }
 #[derive(Debug)]
 struct LibraryGameInput {
@@ -113,6 +351,7 @@ struct LibraryGameInput {
     name: String,
     kind: String,
     playtime_minutes: i64,
+    playtime_2weeks_minutes: i64,
     installed: bool,
     artwork_url: Option<String>,
     last_synced_at: String,
@@ -126,6 +365,9 @@ struct PublicUser {
     email: String,
     steam_linked: bool,
     steam_id: Option<String>,
+    steam_persona_name: Option<String>,
+    steam_avatar_url: Option<String>,
+    steam_profile_url: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -133,31 +375,59 @@ struct PublicUser {
 struct SteamAuthResponse {
     user: PublicUser,
     synced_games: usize,
+    two_factor_required: bool,
 }
 
 struct SteamAuthOutcome {
     user: UserRow,
     synced_games: usize,
-    session_token: String,
+    /// `None` when the account has two-factor authentication enabled; the session is issued
+    /// once a code is verified.
+    session_token: Option<String>,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
+struct SessionResponse {
+    id: String,
+    created_at: String,
+    last_seen_at: String,
+    expires_at: String,
+    current: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
 struct GameResponse {
     id: String,
     provider: String,
     external_id: String,
+    /// The user's display title when set, otherwise the synced name.
     name: String,
+    /// The synced name, only set when a display title overrides it.
+    original_name: Option<String>,
     kind: String,
     playtime_minutes: i64,
+    /// Minutes played over the last two weeks, as reported by the provider.
+    playtime_2weeks_minutes: i64,
     installed: bool,
     artwork_url: Option<String>,
+    /// Local path of artwork the user uploaded, preferred over `artwork_url` when set.
+    custom_artwork_path: Option<String>,
     last_synced_at: String,
     last_played_at: Option<String>,
+    added_at: Option<String>,
     favorite: bool,
+    running: bool,
     steam_tags: Vec<String>,
+    /// Personal tags the user added, on games from any provider.
+    user_tags: Vec<String>,
     genres: Vec<String>,
+    /// Steam store categories such as "Co-op", "Full controller support" or "VR Supported".
+    categories: Vec<String>,
     collections: Vec<String>,
+    /// The game's place in collections the user has ordered by hand, keyed by collection name.
+    collection_positions: BTreeMap<String, i64>,
     hide_in_library: bool,
     // Enriched metadata from store (when available)
     developers: Vec<String>,
@@ -170,12 +440,56 @@ struct GameResponse {
     has_achievements: bool,
     has_cloud_saves: bool,
     controller_support: Option<String>,
+    /// `Only` or `Supported`, from the store categories or an OpenVR entry in the app manifest.
+    vr_support: Option<String>,
     achievements_count: Option<i64>,
+    /// Unlocked achievements as of the last achievements refresh.
+    achievements_unlocked: Option<i64>,
+    /// Every achievement is unlocked, recalculated on each achievements refresh.
+    completed: bool,
     cloud_details: Option<String>,
     features: Vec<FeatureResponse>,
+    // Review scores; only populated for Steam games once synced.
+    metacritic_score: Option<i64>,
+    recommendations_total: Option<i64>,
+    review_score_description: Option<String>,
+    review_positive_percent: Option<i64>,
+    review_total: Option<i64>,
+}
+
+/// Store page details cached per app during sync, so the library can show and filter on them
+/// without a request per game.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SteamAppStoreMetadata {
+    app_type: String,
+    genres: Vec<String>,
+    categories: Vec<String>,
+    developers: Vec<String>,
+    publishers: Vec<String>,
+    release_date: Option<String>,
+    metacritic_score: Option<i64>,
+    recommendations_total: Option<i64>,
+    /// `Full` or `Partial`, from the store categories.
+    controller_support: Option<String>,
+    /// `Only` or `Supported`, from the store categories.
+    vr_support: Option<String>,
 }
 
-#[derive(Serialize)]
+/// The "Very Positive (12,345)" summary shown on a Steam store page.
+#[derive(Debug, Clone, PartialEq)]
+struct SteamReviewSummary {
+    score_description: String,
+    total_positive: i64,
+    total_reviews: i64,
+}
+
+impl SteamReviewSummary {
+    fn positive_percent(&self) -> Option<i64> {
+        (self.total_reviews > 0).then(|| self.total_positive * 100 / self.total_reviews)
+    }
+}
+
+#[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct FeatureResponse {
     key: String,
@@ -190,6 +504,46 @@ struct LibraryResponse {
     user_id: String,
     total: usize,
     games: Vec<GameResponse>,
+    /// Cross-provider view of `games`; only built when the caller asks for it.
+    merged: Option<Vec<library_merge::MergedGameResponse>>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LibraryCountsResponse {
+    total: usize,
+    installed: usize,
+    favorites: usize,
+    hidden: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HomeShelfResponse {
+    id: String,
+    title: String,
+    games: Vec<GameResponse>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HomeDataResponse {
+    recently_played: Vec<GameResponse>,
+    continue_installing: Vec<SteamDownloadProgressResponse>,
+    favorites: Vec<GameResponse>,
+    newly_added: Vec<GameResponse>,
+    library_counts: LibraryCountsResponse,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StartupSnapshotResponse {
+    user: Option<PublicUser>,
+    library_counts: Option<LibraryCountsResponse>,
+    shelves: Vec<HomeShelfResponse>,
+    downloads: Vec<SteamDownloadProgressResponse>,
+    /// Unread notification center entries, newest first.
+    notifications: Vec<notifications::NotificationResponse>,
 }
 
 #[derive(Serialize)]
@@ -199,6 +553,10 @@ struct CollectionResponse {
     name: String,
     game_count: usize,
     contains_game: bool,
+    /// Local path of the collection's cover image, shown on its shelf tile.
+    cover_image_path: Option<String>,
+    /// `#rrggbb` color for the collection's tile.
+    accent_color: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -207,16 +565,129 @@ struct SteamSyncResponse {
     user_id: String,
     provider: String,
     synced_games: usize,
+    added_games: usize,
+    updated_games: usize,
+    removed_games: usize,
+    /// Set when the sync could not read the library, e.g. because the profile is private.
+    warning: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SteamApiKeyStatusResponse {
+    configured: bool,
+    /// `keychain`, `settings` (keychain unavailable), `environment` or `None`.
+    source: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AppSettingsResponse {
+    steam_api_key_configured: bool,
+    steam_root_override: Option<String>,
+    sync_interval_minutes: u64,
+    steam_local_install_detection: bool,
+    steam_settings_debug_logging: bool,
+    minimize_to_tray: bool,
+    discord_rich_presence: bool,
+    discord_client_id: Option<String>,
+    native_notifications: bool,
+    show_non_game_kinds: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SteamSyncProgressPayload {
+    phase: &'static str,
+    processed: usize,
+    total: usize,
+    current_batch: Option<usize>,
+    batch_count: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LocalProviderSyncResponse {
+    provider: String,
+    synced_games: usize,
+    added_games: usize,
+    updated_games: usize,
+    uninstalled_games: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ProviderGamesSyncSummary {
+    total: usize,
+    added: usize,
+    updated: usize,
+    removed: usize,
+    /// Steam hid the owned games because the profile's game details are private; existing
+    /// rows were left untouched.
+    profile_private: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GameStorageBreakdownResponse {
+    install_bytes: Option<u64>,
+    shader_cache_bytes: Option<u64>,
+    compatdata_bytes: Option<u64>,
+    workshop_bytes: Option<u64>,
+    total_bytes: u64,
+    install_path: Option<String>,
+    shader_cache_path: Option<String>,
+    compatdata_path: Option<String>,
+    workshop_path: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SteamCloudStatusResponse {
+    supported: Option<bool>,
+    enabled: bool,
+    last_sync_at: Option<String>,
+    used_bytes: u64,
+    file_count: usize,
+}
+
+/// The tag Steam files its built-in Favorites collection under, in `sharedconfig.vdf` and as the
+/// collection id in cloud storage.
+const STEAM_FAVORITES_COLLECTION: &str = "favorite";
+
+/// `Merge` only adds memberships; `Replace` also drops Steam games from imported collections
+/// when Steam no longer lists them there; `Preview` reports what `Replace` would change.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+enum SteamCollectionsImportMode {
+    #[default]
+    Merge,
+    Replace,
+    Preview,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct SteamCollectionMembershipChangeResponse {
+    collection_name: String,
+    external_id: String,
+    removed: bool,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct SteamCollectionsImportResponse {
+    mode: SteamCollectionsImportMode,
     apps_tagged: usize,
     collections_created: usize,
     memberships_added: usize,
+    memberships_removed: usize,
+    /// Games in Steam's Favorites linked to Catalyst favorites, when the import maps them.
+    favorites_linked: usize,
     skipped_games: usize,
     tags_discovered: usize,
+    /// Steam dynamic collections left out because their filters can't be evaluated here.
+    dynamic_collections_skipped: usize,
+    changes: Vec<SteamCollectionMembershipChangeResponse>,
 }
 
 #[derive(Serialize, Clone, Copy)]
@@ -241,6 +712,42 @@ struct GameInstallLocationResponse {
     free_space_bytes: Option<u64>,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageLibraryResponse {
+    provider: String,
+    path: String,
+    total_bytes: Option<u64>,
+    free_bytes: Option<u64>,
+    games_bytes: u64,
+    game_count: usize,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StorageOverviewResponse {
+    libraries: Vec<StorageLibraryResponse>,
+    total_games_bytes: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct GameMoveProgressPayload {
+    provider: String,
+    external_id: String,
+    phase: &'static str,
+    copied_bytes: u64,
+    total_bytes: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GameMoveResponse {
+    library_path: String,
+    install_path: String,
+    moved_bytes: u64,
+}
+
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct SteamDownloadProgressResponse {
@@ -253,6 +760,8 @@ struct SteamDownloadProgressResponse {
     bytes_total: Option<u64>,
     progress_percent: Option<f64>,
     progress_source: Option<String>,
+    paused: bool,
+    queue_position: Option<usize>,
 }
 
 #[derive(Clone)]
@@ -284,12 +793,45 @@ struct GameCompatibilityToolResponse {
     label: String,
 }
 
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct LaunchWrapperResponse {
+    id: String,
+    label: String,
+    installed: bool,
+    path: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct GameLaunchWrappersSettingsPayload {
+    gamemode: bool,
+    mangohud: bool,
+    gamescope: bool,
+    gamescope_width: Option<u32>,
+    gamescope_height: Option<u32>,
+    gamescope_refresh_rate: Option<u32>,
+    gamescope_fullscreen: bool,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 struct GameGeneralSettingsPayload {
     language: String,
     launch_options: String,
     steam_overlay_enabled: bool,
+    #[serde(default = "default_discord_presence_enabled")]
+    discord_presence_enabled: bool,
+    #[serde(default)]
+    pre_launch_command: String,
+    #[serde(default)]
+    post_exit_command: String,
+    #[serde(default)]
+    launch_hooks_confirmed: bool,
+    #[serde(default)]
+    launch_wrappers: GameLaunchWrappersSettingsPayload,
+    #[serde(default)]
+    environment_variables: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -363,17 +905,49 @@ struct GameVersionBetaOptionResponse {
 struct GameVersionBetasResponse {
     options: Vec<GameVersionBetaOptionResponse>,
     warning: Option<String>,
+    stale: bool,
 }
 
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
 struct GameBetaAccessCodeValidationResponse {
     valid: bool,
-    message: String,
+    #[serde(flatten)]
+    message: i18n::LocalizedMessage,
     branch_id: Option<String>,
     branch_name: Option<String>,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SteamPointsBalanceResponse {
+    points: u64,
+    points_earned: u64,
+    points_spent: u64,
+    warning: Option<String>,
+    #[serde(default)]
+    stale: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct SteamPointsShopItemResponse {
+    def_id: u64,
+    item_class: String,
+    name: String,
+    point_cost: u64,
+    image_url: Option<String>,
+    animated: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SteamPointsShopItemsResponse {
+    items: Vec<SteamPointsShopItemResponse>,
+    warning: Option<String>,
+    stale: bool,
+}
+
 #[derive(Deserialize)]
 struct SteamOwnedGamesApiResponse {
     response: Option<SteamOwnedGamesPayload>,
@@ -381,6 +955,7 @@ struct SteamOwnedGamesApiResponse {
 
 #[derive(Deserialize)]
 struct SteamOwnedGamesPayload {
+    game_count: Option<u64>,
     games: Option<Vec<SteamOwnedGame>>,
 }
 
@@ -389,6 +964,7 @@ struct SteamOwnedGame {
     appid: u64,
     name: Option<String>,
     playtime_forever: Option<i64>,
+    playtime_2weeks: Option<i64>,
     img_logo_url: Option<String>,
     img_icon_url: Option<String>,
     rtime_last_played: Option<i64>,
@@ -437,16 +1013,51 @@ fn complete_steam_auth_flow(
     steam_local_install_detection: bool,
     steam_root_override: Option<String>,
     current_session_token: Option<String>,
+    relink: bool,
+    cancel_requested: &AtomicBool,
+    http_proxy: Option<http_proxy::HttpProxyConfig>,
 ) -> Result<SteamAuthOutcome, String> {
     let connection = open_connection(db_path)?;
     cleanup_expired_sessions(&connection)?;
-    let client = build_http_client()?;
+    let client = build_http_client(http_proxy.as_ref())?;
 
-    let current_user = match current_session_token {
-        Some(token) => find_user_by_session_token(&connection, &token)?,
+    let current_user = match current_session_token.as_deref() {
+        Some(token) => find_user_by_session_token(&connection, token)?,
         None => None,
     };
 
+    // Re-authenticating the account that is already linked to this session does not need the
+    // OpenID roundtrip as long as Steam still serves its profile and library.
+    if let (Some(user), Some((steam_id, api_key)), Some(session_token)) = (
+        current_user.as_ref(),
+        linked_steam_reauth(current_user.as_ref(), relink, steam_api_key.as_deref()),
+        current_session_token,
+    ) {
+        match verify_linked_steam_account(&client, api_key, steam_id) {
+            Ok(true) => {
+                let synced_games = sync_steam_games_for_user(
+                    &connection,
+                    user,
+                    Some(api_key),
+                    steam_local_install_detection,
+                    steam_root_override.as_deref(),
+                    &client,
+                    true,
+                    &|_| {},
+                    &AtomicBool::new(false),
+                )?
+                .total;
+                return Ok(SteamAuthOutcome {
+                    user: find_user_by_id(&connection, &user.id)?.unwrap_or_else(|| user.clone()),
+                    synced_games,
+                    session_token: Some(session_token),
+                });
+            }
+            Ok(false) => {}
+            Err(error) => eprintln!("Linked Steam account check failed: {error}"),
+        }
+    }
+
     let listener = TcpListener::bind("127.0.0.1:0")
         .map_err(|error| format!("Failed to bind Steam callback listener: {error}"))?;
     let port = listener
@@ -470,6 +1081,7 @@ fn complete_steam_auth_flow(
         &state_token,
         STEAM_CALLBACK_TIMEOUT,
         &callback_public_host,
+        cancel_requested,
     )?;
     let verified = verify_steam_openid_response(&client, &callback_params)?;
     if !verified {
@@ -496,8 +1108,19 @@ fn complete_steam_auth_flow(
         steam_local_install_detection,
         steam_root_override.as_deref(),
         &client,
-    )?;
-    let session_token = create_session(&connection, &user.id)?;
+        true,
+        &|_| {},
+        &AtomicBool::new(false),
+    )?
+    .total;
+    // The sync stores the Steam profile summary, so reload to return it with the session.
+    let user = find_user_by_id(&connection, &user.id)?.unwrap_or(user);
+    // Linking Steam from an existing session does not need a second factor.
+    let session_token = if current_user.is_none() && two_factor::is_two_factor_enabled(&connection, &user.id)? {
+        None
+    } else {
+        Some(create_session(&connection, &user.id)?)
+    };
 
     Ok(SteamAuthOutcome {
         user,
@@ -553,6 +1176,7 @@ fn wait_for_steam_callback(
     expected_state: &str,
     timeout: Duration,
     callback_public_host: &str,
+    cancel_requested: &AtomicBool,
 ) -> Result<HashMap<String, String>, String> {
     listener
         .set_nonblocking(true)
@@ -560,6 +1184,11 @@ fn wait_for_steam_callback(
 
     let deadline = Instant::now() + timeout;
     loop {
+        // Returning drops the listener, so a late browser redirect for this flow's state token
+        // can no longer reach Catalyst.
+        if cancel_requested.load(Ordering::SeqCst) {
+            return Err(String::from("Steam sign-in was cancelled"));
+        }
         if Instant::now() >= deadline {
             return Err(String::from(
                 "Timed out waiting for Steam callback. Complete Steam sign-in in your browser and if Windows Firewall prompts for Catalyst, allow local/private access.",
@@ -568,25 +1197,11 @@ fn wait_for_steam_callback(
 
         match listener.accept() {
             Ok((mut stream, _)) => {
-                let request_target = read_http_request_target(&mut stream)?;
-                let callback_url =
-                    Url::parse(&format!("http://{callback_public_host}{request_target}"))
-                    .map_err(|error| format!("Failed to parse callback URL: {error}"))?;
-                let callback_params = callback_url
-                    .query_pairs()
-                    .map(|(key, value)| (key.to_string(), value.to_string()))
-                    .collect::<HashMap<_, _>>();
-
-                if callback_params.get("state").map(|value| value.as_str()) != Some(expected_state)
+                if let Some(callback_params) =
+                    handle_steam_callback_connection(&mut stream, expected_state, callback_public_host)
                 {
-                    let body = "<html><body><h2>Steam login failed</h2><p>State mismatch. Return to Catalyst and try again.</p></body></html>";
-                    let _ = write_http_response(&mut stream, "400 Bad Request", body);
-                    return Err(String::from("Steam callback state mismatch"));
+                    return Ok(callback_params);
                 }
-
-                let body = "<html><body><h2>Steam login complete</h2><p>You can close this tab and return to Catalyst.</p></body></html>";
-                let _ = write_http_response(&mut stream, "200 OK", body);
-                return Ok(callback_params);
             }
             Err(error) if error.kind() == std::io::ErrorKind::WouldBlock => {
                 thread::sleep(Duration::from_millis(50));
@@ -596,40 +1211,117 @@ fn wait_for_steam_callback(
     }
 }
 
-fn read_http_request_target(stream: &mut TcpStream) -> Result<String, String> {
-    let mut buffer = [0u8; 8192];
-    let bytes_read = stream
-        .read(&mut buffer)
-        .map_err(|error| format!("Failed to read callback request: {error}"))?;
-    if bytes_read == 0 {
+#[derive(Debug, PartialEq, Eq)]
+struct HttpRequestHead {
+    method: String,
+    target: String,
+}
+
+/// Serves one loopback connection. Browsers also request `/favicon.ico`, probe with `HEAD` or
+/// open idle keep-alive connections, so anything that is not a valid callback gets a response
+/// and the listener keeps waiting instead of failing the sign-in. Every response closes the
+/// connection, which keeps the server to one request per connection.
+fn handle_steam_callback_connection(
+    stream: &mut TcpStream,
+    expected_state: &str,
+    callback_public_host: &str,
+) -> Option<HashMap<String, String>> {
+    // Accepted sockets inherit non-blocking mode from the listener on some platforms.
+    let _ = stream.set_nonblocking(false);
+    let _ = stream.set_read_timeout(Some(STEAM_CALLBACK_READ_TIMEOUT));
+    let Ok(request) = read_http_request_head(stream) else {
+        let _ = write_http_response(stream, "400 Bad Request", "", true);
+        return None;
+    };
+
+    let include_body = request.method != "HEAD";
+    if request.method != "GET" && request.method != "HEAD" {
+        let _ = write_http_response(stream, "405 Method Not Allowed", "", false);
+        return None;
+    }
+    let Ok(callback_url) = Url::parse(&format!("http://{callback_public_host}{}", request.target))
+    else {
+        let _ = write_http_response(stream, "400 Bad Request", "", false);
+        return None;
+    };
+    if callback_url.path() != "/auth/steam/callback" {
+        let _ = write_http_response(stream, "404 Not Found", "", false);
+        return None;
+    }
+
+    let callback_params = callback_url
+        .query_pairs()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect::<HashMap<_, _>>();
+    let state_matches = callback_params
+        .get("state")
+        .map(|value| constant_time_eq(value.as_bytes(), expected_state.as_bytes()))
+        .unwrap_or(false);
+    if !state_matches {
+        // A stale tab from an earlier or cancelled sign-in; keep waiting for the current one.
+        let body = "<html><body><h2>Steam login failed</h2><p>This sign-in link has expired. Return to Catalyst and try again.</p></body></html>";
+        let _ = write_http_response(stream, "400 Bad Request", body, include_body);
+        return None;
+    }
+
+    let body = "<html><body><h2>Steam login complete</h2><p>You can close this tab and return to Catalyst.</p></body></html>";
+    let _ = write_http_response(stream, "200 OK", body, include_body);
+    // A HEAD probe must not consume the callback; the browser follows up with a GET.
+    include_body.then_some(callback_params)
+}
+
+/// Reads up to the end of the request headers, accumulating partial reads. Request bodies are
+/// ignored because the callback only carries data in the query string.
+fn read_http_request_head(stream: &mut impl Read) -> Result<HttpRequestHead, String> {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 2048];
+    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+        if request.len() > STEAM_CALLBACK_MAX_REQUEST_BYTES {
+            return Err(String::from("Steam callback request headers are too large"));
+        }
+        let bytes_read = stream
+            .read(&mut buffer)
+            .map_err(|error| format!("Failed to read callback request: {error}"))?;
+        if bytes_read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..bytes_read]);
+    }
+    if request.is_empty() {
         return Err(String::from("Steam callback request was empty"));
     }
 
-    let request = String::from_utf8_lossy(&buffer[..bytes_read]);
+    let request = String::from_utf8_lossy(&request);
     let request_line = request
         .lines()
         .next()
         .ok_or_else(|| String::from("Steam callback request line missing"))?;
-
     let mut parts = request_line.split_whitespace();
     let method = parts.next().unwrap_or_default();
     let target = parts.next().unwrap_or_default();
-
-    if method != "GET" {
-        return Err(format!("Steam callback used unsupported method: {method}"));
-    }
-    if target.is_empty() {
-        return Err(String::from("Steam callback request target missing"));
+    if method.is_empty() || !target.starts_with('/') {
+        return Err(String::from("Steam callback request line is malformed"));
     }
 
-    Ok(target.to_owned())
+    Ok(HttpRequestHead {
+        method: method.to_owned(),
+        target: target.to_owned(),
+    })
 }
 
-fn write_http_response(stream: &mut TcpStream, status: &str, body: &str) -> Result<(), String> {
-    let response = format!(
-        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+fn write_http_response(
+    stream: &mut TcpStream,
+    status: &str,
+    body: &str,
+    include_body: bool,
+) -> Result<(), String> {
+    let mut response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
         body.as_bytes().len()
     );
+    if include_body {
+        response.push_str(body);
+    }
 
     stream
         .write_all(response.as_bytes())
@@ -639,6 +1331,18 @@ fn write_http_response(stream: &mut TcpStream, status: &str, body: &str) -> Resu
         .map_err(|error| format!("Failed to flush callback response: {error}"))
 }
 
+/// Compares without exiting early so response timing does not reveal how much of the state
+/// token matched.
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    left.iter()
+        .zip(right)
+        .fold(0u8, |difference, (left, right)| difference | (left ^ right))
+        == 0
+}
+
 fn build_steam_authorization_url(return_to: &str, realm: &str) -> Result<String, String> {
     let mut url = Url::parse(STEAM_OPENID_ENDPOINT)
         .map_err(|error| format!("Failed to parse Steam OpenID endpoint: {error}"))?;
@@ -693,71 +1397,259 @@ fn verify_steam_openid_response(
     Ok(body.contains("is_valid:true"))
 }
 
-fn sync_steam_games_for_user(
-    connection: &Connection,
-    user: &UserRow,
-    steam_api_key: Option<&str>,
-        steam_local_install_detection: bool,
-    steam_root_override: Option<&str>,
-    client: &Client,
-) -> Result<usize, String> {
-    let steam_id = user
-        .steam_id
-        .as_deref()
-        .ok_or_else(|| String::from("User is not linked to Steam"))?;
-
-    let locally_installed_app_ids = if steam_local_install_detection {
-        match detect_locally_installed_steam_app_ids(steam_root_override) {
-            Ok(app_ids) => Some(app_ids),
-            Err(error) => {
-                eprintln!("Local Steam install detection failed: {error}");
-                None
-            }
-        }
-    } else {
-        Some(HashSet::new())
-    };
-
-    let Some(api_key) = steam_api_key
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-    else {
-        if let Some(app_ids) = locally_installed_app_ids.as_ref() {
-            refresh_provider_installed_flags(connection, &user.id, "steam", app_ids)?;
-        }
-        return Ok(0);
-    };
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SteamPlayerSummary {
+    persona_name: Option<String>,
+    avatar_url: Option<String>,
+    profile_url: Option<String>,
+    /// `communityvisibilitystate` 3 is a public profile; anything else hides game details.
+    profile_public: bool,
+}
 
-    let mut request_url = Url::parse(STEAM_WEB_API_ENDPOINT)
-        .map_err(|error| format!("Failed to parse Steam games endpoint: {error}"))?;
+fn fetch_steam_player_summary(
+    client: &Client,
+    api_key: &str,
+    steam_id: &str,
+) -> Result<Option<SteamPlayerSummary>, String> {
+    let mut request_url = Url::parse(STEAM_PLAYER_SUMMARIES_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam player summaries endpoint: {error}"))?;
     request_url
         .query_pairs_mut()
         .append_pair("key", api_key)
-        .append_pair("steamid", steam_id)
-        .append_pair("include_appinfo", "true")
-        .append_pair("include_played_free_games", "true")
+        .append_pair("steamids", steam_id)
         .append_pair("format", "json");
 
-    let response = client
-        .get(request_url)
-        .send()
-        .map_err(|error| format!("Steam owned games request failed: {error}"))?;
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam player summary request failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
-            "Steam owned games request failed with status {}",
+            "Steam player summary request failed with status {}",
             response.status()
         ));
     }
-
     let payload = response
-        .json::<SteamOwnedGamesApiResponse>()
-        .map_err(|error| format!("Failed to decode Steam owned games response: {error}"))?;
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam player summary: {error}"))?;
 
-    let steam_owned_games = payload
-        .response
-        .and_then(|response| response.games)
-        .unwrap_or_default();
-    let existing_installed_flags = if locally_installed_app_ids.is_none() {
+    Ok(parse_steam_player_summary(&payload, steam_id))
+}
+
+fn parse_steam_player_summary(payload: &serde_json::Value, steam_id: &str) -> Option<SteamPlayerSummary> {
+    let player = payload
+        .pointer("/response/players")
+        .and_then(serde_json::Value::as_array)?
+        .iter()
+        .find(|player| player.get("steamid").and_then(serde_json::Value::as_str) == Some(steam_id))?;
+    let read_text = |key: &str| {
+        player
+            .get(key)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_owned)
+    };
+
+    Some(SteamPlayerSummary {
+        persona_name: read_text("personaname"),
+        avatar_url: read_text("avatarfull")
+            .or_else(|| read_text("avatarmedium"))
+            .or_else(|| read_text("avatar")),
+        profile_url: read_text("profileurl"),
+        profile_public: player
+            .get("communityvisibilitystate")
+            .and_then(serde_json::Value::as_i64)
+            == Some(3),
+    })
+}
+
+/// The linked Steam ID and Web API key a re-authentication can be confirmed with instead of the
+/// OpenID roundtrip. `relink` asks for a different account, so it always takes the roundtrip.
+fn linked_steam_reauth<'a>(
+    current_user: Option<&'a UserRow>,
+    relink: bool,
+    steam_api_key: Option<&'a str>,
+) -> Option<(&'a str, &'a str)> {
+    if relink {
+        return None;
+    }
+    let steam_id = current_user?.steam_id.as_deref()?;
+    let api_key = steam_api_key.map(str::trim).filter(|value| !value.is_empty())?;
+    Some((steam_id, api_key))
+}
+
+/// Checks that a previously linked Steam ID still resolves to a public profile whose library
+/// the Web API can read.
+fn verify_linked_steam_account(client: &Client, api_key: &str, steam_id: &str) -> Result<bool, String> {
+    let profile_public = fetch_steam_player_summary(client, api_key, steam_id)?
+        .map(|summary| summary.profile_public)
+        .unwrap_or(false);
+    if !profile_public {
+        return Ok(false);
+    }
+
+    let mut request_url = Url::parse(STEAM_WEB_API_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam games endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("key", api_key)
+        .append_pair("steamid", steam_id)
+        .append_pair("format", "json");
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam owned games request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Ok(false);
+    }
+    let payload = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam owned games response: {error}"))?;
+
+    Ok(payload.pointer("/response/game_count").is_some())
+}
+
+/// Stores the persona name, avatar and profile link shown in the profile header. Steam only
+/// returns summaries for valid IDs, so a missing player leaves the stored values untouched.
+fn refresh_steam_player_summary(
+    connection: &Connection,
+    client: &Client,
+    api_key: &str,
+    user_id: &str,
+    steam_id: &str,
+) -> Result<Option<SteamPlayerSummary>, String> {
+    let Some(summary) = fetch_steam_player_summary(client, api_key, steam_id)? else {
+        return Ok(None);
+    };
+
+    connection
+        .execute(
+            "
+            UPDATE users
+            SET steam_persona_name = ?1, steam_avatar_url = ?2, steam_profile_url = ?3,
+                steam_profile_updated_at = ?4
+            WHERE id = ?5 AND steam_id_lookup = ?6
+            ",
+            params![
+                summary.persona_name,
+                summary.avatar_url,
+                summary.profile_url,
+                Utc::now().to_rfc3339(),
+                user_id,
+                field_encryption::field_lookup_hash(steam_id)?
+            ],
+        )
+        .map_err(|error| format!("Failed to store Steam profile summary: {error}"))?;
+
+    Ok(Some(summary))
+}
+
+/// Steam answers GetOwnedGames for a profile with private game details with an empty
+/// `response` instead of an error, which is indistinguishable from owning nothing unless the
+/// missing `game_count` or the profile visibility is checked.
+fn steam_owned_games_hidden_by_privacy(payload: Option<&SteamOwnedGamesPayload>, profile_public: Option<bool>) -> bool {
+    let has_games = payload
+        .and_then(|payload| payload.games.as_ref())
+        .is_some_and(|games| !games.is_empty());
+    if has_games {
+        return false;
+    }
+    profile_public == Some(false) || payload.and_then(|payload| payload.game_count).is_none()
+}
+
+fn sync_steam_games_for_user(
+    connection: &Connection,
+    user: &UserRow,
+    steam_api_key: Option<&str>,
+    steam_local_install_detection: bool,
+    steam_root_override: Option<&str>,
+    client: &Client,
+    incremental: bool,
+    on_progress: &dyn Fn(SteamSyncProgressPayload),
+    cancel_requested: &AtomicBool,
+) -> Result<ProviderGamesSyncSummary, String> {
+    let steam_id = user
+        .steam_id
+        .as_deref()
+        .ok_or_else(|| String::from("User is not linked to Steam"))?;
+
+    on_progress(steam_sync_phase_progress("detecting_installs", 0, 0));
+    let locally_installed_app_ids = if steam_local_install_detection {
+        match detect_locally_installed_steam_app_ids(steam_root_override) {
+            Ok(app_ids) => Some(app_ids),
+            Err(error) => {
+                eprintln!("Local Steam install detection failed: {error}");
+                None
+            }
+        }
+    } else {
+        Some(HashSet::new())
+    };
+
+    let Some(api_key) = steam_api_key
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        if let Some(app_ids) = locally_installed_app_ids.as_ref() {
+            refresh_provider_installed_flags(connection, &user.id, "steam", app_ids)?;
+        }
+        return Ok(ProviderGamesSyncSummary::default());
+    };
+
+    ensure_steam_sync_not_cancelled(cancel_requested)?;
+    let profile_public = match refresh_steam_player_summary(connection, client, api_key, &user.id, steam_id) {
+        Ok(summary) => summary.map(|summary| summary.profile_public),
+        Err(error) => {
+            eprintln!("Steam profile summary refresh failed: {error}");
+            None
+        }
+    };
+
+    on_progress(steam_sync_phase_progress("fetching_owned_games", 0, 0));
+    let mut request_url = Url::parse(STEAM_WEB_API_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam games endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("key", api_key)
+        .append_pair("steamid", steam_id)
+        .append_pair("include_appinfo", "true")
+        .append_pair("include_played_free_games", "true")
+        .append_pair("format", "json");
+
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam owned games request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Steam owned games request failed with status {}",
+            response.status()
+        ));
+    }
+
+    let payload = response
+        .json::<SteamOwnedGamesApiResponse>()
+        .map_err(|error| format!("Failed to decode Steam owned games response: {error}"))?;
+
+    if steam_owned_games_hidden_by_privacy(payload.response.as_ref(), profile_public) {
+        if let Some(app_ids) = locally_installed_app_ids.as_ref() {
+            refresh_provider_installed_flags(connection, &user.id, "steam", app_ids)?;
+        }
+        let existing_games = connection
+            .query_row(
+                "SELECT COUNT(*) FROM games WHERE user_id = ?1 AND provider = 'steam'",
+                params![user.id],
+                |row| row.get::<_, i64>(0),
+            )
+            .map_err(|error| format!("Failed to count existing Steam games: {error}"))?;
+        eprintln!("Steam returned no games for user {} because the profile is private.", user.id);
+        return Ok(ProviderGamesSyncSummary {
+            total: existing_games.max(0) as usize,
+            profile_private: true,
+            ..ProviderGamesSyncSummary::default()
+        });
+    }
+
+    let steam_owned_games = payload
+        .response
+        .and_then(|response| response.games)
+        .unwrap_or_default();
+    let existing_installed_flags = if locally_installed_app_ids.is_none() {
         load_provider_installed_flags(connection, &user.id, "steam")?
     } else {
         HashMap::new()
@@ -766,7 +1658,13 @@ fn sync_steam_games_for_user(
         .iter()
         .map(|game| game.appid)
         .collect::<Vec<_>>();
-    let resolved_kinds = resolve_steam_game_kinds(connection, client, &steam_owned_games)?;
+    let resolved_kinds = resolve_steam_game_kinds(
+        connection,
+        client,
+        &steam_owned_games,
+        on_progress,
+        cancel_requested,
+    )?;
     let games = steam_owned_games
         .into_iter()
         .map(|game| {
@@ -784,12 +1682,136 @@ fn sync_steam_games_for_user(
         })
         .collect::<Vec<_>>();
 
-    if let Err(error) = refresh_steam_store_tags_cache(connection, client, &steam_owned_app_ids) {
+    if let Err(error) = refresh_steam_store_tags_cache(
+        connection,
+        client,
+        &steam_owned_app_ids,
+        on_progress,
+        cancel_requested,
+    ) {
         eprintln!("Steam Store tag sync failed: {error}");
     }
 
-    replace_provider_games(connection, &user.id, "steam", &games)?;
-    Ok(games.len())
+    if let Err(error) = refresh_steam_review_summaries_cache(
+        connection,
+        client,
+        &steam_owned_app_ids,
+        on_progress,
+        cancel_requested,
+    ) {
+        eprintln!("Steam review summary sync failed: {error}");
+    }
+
+    let uninstalled_app_ids = games
+        .iter()
+        .filter(|game| !game.installed)
+        .filter_map(|game| game.external_id.parse::<u64>().ok())
+        .collect::<Vec<_>>();
+    if let Err(error) = prefetch_steam_install_size_estimates(
+        connection,
+        client,
+        &uninstalled_app_ids,
+        on_progress,
+        cancel_requested,
+    ) {
+        eprintln!("Steam install size prefetch failed: {error}");
+    }
+
+    ensure_steam_sync_not_cancelled(cancel_requested)?;
+    on_progress(steam_sync_phase_progress("saving", 0, games.len()));
+    let summary = replace_provider_games(connection, &user.id, "steam", &games, incremental)?;
+    on_progress(steam_sync_phase_progress("complete", summary.total, summary.total));
+    Ok(summary)
+}
+
+fn steam_sync_phase_progress(
+    phase: &'static str,
+    processed: usize,
+    total: usize,
+) -> SteamSyncProgressPayload {
+    SteamSyncProgressPayload {
+        phase,
+        processed,
+        total,
+        current_batch: None,
+        batch_count: None,
+    }
+}
+
+fn ensure_steam_sync_not_cancelled(cancel_requested: &AtomicBool) -> Result<(), String> {
+    if cancel_requested.load(Ordering::SeqCst) {
+        return Err(String::from(STEAM_SYNC_CANCELLED_MESSAGE));
+    }
+
+    Ok(())
+}
+
+/// Records games detected through a launcher's local install data. Detected games are upserted
+/// as installed; previously detected games that are gone are kept but marked uninstalled so
+/// favorites and collections survive a reinstall.
+fn sync_local_provider_games(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    detected_games: &[store_providers::LocalProviderGame],
+) -> Result<ProviderGamesSyncSummary, String> {
+    let mut statement = connection
+        .prepare("SELECT external_id, name, installed FROM games WHERE user_id = ?1 AND provider = ?2")
+        .map_err(|error| format!("Failed to prepare local provider game query: {error}"))?;
+    let existing_games = statement
+        .query_map(params![user_id, provider], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                (row.get::<_, String>(1)?, row.get::<_, i64>(2)? != 0),
+            ))
+        })
+        .map_err(|error| format!("Failed to query local provider games: {error}"))?
+        .collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|error| format!("Failed to decode local provider games: {error}"))?;
+
+    let synced_at = Utc::now().to_rfc3339();
+    let mut summary = ProviderGamesSyncSummary {
+        total: detected_games.len(),
+        ..ProviderGamesSyncSummary::default()
+    };
+    let mut detected_ids = HashSet::new();
+    for game in detected_games {
+        detected_ids.insert(game.external_id.as_str());
+        match existing_games.get(&game.external_id) {
+            None => summary.added += 1,
+            Some((name, installed)) if *name == game.name && *installed => continue,
+            Some(_) => summary.updated += 1,
+        }
+
+        connection
+            .execute(
+                "
+                INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, artwork_url, last_synced_at, last_played_at, added_at)
+                VALUES (?1, ?2, ?3, ?4, 'game', 0, 1, NULL, ?5, NULL, ?5)
+                ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+                  name = excluded.name,
+                  installed = 1,
+                  last_synced_at = excluded.last_synced_at
+                ",
+                params![user_id, provider, game.external_id, game.name, synced_at],
+            )
+            .map_err(|error| format!("Failed to save {provider} game: {error}"))?;
+    }
+
+    for (external_id, (_, installed)) in &existing_games {
+        if !*installed || detected_ids.contains(external_id.as_str()) {
+            continue;
+        }
+        connection
+            .execute(
+                "UPDATE games SET installed = 0, last_synced_at = ?1 WHERE user_id = ?2 AND provider = ?3 AND external_id = ?4",
+                params![synced_at, user_id, provider, external_id],
+            )
+            .map_err(|error| format!("Failed to update {provider} install state: {error}"))?;
+        summary.removed += 1;
+    }
+
+    Ok(summary)
 }
 
 fn load_provider_installed_flags(
@@ -929,8 +1951,7 @@ fn resolve_steam_root_paths(steam_root_override: Option<&str>) -> Vec<PathBuf> {
             continue;
         }
 
-        let dedupe_path = fs::canonicalize(&candidate).unwrap_or_else(|_| candidate.clone());
-        if !seen_paths.insert(dedupe_path) {
+        if !seen_paths.insert(steam_path_dedupe_key(&candidate)) {
             continue;
         }
         roots.push(candidate);
@@ -1167,12 +2188,29 @@ fn steam_root_candidates() -> Vec<PathBuf> {
     candidates
 }
 
+/// Identifies a Steam path regardless of symlinks (`~/.steam/root` pointing at
+/// `~/.local/share/Steam`) or how its mount point is cased. Paths that don't exist can't be
+/// resolved, so they fall back to the path itself, lowercased where the filesystem usually
+/// ignores case.
+fn steam_path_dedupe_key(path: &Path) -> PathBuf {
+    if let Ok(canonical_path) = fs::canonicalize(path) {
+        return canonical_path;
+    }
+    if cfg!(any(target_os = "windows", target_os = "macos")) {
+        PathBuf::from(path.to_string_lossy().to_lowercase())
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// Lists the `steamapps` directory of every library the Steam root knows about. Libraries
+/// reached through several paths are returned once, under the first path seen.
 fn resolve_steamapps_directories(steam_root: &Path) -> Result<Vec<PathBuf>, String> {
     let root_steamapps_directory = steam_root.join("steamapps");
     let mut steamapps_directories = Vec::new();
     let mut seen_directories = HashSet::new();
 
-    if seen_directories.insert(root_steamapps_directory.clone()) {
+    if seen_directories.insert(steam_path_dedupe_key(&root_steamapps_directory)) {
         steamapps_directories.push(root_steamapps_directory.clone());
     }
 
@@ -1205,7 +2243,7 @@ fn resolve_steamapps_directories(steam_root: &Path) -> Result<Vec<PathBuf>, Stri
 
     for library_path in library_paths {
         let steamapps_directory = library_path.join("steamapps");
-        if seen_directories.insert(steamapps_directory.clone()) {
+        if seen_directories.insert(steam_path_dedupe_key(&steamapps_directory)) {
             steamapps_directories.push(steamapps_directory);
         }
     }
@@ -1383,6 +2421,7 @@ fn resolve_steam_manifest_path_for_app_id(
         return Err(String::from("Could not locate local Steam installation"));
     }
     let manifest_file_name = format!("appmanifest_{app_id}.acf");
+    let mut manifest_paths = Vec::new();
     for steam_root in steam_roots {
         let steamapps_directories = match resolve_steamapps_directories(&steam_root) {
             Ok(paths) => paths,
@@ -1397,12 +2436,24 @@ fn resolve_steam_manifest_path_for_app_id(
         };
         for steamapps_directory in steamapps_directories {
             let manifest_path = steamapps_directory.join(&manifest_file_name);
-            if manifest_path.is_file() {
-                return Ok(manifest_path);
+            if manifest_path.is_file() && !manifest_paths.contains(&manifest_path) {
+                manifest_paths.push(manifest_path);
             }
         }
     }
 
+    // A manifest left behind in another library would otherwise shadow the real install.
+    if manifest_paths.len() > 1 {
+        if let Some(active_manifest_path) =
+            steam_library_conflicts::find_active_steam_manifest_path(steam_root_override, app_id)
+        {
+            return Ok(active_manifest_path);
+        }
+    }
+    if let Some(manifest_path) = manifest_paths.into_iter().next() {
+        return Ok(manifest_path);
+    }
+
     Err(format!(
         "Could not find Steam app manifest for app {app_id}. Install the game first."
     ))
@@ -1690,6 +2741,7 @@ fn collect_steam_download_progress_from_steamapps_dir(
     steamapps_directory: &Path,
     owned_games_by_app_id: &HashMap<u64, OwnedSteamGameMetadata>,
     seen_external_ids: &mut HashSet<String>,
+    skipped_manifest_paths: &HashSet<PathBuf>,
     output: &mut Vec<SteamDownloadProgressResponse>,
 ) -> Result<(), String> {
     let allow_unknown_games = owned_games_by_app_id.is_empty();
@@ -1712,6 +2764,9 @@ fn collect_steam_download_progress_from_steamapps_dir(
         let Some(app_id) = parse_steam_manifest_app_id(&file_name) else {
             continue;
         };
+        if skipped_manifest_paths.contains(&entry.path()) {
+            continue;
+        }
 
         let manifest_contents = match fs::read_to_string(entry.path()) {
             Ok(contents) => contents,
@@ -1793,6 +2848,8 @@ fn collect_steam_download_progress_from_steamapps_dir(
             bytes_total,
             progress_percent,
             progress_source: Some(progress_source),
+            paused: false,
+            queue_position: None,
         });
     }
 
@@ -1888,6 +2945,8 @@ fn collect_steam_download_progress_from_steamapps_dir(
                 bytes_total,
                 progress_percent,
                 progress_source,
+                paused: false,
+                queue_position: None,
             });
         }
     }
@@ -1914,75 +2973,510 @@ fn directory_size_bytes(path: &Path) -> Option<u64> {
     None
 }
 
-fn detect_available_disk_space_bytes(path: &Path) -> Option<u64> {
-    if cfg!(target_os = "windows") {
+/// Like `directory_size_bytes`, but walks the tree itself when `du` is unavailable.
+fn measure_directory_size_bytes(path: &Path) -> Option<u64> {
+    if !path.is_dir() {
         return None;
     }
 
-    let output = Command::new("df")
-        .arg("-Pk")
-        .arg(path)
-        .output()
-        .ok()?;
-    if !output.status.success() {
-        return None;
-    }
+    directory_size_bytes(path).or_else(|| {
+        let mut total_bytes = 0_u64;
+        let mut pending_directories = vec![path.to_path_buf()];
+        while let Some(directory) = pending_directories.pop() {
+            let Ok(entries) = fs::read_dir(&directory) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Ok(metadata) = entry.metadata() else {
+                    continue;
+                };
+                if metadata.is_dir() {
+                    pending_directories.push(entry.path());
+                } else {
+                    total_bytes += metadata.len();
+                }
+            }
+        }
+        Some(total_bytes)
+    })
+}
 
-    let stdout = String::from_utf8(output.stdout).ok()?;
-    let data_row = stdout.lines().nth(1)?;
-    let available_kib = data_row.split_whitespace().nth(3)?.parse::<u64>().ok()?;
-    Some(available_kib.saturating_mul(1024))
+/// The per-app folders Steam keeps in the library that holds the app's manifest.
+struct SteamAppStoragePaths {
+    install_directory: PathBuf,
+    shader_cache_directory: PathBuf,
+    compatdata_directory: PathBuf,
+    workshop_directory: PathBuf,
 }
 
-fn resolve_steam_install_directory_for_app_id(
+fn resolve_steam_app_storage_paths(
     steam_root_override: Option<&str>,
     app_id: u64,
-) -> Result<PathBuf, String> {
+) -> Result<SteamAppStoragePaths, String> {
     let manifest_path = resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)?;
-    let manifest_contents = fs::read_to_string(&manifest_path).map_err(|error| {
-        format!(
-            "Failed to read Steam app manifest at {}: {error}",
-            manifest_path.display()
-        )
-    })?;
-    let install_dir_name = parse_steam_manifest_install_directory(&manifest_contents)?;
     let steamapps_directory = manifest_path.parent().ok_or_else(|| {
         format!(
             "Failed to resolve Steam library directory for manifest {}",
             manifest_path.display()
         )
     })?;
-
-    Ok(steamapps_directory.join("common").join(install_dir_name))
+    let app_id_key = app_id.to_string();
+    Ok(SteamAppStoragePaths {
+        install_directory: resolve_steam_install_directory_for_app_id(steam_root_override, app_id)?,
+        shader_cache_directory: steamapps_directory.join("shadercache").join(&app_id_key),
+        compatdata_directory: steamapps_directory.join("compatdata").join(&app_id_key),
+        workshop_directory: steamapps_directory
+            .join("workshop")
+            .join("content")
+            .join(&app_id_key),
+    })
 }
 
-fn open_path_in_file_manager(path: &Path) -> Result<(), String> {
-    let open_result = if cfg!(target_os = "windows") {
-        Command::new("explorer").arg(path).spawn()
-    } else if cfg!(target_os = "macos") {
-        Command::new("open").arg(path).spawn()
-    } else {
-        Command::new("xdg-open").arg(path).spawn()
-    };
+/// Source and destination paths for relocating an installed Steam app to another library.
+struct SteamAppMovePlan {
+    source_manifest_path: PathBuf,
+    target_manifest_path: PathBuf,
+    source_install_directory: PathBuf,
+    target_install_directory: PathBuf,
+    install_bytes: u64,
+    source_workshop_directory: PathBuf,
+    target_workshop_directory: PathBuf,
+    workshop_bytes: u64,
+    target_library_path: PathBuf,
+}
 
-    open_result
-        .map(|_| ())
-        .map_err(|error| format!("Failed to open path {}: {error}", path.display()))
+impl SteamAppMovePlan {
+    fn total_bytes(&self) -> u64 {
+        self.install_bytes.saturating_add(self.workshop_bytes)
+    }
 }
 
-fn resolve_steam_game_kinds(
-    connection: &Connection,
-    client: &Client,
-    games: &[SteamOwnedGame],
-) -> Result<HashMap<u64, String>, String> {
-    let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_METADATA_CACHE_TTL_HOURS);
-    let mut kinds_by_app_id = HashMap::new();
-    let mut uncached_app_ids = Vec::new();
-    let mut seen_app_ids = HashSet::new();
+/// Returns the canonical `steamapps` directory of `library_path`, provided it is one of the
+/// library folders the Steam client knows about.
+fn resolve_known_steam_library_steamapps(
+    steam_root_override: Option<&str>,
+    library_path: &Path,
+) -> Result<PathBuf, String> {
+    let canonical_steamapps = fs::canonicalize(library_path.join("steamapps")).map_err(|_| {
+        format!(
+            "Target library must be an existing Steam library folder: {}",
+            library_path.display()
+        )
+    })?;
+    let is_known_library = resolve_steam_root_paths(steam_root_override)
+        .iter()
+        .filter_map(|steam_root| resolve_steamapps_directories(steam_root).ok())
+        .flatten()
+        .filter_map(|steamapps_directory| fs::canonicalize(steamapps_directory).ok())
+        .any(|steamapps_directory| steamapps_directory == canonical_steamapps);
+    if !is_known_library {
+        return Err(format!(
+            "Target library must be one of the Steam library folders: {}",
+            library_path.display()
+        ));
+    }
+    Ok(canonical_steamapps)
+}
 
-    for game in games {
-        if !seen_app_ids.insert(game.appid) {
-            continue;
+fn resolve_steam_app_move_plan(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+    target_library_path: &Path,
+) -> Result<SteamAppMovePlan, String> {
+    let source_manifest_path = resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)?;
+    let source_steamapps_directory = source_manifest_path.parent().ok_or_else(|| {
+        format!(
+            "Failed to resolve Steam library directory for manifest {}",
+            source_manifest_path.display()
+        )
+    })?;
+    let target_steamapps_directory = target_library_path.join("steamapps");
+    let canonical_target = resolve_known_steam_library_steamapps(steam_root_override, target_library_path)?;
+    if fs::canonicalize(source_steamapps_directory).ok().as_ref() == Some(&canonical_target) {
+        return Err(String::from(
+            "Target library must be different from the current install location",
+        ));
+    }
+
+    let source_install_directory = resolve_steam_install_directory_for_app_id(steam_root_override, app_id)?;
+    let install_dir_name = source_install_directory
+        .file_name()
+        .ok_or_else(|| String::from("Steam install directory name is invalid"))?;
+    let target_install_directory = target_steamapps_directory.join("common").join(install_dir_name);
+    if target_install_directory.exists() {
+        return Err(format!(
+            "Install folder already exists in the target library: {}",
+            target_install_directory.display()
+        ));
+    }
+
+    let app_id_key = app_id.to_string();
+    let source_workshop_directory = source_steamapps_directory
+        .join("workshop")
+        .join("content")
+        .join(&app_id_key);
+    let target_workshop_directory = target_steamapps_directory
+        .join("workshop")
+        .join("content")
+        .join(&app_id_key);
+
+    Ok(SteamAppMovePlan {
+        target_manifest_path: target_steamapps_directory.join(format!("appmanifest_{app_id}.acf")),
+        install_bytes: measure_directory_size_bytes(&source_install_directory).unwrap_or(0),
+        workshop_bytes: measure_directory_size_bytes(&source_workshop_directory).unwrap_or(0),
+        source_manifest_path,
+        source_install_directory,
+        target_install_directory,
+        source_workshop_directory,
+        target_workshop_directory,
+        target_library_path: target_library_path.to_path_buf(),
+    })
+}
+
+/// Moves the install folder, workshop content and manifest of a Steam app. A plain rename is
+/// tried first; across filesystems the files are copied and the originals removed afterwards,
+/// so a failed copy leaves the existing install untouched. The manifest is written to the
+/// target library before anything moves and removed again if the install folder can't follow,
+/// so files and manifest never end up in different libraries.
+fn execute_steam_app_move<F>(plan: &SteamAppMovePlan, mut on_progress: F) -> Result<(), String>
+where
+    F: FnMut(u64),
+{
+    fs::copy(&plan.source_manifest_path, &plan.target_manifest_path)
+        .map_err(|error| format!("Failed to copy Steam app manifest: {error}"))?;
+
+    let mut copied_bytes = 0_u64;
+    if let Err(error) = move_directory_with_progress(
+        &plan.source_install_directory,
+        &plan.target_install_directory,
+        plan.install_bytes,
+        &mut copied_bytes,
+        &mut on_progress,
+    ) {
+        let _ = fs::remove_file(&plan.target_manifest_path);
+        return Err(error);
+    }
+
+    if plan.source_workshop_directory.is_dir() && !plan.target_workshop_directory.exists() {
+        move_directory_with_progress(
+            &plan.source_workshop_directory,
+            &plan.target_workshop_directory,
+            plan.workshop_bytes,
+            &mut copied_bytes,
+            &mut on_progress,
+        )?;
+        let workshop_manifest_name = plan
+            .source_manifest_path
+            .file_name()
+            .map(|name| name.to_string_lossy().replace("appmanifest_", "appworkshop_"));
+        if let (Some(workshop_manifest_name), Some(source_steamapps), Some(target_steamapps)) = (
+            workshop_manifest_name,
+            plan.source_manifest_path.parent(),
+            plan.target_manifest_path.parent(),
+        ) {
+            let source_workshop_manifest = source_steamapps.join("workshop").join(&workshop_manifest_name);
+            if source_workshop_manifest.is_file() {
+                let target_workshop_manifest = target_steamapps.join("workshop").join(&workshop_manifest_name);
+                fs::copy(&source_workshop_manifest, &target_workshop_manifest).map_err(|error| {
+                    format!("Failed to copy Steam workshop manifest: {error}")
+                })?;
+                let _ = fs::remove_file(&source_workshop_manifest);
+            }
+        }
+    }
+
+    fs::remove_file(&plan.source_manifest_path).map_err(|error| {
+        format!(
+            "Failed to remove old Steam app manifest at {}: {error}",
+            plan.source_manifest_path.display()
+        )
+    })?;
+    Ok(())
+}
+
+fn move_directory_with_progress(
+    source: &Path,
+    destination: &Path,
+    directory_bytes: u64,
+    copied_bytes: &mut u64,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|error| format!("Failed to create {}: {error}", parent.display()))?;
+    }
+
+    if fs::rename(source, destination).is_ok() {
+        *copied_bytes = copied_bytes.saturating_add(directory_bytes);
+        on_progress(*copied_bytes);
+        return Ok(());
+    }
+
+    if let Err(error) = copy_directory_with_progress(source, destination, copied_bytes, on_progress) {
+        let _ = fs::remove_dir_all(destination);
+        return Err(error);
+    }
+    fs::remove_dir_all(source)
+        .map_err(|error| format!("Failed to remove {} after copying: {error}", source.display()))
+}
+
+fn copy_directory_with_progress(
+    source: &Path,
+    destination: &Path,
+    copied_bytes: &mut u64,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    fs::create_dir_all(destination)
+        .map_err(|error| format!("Failed to create {}: {error}", destination.display()))?;
+    let entries = fs::read_dir(source)
+        .map_err(|error| format!("Failed to read {}: {error}", source.display()))?;
+    for entry in entries {
+        let entry = entry.map_err(|error| format!("Failed to read {}: {error}", source.display()))?;
+        let source_path = entry.path();
+        let destination_path = destination.join(entry.file_name());
+        let file_type = entry
+            .file_type()
+            .map_err(|error| format!("Failed to inspect {}: {error}", source_path.display()))?;
+
+        if file_type.is_dir() {
+            copy_directory_with_progress(&source_path, &destination_path, copied_bytes, on_progress)?;
+            continue;
+        }
+
+        #[cfg(unix)]
+        if file_type.is_symlink() {
+            let link_target = fs::read_link(&source_path)
+                .map_err(|error| format!("Failed to read link {}: {error}", source_path.display()))?;
+            std::os::unix::fs::symlink(&link_target, &destination_path)
+                .map_err(|error| format!("Failed to create link {}: {error}", destination_path.display()))?;
+            continue;
+        }
+
+        let file_bytes = fs::copy(&source_path, &destination_path)
+            .map_err(|error| format!("Failed to copy {}: {error}", source_path.display()))?;
+        *copied_bytes = copied_bytes.saturating_add(file_bytes);
+        on_progress(*copied_bytes);
+    }
+    Ok(())
+}
+
+fn detect_available_disk_space_bytes(path: &Path) -> Option<u64> {
+    disk_space::detect_disk_space(path).map(|disk_space| disk_space.available_bytes)
+}
+
+/// Sums `SizeOnDisk` over the app manifests in one `steamapps` folder.
+fn summarize_steamapps_directory_usage(steamapps_directory: &Path) -> (u64, usize) {
+    let Ok(entries) = fs::read_dir(steamapps_directory) else {
+        return (0, 0);
+    };
+
+    let mut used_bytes = 0_u64;
+    let mut game_count = 0_usize;
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        if !file_name.starts_with("appmanifest_") || !file_name.ends_with(".acf") {
+            continue;
+        }
+        let Ok(manifest_contents) = fs::read_to_string(entry.path()) else {
+            continue;
+        };
+        game_count += 1;
+        used_bytes = used_bytes
+            .saturating_add(parse_steam_manifest_size_on_disk_bytes(&manifest_contents).unwrap_or(0));
+    }
+
+    (used_bytes, game_count)
+}
+
+fn collect_steam_library_storage(steam_root_override: Option<&str>) -> Vec<StorageLibraryResponse> {
+    let mut libraries = Vec::new();
+    let mut seen_paths = HashSet::new();
+    for steam_root in resolve_steam_root_paths(steam_root_override) {
+        let steamapps_directories = match resolve_steamapps_directories(&steam_root) {
+            Ok(paths) => paths,
+            Err(error) => {
+                eprintln!(
+                    "Could not resolve Steam library paths from root {}: {}",
+                    steam_root.display(),
+                    error
+                );
+                continue;
+            }
+        };
+
+        for steamapps_directory in steamapps_directories {
+            if !steamapps_directory.is_dir() {
+                continue;
+            }
+            let library_path = steamapps_directory
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| steamapps_directory.clone());
+            let path_label = library_path.display().to_string();
+            if !seen_paths.insert(steam_path_dedupe_key(&library_path)) {
+                continue;
+            }
+
+            let (games_bytes, game_count) = summarize_steamapps_directory_usage(&steamapps_directory);
+            let disk_space = disk_space::detect_disk_space(&library_path);
+            libraries.push(StorageLibraryResponse {
+                provider: String::from("steam"),
+                path: path_label,
+                total_bytes: disk_space.map(|value| value.total_bytes),
+                free_bytes: disk_space.map(|value| value.available_bytes),
+                games_bytes,
+                game_count,
+            });
+        }
+    }
+
+    libraries
+}
+
+fn resolve_steam_install_directory_for_app_id(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+) -> Result<PathBuf, String> {
+    let manifest_path = resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)?;
+    let manifest_contents = fs::read_to_string(&manifest_path).map_err(|error| {
+        format!(
+            "Failed to read Steam app manifest at {}: {error}",
+            manifest_path.display()
+        )
+    })?;
+    let install_dir_name = parse_steam_manifest_install_directory(&manifest_contents)?;
+    let steamapps_directory = manifest_path.parent().ok_or_else(|| {
+        format!(
+            "Failed to resolve Steam library directory for manifest {}",
+            manifest_path.display()
+        )
+    })?;
+
+    Ok(steamapps_directory.join("common").join(install_dir_name))
+}
+
+fn resolve_steam_soundtrack_directory_for_app_id(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+) -> Result<PathBuf, String> {
+    let manifest_path = resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)?;
+    let manifest_contents = fs::read_to_string(&manifest_path).map_err(|error| {
+        format!(
+            "Failed to read Steam app manifest at {}: {error}",
+            manifest_path.display()
+        )
+    })?;
+    let install_dir_name = parse_steam_manifest_install_directory(&manifest_contents)?;
+    let steamapps_directory = manifest_path.parent().ok_or_else(|| {
+        format!(
+            "Failed to resolve Steam library directory for manifest {}",
+            manifest_path.display()
+        )
+    })?;
+
+    // Steam installs soundtrack apps under `steamapps/music`, older ones still land in `common`.
+    let music_directory = steamapps_directory.join("music").join(&install_dir_name);
+    if music_directory.is_dir() {
+        return Ok(music_directory);
+    }
+
+    Ok(steamapps_directory.join("common").join(install_dir_name))
+}
+
+fn collect_soundtrack_audio_files(directory: &Path, output: &mut Vec<PathBuf>) -> Result<(), String> {
+    let entries = fs::read_dir(directory).map_err(|error| {
+        format!(
+            "Failed to read soundtrack directory {}: {error}",
+            directory.display()
+        )
+    })?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_soundtrack_audio_files(&path, output)?;
+            continue;
+        }
+
+        let is_audio_file = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| {
+                SOUNDTRACK_AUDIO_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+            })
+            .unwrap_or(false);
+        if is_audio_file {
+            output.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+fn write_soundtrack_playlist(app_id: u64, tracks: &[PathBuf]) -> Result<PathBuf, String> {
+    let playlist_directory = std::env::temp_dir().join("catalyst-soundtracks");
+    fs::create_dir_all(&playlist_directory)
+        .map_err(|error| format!("Failed to create soundtrack playlist directory: {error}"))?;
+
+    let mut contents = String::from("#EXTM3U\n");
+    for track in tracks {
+        contents.push_str(&track.display().to_string());
+        contents.push('\n');
+    }
+
+    let playlist_path = playlist_directory.join(format!("steam-{app_id}.m3u"));
+    fs::write(&playlist_path, contents)
+        .map_err(|error| format!("Failed to write soundtrack playlist: {error}"))?;
+    Ok(playlist_path)
+}
+
+/// The program and arguments that open `uri` with its registered handler on `target_os`. Windows
+/// hands the URI straight to the protocol handler: `cmd /C start` would cut it at the first `&`.
+fn launcher_uri_command<'a>(uri: &'a str, target_os: &str) -> (&'static str, Vec<&'a str>) {
+    match target_os {
+        "windows" => ("rundll32", vec!["url.dll,FileProtocolHandler", uri]),
+        "macos" => ("open", vec![uri]),
+        _ => ("xdg-open", vec![uri]),
+    }
+}
+
+fn launch_launcher_uri(uri: &str, launcher_name: &str) -> Result<(), String> {
+    let (command, args) = launcher_uri_command(uri, std::env::consts::OS);
+    try_spawn_command(command, &args).map_err(|error| {
+        format!("Could not open {launcher_name}. Make sure it is installed. Attempt: {error}")
+    })
+}
+
+fn open_path_in_file_manager(path: &Path) -> Result<(), String> {
+    let open_result = if cfg!(target_os = "windows") {
+        Command::new("explorer").arg(path).spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg(path).spawn()
+    } else {
+        Command::new("xdg-open").arg(path).spawn()
+    };
+
+    open_result
+        .map(|_| ())
+        .map_err(|error| format!("Failed to open path {}: {error}", path.display()))
+}
+
+fn resolve_steam_game_kinds(
+    connection: &Connection,
+    client: &Client,
+    games: &[SteamOwnedGame],
+    on_progress: &dyn Fn(SteamSyncProgressPayload),
+    cancel_requested: &AtomicBool,
+) -> Result<HashMap<u64, String>, String> {
+    let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_METADATA_CACHE_TTL_HOURS);
+    let mut kinds_by_app_id = HashMap::new();
+    let mut uncached_app_ids = Vec::new();
+    let mut seen_app_ids = HashSet::new();
+
+    for game in games {
+        if !seen_app_ids.insert(game.appid) {
+            continue;
         }
 
         if let Some(cached_type) = find_cached_steam_app_type(connection, game.appid, stale_before)?
@@ -1996,15 +3490,30 @@ fn resolve_steam_game_kinds(
         }
     }
 
-    for app_id_batch in uncached_app_ids.chunks(STEAM_APP_DETAILS_BATCH_SIZE) {
-        let fetched_types = match fetch_steam_app_types_batch(client, app_id_batch) {
-            Ok(types) => types,
+    let batch_count = uncached_app_ids.len().div_ceil(STEAM_APP_DETAILS_BATCH_SIZE);
+    for (batch_index, app_id_batch) in uncached_app_ids
+        .chunks(STEAM_APP_DETAILS_BATCH_SIZE)
+        .enumerate()
+    {
+        ensure_steam_sync_not_cancelled(cancel_requested)?;
+        on_progress(SteamSyncProgressPayload {
+            phase: "resolving_kinds",
+            processed: batch_index * STEAM_APP_DETAILS_BATCH_SIZE,
+            total: uncached_app_ids.len(),
+            current_batch: Some(batch_index + 1),
+            batch_count: Some(batch_count),
+        });
+        let fetched_metadata = match fetch_steam_app_metadata_batch(client, app_id_batch) {
+            Ok(metadata) => metadata,
             Err(_) => continue,
         };
 
-        for (app_id, app_type) in fetched_types {
-            cache_steam_app_type(connection, app_id, &app_type)?;
-            kinds_by_app_id.insert(app_id, steam_kind_from_app_type(&app_type).to_owned());
+        for (app_id, metadata) in fetched_metadata {
+            cache_steam_app_metadata(connection, app_id, &metadata)?;
+            kinds_by_app_id.insert(
+                app_id,
+                steam_kind_from_app_type(&metadata.app_type).to_owned(),
+            );
         }
     }
 
@@ -2044,36 +3553,185 @@ fn find_cached_steam_app_type(
     Ok(Some(normalized_type))
 }
 
-fn cache_steam_app_type(
+fn cache_steam_app_metadata(
     connection: &Connection,
     app_id: u64,
-    app_type: &str,
+    metadata: &SteamAppStoreMetadata,
 ) -> Result<(), String> {
-    let normalized_type = normalize_steam_app_type(app_type);
+    let normalized_type = normalize_steam_app_type(&metadata.app_type);
     if normalized_type.is_empty() {
         return Ok(());
     }
 
+    let encode_list = |values: &[String]| {
+        serde_json::to_string(values)
+            .map_err(|error| format!("Failed to encode Steam app metadata cache entry: {error}"))
+    };
     connection
         .execute(
             "
-            INSERT INTO steam_app_metadata (app_id, app_type, fetched_at)
-            VALUES (?1, ?2, ?3)
+            INSERT INTO steam_app_metadata (
+              app_id, app_type, genres_json, categories_json, developers_json, publishers_json,
+              release_date, metacritic_score, recommendations_total, controller_support, vr_support,
+              fetched_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
             ON CONFLICT(app_id) DO UPDATE SET
               app_type = excluded.app_type,
+              genres_json = excluded.genres_json,
+              categories_json = excluded.categories_json,
+              developers_json = excluded.developers_json,
+              publishers_json = excluded.publishers_json,
+              release_date = excluded.release_date,
+              metacritic_score = excluded.metacritic_score,
+              recommendations_total = excluded.recommendations_total,
+              controller_support = excluded.controller_support,
+              vr_support = excluded.vr_support,
               fetched_at = excluded.fetched_at
             ",
-            params![app_id.to_string(), normalized_type, Utc::now().to_rfc3339()],
+            params![
+                app_id.to_string(),
+                normalized_type,
+                encode_list(&metadata.genres)?,
+                encode_list(&metadata.categories)?,
+                encode_list(&metadata.developers)?,
+                encode_list(&metadata.publishers)?,
+                metadata.release_date,
+                metadata.metacritic_score,
+                metadata.recommendations_total,
+                metadata.controller_support,
+                metadata.vr_support,
+                Utc::now().to_rfc3339()
+            ],
         )
         .map_err(|error| format!("Failed to cache Steam app metadata: {error}"))?;
 
     Ok(())
 }
 
-fn fetch_steam_app_types_batch(
+fn parse_steam_app_store_metadata(data: Option<&serde_json::Value>) -> SteamAppStoreMetadata {
+    let string_list = |field: &str| {
+        data.and_then(|value| value.get(field))
+            .and_then(serde_json::Value::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+    // Genres and categories are lists of `{ id, description }` objects.
+    let descriptions = |field: &str| {
+        data.and_then(|value| value.get(field))
+            .and_then(serde_json::Value::as_array)
+            .map(|values| {
+                values
+                    .iter()
+                    .filter_map(|value| value.get("description"))
+                    .filter_map(serde_json::Value::as_str)
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_owned)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default()
+    };
+
+    SteamAppStoreMetadata {
+        app_type: data
+            .and_then(|value| value.get("type"))
+            .and_then(serde_json::Value::as_str)
+            .map(normalize_steam_app_type)
+            .filter(|value| !value.is_empty())
+            .unwrap_or_else(|| String::from("unknown")),
+        genres: descriptions("genres"),
+        categories: descriptions("categories"),
+        developers: string_list("developers"),
+        publishers: string_list("publishers"),
+        release_date: data
+            .and_then(|value| value.get("release_date"))
+            .and_then(|value| value.get("date"))
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_owned),
+        metacritic_score: data
+            .and_then(|value| value.get("metacritic"))
+            .and_then(|value| value.get("score"))
+            .and_then(serde_json::Value::as_i64),
+        recommendations_total: data
+            .and_then(|value| value.get("recommendations"))
+            .and_then(|value| value.get("total"))
+            .and_then(serde_json::Value::as_i64),
+        controller_support: parse_steam_controller_support(data),
+        vr_support: parse_steam_vr_support(data),
+    }
+}
+
+/// Store categories as `(id, lowercased description)`. Descriptions are kept for matching, as
+/// ids occasionally come back as strings.
+fn parse_steam_store_categories(data: Option<&serde_json::Value>) -> Vec<(Option<u64>, String)> {
+    let Some(categories) = data
+        .and_then(|value| value.get("categories"))
+        .and_then(serde_json::Value::as_array)
+    else {
+        return Vec::new();
+    };
+    categories
+        .iter()
+        .map(|category| {
+            let id = category.get("id").and_then(|id| {
+                id.as_u64()
+                    .or_else(|| id.as_str().and_then(|id| id.trim().parse::<u64>().ok()))
+            });
+            let description = category
+                .get("description")
+                .and_then(serde_json::Value::as_str)
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            (id, description)
+        })
+        .collect()
+}
+
+/// Steam files controller support under store categories 28 ("Full controller support") and
+/// 18 ("Partial Controller Support").
+fn parse_steam_controller_support(data: Option<&serde_json::Value>) -> Option<String> {
+    let mut partial = false;
+    for (id, description) in parse_steam_store_categories(data) {
+        if id == Some(28) || description.contains("full controller support") {
+            return Some(String::from("Full"));
+        }
+        if id == Some(18) || description.contains("partial controller support") {
+            partial = true;
+        }
+    }
+    partial.then(|| String::from("Partial"))
+}
+
+/// Steam files VR titles under store categories 54 ("VR Only"), 53 ("VR Supported") and the
+/// older 31 ("VR Support").
+fn parse_steam_vr_support(data: Option<&serde_json::Value>) -> Option<String> {
+    let mut supported = false;
+    for (id, description) in parse_steam_store_categories(data) {
+        if id == Some(54) || description == "vr only" {
+            return Some(String::from("Only"));
+        }
+        if matches!(id, Some(31 | 53)) || description.starts_with("vr support") {
+            supported = true;
+        }
+    }
+    supported.then(|| String::from("Supported"))
+}
+
+fn fetch_steam_app_metadata_batch(
     client: &Client,
     app_id_batch: &[u64],
-) -> Result<HashMap<u64, String>, String> {
+) -> Result<HashMap<u64, SteamAppStoreMetadata>, String> {
     if app_id_batch.is_empty() {
         return Ok(HashMap::new());
     }
@@ -2089,9 +3747,7 @@ fn fetch_steam_app_types_batch(
         .query_pairs_mut()
         .append_pair("appids", &app_ids);
 
-    let response = client
-        .get(request_url)
-        .send()
+    let response = send_steam_get_request(client, request_url)
         .map_err(|error| format!("Steam app details request failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
@@ -2104,7 +3760,7 @@ fn fetch_steam_app_types_batch(
         .json::<serde_json::Value>()
         .map_err(|error| format!("Failed to decode Steam app details response: {error}"))?;
 
-    let mut app_types = HashMap::new();
+    let mut metadata_by_app_id = HashMap::new();
     for app_id in app_id_batch {
         let key = app_id.to_string();
         let Some(entry) = payload.get(&key) else {
@@ -2114,29 +3770,23 @@ fn fetch_steam_app_types_batch(
             continue;
         };
 
-        let app_type = entry
-            .get("data")
-            .and_then(|value| value.get("type"))
-            .and_then(serde_json::Value::as_str)
-            .map(normalize_steam_app_type)
-            .filter(|value| !value.is_empty())
-            .unwrap_or_else(|| String::from("unknown"));
-
-        app_types.insert(*app_id, app_type);
+        metadata_by_app_id.insert(*app_id, parse_steam_app_store_metadata(entry.get("data")));
     }
 
-    Ok(app_types)
+    Ok(metadata_by_app_id)
 }
 
 fn refresh_steam_store_tags_cache(
     connection: &Connection,
     client: &Client,
     app_ids: &[u64],
+    on_progress: &dyn Fn(SteamSyncProgressPayload),
+    cancel_requested: &AtomicBool,
 ) -> Result<(), String> {
     let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_STORE_TAGS_CACHE_TTL_HOURS);
     let mut seen_app_ids = HashSet::new();
 
-    for app_id in app_ids {
+    for (index, app_id) in app_ids.iter().enumerate() {
         if !seen_app_ids.insert(*app_id) {
             continue;
         }
@@ -2145,6 +3795,9 @@ fn refresh_steam_store_tags_cache(
             continue;
         }
 
+        ensure_steam_sync_not_cancelled(cancel_requested)?;
+        on_progress(steam_sync_phase_progress("fetching_store_tags", index, app_ids.len()));
+
         let fetched_tags = match fetch_steam_store_user_tags(client, *app_id) {
             Ok(tags) => tags,
             Err(error) => {
@@ -2158,11 +3811,325 @@ fn refresh_steam_store_tags_cache(
     Ok(())
 }
 
-fn find_cached_steam_store_tags(
+fn refresh_steam_review_summaries_cache(
     connection: &Connection,
-    app_id: u64,
-    stale_before: chrono::DateTime<Utc>,
-) -> Result<Option<Vec<String>>, String> {
+    client: &Client,
+    app_ids: &[u64],
+    on_progress: &dyn Fn(SteamSyncProgressPayload),
+    cancel_requested: &AtomicBool,
+) -> Result<(), String> {
+    let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_REVIEW_SUMMARY_CACHE_TTL_HOURS);
+    let mut seen_app_ids = HashSet::new();
+
+    for (index, app_id) in app_ids.iter().enumerate() {
+        if !seen_app_ids.insert(*app_id) {
+            continue;
+        }
+
+        if find_cached_steam_review_summary(connection, *app_id, stale_before)?.is_some() {
+            continue;
+        }
+
+        ensure_steam_sync_not_cancelled(cancel_requested)?;
+        on_progress(steam_sync_phase_progress("fetching_review_summaries", index, app_ids.len()));
+
+        match fetch_steam_review_summary(client, *app_id) {
+            Ok(Some(summary)) => cache_steam_review_summary(connection, *app_id, &summary)?,
+            Ok(None) => {}
+            Err(error) => {
+                eprintln!("Could not fetch Steam review summary for app {app_id}: {error}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn fetch_steam_review_summary(
+    client: &Client,
+    app_id: u64,
+) -> Result<Option<SteamReviewSummary>, String> {
+    let mut request_url = Url::parse(&format!("{STEAM_STORE_APP_REVIEWS_ENDPOINT}/{app_id}"))
+        .map_err(|error| format!("Failed to parse Steam reviews endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("json", "1")
+        .append_pair("language", "all")
+        .append_pair("purchase_type", "all")
+        .append_pair("num_per_page", "0");
+
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam reviews request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Steam reviews request failed with status {}",
+            response.status()
+        ));
+    }
+
+    let payload = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam reviews response: {error}"))?;
+    Ok(parse_steam_review_summary(&payload))
+}
+
+fn parse_steam_review_summary(payload: &serde_json::Value) -> Option<SteamReviewSummary> {
+    let summary = payload.get("query_summary")?;
+    let total_reviews = summary.get("total_reviews")?.as_i64()?;
+    let score_description = summary
+        .get("review_score_desc")
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())?;
+    // Apps without reviews report "No user reviews"; there is nothing to rank them by.
+    if total_reviews <= 0 {
+        return None;
+    }
+
+    Some(SteamReviewSummary {
+        score_description: score_description.to_owned(),
+        total_positive: summary
+            .get("total_positive")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(0),
+        total_reviews,
+    })
+}
+
+fn find_cached_steam_review_summary(
+    connection: &Connection,
+    app_id: u64,
+    stale_before: chrono::DateTime<Utc>,
+) -> Result<Option<SteamReviewSummary>, String> {
+    let cached = connection
+        .query_row(
+            "
+            SELECT score_description, total_positive, total_reviews, fetched_at
+            FROM steam_app_review_summaries
+            WHERE app_id = ?1
+            ",
+            params![app_id.to_string()],
+            |row| {
+                Ok((
+                    SteamReviewSummary {
+                        score_description: row.get(0)?,
+                        total_positive: row.get(1)?,
+                        total_reviews: row.get(2)?,
+                    },
+                    row.get::<_, String>(3)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query cached Steam review summary: {error}"))?;
+
+    let Some((summary, fetched_at)) = cached else {
+        return Ok(None);
+    };
+
+    let is_fresh = chrono::DateTime::parse_from_rfc3339(&fetched_at)
+        .map(|timestamp| timestamp.with_timezone(&Utc) >= stale_before)
+        .unwrap_or(false);
+    Ok(is_fresh.then_some(summary))
+}
+
+fn cache_steam_review_summary(
+    connection: &Connection,
+    app_id: u64,
+    summary: &SteamReviewSummary,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            INSERT INTO steam_app_review_summaries (
+              app_id, score_description, total_positive, total_reviews, fetched_at
+            )
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(app_id) DO UPDATE SET
+              score_description = excluded.score_description,
+              total_positive = excluded.total_positive,
+              total_reviews = excluded.total_reviews,
+              fetched_at = excluded.fetched_at
+            ",
+            params![
+                app_id.to_string(),
+                summary.score_description,
+                summary.total_positive,
+                summary.total_reviews,
+                Utc::now().to_rfc3339()
+            ],
+        )
+        .map_err(|error| format!("Failed to cache Steam review summary: {error}"))?;
+
+    Ok(())
+}
+
+/// Fills the install size cache for games that are not installed, so the install dialog does
+/// not have to wait on a store request. Uses batched appdetails requests, which go through the
+/// same rate limiter as every other Steam Store call.
+fn prefetch_steam_install_size_estimates(
+    connection: &Connection,
+    client: &Client,
+    app_ids: &[u64],
+    on_progress: &dyn Fn(SteamSyncProgressPayload),
+    cancel_requested: &AtomicBool,
+) -> Result<(), String> {
+    let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_SIZE_ESTIMATE_CACHE_TTL_HOURS);
+    let mut seen_app_ids = HashSet::new();
+    let mut uncached_app_ids = Vec::new();
+    for app_id in app_ids {
+        if !seen_app_ids.insert(*app_id) {
+            continue;
+        }
+        if find_cached_steam_app_size_estimate(connection, *app_id, stale_before)?.is_none() {
+            uncached_app_ids.push(*app_id);
+        }
+    }
+
+    let batch_count = uncached_app_ids.len().div_ceil(STEAM_APP_DETAILS_BATCH_SIZE);
+    for (batch_index, app_id_batch) in uncached_app_ids
+        .chunks(STEAM_APP_DETAILS_BATCH_SIZE)
+        .enumerate()
+    {
+        ensure_steam_sync_not_cancelled(cancel_requested)?;
+        on_progress(SteamSyncProgressPayload {
+            phase: "estimating_install_sizes",
+            processed: batch_index * STEAM_APP_DETAILS_BATCH_SIZE,
+            total: uncached_app_ids.len(),
+            current_batch: Some(batch_index + 1),
+            batch_count: Some(batch_count),
+        });
+        let estimates = match fetch_steam_install_size_estimates_batch(client, app_id_batch) {
+            Ok(estimates) => estimates,
+            Err(error) => {
+                eprintln!("Could not fetch Steam install size estimates: {error}");
+                continue;
+            }
+        };
+
+        for (app_id, size_bytes) in estimates {
+            cache_steam_app_size_estimate(connection, app_id, size_bytes)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn fetch_steam_install_size_estimates_batch(
+    client: &Client,
+    app_id_batch: &[u64],
+) -> Result<HashMap<u64, Option<u64>>, String> {
+    if app_id_batch.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let app_ids = app_id_batch
+        .iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(",");
+    let mut request_url = Url::parse(STEAM_APP_DETAILS_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam app details endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("appids", &app_ids)
+        .append_pair("l", "english")
+        .append_pair("cc", "us");
+
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam app details request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Steam app details request failed with status {}",
+            response.status()
+        ));
+    }
+
+    let payload = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam app details response: {error}"))?;
+
+    let mut estimates = HashMap::new();
+    for app_id in app_id_batch {
+        let Some(entry) = payload.get(app_id.to_string()) else {
+            continue;
+        };
+        // Apps the store has no page for are cached as unknown so they are not retried every sync.
+        let size_bytes = entry
+            .get("data")
+            .filter(|_| entry.get("success").and_then(serde_json::Value::as_bool) == Some(true))
+            .and_then(parse_steam_install_size_from_app_data);
+        estimates.insert(*app_id, size_bytes);
+    }
+
+    Ok(estimates)
+}
+
+fn parse_steam_install_size_from_app_data(data: &serde_json::Value) -> Option<u64> {
+    ["pc_requirements", "mac_requirements", "linux_requirements"]
+        .into_iter()
+        .filter_map(|requirements_field| data.get(requirements_field))
+        .filter_map(parse_steam_install_size_from_requirements_value)
+        .max()
+}
+
+/// Returns `Some(None)` for a fresh entry whose size could not be determined, and `None` when
+/// there is no fresh entry at all.
+fn find_cached_steam_app_size_estimate(
+    connection: &Connection,
+    app_id: u64,
+    stale_before: chrono::DateTime<Utc>,
+) -> Result<Option<Option<u64>>, String> {
+    let cached = connection
+        .query_row(
+            "SELECT size_bytes, fetched_at FROM steam_app_size_estimates WHERE app_id = ?1",
+            params![app_id.to_string()],
+            |row| Ok((row.get::<_, Option<i64>>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query cached Steam install size: {error}"))?;
+
+    let Some((size_bytes, fetched_at)) = cached else {
+        return Ok(None);
+    };
+
+    let is_fresh = chrono::DateTime::parse_from_rfc3339(&fetched_at)
+        .map(|timestamp| timestamp.with_timezone(&Utc) >= stale_before)
+        .unwrap_or(false);
+    if !is_fresh {
+        return Ok(None);
+    }
+
+    Ok(Some(size_bytes.and_then(|value| u64::try_from(value).ok())))
+}
+
+fn cache_steam_app_size_estimate(
+    connection: &Connection,
+    app_id: u64,
+    size_bytes: Option<u64>,
+) -> Result<(), String> {
+    let size_bytes = size_bytes.and_then(|value| i64::try_from(value).ok());
+    connection
+        .execute(
+            "
+            INSERT INTO steam_app_size_estimates (app_id, size_bytes, fetched_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(app_id) DO UPDATE SET
+              size_bytes = excluded.size_bytes,
+              fetched_at = excluded.fetched_at
+            ",
+            params![app_id.to_string(), size_bytes, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to cache Steam install size: {error}"))?;
+
+    Ok(())
+}
+
+fn find_cached_steam_store_tags(
+    connection: &Connection,
+    app_id: u64,
+    stale_before: chrono::DateTime<Utc>,
+) -> Result<Option<Vec<String>>, String> {
     let cached = connection
         .query_row(
             "SELECT tags_json, fetched_at FROM steam_app_store_tags WHERE app_id = ?1",
@@ -2372,6 +4339,94 @@ mod tests {
     use super::*;
     use rusqlite::Connection;
 
+    #[test]
+    fn compose_launch_wrapper_options_wraps_command() {
+        let wrappers = GameLaunchWrappersSettingsPayload {
+            gamemode: true,
+            mangohud: true,
+            gamescope: true,
+            gamescope_width: Some(1920),
+            gamescope_height: Some(1080),
+            gamescope_refresh_rate: None,
+            gamescope_fullscreen: true,
+        };
+
+        assert_eq!(
+            compose_launch_wrapper_options("-novid", &wrappers),
+            "gamemoderun gamescope -W 1920 -H 1080 -f -- mangohud %command% -novid"
+        );
+        assert_eq!(
+            compose_launch_wrapper_options(
+                "PROTON_LOG=1 %command%",
+                &GameLaunchWrappersSettingsPayload {
+                    mangohud: true,
+                    ..GameLaunchWrappersSettingsPayload::default()
+                }
+            ),
+            "PROTON_LOG=1 mangohud %command%"
+        );
+    }
+
+    #[test]
+    fn compose_launch_environment_options_prefixes_command() {
+        let variables = normalize_launch_environment_variables(BTreeMap::from([
+            (String::from(" DXVK_HUD "), String::from("fps,frametimes")),
+            (String::from("WINEDLLOVERRIDES"), String::from("dinput8=n,b")),
+            (String::from("PROTON_LOG_DIR"), String::from("/tmp/my logs")),
+            (String::from("1INVALID"), String::from("1")),
+        ]));
+
+        assert_eq!(
+            compose_launch_environment_options("-novid", &variables),
+            "DXVK_HUD=fps,frametimes PROTON_LOG_DIR='/tmp/my logs' WINEDLLOVERRIDES=dinput8=n,b %command% -novid"
+        );
+        assert_eq!(
+            compose_launch_environment_options("gamemoderun %command%", &BTreeMap::new()),
+            "gamemoderun %command%"
+        );
+    }
+
+    #[test]
+    fn mark_steam_manifest_language_update_required_flags_language_changes() {
+        let mut manifest = parse_vdf_document(
+            "\"AppState\" { \"StateFlags\" \"4\" \"MountedConfig\" { \"language\" \"english\" } }",
+        )
+        .expect("parse manifest");
+        let app_state = vdf_ensure_object_path_mut(&mut manifest, &["AppState"]);
+
+        assert!(!mark_steam_manifest_language_update_required(app_state, "english"));
+        assert!(mark_steam_manifest_language_update_required(app_state, "german"));
+        assert_eq!(vdf_get_text_entry(app_state, "StateFlags"), Some("6"));
+    }
+
+    #[test]
+    fn parse_steam_remotecache_usage_sums_tracked_files() {
+        let contents = r#"
+"620"
+{
+	"ChangeNumber"		"12"
+	"ostype"		"-184"
+	"portal2/SAVE/quick.sav"
+	{
+		"size"		"2048"
+		"localtime"		"1700000000"
+		"time"		"1700000000"
+		"remotetime"		"1700000100"
+	}
+	"portal2/cfg/config.cfg"
+	{
+		"size"		"512"
+		"time"		"1690000000"
+		"remotetime"		"1690000000"
+	}
+}
+"#;
+
+        let usage = parse_steam_remotecache_usage(contents, 620).expect("remotecache should parse");
+
+        assert_eq!(usage, (2560, 2, Some(1_700_000_100)));
+    }
+
     #[test]
     fn cache_and_find_steam_app_details_roundtrip() {
         let connection = Connection::open_in_memory().expect("open in-memory");
@@ -2405,43 +4460,1152 @@ mod tests {
         assert_eq!(cached.get("success").and_then(|v| v.as_bool()), Some(true));
         assert!(cached.get("data").is_some());
     }
-}
 
-fn fetch_steam_store_user_tags(client: &Client, app_id: u64) -> Result<Vec<String>, String> {
-    let mut request_url = Url::parse(&format!("{STEAM_STORE_APP_ENDPOINT}/{app_id}/"))
-        .map_err(|error| format!("Failed to parse Steam Store endpoint: {error}"))?;
-    request_url
-        .query_pairs_mut()
-        .append_pair("l", "english")
-        .append_pair("cc", "us");
+    #[test]
+    fn steam_backoff_delay_grows_and_honours_retry_after() {
+        let first = steam_backoff_delay(0, None);
+        assert!(first >= Duration::from_millis(STEAM_HTTP_BACKOFF_BASE_MILLIS));
+        assert!(first <= Duration::from_millis(STEAM_HTTP_BACKOFF_BASE_MILLIS * 3 / 2 + 1));
 
-    let response = client
-        .get(request_url)
-        .send()
-        .map_err(|error| format!("Steam Store tags request failed: {error}"))?;
-    if !response.status().is_success() {
-        return Err(format!(
-            "Steam Store tags request failed with status {}",
-            response.status()
-        ));
+        let capped = steam_backoff_delay(30, None);
+        assert!(capped <= Duration::from_millis(STEAM_HTTP_BACKOFF_MAX_MILLIS * 3 / 2 + 1));
+
+        let retry_after = steam_backoff_delay(0, Some(Duration::from_secs(10)));
+        assert_eq!(retry_after, Duration::from_secs(10));
     }
 
-    let html = response
-        .text()
-        .map_err(|error| format!("Failed to decode Steam Store tags response: {error}"))?;
-    Ok(parse_steam_store_user_tags_from_html(&html))
-}
+    #[test]
+    fn steam_backoff_delay_caps_retry_after() {
+        let max_delay = Duration::from_millis(STEAM_HTTP_BACKOFF_MAX_MILLIS);
 
-fn parse_steam_store_user_tags_from_html(html: &str) -> Vec<String> {
-    let tag_regex = match Regex::new(
-        r#"(?is)<a[^>]*\bclass\s*=\s*"[^"]*\bapp_tag\b[^"]*"[^>]*>(.*?)</a>"#,
-    ) {
-        Ok(regex) => regex,
-        Err(_) => return Vec::new(),
-    };
-    let strip_markup_regex = Regex::new(r"(?is)<[^>]+>").ok();
-    let mut tags = Vec::new();
-    let mut seen = HashSet::new();
+        assert_eq!(steam_backoff_delay(0, Some(Duration::from_secs(60))), max_delay);
+        assert_eq!(steam_backoff_delay(0, Some(Duration::from_secs(u64::MAX))), max_delay);
+        // A zero Retry-After still waits at least the regular backoff.
+        assert!(steam_backoff_delay(0, Some(Duration::ZERO)) >= Duration::from_millis(STEAM_HTTP_BACKOFF_BASE_MILLIS));
+    }
+
+    #[test]
+    fn parse_steam_points_shop_items_payload_maps_definitions() {
+        let payload = serde_json::json!({
+            "response": {
+                "definitions": [
+                    {
+                        "appid": 620,
+                        "defid": 1234,
+                        "community_item_class": 15,
+                        "point_cost": "3000",
+                        "community_item_data": {
+                            "item_title": "Companion Cube",
+                            "item_image_small": "abc.png"
+                        }
+                    },
+                    {
+                        "appid": 999,
+                        "defid": 5678,
+                        "community_item_class": 11,
+                        "point_cost": 500
+                    }
+                ],
+                "next_cursor": "next"
+            }
+        });
+
+        let (items, next_cursor) = parse_steam_points_shop_items_payload(&payload, 620);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].def_id, 1234);
+        assert_eq!(items[0].item_class, "animated_avatar");
+        assert_eq!(items[0].point_cost, 3000);
+        assert!(items[0].animated);
+        assert_eq!(
+            items[0].image_url.as_deref(),
+            Some("https://cdn.akamai.steamstatic.com/steamcommunity/public/images/items/620/abc.png")
+        );
+        assert_eq!(next_cursor.as_deref(), Some("next"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolve_steamapps_directories_dedupes_symlinked_libraries() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let steam_root = dir.path().join("Steam");
+        let library = dir.path().join("games");
+        fs::create_dir_all(steam_root.join("steamapps")).expect("create steam root");
+        fs::create_dir_all(library.join("steamapps")).expect("create library");
+        std::os::unix::fs::symlink(&steam_root, dir.path().join("steam-link")).expect("link steam root");
+        std::os::unix::fs::symlink(&library, dir.path().join("games-link")).expect("link library");
+        fs::write(
+            steam_root.join("steamapps").join("libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\" {{ \"0\" {{ \"path\" \"{}\" }} \"1\" {{ \"path\" \"{}\" }} \"2\" {{ \"path\" \"{}\" }} }}",
+                dir.path().join("steam-link").display(),
+                library.display(),
+                dir.path().join("games-link").display()
+            ),
+        )
+        .expect("write libraryfolders");
+
+        let directories = resolve_steamapps_directories(&steam_root).expect("resolve libraries");
+
+        assert_eq!(directories, vec![steam_root.join("steamapps"), library.join("steamapps")]);
+    }
+
+    #[test]
+    fn steam_id_is_encrypted_at_rest_and_found_by_lookup_hash() {
+        let database = test_support::test_database();
+        let connection = &database.connection;
+
+        let user = create_steam_user(&connection, "76561197960287930").expect("create user");
+        let stored_steam_id: String = connection
+            .query_row("SELECT steam_id FROM users WHERE id = ?1", params![&user.id], |row| row.get(0))
+            .expect("read stored steam id");
+
+        assert!(field_encryption::is_encrypted_field(&stored_steam_id));
+        assert_eq!(user.steam_id.as_deref(), Some("76561197960287930"));
+        let found = find_user_by_steam_id(&connection, "76561197960287930")
+            .expect("query user")
+            .expect("user should be found");
+        assert_eq!(found.id, user.id);
+    }
+
+    #[test]
+    fn read_http_request_head_accumulates_partial_reads() {
+        struct ChunkedReader(Vec<&'static [u8]>);
+
+        impl Read for ChunkedReader {
+            fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+                if self.0.is_empty() {
+                    return Ok(0);
+                }
+                let chunk = self.0.remove(0);
+                buffer[..chunk.len()].copy_from_slice(chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let mut reader = ChunkedReader(vec![
+            b"HEAD /auth/steam/call",
+            b"back?state=abc HTTP/1.1\r\nHost: catalyst\r\n",
+            b"\r\n",
+        ]);
+
+        assert_eq!(
+            read_http_request_head(&mut reader).expect("request should parse"),
+            HttpRequestHead {
+                method: String::from("HEAD"),
+                target: String::from("/auth/steam/callback?state=abc"),
+            }
+        );
+        assert!(constant_time_eq(b"abc", b"abc"));
+        assert!(!constant_time_eq(b"abc", b"abd"));
+        assert!(!constant_time_eq(b"abc", b"abcd"));
+    }
+
+    #[test]
+    fn parse_steam_player_summary_reads_matching_player() {
+        let payload = serde_json::json!({
+            "response": {
+                "players": [
+                    {
+                        "steamid": "76561197960287930",
+                        "communityvisibilitystate": 3,
+                        "personaname": " Rabscuttle ",
+                        "profileurl": "https://steamcommunity.com/id/rabscuttle/",
+                        "avatarmedium": "https://avatars.steamstatic.com/abc_medium.jpg",
+                        "avatarfull": ""
+                    }
+                ]
+            }
+        });
+
+        assert_eq!(
+            parse_steam_player_summary(&payload, "76561197960287930"),
+            Some(SteamPlayerSummary {
+                persona_name: Some(String::from("Rabscuttle")),
+                avatar_url: Some(String::from("https://avatars.steamstatic.com/abc_medium.jpg")),
+                profile_url: Some(String::from("https://steamcommunity.com/id/rabscuttle/")),
+                profile_public: true,
+            })
+        );
+        assert_eq!(parse_steam_player_summary(&payload, "76561197960287931"), None);
+    }
+
+    #[test]
+    fn parse_steam_app_store_metadata_reads_store_details() {
+        let data = serde_json::json!({
+            "type": "Game",
+            "developers": ["Larian Studios"],
+            "publishers": ["Larian Studios", " "],
+            "genres": [{ "id": "3", "description": "RPG" }, { "id": "25", "description": "Adventure" }],
+            "categories": [{ "id": 9, "description": "Co-op" }, { "id": 28, "description": "Full controller support" }],
+            "release_date": { "coming_soon": false, "date": "3 Aug, 2023" },
+            "metacritic": { "score": 96, "url": "https://www.metacritic.com/game/baldurs-gate-3/" },
+            "recommendations": { "total": 512340 }
+        });
+
+        assert_eq!(
+            parse_steam_app_store_metadata(Some(&data)),
+            SteamAppStoreMetadata {
+                app_type: String::from("game"),
+                genres: vec![String::from("RPG"), String::from("Adventure")],
+                categories: vec![String::from("Co-op"), String::from("Full controller support")],
+                developers: vec![String::from("Larian Studios")],
+                publishers: vec![String::from("Larian Studios")],
+                release_date: Some(String::from("3 Aug, 2023")),
+                metacritic_score: Some(96),
+                recommendations_total: Some(512_340),
+                controller_support: Some(String::from("Full")),
+                vr_support: None,
+            }
+        );
+        assert_eq!(
+            parse_steam_controller_support(Some(&serde_json::json!({
+                "categories": [{ "id": 2, "description": "Single-player" }, { "id": "18", "description": "Partial Controller Support" }]
+            }))),
+            Some(String::from("Partial"))
+        );
+        assert_eq!(
+            parse_steam_vr_support(Some(&serde_json::json!({
+                "categories": [{ "id": 53, "description": "VR Supported" }, { "id": 54, "description": "VR Only" }]
+            }))),
+            Some(String::from("Only"))
+        );
+        assert_eq!(parse_steam_app_store_metadata(None).app_type, "unknown");
+    }
+
+    #[test]
+    fn steam_kind_from_app_type_recognizes_non_game_kinds() {
+        assert_eq!(steam_kind_from_app_type(" Tool "), "tool");
+        assert_eq!(steam_kind_from_app_type("Application"), "application");
+        assert_eq!(steam_kind_from_app_type("Series"), "video");
+        assert_eq!(steam_kind_from_app_type("hardware"), "unknown");
+        assert!(NON_GAME_KINDS.contains(&steam_kind_from_app_type("music")));
+    }
+
+    #[test]
+    fn map_steam_game_keeps_recent_playtime_and_last_played() {
+        let payload = serde_json::from_value::<SteamOwnedGamesPayload>(serde_json::json!({
+            "games": [{
+                "appid": 620,
+                "name": "Portal 2",
+                "playtime_forever": 600,
+                "playtime_2weeks": 45,
+                "rtime_last_played": 1_700_000_000
+            }]
+        }))
+        .expect("owned games payload should parse");
+        let game = payload.games.unwrap_or_default().remove(0);
+
+        let input = map_steam_game(game, Some("game"), false);
+
+        assert_eq!(input.playtime_minutes, 600);
+        assert_eq!(input.playtime_2weeks_minutes, 45);
+        assert_eq!(input.last_played_at.as_deref(), Some("2023-11-14T22:13:20+00:00"));
+    }
+
+    #[test]
+    fn steam_owned_games_hidden_by_privacy_detects_private_profiles() {
+        let payload = |value: serde_json::Value| {
+            serde_json::from_value::<SteamOwnedGamesPayload>(value).expect("owned games payload should parse")
+        };
+        let private_response = payload(serde_json::json!({}));
+        let empty_library = payload(serde_json::json!({ "game_count": 0 }));
+        let library = payload(serde_json::json!({ "game_count": 1, "games": [{ "appid": 620 }] }));
+
+        assert!(steam_owned_games_hidden_by_privacy(Some(&private_response), None));
+        assert!(steam_owned_games_hidden_by_privacy(None, Some(true)));
+        assert!(steam_owned_games_hidden_by_privacy(Some(&empty_library), Some(false)));
+        assert!(!steam_owned_games_hidden_by_privacy(Some(&empty_library), Some(true)));
+        assert!(!steam_owned_games_hidden_by_privacy(Some(&library), Some(false)));
+    }
+
+    fn benchmark_library_inputs(count: usize) -> Vec<LibraryGameInput> {
+        (0..count)
+            .map(|index| LibraryGameInput {
+                external_id: (10_000 + index).to_string(),
+                name: format!("Game {index}"),
+                kind: String::from("game"),
+                playtime_minutes: index as i64,
+                playtime_2weeks_minutes: 0,
+                installed: index % 3 == 0,
+                artwork_url: None,
+                last_synced_at: String::from("2024-01-01T00:00:00Z"),
+                last_played_at: None,
+            })
+            .collect()
+    }
+
+    fn sync_input(external_id: &str, name: &str) -> LibraryGameInput {
+        LibraryGameInput {
+            external_id: external_id.to_owned(),
+            name: name.to_owned(),
+            ..benchmark_library_inputs(1).remove(0)
+        }
+    }
+
+    #[test]
+    fn replace_provider_games_counts_added_updated_and_removed_games() {
+        let database = test_support::test_database();
+        let (connection, user_id) = (&database.connection, database.user.id.as_str());
+        replace_provider_games(
+            connection,
+            user_id,
+            "steam",
+            &[sync_input("10", "Portal"), sync_input("20", "Portal 2"), sync_input("30", "Half-Life")],
+            false,
+        )
+        .expect("first sync");
+
+        let summary = replace_provider_games(
+            connection,
+            user_id,
+            "steam",
+            &[sync_input("20", "Portal 2: Remastered"), sync_input("30", "Half-Life"), sync_input("40", "Dota 2")],
+            false,
+        )
+        .expect("second sync");
+        assert_eq!((summary.added, summary.updated, summary.removed), (1, 1, 1));
+    }
+
+    #[test]
+    fn replace_provider_games_rolls_back_when_a_write_fails_partway() {
+        let database = test_support::test_database();
+        let (connection, user_id) = (&database.connection, database.user.id.as_str());
+        replace_provider_games(
+            connection,
+            user_id,
+            "steam",
+            &[sync_input("10", "Portal"), sync_input("20", "Portal 2")],
+            false,
+        )
+        .expect("first sync");
+        connection
+            .execute_batch(
+                "
+                CREATE TEMP TRIGGER fail_game_insert BEFORE INSERT ON games WHEN NEW.external_id = '40'
+                BEGIN SELECT RAISE(ABORT, 'disk full'); END;
+                ",
+            )
+            .expect("create failing trigger");
+
+        // Game 10 is deleted and game 20 renamed before the insert of game 40 fails.
+        let error = replace_provider_games(
+            connection,
+            user_id,
+            "steam",
+            &[sync_input("20", "Portal 2: Remastered"), sync_input("40", "Dota 2")],
+            false,
+        )
+        .expect_err("sync with a failing insert");
+        assert!(error.contains("disk full"), "unexpected error: {error}");
+
+        let mut statement = connection
+            .prepare("SELECT external_id, name FROM games WHERE user_id = ?1 ORDER BY external_id")
+            .expect("prepare games query");
+        let stored_games = statement
+            .query_map(params![user_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+            .expect("query games")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("read games");
+        assert_eq!(
+            stored_games,
+            vec![
+                (String::from("10"), String::from("Portal")),
+                (String::from("20"), String::from("Portal 2"))
+            ]
+        );
+    }
+
+    #[test]
+    #[ignore = "timing benchmark; run with `cargo test -- --ignored --nocapture`"]
+    fn replace_provider_games_batches_large_syncs() {
+        let games = benchmark_library_inputs(3000);
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+
+        // Baseline: one autocommitted upsert per game, as syncs ran before batching.
+        let baseline_db_path = dir.path().join("baseline.db");
+        initialize_database(&baseline_db_path).expect("init db");
+        let connection = open_connection(&baseline_db_path).expect("open conn");
+        let user = create_user(&connection, "baseline@example.com", "hash", None).expect("create user");
+        let started_at = Instant::now();
+        for game in &games {
+            connection
+                .execute(
+                    "
+                    INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, last_synced_at, added_at)
+                    VALUES (?1, 'steam', ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+                    ",
+                    params![
+                        user.id,
+                        game.external_id,
+                        game.name,
+                        game.kind,
+                        game.playtime_minutes,
+                        game.installed,
+                        game.last_synced_at
+                    ],
+                )
+                .expect("insert game");
+            connection
+                .execute(
+                    "DELETE FROM game_genres WHERE user_id = ?1 AND provider = 'steam' AND external_id = ?2",
+                    params![user.id, game.external_id],
+                )
+                .expect("delete genres");
+        }
+        let baseline_elapsed = started_at.elapsed();
+
+        let batched_db_path = dir.path().join("batched.db");
+        initialize_database(&batched_db_path).expect("init db");
+        let connection = open_connection(&batched_db_path).expect("open conn");
+        let user = create_user(&connection, "batched@example.com", "hash", None).expect("create user");
+        let started_at = Instant::now();
+        let summary = replace_provider_games(&connection, &user.id, "steam", &games, false).expect("replace games");
+        let batched_elapsed = started_at.elapsed();
+
+        println!("3000 games: per-statement {baseline_elapsed:?}, batched {batched_elapsed:?}");
+        assert_eq!(summary.added, 3000);
+        let stored_games: i64 = connection
+            .query_row("SELECT COUNT(*) FROM games WHERE user_id = ?1", params![user.id], |row| row.get(0))
+            .expect("count games");
+        assert_eq!(stored_games, 3000);
+        assert!(batched_elapsed < baseline_elapsed);
+    }
+
+    #[test]
+    fn list_collections_by_user_counts_hidden_games_only_when_requested() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let collection = create_user_collection(&connection, &user.id, "Favorites").expect("create collection");
+        for external_id in ["620", "400"] {
+            database.insert_game("steam", external_id, external_id);
+            add_game_to_collection_membership(&connection, &user.id, &collection.id, "steam", external_id)
+                .expect("add membership");
+        }
+        save_game_privacy_settings(
+            &connection,
+            &user.id,
+            "steam",
+            "400",
+            GamePrivacySettingsResponse {
+                hide_in_library: true,
+                mark_as_private: false,
+                overlay_data_deleted: false,
+            },
+        )
+        .expect("save privacy settings");
+
+        let visible = list_collections_by_user(&connection, &user.id, None, None, false).expect("list collections");
+        let all = list_collections_by_user(&connection, &user.id, None, None, true).expect("list collections");
+
+        assert_eq!(visible[0].game_count, 1);
+        assert_eq!(all[0].game_count, 2);
+    }
+
+    #[test]
+    fn ensure_owned_games_exist_reports_the_first_unowned_game() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for external_id in ["620", "400"] {
+            database.insert_game("steam", external_id, external_id);
+        }
+        let game = |provider: &str, external_id: &str| (provider.to_owned(), external_id.to_owned());
+
+        ensure_owned_games_exist(&connection, &user.id, &[game("steam", "620"), game("steam", "400")])
+            .expect("owned games");
+        let error = ensure_owned_games_exist(
+            &connection,
+            &user.id,
+            &[game("steam", "620"), game("epic", "620"), game("steam", "570")],
+        )
+        .expect_err("unowned game");
+        assert_eq!(error, "Game not found for current user: epic:620");
+    }
+
+    #[test]
+    fn collection_cover_image_and_accent_color_are_saved_and_cleared() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let collection = create_user_collection(&connection, &user.id, "Play next").expect("create collection");
+
+        assert!(set_user_collection_accent_color(&connection, &user.id, &collection.id, Some("blue")).is_err());
+        let colored = set_user_collection_accent_color(&connection, &user.id, &collection.id, Some("#3A7"))
+            .expect("set accent color");
+        assert_eq!(colored.accent_color.as_deref(), Some("#33aa77"));
+
+        let cover_file = database.dir.path().join("shelf.png");
+        fs::write(&cover_file, b"\x89PNG\r\n\x1a\nrest").expect("write png");
+        let covered =
+            set_user_collection_cover_image(&connection, &database.db_path, &user.id, &collection.id, Some(&cover_file))
+                .expect("set cover image");
+        let cover_image_path = covered.cover_image_path.expect("cover image path");
+        assert!(Path::new(&cover_image_path).is_file());
+        assert_eq!(covered.accent_color.as_deref(), Some("#33aa77"));
+
+        let listed = list_collections_by_user(&connection, &user.id, None, None, false).expect("list collections");
+        assert_eq!(listed[0].cover_image_path.as_deref(), Some(cover_image_path.as_str()));
+
+        let cleared = set_user_collection_cover_image(&connection, &database.db_path, &user.id, &collection.id, None)
+            .expect("clear cover image");
+        assert_eq!(cleared.cover_image_path, None);
+        assert!(!Path::new(&cover_image_path).exists());
+    }
+
+    #[test]
+    fn set_collection_game_positions_orders_listed_games_and_resets_the_rest() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let collection = create_user_collection(&connection, &user.id, "Play next").expect("create collection");
+        for external_id in ["620", "400", "570"] {
+            database.insert_game("steam", external_id, external_id);
+            add_game_to_collection_membership(&connection, &user.id, &collection.id, "steam", external_id)
+                .expect("add membership");
+        }
+        let game = |external_id: &str| (String::from("steam"), external_id.to_owned());
+
+        set_collection_game_positions(&connection, &user.id, &collection.id, &[game("570"), game("620")])
+            .expect("set order");
+        let positions = load_collection_positions_by_game(&connection, &user.id).expect("load positions");
+        assert_eq!(positions["steam:570"]["Play next"], 0);
+        assert_eq!(positions["steam:620"]["Play next"], 1);
+        assert!(!positions.contains_key("steam:400"));
+
+        assert!(set_collection_game_positions(&connection, &user.id, &collection.id, &[game("620"), game("620")])
+            .is_err());
+        assert!(set_collection_game_positions(&connection, &user.id, &collection.id, &[game("730")]).is_err());
+        let unchanged = load_collection_positions_by_game(&connection, &user.id).expect("load positions");
+        assert_eq!(unchanged, positions);
+
+        set_collection_game_positions(&connection, &user.id, &collection.id, &[game("400")]).expect("reorder");
+        let reordered = load_collection_positions_by_game(&connection, &user.id).expect("load positions");
+        assert_eq!(reordered.keys().collect::<Vec<_>>(), vec!["steam:400"]);
+    }
+
+    #[test]
+    fn import_steam_collections_for_user_previews_and_replaces_memberships() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for external_id in ["620", "400"] {
+            database.insert_game("steam", external_id, external_id);
+        }
+        let collection = create_user_collection(&connection, &user.id, "Co-op").expect("create collection");
+        add_game_to_collection_membership(&connection, &user.id, &collection.id, "steam", "400")
+            .expect("add membership");
+        let steam_collections = || HashMap::from([(String::from("620"), HashSet::from([String::from("co-op")]))]);
+        let members = |connection: &Connection| {
+            list_collection_provider_external_ids(connection, &user.id, &collection.id, "steam")
+                .expect("list members")
+        };
+
+        let preview =
+            import_steam_collections_for_user(&connection, &user.id, steam_collections(), SteamCollectionsImportMode::Preview, false)
+                .expect("preview import");
+        assert_eq!((preview.memberships_added, preview.memberships_removed), (1, 1));
+        assert_eq!(preview.changes.len(), 2);
+        assert_eq!(members(&connection), vec![String::from("400")]);
+
+        import_steam_collections_for_user(&connection, &user.id, steam_collections(), SteamCollectionsImportMode::Merge, false)
+            .expect("merge import");
+        let mut merged = members(&connection);
+        merged.sort();
+        assert_eq!(merged, vec![String::from("400"), String::from("620")]);
+
+        let replaced =
+            import_steam_collections_for_user(&connection, &user.id, steam_collections(), SteamCollectionsImportMode::Replace, false)
+                .expect("replace import");
+        assert_eq!((replaced.memberships_added, replaced.memberships_removed), (0, 1));
+        assert_eq!(members(&connection), vec![String::from("620")]);
+
+        let favorites = import_steam_collections_for_user(
+            &connection,
+            &user.id,
+            HashMap::from([(String::from("400"), HashSet::from([String::from("favorite")]))]),
+            SteamCollectionsImportMode::Merge,
+            true,
+        )
+        .expect("favorites import");
+        assert_eq!((favorites.favorites_linked, favorites.collections_created), (1, 0));
+        assert!(find_collection_id_by_name(&connection, &user.id, "favorite").expect("find collection").is_none());
+    }
+
+    #[test]
+    fn classify_steam_game_kind_separates_soundtracks_from_dlc() {
+        assert_eq!(classify_steam_game_kind("Portal 2 - Soundtrack"), "music");
+        assert_eq!(classify_steam_game_kind("Celeste Original Soundtrack"), "music");
+        assert_eq!(classify_steam_game_kind("Hades OST"), "music");
+        // "ost" must be a whole word, not part of one.
+        assert_eq!(classify_steam_game_kind("Ghost of a Tale"), "game");
+        assert_eq!(classify_steam_game_kind("Borderlands 3 Season Pass"), "dlc");
+        assert_eq!(classify_steam_game_kind("Cities: Skylines - Deluxe Add-On"), "dlc");
+        // A soundtrack sold as DLC is still DLC.
+        assert_eq!(classify_steam_game_kind("Soundtrack DLC"), "dlc");
+        assert_eq!(classify_steam_game_kind("Portal 2 Demo"), "demo");
+        assert_eq!(classify_steam_game_kind("Democracy 4"), "game");
+        assert_eq!(steam_kind_from_app_type("Music"), "music");
+    }
+
+    #[test]
+    fn collect_soundtrack_audio_files_recurses_and_filters_extensions() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        fs::create_dir_all(dir.path().join("Disc 2")).expect("create disc dir");
+        for file_name in ["01 Main Theme.MP3", "cover.jpg", "Disc 2/01 Credits.flac", "notes.txt"] {
+            fs::write(dir.path().join(file_name), b"").expect("write file");
+        }
+
+        let mut tracks = Vec::new();
+        collect_soundtrack_audio_files(dir.path(), &mut tracks).expect("collect tracks");
+        tracks.sort();
+
+        assert_eq!(
+            tracks,
+            vec![dir.path().join("01 Main Theme.MP3"), dir.path().join("Disc 2").join("01 Credits.flac")]
+        );
+        assert!(collect_soundtrack_audio_files(&dir.path().join("missing"), &mut tracks).is_err());
+    }
+
+    #[test]
+    fn incremental_sync_refreshes_genres_and_sync_time_of_unchanged_games() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let mut games = benchmark_library_inputs(2);
+        replace_provider_games(connection, &user.id, "steam", &games, true).expect("first sync");
+        cache_steam_store_tags(connection, 10_000, &[String::from("Racing"), String::from("Indie")])
+            .expect("cache tags");
+
+        for game in &mut games {
+            game.last_synced_at = String::from("2024-02-01T00:00:00Z");
+        }
+        let summary = replace_provider_games(connection, &user.id, "steam", &games, true).expect("second sync");
+
+        assert_eq!((summary.added, summary.updated, summary.removed), (0, 0, 0));
+        let synced_at = connection
+            .prepare("SELECT DISTINCT last_synced_at FROM games WHERE user_id = ?1")
+            .expect("prepare query")
+            .query_map(params![user.id], |row| row.get::<_, String>(0))
+            .expect("query sync times")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("decode sync times");
+        assert_eq!(synced_at, vec![String::from("2024-02-01T00:00:00Z")]);
+        let mut genres = connection
+            .prepare("SELECT genre FROM game_genres WHERE user_id = ?1 AND external_id = '10000'")
+            .expect("prepare query")
+            .query_map(params![user.id], |row| row.get::<_, String>(0))
+            .expect("query genres")
+            .collect::<Result<Vec<_>, _>>()
+            .expect("decode genres");
+        genres.sort();
+        assert_eq!(genres, vec![String::from("indie"), String::from("racing")]);
+    }
+
+    #[test]
+    fn incremental_sync_renames_games_in_search() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let mut games = benchmark_library_inputs(1);
+        replace_provider_games(connection, &user.id, "steam", &games, true).expect("first sync");
+
+        games[0].name = String::from("Portal 2");
+        let summary = replace_provider_games(connection, &user.id, "steam", &games, true).expect("second sync");
+
+        assert_eq!(summary.updated, 1);
+        let matches = library_search::search_library_games(connection, &user.id, "portal", None).expect("search");
+        assert_eq!(matches.len(), 1);
+        assert!(library_search::search_library_games(connection, &user.id, "game", None)
+            .expect("search")
+            .is_empty());
+    }
+
+    #[test]
+    fn collect_steam_library_storage_sums_manifest_sizes_once_per_library() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let steamapps = dir.path().join("steamapps");
+        fs::create_dir_all(&steamapps).expect("create steamapps");
+        for (app_id, size_on_disk) in [(620, "1000"), (400, "250"), (570, "not a size")] {
+            fs::write(
+                steamapps.join(format!("appmanifest_{app_id}.acf")),
+                format!("\"AppState\"\n{{\n\t\"appid\"\t\"{app_id}\"\n\t\"SizeOnDisk\"\t\"{size_on_disk}\"\n}}\n"),
+            )
+            .expect("write manifest");
+        }
+        fs::write(steamapps.join("appmanifest_620.acf.tmp"), "partial").expect("write temp file");
+        // The root library is listed again in libraryfolders.vdf, as Steam does.
+        fs::write(
+            steamapps.join("libraryfolders.vdf"),
+            format!(
+                "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+                dir.path().display().to_string().replace('\\', "\\\\")
+            ),
+        )
+        .expect("write library folders");
+
+        let libraries = collect_steam_library_storage(dir.path().to_str());
+
+        assert_eq!(libraries.len(), 1);
+        assert_eq!(libraries[0].provider, "steam");
+        assert_eq!((libraries[0].games_bytes, libraries[0].game_count), (1250, 3));
+    }
+
+    #[test]
+    fn send_limited_request_rebuilds_posts_for_each_retry() {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind test server");
+        let request_url =
+            Url::parse(&format!("http://{}/form", listener.local_addr().expect("server address")))
+                .expect("request url");
+        let server = thread::spawn(move || {
+            let mut request_lines = Vec::new();
+            for response in [
+                "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                "HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+            ] {
+                let (mut stream, _) = listener.accept().expect("accept request");
+                stream
+                    .set_read_timeout(Some(Duration::from_secs(2)))
+                    .expect("set read timeout");
+                let mut request = Vec::new();
+                let mut chunk = [0_u8; 1024];
+                while !request.ends_with(b"itemcount=1") {
+                    match stream.read(&mut chunk) {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&chunk[..read]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request).into_owned();
+                request_lines.push((
+                    request.lines().next().unwrap_or_default().to_owned(),
+                    request.ends_with("itemcount=1"),
+                ));
+                stream.write_all(response.as_bytes()).expect("write response");
+            }
+            request_lines
+        });
+        let client = build_http_client(None).expect("client");
+
+        let response = send_limited_request(&request_url, || {
+            client.post(request_url.clone()).form(&[("itemcount", "1")])
+        })
+        .expect("request succeeds after a retry");
+
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let request_lines = server.join().expect("server thread");
+        assert_eq!(
+            request_lines,
+            vec![(String::from("POST /form HTTP/1.1"), true); 2]
+        );
+    }
+
+    #[test]
+    fn linked_steam_reauth_only_skips_the_roundtrip_for_a_linked_account() {
+        let database = test_support::test_database();
+        let linked_user = UserRow {
+            steam_id: Some(String::from("76561197960287930")),
+            ..database.user.clone()
+        };
+        let api_key = Some(" 0123456789abcdef0123456789abcdef ");
+
+        assert_eq!(
+            linked_steam_reauth(Some(&linked_user), false, api_key),
+            Some(("76561197960287930", "0123456789abcdef0123456789abcdef"))
+        );
+        assert_eq!(linked_steam_reauth(Some(&linked_user), true, api_key), None);
+        assert_eq!(linked_steam_reauth(Some(&linked_user), false, Some("  ")), None);
+        assert_eq!(linked_steam_reauth(Some(&linked_user), false, None), None);
+        assert_eq!(linked_steam_reauth(Some(&database.user), false, api_key), None);
+        assert_eq!(linked_steam_reauth(None, false, api_key), None);
+    }
+
+    #[test]
+    fn prefetch_steam_install_size_estimates_skips_fresh_cache_entries() {
+        let database = test_support::test_database();
+        let connection = &database.connection;
+        cache_steam_app_size_estimate(connection, 620, Some(12_000_000_000)).expect("cache size");
+        cache_steam_app_size_estimate(connection, 400, None).expect("cache unknown size");
+
+        let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_SIZE_ESTIMATE_CACHE_TTL_HOURS);
+        assert_eq!(
+            find_cached_steam_app_size_estimate(connection, 620, stale_before).expect("cached"),
+            Some(Some(12_000_000_000))
+        );
+        assert_eq!(
+            find_cached_steam_app_size_estimate(connection, 400, stale_before).expect("cached"),
+            Some(None)
+        );
+        assert_eq!(find_cached_steam_app_size_estimate(connection, 70, stale_before).expect("missing"), None);
+        let after_fetch = Utc::now() + ChronoDuration::minutes(1);
+        assert_eq!(find_cached_steam_app_size_estimate(connection, 620, after_fetch).expect("stale"), None);
+
+        // Every app is cached, so no batch is requested and no progress is reported.
+        let client = build_http_client(None).expect("http client");
+        let progress_events = std::cell::Cell::new(0);
+        prefetch_steam_install_size_estimates(
+            connection,
+            &client,
+            &[620, 400, 620],
+            &|_| progress_events.set(progress_events.get() + 1),
+            &AtomicBool::new(false),
+        )
+        .expect("prefetch");
+        assert_eq!(progress_events.get(), 0);
+    }
+
+    #[test]
+    fn parse_steam_install_size_from_app_data_takes_the_largest_platform() {
+        let linux_requirements = serde_json::json!({
+            "minimum": "<strong>Storage:</strong> 25 GB available space"
+        });
+        let data = serde_json::json!({
+            "pc_requirements": { "minimum": "<strong>Storage:</strong> 20 GB available space" },
+            "mac_requirements": [],
+            "linux_requirements": linux_requirements,
+        });
+
+        assert!(parse_steam_install_size_from_requirements_value(&linux_requirements).is_some());
+        assert_eq!(
+            parse_steam_install_size_from_app_data(&data),
+            parse_steam_install_size_from_requirements_value(&linux_requirements)
+        );
+        assert_eq!(parse_steam_install_size_from_app_data(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn parse_steam_review_summary_skips_apps_without_reviews() {
+        let payload = serde_json::json!({
+            "success": 1,
+            "query_summary": {
+                "review_score_desc": " Very Positive ",
+                "total_positive": 950,
+                "total_negative": 50,
+                "total_reviews": 1000
+            }
+        });
+        let summary = parse_steam_review_summary(&payload).expect("review summary");
+        assert_eq!(summary.score_description, "Very Positive");
+        assert_eq!(summary.positive_percent(), Some(95));
+
+        let no_reviews = serde_json::json!({
+            "query_summary": { "review_score_desc": "No user reviews", "total_positive": 0, "total_reviews": 0 }
+        });
+        assert_eq!(parse_steam_review_summary(&no_reviews), None);
+        assert_eq!(parse_steam_review_summary(&serde_json::json!({ "success": 2 })), None);
+    }
+
+    #[test]
+    fn list_games_by_user_surfaces_cached_review_scores() {
+        let database = test_support::test_database();
+        database.insert_game("steam", "620", "Portal 2");
+        database.insert_game("steam", "400", "Portal");
+        let metadata = SteamAppStoreMetadata {
+            metacritic_score: Some(95),
+            recommendations_total: Some(300_000),
+            ..parse_steam_app_store_metadata(Some(&serde_json::json!({ "type": "game" })))
+        };
+        cache_steam_app_metadata(&database.connection, 620, &metadata).expect("cache metadata");
+        let summary = SteamReviewSummary {
+            score_description: String::from("Overwhelmingly Positive"),
+            total_positive: 990,
+            total_reviews: 1000,
+        };
+        cache_steam_review_summary(&database.connection, 620, &summary).expect("cache reviews");
+        let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_REVIEW_SUMMARY_CACHE_TTL_HOURS);
+        assert_eq!(
+            find_cached_steam_review_summary(&database.connection, 620, stale_before).expect("cached"),
+            Some(summary)
+        );
+
+        let games = list_games_by_user(
+            &database.connection,
+            &database.user.id,
+            &library_lookup_cache::LibraryLookupCache::default(),
+        )
+        .expect("list games");
+        let portal_2 = games.iter().find(|game| game.external_id == "620").expect("Portal 2");
+        assert_eq!(portal_2.metacritic_score, Some(95));
+        assert_eq!(portal_2.recommendations_total, Some(300_000));
+        assert_eq!(portal_2.review_score_description.as_deref(), Some("Overwhelmingly Positive"));
+        assert_eq!(portal_2.review_positive_percent, Some(99));
+        assert_eq!(portal_2.review_total, Some(1000));
+        let portal = games.iter().find(|game| game.external_id == "400").expect("Portal");
+        assert_eq!((portal.metacritic_score, portal.review_positive_percent), (None, None));
+    }
+
+    #[test]
+    fn parse_steam_controller_support_prefers_full_support() {
+        let categories = |categories: serde_json::Value| serde_json::json!({ "categories": categories });
+        assert_eq!(
+            parse_steam_controller_support(Some(&categories(serde_json::json!([
+                { "id": 18, "description": "Partial Controller Support" },
+                { "id": 28, "description": "Full controller support" }
+            ])))),
+            Some(String::from("Full"))
+        );
+        assert_eq!(
+            parse_steam_controller_support(Some(&categories(serde_json::json!([
+                { "description": "Full Controller Support" }
+            ])))),
+            Some(String::from("Full"))
+        );
+        assert_eq!(
+            parse_steam_controller_support(Some(&categories(serde_json::json!([{ "id": 2, "description": "Single-player" }])))),
+            None
+        );
+        assert_eq!(parse_steam_controller_support(Some(&serde_json::json!({ "type": "game" }))), None);
+        assert_eq!(parse_steam_controller_support(None), None);
+    }
+
+    #[test]
+    fn list_games_by_user_flags_controller_support_from_cached_metadata() {
+        let database = test_support::test_database();
+        database.insert_game("steam", "620", "Portal 2");
+        database.insert_game("steam", "400", "Portal");
+        let metadata = parse_steam_app_store_metadata(Some(&serde_json::json!({
+            "type": "game",
+            "categories": [{ "id": 28, "description": "Full controller support" }]
+        })));
+        cache_steam_app_metadata(&database.connection, 620, &metadata).expect("cache metadata");
+
+        let games = list_games_by_user(
+            &database.connection,
+            &database.user.id,
+            &library_lookup_cache::LibraryLookupCache::default(),
+        )
+        .expect("list games");
+        let controller_support = |external_id: &str| {
+            games
+                .iter()
+                .find(|game| game.external_id == external_id)
+                .and_then(|game| game.controller_support.clone())
+        };
+        assert_eq!(controller_support("620").as_deref(), Some("Full"));
+        assert_eq!(controller_support("400"), None);
+    }
+
+    #[test]
+    fn import_steam_collections_for_user_preview_writes_nothing_and_replace_keeps_other_games() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+        database.insert_game("steam", "400", "Portal");
+        database.insert_game(library_import::CUSTOM_PROVIDER, "doom", "Doom");
+        let collection = create_user_collection(connection, &user.id, "Co-op").expect("create collection");
+        for (provider, external_id) in [("steam", "400"), (library_import::CUSTOM_PROVIDER, "doom")] {
+            add_game_to_collection_membership(connection, &user.id, &collection.id, provider, external_id)
+                .expect("add membership");
+        }
+        let steam_collections = || {
+            HashMap::from([
+                (String::from("620"), HashSet::from([String::from("Co-op"), String::from("Puzzle")])),
+                (String::from("999"), HashSet::from([String::from("Puzzle")])),
+            ])
+        };
+
+        let preview = import_steam_collections_for_user(
+            connection,
+            &user.id,
+            steam_collections(),
+            SteamCollectionsImportMode::Preview,
+            false,
+        )
+        .expect("preview import");
+        assert_eq!((preview.collections_created, preview.skipped_games), (1, 1));
+        assert!(find_collection_id_by_name(connection, &user.id, "Puzzle").expect("find collection").is_none());
+
+        import_steam_collections_for_user(
+            connection,
+            &user.id,
+            steam_collections(),
+            SteamCollectionsImportMode::Replace,
+            false,
+        )
+        .expect("replace import");
+        assert_eq!(
+            list_collection_provider_external_ids(connection, &user.id, &collection.id, "steam").expect("steam members"),
+            vec![String::from("620")]
+        );
+        // Replace only mirrors Steam games; games from other providers stay in the collection.
+        assert_eq!(
+            list_collection_provider_external_ids(connection, &user.id, &collection.id, library_import::CUSTOM_PROVIDER)
+                .expect("custom members"),
+            vec![String::from("doom")]
+        );
+    }
+
+    #[test]
+    fn import_steam_collections_for_user_links_favorites_only_when_asked() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+        database.insert_game("steam", "400", "Portal");
+        let steam_favorites = || {
+            HashMap::from([
+                (String::from("620"), HashSet::from([String::from("favorite")])),
+                (String::from("400"), HashSet::from([String::from("favorite")])),
+            ])
+        };
+        let favorite_count = |connection: &Connection| {
+            connection
+                .query_row("SELECT COUNT(*) FROM game_favorites WHERE user_id = ?1", params![user.id], |row| {
+                    row.get::<_, i64>(0)
+                })
+                .expect("count favorites")
+        };
+        upsert_game_favorite(connection, &user.id, "steam", "620").expect("favorite Portal 2");
+
+        let preview = import_steam_collections_for_user(
+            connection,
+            &user.id,
+            steam_favorites(),
+            SteamCollectionsImportMode::Preview,
+            true,
+        )
+        .expect("preview import");
+        assert_eq!(preview.favorites_linked, 1);
+        assert_eq!(favorite_count(connection), 1);
+
+        // Games that already are favorites are not counted again.
+        let linked = import_steam_collections_for_user(
+            connection,
+            &user.id,
+            steam_favorites(),
+            SteamCollectionsImportMode::Merge,
+            true,
+        )
+        .expect("favorites import");
+        assert_eq!((linked.favorites_linked, linked.memberships_added), (1, 0));
+        assert_eq!(favorite_count(connection), 2);
+
+        let as_collection = import_steam_collections_for_user(
+            connection,
+            &user.id,
+            steam_favorites(),
+            SteamCollectionsImportMode::Merge,
+            false,
+        )
+        .expect("collection import");
+        assert_eq!((as_collection.favorites_linked, as_collection.memberships_added), (0, 2));
+        assert!(find_collection_id_by_name(connection, &user.id, "favorite").expect("find collection").is_some());
+    }
+
+    #[test]
+    fn normalize_collection_accent_color_accepts_only_hex_colors() {
+        assert_eq!(normalize_collection_accent_color(" #ABCDEF "), Ok(String::from("#abcdef")));
+        for color in ["abcdef", "#12", "#1234567", "#ggg", "#", ""] {
+            assert!(normalize_collection_accent_color(color).is_err(), "{color} was accepted");
+        }
+    }
+
+    #[test]
+    fn collection_cover_image_survives_bad_uploads_and_goes_with_the_collection() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let collection = create_user_collection(connection, &user.id, "Play next").expect("create collection");
+        let other_user = create_user(connection, "friend@example.com", "hash", None).expect("create user");
+        let cover_file = database.dir.path().join("shelf.png");
+        fs::write(&cover_file, b"\x89PNG\r\n\x1a\nrest").expect("write png");
+        let text_file = database.dir.path().join("notes.png");
+        fs::write(&text_file, "not an image").expect("write text file");
+
+        let cover_image_path =
+            set_user_collection_cover_image(connection, &database.db_path, &user.id, &collection.id, Some(&cover_file))
+                .expect("set cover image")
+                .cover_image_path
+                .expect("cover image path");
+        assert!(
+            set_user_collection_cover_image(connection, &database.db_path, &user.id, &collection.id, Some(&text_file))
+                .is_err()
+        );
+        assert!(set_user_collection_cover_image(
+            connection,
+            &database.db_path,
+            &other_user.id,
+            &collection.id,
+            Some(&cover_file)
+        )
+        .is_err());
+        assert!(set_user_collection_accent_color(connection, &other_user.id, &collection.id, Some("#fff")).is_err());
+        let unchanged = get_user_collection(connection, &user.id, &collection.id).expect("load collection");
+        assert_eq!(unchanged.cover_image_path.as_deref(), Some(cover_image_path.as_str()));
+        assert!(Path::new(&cover_image_path).is_file());
+
+        let cleared = set_user_collection_accent_color(connection, &user.id, &collection.id, Some("  "))
+            .expect("clear accent color");
+        assert_eq!(cleared.accent_color, None);
+        delete_user_collection(connection, &user.id, &collection.id).expect("delete collection");
+        assert!(!Path::new(&cover_image_path).exists());
+    }
+
+    #[test]
+    fn set_collection_game_positions_clears_with_an_empty_order_and_forgets_removed_games() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let collection = create_user_collection(connection, &user.id, "Play next").expect("create collection");
+        for external_id in ["620", "400"] {
+            database.insert_game("steam", external_id, external_id);
+            add_game_to_collection_membership(connection, &user.id, &collection.id, "steam", external_id)
+                .expect("add membership");
+        }
+        let game = |external_id: &str| (String::from("steam"), external_id.to_owned());
+        set_collection_game_positions(connection, &user.id, &collection.id, &[game("400"), game("620")])
+            .expect("set order");
+
+        // A game taken out and added back returns unordered.
+        remove_game_from_collection_membership(connection, &user.id, &collection.id, "steam", "400")
+            .expect("remove membership");
+        add_game_to_collection_membership(connection, &user.id, &collection.id, "steam", "400")
+            .expect("add membership");
+        let positions = load_collection_positions_by_game(connection, &user.id).expect("load positions");
+        assert_eq!(positions.keys().collect::<Vec<_>>(), vec!["steam:620"]);
+
+        set_collection_game_positions(connection, &user.id, &collection.id, &[]).expect("clear order");
+        assert!(load_collection_positions_by_game(connection, &user.id).expect("load positions").is_empty());
+
+        let other_user = create_user(connection, "friend@example.com", "hash", None).expect("create user");
+        assert!(set_collection_game_positions(connection, &other_user.id, &collection.id, &[game("620")]).is_err());
+    }
+
+    #[test]
+    fn launcher_uri_command_passes_query_strings_through_unsplit() {
+        let uri = "origin2://game/launch?offerIds=Origin.OFR.50.0001&autoDownload=1";
+
+        assert_eq!(
+            launcher_uri_command(uri, "windows"),
+            ("rundll32", vec!["url.dll,FileProtocolHandler", uri])
+        );
+        assert_eq!(launcher_uri_command(uri, "macos"), ("open", vec![uri]));
+        assert_eq!(launcher_uri_command(uri, "linux"), ("xdg-open", vec![uri]));
+    }
+}
+
+fn fetch_steam_store_user_tags(client: &Client, app_id: u64) -> Result<Vec<String>, String> {
+    let mut request_url = Url::parse(&format!("{STEAM_STORE_APP_ENDPOINT}/{app_id}/"))
+        .map_err(|error| format!("Failed to parse Steam Store endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("l", "english")
+        .append_pair("cc", "us");
+
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam Store tags request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Steam Store tags request failed with status {}",
+            response.status()
+        ));
+    }
+
+    let html = response
+        .text()
+        .map_err(|error| format!("Failed to decode Steam Store tags response: {error}"))?;
+    Ok(parse_steam_store_user_tags_from_html(&html))
+}
+
+fn parse_steam_store_user_tags_from_html(html: &str) -> Vec<String> {
+    let tag_regex = match Regex::new(
+        r#"(?is)<a[^>]*\bclass\s*=\s*"[^"]*\bapp_tag\b[^"]*"[^>]*>(.*?)</a>"#,
+    ) {
+        Ok(regex) => regex,
+        Err(_) => return Vec::new(),
+    };
+    let strip_markup_regex = Regex::new(r"(?is)<[^>]+>").ok();
+    let mut tags = Vec::new();
+    let mut seen = HashSet::new();
 
     for captures in tag_regex.captures_iter(html) {
         let Some(raw_text) = captures.get(1).map(|value| value.as_str()) else {
@@ -2555,9 +5719,7 @@ fn fetch_steam_supported_languages(
         .append_pair("appids", &app_id.to_string())
         .append_pair("l", "english");
 
-    let response = client
-        .get(request_url)
-        .send()
+    let response = send_steam_get_request(client, request_url)
         .map_err(|error| format!("Steam app details request failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
@@ -2664,9 +5826,7 @@ fn fetch_steam_install_size_estimate_from_store(
         .append_pair("l", "english")
         .append_pair("cc", "us");
 
-    let response = client
-        .get(request_url)
-        .send()
+    let response = send_steam_get_request(client, request_url)
         .map_err(|error| format!("Steam app details request failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
@@ -2733,9 +5893,7 @@ fn fetch_steam_app_linux_platform_support_from_store(
         .append_pair("l", "english")
         .append_pair("cc", "us");
 
-    let response = client
-        .get(request_url)
-        .send()
+    let response = send_steam_get_request(client, request_url)
         .map_err(|error| format!("Steam app details request failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
@@ -3015,9 +6173,7 @@ fn fetch_steam_game_version_betas(
         .append_pair("key", api_key)
         .append_pair("appid", &app_id.to_string());
 
-    let response = client
-        .get(request_url)
-        .send()
+    let response = send_steam_get_request(client, request_url)
         .map_err(|error| format!("Steam betas request failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
@@ -3044,9 +6200,7 @@ fn fetch_steam_game_version_betas_from_store(
         .append_pair("appids", &app_id.to_string())
         .append_pair("l", "english");
 
-    let response = client
-        .get(request_url)
-        .send()
+    let response = send_steam_get_request(client, request_url)
         .map_err(|error| format!("Steam app details request failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
@@ -3076,9 +6230,7 @@ fn fetch_steam_beta_access_code_validation(
         .append_pair("appid", &app_id.to_string())
         .append_pair("betapassword", access_code);
 
-    let response = client
-        .get(request_url)
-        .send()
+    let response = send_steam_get_request(client, request_url)
         .map_err(|error| format!("Steam beta code check failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
@@ -3196,7 +6348,7 @@ fn parse_steam_beta_access_code_validation_payload(
     let Some(response_object) = response_object else {
         return GameBetaAccessCodeValidationResponse {
             valid: false,
-            message: String::from("Could not parse Steam beta code check response."),
+            message: i18n::localized("beta_code_unparsable", &[]),
             branch_id: None,
             branch_name: None,
         };
@@ -3219,7 +6371,7 @@ fn parse_steam_beta_access_code_validation_payload(
     if !valid {
         return GameBetaAccessCodeValidationResponse {
             valid: false,
-            message: String::from("Code is invalid or no beta branch is associated with it."),
+            message: i18n::localized("beta_code_rejected", &[]),
             branch_id: None,
             branch_name: None,
         };
@@ -3229,9 +6381,9 @@ fn parse_steam_beta_access_code_validation_payload(
     GameBetaAccessCodeValidationResponse {
         valid: true,
         message: if let Some(branch) = branch_name.as_deref() {
-            format!("Code accepted. Branch unlocked: {branch}.")
+            i18n::localized("beta_code_accepted_branch", &[("branch", branch.to_owned())])
         } else {
-            String::from("Code accepted.")
+            i18n::localized("beta_code_accepted", &[])
         },
         branch_id,
         branch_name,
@@ -3511,48 +6663,271 @@ fn parse_steam_supported_languages(raw_value: &str) -> Vec<String> {
         }
     }
 
-    normalize_language_list(&languages)
+    normalize_language_list(&languages)
+}
+
+fn normalize_language_list(raw_languages: &[String]) -> Vec<String> {
+    let mut normalized_languages = Vec::new();
+    let mut seen = HashSet::new();
+
+    for language in raw_languages {
+        let trimmed = language.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        let dedupe_key = trimmed.to_ascii_lowercase();
+        if seen.insert(dedupe_key) {
+            normalized_languages.push(trimmed.to_owned());
+        }
+    }
+
+    normalized_languages
+}
+
+fn decode_basic_html_entities(value: &str) -> String {
+    value
+        .replace("&amp;", "&")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+}
+
+fn steam_community_item_class_label(item_class: u64) -> &'static str {
+    match item_class {
+        3 => "profile_background",
+        4 => "emoticon",
+        8 => "profile_modifier",
+        11 => "sticker",
+        12 => "chat_effect",
+        13 => "mini_profile_background",
+        14 => "avatar_frame",
+        15 => "animated_avatar",
+        16 => "keyboard_skin",
+        17 => "startup_movie",
+        _ => "other",
+    }
+}
+
+fn parse_json_u64_value(value: Option<&serde_json::Value>) -> Option<u64> {
+    value
+        .and_then(parse_json_text_value)
+        .and_then(|text| text.parse::<u64>().ok())
+}
+
+fn fetch_steam_points_summary(
+    client: &Client,
+    api_key: &str,
+    steam_id: &str,
+) -> Result<SteamPointsBalanceResponse, String> {
+    let mut request_url = Url::parse(STEAM_LOYALTY_SUMMARY_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam points summary endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("key", api_key)
+        .append_pair("steamid", steam_id);
+
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam points summary request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Steam points summary request failed with status {}",
+            response.status()
+        ));
+    }
+
+    let payload = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam points summary response: {error}"))?;
+    let summary = payload
+        .get("response")
+        .and_then(|value| value.get("summary"))
+        .ok_or_else(|| String::from("Steam points summary response did not include a summary"))?;
+
+    Ok(SteamPointsBalanceResponse {
+        points: parse_json_u64_value(summary.get("points")).unwrap_or(0),
+        points_earned: parse_json_u64_value(summary.get("points_earned")).unwrap_or(0),
+        points_spent: parse_json_u64_value(summary.get("points_spent")).unwrap_or(0),
+        warning: None,
+        stale: false,
+    })
+}
+
+fn fetch_steam_points_shop_items(
+    client: &Client,
+    app_id: u64,
+    api_key: Option<&str>,
+) -> Result<Vec<SteamPointsShopItemResponse>, String> {
+    let mut items = Vec::new();
+    let mut cursor: Option<String> = None;
+
+    for _ in 0..STEAM_POINTS_SHOP_MAX_PAGES {
+        let mut request_url = Url::parse(STEAM_LOYALTY_REWARD_ITEMS_ENDPOINT)
+            .map_err(|error| format!("Failed to parse Steam points shop endpoint: {error}"))?;
+        {
+            let mut query = request_url.query_pairs_mut();
+            query.append_pair("appids[0]", &app_id.to_string());
+            if let Some(api_key) = api_key {
+                query.append_pair("key", api_key);
+            }
+            if let Some(cursor) = cursor.as_deref() {
+                query.append_pair("cursor", cursor);
+            }
+        }
+
+        let response = send_steam_get_request(client, request_url)
+            .map_err(|error| format!("Steam points shop request failed: {error}"))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Steam points shop request failed with status {}",
+                response.status()
+            ));
+        }
+
+        let payload = response
+            .json::<serde_json::Value>()
+            .map_err(|error| format!("Failed to decode Steam points shop response: {error}"))?;
+        let (page_items, next_cursor) = parse_steam_points_shop_items_payload(&payload, app_id);
+        let page_was_empty = page_items.is_empty();
+        items.extend(page_items);
+
+        match next_cursor {
+            Some(next) if !page_was_empty && cursor.as_deref() != Some(next.as_str()) => {
+                cursor = Some(next);
+            }
+            _ => break,
+        }
+    }
+
+    Ok(items)
+}
+
+fn parse_steam_points_shop_items_payload(
+    payload: &serde_json::Value,
+    app_id: u64,
+) -> (Vec<SteamPointsShopItemResponse>, Option<String>) {
+    let Some(response) = payload.get("response") else {
+        return (Vec::new(), None);
+    };
+
+    let mut items = Vec::new();
+    for definition in response
+        .get("definitions")
+        .and_then(serde_json::Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        let Some(def_id) = parse_json_u64_value(definition.get("defid")) else {
+            continue;
+        };
+        if parse_json_u64_value(definition.get("appid")).is_some_and(|value| value != app_id) {
+            continue;
+        }
+
+        let item_data = definition.get("community_item_data");
+        let name = item_data
+            .and_then(|data| data.get("item_title").or_else(|| data.get("item_name")))
+            .and_then(parse_json_text_value)
+            .unwrap_or_else(|| format!("Item {def_id}"));
+        let image_url = item_data
+            .and_then(|data| data.get("item_image_small").or_else(|| data.get("item_image_large")))
+            .and_then(parse_json_text_value)
+            .map(|file_name| {
+                if file_name.starts_with("http") {
+                    file_name
+                } else {
+                    format!("{STEAM_COMMUNITY_ITEM_IMAGE_BASE_URL}/{app_id}/{file_name}")
+                }
+            });
+        let item_class = parse_json_u64_value(definition.get("community_item_class")).unwrap_or(0);
+
+        items.push(SteamPointsShopItemResponse {
+            def_id,
+            item_class: steam_community_item_class_label(item_class).to_owned(),
+            name,
+            point_cost: parse_json_u64_value(definition.get("point_cost")).unwrap_or(0),
+            image_url,
+            animated: parse_json_bool(item_data.and_then(|data| data.get("animated")))
+                || item_class == 15,
+        });
+    }
+
+    let next_cursor = response
+        .get("next_cursor")
+        .and_then(parse_json_text_value);
+    (items, next_cursor)
 }
 
-fn normalize_language_list(raw_languages: &[String]) -> Vec<String> {
-    let mut normalized_languages = Vec::new();
-    let mut seen = HashSet::new();
+fn find_cached_steam_points_shop_items(
+    connection: &Connection,
+    app_id: u64,
+) -> Result<Option<(Vec<SteamPointsShopItemResponse>, chrono::DateTime<Utc>)>, String> {
+    let cached = connection
+        .query_row(
+            "SELECT items_json, fetched_at FROM steam_app_points_shop_items WHERE app_id = ?1",
+            params![app_id.to_string()],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query cached Steam points shop items: {error}"))?;
 
-    for language in raw_languages {
-        let trimmed = language.trim();
-        if trimmed.is_empty() {
-            continue;
-        }
+    let Some((items_json, fetched_at)) = cached else {
+        return Ok(None);
+    };
 
-        let dedupe_key = trimmed.to_ascii_lowercase();
-        if seen.insert(dedupe_key) {
-            normalized_languages.push(trimmed.to_owned());
-        }
-    }
+    let fetched_at = match chrono::DateTime::parse_from_rfc3339(&fetched_at) {
+        Ok(timestamp) => timestamp.with_timezone(&Utc),
+        Err(_) => return Ok(None),
+    };
+    let items = serde_json::from_str::<Vec<SteamPointsShopItemResponse>>(&items_json)
+        .map_err(|error| format!("Failed to decode cached Steam points shop items: {error}"))?;
 
-    normalized_languages
+    Ok(Some((items, fetched_at)))
 }
 
-fn decode_basic_html_entities(value: &str) -> String {
-    value
-        .replace("&amp;", "&")
-        .replace("&quot;", "\"")
-        .replace("&#39;", "'")
-        .replace("&apos;", "'")
-        .replace("&nbsp;", " ")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
+fn cache_steam_points_shop_items(
+    connection: &Connection,
+    app_id: u64,
+    items: &[SteamPointsShopItemResponse],
+) -> Result<(), String> {
+    let items_json = serde_json::to_string(items)
+        .map_err(|error| format!("Failed to encode Steam points shop cache entry: {error}"))?;
+
+    connection
+        .execute(
+            "
+            INSERT INTO steam_app_points_shop_items (app_id, items_json, fetched_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(app_id) DO UPDATE SET
+              items_json = excluded.items_json,
+              fetched_at = excluded.fetched_at
+            ",
+            params![app_id.to_string(), items_json, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to cache Steam points shop items: {error}"))?;
+
+    Ok(())
 }
 
 fn normalize_steam_app_type(value: &str) -> String {
     value.trim().to_ascii_lowercase()
 }
 
+/// Kinds that aren't playable on their own; the library hides them unless the user opts in.
+const NON_GAME_KINDS: [&str; 4] = ["music", "tool", "application", "video"];
+
 fn steam_kind_from_app_type(app_type: &str) -> &'static str {
     match normalize_steam_app_type(app_type).as_str() {
         "game" => "game",
         "demo" => "demo",
         "dlc" => "dlc",
+        "music" => "music",
+        "tool" => "tool",
+        "application" => "application",
+        "video" | "series" | "episode" => "video",
         _ => "unknown",
     }
 }
@@ -3606,6 +6981,7 @@ fn map_steam_game(
         name,
         kind,
         playtime_minutes: game.playtime_forever.unwrap_or(0),
+        playtime_2weeks_minutes: game.playtime_2weeks.unwrap_or(0),
         installed,
         artwork_url,
         last_synced_at: Utc::now().to_rfc3339(),
@@ -3638,11 +7014,14 @@ fn classify_steam_game_kind(name: &str) -> &'static str {
         || normalized.contains("expansion pass")
         || normalized.contains("add-on")
         || normalized.contains("add on")
-        || normalized.contains("soundtrack")
     {
         return "dlc";
     }
 
+    if normalized.contains("soundtrack") || contains_word("ost") {
+        return "music";
+    }
+
     "game"
 }
 
@@ -3651,95 +7030,173 @@ fn replace_provider_games(
     user_id: &str,
     provider: &str,
     games: &[LibraryGameInput],
-) -> Result<(), String> {
-    let incoming_external_ids = games
-        .iter()
-        .map(|game| game.external_id.clone())
-        .collect::<HashSet<_>>();
+    incremental: bool,
+) -> Result<ProviderGamesSyncSummary, String> {
     let mut existing_statement = connection
-        .prepare("SELECT external_id FROM games WHERE user_id = ?1 AND provider = ?2")
+        .prepare(
+            "
+            SELECT external_id, name, kind, playtime_minutes, installed, artwork_url, last_played_at, playtime_2weeks_minutes
+            FROM games
+            WHERE user_id = ?1 AND provider = ?2
+            ",
+        )
         .map_err(|error| format!("Failed to prepare existing provider game query: {error}"))?;
-    let existing_external_ids = existing_statement
-        .query_map(params![user_id, provider], |row| row.get::<_, String>(0))
+    let existing_games = existing_statement
+        .query_map(params![user_id, provider], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                (
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, i64>(3)?,
+                    row.get::<_, i64>(4)? != 0,
+                    row.get::<_, Option<String>>(5)?,
+                    row.get::<_, Option<String>>(6)?,
+                    row.get::<_, i64>(7)?,
+                ),
+            ))
+        })
         .map_err(|error| format!("Failed to query existing provider games: {error}"))?
-        .collect::<Result<Vec<_>, _>>()
+        .collect::<Result<HashMap<_, _>, _>>()
         .map_err(|error| format!("Failed to decode existing provider games: {error}"))?;
-    let mut delete = connection
-        .prepare("DELETE FROM games WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3")
-        .map_err(|error| format!("Failed to prepare stale game cleanup statement: {error}"))?;
-    for existing_external_id in existing_external_ids {
-        if incoming_external_ids.contains(&existing_external_id) {
-            continue;
-        }
 
-        delete
-            .execute(params![user_id, provider, existing_external_id])
-            .map_err(|error| format!("Failed to delete stale provider game: {error}"))?;
+    let mut summary = ProviderGamesSyncSummary {
+        total: games.len(),
+        ..ProviderGamesSyncSummary::default()
+    };
+
+    // Steam occasionally answers with an empty game list (private profile hiccups, API
+    // outages). Never treat that as "the user owns nothing" and wipe the library.
+    if games.is_empty() && !existing_games.is_empty() {
+        eprintln!(
+            "Provider {provider} returned no games for user {user_id}; keeping {} existing rows.",
+            existing_games.len()
+        );
+        summary.total = existing_games.len();
+        return Ok(summary);
     }
 
-    let mut insert = connection
-        .prepare(
-            "
-                        INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, artwork_url, last_synced_at, last_played_at)
-                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+    let incoming_external_ids = games
+        .iter()
+        .map(|game| game.external_id.clone())
+        .collect::<HashSet<_>>();
+    // A single transaction keeps a failed sync from leaving the library half replaced, and spares
+    // SQLite a journal commit per row, which dominated syncs of large libraries.
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| format!("Failed to start provider game sync transaction: {error}"))?;
+    {
+        let mut delete = transaction
+            .prepare_cached("DELETE FROM games WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3")
+            .map_err(|error| format!("Failed to prepare stale game cleanup statement: {error}"))?;
+        for existing_external_id in existing_games.keys() {
+            if incoming_external_ids.contains(existing_external_id) {
+                continue;
+            }
+
+            delete
+                .execute(params![user_id, provider, existing_external_id])
+                .map_err(|error| format!("Failed to delete stale provider game: {error}"))?;
+            summary.removed += 1;
+        }
+
+        let mut insert = transaction
+            .prepare_cached(
+                "
+                        INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, artwork_url, last_synced_at, last_played_at, added_at, playtime_2weeks_minutes)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?9, ?11)
                         ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
-                            name = excluded.name,
                             kind = excluded.kind,
                             playtime_minutes = excluded.playtime_minutes,
                             installed = excluded.installed,
                             artwork_url = excluded.artwork_url,
                             last_synced_at = excluded.last_synced_at,
-                            last_played_at = excluded.last_played_at
-            ",
-        )
-        .map_err(|error| format!("Failed to prepare game insert statement: {error}"))?;
-
-    for game in games {
-            insert
-            .execute(params![
-                user_id,
-                provider,
-                game.external_id,
-                game.name,
-                game.kind,
-                game.playtime_minutes,
-                if game.installed { 1 } else { 0 },
-                game.artwork_url,
-                game.last_synced_at,
-                game.last_played_at
-            ])
-            .map_err(|error| format!("Failed to persist synced game: {error}"))?;
-        // Persist derived genres for this game from cached Steam store tags (if any).
-        // Delete existing genre rows for freshness, then insert new ones.
-        let mut delete_stmt = connection
-            .prepare(
-                "DELETE FROM game_genres WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+                            last_played_at = excluded.last_played_at,
+                            playtime_2weeks_minutes = excluded.playtime_2weeks_minutes
+                ",
             )
+            .map_err(|error| format!("Failed to prepare game insert statement: {error}"))?;
+        // Renames are written on their own: any write to `name` reindexes the game for search.
+        let mut rename = transaction
+            .prepare_cached("UPDATE games SET name = ?4 WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3")
+            .map_err(|error| format!("Failed to prepare game rename statement: {error}"))?;
+        let mut touch = transaction
+            .prepare_cached("UPDATE games SET last_synced_at = ?4 WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3")
+            .map_err(|error| format!("Failed to prepare game sync timestamp statement: {error}"))?;
+        let mut delete_genres = transaction
+            .prepare_cached("DELETE FROM game_genres WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3")
             .map_err(|error| format!("Failed to prepare genre delete statement: {error}"))?;
-        delete_stmt
-            .execute(params![user_id, provider, game.external_id])
-            .map_err(|error| format!("Failed to delete old genres: {error}"))?;
-
-        // Look up cached Steam tags (if provider is steam) and map to genres.
-        if provider.eq_ignore_ascii_case("steam") {
-            let mut tags_stmt = connection
-                .prepare("SELECT tags_json FROM steam_app_store_tags WHERE app_id = ?1")
-                .map_err(|error| format!("Failed to prepare steam tags lookup: {error}"))?;
-            let tag_row = tags_stmt
-                .query_row(params![game.external_id], |row| row.get::<_, String>(0))
-                .optional()
-                .map_err(|error| format!("Failed to query steam tags: {error}"))?;
-            if let Some(tags_json) = tag_row {
-                let parsed_tags = serde_json::from_str::<Vec<String>>(&tags_json).unwrap_or_default();
-                let normalized_tags = normalize_steam_store_tags(&parsed_tags);
-                let mapped_genres = map_steam_tags_to_genres(&normalized_tags);
-                if !mapped_genres.is_empty() {
-                    let mut insert_genre = connection
-                        .prepare(
-                            "INSERT INTO game_genres (user_id, provider, external_id, genre) VALUES (?1, ?2, ?3, ?4)",
-                        )
-                        .map_err(|error| format!("Failed to prepare genre insert statement: {error}"))?;
-                    for genre in mapped_genres {
+        let mut select_tags = transaction
+            .prepare_cached("SELECT tags_json FROM steam_app_store_tags WHERE app_id = ?1")
+            .map_err(|error| format!("Failed to prepare steam tags lookup: {error}"))?;
+        let mut insert_genre = transaction
+            .prepare_cached("INSERT INTO game_genres (user_id, provider, external_id, genre) VALUES (?1, ?2, ?3, ?4)")
+            .map_err(|error| format!("Failed to prepare genre insert statement: {error}"))?;
+
+        for game in games {
+            let existing_game = existing_games.get(&game.external_id);
+            let unchanged = match existing_game {
+                None => {
+                    summary.added += 1;
+                    false
+                }
+                Some((name, kind, playtime_minutes, installed, artwork_url, last_played_at, playtime_2weeks_minutes)) => {
+                    let unchanged = *name == game.name
+                        && *kind == game.kind
+                        && *playtime_minutes == game.playtime_minutes
+                        && *installed == game.installed
+                        && *artwork_url == game.artwork_url
+                        && *last_played_at == game.last_played_at
+                        && *playtime_2weeks_minutes == game.playtime_2weeks_minutes;
+                    if !unchanged {
+                        summary.updated += 1;
+                    }
+                    unchanged
+                }
+            };
+
+            if unchanged && incremental {
+                touch
+                    .execute(params![user_id, provider, game.external_id, game.last_synced_at])
+                    .map_err(|error| format!("Failed to update game sync timestamp: {error}"))?;
+            } else {
+                insert
+                    .execute(params![
+                        user_id,
+                        provider,
+                        game.external_id,
+                        game.name,
+                        game.kind,
+                        game.playtime_minutes,
+                        if game.installed { 1 } else { 0 },
+                        game.artwork_url,
+                        game.last_synced_at,
+                        game.last_played_at,
+                        game.playtime_2weeks_minutes
+                    ])
+                    .map_err(|error| format!("Failed to persist synced game: {error}"))?;
+            }
+            if existing_game.is_some_and(|(name, ..)| *name != game.name) {
+                rename
+                    .execute(params![user_id, provider, game.external_id, game.name])
+                    .map_err(|error| format!("Failed to rename synced game: {error}"))?;
+            }
+            // Persist derived genres for this game from cached Steam store tags (if any).
+            // Delete existing genre rows for freshness, then insert new ones.
+            delete_genres
+                .execute(params![user_id, provider, game.external_id])
+                .map_err(|error| format!("Failed to delete old genres: {error}"))?;
+
+            // Look up cached Steam tags (if provider is steam) and map to genres.
+            if provider.eq_ignore_ascii_case("steam") {
+                let tag_row = select_tags
+                    .query_row(params![game.external_id], |row| row.get::<_, String>(0))
+                    .optional()
+                    .map_err(|error| format!("Failed to query steam tags: {error}"))?;
+                if let Some(tags_json) = tag_row {
+                    let parsed_tags = serde_json::from_str::<Vec<String>>(&tags_json).unwrap_or_default();
+                    let normalized_tags = normalize_steam_store_tags(&parsed_tags);
+                    for genre in map_steam_tags_to_genres(&normalized_tags) {
                         insert_genre
                             .execute(params![user_id, provider, game.external_id, genre])
                             .map_err(|error| format!("Failed to persist genre: {error}"))?;
@@ -3748,27 +7205,36 @@ fn replace_provider_games(
             }
         }
     }
+    transaction
+        .commit()
+        .map_err(|error| format!("Failed to commit provider game sync: {error}"))?;
 
-    Ok(())
+    Ok(summary)
 }
 
-fn list_games_by_user(connection: &Connection, user_id: &str) -> Result<Vec<GameResponse>, String> {
-    let collections_by_game = load_collection_names_by_game(connection, user_id)?;
-    let steam_tags_by_game = load_steam_tags_by_game(connection, user_id)?;
-    let game_genres_by_game = load_game_genres_by_game(connection, user_id)?;
+fn list_games_by_user(
+    connection: &Connection,
+    user_id: &str,
+    lookup_cache: &library_lookup_cache::LibraryLookupCache,
+) -> Result<Vec<GameResponse>, String> {
+    let lookups = lookup_cache.get_or_load(connection, user_id)?;
+    let collections_by_game = &lookups.collections_by_game;
+    let steam_tags_by_game = &lookups.steam_tags_by_game;
+    let game_genres_by_game = &lookups.game_genres_by_game;
     let mut statement = connection
         .prepare(
             "
             SELECT
               g.provider,
               g.external_id,
-              g.name,
+              COALESCE(display.display_name, g.name) AS name,
               g.kind,
               g.playtime_minutes,
               g.installed,
               g.artwork_url,
                             g.last_synced_at,
                             g.last_played_at,
+                            g.added_at,
                             EXISTS(
                 SELECT 1
                 FROM game_favorites favorite
@@ -3776,14 +7242,55 @@ fn list_games_by_user(connection: &Connection, user_id: &str) -> Result<Vec<Game
                   AND favorite.provider = g.provider
                   AND favorite.external_id = g.external_id
               ) AS favorite,
-              COALESCE(privacy.hide_in_library, 0) AS hide_in_library
+              COALESCE(privacy.hide_in_library, 0) AS hide_in_library,
+              metadata.genres_json,
+              metadata.categories_json,
+              metadata.developers_json,
+              metadata.publishers_json,
+              metadata.release_date,
+              metadata.metacritic_score,
+              metadata.recommendations_total,
+              reviews.score_description,
+              reviews.total_positive,
+              reviews.total_reviews,
+              g.playtime_2weeks_minutes,
+              achievement_progress.unlocked_count,
+              COALESCE(achievement_progress.completed, 0) AS completed,
+              metadata.controller_support,
+              COALESCE(
+                metadata.vr_support,
+                CASE WHEN local_vr.app_id IS NOT NULL THEN 'Supported' END
+              ) AS vr_support,
+              CASE WHEN display.display_name IS NOT NULL THEN g.name END AS original_name,
+              custom_artwork.artwork_path
             FROM games g
             LEFT JOIN game_privacy_settings privacy
               ON privacy.user_id = g.user_id
               AND privacy.provider = g.provider
               AND privacy.external_id = g.external_id
+            LEFT JOIN steam_app_metadata metadata
+              ON g.provider = 'steam'
+              AND metadata.app_id = g.external_id
+            LEFT JOIN steam_app_review_summaries reviews
+              ON g.provider = 'steam'
+              AND reviews.app_id = g.external_id
+            LEFT JOIN game_achievement_progress achievement_progress
+              ON achievement_progress.user_id = g.user_id
+              AND achievement_progress.provider = g.provider
+              AND achievement_progress.external_id = g.external_id
+            LEFT JOIN steam_local_vr_apps local_vr
+              ON g.provider = 'steam'
+              AND local_vr.app_id = g.external_id
+            LEFT JOIN game_display_names display
+              ON display.user_id = g.user_id
+              AND display.provider = g.provider
+              AND display.external_id = g.external_id
+            LEFT JOIN game_custom_artwork custom_artwork
+              ON custom_artwork.user_id = g.user_id
+              AND custom_artwork.provider = g.provider
+              AND custom_artwork.external_id = g.external_id
             WHERE g.user_id = ?1
-            ORDER BY g.name COLLATE NOCASE ASC
+            ORDER BY COALESCE(display.display_name, g.name) COLLATE NOCASE ASC
             ",
         )
         .map_err(|error| format!("Failed to prepare library query: {error}"))?;
@@ -3794,8 +7301,9 @@ fn list_games_by_user(connection: &Connection, user_id: &str) -> Result<Vec<Game
             let external_id: String = row.get(1)?;
             let installed_raw: i64 = row.get(5)?;
             let last_played: Option<String> = row.get(8)?;
-            let favorite_raw: i64 = row.get(9)?;
-            let hide_in_library_raw: i64 = row.get(10)?;
+            let favorite_raw: i64 = row.get(10)?;
+            let hide_in_library_raw: i64 = row.get(11)?;
+            let completed_raw: i64 = row.get(24)?;
             let steam_tags = if provider.eq_ignore_ascii_case("steam") {
                 steam_tags_by_game
                     .get(&external_id)
@@ -3804,43 +7312,91 @@ fn list_games_by_user(connection: &Connection, user_id: &str) -> Result<Vec<Game
             } else {
                 Vec::new()
             };
+            let decode_list = |json: Option<String>| {
+                json.and_then(|json| serde_json::from_str::<Vec<String>>(&json).ok())
+                    .unwrap_or_default()
+            };
+            let store_genres = decode_list(row.get(12)?);
+            let review_summary = match row.get::<_, Option<String>>(19)? {
+                Some(score_description) => Some(SteamReviewSummary {
+                    score_description,
+                    total_positive: row.get::<_, Option<i64>>(20)?.unwrap_or(0),
+                    total_reviews: row.get::<_, Option<i64>>(21)?.unwrap_or(0),
+                }),
+                None => None,
+            };
             let game_key = game_membership_key(&provider, &external_id);
-            let genres = game_genres_by_game
+            let mut genres = game_genres_by_game
                 .get(&game_key)
                 .cloned()
                 .unwrap_or_else(|| map_steam_tags_to_genres(&steam_tags));
+            for store_genre in &store_genres {
+                let slug = store_genre.to_ascii_lowercase().replace(' ', "-");
+                if !genres.contains(&slug) {
+                    genres.push(slug);
+                }
+            }
+            genres.sort();
             let collections = collections_by_game
                 .get(&game_key)
                 .cloned()
                 .unwrap_or_default();
+            let collection_positions = lookups
+                .collection_positions_by_game
+                .get(&game_key)
+                .cloned()
+                .unwrap_or_default();
+            let user_tags = lookups
+                .user_tags_by_game
+                .get(&game_key)
+                .cloned()
+                .unwrap_or_default();
             Ok(GameResponse {
                 id: format!("{provider}:{external_id}"),
                 provider,
                 external_id,
                 name: row.get(2)?,
+                original_name: row.get(27)?,
                 kind: row.get(3)?,
                 playtime_minutes: row.get(4)?,
+                playtime_2weeks_minutes: row.get(22)?,
                 installed: installed_raw > 0,
                 artwork_url: row.get(6)?,
+                custom_artwork_path: row.get(28)?,
                 last_synced_at: row.get(7)?,
                 last_played_at: last_played,
+                added_at: row.get(9)?,
                 favorite: favorite_raw > 0,
+                running: false,
                 steam_tags,
+                user_tags,
                 genres,
+                categories: decode_list(row.get(13)?),
                 collections,
+                collection_positions,
                 hide_in_library: hide_in_library_raw > 0,
-                developers: Vec::new(),
-                publishers: Vec::new(),
+                developers: decode_list(row.get(14)?),
+                publishers: decode_list(row.get(15)?),
                 franchise: None,
-                release_date: None,
+                release_date: row.get(16)?,
                 short_description: None,
                 header_image: None,
                 has_achievements: false,
                 has_cloud_saves: false,
-                controller_support: None,
+                controller_support: row.get(25)?,
+                vr_support: row.get(26)?,
                 achievements_count: None,
+                achievements_unlocked: row.get(23)?,
+                completed: completed_raw > 0,
                 cloud_details: None,
                 features: Vec::new(),
+                metacritic_score: row.get(17)?,
+                recommendations_total: row.get(18)?,
+                review_positive_percent: review_summary
+                    .as_ref()
+                    .and_then(SteamReviewSummary::positive_percent),
+                review_total: review_summary.as_ref().map(|summary| summary.total_reviews),
+                review_score_description: review_summary.map(|summary| summary.score_description),
             })
         })
         .map_err(|error| format!("Failed to query library rows: {error}"))?;
@@ -3975,7 +7531,10 @@ fn list_games_by_user(connection: &Connection, user_id: &str) -> Result<Vec<Game
                 game.achievements_count = *ach_count_opt;
                 game.has_cloud_saves = *has_cloud;
                 game.cloud_details = cloud_details_opt.clone();
-                game.controller_support = controller_opt.clone();
+                // Store categories cached with the app metadata take precedence.
+                if game.controller_support.is_none() {
+                    game.controller_support = controller_opt.clone();
+                }
             }
 
             // Build normalized features for the game based on cached details and features
@@ -4157,6 +7716,45 @@ fn load_collection_names_by_game(
     Ok(collections_by_game)
 }
 
+fn load_collection_positions_by_game(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<HashMap<String, BTreeMap<String, i64>>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT membership.provider, membership.external_id, c.name, membership.position
+            FROM collection_games membership
+            JOIN collections c
+              ON c.id = membership.collection_id
+             AND c.user_id = membership.user_id
+            WHERE membership.user_id = ?1 AND membership.position IS NOT NULL
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare collection position query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query collection positions: {error}"))?;
+
+    let mut positions_by_game: HashMap<String, BTreeMap<String, i64>> = HashMap::new();
+    for row in rows {
+        let (provider, external_id, collection_name, position) =
+            row.map_err(|error| format!("Failed to decode collection position row: {error}"))?;
+        positions_by_game
+            .entry(game_membership_key(&provider, &external_id))
+            .or_default()
+            .insert(collection_name.trim().to_owned(), position);
+    }
+    Ok(positions_by_game)
+}
+
 fn load_steam_tags_by_game(
     connection: &Connection,
     user_id: &str,
@@ -4277,13 +7875,56 @@ fn ensure_owned_game_exists(
     Ok(())
 }
 
+/// Checks every game in one query, so batch commands don't look games up one by one.
+fn ensure_owned_games_exist(
+    connection: &Connection,
+    user_id: &str,
+    games: &[(String, String)],
+) -> Result<(), String> {
+    if games.is_empty() {
+        return Ok(());
+    }
+    let placeholders = (0..games.len())
+        .map(|index| format!("(?{}, ?{})", index * 2 + 2, index * 2 + 3))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut statement = connection
+        .prepare(&format!(
+            "
+            SELECT provider, external_id
+            FROM games
+            WHERE user_id = ?1 AND (provider, external_id) IN (VALUES {placeholders})
+            "
+        ))
+        .map_err(|error| format!("Failed to prepare game ownership query: {error}"))?;
+    let parameters = std::iter::once(user_id).chain(
+        games
+            .iter()
+            .flat_map(|(provider, external_id)| [provider.as_str(), external_id.as_str()]),
+    );
+    let owned_games = statement
+        .query_map(rusqlite::params_from_iter(parameters), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .map_err(|error| format!("Failed to validate game ownership: {error}"))?
+        .collect::<Result<HashSet<_>, _>>()
+        .map_err(|error| format!("Failed to decode game ownership row: {error}"))?;
+
+    match games.iter().find(|game| !owned_games.contains(*game)) {
+        Some((provider, external_id)) => Err(format!(
+            "Game not found for current user: {provider}:{external_id}"
+        )),
+        None => Ok(()),
+    }
+}
+
 fn upsert_game_favorite(
     connection: &Connection,
     user_id: &str,
     provider: &str,
     external_id: &str,
-) -> Result<(), String> {
-    connection
+) -> Result<bool, String> {
+    let inserted_rows = connection
         .execute(
             "
             INSERT INTO game_favorites (user_id, provider, external_id, created_at)
@@ -4294,7 +7935,46 @@ fn upsert_game_favorite(
         )
         .map_err(|error| format!("Failed to update game favorite: {error}"))?;
 
-    Ok(())
+    Ok(inserted_rows > 0)
+}
+
+/// A blank `display_name` clears the override. Returns the stored title, if any.
+fn save_game_display_name(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    display_name: Option<&str>,
+) -> Result<Option<String>, String> {
+    let display_name = display_name.map(str::trim).filter(|value| !value.is_empty());
+    let Some(display_name) = display_name else {
+        connection
+            .execute(
+                "DELETE FROM game_display_names WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+                params![user_id, provider, external_id],
+            )
+            .map_err(|error| format!("Failed to clear game display name: {error}"))?;
+        return Ok(None);
+    };
+    if display_name.chars().count() > GAME_DISPLAY_NAME_MAX_LENGTH {
+        return Err(format!(
+            "Display names can be at most {GAME_DISPLAY_NAME_MAX_LENGTH} characters"
+        ));
+    }
+
+    connection
+        .execute(
+            "
+            INSERT INTO game_display_names (user_id, provider, external_id, display_name, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+              display_name = excluded.display_name,
+              updated_at = excluded.updated_at
+            ",
+            params![user_id, provider, external_id, display_name, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to save game display name: {error}"))?;
+    Ok(Some(display_name.to_owned()))
 }
 
 fn remove_game_favorite(
@@ -4347,6 +8027,8 @@ fn create_user_collection(
             name: normalized_name,
             game_count: 0,
             contains_game: false,
+            cover_image_path: None,
+            accent_color: None,
         }),
         Err(error) if error.to_string().contains("UNIQUE constraint failed: collections.user_id, collections.name") => {
             Err(String::from("Collection name already exists"))
@@ -4355,57 +8037,174 @@ fn create_user_collection(
     }
 }
 
-fn rename_user_collection(
+fn rename_user_collection(
+    connection: &Connection,
+    user_id: &str,
+    collection_id: &str,
+    name: &str,
+) -> Result<CollectionResponse, String> {
+    ensure_owned_collection_exists(connection, user_id, collection_id)?;
+    let normalized_name = normalize_collection_name(name)?;
+    let now = Utc::now().to_rfc3339();
+    let update_result = connection.execute(
+        "
+        UPDATE collections
+        SET name = ?1, updated_at = ?2
+        WHERE id = ?3 AND user_id = ?4
+        ",
+        params![normalized_name, now, collection_id, user_id],
+    );
+    match update_result {
+        Ok(updated_rows) => {
+            if updated_rows == 0 {
+                return Err(String::from("Collection not found for current user"));
+            }
+        }
+        Err(error)
+            if error
+                .to_string()
+                .contains("UNIQUE constraint failed: collections.user_id, collections.name") =>
+        {
+            return Err(String::from("Collection name already exists"));
+        }
+        Err(error) => return Err(format!("Failed to rename collection: {error}")),
+    }
+
+    get_user_collection(connection, user_id, collection_id)
+}
+
+/// A single collection with its full game count, as returned after it is edited.
+fn get_user_collection(
+    connection: &Connection,
+    user_id: &str,
+    collection_id: &str,
+) -> Result<CollectionResponse, String> {
+    connection
+        .query_row(
+            "
+            SELECT
+              c.name,
+              c.cover_image_path,
+              c.accent_color,
+              (
+                SELECT COUNT(*)
+                FROM collection_games membership
+                WHERE membership.user_id = c.user_id AND membership.collection_id = c.id
+              )
+            FROM collections c
+            WHERE c.id = ?1 AND c.user_id = ?2
+            ",
+            params![collection_id, user_id],
+            |row| {
+                let game_count_raw: i64 = row.get(3)?;
+                Ok(CollectionResponse {
+                    id: collection_id.to_owned(),
+                    name: row.get(0)?,
+                    game_count: usize::try_from(game_count_raw).unwrap_or_default(),
+                    contains_game: false,
+                    cover_image_path: row.get(1)?,
+                    accent_color: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| format!("Failed to load collection: {error}"))?
+        .ok_or_else(|| String::from("Collection not found for current user"))
+}
+
+fn load_collection_cover_image_path(
     connection: &Connection,
     user_id: &str,
     collection_id: &str,
-    name: &str,
+) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "SELECT cover_image_path FROM collections WHERE id = ?1 AND user_id = ?2",
+            params![collection_id, user_id],
+            |row| row.get::<_, Option<String>>(0),
+        )
+        .optional()
+        .map(Option::flatten)
+        .map_err(|error| format!("Failed to load collection cover image: {error}"))
+}
+
+/// Copies `source_path` in as the collection's cover image, or clears it when `None`.
+fn set_user_collection_cover_image(
+    connection: &Connection,
+    db_path: &Path,
+    user_id: &str,
+    collection_id: &str,
+    source_path: Option<&Path>,
 ) -> Result<CollectionResponse, String> {
     ensure_owned_collection_exists(connection, user_id, collection_id)?;
-    let normalized_name = normalize_collection_name(name)?;
-    let now = Utc::now().to_rfc3339();
-    let update_result = connection.execute(
-        "
-        UPDATE collections
-        SET name = ?1, updated_at = ?2
-        WHERE id = ?3 AND user_id = ?4
-        ",
-        params![normalized_name, now, collection_id, user_id],
-    );
-    match update_result {
-        Ok(updated_rows) => {
-            if updated_rows == 0 {
-                return Err(String::from("Collection not found for current user"));
-            }
-        }
-        Err(error)
-            if error
-                .to_string()
-                .contains("UNIQUE constraint failed: collections.user_id, collections.name") =>
-        {
-            return Err(String::from("Collection name already exists"));
-        }
-        Err(error) => return Err(format!("Failed to rename collection: {error}")),
+    let previous_path = load_collection_cover_image_path(connection, user_id, collection_id)?;
+    let cover_image_path = source_path
+        .map(|source_path| {
+            custom_artwork::copy_custom_artwork_file(db_path, &format!("collection-{collection_id}"), source_path)
+        })
+        .transpose()?
+        .map(|cover_image_path| cover_image_path.display().to_string());
+    connection
+        .execute(
+            "
+            UPDATE collections
+            SET cover_image_path = ?1, updated_at = ?2
+            WHERE id = ?3 AND user_id = ?4
+            ",
+            params![cover_image_path, Utc::now().to_rfc3339(), collection_id, user_id],
+        )
+        .map_err(|error| format!("Failed to save collection cover image: {error}"))?;
+    if let Some(previous_path) = previous_path {
+        custom_artwork::remove_custom_artwork_file(&previous_path);
+    }
+
+    get_user_collection(connection, user_id, collection_id)
+}
+
+/// Accepts `#rgb` or `#rrggbb` and stores the long, lowercase form.
+fn normalize_collection_accent_color(color: &str) -> Result<String, String> {
+    let hex = color
+        .trim()
+        .strip_prefix('#')
+        .filter(|hex| hex.chars().all(|character| character.is_ascii_hexdigit()))
+        .ok_or_else(|| String::from("Accent color must be a hex color such as #3a7bd5"))?;
+    match hex.len() {
+        3 => Ok(format!(
+            "#{}",
+            hex.chars()
+                .flat_map(|character| [character, character])
+                .collect::<String>()
+                .to_ascii_lowercase()
+        )),
+        6 => Ok(format!("#{}", hex.to_ascii_lowercase())),
+        _ => Err(String::from("Accent color must be a hex color such as #3a7bd5")),
     }
+}
 
-    let game_count_raw = connection
-        .query_row(
+/// Sets the collection's accent color, or clears it when `None` or blank.
+fn set_user_collection_accent_color(
+    connection: &Connection,
+    user_id: &str,
+    collection_id: &str,
+    accent_color: Option<&str>,
+) -> Result<CollectionResponse, String> {
+    ensure_owned_collection_exists(connection, user_id, collection_id)?;
+    let accent_color = accent_color
+        .filter(|color| !color.trim().is_empty())
+        .map(normalize_collection_accent_color)
+        .transpose()?;
+    connection
+        .execute(
             "
-            SELECT COUNT(*)
-            FROM collection_games
-            WHERE user_id = ?1 AND collection_id = ?2
+            UPDATE collections
+            SET accent_color = ?1, updated_at = ?2
+            WHERE id = ?3 AND user_id = ?4
             ",
-            params![user_id, collection_id],
-            |row| row.get::<_, i64>(0),
+            params![accent_color, Utc::now().to_rfc3339(), collection_id, user_id],
         )
-        .map_err(|error| format!("Failed to query renamed collection size: {error}"))?;
+        .map_err(|error| format!("Failed to save collection accent color: {error}"))?;
 
-    Ok(CollectionResponse {
-        id: collection_id.to_owned(),
-        name: normalized_name,
-        game_count: usize::try_from(game_count_raw).unwrap_or_default(),
-        contains_game: false,
-    })
+    get_user_collection(connection, user_id, collection_id)
 }
 
 fn delete_user_collection(
@@ -4414,6 +8213,7 @@ fn delete_user_collection(
     collection_id: &str,
 ) -> Result<(), String> {
     ensure_owned_collection_exists(connection, user_id, collection_id)?;
+    let cover_image_path = load_collection_cover_image_path(connection, user_id, collection_id)?;
     let deleted_rows = connection
         .execute(
             "DELETE FROM collections WHERE id = ?1 AND user_id = ?2",
@@ -4423,6 +8223,9 @@ fn delete_user_collection(
     if deleted_rows == 0 {
         return Err(String::from("Collection not found for current user"));
     }
+    if let Some(cover_image_path) = cover_image_path {
+        custom_artwork::remove_custom_artwork_file(&cover_image_path);
+    }
 
     Ok(())
 }
@@ -4466,11 +8269,32 @@ fn add_game_to_collection_membership(
     Ok(inserted_rows > 0)
 }
 
+fn remove_game_from_collection_membership(
+    connection: &Connection,
+    user_id: &str,
+    collection_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<bool, String> {
+    let deleted_rows = connection
+        .execute(
+            "
+            DELETE FROM collection_games
+            WHERE user_id = ?1 AND collection_id = ?2 AND provider = ?3 AND external_id = ?4
+            ",
+            params![user_id, collection_id, provider, external_id],
+        )
+        .map_err(|error| format!("Failed to remove game from collection: {error}"))?;
+    Ok(deleted_rows > 0)
+}
+
+/// Game counts leave out games hidden from the library unless `include_hidden` is set.
 fn list_collections_by_user(
     connection: &Connection,
     user_id: &str,
     provider: Option<&str>,
     external_id: Option<&str>,
+    include_hidden: bool,
 ) -> Result<Vec<CollectionResponse>, String> {
     let mut statement = connection
         .prepare(
@@ -4478,11 +8302,24 @@ fn list_collections_by_user(
             SELECT
               c.id,
               c.name,
+              c.cover_image_path,
+              c.accent_color,
               (
                 SELECT COUNT(*)
                 FROM collection_games membership
                 WHERE membership.user_id = c.user_id
                   AND membership.collection_id = c.id
+                  AND (
+                    ?2
+                    OR NOT EXISTS (
+                      SELECT 1
+                      FROM game_privacy_settings privacy
+                      WHERE privacy.user_id = membership.user_id
+                        AND privacy.provider = membership.provider
+                        AND privacy.external_id = membership.external_id
+                        AND privacy.hide_in_library != 0
+                    )
+                  )
               ) AS game_count
             FROM collections c
             WHERE c.user_id = ?1
@@ -4492,13 +8329,15 @@ fn list_collections_by_user(
         .map_err(|error| format!("Failed to prepare collections query: {error}"))?;
 
     let rows = statement
-        .query_map(params![user_id], |row| {
-            let game_count_raw: i64 = row.get(2)?;
+        .query_map(params![user_id, include_hidden], |row| {
+            let game_count_raw: i64 = row.get(4)?;
             Ok(CollectionResponse {
                 id: row.get(0)?,
                 name: row.get(1)?,
                 game_count: usize::try_from(game_count_raw).unwrap_or_default(),
                 contains_game: false,
+                cover_image_path: row.get(2)?,
+                accent_color: row.get(3)?,
             })
         })
         .map_err(|error| format!("Failed to query collections: {error}"))?;
@@ -4597,6 +8436,22 @@ fn load_provider_game_external_ids(
         .map_err(|error| format!("Failed to decode provider game list: {error}"))
 }
 
+fn find_game_name(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "SELECT name FROM games WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+            params![user_id, provider, external_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query game name: {error}"))
+}
+
 fn load_owned_steam_games_by_app_id(
     connection: &Connection,
     user_id: &str,
@@ -5055,12 +8910,22 @@ fn import_steam_collections_for_user(
     connection: &Connection,
     user_id: &str,
     collections_by_app_id: HashMap<String, HashSet<String>>,
+    mode: SteamCollectionsImportMode,
+    import_favorites: bool,
 ) -> Result<SteamCollectionsImportResponse, String> {
     let owned_steam_game_external_ids = load_provider_game_external_ids(connection, user_id, "steam")?;
+    // Preview runs the whole import and rolls it back instead of committing.
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| format!("Failed to start Steam collection import: {error}"))?;
     let mut collection_ids_by_name: HashMap<String, String> = HashMap::new();
+    let mut steam_members_by_collection: HashMap<String, (String, HashSet<String>)> = HashMap::new();
+    let mut changes = Vec::new();
     let mut apps_tagged = 0usize;
     let mut collections_created = 0usize;
     let mut memberships_added = 0usize;
+    let mut memberships_removed = 0usize;
+    let mut favorites_linked = 0usize;
     let mut skipped_games = 0usize;
     let mut tags_discovered = 0usize;
 
@@ -5078,6 +8943,12 @@ fn import_steam_collections_for_user(
             if normalized_key.is_empty() {
                 continue;
             }
+            if import_favorites && normalized_key == STEAM_FAVORITES_COLLECTION {
+                if upsert_game_favorite(&transaction, user_id, "steam", &external_id)? {
+                    favorites_linked += 1;
+                }
+                continue;
+            }
 
             let collection_id = if let Some(existing_collection_id) =
                 collection_ids_by_name.get(&normalized_key)
@@ -5085,35 +8956,151 @@ fn import_steam_collections_for_user(
                 existing_collection_id.clone()
             } else {
                 let (collection_id, created) =
-                    get_or_create_collection_id_by_name(connection, user_id, &collection_name)?;
+                    get_or_create_collection_id_by_name(&transaction, user_id, &collection_name)?;
                 if created {
                     collections_created += 1;
                 }
                 collection_ids_by_name.insert(normalized_key, collection_id.clone());
                 collection_id
             };
+            steam_members_by_collection
+                .entry(collection_id.clone())
+                .or_insert_with(|| (collection_name.clone(), HashSet::new()))
+                .1
+                .insert(external_id.clone());
 
             if add_game_to_collection_membership(
-                connection,
+                &transaction,
                 user_id,
                 &collection_id,
                 "steam",
                 &external_id,
             )? {
                 memberships_added += 1;
+                changes.push(SteamCollectionMembershipChangeResponse {
+                    collection_name,
+                    external_id: external_id.clone(),
+                    removed: false,
+                });
+            }
+        }
+    }
+
+    if mode != SteamCollectionsImportMode::Merge {
+        for (collection_id, (collection_name, steam_members)) in &steam_members_by_collection {
+            for external_id in
+                list_collection_provider_external_ids(&transaction, user_id, collection_id, "steam")?
+            {
+                if steam_members.contains(&external_id) {
+                    continue;
+                }
+                remove_game_from_collection_membership(
+                    &transaction,
+                    user_id,
+                    collection_id,
+                    "steam",
+                    &external_id,
+                )?;
+                memberships_removed += 1;
+                changes.push(SteamCollectionMembershipChangeResponse {
+                    collection_name: collection_name.clone(),
+                    external_id,
+                    removed: true,
+                });
             }
         }
     }
 
+    if mode != SteamCollectionsImportMode::Preview {
+        transaction
+            .commit()
+            .map_err(|error| format!("Failed to commit Steam collection import: {error}"))?;
+    }
+    changes.sort_by(|left, right| {
+        left.collection_name
+            .to_lowercase()
+            .cmp(&right.collection_name.to_lowercase())
+            .then_with(|| left.external_id.cmp(&right.external_id))
+    });
+
     Ok(SteamCollectionsImportResponse {
+        mode,
         apps_tagged,
         collections_created,
         memberships_added,
+        memberships_removed,
+        favorites_linked,
         skipped_games,
         tags_discovered,
+        dynamic_collections_skipped: 0,
+        changes,
     })
 }
 
+/// Orders a collection by hand: `games` get positions in the given order and any other members
+/// drop back to name order after them.
+fn set_collection_game_positions(
+    connection: &Connection,
+    user_id: &str,
+    collection_id: &str,
+    games: &[(String, String)],
+) -> Result<(), String> {
+    let mut seen_games = HashSet::new();
+    if let Some((provider, external_id)) = games.iter().find(|game| !seen_games.insert(*game)) {
+        return Err(format!("{provider}:{external_id} is listed more than once"));
+    }
+
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| format!("Failed to start collection order transaction: {error}"))?;
+    transaction
+        .execute(
+            "UPDATE collection_games SET position = NULL WHERE user_id = ?1 AND collection_id = ?2",
+            params![user_id, collection_id],
+        )
+        .map_err(|error| format!("Failed to reset collection order: {error}"))?;
+    for (position, (provider, external_id)) in games.iter().enumerate() {
+        let updated_rows = transaction
+            .execute(
+                "
+                UPDATE collection_games
+                SET position = ?1
+                WHERE user_id = ?2 AND collection_id = ?3 AND provider = ?4 AND external_id = ?5
+                ",
+                params![position as i64, user_id, collection_id, provider, external_id],
+            )
+            .map_err(|error| format!("Failed to save collection order: {error}"))?;
+        if updated_rows == 0 {
+            return Err(format!("{provider}:{external_id} was not found in this collection"));
+        }
+    }
+    transaction
+        .commit()
+        .map_err(|error| format!("Failed to commit collection order: {error}"))
+}
+
+fn list_collection_provider_external_ids(
+    connection: &Connection,
+    user_id: &str,
+    collection_id: &str,
+    provider: &str,
+) -> Result<Vec<String>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT external_id
+            FROM collection_games
+            WHERE user_id = ?1 AND collection_id = ?2 AND provider = ?3
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare collection member query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id, collection_id, provider], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to query collection members: {error}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode collection members: {error}"))
+}
+
 fn encode_steam_launch_options(launch_options: &str) -> String {
     url::form_urlencoded::byte_serialize(launch_options.as_bytes()).collect::<String>()
 }
@@ -5439,6 +9426,25 @@ fn open_provider_game_uri(
 
             launch_steam_uri(&uri, action)
         }
+        "ea" => {
+            let uri = match action {
+                "play" => format!("origin2://game/launch?offerIds={external_id}&autoDownload=1"),
+                "install" => format!("origin2://game/download?offerId={external_id}"),
+                _ => return Err(String::from("Unsupported EA action")),
+            };
+            launch_launcher_uri(&uri, "EA app")
+        }
+        "ubisoft" => {
+            let game_id = external_id
+                .parse::<u64>()
+                .map_err(|_| String::from("Ubisoft external_id must be a numeric game ID"))?;
+            let uri = match action {
+                "play" => format!("uplay://launch/{game_id}/0"),
+                "install" => format!("uplay://install/{game_id}"),
+                _ => return Err(String::from("Unsupported Ubisoft action")),
+            };
+            launch_launcher_uri(&uri, "Ubisoft Connect")
+        }
         _ => Err(format!(
             "Provider '{provider}' is not supported for action '{action}'"
         )),
@@ -5451,12 +9457,22 @@ fn default_game_customization_settings_payload() -> GameCustomizationSettingsPay
     }
 }
 
+fn default_discord_presence_enabled() -> bool {
+    true
+}
+
 fn default_game_properties_settings_payload() -> GamePropertiesSettingsPayload {
     GamePropertiesSettingsPayload {
         general: GameGeneralSettingsPayload {
             language: String::from("English"),
             launch_options: String::new(),
             steam_overlay_enabled: true,
+            discord_presence_enabled: default_discord_presence_enabled(),
+            pre_launch_command: String::new(),
+            post_exit_command: String::new(),
+            launch_hooks_confirmed: false,
+            launch_wrappers: GameLaunchWrappersSettingsPayload::default(),
+            environment_variables: BTreeMap::new(),
         },
         compatibility: GameCompatibilitySettingsPayload {
             force_steam_play_compatibility_tool: false,
@@ -5510,6 +9526,12 @@ fn normalize_game_properties_settings_payload(
             },
             launch_options: settings.general.launch_options.trim().to_owned(),
             steam_overlay_enabled: settings.general.steam_overlay_enabled,
+            discord_presence_enabled: settings.general.discord_presence_enabled,
+            pre_launch_command: settings.general.pre_launch_command.trim().to_owned(),
+            post_exit_command: settings.general.post_exit_command.trim().to_owned(),
+            launch_hooks_confirmed: settings.general.launch_hooks_confirmed,
+            launch_wrappers: settings.general.launch_wrappers,
+            environment_variables: normalize_launch_environment_variables(settings.general.environment_variables),
         },
         compatibility: GameCompatibilitySettingsPayload {
             force_steam_play_compatibility_tool: settings
@@ -5524,21 +9546,12 @@ fn normalize_game_properties_settings_payload(
         updates: GameUpdatesSettingsPayload {
             automatic_updates_mode: normalize_game_properties_mode(
                 settings.updates.automatic_updates_mode,
-                &[
-                    "use-global-setting",
-                    "wait-until-launch",
-                    "let-steam-decide",
-                    "immediately-download",
-                ],
+                &STEAM_AUTOMATIC_UPDATES_MODES,
                 &defaults.updates.automatic_updates_mode,
             ),
             background_downloads_mode: normalize_game_properties_mode(
                 settings.updates.background_downloads_mode,
-                &[
-                    "pause-while-playing-global",
-                    "always-allow",
-                    "never-allow",
-                ],
+                &STEAM_BACKGROUND_DOWNLOADS_MODES,
                 &defaults.updates.background_downloads_mode,
             ),
         },
@@ -5631,6 +9644,130 @@ fn save_game_properties_settings(
     Ok(())
 }
 
+const LAUNCH_WRAPPERS: [(&str, &str, &str); 3] = [
+    ("gamemode", "Feral GameMode", "gamemoderun"),
+    ("mangohud", "MangoHud", "mangohud"),
+    ("gamescope", "Gamescope", "gamescope"),
+];
+
+fn find_executable_in_path(executable_name: &str) -> Option<PathBuf> {
+    let path_variable = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_variable)
+        .map(|directory| directory.join(executable_name))
+        .find(|candidate| candidate.is_file())
+}
+
+fn detect_launch_wrappers() -> Vec<LaunchWrapperResponse> {
+    LAUNCH_WRAPPERS
+        .iter()
+        .map(|(id, label, executable_name)| {
+            let path = if cfg!(target_os = "linux") {
+                find_executable_in_path(executable_name)
+            } else {
+                None
+            };
+            LaunchWrapperResponse {
+                id: (*id).to_owned(),
+                label: (*label).to_owned(),
+                installed: path.is_some(),
+                path: path.map(|resolved| resolved.display().to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Wraps `%command%` in the enabled launch wrappers, e.g.
+/// `gamemoderun gamescope -W 1920 -H 1080 -f -- mangohud %command% -novid`. User launch options
+/// without `%command%` are treated as game arguments.
+fn compose_launch_wrapper_options(launch_options: &str, wrappers: &GameLaunchWrappersSettingsPayload) -> String {
+    let launch_options = launch_options.trim();
+    let mut prefix = Vec::new();
+    if wrappers.gamemode {
+        prefix.push(String::from("gamemoderun"));
+    }
+    if wrappers.gamescope {
+        prefix.push(String::from("gamescope"));
+        for (flag, value) in [
+            ("-W", wrappers.gamescope_width),
+            ("-H", wrappers.gamescope_height),
+            ("-r", wrappers.gamescope_refresh_rate),
+        ] {
+            if let Some(value) = value.filter(|value| *value > 0) {
+                prefix.push(format!("{flag} {value}"));
+            }
+        }
+        if wrappers.gamescope_fullscreen {
+            prefix.push(String::from("-f"));
+        }
+        prefix.push(String::from("--"));
+    }
+    if wrappers.mangohud {
+        prefix.push(String::from("mangohud"));
+    }
+    if prefix.is_empty() {
+        return launch_options.to_owned();
+    }
+
+    let wrapped_command = format!("{} %command%", prefix.join(" "));
+    if launch_options.contains("%command%") {
+        launch_options.replacen("%command%", &wrapped_command, 1)
+    } else if launch_options.is_empty() {
+        wrapped_command
+    } else {
+        format!("{wrapped_command} {launch_options}")
+    }
+}
+
+fn is_valid_environment_variable_name(name: &str) -> bool {
+    let mut characters = name.chars();
+    characters
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && characters.all(|character| character.is_ascii_alphanumeric() || character == '_')
+}
+
+/// Trims names and values and drops entries whose name a shell would not accept.
+fn normalize_launch_environment_variables(variables: BTreeMap<String, String>) -> BTreeMap<String, String> {
+    variables
+        .into_iter()
+        .map(|(name, value)| (name.trim().to_owned(), value.trim().to_owned()))
+        .filter(|(name, _)| is_valid_environment_variable_name(name))
+        .collect()
+}
+
+/// Prefixes `%command%` with `VAR=value` assignments, e.g. `DXVK_HUD=fps PROTON_LOG=1 %command%`.
+/// Values containing shell metacharacters are single-quoted since Steam runs launch options
+/// through a shell.
+fn compose_launch_environment_options(launch_options: &str, variables: &BTreeMap<String, String>) -> String {
+    let launch_options = launch_options.trim();
+    if variables.is_empty() {
+        return launch_options.to_owned();
+    }
+
+    let assignments = variables
+        .iter()
+        .map(|(name, value)| {
+            let needs_quotes = value.is_empty()
+                || value
+                    .chars()
+                    .any(|character| !(character.is_ascii_alphanumeric() || "_-.,:/=+@%".contains(character)));
+            if needs_quotes {
+                format!("{name}='{}'", value.replace('\'', "'\\''"))
+            } else {
+                format!("{name}={value}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if launch_options.contains("%command%") {
+        format!("{assignments} {launch_options}")
+    } else if launch_options.is_empty() {
+        format!("{assignments} %command%")
+    } else {
+        format!("{assignments} %command% {launch_options}")
+    }
+}
+
 fn map_compatibility_tool_label_to_steam_name(label: &str) -> String {
     let trimmed_label = label.trim();
     if trimmed_label.is_empty() {
@@ -5939,12 +10076,36 @@ fn normalize_steam_manifest_language(language: &str) -> Option<String> {
     Some(mapped.to_owned())
 }
 
+/// Steam only fetches the depots of a new language once an installed app is flagged as needing
+/// an update, so the flag is raised when the chosen language differs from the mounted one.
+/// Returns whether the flag was set.
+fn mark_steam_manifest_language_update_required(app_state_object: &mut VdfValue, language: &str) -> bool {
+    let mounted_language = vdf_find_object_value(app_state_object, "MountedConfig")
+        .and_then(|mounted_config| vdf_get_text_entry(mounted_config, "language"))
+        .unwrap_or_default();
+    let state_flags = vdf_get_text_entry(app_state_object, "StateFlags")
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    let Some(state_flags) = state_flags else {
+        return false;
+    };
+    if state_flags & STEAM_APP_STATE_FULLY_INSTALLED == 0 || mounted_language.eq_ignore_ascii_case(language) {
+        return false;
+    }
+
+    vdf_set_text_entry(
+        app_state_object,
+        "StateFlags",
+        &(state_flags | STEAM_APP_STATE_UPDATE_REQUIRED).to_string(),
+    );
+    true
+}
+
 fn apply_steam_manifest_game_properties_settings(
     state: &AppState,
     app_id: u64,
     settings: &GamePropertiesSettingsPayload,
 ) -> Result<(), String> {
-    let manifest_path = match resolve_steam_manifest_path_for_app_id(state.steam_root_override.as_deref(), app_id) {
+    let manifest_path = match resolve_steam_manifest_path_for_app_id(state.steam_root_override().as_deref(), app_id) {
         Ok(path) => path,
         Err(error) => {
             log_steam_settings_debug(
@@ -5981,9 +10142,10 @@ fn apply_steam_manifest_game_properties_settings(
         _ => {}
     }
 
+    let steam_language = normalize_steam_manifest_language(&settings.general.language);
     let user_config_object = vdf_ensure_object_path_mut(app_state_object, &["UserConfig"]);
-    if let Some(language) = normalize_steam_manifest_language(&settings.general.language) {
-        vdf_set_text_entry(user_config_object, "language", &language);
+    if let Some(language) = steam_language.as_deref() {
+        vdf_set_text_entry(user_config_object, "language", language);
     }
 
     let selected_beta_branch = settings.game_versions_betas.selected_version_id.trim();
@@ -6001,8 +10163,17 @@ fn apply_steam_manifest_game_properties_settings(
         vdf_set_text_entry(user_config_object, "betapassword", private_access_code);
     }
 
+    if let Some(language) = steam_language.as_deref() {
+        if mark_steam_manifest_language_update_required(app_state_object, language) {
+            log_steam_settings_debug(
+                state,
+                &format!("app {}: language changed to {}, flagged for update", app_id, language),
+            );
+        }
+    }
+
     let serialized_manifest = serialize_vdf_document(&manifest_value);
-    fs::write(&manifest_path, serialized_manifest).map_err(|error| {
+    steam_config_writer::write_steam_config_file(&manifest_path, serialized_manifest).map_err(|error| {
         format!(
             "Failed to write Steam app manifest at {}: {error}",
             manifest_path.display()
@@ -6045,7 +10216,7 @@ fn clear_steam_game_overlay_data(state: &AppState, user: &UserRow, app_id: u64)
         .steam_id
         .as_deref()
         .ok_or_else(|| String::from("Steam is not linked for this account"))?;
-    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override.as_deref(), steam_id)?;
+    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override().as_deref(), steam_id)?;
     let localconfig_contents = fs::read_to_string(&localconfig_path).map_err(|error| {
         format!(
             "Failed to read Steam localconfig at {}: {error}",
@@ -6076,7 +10247,7 @@ fn clear_steam_game_overlay_data(state: &AppState, user: &UserRow, app_id: u64)
     }
 
     let serialized_localconfig = serialize_vdf_document(&localconfig_value);
-    fs::write(&localconfig_path, serialized_localconfig).map_err(|error| {
+    steam_config_writer::write_steam_config_file(&localconfig_path, serialized_localconfig).map_err(|error| {
         format!(
             "Failed to write Steam localconfig at {}: {error}",
             localconfig_path.display()
@@ -6090,7 +10261,7 @@ fn clear_steam_game_overlay_data(state: &AppState, user: &UserRow, app_id: u64)
 }
 
 fn log_steam_settings_debug(state: &AppState, message: &str) {
-    if state.steam_settings_debug_logging {
+    if state.steam_settings_debug_logging() {
         eprintln!("[catalyst:steam-settings] {message}");
     }
 }
@@ -6490,7 +10661,7 @@ fn update_steam_cloudstorage_hidden_collection_namespace(
             namespace_path.display()
         )
     })?;
-    fs::write(namespace_path, serialized_namespace).map_err(|error| {
+    steam_config_writer::write_steam_config_file(namespace_path, serialized_namespace).map_err(|error| {
         format!(
             "Failed to write Steam cloudstorage namespace file at {}: {error}",
             namespace_path.display()
@@ -6554,7 +10725,7 @@ fn update_steam_cloudstorage_namespaces_version(
             namespaces_path.display()
         )
     })?;
-    fs::write(namespaces_path, serialized_namespaces).map_err(|error| {
+    steam_config_writer::write_steam_config_file(namespaces_path, serialized_namespaces).map_err(|error| {
         format!(
             "Failed to write Steam cloudstorage namespaces file at {}: {error}",
             namespaces_path.display()
@@ -6570,7 +10741,7 @@ fn apply_steam_cloudstorage_hidden_collection_state(
     hide_in_library: bool,
 ) -> Result<(), String> {
     let cloudstorage_directory =
-        resolve_steam_cloudstorage_directory(state.steam_root_override.as_deref(), steam_id)?;
+        resolve_steam_cloudstorage_directory(state.steam_root_override().as_deref(), steam_id)?;
     let namespace_path = cloudstorage_directory.join("cloud-storage-namespace-1.json");
     if !namespace_path.is_file() {
         return Ok(());
@@ -6602,7 +10773,7 @@ fn apply_steam_game_privacy_settings(
         .steam_id
         .as_deref()
         .ok_or_else(|| String::from("Steam is not linked for this account"))?;
-    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override.as_deref(), steam_id)?;
+    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override().as_deref(), steam_id)?;
     log_steam_settings_debug(
         state,
         &format!(
@@ -6632,7 +10803,7 @@ fn apply_steam_game_privacy_settings(
     )?;
 
     let serialized_localconfig = serialize_vdf_document(&localconfig_value);
-    fs::write(&localconfig_path, serialized_localconfig).map_err(|error| {
+    steam_config_writer::write_steam_config_file(&localconfig_path, serialized_localconfig).map_err(|error| {
         format!(
             "Failed to write Steam localconfig at {}: {error}",
             localconfig_path.display()
@@ -6644,7 +10815,7 @@ fn apply_steam_game_privacy_settings(
     );
 
     let sharedconfig_paths =
-        resolve_steam_sharedconfig_paths(state.steam_root_override.as_deref(), steam_id)?;
+        resolve_steam_sharedconfig_paths(state.steam_root_override().as_deref(), steam_id)?;
     for sharedconfig_path in sharedconfig_paths {
         let sharedconfig_contents = fs::read_to_string(&sharedconfig_path).map_err(|error| {
             format!(
@@ -6660,7 +10831,7 @@ fn apply_steam_game_privacy_settings(
             settings,
         )?;
         let serialized_sharedconfig = serialize_vdf_document(&sharedconfig_value);
-        fs::write(&sharedconfig_path, serialized_sharedconfig).map_err(|error| {
+        steam_config_writer::write_steam_config_file(&sharedconfig_path, serialized_sharedconfig).map_err(|error| {
             format!(
                 "Failed to write Steam sharedconfig at {}: {error}",
                 sharedconfig_path.display()
@@ -6687,8 +10858,74 @@ fn apply_steam_game_privacy_settings(
             ),
         );
     }
-
-    Ok(())
+
+    Ok(())
+}
+
+/// Writes the automatic update and background download modes into an app's entry in
+/// localconfig.vdf.
+fn apply_steam_update_policy_to_app_settings(
+    state: &AppState,
+    app_id: u64,
+    app_settings_object: &mut VdfValue,
+    updates: &GameUpdatesSettingsPayload,
+) {
+    match updates.automatic_updates_mode.as_str() {
+        "use-global-setting" => {
+            vdf_remove_entry(app_settings_object, "AutoUpdateBehavior");
+            log_steam_settings_debug(state, &format!("app {}: cleared AutoUpdateBehavior", app_id));
+        }
+        "wait-until-launch" => {
+            vdf_set_text_entry(app_settings_object, "AutoUpdateBehavior", "1");
+            log_steam_settings_debug(state, &format!("app {}: set AutoUpdateBehavior=1", app_id));
+        }
+        "let-steam-decide" => {
+            vdf_set_text_entry(app_settings_object, "AutoUpdateBehavior", "0");
+            log_steam_settings_debug(state, &format!("app {}: set AutoUpdateBehavior=0", app_id));
+        }
+        "immediately-download" => {
+            vdf_set_text_entry(app_settings_object, "AutoUpdateBehavior", "2");
+            log_steam_settings_debug(state, &format!("app {}: set AutoUpdateBehavior=2", app_id));
+        }
+        _ => {}
+    }
+
+    match updates.background_downloads_mode.as_str() {
+        "pause-while-playing-global" => {
+            vdf_remove_entry(app_settings_object, "AllowDownloadsWhileRunning");
+            vdf_remove_entry(app_settings_object, "AllowOtherDownloadsWhileRunning");
+            log_steam_settings_debug(
+                state,
+                &format!(
+                    "app {}: cleared AllowDownloadsWhileRunning and AllowOtherDownloadsWhileRunning",
+                    app_id
+                ),
+            );
+        }
+        "always-allow" => {
+            vdf_set_text_entry(app_settings_object, "AllowDownloadsWhileRunning", "1");
+            vdf_set_text_entry(app_settings_object, "AllowOtherDownloadsWhileRunning", "1");
+            log_steam_settings_debug(
+                state,
+                &format!(
+                    "app {}: set AllowDownloadsWhileRunning=1 and AllowOtherDownloadsWhileRunning=1",
+                    app_id
+                ),
+            );
+        }
+        "never-allow" => {
+            vdf_set_text_entry(app_settings_object, "AllowDownloadsWhileRunning", "0");
+            vdf_set_text_entry(app_settings_object, "AllowOtherDownloadsWhileRunning", "0");
+            log_steam_settings_debug(
+                state,
+                &format!(
+                    "app {}: set AllowDownloadsWhileRunning=0 and AllowOtherDownloadsWhileRunning=0",
+                    app_id
+                ),
+            );
+        }
+        _ => {}
+    }
 }
 
 fn apply_steam_game_properties_settings(
@@ -6701,7 +10938,7 @@ fn apply_steam_game_properties_settings(
         .steam_id
         .as_deref()
         .ok_or_else(|| String::from("Steam is not linked for this account"))?;
-    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override.as_deref(), steam_id)?;
+    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override().as_deref(), steam_id)?;
     log_steam_settings_debug(
         state,
         &format!(
@@ -6725,7 +10962,15 @@ fn apply_steam_game_properties_settings(
     );
     let app_settings_object = vdf_ensure_object_path_mut(apps_object, &[app_id_key.as_str()]);
 
-    let launch_options = settings.general.launch_options.trim();
+    let composed_launch_options = if cfg!(target_os = "linux") {
+        compose_launch_environment_options(
+            &compose_launch_wrapper_options(&settings.general.launch_options, &settings.general.launch_wrappers),
+            &settings.general.environment_variables,
+        )
+    } else {
+        settings.general.launch_options.clone()
+    };
+    let launch_options = composed_launch_options.trim();
     if launch_options.is_empty() {
         vdf_remove_entry(app_settings_object, "LaunchOptions");
         log_steam_settings_debug(state, &format!("app {}: cleared LaunchOptions", app_id));
@@ -6750,62 +10995,7 @@ fn apply_steam_game_properties_settings(
         log_steam_settings_debug(state, &format!("app {}: disabled Steam Overlay", app_id));
     }
 
-    match settings.updates.automatic_updates_mode.as_str() {
-        "use-global-setting" => {
-            vdf_remove_entry(app_settings_object, "AutoUpdateBehavior");
-            log_steam_settings_debug(state, &format!("app {}: cleared AutoUpdateBehavior", app_id));
-        }
-        "wait-until-launch" => {
-            vdf_set_text_entry(app_settings_object, "AutoUpdateBehavior", "1");
-            log_steam_settings_debug(state, &format!("app {}: set AutoUpdateBehavior=1", app_id));
-        }
-        "let-steam-decide" => {
-            vdf_set_text_entry(app_settings_object, "AutoUpdateBehavior", "0");
-            log_steam_settings_debug(state, &format!("app {}: set AutoUpdateBehavior=0", app_id));
-        }
-        "immediately-download" => {
-            vdf_set_text_entry(app_settings_object, "AutoUpdateBehavior", "2");
-            log_steam_settings_debug(state, &format!("app {}: set AutoUpdateBehavior=2", app_id));
-        }
-        _ => {}
-    }
-
-    match settings.updates.background_downloads_mode.as_str() {
-        "pause-while-playing-global" => {
-            vdf_remove_entry(app_settings_object, "AllowDownloadsWhileRunning");
-            vdf_remove_entry(app_settings_object, "AllowOtherDownloadsWhileRunning");
-            log_steam_settings_debug(
-                state,
-                &format!(
-                    "app {}: cleared AllowDownloadsWhileRunning and AllowOtherDownloadsWhileRunning",
-                    app_id
-                ),
-            );
-        }
-        "always-allow" => {
-            vdf_set_text_entry(app_settings_object, "AllowDownloadsWhileRunning", "1");
-            vdf_set_text_entry(app_settings_object, "AllowOtherDownloadsWhileRunning", "1");
-            log_steam_settings_debug(
-                state,
-                &format!(
-                    "app {}: set AllowDownloadsWhileRunning=1 and AllowOtherDownloadsWhileRunning=1",
-                    app_id
-                ),
-            );
-        }
-        "never-allow" => {
-            vdf_set_text_entry(app_settings_object, "AllowDownloadsWhileRunning", "0");
-            vdf_set_text_entry(app_settings_object, "AllowOtherDownloadsWhileRunning", "0");
-            log_steam_settings_debug(
-                state,
-                &format!(
-                    "app {}: set AllowDownloadsWhileRunning=0 and AllowOtherDownloadsWhileRunning=0",
-                    app_id
-                ),
-            );
-        }
-        _ => {}
-    }
+    apply_steam_update_policy_to_app_settings(state, app_id, app_settings_object, &settings.updates);
 
     match settings.controller.steam_input_override.as_str() {
         "use-default-settings" => {
@@ -6865,7 +11055,7 @@ fn apply_steam_game_properties_settings(
     }
 
     let serialized_localconfig = serialize_vdf_document(&localconfig_value);
-    fs::write(&localconfig_path, serialized_localconfig).map_err(|error| {
+    steam_config_writer::write_steam_config_file(&localconfig_path, serialized_localconfig).map_err(|error| {
         format!(
             "Failed to write Steam localconfig at {}: {error}",
             localconfig_path.display()
@@ -6879,6 +11069,181 @@ fn apply_steam_game_properties_settings(
     Ok(())
 }
 
+/// Applies update policies for many Steam apps with a single localconfig.vdf rewrite. App
+/// manifests are still written one by one since each app has its own.
+fn apply_steam_update_policies(
+    state: &AppState,
+    user: &UserRow,
+    games: &[(u64, GamePropertiesSettingsPayload)],
+) -> Result<(), String> {
+    if games.is_empty() {
+        return Ok(());
+    }
+    let steam_id = user
+        .steam_id
+        .as_deref()
+        .ok_or_else(|| String::from("Steam is not linked for this account"))?;
+    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override().as_deref(), steam_id)?;
+    let localconfig_contents = fs::read_to_string(&localconfig_path).map_err(|error| {
+        format!(
+            "Failed to read Steam localconfig at {}: {error}",
+            localconfig_path.display()
+        )
+    })?;
+    let mut localconfig_value = parse_vdf_document(&localconfig_contents)?;
+    let apps_object = vdf_ensure_object_path_mut(
+        &mut localconfig_value,
+        &["UserLocalConfigStore", "Software", "Valve", "Steam", "apps"],
+    );
+    for (app_id, settings) in games {
+        let app_id_key = app_id.to_string();
+        let app_settings_object = vdf_ensure_object_path_mut(apps_object, &[app_id_key.as_str()]);
+        apply_steam_update_policy_to_app_settings(state, *app_id, app_settings_object, &settings.updates);
+    }
+    steam_config_writer::write_steam_config_file(&localconfig_path, serialize_vdf_document(&localconfig_value)).map_err(|error| {
+        format!(
+            "Failed to write Steam localconfig at {}: {error}",
+            localconfig_path.display()
+        )
+    })?;
+
+    let mut manifest_errors = Vec::new();
+    for (app_id, settings) in games {
+        if let Err(error) = apply_steam_manifest_game_properties_settings(state, *app_id, settings) {
+            manifest_errors.push(format!("app {app_id}: {error}"));
+        }
+    }
+    if !manifest_errors.is_empty() {
+        return Err(format!(
+            "Failed to update some Steam app manifests: {}",
+            manifest_errors.join("; ")
+        ));
+    }
+    Ok(())
+}
+
+/// Sums the files Steam Cloud tracks for an app in `userdata/<id>/<appid>/remotecache.vdf` and
+/// returns `(used_bytes, file_count, last_sync_unix)`.
+fn parse_steam_remotecache_usage(contents: &str, app_id: u64) -> Result<(u64, usize, Option<i64>), String> {
+    let document = parse_vdf_document(contents)?;
+    let Some(VdfValue::Object(entries)) = vdf_find_object_value(&document, &app_id.to_string()) else {
+        return Ok((0, 0, None));
+    };
+
+    let mut used_bytes = 0_u64;
+    let mut file_count = 0;
+    let mut last_sync_unix = None;
+    for (_, entry) in entries {
+        if !matches!(entry, VdfValue::Object(_)) {
+            continue;
+        }
+        file_count += 1;
+        used_bytes += vdf_get_text_entry(entry, "size")
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0);
+        let synced_at = ["remotetime", "time"]
+            .iter()
+            .filter_map(|key| vdf_get_text_entry(entry, key))
+            .filter_map(|value| value.parse::<i64>().ok())
+            .max();
+        last_sync_unix = last_sync_unix.max(synced_at);
+    }
+
+    Ok((used_bytes, file_count, last_sync_unix))
+}
+
+fn read_steam_app_cloud_status(
+    state: &AppState,
+    connection: &Connection,
+    user: &UserRow,
+    app_id: u64,
+) -> Result<SteamCloudStatusResponse, String> {
+    let steam_id = user
+        .steam_id
+        .as_deref()
+        .ok_or_else(|| String::from("Steam is not linked for this account"))?;
+    let steam_root_override = state.steam_root_override();
+    let steam_root = resolve_steam_root_path(steam_root_override.as_deref())
+        .ok_or_else(|| String::from("Could not locate local Steam installation"))?;
+    let userdata_directory = resolve_steam_userdata_directory(&steam_root, steam_id)?;
+
+    let remotecache_path = userdata_directory.join(app_id.to_string()).join("remotecache.vdf");
+    let (used_bytes, file_count, last_sync_unix) = match fs::read_to_string(&remotecache_path) {
+        Ok(contents) => parse_steam_remotecache_usage(&contents, app_id)?,
+        Err(_) => (0, 0, None),
+    };
+
+    let localconfig_path = resolve_steam_localconfig_path(steam_root_override.as_deref(), steam_id)?;
+    let localconfig_contents = fs::read_to_string(&localconfig_path).map_err(|error| {
+        format!(
+            "Failed to read Steam localconfig at {}: {error}",
+            localconfig_path.display()
+        )
+    })?;
+    let localconfig_value = parse_vdf_document(&localconfig_contents)?;
+    let app_id_key = app_id.to_string();
+    let enabled = ["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", app_id_key.as_str()]
+        .iter()
+        .try_fold(&localconfig_value, |value, key| vdf_find_object_value(value, key))
+        .and_then(|app_settings| vdf_get_text_entry(app_settings, "cloudenabled"))
+        .map(|value| value.trim() != "0")
+        .unwrap_or(true);
+
+    let supported = find_cached_steam_app_features(connection, app_id, chrono::DateTime::<Utc>::MIN_UTC)?
+        .map(|(_, _, has_cloud_saves, _, _)| has_cloud_saves);
+
+    Ok(SteamCloudStatusResponse {
+        supported,
+        enabled,
+        last_sync_at: last_sync_unix
+            .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+            .map(|timestamp| timestamp.to_rfc3339()),
+        used_bytes,
+        file_count,
+    })
+}
+
+fn apply_steam_app_cloud_enabled(
+    state: &AppState,
+    user: &UserRow,
+    app_id: u64,
+    enabled: bool,
+) -> Result<(), String> {
+    let steam_id = user
+        .steam_id
+        .as_deref()
+        .ok_or_else(|| String::from("Steam is not linked for this account"))?;
+    let localconfig_path = resolve_steam_localconfig_path(state.steam_root_override().as_deref(), steam_id)?;
+    let localconfig_contents = fs::read_to_string(&localconfig_path).map_err(|error| {
+        format!(
+            "Failed to read Steam localconfig at {}: {error}",
+            localconfig_path.display()
+        )
+    })?;
+    let mut localconfig_value = parse_vdf_document(&localconfig_contents)?;
+    let app_id_key = app_id.to_string();
+    let app_settings_object = vdf_ensure_object_path_mut(
+        &mut localconfig_value,
+        &["UserLocalConfigStore", "Software", "Valve", "Steam", "apps", app_id_key.as_str()],
+    );
+    if enabled {
+        vdf_remove_entry(app_settings_object, "cloudenabled");
+    } else {
+        vdf_set_text_entry(app_settings_object, "cloudenabled", "0");
+    }
+    log_steam_settings_debug(
+        state,
+        &format!("app {}: set Steam Cloud enabled={}", app_id, enabled),
+    );
+
+    steam_config_writer::write_steam_config_file(&localconfig_path, serialize_vdf_document(&localconfig_value)).map_err(|error| {
+        format!(
+            "Failed to write Steam localconfig at {}: {error}",
+            localconfig_path.display()
+        )
+    })
+}
+
 fn load_game_privacy_settings(
     connection: &Connection,
     user_id: &str,
@@ -6971,18 +11336,23 @@ fn get_authenticated_user(state: &AppState, connection: &Connection) -> Result<U
 fn find_user_by_id(connection: &Connection, user_id: &str) -> Result<Option<UserRow>, String> {
     connection
         .query_row(
-            "SELECT id, email, steam_id FROM users WHERE id = ?1",
+            "SELECT id, email, steam_id, steam_persona_name, steam_avatar_url, steam_profile_url FROM users WHERE id = ?1",
             params![user_id],
             |row| {
                 Ok(UserRow {
                     id: row.get(0)?,
                     email: row.get(1)?,
                     steam_id: row.get(2)?,
+                    steam_persona_name: row.get(3)?,
+                    steam_avatar_url: row.get(4)?,
+                    steam_profile_url: row.get(5)?,
                 })
             },
         )
         .optional()
-        .map_err(|error| format!("Failed to query user by ID: {error}"))
+        .map_err(|error| format!("Failed to query user by ID: {error}"))?
+        .map(decrypt_user_row)
+        .transpose()
 }
 
 fn find_user_by_steam_id(
@@ -6991,18 +11361,43 @@ fn find_user_by_steam_id(
 ) -> Result<Option<UserRow>, String> {
     connection
         .query_row(
-            "SELECT id, email, steam_id FROM users WHERE steam_id = ?1",
-            params![steam_id],
+            "SELECT id, email, steam_id, steam_persona_name, steam_avatar_url, steam_profile_url FROM users WHERE steam_id_lookup = ?1",
+            params![field_encryption::field_lookup_hash(steam_id)?],
             |row| {
                 Ok(UserRow {
                     id: row.get(0)?,
                     email: row.get(1)?,
                     steam_id: row.get(2)?,
+                    steam_persona_name: row.get(3)?,
+                    steam_avatar_url: row.get(4)?,
+                    steam_profile_url: row.get(5)?,
                 })
             },
         )
         .optional()
-        .map_err(|error| format!("Failed to query user by Steam ID: {error}"))
+        .map_err(|error| format!("Failed to query user by Steam ID: {error}"))?
+        .map(decrypt_user_row)
+        .transpose()
+}
+
+/// `users.steam_id` is stored encrypted; lookups by Steam ID use `steam_id_lookup` instead.
+fn decrypt_user_row(mut user: UserRow) -> Result<UserRow, String> {
+    user.steam_id = user
+        .steam_id
+        .as_deref()
+        .map(field_encryption::decrypt_field)
+        .transpose()?;
+    Ok(user)
+}
+
+fn encrypt_steam_id_columns(steam_id: Option<&str>) -> Result<(Option<String>, Option<String>), String> {
+    let Some(steam_id) = steam_id else {
+        return Ok((None, None));
+    };
+    Ok((
+        Some(field_encryption::encrypt_field(steam_id)?),
+        Some(field_encryption::field_lookup_hash(steam_id)?),
+    ))
 }
 
 fn create_user(
@@ -7013,11 +11408,20 @@ fn create_user(
 ) -> Result<UserRow, String> {
     let user_id = Uuid::new_v4().to_string();
     let timestamp = Utc::now().to_rfc3339();
+    let (encrypted_steam_id, steam_id_lookup) = encrypt_steam_id_columns(steam_id)?;
 
     connection
         .execute(
-            "INSERT INTO users (id, email, password_hash, steam_id, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-            params![user_id, email, password_hash, steam_id, timestamp, timestamp],
+            "INSERT INTO users (id, email, password_hash, steam_id, steam_id_lookup, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                user_id,
+                email,
+                password_hash,
+                encrypted_steam_id,
+                steam_id_lookup,
+                timestamp,
+                timestamp
+            ],
         )
         .map_err(|error| format!("Failed to create user: {error}"))?;
 
@@ -7025,6 +11429,34 @@ fn create_user(
         .ok_or_else(|| String::from("Failed to load newly created user"))
 }
 
+fn find_user_password_hash(connection: &Connection, user_id: &str) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "SELECT password_hash FROM users WHERE id = ?1",
+            params![user_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query user password hash: {error}"))
+}
+
+fn is_placeholder_steam_account(user: &UserRow) -> bool {
+    user.steam_id.is_some() && user.email.starts_with("steam_") && user.email.ends_with("@steam.local")
+}
+
+fn delete_user_by_id(connection: &Connection, user_id: &str) -> Result<(), String> {
+    // Games, sessions, collections and per-game settings are removed via ON DELETE CASCADE.
+    let changed = connection
+        .execute("DELETE FROM users WHERE id = ?1", params![user_id])
+        .map_err(|error| format!("Failed to delete user: {error}"))?;
+
+    if changed == 0 {
+        return Err(String::from("User not found"));
+    }
+
+    Ok(())
+}
+
 fn create_steam_user(connection: &Connection, steam_id: &str) -> Result<UserRow, String> {
     let placeholder_email = format!("steam_{}@steam.local", Uuid::new_v4().simple());
     let placeholder_password_hash = hash(Uuid::new_v4().to_string(), DEFAULT_COST)
@@ -7052,10 +11484,16 @@ fn set_user_steam_id(
     }
 
     let updated_at = Utc::now().to_rfc3339();
+    let (encrypted_steam_id, steam_id_lookup) = encrypt_steam_id_columns(Some(steam_id))?;
     let changed = connection
         .execute(
-            "UPDATE users SET steam_id = ?1, updated_at = ?2 WHERE id = ?3",
-            params![steam_id, updated_at, user_id],
+            "
+            UPDATE users
+            SET steam_id = ?1, steam_id_lookup = ?2, steam_persona_name = NULL, steam_avatar_url = NULL,
+                steam_profile_url = NULL, steam_profile_updated_at = NULL, updated_at = ?3
+            WHERE id = ?4
+            ",
+            params![encrypted_steam_id, steam_id_lookup, updated_at, user_id],
         )
         .map_err(|error| format!("Failed to update Steam link for user: {error}"))?;
 
@@ -7097,18 +11535,23 @@ fn find_user_by_session_token(
 
     let user = connection
         .query_row(
-            "SELECT u.id, u.email, u.steam_id FROM sessions s JOIN users u ON u.id = s.user_id WHERE s.token_hash = ?1 AND s.expires_at > ?2",
+            "SELECT u.id, u.email, u.steam_id, u.steam_persona_name, u.steam_avatar_url, u.steam_profile_url FROM sessions s JOIN users u ON u.id = s.user_id WHERE s.token_hash = ?1 AND s.expires_at > ?2",
             params![token_hash, now],
             |row| {
                 Ok(UserRow {
                     id: row.get(0)?,
                     email: row.get(1)?,
                     steam_id: row.get(2)?,
+                    steam_persona_name: row.get(3)?,
+                    steam_avatar_url: row.get(4)?,
+                    steam_profile_url: row.get(5)?,
                 })
             },
         )
         .optional()
-        .map_err(|error| format!("Failed to query session user: {error}"))?;
+        .map_err(|error| format!("Failed to query session user: {error}"))?
+        .map(decrypt_user_row)
+        .transpose()?;
 
     if user.is_some() {
         connection
@@ -7133,6 +11576,65 @@ fn invalidate_session_by_token(connection: &Connection, session_token: &str) ->
     Ok(())
 }
 
+fn list_sessions_by_user(
+    connection: &Connection,
+    user_id: &str,
+    current_session_token: Option<&str>,
+) -> Result<Vec<SessionResponse>, String> {
+    let current_token_hash = current_session_token.map(hash_session_token);
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT token_hash, created_at, last_seen_at, expires_at
+            FROM sessions
+            WHERE user_id = ?1
+            ORDER BY last_seen_at DESC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare session list query: {error}"))?;
+
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query sessions: {error}"))?;
+
+    let mut sessions = Vec::new();
+    for row in rows {
+        let (token_hash, created_at, last_seen_at, expires_at) =
+            row.map_err(|error| format!("Failed to decode session row: {error}"))?;
+        sessions.push(SessionResponse {
+            current: current_token_hash.as_deref() == Some(token_hash.as_str()),
+            id: token_hash,
+            created_at,
+            last_seen_at,
+            expires_at,
+        });
+    }
+
+    Ok(sessions)
+}
+
+fn revoke_user_session(connection: &Connection, user_id: &str, session_id: &str) -> Result<(), String> {
+    let changed = connection
+        .execute(
+            "DELETE FROM sessions WHERE token_hash = ?1 AND user_id = ?2",
+            params![session_id, user_id],
+        )
+        .map_err(|error| format!("Failed to revoke session: {error}"))?;
+
+    if changed == 0 {
+        return Err(String::from("Session not found"));
+    }
+
+    Ok(())
+}
+
 fn cleanup_expired_sessions(connection: &Connection) -> Result<(), String> {
     connection
         .execute(
@@ -7166,19 +11668,30 @@ fn set_state_session_token(state: &AppState, session_token: Option<String>) -> R
 }
 
 fn persist_active_session(state: &AppState, session_token: &str) -> Result<(), String> {
-    persist_session_token(&state.session_token_path, session_token)?;
+    persist_session_token(
+        &state.session_token_path,
+        &field_encryption::encrypt_field(session_token)?,
+    )?;
     set_state_session_token(state, Some(session_token.to_owned()))
 }
 
 fn clear_active_session(state: &AppState) -> Result<(), String> {
+    private_games::lock_private_games(state);
     clear_session_token_file(&state.session_token_path)?;
     set_state_session_token(state, None)
 }
 
 fn restore_persisted_session(state: &AppState) -> Result<(), String> {
-    let Some(session_token) = read_session_token(&state.session_token_path)? else {
+    let Some(stored_token) = read_session_token(&state.session_token_path)? else {
         return Ok(());
     };
+    // Token files written before field encryption are plain text; rewrite them encrypted.
+    let Ok(session_token) = field_encryption::decrypt_field(&stored_token) else {
+        return clear_active_session(state);
+    };
+    if !field_encryption::is_encrypted_field(&stored_token) {
+        persist_active_session(state, &session_token)?;
+    }
 
     let connection = open_connection(&state.db_path)?;
     cleanup_expired_sessions(&connection)?;
@@ -7233,17 +11746,134 @@ fn persist_session_token(session_path: &Path, session_token: &str) -> Result<(),
     }
 }
 
-fn clear_session_token_file(session_path: &Path) -> Result<(), String> {
-    match fs::remove_file(session_path) {
-        Ok(()) => Ok(()),
-        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
-        Err(error) => Err(format!("Failed to clear session token file: {error}")),
+fn clear_session_token_file(session_path: &Path) -> Result<(), String> {
+    match fs::remove_file(session_path) {
+        Ok(()) => Ok(()),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(error) => Err(format!("Failed to clear session token file: {error}")),
+    }
+}
+
+fn steam_min_request_interval(request_url: &Url) -> Duration {
+    match request_url.host_str().unwrap_or_default() {
+        "store.steampowered.com" => STEAM_STORE_MIN_REQUEST_INTERVAL,
+        "steamcommunity.com" => STEAM_COMMUNITY_MIN_REQUEST_INTERVAL,
+        _ => STEAM_WEB_API_MIN_REQUEST_INTERVAL,
+    }
+}
+
+fn wait_for_steam_request_slot(request_url: &Url) {
+    let endpoint_key = format!(
+        "{}{}",
+        request_url.host_str().unwrap_or_default(),
+        request_url.path()
+    );
+    let min_interval = steam_min_request_interval(request_url);
+
+    // Reserve the next slot while holding the lock, then sleep outside of it so
+    // concurrent callers queue up behind each other instead of bursting.
+    let scheduled_at = match STEAM_REQUEST_SCHEDULE.lock() {
+        Ok(mut schedule) => {
+            let now = Instant::now();
+            let next_slot = schedule
+                .get(&endpoint_key)
+                .map(|last_slot| (*last_slot + min_interval).max(now))
+                .unwrap_or(now);
+            schedule.insert(endpoint_key, next_slot);
+            next_slot
+        }
+        Err(_) => return,
+    };
+
+    let now = Instant::now();
+    if scheduled_at > now {
+        thread::sleep(scheduled_at - now);
+    }
+}
+
+fn steam_backoff_delay(attempt: u32, retry_after: Option<Duration>) -> Duration {
+    let exponential_millis = STEAM_HTTP_BACKOFF_BASE_MILLIS
+        .saturating_mul(1_u64 << attempt.min(16))
+        .min(STEAM_HTTP_BACKOFF_MAX_MILLIS);
+    let jitter_seed = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| u64::from(duration.subsec_nanos()))
+        .unwrap_or(0);
+    let jitter_millis = jitter_seed % (exponential_millis / 2 + 1);
+    let backoff = Duration::from_millis(exponential_millis + jitter_millis);
+
+    // `Retry-After` comes from the server, so it is capped like the backoff itself.
+    retry_after
+        .map(|delay| delay.min(Duration::from_millis(STEAM_HTTP_BACKOFF_MAX_MILLIS)).max(backoff))
+        .unwrap_or(backoff)
+}
+
+fn is_retryable_steam_status(status: reqwest::StatusCode) -> bool {
+    status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// Shared GET path for every Steam endpoint; see `send_limited_request`.
+fn send_steam_get_request(
+    client: &Client,
+    request_url: Url,
+) -> reqwest::Result<reqwest::blocking::Response> {
+    send_limited_request(&request_url, || client.get(request_url.clone()))
+}
+
+/// Sends a request built by `build_request` for `request_url`, applying per-endpoint rate
+/// limiting and retrying 429/5xx responses and transient transport errors with jittered
+/// backoff. The builder runs once per attempt, so POST bodies are rebuilt for each retry.
+fn send_limited_request(
+    request_url: &Url,
+    build_request: impl Fn() -> reqwest::blocking::RequestBuilder,
+) -> reqwest::Result<reqwest::blocking::Response> {
+    let mut attempt = 0;
+    loop {
+        wait_for_steam_request_slot(request_url);
+        let result = build_request().send();
+        let can_retry = attempt < STEAM_HTTP_MAX_RETRIES;
+
+        match result {
+            Ok(response) if can_retry && is_retryable_steam_status(response.status()) => {
+                let retry_after = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|value| value.to_str().ok())
+                    .and_then(|value| value.trim().parse::<u64>().ok())
+                    .map(Duration::from_secs);
+                eprintln!(
+                    "Request to {}{} returned {}; retrying (attempt {}/{}).",
+                    request_url.host_str().unwrap_or_default(),
+                    request_url.path(),
+                    response.status(),
+                    attempt + 1,
+                    STEAM_HTTP_MAX_RETRIES
+                );
+                thread::sleep(steam_backoff_delay(attempt, retry_after));
+            }
+            Err(error) if can_retry && (error.is_timeout() || error.is_connect()) => {
+                eprintln!(
+                    "Request to {}{} failed ({error}); retrying (attempt {}/{}).",
+                    request_url.host_str().unwrap_or_default(),
+                    request_url.path(),
+                    attempt + 1,
+                    STEAM_HTTP_MAX_RETRIES
+                );
+                thread::sleep(steam_backoff_delay(attempt, None));
+            }
+            other => return other,
+        }
+
+        attempt += 1;
     }
 }
 
-fn build_http_client() -> Result<Client, String> {
-    Client::builder()
-        .timeout(Duration::from_secs(20))
+fn build_http_client(http_proxy: Option<&http_proxy::HttpProxyConfig>) -> Result<Client, String> {
+    let mut builder = Client::builder().timeout(Duration::from_secs(20));
+    if let Some(http_proxy) = http_proxy {
+        builder = builder.proxy(http_proxy::build_reqwest_proxy(http_proxy)?);
+    }
+    builder
         .build()
         .map_err(|error| format!("Failed to initialize HTTP client: {error}"))
 }
@@ -7256,6 +11886,9 @@ fn public_user_from_row(user: &UserRow) -> PublicUser {
         email: user.email.clone(),
         steam_linked: user.steam_id.is_some(),
         steam_id: user.steam_id.clone(),
+        steam_persona_name: user.steam_persona_name.clone(),
+        steam_avatar_url: user.steam_avatar_url.clone(),
+        steam_profile_url: user.steam_profile_url.clone(),
     }
 }
 
@@ -7285,10 +11918,156 @@ fn initialize_database(db_path: &Path) -> Result<(), String> {
               email TEXT NOT NULL UNIQUE,
               password_hash TEXT NOT NULL,
               steam_id TEXT UNIQUE,
+              steam_id_lookup TEXT,
+              steam_persona_name TEXT,
+              steam_avatar_url TEXT,
+              steam_profile_url TEXT,
+              steam_profile_updated_at TEXT,
               created_at TEXT NOT NULL,
               updated_at TEXT NOT NULL
             );
 
+            CREATE TABLE IF NOT EXISTS pending_steam_config_writes (
+              user_id TEXT NOT NULL,
+              app_id TEXT NOT NULL,
+              queued_at TEXT NOT NULL,
+              PRIMARY KEY (user_id, app_id),
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS steam_download_queue (
+              user_id TEXT NOT NULL,
+              app_id TEXT NOT NULL,
+              queue_position INTEGER,
+              paused INTEGER NOT NULL DEFAULT 0,
+              paused_by_schedule INTEGER NOT NULL DEFAULT 0,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY (user_id, app_id),
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS steam_download_history (
+              app_id TEXT NOT NULL,
+              started_at TEXT NOT NULL,
+              finished_at TEXT,
+              bytes_downloaded INTEGER NOT NULL DEFAULT 0,
+              bytes_total INTEGER NOT NULL DEFAULT 0,
+              status TEXT NOT NULL,
+              PRIMARY KEY (app_id, started_at)
+            );
+
+            CREATE TABLE IF NOT EXISTS notifications (
+              id TEXT PRIMARY KEY,
+              user_id TEXT NOT NULL,
+              kind TEXT NOT NULL,
+              title TEXT NOT NULL,
+              body TEXT NOT NULL,
+              provider TEXT,
+              external_id TEXT,
+              read_at TEXT,
+              dispatched_at TEXT,
+              created_at TEXT NOT NULL,
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_notifications_user_created
+              ON notifications(user_id, created_at);
+
+            CREATE TABLE IF NOT EXISTS game_verifications (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              started_at TEXT,
+              validating_seen INTEGER NOT NULL DEFAULT 0,
+              last_verified_at TEXT,
+              PRIMARY KEY (user_id, provider, external_id),
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS install_jobs (
+              id TEXT PRIMARY KEY,
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              source_path TEXT NOT NULL,
+              install_path TEXT NOT NULL,
+              create_desktop_shortcut INTEGER NOT NULL DEFAULT 0,
+              create_application_shortcut INTEGER NOT NULL DEFAULT 0,
+              status TEXT NOT NULL,
+              bytes_total INTEGER NOT NULL DEFAULT 0,
+              bytes_done INTEGER NOT NULL DEFAULT 0,
+              error TEXT,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_install_jobs_status_created
+              ON install_jobs(status, created_at);
+
+            CREATE TABLE IF NOT EXISTS play_sessions (
+              id TEXT PRIMARY KEY,
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              started_at TEXT NOT NULL,
+              ended_at TEXT,
+              duration_minutes INTEGER NOT NULL DEFAULT 0,
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_play_sessions_user_started
+              ON play_sessions(user_id, started_at);
+
+            CREATE TABLE IF NOT EXISTS custom_game_launch_targets (
+              user_id TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              target TEXT NOT NULL,
+              arguments TEXT,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY (user_id, external_id),
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_identity_links (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              group_key TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              PRIMARY KEY (user_id, provider, external_id),
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_merge_preferences (
+              user_id TEXT NOT NULL,
+              group_key TEXT NOT NULL,
+              preferred_provider TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY (user_id, group_key),
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS user_two_factor (
+              user_id TEXT PRIMARY KEY,
+              encrypted_secret TEXT NOT NULL,
+              recovery_code_hashes TEXT NOT NULL DEFAULT '[]',
+              enabled_at TEXT,
+              last_used_step INTEGER,
+              created_at TEXT NOT NULL,
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS private_games_pins (
+              user_id TEXT PRIMARY KEY,
+              pin_hash TEXT NOT NULL,
+              failed_attempts INTEGER NOT NULL DEFAULT 0,
+              locked_until TEXT,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
             CREATE TABLE IF NOT EXISTS sessions (
               token_hash TEXT PRIMARY KEY,
               user_id TEXT NOT NULL,
@@ -7308,10 +12087,12 @@ fn initialize_database(db_path: &Path) -> Result<(), String> {
               name TEXT NOT NULL,
               kind TEXT NOT NULL DEFAULT 'unknown',
               playtime_minutes INTEGER NOT NULL,
+              playtime_2weeks_minutes INTEGER NOT NULL DEFAULT 0,
               installed INTEGER NOT NULL DEFAULT 0,
               artwork_url TEXT,
                             last_synced_at TEXT NOT NULL,
                             last_played_at TEXT,
+              added_at TEXT,
               PRIMARY KEY (user_id, provider, external_id),
               FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE
             );
@@ -7334,6 +12115,8 @@ fn initialize_database(db_path: &Path) -> Result<(), String> {
               id TEXT PRIMARY KEY,
               user_id TEXT NOT NULL,
               name TEXT NOT NULL COLLATE NOCASE,
+              cover_image_path TEXT,
+              accent_color TEXT,
               created_at TEXT NOT NULL,
               updated_at TEXT NOT NULL,
               UNIQUE (user_id, name),
@@ -7348,6 +12131,7 @@ fn initialize_database(db_path: &Path) -> Result<(), String> {
               collection_id TEXT NOT NULL,
               provider TEXT NOT NULL,
               external_id TEXT NOT NULL,
+              position INTEGER,
               created_at TEXT NOT NULL,
               PRIMARY KEY (user_id, collection_id, provider, external_id),
               FOREIGN KEY (user_id, provider, external_id) REFERENCES games(user_id, provider, external_id) ON DELETE CASCADE,
@@ -7391,66 +12175,422 @@ fn initialize_database(db_path: &Path) -> Result<(), String> {
               fetched_at TEXT NOT NULL
             );
 
-            CREATE INDEX IF NOT EXISTS idx_steam_app_metadata_fetched_at ON steam_app_metadata(fetched_at);
+            CREATE INDEX IF NOT EXISTS idx_steam_app_metadata_fetched_at ON steam_app_metadata(fetched_at);
+
+            CREATE TABLE IF NOT EXISTS steam_app_languages (
+              app_id TEXT PRIMARY KEY,
+              languages_json TEXT NOT NULL,
+              fetched_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_steam_app_languages_fetched_at ON steam_app_languages(fetched_at);
+
+            CREATE TABLE IF NOT EXISTS steam_app_betas (
+              app_id TEXT PRIMARY KEY,
+              betas_json TEXT NOT NULL,
+              fetched_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_steam_app_betas_fetched_at ON steam_app_betas(fetched_at);
+
+            CREATE TABLE IF NOT EXISTS steam_app_store_tags (
+              app_id TEXT PRIMARY KEY,
+              tags_json TEXT NOT NULL,
+              fetched_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS steam_app_review_summaries (
+              app_id TEXT PRIMARY KEY,
+              score_description TEXT NOT NULL,
+              total_positive INTEGER NOT NULL,
+              total_reviews INTEGER NOT NULL,
+              fetched_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS steam_app_size_estimates (
+              app_id TEXT PRIMARY KEY,
+              size_bytes INTEGER,
+              fetched_at TEXT NOT NULL
+            );
+
+                        CREATE TABLE IF NOT EXISTS game_genres (
+                            user_id TEXT NOT NULL,
+                            provider TEXT NOT NULL,
+                            external_id TEXT NOT NULL,
+                            genre TEXT NOT NULL,
+                            PRIMARY KEY (user_id, provider, external_id, genre),
+                            FOREIGN KEY (user_id, provider, external_id) REFERENCES games(user_id, provider, external_id) ON DELETE CASCADE
+                        );
+
+                        CREATE INDEX IF NOT EXISTS idx_game_genres_user_game ON game_genres(user_id, provider, external_id);
+
+                        CREATE TABLE IF NOT EXISTS steam_app_details (
+                            app_id TEXT PRIMARY KEY,
+                            details_json TEXT NOT NULL,
+                            fetched_at TEXT NOT NULL
+                        );
+
+                        CREATE INDEX IF NOT EXISTS idx_steam_app_details_fetched_at ON steam_app_details(fetched_at);
+
+                                    CREATE TABLE IF NOT EXISTS steam_app_features (
+                                        app_id TEXT PRIMARY KEY,
+                                        has_achievements INTEGER NOT NULL DEFAULT 0,
+                                        achievements_count INTEGER,
+                                        has_cloud_saves INTEGER NOT NULL DEFAULT 0,
+                                        cloud_details TEXT,
+                                        controller_support TEXT,
+                                        fetched_at TEXT NOT NULL
+                                    );
+
+                                    CREATE INDEX IF NOT EXISTS idx_steam_app_features_fetched_at ON steam_app_features(fetched_at);
+
+            CREATE INDEX IF NOT EXISTS idx_steam_app_store_tags_fetched_at ON steam_app_store_tags(fetched_at);
+
+            CREATE TABLE IF NOT EXISTS steam_app_points_shop_items (
+              app_id TEXT PRIMARY KEY,
+              items_json TEXT NOT NULL,
+              fetched_at TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_steam_app_points_shop_items_fetched_at ON steam_app_points_shop_items(fetched_at);
+
+            CREATE TABLE IF NOT EXISTS app_settings (
+              key TEXT PRIMARY KEY,
+              value TEXT NOT NULL,
+              updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS emulator_profiles (
+              id TEXT PRIMARY KEY,
+              user_id TEXT NOT NULL,
+              name TEXT NOT NULL,
+              executable_path TEXT NOT NULL,
+              args_template TEXT NOT NULL,
+              extensions_json TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS launch_templates (
+              id TEXT PRIMARY KEY,
+              user_id TEXT NOT NULL,
+              name TEXT NOT NULL,
+              launch_options TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS rom_directories (
+              id TEXT PRIMARY KEY,
+              user_id TEXT NOT NULL,
+              path TEXT NOT NULL,
+              profile_id TEXT NOT NULL,
+              created_at TEXT NOT NULL,
+              UNIQUE(user_id, path),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+              FOREIGN KEY(profile_id) REFERENCES emulator_profiles(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS rom_files (
+              user_id TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              path TEXT NOT NULL,
+              profile_id TEXT NOT NULL,
+              size_bytes INTEGER NOT NULL,
+              scanned_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, external_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+              FOREIGN KEY(profile_id) REFERENCES emulator_profiles(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_save_locations (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              path_template TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, provider, external_id, path_template),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_achievement_progress (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              unlocked_count INTEGER NOT NULL,
+              total_count INTEGER NOT NULL,
+              completed INTEGER NOT NULL DEFAULT 0,
+              refreshed_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, provider, external_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_screenshot_directories (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              directory TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, provider, external_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_notes (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              notes TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, provider, external_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS steam_local_vr_apps (
+              app_id TEXT PRIMARY KEY
+            );
+
+            CREATE TABLE IF NOT EXISTS game_display_names (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              display_name TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, provider, external_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_user_tags (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              tag TEXT NOT NULL COLLATE NOCASE,
+              created_at TEXT NOT NULL,
+              PRIMARY KEY (user_id, provider, external_id, tag),
+              FOREIGN KEY (user_id, provider, external_id) REFERENCES games(user_id, provider, external_id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS game_custom_artwork (
+              user_id TEXT NOT NULL,
+              provider TEXT NOT NULL,
+              external_id TEXT NOT NULL,
+              artwork_path TEXT NOT NULL,
+              updated_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, provider, external_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS discovery_follows (
+              user_id TEXT NOT NULL,
+              kind TEXT NOT NULL,
+              source_id TEXT NOT NULL,
+              name TEXT NOT NULL,
+              followed_at TEXT NOT NULL,
+              last_fetched_at TEXT,
+              PRIMARY KEY(user_id, kind, source_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS discovery_feed_items (
+              user_id TEXT NOT NULL,
+              source TEXT NOT NULL,
+              app_id TEXT NOT NULL,
+              name TEXT NOT NULL,
+              detail TEXT,
+              discount_percent INTEGER,
+              price TEXT,
+              position INTEGER NOT NULL,
+              fetched_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, source, app_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS developer_watches (
+              user_id TEXT NOT NULL,
+              name TEXT NOT NULL,
+              role TEXT NOT NULL,
+              owned_games INTEGER NOT NULL,
+              last_scanned_at TEXT,
+              PRIMARY KEY(user_id, name),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+
+            CREATE TABLE IF NOT EXISTS developer_releases (
+              user_id TEXT NOT NULL,
+              developer TEXT NOT NULL,
+              app_id TEXT NOT NULL,
+              name TEXT NOT NULL,
+              release_date TEXT,
+              baseline INTEGER NOT NULL,
+              first_seen_at TEXT NOT NULL,
+              PRIMARY KEY(user_id, developer, app_id),
+              FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+            );
+            ",
+        )
+        .map_err(|error| format!("Failed to run SQLite migrations: {error}"))?;
+    migrate_games_table(&connection)?;
+    migrate_users_table(&connection)?;
+    migrate_steam_app_metadata_table(&connection)?;
+    migrate_collections_table(&connection)?;
+    migrate_play_sessions_table(&connection)?;
+    migrate_user_two_factor_table(&connection)?;
+    migrate_private_games_pins_table(&connection)?;
+    library_search::migrate_game_search_index(&connection)?;
+
+    Ok(())
+}
+
+fn migrate_users_table(connection: &Connection) -> Result<(), String> {
+    for column in [
+        "steam_persona_name",
+        "steam_avatar_url",
+        "steam_profile_url",
+        "steam_profile_updated_at",
+        "steam_id_lookup",
+    ] {
+        if !table_has_column(connection, "users", column)? {
+            connection
+                .execute(&format!("ALTER TABLE users ADD COLUMN {column} TEXT"), [])
+                .map_err(|error| {
+                    format!("Failed to migrate users table with {column} column: {error}")
+                })?;
+        }
+    }
+    connection
+        .execute(
+            "CREATE UNIQUE INDEX IF NOT EXISTS idx_users_steam_id_lookup ON users(steam_id_lookup)",
+            [],
+        )
+        .map_err(|error| format!("Failed to create Steam ID lookup index: {error}"))?;
 
-            CREATE TABLE IF NOT EXISTS steam_app_languages (
-              app_id TEXT PRIMARY KEY,
-              languages_json TEXT NOT NULL,
-              fetched_at TEXT NOT NULL
-            );
+    encrypt_plaintext_steam_ids(connection)
+}
 
-            CREATE INDEX IF NOT EXISTS idx_steam_app_languages_fetched_at ON steam_app_languages(fetched_at);
+/// Databases created before field encryption hold Steam IDs in plain text.
+fn encrypt_plaintext_steam_ids(connection: &Connection) -> Result<(), String> {
+    let mut statement = connection
+        .prepare("SELECT id, steam_id FROM users WHERE steam_id IS NOT NULL")
+        .map_err(|error| format!("Failed to prepare Steam ID migration query: {error}"))?;
+    let rows = statement
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|error| format!("Failed to query Steam IDs for migration: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode Steam ID migration row: {error}"))?;
 
-            CREATE TABLE IF NOT EXISTS steam_app_betas (
-              app_id TEXT PRIMARY KEY,
-              betas_json TEXT NOT NULL,
-              fetched_at TEXT NOT NULL
-            );
+    for (user_id, steam_id) in rows
+        .into_iter()
+        .filter(|(_, steam_id)| !field_encryption::is_encrypted_field(steam_id))
+    {
+        let (encrypted_steam_id, steam_id_lookup) = encrypt_steam_id_columns(Some(&steam_id))?;
+        connection
+            .execute(
+                "UPDATE users SET steam_id = ?1, steam_id_lookup = ?2 WHERE id = ?3",
+                params![encrypted_steam_id, steam_id_lookup, user_id],
+            )
+            .map_err(|error| format!("Failed to encrypt stored Steam ID: {error}"))?;
+    }
 
-            CREATE INDEX IF NOT EXISTS idx_steam_app_betas_fetched_at ON steam_app_betas(fetched_at);
+    Ok(())
+}
 
-            CREATE TABLE IF NOT EXISTS steam_app_store_tags (
-              app_id TEXT PRIMARY KEY,
-              tags_json TEXT NOT NULL,
-              fetched_at TEXT NOT NULL
-            );
+fn migrate_collections_table(connection: &Connection) -> Result<(), String> {
+    for column in ["cover_image_path", "accent_color"] {
+        if !table_has_column(connection, "collections", column)? {
+            connection
+                .execute(&format!("ALTER TABLE collections ADD COLUMN {column} TEXT"), [])
+                .map_err(|error| {
+                    format!("Failed to migrate collections table with {column} column: {error}")
+                })?;
+        }
+    }
+    if !table_has_column(connection, "collection_games", "position")? {
+        connection
+            .execute("ALTER TABLE collection_games ADD COLUMN position INTEGER", [])
+            .map_err(|error| format!("Failed to migrate collection games table with position column: {error}"))?;
+    }
 
-                        CREATE TABLE IF NOT EXISTS game_genres (
-                            user_id TEXT NOT NULL,
-                            provider TEXT NOT NULL,
-                            external_id TEXT NOT NULL,
-                            genre TEXT NOT NULL,
-                            PRIMARY KEY (user_id, provider, external_id, genre),
-                            FOREIGN KEY (user_id, provider, external_id) REFERENCES games(user_id, provider, external_id) ON DELETE CASCADE
-                        );
+    Ok(())
+}
 
-                        CREATE INDEX IF NOT EXISTS idx_game_genres_user_game ON game_genres(user_id, provider, external_id);
+fn migrate_play_sessions_table(connection: &Connection) -> Result<(), String> {
+    for (column, column_type) in [("notes", "TEXT"), ("rating", "INTEGER"), ("annotated_at", "TEXT")] {
+        if !table_has_column(connection, "play_sessions", column)? {
+            connection
+                .execute(&format!("ALTER TABLE play_sessions ADD COLUMN {column} {column_type}"), [])
+                .map_err(|error| format!("Failed to migrate play sessions table with {column} column: {error}"))?;
+        }
+    }
 
-                        CREATE TABLE IF NOT EXISTS steam_app_details (
-                            app_id TEXT PRIMARY KEY,
-                            details_json TEXT NOT NULL,
-                            fetched_at TEXT NOT NULL
-                        );
+    Ok(())
+}
 
-                        CREATE INDEX IF NOT EXISTS idx_steam_app_details_fetched_at ON steam_app_details(fetched_at);
+fn migrate_user_two_factor_table(connection: &Connection) -> Result<(), String> {
+    if !table_has_column(connection, "user_two_factor", "last_used_step")? {
+        connection
+            .execute("ALTER TABLE user_two_factor ADD COLUMN last_used_step INTEGER", [])
+            .map_err(|error| {
+                format!("Failed to migrate two-factor table with last_used_step column: {error}")
+            })?;
+    }
 
-                                    CREATE TABLE IF NOT EXISTS steam_app_features (
-                                        app_id TEXT PRIMARY KEY,
-                                        has_achievements INTEGER NOT NULL DEFAULT 0,
-                                        achievements_count INTEGER,
-                                        has_cloud_saves INTEGER NOT NULL DEFAULT 0,
-                                        cloud_details TEXT,
-                                        controller_support TEXT,
-                                        fetched_at TEXT NOT NULL
-                                    );
+    Ok(())
+}
 
-                                    CREATE INDEX IF NOT EXISTS idx_steam_app_features_fetched_at ON steam_app_features(fetched_at);
+fn migrate_private_games_pins_table(connection: &Connection) -> Result<(), String> {
+    for (column, column_type) in [
+        ("failed_attempts", "INTEGER NOT NULL DEFAULT 0"),
+        ("locked_until", "TEXT"),
+    ] {
+        if !table_has_column(connection, "private_games_pins", column)? {
+            connection
+                .execute(
+                    &format!("ALTER TABLE private_games_pins ADD COLUMN {column} {column_type}"),
+                    [],
+                )
+                .map_err(|error| {
+                    format!("Failed to migrate private games PIN table with {column} column: {error}")
+                })?;
+        }
+    }
 
-            CREATE INDEX IF NOT EXISTS idx_steam_app_store_tags_fetched_at ON steam_app_store_tags(fetched_at);
-            ",
-        )
-        .map_err(|error| format!("Failed to run SQLite migrations: {error}"))?;
-    migrate_games_table(&connection)?;
+    Ok(())
+}
+
+fn migrate_steam_app_metadata_table(connection: &Connection) -> Result<(), String> {
+    let mut added_columns = false;
+    for (column, column_type) in [
+        ("genres_json", "TEXT"),
+        ("categories_json", "TEXT"),
+        ("developers_json", "TEXT"),
+        ("publishers_json", "TEXT"),
+        ("release_date", "TEXT"),
+        ("metacritic_score", "INTEGER"),
+        ("recommendations_total", "INTEGER"),
+        ("controller_support", "TEXT"),
+        ("vr_support", "TEXT"),
+    ] {
+        if !table_has_column(connection, "steam_app_metadata", column)? {
+            connection
+                .execute(
+                    &format!("ALTER TABLE steam_app_metadata ADD COLUMN {column} {column_type}"),
+                    [],
+                )
+                .map_err(|error| {
+                    format!("Failed to migrate Steam app metadata table with {column} column: {error}")
+                })?;
+            added_columns = true;
+        }
+    }
+
+    // Rows cached before a column existed have no value for it; expire them so the next sync
+    // fetches the rest.
+    if added_columns {
+        connection
+            .execute("UPDATE steam_app_metadata SET fetched_at = ''", [])
+            .map_err(|error| format!("Failed to expire cached Steam app metadata: {error}"))?;
+    }
 
     Ok(())
 }
@@ -7487,21 +12627,52 @@ fn migrate_games_table(connection: &Connection) -> Result<(), String> {
             })?;
     }
 
+    if !games_table_has_column(connection, "playtime_2weeks_minutes")? {
+        connection
+            .execute(
+                "ALTER TABLE games ADD COLUMN playtime_2weeks_minutes INTEGER NOT NULL DEFAULT 0",
+                [],
+            )
+            .map_err(|error| {
+                format!("Failed to migrate games table with playtime_2weeks_minutes column: {error}")
+            })?;
+    }
+
+    if !games_table_has_column(connection, "added_at")? {
+        // Existing rows have no first-seen time; their last sync is the closest approximation.
+        connection
+            .execute_batch(
+                "
+                ALTER TABLE games ADD COLUMN added_at TEXT;
+                UPDATE games SET added_at = last_synced_at WHERE added_at IS NULL;
+                ",
+            )
+            .map_err(|error| format!("Failed to migrate games table with added_at column: {error}"))?;
+    }
+
     Ok(())
 }
 
 fn games_table_has_column(connection: &Connection, expected_column: &str) -> Result<bool, String> {
+    table_has_column(connection, "games", expected_column)
+}
+
+fn table_has_column(
+    connection: &Connection,
+    table: &str,
+    expected_column: &str,
+) -> Result<bool, String> {
     let mut statement = connection
-        .prepare("PRAGMA table_info(games)")
-        .map_err(|error| format!("Failed to inspect games table schema: {error}"))?;
+        .prepare(&format!("PRAGMA table_info({table})"))
+        .map_err(|error| format!("Failed to inspect {table} table schema: {error}"))?;
 
     let rows = statement
         .query_map([], |row| row.get::<_, String>(1))
-        .map_err(|error| format!("Failed to query games table schema: {error}"))?;
+        .map_err(|error| format!("Failed to query {table} table schema: {error}"))?;
 
     for row in rows {
         let column_name =
-            row.map_err(|error| format!("Failed to decode games table schema row: {error}"))?;
+            row.map_err(|error| format!("Failed to decode {table} table schema row: {error}"))?;
         if column_name == expected_column {
             return Ok(true);
         }
@@ -7510,16 +12681,453 @@ fn games_table_has_column(connection: &Connection, expected_column: &str) -> Res
     Ok(false)
 }
 
+fn load_app_settings(connection: &Connection) -> Result<HashMap<String, String>, String> {
+    let mut statement = connection
+        .prepare("SELECT key, value FROM app_settings")
+        .map_err(|error| format!("Failed to prepare app settings query: {error}"))?;
+    let rows = statement
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|error| format!("Failed to query app settings: {error}"))?;
+
+    let mut settings = HashMap::new();
+    for row in rows {
+        let (key, value) = row.map_err(|error| format!("Failed to decode app setting row: {error}"))?;
+        settings.insert(key, value);
+    }
+
+    Ok(settings)
+}
+
+fn save_app_setting(connection: &Connection, key: &str, value: Option<&str>) -> Result<(), String> {
+    match value {
+        Some(value) => connection
+            .execute(
+                "
+                INSERT INTO app_settings (key, value, updated_at)
+                VALUES (?1, ?2, ?3)
+                ON CONFLICT(key) DO UPDATE SET
+                  value = excluded.value,
+                  updated_at = excluded.updated_at
+                ",
+                params![key, value, Utc::now().to_rfc3339()],
+            )
+            .map_err(|error| format!("Failed to save app setting {key}: {error}"))?,
+        None => connection
+            .execute("DELETE FROM app_settings WHERE key = ?1", params![key])
+            .map_err(|error| format!("Failed to clear app setting {key}: {error}"))?,
+    };
+
+    Ok(())
+}
+
+fn parse_app_setting_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Applies persisted settings on top of the env-derived defaults already held in `state`.
+fn apply_app_settings(state: &AppState, settings: &HashMap<String, String>) {
+    if let Some(locale) = settings.get(APP_SETTING_LOCALE) {
+        i18n::set_active_locale(locale);
+    }
+    if let Some(api_key) = settings.get(APP_SETTING_STEAM_API_KEY) {
+        let api_key = field_encryption::decrypt_field(api_key).unwrap_or_else(|error| {
+            eprintln!("Failed to decrypt stored Steam API key: {error}");
+            String::new()
+        });
+        if let Ok(mut guard) = state.steam_api_key.lock() {
+            *guard = Some(api_key).filter(|value| !value.trim().is_empty());
+        }
+    }
+    if let Some(steam_root) = settings.get(APP_SETTING_STEAM_ROOT_OVERRIDE) {
+        if let Ok(mut guard) = state.steam_root_override.lock() {
+            *guard = Some(steam_root.clone()).filter(|value| !value.trim().is_empty());
+        }
+    }
+    if let Some(interval) = settings
+        .get(APP_SETTING_STEAM_SYNC_INTERVAL_MINUTES)
+        .and_then(|value| value.trim().parse::<u64>().ok())
+    {
+        state
+            .steam_sync_interval_minutes
+            .store(interval, Ordering::SeqCst);
+    }
+    if let Some(enabled) = settings
+        .get(APP_SETTING_STEAM_LOCAL_INSTALL_DETECTION)
+        .and_then(|value| parse_app_setting_flag(value))
+    {
+        state
+            .steam_local_install_detection
+            .store(enabled, Ordering::SeqCst);
+    }
+    if let Some(enabled) = settings
+        .get(APP_SETTING_STEAM_SETTINGS_DEBUG_LOGGING)
+        .and_then(|value| parse_app_setting_flag(value))
+    {
+        state
+            .steam_settings_debug_logging
+            .store(enabled, Ordering::SeqCst);
+    }
+    if let Some(enabled) = settings
+        .get(APP_SETTING_MINIMIZE_TO_TRAY)
+        .and_then(|value| parse_app_setting_flag(value))
+    {
+        state.minimize_to_tray.store(enabled, Ordering::SeqCst);
+    }
+    if let Some(enabled) = settings
+        .get(APP_SETTING_DISCORD_RICH_PRESENCE)
+        .and_then(|value| parse_app_setting_flag(value))
+    {
+        state.discord_rich_presence.store(enabled, Ordering::SeqCst);
+    }
+    if let Some(client_id) = settings.get(APP_SETTING_DISCORD_CLIENT_ID) {
+        if let Ok(mut guard) = state.discord_client_id.lock() {
+            *guard = Some(client_id.clone()).filter(|value| !value.trim().is_empty());
+        }
+    }
+    if let Some(enabled) = settings
+        .get(APP_SETTING_NATIVE_NOTIFICATIONS)
+        .and_then(|value| parse_app_setting_flag(value))
+    {
+        state.native_notifications.store(enabled, Ordering::SeqCst);
+    }
+    if let Some(enabled) = settings
+        .get(APP_SETTING_SHOW_NON_GAME_KINDS)
+        .and_then(|value| parse_app_setting_flag(value))
+    {
+        state.show_non_game_kinds.store(enabled, Ordering::SeqCst);
+    }
+}
+
+fn read_env_steam_api_key() -> Option<String> {
+    std::env::var("STEAM_API_KEY")
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+/// Loads the Steam API key from the OS keychain, which takes precedence over the settings table
+/// and `STEAM_API_KEY`. A key saved in the settings table by an older build, or while the
+/// keychain was unavailable, is moved into the keychain.
+fn restore_keychain_steam_api_key(
+    state: &AppState,
+    connection: &Connection,
+    settings: &HashMap<String, String>,
+) {
+    match secret_store::read_secret(secret_store::STEAM_API_KEY_ACCOUNT) {
+        Ok(Some(api_key)) => {
+            if let Ok(mut guard) = state.steam_api_key.lock() {
+                *guard = Some(api_key).filter(|value| !value.trim().is_empty());
+            }
+        }
+        Ok(None) => {
+            let Some(api_key) = settings
+                .get(APP_SETTING_STEAM_API_KEY)
+                .and_then(|value| field_encryption::decrypt_field(value).ok())
+                .filter(|value| !value.trim().is_empty())
+            else {
+                return;
+            };
+            let migrated = secret_store::write_secret(secret_store::STEAM_API_KEY_ACCOUNT, &api_key)
+                .and_then(|_| save_app_setting(connection, APP_SETTING_STEAM_API_KEY, None));
+            if let Err(error) = migrated {
+                eprintln!("Failed to move Steam API key into the OS keychain: {error}");
+            }
+        }
+        Err(error) => eprintln!("{error}"),
+    }
+}
+
+fn app_settings_response(state: &AppState) -> AppSettingsResponse {
+    AppSettingsResponse {
+        steam_api_key_configured: state.steam_api_key().is_some(),
+        steam_root_override: state.steam_root_override(),
+        sync_interval_minutes: state.steam_sync_interval_minutes(),
+        steam_local_install_detection: state.steam_local_install_detection(),
+        steam_settings_debug_logging: state.steam_settings_debug_logging(),
+        minimize_to_tray: state.minimize_to_tray(),
+        discord_rich_presence: state.discord_rich_presence.load(Ordering::SeqCst),
+        discord_client_id: state
+            .discord_client_id
+            .lock()
+            .ok()
+            .and_then(|value| value.clone()),
+        native_notifications: state.native_notifications(),
+        show_non_game_kinds: state.show_non_game_kinds(),
+    }
+}
+
+/// Reads the app ID Steam reports as currently running, if any.
+fn detect_running_steam_app_id() -> Option<u64> {
+    if cfg!(target_os = "windows") {
+        let output = Command::new("reg")
+            .args(["query", r"HKCU\Software\Valve\Steam", "/v", "RunningAppID"])
+            .output()
+            .ok()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let raw_value = stdout
+            .lines()
+            .find(|line| line.contains("RunningAppID"))?
+            .split_whitespace()
+            .last()?;
+        let app_id = u64::from_str_radix(raw_value.trim_start_matches("0x"), 16).ok()?;
+        return Some(app_id).filter(|value| *value != 0);
+    }
+
+    let home_path = PathBuf::from(std::env::var("HOME").ok()?);
+    let registry_candidates = if cfg!(target_os = "macos") {
+        vec![home_path.join("Library/Application Support/Steam/registry.vdf")]
+    } else {
+        vec![
+            home_path.join(".steam/registry.vdf"),
+            home_path.join(".var/app/com.valvesoftware.Steam/.steam/registry.vdf"),
+        ]
+    };
+    let running_app_pattern = Regex::new(r#"(?i)"RunningAppID"\s+"(\d+)""#).ok()?;
+    registry_candidates.iter().find_map(|path| {
+        let contents = fs::read_to_string(path).ok()?;
+        running_app_pattern
+            .captures(&contents)
+            .and_then(|capture| capture.get(1))
+            .and_then(|matched| matched.as_str().parse::<u64>().ok())
+            .filter(|app_id| *app_id != 0)
+    })
+}
+
+/// A user-configured shell command run around a game session. `%APPID%`, `%PROVIDER%` and
+/// `%GAMENAME%` are substituted in the command and exported as `CATALYST_*` variables.
+struct LaunchHook {
+    command: String,
+    variables: Vec<(&'static str, String)>,
+}
+
+impl LaunchHook {
+    fn new(command: &str, provider: &str, external_id: &str, game_name: &str) -> Option<Self> {
+        let command = command.trim();
+        if command.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            command: command
+                .replace("%APPID%", external_id)
+                .replace("%PROVIDER%", provider)
+                .replace("%GAMENAME%", game_name),
+            variables: vec![
+                ("CATALYST_APPID", external_id.to_owned()),
+                ("CATALYST_PROVIDER", provider.to_owned()),
+                ("CATALYST_GAME_NAME", game_name.to_owned()),
+            ],
+        })
+    }
+
+    /// Runs the hook through the platform shell and waits for it to finish.
+    fn run(&self) -> Result<(), String> {
+        let mut command = if cfg!(target_os = "windows") {
+            let mut command = Command::new("cmd");
+            command.args(["/C", &self.command]);
+            command
+        } else {
+            let mut command = Command::new("sh");
+            command.args(["-c", &self.command]);
+            command
+        };
+        let status = command
+            .envs(self.variables.iter().map(|(key, value)| (*key, value.as_str())))
+            .status()
+            .map_err(|error| format!("Failed to run launch hook `{}`: {error}", self.command))?;
+        if !status.success() {
+            return Err(format!("Launch hook `{}` exited with {status}", self.command));
+        }
+        Ok(())
+    }
+}
+
+fn run_post_exit_hook(post_exit_hook: Option<&LaunchHook>) {
+    if let Some(hook) = post_exit_hook {
+        if let Err(error) = hook.run() {
+            eprintln!("{error}");
+        }
+    }
+}
+
+/// Watches a freshly launched Steam title until it exits, publishing Discord Rich Presence for
+/// the duration of the session when a client ID is supplied and running the post-exit hook.
+fn spawn_steam_game_session_tracker(
+    app_id: u64,
+    game_name: String,
+    discord_client_id: Option<String>,
+    post_exit_hook: Option<LaunchHook>,
+) -> Option<thread::JoinHandle<()>> {
+    thread::Builder::new()
+        .name(format!("game-session-{app_id}"))
+        .spawn(move || {
+            let launch_requested_at = Instant::now();
+            while detect_running_steam_app_id() != Some(app_id) {
+                if launch_requested_at.elapsed() >= GAME_SESSION_START_TIMEOUT {
+                    return;
+                }
+                thread::sleep(GAME_SESSION_POLL_INTERVAL);
+            }
+
+            let started_at = Utc::now().timestamp();
+            let mut presence = discord_client_id.and_then(|client_id| {
+                match discord::DiscordPresence::connect(&client_id) {
+                    Ok(mut presence) => match presence.set_activity(&game_name, started_at) {
+                        Ok(()) => Some(presence),
+                        Err(error) => {
+                            eprintln!("Failed to set Discord presence for {game_name}: {error}");
+                            None
+                        }
+                    },
+                    Err(error) => {
+                        eprintln!("Discord Rich Presence unavailable: {error}");
+                        None
+                    }
+                }
+            });
+
+            while detect_running_steam_app_id() == Some(app_id) {
+                thread::sleep(GAME_SESSION_POLL_INTERVAL);
+            }
+
+            if let Some(presence) = presence.as_mut() {
+                let _ = presence.clear_activity();
+            }
+            run_post_exit_hook(post_exit_hook.as_ref());
+        })
+        .ok()
+}
+
+/// Deletes expired sessions from the database every `SESSION_CLEANUP_INTERVAL`.
+fn spawn_session_cleanup_scheduler(db_path: PathBuf) {
+    let _ = thread::Builder::new()
+        .name("session-cleanup".into())
+        .spawn(move || loop {
+            thread::sleep(SESSION_CLEANUP_INTERVAL);
+            if let Err(error) = open_connection(&db_path).and_then(|connection| cleanup_expired_sessions(&connection)) {
+                eprintln!("Expired session cleanup failed: {error}");
+            }
+        });
+}
+
+/// Runs a background Steam sync whenever the configured sync interval elapses. The interval is
+/// read on every tick so changes from `set_app_settings` take effect without a restart.
+fn spawn_steam_sync_scheduler(app_handle: tauri::AppHandle) {
+    use tauri::Emitter;
+
+    let _ = thread::Builder::new()
+        .name("steam-sync-scheduler".into())
+        .spawn(move || {
+            let mut last_sync_at = Instant::now();
+            loop {
+                thread::sleep(STEAM_SYNC_SCHEDULER_TICK);
+                let state = app_handle.state::<AppState>();
+                let interval_minutes = state.steam_sync_interval_minutes();
+                if interval_minutes == 0
+                    || state.is_offline()
+                    || last_sync_at.elapsed() < Duration::from_secs(interval_minutes * 60)
+                {
+                    continue;
+                }
+
+                last_sync_at = Instant::now();
+                match application::services::library_service::sync_steam_library(
+                    state.inner(),
+                    None,
+                    |progress| {
+                        let _ = app_handle.emit(STEAM_SYNC_PROGRESS_EVENT, progress);
+                    },
+                ) {
+                    // A manual or tray sync is already bringing the library up to date.
+                    Err(error) if error.code == "steam_sync_in_progress" => {}
+                    Err(error) => eprintln!("Scheduled Steam sync failed: {}", error.message),
+                    Ok(_) => {}
+                }
+            }
+        });
+}
+
 fn env_flag(name: &str, default_value: bool) -> bool {
     let Ok(raw_value) = std::env::var(name) else {
         return default_value;
     };
 
-    match raw_value.trim().to_ascii_lowercase().as_str() {
-        "1" | "true" | "yes" | "on" => true,
-        "0" | "false" | "no" | "off" => false,
-        _ => default_value,
+    parse_app_setting_flag(&raw_value).unwrap_or(default_value)
+}
+
+/// Opens the database under `app_data_dir` and restores settings and the signed-in session.
+/// Shared by the desktop app and the headless CLI.
+fn initialize_app_state(app_data_dir: &Path) -> Result<AppState, String> {
+    // Demo mode runs against an isolated database and a fake Steam root so it never
+    // touches the real library or a real Steam installation.
+    let demo_mode = env_flag("CATALYST_DEMO", false);
+    let (db_path, session_token_path) = if demo_mode {
+        (
+            app_data_dir.join("catalyst-demo.db"),
+            app_data_dir.join("session-demo.token"),
+        )
+    } else {
+        (
+            app_data_dir.join("catalyst.db"),
+            app_data_dir.join("session.token"),
+        )
+    };
+    field_encryption::configure_field_key_fallback(db_path.with_extension("key"));
+    steam_config_writer::configure_steam_config_backups(app_data_dir.join("steam-config-backups"));
+    initialize_database(&db_path)?;
+
+    let steam_api_key = read_env_steam_api_key().filter(|_| !demo_mode);
+    let steam_local_install_detection = env_flag("STEAM_LOCAL_INSTALL_DETECTION", true);
+    let steam_settings_debug_logging = env_flag("STEAM_SETTINGS_DEBUG_LOGGING", false);
+    let steam_root_override = if demo_mode {
+        Some(app_data_dir.join("demo-steam").display().to_string())
+    } else {
+        std::env::var("STEAM_ROOT_OVERRIDE")
+            .ok()
+            .map(|value| value.trim().to_owned())
+            .filter(|value| !value.is_empty())
+    };
+    let demo_session_token = if demo_mode {
+        Some(demo::seed_demo_environment(
+            &db_path,
+            &app_data_dir.join("demo-steam"),
+        )?)
+    } else {
+        None
+    };
+
+    let state = AppState::new(
+        db_path,
+        session_token_path,
+        steam_api_key,
+        steam_local_install_detection,
+        steam_settings_debug_logging,
+        steam_root_override,
+    );
+    state
+        .offline_mode
+        .store(env_flag("CATALYST_OFFLINE", false), Ordering::SeqCst);
+    let settings_connection = open_connection(&state.db_path)?;
+    let persisted_settings = load_app_settings(&settings_connection)?;
+    apply_app_settings(&state, &persisted_settings);
+    if !demo_mode {
+        restore_keychain_steam_api_key(&state, &settings_connection, &persisted_settings);
+        http_proxy::restore_http_proxy(&state, &persisted_settings);
+    }
+    match demo_session_token {
+        Some(session_token) => persist_active_session(&state, &session_token)?,
+        None => restore_persisted_session(&state)?,
     }
+    Ok(state)
+}
+
+/// Runs `catalyst-cli` without starting the webview; see `interface::cli`.
+pub fn run_cli() -> std::process::ExitCode {
+    interface::cli::run_cli(std::env::args().skip(1).collect())
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -7530,34 +13138,31 @@ pub fn run() {
                 .path()
                 .app_data_dir()
                 .map_err(|error| format!("Failed to resolve app data directory: {error}"))?;
-            let db_path = app_data_dir.join("catalyst.db");
-            let session_token_path = app_data_dir.join("session.token");
-            initialize_database(&db_path)?;
-
-            let steam_api_key = std::env::var("STEAM_API_KEY")
-                .ok()
-                .map(|value| value.trim().to_owned())
-                .filter(|value| !value.is_empty());
-            let steam_local_install_detection = env_flag("STEAM_LOCAL_INSTALL_DETECTION", true);
-            let steam_settings_debug_logging = env_flag("STEAM_SETTINGS_DEBUG_LOGGING", false);
-            let steam_root_override = std::env::var("STEAM_ROOT_OVERRIDE")
-                .ok()
-                .map(|value| value.trim().to_owned())
-                .filter(|value| !value.is_empty());
-
-            let state = AppState::new(
-                db_path,
-                session_token_path,
-                steam_api_key,
-                steam_local_install_detection,
-                steam_settings_debug_logging,
-                steam_root_override,
-            );
-            restore_persisted_session(&state)?;
+            let state = initialize_app_state(&app_data_dir)?;
             app.manage(state);
+            spawn_steam_sync_scheduler(app.handle().clone());
+            spawn_session_cleanup_scheduler(app.state::<AppState>().db_path.clone());
+            process_watcher::spawn_running_game_watcher(app.handle().clone());
+            steam_manifest_watcher::spawn_steam_manifest_watcher(app.handle().clone());
+            notifications::spawn_notification_monitor(app.handle().clone());
+            discovery_feed::spawn_discovery_feed_refresher(app.handle().clone());
+            download_queue::spawn_download_schedule_monitor(app.handle().clone());
+            steam_config_writer::spawn_pending_steam_config_writer(app.handle().clone());
+            game_verification::spawn_game_verification_monitor(app.handle().clone());
+            install_jobs::spawn_install_job_worker(app.handle().clone());
+            api_server::spawn_api_server(app.handle().clone());
+            interface::tauri::tray::setup_tray(app.handle())?;
+            interface::tauri::deep_link::setup_deep_links(app.handle());
             Ok(())
         })
+        .on_window_event(|window, event| interface::tauri::tray::handle_window_event(window, event))
+        // Registered first so a second launch exits before any other plugin starts.
+        .plugin(tauri_plugin_single_instance::init(|app, args, cwd| {
+            interface::tauri::single_instance::handle_second_instance(app, args, cwd)
+        }))
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_notification::init())
         .invoke_handler(tauri::generate_handler![
             // `register` and `login` (local credentials) are intentionally
             // not exposed over the IPC surface. Authentication is primarily
@@ -7567,40 +13172,171 @@ pub fn run() {
             interface::tauri::commands::auth::logout,
             interface::tauri::commands::auth::get_session,
             interface::tauri::commands::auth::start_steam_auth,
+            interface::tauri::commands::auth::cancel_steam_auth,
+            interface::tauri::commands::auth::verify_2fa_login,
+            interface::tauri::commands::auth::list_sessions,
+            interface::tauri::commands::auth::revoke_session,
+            interface::tauri::commands::auth::delete_account,
+            interface::tauri::commands::auth::enable_2fa,
+            interface::tauri::commands::auth::verify_2fa_setup,
+            interface::tauri::commands::auth::disable_2fa,
+            interface::tauri::commands::batch::batch,
             interface::tauri::commands::library::get_library,
+            interface::tauri::commands::library::list_steam_tags,
+            interface::tauri::commands::library::list_hidden_games,
+            interface::tauri::commands::library::link_library_games,
+            interface::tauri::commands::library::unlink_library_game,
+            interface::tauri::commands::library::set_preferred_launch_provider,
+            interface::tauri::commands::library::import_playnite_library,
+            interface::tauri::commands::library::import_lutris_library,
+            interface::tauri::commands::notifications::list_notifications,
+            interface::tauri::commands::notifications::mark_notification_read,
+            interface::tauri::commands::library::get_startup_snapshot,
+            interface::tauri::commands::library::get_home_data,
             interface::tauri::commands::library::get_game_store_metadata,
             // `get_steam_status` is a server-side helper (not exposed to the
             // frontend) and is intentionally not registered here.
             interface::tauri::commands::library::sync_steam_library,
+            interface::tauri::commands::library::cancel_steam_sync,
+            interface::tauri::commands::library::get_storage_overview,
+            interface::tauri::commands::library::get_library_stats,
+            interface::tauri::commands::library::get_playtime_timeline,
+            interface::tauri::commands::library::suggest_game,
+            interface::tauri::commands::library::refresh_achievements,
+            interface::tauri::commands::library::sync_local_store_libraries,
+            interface::tauri::commands::library::get_library_conflicts,
+            interface::tauri::commands::library::search_library,
+            interface::tauri::commands::library::get_game_notes,
+            interface::tauri::commands::library::save_game_notes,
+            interface::tauri::commands::library::set_game_display_name,
+            interface::tauri::commands::library::add_game_user_tag,
+            interface::tauri::commands::library::remove_game_user_tag,
+            interface::tauri::commands::launch_templates::list_launch_templates,
+            interface::tauri::commands::launch_templates::save_launch_template,
+            interface::tauri::commands::launch_templates::delete_launch_template,
+            interface::tauri::commands::launch_templates::apply_launch_template,
+            interface::tauri::commands::roms::list_emulator_profiles,
+            interface::tauri::commands::roms::save_emulator_profile,
+            interface::tauri::commands::roms::delete_emulator_profile,
+            interface::tauri::commands::roms::list_rom_directories,
+            interface::tauri::commands::roms::add_rom_directory,
+            interface::tauri::commands::roms::remove_rom_directory,
+            interface::tauri::commands::roms::scan_rom_library,
             interface::tauri::commands::library::set_game_favorite,
+            interface::tauri::commands::library::set_games_favorite,
+            interface::tauri::commands::settings::get_offline_mode,
+            interface::tauri::commands::settings::set_offline_mode,
+            interface::tauri::commands::settings::get_app_settings,
+            interface::tauri::commands::settings::set_app_settings,
+            interface::tauri::commands::settings::get_download_schedule,
+            interface::tauri::commands::settings::set_download_schedule,
+            interface::tauri::commands::settings::get_steamcmd_settings,
+            interface::tauri::commands::settings::set_steamcmd_settings,
+            interface::tauri::commands::settings::get_api_server_settings,
+            interface::tauri::commands::settings::set_api_server_settings,
+            interface::tauri::commands::settings::regenerate_api_server_token,
+            interface::tauri::commands::settings::get_gaming_pc_settings,
+            interface::tauri::commands::settings::set_gaming_pc_settings,
+            interface::tauri::commands::settings::get_http_proxy_settings,
+            interface::tauri::commands::settings::set_http_proxy_settings,
+            interface::tauri::commands::settings::test_proxy_connection,
+            interface::tauri::commands::settings::get_locale,
+            interface::tauri::commands::settings::set_locale,
+            interface::tauri::commands::discovery::get_discovery_feed,
+            interface::tauri::commands::discovery::refresh_discovery_feed,
+            interface::tauri::commands::discovery::follow_discovery_source,
+            interface::tauri::commands::discovery::unfollow_discovery_source,
+            interface::tauri::commands::discovery::get_developer_releases,
+            interface::tauri::commands::settings::get_steam_api_key_status,
+            interface::tauri::commands::settings::set_steam_api_key,
             interface::tauri::commands::collections::list_collections,
+            interface::tauri::commands::deep_link::handle_deep_link,
             interface::tauri::commands::game_settings::list_game_languages,
             interface::tauri::commands::game_settings::list_game_compatibility_tools,
             interface::tauri::commands::game_settings::get_game_privacy_settings,
             interface::tauri::commands::game_settings::set_game_privacy_settings,
+            interface::tauri::commands::game_settings::set_games_hidden,
+            interface::tauri::commands::game_settings::get_private_games_lock_status,
+            interface::tauri::commands::game_settings::set_private_games_pin,
+            interface::tauri::commands::game_settings::clear_private_games_pin,
+            interface::tauri::commands::game_settings::unlock_private_games,
+            interface::tauri::commands::game_settings::lock_private_games,
             interface::tauri::commands::game_settings::clear_game_overlay_data,
             interface::tauri::commands::game_settings::get_game_properties_settings,
             interface::tauri::commands::game_settings::set_game_properties_settings,
+            interface::tauri::commands::game_settings::bulk_set_update_policy,
             interface::tauri::commands::game_settings::get_game_customization_artwork,
+            interface::tauri::commands::game_settings::set_game_custom_artwork,
+            interface::tauri::commands::game_settings::clear_game_custom_artwork,
+            interface::tauri::commands::game_settings::get_game_cloud_status,
+            interface::tauri::commands::game_settings::set_game_cloud_enabled,
+            interface::tauri::commands::game_settings::list_launch_wrappers,
             interface::tauri::commands::game_settings::get_game_installation_details,
             interface::tauri::commands::game_settings::get_game_install_size_estimate,
+            interface::tauri::commands::game_settings::get_pending_update_size,
             interface::tauri::commands::game_settings::list_game_install_locations,
+            interface::tauri::commands::game_settings::list_controller_layouts,
+            interface::tauri::commands::game_settings::set_game_controller_layout,
             interface::tauri::commands::library::list_steam_downloads,
+            interface::tauri::commands::library::pause_download,
+            interface::tauri::commands::library::resume_download,
+            interface::tauri::commands::library::reorder_download_queue,
+            interface::tauri::commands::library::get_download_history,
+            interface::tauri::commands::library::get_download_rate_limit,
+            interface::tauri::commands::library::set_download_rate_limit,
             interface::tauri::commands::steam::list_game_versions_betas,
             interface::tauri::commands::steam::validate_game_beta_access_code,
+            interface::tauri::commands::steam::list_steam_config_backups,
+            interface::tauri::commands::steam::restore_steam_config_backup,
             interface::tauri::commands::collections::create_collection,
             interface::tauri::commands::collections::rename_collection,
+            interface::tauri::commands::collections::set_collection_cover_image,
+            interface::tauri::commands::collections::set_collection_accent_color,
             interface::tauri::commands::collections::delete_collection,
             interface::tauri::commands::collections::add_game_to_collection,
+            interface::tauri::commands::collections::set_collection_game_order,
             interface::tauri::commands::game_actions::play_game,
+            interface::tauri::commands::game_actions::stop_game,
             interface::tauri::commands::game_actions::install_game,
+            interface::tauri::commands::game_actions::list_install_jobs,
             interface::tauri::commands::game_actions::uninstall_game,
             interface::tauri::commands::game_actions::browse_game_installed_files,
             interface::tauri::commands::game_actions::backup_game_files,
+            interface::tauri::commands::game_actions::get_game_save_locations,
+            interface::tauri::commands::game_actions::set_game_save_locations,
+            interface::tauri::commands::game_actions::backup_game_saves,
+            interface::tauri::commands::game_actions::list_save_backups,
+            interface::tauri::commands::game_actions::restore_game_saves,
+            interface::tauri::commands::game_actions::list_game_screenshots,
+            interface::tauri::commands::game_actions::get_game_sessions,
+            interface::tauri::commands::game_actions::annotate_game_session,
+            interface::tauri::commands::game_actions::open_screenshots_folder,
+            interface::tauri::commands::game_actions::set_game_screenshot_directory,
+            interface::tauri::commands::game_actions::move_game_install,
+            interface::tauri::commands::game_actions::install_game_with_steamcmd,
+            interface::tauri::commands::game_actions::list_remote_play_hosts,
+            interface::tauri::commands::game_actions::play_game_remote,
+            interface::tauri::commands::game_actions::wake_gaming_pc,
+            interface::tauri::commands::game_actions::start_gaming_pc_download,
+            interface::tauri::commands::game_actions::list_game_workshop_items,
+            interface::tauri::commands::game_actions::open_workshop_page,
+            interface::tauri::commands::game_actions::get_game_storage_breakdown,
+            interface::tauri::commands::game_actions::clear_game_shader_cache,
+            interface::tauri::commands::game_actions::delete_game_compatdata,
+            interface::tauri::commands::game_actions::open_game_proton_prefix,
+            interface::tauri::commands::game_actions::run_game_prefix_tool,
             interface::tauri::commands::game_actions::verify_game_files,
+            interface::tauri::commands::game_actions::get_game_verification_status,
             interface::tauri::commands::game_actions::add_game_desktop_shortcut,
+            interface::tauri::commands::game_actions::create_game_shortcut,
             interface::tauri::commands::game_actions::open_game_recording_settings,
-            interface::tauri::commands::steam::import_steam_collections
+            interface::tauri::commands::game_actions::play_soundtrack,
+            interface::tauri::commands::steam::import_steam_collections,
+            interface::tauri::commands::steam::add_to_steam,
+            interface::tauri::commands::steam::get_steam_points_balance,
+            interface::tauri::commands::steam::list_game_points_shop_items,
+            interface::tauri::commands::steam::get_game_card_status,
+            interface::tauri::commands::steam::get_steam_badges
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,540 @@
+use crate::*;
+use rusqlite::OpenFlags;
+
+/// Provider for imported games that no supported launcher owns. They launch through the
+/// target recorded in `custom_game_launch_targets`.
+pub(crate) const CUSTOM_PROVIDER: &str = "custom";
+const LUTRIS_GAME_URI_PREFIX: &str = "lutris:rungameid/";
+const PLAYNITE_INSTALL_DIR_VARIABLE: &str = "{InstallDir}";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibraryImportResponse {
+    pub source: String,
+    pub dry_run: bool,
+    pub total_entries: usize,
+    pub games_added: usize,
+    pub games_already_in_library: usize,
+    pub custom_games: usize,
+    pub skipped_entries: usize,
+    pub collections_created: usize,
+    pub memberships_added: usize,
+}
+
+/// One game read from another launcher's library, already mapped to a Catalyst provider.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ImportedLibraryEntry {
+    pub provider: String,
+    pub external_id: String,
+    pub name: String,
+    pub playtime_minutes: i64,
+    pub installed: bool,
+    pub last_played_at: Option<String>,
+    /// Only set for `custom` games: a launcher URI or an executable path.
+    pub launch_target: Option<String>,
+    pub launch_arguments: Option<String>,
+    pub categories: Vec<String>,
+}
+
+fn map_store_provider(source_name: &str, game_id: &str) -> Option<&'static str> {
+    let provider = match source_name.trim().to_ascii_lowercase().as_str() {
+        "steam" => "steam",
+        "ea app" | "ea" | "origin" => "ea",
+        "ubisoft connect" | "ubisoft" | "uplay" => "ubisoft",
+        _ => return None,
+    };
+    // Steam and Ubisoft launch URIs take numeric IDs; anything else is launched as a custom game.
+    let needs_numeric_id = provider != "ea";
+    if game_id.is_empty() || (needs_numeric_id && game_id.parse::<u64>().is_err()) {
+        return None;
+    }
+    Some(provider)
+}
+
+/// Reads a Playnite library exported as JSON (a list of Playnite game objects, as written by the
+/// library export extensions). Games from the Steam, EA and Ubisoft plugins map onto those
+/// providers; everything else becomes a `custom` game using its first play action.
+pub(crate) fn read_playnite_library_export(path: &Path) -> Result<Vec<ImportedLibraryEntry>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|error| format!("Failed to read Playnite export {}: {error}", path.display()))?;
+    let payload = serde_json::from_str::<serde_json::Value>(contents.trim_start_matches('\u{feff}'))
+        .map_err(|error| format!("Playnite export is not valid JSON: {error}"))?;
+    let games = payload
+        .as_array()
+        .or_else(|| payload.get("Games").and_then(serde_json::Value::as_array))
+        .ok_or_else(|| String::from("Playnite export does not contain a list of games"))?;
+
+    Ok(games.iter().filter_map(parse_playnite_game).collect())
+}
+
+fn parse_playnite_game(game: &serde_json::Value) -> Option<ImportedLibraryEntry> {
+    let text = |field: &str| {
+        game.get(field)
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+    };
+    let name = text("Name")?.to_owned();
+    let playnite_id = text("Id")?;
+    let game_id = text("GameId").unwrap_or_default();
+    let source_name = game
+        .get("Source")
+        .and_then(|source| source.get("Name").or(Some(source)))
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or_default();
+    let categories = game
+        .get("Categories")
+        .and_then(serde_json::Value::as_array)
+        .map(|categories| {
+            categories
+                .iter()
+                .filter_map(|category| category.get("Name").or(Some(category)))
+                .filter_map(serde_json::Value::as_str)
+                .map(str::trim)
+                .filter(|category| !category.is_empty())
+                .map(str::to_owned)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+
+    let (provider, external_id, launch_target, launch_arguments) =
+        match map_store_provider(source_name, game_id) {
+            Some(provider) => (provider.to_owned(), game_id.to_owned(), None, None),
+            None => {
+                let install_directory = text("InstallDirectory").unwrap_or_default();
+                let play_action = game
+                    .get("GameActions")
+                    .and_then(serde_json::Value::as_array)
+                    .and_then(|actions| {
+                        actions
+                            .iter()
+                            .find(|action| {
+                                action.get("IsPlayAction").and_then(serde_json::Value::as_bool) == Some(true)
+                            })
+                            .or_else(|| actions.first())
+                    });
+                let action_text = |field: &str| {
+                    play_action
+                        .and_then(|action| action.get(field))
+                        .and_then(serde_json::Value::as_str)
+                        .map(|value| value.replace(PLAYNITE_INSTALL_DIR_VARIABLE, install_directory))
+                        .map(|value| value.trim().to_owned())
+                        .filter(|value| !value.is_empty())
+                };
+                (
+                    String::from(CUSTOM_PROVIDER),
+                    format!("playnite-{}", playnite_id.to_ascii_lowercase()),
+                    action_text("Path"),
+                    action_text("Arguments"),
+                )
+            }
+        };
+
+    Some(ImportedLibraryEntry {
+        provider,
+        external_id,
+        name,
+        // Playnite tracks playtime in seconds.
+        playtime_minutes: game
+            .get("Playtime")
+            .and_then(serde_json::Value::as_i64)
+            .unwrap_or(0)
+            / 60,
+        installed: game
+            .get("IsInstalled")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false),
+        last_played_at: text("LastActivity")
+            .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+            .map(|timestamp| timestamp.with_timezone(&Utc).to_rfc3339()),
+        launch_target,
+        launch_arguments,
+        categories,
+    })
+}
+
+/// Lutris keeps its library in `pga.db` under the XDG data directory.
+pub(crate) fn default_lutris_database_path() -> Option<PathBuf> {
+    std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var("HOME")
+                .ok()
+                .map(|home| PathBuf::from(home).join(".local").join("share"))
+        })
+        .map(|data_home| data_home.join("lutris").join("pga.db"))
+}
+
+/// Reads games and categories from Lutris' SQLite database. Games Lutris imported from Steam map
+/// onto the Steam provider; everything else becomes a `custom` game launched through Lutris.
+pub(crate) fn read_lutris_library(path: &Path) -> Result<Vec<ImportedLibraryEntry>, String> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|error| format!("Failed to open Lutris database {}: {error}", path.display()))?;
+    // `service` and `service_id` only exist in Lutris 0.5.9 and later.
+    let has_service_columns = table_has_column(&connection, "games", "service")?;
+    let service_columns = if has_service_columns {
+        "service, service_id"
+    } else {
+        "NULL, NULL"
+    };
+    let mut statement = connection
+        .prepare(&format!(
+            "SELECT id, name, slug, installed, lastplayed, playtime, {service_columns} FROM games"
+        ))
+        .map_err(|error| format!("Failed to prepare Lutris game query: {error}"))?;
+    let rows = statement
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, Option<i64>>(3)?,
+                row.get::<_, Option<i64>>(4)?,
+                row.get::<_, Option<f64>>(5)?,
+                row.get::<_, Option<String>>(6)?,
+                row.get::<_, Option<String>>(7)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query Lutris games: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode Lutris game row: {error}"))?;
+    let mut categories_by_game_id = load_lutris_categories(&connection)?;
+
+    let mut entries = Vec::new();
+    for (game_id, name, slug, installed, last_played, playtime_hours, service, service_id) in rows {
+        let Some(name) = name.map(|name| name.trim().to_owned()).filter(|name| !name.is_empty()) else {
+            continue;
+        };
+        let service_id = service_id.unwrap_or_default();
+        let (provider, external_id, launch_target) =
+            match map_store_provider(service.as_deref().unwrap_or_default(), service_id.trim()) {
+                Some(provider) => (provider.to_owned(), service_id.trim().to_owned(), None),
+                None => (
+                    String::from(CUSTOM_PROVIDER),
+                    format!("lutris-{}", slug.unwrap_or_else(|| game_id.to_string())),
+                    Some(format!("{LUTRIS_GAME_URI_PREFIX}{game_id}")),
+                ),
+            };
+
+        entries.push(ImportedLibraryEntry {
+            provider,
+            external_id,
+            name,
+            // Lutris tracks playtime in hours.
+            playtime_minutes: (playtime_hours.unwrap_or(0.0) * 60.0).round() as i64,
+            installed: installed.unwrap_or(0) != 0,
+            last_played_at: last_played
+                .filter(|timestamp| *timestamp > 0)
+                .and_then(|timestamp| chrono::DateTime::<Utc>::from_timestamp(timestamp, 0))
+                .map(|timestamp| timestamp.to_rfc3339()),
+            launch_target,
+            launch_arguments: None,
+            categories: categories_by_game_id.remove(&game_id).unwrap_or_default(),
+        });
+    }
+
+    Ok(entries)
+}
+
+fn load_lutris_categories(connection: &Connection) -> Result<HashMap<i64, Vec<String>>, String> {
+    let has_categories = connection
+        .query_row(
+            "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'games_categories'",
+            [],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to inspect Lutris database: {error}"))?
+        .is_some();
+    if !has_categories {
+        return Ok(HashMap::new());
+    }
+
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT games_categories.game_id, categories.name
+            FROM games_categories
+            INNER JOIN categories ON categories.id = games_categories.category_id
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare Lutris category query: {error}"))?;
+    let rows = statement
+        .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|error| format!("Failed to query Lutris categories: {error}"))?;
+
+    let mut categories_by_game_id: HashMap<i64, Vec<String>> = HashMap::new();
+    for row in rows {
+        let (game_id, name) = row.map_err(|error| format!("Failed to decode Lutris category row: {error}"))?;
+        let name = name.trim();
+        // Lutris stores built-in flags such as `.hidden` as dot-prefixed categories.
+        if name.is_empty() || name.starts_with('.') {
+            continue;
+        }
+        categories_by_game_id.entry(game_id).or_default().push(name.to_owned());
+    }
+    Ok(categories_by_game_id)
+}
+
+/// Adds imported games to the library and their categories as collections. Games already in
+/// the library are left as they are apart from collection membership. With `dry_run` nothing is
+/// written and the summary reports what an import would do.
+pub(crate) fn import_library_entries(
+    connection: &Connection,
+    user_id: &str,
+    source: &str,
+    entries: &[ImportedLibraryEntry],
+    dry_run: bool,
+) -> Result<LibraryImportResponse, String> {
+    let mut summary = LibraryImportResponse {
+        source: source.to_owned(),
+        dry_run,
+        total_entries: entries.len(),
+        games_added: 0,
+        games_already_in_library: 0,
+        custom_games: 0,
+        skipped_entries: 0,
+        collections_created: 0,
+        memberships_added: 0,
+    };
+    let imported_at = Utc::now().to_rfc3339();
+    let mut seen_games = HashSet::new();
+    let mut collection_ids_by_name: HashMap<String, Option<String>> = HashMap::new();
+
+    for entry in entries {
+        if !seen_games.insert((entry.provider.as_str(), entry.external_id.as_str())) {
+            summary.skipped_entries += 1;
+            continue;
+        }
+        if entry.provider == CUSTOM_PROVIDER && entry.launch_target.is_none() {
+            summary.skipped_entries += 1;
+            continue;
+        }
+
+        let exists = find_game_name(connection, user_id, &entry.provider, &entry.external_id)?.is_some();
+        if exists {
+            summary.games_already_in_library += 1;
+        } else {
+            summary.games_added += 1;
+        }
+        if entry.provider == CUSTOM_PROVIDER {
+            summary.custom_games += 1;
+        }
+
+        if !dry_run {
+            if !exists {
+                connection
+                    .execute(
+                        "
+                        INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, artwork_url, last_synced_at, last_played_at, added_at)
+                        VALUES (?1, ?2, ?3, ?4, 'game', ?5, ?6, NULL, ?7, ?8, ?7)
+                        ",
+                        params![
+                            user_id,
+                            entry.provider,
+                            entry.external_id,
+                            entry.name,
+                            entry.playtime_minutes,
+                            i64::from(entry.installed),
+                            imported_at,
+                            entry.last_played_at
+                        ],
+                    )
+                    .map_err(|error| format!("Failed to import {}: {error}", entry.name))?;
+            }
+            if let Some(launch_target) = entry.launch_target.as_deref() {
+                save_custom_game_launch_target(
+                    connection,
+                    user_id,
+                    &entry.external_id,
+                    launch_target,
+                    entry.launch_arguments.as_deref(),
+                )?;
+            }
+        }
+
+        for category in &entry.categories {
+            let collection_key = category.trim().to_ascii_lowercase();
+            let collection_id = match collection_ids_by_name.get(&collection_key) {
+                Some(collection_id) => collection_id.clone(),
+                None => {
+                    let collection_id = if dry_run {
+                        let existing_id = find_collection_id_by_name(connection, user_id, category.trim())?;
+                        if existing_id.is_none() {
+                            summary.collections_created += 1;
+                        }
+                        existing_id
+                    } else {
+                        let (collection_id, created) =
+                            get_or_create_collection_id_by_name(connection, user_id, category)?;
+                        if created {
+                            summary.collections_created += 1;
+                        }
+                        Some(collection_id)
+                    };
+                    collection_ids_by_name.insert(collection_key, collection_id.clone());
+                    collection_id
+                }
+            };
+
+            let Some(collection_id) = collection_id else {
+                // The collection does not exist yet, so every game would be a new member.
+                summary.memberships_added += 1;
+                continue;
+            };
+            if dry_run {
+                if !is_game_in_collection(connection, user_id, &collection_id, entry)? {
+                    summary.memberships_added += 1;
+                }
+            } else if add_game_to_collection_membership(
+                connection,
+                user_id,
+                &collection_id,
+                &entry.provider,
+                &entry.external_id,
+            )? {
+                summary.memberships_added += 1;
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+fn is_game_in_collection(
+    connection: &Connection,
+    user_id: &str,
+    collection_id: &str,
+    entry: &ImportedLibraryEntry,
+) -> Result<bool, String> {
+    connection
+        .query_row(
+            "
+            SELECT 1 FROM collection_games
+            WHERE user_id = ?1 AND collection_id = ?2 AND provider = ?3 AND external_id = ?4
+            ",
+            params![user_id, collection_id, entry.provider, entry.external_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()
+        .map(|row| row.is_some())
+        .map_err(|error| format!("Failed to query collection membership: {error}"))
+}
+
+pub(crate) fn save_custom_game_launch_target(
+    connection: &Connection,
+    user_id: &str,
+    external_id: &str,
+    target: &str,
+    arguments: Option<&str>,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            INSERT INTO custom_game_launch_targets (user_id, external_id, target, arguments, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, external_id) DO UPDATE SET
+              target = excluded.target,
+              arguments = excluded.arguments,
+              updated_at = excluded.updated_at
+            ",
+            params![user_id, external_id, target, arguments, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to save custom game launch target: {error}"))?;
+    Ok(())
+}
+
+/// The launch target and arguments of a custom game, if one was recorded.
+pub(crate) fn find_custom_game_launch_target(
+    connection: &Connection,
+    user_id: &str,
+    external_id: &str,
+) -> Result<Option<(String, Option<String>)>, String> {
+    connection
+        .query_row(
+            "SELECT target, arguments FROM custom_game_launch_targets WHERE user_id = ?1 AND external_id = ?2",
+            params![user_id, external_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query custom game launch target: {error}"))
+}
+
+/// Starts an imported custom game. Launcher URIs such as `lutris:rungameid/12` are handed to the
+/// OS; executables are spawned directly so the process watcher can track them.
+pub(crate) fn launch_custom_game(
+    connection: &Connection,
+    user_id: &str,
+    external_id: &str,
+    launch_options: Option<&str>,
+    environment_variables: &BTreeMap<String, String>,
+) -> Result<Option<std::process::Child>, String> {
+    let (target, arguments) = find_custom_game_launch_target(connection, user_id, external_id)?
+        .ok_or_else(|| String::from("This game has no launch target; import it again"))?;
+
+    let is_uri = Url::parse(&target)
+        .map(|url| url.scheme().len() > 1)
+        .unwrap_or(false);
+    if is_uri {
+        launch_launcher_uri(&target, "the game's launcher")?;
+        return Ok(None);
+    }
+
+    let executable_path = Path::new(&target);
+    if !executable_path.is_file() {
+        return Err(format!("Game executable not found at {target}"));
+    }
+    let mut command = Command::new(executable_path);
+    command.args(
+        [arguments.as_deref(), launch_options]
+            .into_iter()
+            .flatten()
+            .flat_map(str::split_whitespace),
+    );
+    command.envs(environment_variables);
+    if let Some(working_directory) = executable_path.parent() {
+        command.current_dir(working_directory);
+    }
+    command
+        .spawn()
+        .map(Some)
+        .map_err(|error| format!("Failed to start {target}: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_playnite_game_maps_store_and_custom_games() {
+        let steam_game = serde_json::json!({
+            "Id": "5bc2e9a8-3c11-4a52-9bd4-7ad1c6f0b4a1",
+            "Name": "Portal 2",
+            "GameId": "620",
+            "Source": { "Name": "Steam" },
+            "Playtime": 7260,
+            "IsInstalled": true,
+            "Categories": [{ "Name": "Puzzle" }]
+        });
+        let custom_game = serde_json::json!({
+            "Id": "0D1A6C4E-1F1B-4B77-9A3B-6E0F7D2C9E11",
+            "Name": "Doom (GZDoom)",
+            "InstallDirectory": "C:\\Games\\GZDoom",
+            "GameActions": [{ "Type": 0, "Path": "{InstallDir}\\gzdoom.exe", "Arguments": "-iwad doom.wad", "IsPlayAction": true }]
+        });
+
+        let steam_entry = parse_playnite_game(&steam_game).expect("steam game should parse");
+        assert_eq!(steam_entry.provider, "steam");
+        assert_eq!(steam_entry.external_id, "620");
+        assert_eq!(steam_entry.playtime_minutes, 121);
+        assert_eq!(steam_entry.categories, vec![String::from("Puzzle")]);
+
+        let custom_entry = parse_playnite_game(&custom_game).expect("custom game should parse");
+        assert_eq!(custom_entry.provider, CUSTOM_PROVIDER);
+        assert_eq!(custom_entry.external_id, "playnite-0d1a6c4e-1f1b-4b77-9a3b-6e0f7d2c9e11");
+        assert_eq!(custom_entry.launch_target.as_deref(), Some("C:\\Games\\GZDoom\\gzdoom.exe"));
+        assert_eq!(custom_entry.launch_arguments.as_deref(), Some("-iwad doom.wad"));
+    }
+}
@@ -0,0 +1,142 @@
+use crate::*;
+
+/// The per-game lookups `list_games_by_user` attaches to every row. Rebuilding them is most of
+/// the work behind `get_library` for large libraries.
+#[derive(Debug, Default)]
+pub(crate) struct LibraryLookups {
+    pub collections_by_game: HashMap<String, Vec<String>>,
+    pub collection_positions_by_game: HashMap<String, BTreeMap<String, i64>>,
+    pub steam_tags_by_game: HashMap<String, Vec<String>>,
+    pub user_tags_by_game: HashMap<String, Vec<String>>,
+    pub game_genres_by_game: HashMap<String, Vec<String>>,
+}
+
+impl LibraryLookups {
+    fn load(connection: &Connection, user_id: &str) -> Result<Self, String> {
+        Ok(Self {
+            collections_by_game: load_collection_names_by_game(connection, user_id)?,
+            collection_positions_by_game: load_collection_positions_by_game(connection, user_id)?,
+            steam_tags_by_game: load_steam_tags_by_game(connection, user_id)?,
+            user_tags_by_game: game_user_tags::load_user_tags_by_game(connection, user_id)?,
+            game_genres_by_game: load_game_genres_by_game(connection, user_id)?,
+        })
+    }
+}
+
+/// Library lookups per user, kept until a write invalidates them. Services that change
+/// collections, memberships, genres, user tags or Steam Store tags must call `invalidate_user` or
+/// `clear`.
+#[derive(Default)]
+pub(crate) struct LibraryLookupCache {
+    users: Mutex<HashMap<String, Arc<LibraryLookups>>>,
+    /// Bumped on every invalidation so a load that raced a write isn't cached.
+    generation: AtomicU64,
+}
+
+impl LibraryLookupCache {
+    pub(crate) fn get_or_load(&self, connection: &Connection, user_id: &str) -> Result<Arc<LibraryLookups>, String> {
+        if let Some(lookups) = self.users.lock().ok().and_then(|users| users.get(user_id).cloned()) {
+            return Ok(lookups);
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let lookups = Arc::new(LibraryLookups::load(connection, user_id)?);
+        if let Ok(mut users) = self.users.lock() {
+            if self.generation.load(Ordering::SeqCst) == generation {
+                users.insert(user_id.to_owned(), Arc::clone(&lookups));
+            }
+        }
+        Ok(lookups)
+    }
+
+    pub(crate) fn invalidate_user(&self, user_id: &str) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut users) = self.users.lock() {
+            users.remove(user_id);
+        }
+    }
+
+    /// Steam Store tags are shared between users, so syncs drop every cached user.
+    pub(crate) fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        if let Ok(mut users) = self.users.lock() {
+            users.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::application::services::{collection_service, library_service};
+
+    #[test]
+    fn get_or_load_serves_cached_lookups_until_invalidated() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        connection
+            .execute(
+                "
+                INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, last_synced_at)
+                VALUES (?1, 'steam', '620', 'Portal 2', 'game', 0, 0, '2024-01-01T00:00:00Z')
+                ",
+                params![user.id],
+            )
+            .expect("insert game");
+        let cache = LibraryLookupCache::default();
+
+        let lookups = cache.get_or_load(&connection, &user.id).expect("load lookups");
+        assert!(lookups.collections_by_game.is_empty());
+
+        let collection = create_user_collection(&connection, &user.id, "Favorites").expect("create collection");
+        add_game_to_collection_membership(&connection, &user.id, &collection.id, "steam", "620")
+            .expect("add membership");
+        let cached = cache.get_or_load(&connection, &user.id).expect("load lookups");
+        assert!(Arc::ptr_eq(&lookups, &cached));
+
+        cache.invalidate_user(&user.id);
+        let reloaded = cache.get_or_load(&connection, &user.id).expect("load lookups");
+        assert_eq!(
+            reloaded.collections_by_game.get("steam:620"),
+            Some(&vec![String::from("Favorites")])
+        );
+    }
+
+    #[test]
+    fn invalidate_user_keeps_other_users_cached_and_clear_drops_everyone() {
+        let database = test_support::test_database();
+        let connection = &database.connection;
+        let other_user = create_user(connection, "friend@example.com", "hash", None).expect("create user");
+        let cache = LibraryLookupCache::default();
+        let lookups = cache.get_or_load(connection, &database.user.id).expect("load lookups");
+        let other_lookups = cache.get_or_load(connection, &other_user.id).expect("load lookups");
+
+        cache.invalidate_user(&database.user.id);
+        assert!(!Arc::ptr_eq(&lookups, &cache.get_or_load(connection, &database.user.id).expect("reload")));
+        assert!(Arc::ptr_eq(&other_lookups, &cache.get_or_load(connection, &other_user.id).expect("cached")));
+
+        cache.clear();
+        assert!(!Arc::ptr_eq(&other_lookups, &cache.get_or_load(connection, &other_user.id).expect("reload")));
+    }
+
+    #[test]
+    fn collection_changes_through_the_service_reach_the_library() {
+        let database = test_support::test_database();
+        database.insert_game("steam", "620", "Portal 2");
+        let state = database.signed_in_state();
+        let collections = |state: &AppState| {
+            library_service::get_library(state, None, None, None).expect("library").games[0]
+                .collections
+                .clone()
+        };
+        assert!(collections(&state).is_empty());
+
+        let collection = collection_service::create_collection(&state, String::from("Favorites")).expect("create");
+        collection_service::add_game_to_collection(&state, "steam".into(), "620".into(), collection.id.clone())
+            .expect("add game");
+        assert_eq!(collections(&state), vec![String::from("Favorites")]);
+
+        collection_service::delete_collection(&state, collection.id).expect("delete collection");
+        assert!(collections(&state).is_empty());
+    }
+}
@@ -0,0 +1,360 @@
+use crate::*;
+
+// Trailing qualifiers that differ between storefronts for the same game.
+const TITLE_EDITION_SUFFIXES: [&str; 10] = [
+    "game of the year edition",
+    "goty edition",
+    "definitive edition",
+    "deluxe edition",
+    "complete edition",
+    "standard edition",
+    "enhanced edition",
+    "remastered",
+    "goty",
+    "edition",
+];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MergedGameResponse {
+    pub group_key: String,
+    pub name: String,
+    pub preferred_provider: String,
+    pub preferred_game_id: String,
+    pub installed: bool,
+    pub playtime_minutes: i64,
+    /// Every copy of the game, preferred launch provider first.
+    pub games: Vec<GameResponse>,
+}
+
+/// Lowercases a title and drops punctuation, trademark symbols, bracketed qualifiers and
+/// edition suffixes so storefront variants of the same name compare equal.
+pub(crate) fn normalize_game_title(name: &str) -> String {
+    let mut unbracketed = String::with_capacity(name.len());
+    let mut bracket_depth = 0usize;
+    for character in name.chars() {
+        match character {
+            '(' | '[' => bracket_depth += 1,
+            ')' | ']' => bracket_depth = bracket_depth.saturating_sub(1),
+            _ if bracket_depth == 0 => unbracketed.push(character),
+            _ => {}
+        }
+    }
+
+    let mut normalized = unbracketed
+        .to_lowercase()
+        .chars()
+        .map(|character| if character.is_alphanumeric() { character } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    while let Some(stripped) = TITLE_EDITION_SUFFIXES.iter().find_map(|suffix| {
+        normalized
+            .strip_suffix(suffix)
+            .filter(|rest| rest.is_empty() || rest.ends_with(' '))
+            .map(|rest| rest.trim_end().to_owned())
+    }) {
+        if stripped.is_empty() {
+            break;
+        }
+        normalized = stripped;
+    }
+    normalized
+}
+
+fn release_year(release_date: Option<&str>) -> Option<u32> {
+    let digits = release_date?.as_bytes();
+    digits
+        .windows(4)
+        .enumerate()
+        .find(|(index, window)| {
+            window.iter().all(u8::is_ascii_digit)
+                && (window.starts_with(b"19") || window.starts_with(b"20"))
+                && !digits.get(index + 4).is_some_and(u8::is_ascii_digit)
+                && !(*index > 0 && digits[index - 1].is_ascii_digit())
+        })
+        .and_then(|(_, window)| std::str::from_utf8(window).ok()?.parse().ok())
+}
+
+pub(crate) fn load_game_identity_links(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<HashMap<String, String>, String> {
+    let mut statement = connection
+        .prepare("SELECT provider, external_id, group_key FROM game_identity_links WHERE user_id = ?1")
+        .map_err(|error| format!("Failed to prepare game link query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok((
+                format!("{}:{}", row.get::<_, String>(0)?, row.get::<_, String>(1)?),
+                row.get::<_, String>(2)?,
+            ))
+        })
+        .map_err(|error| format!("Failed to query game links: {error}"))?;
+
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|error| format!("Failed to decode game link row: {error}"))
+}
+
+pub(crate) fn load_game_merge_preferences(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<HashMap<String, String>, String> {
+    let mut statement = connection
+        .prepare("SELECT group_key, preferred_provider FROM game_merge_preferences WHERE user_id = ?1")
+        .map_err(|error| format!("Failed to prepare merge preference query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|error| format!("Failed to query merge preferences: {error}"))?;
+
+    rows.collect::<Result<HashMap<_, _>, _>>()
+        .map_err(|error| format!("Failed to decode merge preference row: {error}"))
+}
+
+/// Assigns the given games to one explicit group (or to their own group each, when
+/// `group_key` is `None`), overriding title matching for them.
+pub(crate) fn save_game_identity_links(
+    connection: &Connection,
+    user_id: &str,
+    games: &[(String, String)],
+    group_key: Option<&str>,
+) -> Result<(), String> {
+    let now = Utc::now().to_rfc3339();
+    for (provider, external_id) in games {
+        let game_group_key = group_key
+            .map(str::to_owned)
+            .unwrap_or_else(|| format!("game:{provider}:{external_id}"));
+        connection
+            .execute(
+                "
+                INSERT INTO game_identity_links (user_id, provider, external_id, group_key, created_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+                  group_key = excluded.group_key,
+                  created_at = excluded.created_at
+                ",
+                params![user_id, provider, external_id, game_group_key, now],
+            )
+            .map_err(|error| format!("Failed to save game link: {error}"))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn save_game_merge_preference(
+    connection: &Connection,
+    user_id: &str,
+    group_key: &str,
+    preferred_provider: &str,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            INSERT INTO game_merge_preferences (user_id, group_key, preferred_provider, updated_at)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT(user_id, group_key) DO UPDATE SET
+              preferred_provider = excluded.preferred_provider,
+              updated_at = excluded.updated_at
+            ",
+            params![user_id, group_key, preferred_provider, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to save preferred launch provider: {error}"))?;
+    Ok(())
+}
+
+/// Groups copies of the same game owned on different providers. Explicit links win; otherwise
+/// games match on normalized title and kind, and known release years must agree. A provider
+/// contributes at most one copy to a group.
+pub(crate) fn merge_library_games(
+    games: &[GameResponse],
+    links: &HashMap<String, String>,
+    preferences: &HashMap<String, String>,
+) -> Vec<MergedGameResponse> {
+    let mut candidate_groups: Vec<(String, Vec<usize>)> = Vec::new();
+    let mut candidate_index_by_key: HashMap<String, usize> = HashMap::new();
+    for (index, game) in games.iter().enumerate() {
+        let key = match links.get(&game.id) {
+            Some(group_key) => group_key.clone(),
+            None => {
+                let title = normalize_game_title(&game.name);
+                if title.is_empty() {
+                    format!("game:{}", game.id)
+                } else {
+                    format!("title:{}:{title}", game.kind)
+                }
+            }
+        };
+        let group_index = *candidate_index_by_key.entry(key.clone()).or_insert_with(|| {
+            candidate_groups.push((key, Vec::new()));
+            candidate_groups.len() - 1
+        });
+        candidate_groups[group_index].1.push(index);
+    }
+
+    let mut groups: Vec<(String, Vec<usize>)> = Vec::new();
+    for (key, members) in candidate_groups {
+        if !key.starts_with("title:") {
+            groups.push((key, members));
+            continue;
+        }
+
+        // Split title matches whose known release years disagree; undated copies join the
+        // first dated group.
+        let mut year_groups: Vec<(Option<u32>, Vec<usize>)> = Vec::new();
+        let mut undated = Vec::new();
+        for index in members {
+            match release_year(games[index].release_date.as_deref()) {
+                Some(year) => match year_groups.iter_mut().find(|(group_year, _)| *group_year == Some(year)) {
+                    Some((_, group)) => group.push(index),
+                    None => year_groups.push((Some(year), vec![index])),
+                },
+                None => undated.push(index),
+            }
+        }
+        match year_groups.first_mut() {
+            Some((_, group)) => group.extend(undated),
+            None => year_groups.push((None, undated)),
+        }
+
+        let split_by_year = year_groups.len() > 1;
+        for (year, mut year_members) in year_groups {
+            year_members.sort_unstable();
+            let group_key = match year {
+                Some(year) if split_by_year => format!("{key}:{year}"),
+                _ => key.clone(),
+            };
+            let mut seen_providers = HashSet::new();
+            let mut merged_members = Vec::new();
+            for index in year_members {
+                if seen_providers.insert(games[index].provider.clone()) {
+                    merged_members.push(index);
+                } else {
+                    groups.push((format!("game:{}", games[index].id), vec![index]));
+                }
+            }
+            groups.push((group_key, merged_members));
+        }
+    }
+
+    groups.sort_by_key(|(_, members)| members.first().copied().unwrap_or(usize::MAX));
+    groups
+        .into_iter()
+        .filter(|(_, members)| !members.is_empty())
+        .map(|(group_key, members)| build_merged_game(games, group_key, members, preferences))
+        .collect()
+}
+
+fn build_merged_game(
+    games: &[GameResponse],
+    group_key: String,
+    mut members: Vec<usize>,
+    preferences: &HashMap<String, String>,
+) -> MergedGameResponse {
+    let preferred_provider = preferences.get(&group_key);
+    // Preference order: the user's choice, then an installed copy, then Steam, then the copy
+    // with the most playtime.
+    members.sort_by_key(|index| {
+        let game = &games[*index];
+        (
+            preferred_provider != Some(&game.provider),
+            !game.installed,
+            game.provider != "steam",
+            std::cmp::Reverse(game.playtime_minutes),
+        )
+    });
+    let members = members
+        .into_iter()
+        .map(|index| games[index].clone())
+        .collect::<Vec<_>>();
+    let preferred = &members[0];
+
+    MergedGameResponse {
+        name: preferred.name.clone(),
+        preferred_provider: preferred.provider.clone(),
+        preferred_game_id: preferred.id.clone(),
+        installed: members.iter().any(|game| game.installed),
+        playtime_minutes: members.iter().map(|game| game.playtime_minutes).sum(),
+        group_key,
+        games: members,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn library_game(provider: &str, external_id: &str, name: &str, release_date: Option<&str>) -> GameResponse {
+        GameResponse {
+            id: format!("{provider}:{external_id}"),
+            provider: provider.to_owned(),
+            external_id: external_id.to_owned(),
+            name: name.to_owned(),
+            original_name: None,
+            kind: String::from("game"),
+            playtime_minutes: 0,
+            playtime_2weeks_minutes: 0,
+            installed: provider == "ea",
+            artwork_url: None,
+            custom_artwork_path: None,
+            last_synced_at: String::new(),
+            last_played_at: None,
+            added_at: None,
+            favorite: false,
+            running: false,
+            steam_tags: Vec::new(),
+            user_tags: Vec::new(),
+            genres: Vec::new(),
+            categories: Vec::new(),
+            collections: Vec::new(),
+            collection_positions: BTreeMap::new(),
+            hide_in_library: false,
+            developers: Vec::new(),
+            publishers: Vec::new(),
+            franchise: None,
+            release_date: release_date.map(str::to_owned),
+            short_description: None,
+            header_image: None,
+            has_achievements: false,
+            has_cloud_saves: false,
+            controller_support: None,
+            vr_support: None,
+            achievements_count: None,
+            achievements_unlocked: None,
+            completed: false,
+            cloud_details: None,
+            features: Vec::new(),
+            metacritic_score: None,
+            recommendations_total: None,
+            review_score_description: None,
+            review_positive_percent: None,
+            review_total: None,
+        }
+    }
+
+    #[test]
+    fn merge_library_games_groups_cross_provider_copies() {
+        assert_eq!(
+            normalize_game_title("Mass Effect™ Legendary Edition (2021)"),
+            "mass effect legendary"
+        );
+
+        let games = vec![
+            library_game("steam", "1328670", "Mass Effect Legendary Edition", Some("14 May, 2021")),
+            library_game("ea", "Origin.OFR.50.0004096", "Mass Effect™ Legendary Edition", None),
+            library_game("steam", "17460", "Mass Effect", Some("7 Jul, 2008")),
+            library_game("ubisoft", "5", "Mass Effect", Some("2020")),
+        ];
+
+        let merged = merge_library_games(&games, &HashMap::new(), &HashMap::new());
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[0].preferred_provider, "ea");
+        assert_eq!(merged[0].games.len(), 2);
+        assert_eq!(merged[1].games.len(), 1);
+        assert_eq!(merged[2].games.len(), 1);
+
+        let preferences = HashMap::from([(merged[0].group_key.clone(), String::from("steam"))]);
+        let merged = merge_library_games(&games, &HashMap::new(), &preferences);
+        assert_eq!(merged[0].preferred_game_id, "steam:1328670");
+    }
+}
@@ -0,0 +1,576 @@
+use crate::*;
+
+const LIBRARY_SEARCH_DEFAULT_LIMIT: usize = 50;
+const LIBRARY_SEARCH_MAX_LIMIT: usize = 200;
+const GAME_NOTES_MAX_LENGTH: usize = 10_000;
+const LIBRARY_SEARCH_HIGHLIGHT_START: &str = "<mark>";
+const LIBRARY_SEARCH_HIGHLIGHT_END: &str = "</mark>";
+
+/// `game_search` rows share their rowid with `games`, so triggers can replace a game's entry
+/// without scanning the index. `game_search_source` assembles the indexed text for a game; a
+/// display title is indexed alongside the synced name so either finds the game, and the user's
+/// own tags alongside the Steam Store tags.
+const GAME_SEARCH_SCHEMA: &str = r#"
+    CREATE VIRTUAL TABLE IF NOT EXISTS game_search USING fts5(
+      name,
+      tags,
+      collections,
+      notes,
+      tokenize = 'unicode61 remove_diacritics 2'
+    );
+
+    CREATE VIEW IF NOT EXISTS game_search_source AS
+    SELECT
+      g.rowid AS game_rowid,
+      COALESCE(
+        (
+          SELECT d.display_name || ' ' || g.name
+          FROM game_display_names d
+          WHERE d.user_id = g.user_id
+            AND d.provider = g.provider
+            AND d.external_id = g.external_id
+        ),
+        g.name
+      ) AS name,
+      NULLIF(
+        trim(
+          COALESCE(
+            CASE WHEN g.provider = 'steam' THEN (
+              SELECT replace(replace(replace(t.tags_json, '","', ', '), '["', ''), '"]', '')
+              FROM steam_app_store_tags t
+              WHERE t.app_id = g.external_id
+            ) END,
+            ''
+          ) || ', ' || COALESCE(
+            (
+              SELECT group_concat(u.tag, ', ')
+              FROM game_user_tags u
+              WHERE u.user_id = g.user_id
+                AND u.provider = g.provider
+                AND u.external_id = g.external_id
+            ),
+            ''
+          ),
+          ', '
+        ),
+        ''
+      ) AS tags,
+      (
+        SELECT group_concat(c.name, ', ')
+        FROM collection_games membership
+        JOIN collections c
+          ON c.id = membership.collection_id
+         AND c.user_id = membership.user_id
+        WHERE membership.user_id = g.user_id
+          AND membership.provider = g.provider
+          AND membership.external_id = g.external_id
+      ) AS collections,
+      (
+        SELECT n.notes
+        FROM game_notes n
+        WHERE n.user_id = g.user_id
+          AND n.provider = g.provider
+          AND n.external_id = g.external_id
+      ) AS notes
+    FROM games g;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_games_insert AFTER INSERT ON games BEGIN
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source WHERE game_rowid = NEW.rowid;
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_games_update AFTER UPDATE OF name ON games BEGIN
+      DELETE FROM game_search WHERE rowid = OLD.rowid;
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source WHERE game_rowid = NEW.rowid;
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_games_delete AFTER DELETE ON games BEGIN
+      DELETE FROM game_search WHERE rowid = OLD.rowid;
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_store_tags_insert AFTER INSERT ON steam_app_store_tags BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (SELECT rowid FROM games WHERE provider = 'steam' AND external_id = NEW.app_id);
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (SELECT rowid FROM games WHERE provider = 'steam' AND external_id = NEW.app_id);
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_store_tags_update AFTER UPDATE ON steam_app_store_tags BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (SELECT rowid FROM games WHERE provider = 'steam' AND external_id = NEW.app_id);
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (SELECT rowid FROM games WHERE provider = 'steam' AND external_id = NEW.app_id);
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_membership_insert AFTER INSERT ON collection_games BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_membership_delete AFTER DELETE ON collection_games BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_collection_rename AFTER UPDATE OF name ON collections BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT g.rowid FROM games g
+        JOIN collection_games membership
+          ON membership.user_id = g.user_id
+         AND membership.provider = g.provider
+         AND membership.external_id = g.external_id
+        WHERE membership.collection_id = NEW.id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT g.rowid FROM games g
+        JOIN collection_games membership
+          ON membership.user_id = g.user_id
+         AND membership.provider = g.provider
+         AND membership.external_id = g.external_id
+        WHERE membership.collection_id = NEW.id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_notes_insert AFTER INSERT ON game_notes BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_notes_update AFTER UPDATE ON game_notes BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_notes_delete AFTER DELETE ON game_notes BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_display_name_insert AFTER INSERT ON game_display_names BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_display_name_update AFTER UPDATE ON game_display_names BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_display_name_delete AFTER DELETE ON game_display_names BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_user_tag_insert AFTER INSERT ON game_user_tags BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = NEW.user_id AND provider = NEW.provider AND external_id = NEW.external_id
+      );
+    END;
+
+    CREATE TRIGGER IF NOT EXISTS game_search_user_tag_delete AFTER DELETE ON game_user_tags BEGIN
+      DELETE FROM game_search
+      WHERE rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+      INSERT INTO game_search (rowid, name, tags, collections, notes)
+      SELECT game_rowid, name, tags, collections, notes FROM game_search_source
+      WHERE game_rowid IN (
+        SELECT rowid FROM games
+        WHERE user_id = OLD.user_id AND provider = OLD.provider AND external_id = OLD.external_id
+      );
+    END;
+"#;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibrarySearchMatch {
+    pub provider: String,
+    pub external_id: String,
+    pub name: String,
+    /// The best matching excerpt, with matches wrapped in `<mark>`; the source text itself is
+    /// not HTML-escaped.
+    pub snippet: String,
+    /// BM25 score; lower is a better match.
+    pub rank: f64,
+}
+
+/// Creates the search index and its triggers, and rebuilds the index when it has drifted from
+/// `games`, e.g. on the first start after upgrading.
+pub(crate) fn migrate_game_search_index(connection: &Connection) -> Result<(), String> {
+    // Views can't be altered, so one from before display titles or user tags is dropped and
+    // recreated.
+    let outdated_source_view = connection
+        .query_row(
+            "SELECT sql FROM sqlite_master WHERE type = 'view' AND name = 'game_search_source'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to inspect library search view: {error}"))?
+        .is_some_and(|sql| !sql.contains("game_display_names") || !sql.contains("game_user_tags"));
+    if outdated_source_view {
+        connection
+            .execute_batch("DROP VIEW game_search_source;")
+            .map_err(|error| format!("Failed to drop library search view: {error}"))?;
+    }
+    connection
+        .execute_batch(GAME_SEARCH_SCHEMA)
+        .map_err(|error| format!("Failed to create library search index: {error}"))?;
+
+    let (indexed_games, games): (i64, i64) = connection
+        .query_row(
+            "SELECT (SELECT COUNT(*) FROM game_search), (SELECT COUNT(*) FROM games)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(|error| format!("Failed to inspect library search index: {error}"))?;
+    if outdated_source_view || indexed_games != games {
+        connection
+            .execute_batch(
+                "
+                DELETE FROM game_search;
+                INSERT INTO game_search (rowid, name, tags, collections, notes)
+                SELECT game_rowid, name, tags, collections, notes FROM game_search_source;
+                ",
+            )
+            .map_err(|error| format!("Failed to rebuild library search index: {error}"))?;
+    }
+
+    Ok(())
+}
+
+/// Turns free text into an FTS5 query that matches every word as a prefix, so user input can't
+/// trip over FTS5 operators or unbalanced quotes.
+fn build_library_search_query(query: &str) -> Option<String> {
+    let terms = query
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{term}\"*"))
+        .collect::<Vec<_>>();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+pub(crate) fn search_library_games(
+    connection: &Connection,
+    user_id: &str,
+    query: &str,
+    limit: Option<usize>,
+) -> Result<Vec<LibrarySearchMatch>, String> {
+    let Some(match_query) = build_library_search_query(query) else {
+        return Ok(Vec::new());
+    };
+    let limit = limit
+        .unwrap_or(LIBRARY_SEARCH_DEFAULT_LIMIT)
+        .clamp(1, LIBRARY_SEARCH_MAX_LIMIT);
+
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT
+              g.provider,
+              g.external_id,
+              COALESCE(display.display_name, g.name),
+              snippet(game_search, -1, ?3, ?4, '…', 12),
+              bm25(game_search, 10.0, 2.0, 3.0, 1.0) AS score
+            FROM game_search
+            JOIN games g ON g.rowid = game_search.rowid
+            LEFT JOIN game_display_names display
+              ON display.user_id = g.user_id
+              AND display.provider = g.provider
+              AND display.external_id = g.external_id
+            WHERE game_search MATCH ?1
+              AND g.user_id = ?2
+            ORDER BY score ASC
+            LIMIT ?5
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare library search: {error}"))?;
+    let rows = statement
+        .query_map(
+            params![
+                match_query,
+                user_id,
+                LIBRARY_SEARCH_HIGHLIGHT_START,
+                LIBRARY_SEARCH_HIGHLIGHT_END,
+                limit as i64
+            ],
+            |row| {
+                Ok(LibrarySearchMatch {
+                    provider: row.get(0)?,
+                    external_id: row.get(1)?,
+                    name: row.get(2)?,
+                    snippet: row.get(3)?,
+                    rank: row.get(4)?,
+                })
+            },
+        )
+        .map_err(|error| format!("Failed to search library: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode library search result: {error}"))
+}
+
+pub(crate) fn load_game_notes(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<String, String> {
+    connection
+        .query_row(
+            "SELECT notes FROM game_notes WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+            params![user_id, provider, external_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map(Option::unwrap_or_default)
+        .map_err(|error| format!("Failed to load game notes: {error}"))
+}
+
+/// Blank notes delete the row, which also drops them from the search index.
+pub(crate) fn save_game_notes(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    notes: &str,
+) -> Result<String, String> {
+    let notes = notes.trim();
+    if notes.chars().count() > GAME_NOTES_MAX_LENGTH {
+        return Err(format!("Notes can be at most {GAME_NOTES_MAX_LENGTH} characters"));
+    }
+    if notes.is_empty() {
+        connection
+            .execute(
+                "DELETE FROM game_notes WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+                params![user_id, provider, external_id],
+            )
+            .map_err(|error| format!("Failed to clear game notes: {error}"))?;
+        return Ok(String::new());
+    }
+
+    connection
+        .execute(
+            "
+            INSERT INTO game_notes (user_id, provider, external_id, notes, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+              notes = excluded.notes,
+              updated_at = excluded.updated_at
+            ",
+            params![user_id, provider, external_id, notes, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to save game notes: {error}"))?;
+    Ok(notes.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn search_library_games_ranks_and_follows_writes() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for (external_id, name) in [("620", "Portal 2"), ("400", "Portal"), ("70", "Half-Life")] {
+            database.insert_game("steam", external_id, name);
+        }
+        cache_steam_store_tags(&connection, 70, &[String::from("Shooter"), String::from("Classic")])
+            .expect("cache tags");
+        let collection = create_user_collection(&connection, &user.id, "Couch Co-op").expect("create collection");
+        add_game_to_collection_membership(&connection, &user.id, &collection.id, "steam", "620")
+            .expect("add membership");
+        save_game_notes(&connection, &user.id, "steam", "70", "Replay before the Portal games")
+            .expect("save notes");
+
+        let portal_matches = search_library_games(&connection, &user.id, "portal", None).expect("search");
+        assert_eq!(portal_matches.len(), 3);
+        // Name matches outrank the same word in notes.
+        assert_eq!(portal_matches[2].external_id, "70");
+        assert!(portal_matches[2].snippet.contains("<mark>Portal</mark>"));
+
+        let tag_matches = search_library_games(&connection, &user.id, "shoot", None).expect("search");
+        assert_eq!(tag_matches.len(), 1);
+        assert_eq!(tag_matches[0].external_id, "70");
+        assert!(tag_matches[0].snippet.contains("<mark>Shooter</mark>"));
+
+        let collection_matches = search_library_games(&connection, &user.id, "couch", None).expect("search");
+        assert_eq!(collection_matches.len(), 1);
+        rename_user_collection(&connection, &user.id, &collection.id, "Local Multiplayer").expect("rename");
+        assert!(search_library_games(&connection, &user.id, "couch", None).expect("search").is_empty());
+        assert_eq!(
+            search_library_games(&connection, &user.id, "multiplayer", None).expect("search").len(),
+            1
+        );
+
+        connection
+            .execute("DELETE FROM games WHERE user_id = ?1 AND external_id = '70'", params![user.id])
+            .expect("delete game");
+        assert!(search_library_games(&connection, &user.id, "shooter", None).expect("search").is_empty());
+        assert!(search_library_games(&connection, &user.id, "\"  ", None).expect("search").is_empty());
+
+        save_game_display_name(&connection, &user.id, "steam", "400", Some("Aperture Classic")).expect("rename");
+        let display_matches = search_library_games(&connection, &user.id, "aperture", None).expect("search");
+        assert_eq!(display_matches[0].name, "Aperture Classic");
+        assert_eq!(search_library_games(&connection, &user.id, "portal", None).expect("search").len(), 2);
+        save_game_display_name(&connection, &user.id, "steam", "400", None).expect("clear");
+        assert!(search_library_games(&connection, &user.id, "aperture", None).expect("search").is_empty());
+    }
+
+    #[test]
+    fn build_library_search_query_quotes_every_term() {
+        assert_eq!(
+            build_library_search_query(" half-life  OR \"portal "),
+            Some(String::from("\"half-life\"* \"OR\"* \"portal\"*"))
+        );
+        assert_eq!(build_library_search_query("\" \"\""), None);
+        assert_eq!(build_library_search_query("   "), None);
+    }
+
+    #[test]
+    fn search_library_games_stays_within_the_user_and_limit() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for index in 0..3 {
+            database.insert_game("steam", &format!("{index}"), &format!("Portal {index}"));
+        }
+        let other_user = create_user(connection, "friend@example.com", "hash", None).expect("create user");
+        connection
+            .execute(
+                "
+                INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, last_synced_at)
+                VALUES (?1, 'steam', '620', 'Portal Stories', 'game', 0, '2024-01-01T00:00:00Z')
+                ",
+                params![other_user.id],
+            )
+            .expect("insert other user's game");
+
+        assert_eq!(search_library_games(connection, &user.id, "portal", None).expect("search").len(), 3);
+        assert_eq!(search_library_games(connection, &user.id, "portal", Some(2)).expect("search").len(), 2);
+        assert_eq!(search_library_games(connection, &user.id, "portal", Some(0)).expect("search").len(), 1);
+        assert!(search_library_games(connection, &user.id, "stories", None).expect("search").is_empty());
+        // FTS5 syntax in user input is matched as plain words instead of failing the query.
+        for query in ["NEAR(portal", "name:portal", "portal*)", "-portal"] {
+            assert!(search_library_games(connection, &user.id, query, None).is_ok(), "{query} failed");
+        }
+    }
+
+    #[test]
+    fn save_game_notes_limits_length_and_blank_notes_leave_the_index() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+
+        let too_long = "a".repeat(GAME_NOTES_MAX_LENGTH + 1);
+        assert!(save_game_notes(connection, &user.id, "steam", "620", &too_long).is_err());
+        assert_eq!(
+            save_game_notes(connection, &user.id, "steam", "620", "  speedrun route  ").expect("save notes"),
+            "speedrun route"
+        );
+        assert_eq!(search_library_games(connection, &user.id, "speedrun", None).expect("search").len(), 1);
+
+        assert_eq!(save_game_notes(connection, &user.id, "steam", "620", "  ").expect("clear notes"), "");
+        assert_eq!(load_game_notes(connection, &user.id, "steam", "620").expect("load notes"), "");
+        assert!(search_library_games(connection, &user.id, "speedrun", None).expect("search").is_empty());
+    }
+
+    #[test]
+    fn migrate_game_search_index_rebuilds_a_drifted_index() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+        connection.execute("DELETE FROM game_search", []).expect("drop index rows");
+        assert!(search_library_games(connection, &user.id, "portal", None).expect("search").is_empty());
+
+        migrate_game_search_index(connection).expect("migrate index");
+        assert_eq!(search_library_games(connection, &user.id, "portal", None).expect("search").len(), 1);
+    }
+}
@@ -0,0 +1,315 @@
+use crate::*;
+
+/// Games with less playtime than this count toward the backlog.
+pub(crate) const LIBRARY_STATS_BACKLOG_MAX_MINUTES: i64 = 120;
+const LIBRARY_STATS_TOP_BREAKDOWN_LIMIT: i64 = 25;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LibraryStatsResponse {
+    pub total_games: usize,
+    pub installed_games: usize,
+    pub total_playtime_minutes: i64,
+    pub backlog_games: usize,
+    pub playtime_by_genre: Vec<PlaytimeBreakdownEntry>,
+    pub playtime_by_tag: Vec<PlaytimeBreakdownEntry>,
+    pub most_played_by_year: Vec<MostPlayedYearEntry>,
+    pub disk_usage_by_provider: Vec<ProviderDiskUsageEntry>,
+    pub generated_at: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlaytimeBreakdownEntry {
+    pub name: String,
+    pub game_count: usize,
+    pub playtime_minutes: i64,
+}
+
+/// The game with the most total playtime among those last played in `year`. Per-year
+/// playtime isn't recorded, so the lifetime total is what is compared.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct MostPlayedYearEntry {
+    pub year: i32,
+    pub provider: String,
+    pub external_id: String,
+    pub name: String,
+    pub playtime_minutes: i64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProviderDiskUsageEntry {
+    pub provider: String,
+    pub games_bytes: u64,
+    pub game_count: usize,
+}
+
+fn query_playtime_breakdown(
+    connection: &Connection,
+    sql: &str,
+    user_id: &str,
+    exclude_private: bool,
+) -> Result<Vec<PlaytimeBreakdownEntry>, String> {
+    let mut statement = connection
+        .prepare(sql)
+        .map_err(|error| format!("Failed to prepare playtime breakdown query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id, LIBRARY_STATS_TOP_BREAKDOWN_LIMIT, exclude_private], |row| {
+            Ok(PlaytimeBreakdownEntry {
+                name: row.get(0)?,
+                game_count: row.get::<_, i64>(1)?.max(0) as usize,
+                playtime_minutes: row.get(2)?,
+            })
+        })
+        .map_err(|error| format!("Failed to query playtime breakdown: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode playtime breakdown row: {error}"))
+}
+
+fn query_most_played_by_year(
+    connection: &Connection,
+    user_id: &str,
+    exclude_private: bool,
+) -> Result<Vec<MostPlayedYearEntry>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT year, provider, external_id, name, playtime_minutes
+            FROM (
+              SELECT
+                CAST(substr(last_played_at, 1, 4) AS INTEGER) AS year,
+                provider,
+                external_id,
+                name,
+                playtime_minutes,
+                ROW_NUMBER() OVER (
+                  PARTITION BY substr(last_played_at, 1, 4)
+                  ORDER BY playtime_minutes DESC, name COLLATE NOCASE ASC
+                ) AS year_rank
+              FROM games game
+              WHERE user_id = ?1
+                AND last_played_at IS NOT NULL
+                AND playtime_minutes > 0
+                AND (?2 = 0 OR NOT EXISTS (
+                  SELECT 1 FROM game_privacy_settings privacy
+                  WHERE privacy.user_id = game.user_id
+                    AND privacy.provider = game.provider
+                    AND privacy.external_id = game.external_id
+                    AND privacy.mark_as_private != 0
+                ))
+            )
+            WHERE year_rank = 1 AND year > 0
+            ORDER BY year DESC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare most played query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id, exclude_private], |row| {
+            Ok(MostPlayedYearEntry {
+                year: row.get(0)?,
+                provider: row.get(1)?,
+                external_id: row.get(2)?,
+                name: row.get(3)?,
+                playtime_minutes: row.get(4)?,
+            })
+        })
+        .map_err(|error| format!("Failed to query most played games: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode most played row: {error}"))
+}
+
+/// Computes the library-wide numbers of the stats dashboard. Disk usage comes from the storage
+/// overview, since installed sizes live on disk rather than in the database. With
+/// `exclude_private`, games marked private count nowhere, as while they are locked.
+pub(crate) fn compute_library_stats(
+    connection: &Connection,
+    user_id: &str,
+    disk_usage_by_provider: Vec<ProviderDiskUsageEntry>,
+    exclude_private: bool,
+) -> Result<LibraryStatsResponse, String> {
+    let (total_games, installed_games, total_playtime_minutes, backlog_games) = connection
+        .query_row(
+            "
+            SELECT
+              COUNT(*),
+              COALESCE(SUM(CASE WHEN installed != 0 THEN 1 ELSE 0 END), 0),
+              COALESCE(SUM(playtime_minutes), 0),
+              COALESCE(SUM(CASE WHEN playtime_minutes < ?2 THEN 1 ELSE 0 END), 0)
+            FROM games game
+            WHERE user_id = ?1
+              AND (?3 = 0 OR NOT EXISTS (
+                SELECT 1 FROM game_privacy_settings privacy
+                WHERE privacy.user_id = game.user_id
+                  AND privacy.provider = game.provider
+                  AND privacy.external_id = game.external_id
+                  AND privacy.mark_as_private != 0
+              ))
+            ",
+            params![user_id, LIBRARY_STATS_BACKLOG_MAX_MINUTES, exclude_private],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, i64>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, i64>(3)?,
+                ))
+            },
+        )
+        .map_err(|error| format!("Failed to query library totals: {error}"))?;
+
+    let playtime_by_genre = query_playtime_breakdown(
+        connection,
+        "
+        SELECT genre.genre, COUNT(*), COALESCE(SUM(game.playtime_minutes), 0)
+        FROM game_genres genre
+        JOIN games game
+          ON game.user_id = genre.user_id
+         AND game.provider = genre.provider
+         AND game.external_id = genre.external_id
+        WHERE genre.user_id = ?1
+          AND (?3 = 0 OR NOT EXISTS (
+            SELECT 1 FROM game_privacy_settings privacy
+            WHERE privacy.user_id = game.user_id
+              AND privacy.provider = game.provider
+              AND privacy.external_id = game.external_id
+              AND privacy.mark_as_private != 0
+          ))
+        GROUP BY genre.genre COLLATE NOCASE
+        ORDER BY 3 DESC, 2 DESC
+        LIMIT ?2
+        ",
+        user_id,
+        exclude_private,
+    )?;
+    let playtime_by_tag = query_playtime_breakdown(
+        connection,
+        "
+        SELECT tag.value, COUNT(*), COALESCE(SUM(game.playtime_minutes), 0)
+        FROM games game
+        JOIN steam_app_store_tags tags ON tags.app_id = game.external_id
+        JOIN json_each(CASE WHEN json_valid(tags.tags_json) THEN tags.tags_json ELSE '[]' END) tag
+        WHERE game.user_id = ?1
+          AND game.provider = 'steam'
+          AND (?3 = 0 OR NOT EXISTS (
+            SELECT 1 FROM game_privacy_settings privacy
+            WHERE privacy.user_id = game.user_id
+              AND privacy.provider = game.provider
+              AND privacy.external_id = game.external_id
+              AND privacy.mark_as_private != 0
+          ))
+        GROUP BY tag.value COLLATE NOCASE
+        ORDER BY 3 DESC, 2 DESC
+        LIMIT ?2
+        ",
+        user_id,
+        exclude_private,
+    )?;
+
+    Ok(LibraryStatsResponse {
+        total_games: total_games.max(0) as usize,
+        installed_games: installed_games.max(0) as usize,
+        total_playtime_minutes,
+        backlog_games: backlog_games.max(0) as usize,
+        playtime_by_genre,
+        playtime_by_tag,
+        most_played_by_year: query_most_played_by_year(connection, user_id, exclude_private)?,
+        disk_usage_by_provider,
+        generated_at: Utc::now().to_rfc3339(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_library_stats_aggregates_playtime() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for (external_id, name, playtime_minutes, installed, last_played_at) in [
+            ("620", "Portal 2", 600, 1, "2023-05-01T10:00:00Z"),
+            ("400", "Portal", 300, 0, "2023-02-01T10:00:00Z"),
+            ("440", "Team Fortress 2", 30, 1, "2024-01-01T10:00:00Z"),
+        ] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, installed, last_synced_at, last_played_at)
+                    VALUES (?1, 'steam', ?2, ?3, 'game', ?4, ?5, ?6, ?6)
+                    ",
+                    params![user.id, external_id, name, playtime_minutes, installed, last_played_at],
+                )
+                .expect("insert game");
+        }
+        connection
+            .execute(
+                "INSERT INTO steam_app_store_tags (app_id, tags_json, fetched_at) VALUES ('620', '[\"Puzzle\",\"Co-op\"]', '2024-01-01T00:00:00Z')",
+                [],
+            )
+            .expect("insert tags");
+
+        let stats = compute_library_stats(&connection, &user.id, Vec::new(), false).expect("compute stats");
+
+        assert_eq!(stats.total_games, 3);
+        assert_eq!(stats.installed_games, 2);
+        assert_eq!(stats.total_playtime_minutes, 930);
+        assert_eq!(stats.backlog_games, 1);
+        assert_eq!(stats.playtime_by_tag.len(), 2);
+        assert_eq!(stats.playtime_by_tag[0].playtime_minutes, 600);
+        assert_eq!(
+            stats.most_played_by_year.iter().map(|entry| (entry.year, entry.name.as_str())).collect::<Vec<_>>(),
+            vec![(2024, "Team Fortress 2"), (2023, "Portal 2")]
+        );
+    }
+
+    #[test]
+    fn compute_library_stats_only_counts_the_users_dated_playtime() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let empty = compute_library_stats(connection, &user.id, Vec::new(), false).expect("empty stats");
+        assert_eq!((empty.total_games, empty.installed_games, empty.total_playtime_minutes), (0, 0, 0));
+        assert!(empty.playtime_by_genre.is_empty() && empty.most_played_by_year.is_empty());
+
+        let other_user = create_user(connection, "other@example.com", "hash", None).expect("create user");
+        for (user_id, external_id, name, playtime_minutes, last_played_at) in [
+            (user.id.as_str(), "620", "Portal 2", 0, Some("2024-05-01T10:00:00Z")),
+            (user.id.as_str(), "400", "Portal", 90, Some("never")),
+            (user.id.as_str(), "70", "Half-Life", 45, None),
+            (other_user.id.as_str(), "620", "Portal 2", 900, Some("2024-05-01T10:00:00Z")),
+        ] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, last_synced_at, last_played_at)
+                    VALUES (?1, 'steam', ?2, ?3, 'game', ?4, '2024-01-01T00:00:00Z', ?5)
+                    ",
+                    params![user_id, external_id, name, playtime_minutes, last_played_at],
+                )
+                .expect("insert game");
+            connection
+                .execute(
+                    "INSERT INTO game_genres (user_id, provider, external_id, genre) VALUES (?1, 'steam', ?2, 'Puzzle')",
+                    params![user_id, external_id],
+                )
+                .expect("insert genre");
+        }
+
+        let stats = compute_library_stats(connection, &user.id, Vec::new(), false).expect("compute stats");
+
+        assert_eq!((stats.total_games, stats.total_playtime_minutes, stats.backlog_games), (3, 135, 3));
+        assert_eq!(
+            stats.playtime_by_genre,
+            vec![PlaytimeBreakdownEntry {
+                name: String::from("Puzzle"),
+                game_count: 3,
+                playtime_minutes: 135,
+            }]
+        );
+        // Unplayed games and unparseable dates have no year to be the most played of.
+        assert!(stats.most_played_by_year.is_empty());
+    }
+}
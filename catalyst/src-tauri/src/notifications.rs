@@ -0,0 +1,372 @@
+use crate::*;
+
+pub(crate) const NOTIFICATION_KIND_DEVELOPER_RELEASE: &str = "developer_release";
+pub(crate) const NOTIFICATION_KIND_DOWNLOAD_FINISHED: &str = "download_finished";
+pub(crate) const NOTIFICATION_KIND_SYNC_COMPLETED: &str = "sync_completed";
+pub(crate) const NOTIFICATION_KIND_SYNC_FAILED: &str = "sync_failed";
+pub(crate) const NOTIFICATION_CREATED_EVENT: &str = "notification-created";
+const NOTIFICATION_MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(10);
+const NOTIFICATION_RETENTION_LIMIT: i64 = 200;
+const NOTIFICATION_LIST_DEFAULT_LIMIT: usize = 50;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct NotificationResponse {
+    pub id: String,
+    pub kind: String,
+    pub title: String,
+    pub body: String,
+    pub provider: Option<String>,
+    pub external_id: Option<String>,
+    pub read: bool,
+    pub created_at: String,
+}
+
+fn map_notification_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<NotificationResponse> {
+    Ok(NotificationResponse {
+        id: row.get(0)?,
+        kind: row.get(1)?,
+        title: row.get(2)?,
+        body: row.get(3)?,
+        provider: row.get(4)?,
+        external_id: row.get(5)?,
+        read: row.get::<_, Option<String>>(6)?.is_some(),
+        created_at: row.get(7)?,
+    })
+}
+
+/// Stores a notification for the notification center. It is shown natively the next time the
+/// notification monitor dispatches pending notifications. Only the newest
+/// `NOTIFICATION_RETENTION_LIMIT` notifications per user are kept.
+pub(crate) fn create_notification(
+    connection: &Connection,
+    user_id: &str,
+    kind: &str,
+    title: &str,
+    body: &str,
+    game: Option<(&str, &str)>,
+) -> Result<NotificationResponse, String> {
+    let notification = NotificationResponse {
+        id: Uuid::new_v4().to_string(),
+        kind: kind.to_owned(),
+        title: title.to_owned(),
+        body: body.to_owned(),
+        provider: game.map(|(provider, _)| provider.to_owned()),
+        external_id: game.map(|(_, external_id)| external_id.to_owned()),
+        read: false,
+        created_at: Utc::now().to_rfc3339(),
+    };
+    connection
+        .execute(
+            "
+            INSERT INTO notifications (id, user_id, kind, title, body, provider, external_id, read_at, dispatched_at, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, NULL, NULL, ?8)
+            ",
+            params![
+                notification.id,
+                user_id,
+                notification.kind,
+                notification.title,
+                notification.body,
+                notification.provider,
+                notification.external_id,
+                notification.created_at
+            ],
+        )
+        .map_err(|error| format!("Failed to save notification: {error}"))?;
+    connection
+        .execute(
+            "
+            DELETE FROM notifications
+            WHERE user_id = ?1
+              AND id NOT IN (
+                SELECT id FROM notifications WHERE user_id = ?1 ORDER BY created_at DESC LIMIT ?2
+              )
+            ",
+            params![user_id, NOTIFICATION_RETENTION_LIMIT],
+        )
+        .map_err(|error| format!("Failed to prune notifications: {error}"))?;
+
+    Ok(notification)
+}
+
+pub(crate) fn list_notifications(
+    connection: &Connection,
+    user_id: &str,
+    unread_only: bool,
+    limit: Option<usize>,
+) -> Result<Vec<NotificationResponse>, String> {
+    let limit = limit.unwrap_or(NOTIFICATION_LIST_DEFAULT_LIMIT).clamp(1, NOTIFICATION_RETENTION_LIMIT as usize);
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT id, kind, title, body, provider, external_id, read_at, created_at
+            FROM notifications
+            WHERE user_id = ?1 AND (?2 = 0 OR read_at IS NULL)
+            ORDER BY created_at DESC
+            LIMIT ?3
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare notification query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id, i64::from(unread_only), limit as i64], map_notification_row)
+        .map_err(|error| format!("Failed to query notifications: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode notification row: {error}"))
+}
+
+/// Marks one notification as read, or every unread notification when `notification_id` is
+/// `None`. Returns how many notifications changed.
+pub(crate) fn mark_notifications_read(
+    connection: &Connection,
+    user_id: &str,
+    notification_id: Option<&str>,
+) -> Result<usize, String> {
+    let read_at = Utc::now().to_rfc3339();
+    let updated_rows = match notification_id {
+        Some(notification_id) => connection.execute(
+            "UPDATE notifications SET read_at = ?1 WHERE user_id = ?2 AND id = ?3 AND read_at IS NULL",
+            params![read_at, user_id, notification_id],
+        ),
+        None => connection.execute(
+            "UPDATE notifications SET read_at = ?1 WHERE user_id = ?2 AND read_at IS NULL",
+            params![read_at, user_id],
+        ),
+    }
+    .map_err(|error| format!("Failed to mark notifications as read: {error}"))?;
+    Ok(updated_rows)
+}
+
+/// Returns notifications that have not been shown yet and marks them as dispatched, so each
+/// one is surfaced once even if several monitors race.
+pub(crate) fn take_undispatched_notifications(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<Vec<NotificationResponse>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT id, kind, title, body, provider, external_id, read_at, created_at
+            FROM notifications
+            WHERE user_id = ?1 AND dispatched_at IS NULL
+            ORDER BY created_at ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare pending notification query: {error}"))?;
+    let pending = statement
+        .query_map(params![user_id], map_notification_row)
+        .map_err(|error| format!("Failed to query pending notifications: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode pending notification row: {error}"))?;
+
+    let dispatched_at = Utc::now().to_rfc3339();
+    let mut dispatched = Vec::with_capacity(pending.len());
+    for notification in pending {
+        let claimed = connection
+            .execute(
+                "UPDATE notifications SET dispatched_at = ?1 WHERE id = ?2 AND dispatched_at IS NULL",
+                params![dispatched_at, notification.id],
+            )
+            .map_err(|error| format!("Failed to mark notification as dispatched: {error}"))?;
+        if claimed > 0 {
+            dispatched.push(notification);
+        }
+    }
+    Ok(dispatched)
+}
+
+fn record_finished_downloads(
+    state: &AppState,
+    previous_downloads: &HashMap<String, SteamDownloadProgressResponse>,
+    current_downloads: &HashMap<String, SteamDownloadProgressResponse>,
+) -> Result<(), String> {
+    let finished = previous_downloads
+        .iter()
+        .filter(|(external_id, _)| !current_downloads.contains_key(*external_id))
+        .filter(|(external_id, _)| {
+            external_id.parse::<u64>().is_ok_and(|app_id| {
+                download_queue::is_steam_app_fully_installed(state.steam_root_override().as_deref(), app_id)
+            })
+        })
+        .map(|(_, download)| download)
+        .collect::<Vec<_>>();
+    if finished.is_empty() {
+        return Ok(());
+    }
+
+    let connection = open_connection(&state.db_path)?;
+    let user = get_authenticated_user(state, &connection)?;
+    for download in finished {
+        create_notification(
+            &connection,
+            &user.id,
+            NOTIFICATION_KIND_DOWNLOAD_FINISHED,
+            "Download finished",
+            &format!("{} is ready to play.", download.name),
+            Some((&download.provider, &download.external_id)),
+        )?;
+    }
+    Ok(())
+}
+
+fn dispatch_pending_notifications(app_handle: &tauri::AppHandle, state: &AppState) -> Result<(), String> {
+    use tauri::Emitter;
+    use tauri_plugin_notification::NotificationExt;
+
+    let connection = open_connection(&state.db_path)?;
+    let Ok(user) = get_authenticated_user(state, &connection) else {
+        return Ok(());
+    };
+    for notification in take_undispatched_notifications(&connection, &user.id)? {
+        if state.native_notifications() {
+            if let Err(error) = app_handle
+                .notification()
+                .builder()
+                .title(&notification.title)
+                .body(&notification.body)
+                .show()
+            {
+                eprintln!("Failed to show native notification: {error}");
+            }
+        }
+        let _ = app_handle.emit(NOTIFICATION_CREATED_EVENT, notification);
+    }
+    Ok(())
+}
+
+/// Watches Steam downloads for completions and surfaces newly stored notifications, both as a
+/// `notification-created` event for the notification center and, when enabled, natively.
+pub(crate) fn spawn_notification_monitor(app_handle: tauri::AppHandle) {
+    use tauri::Manager;
+
+    let _ = thread::Builder::new()
+        .name("notification-monitor".into())
+        .spawn(move || {
+            let mut previous_downloads: Option<HashMap<String, SteamDownloadProgressResponse>> = None;
+            loop {
+                let state = app_handle.state::<AppState>();
+                if let Ok(downloads) =
+                    application::services::library_service::list_steam_downloads(state.inner())
+                {
+                    let current_downloads = downloads
+                        .into_iter()
+                        .map(|download| (download.external_id.clone(), download))
+                        .collect::<HashMap<_, _>>();
+                    // The first poll only establishes a baseline.
+                    if let Some(previous_downloads) = &previous_downloads {
+                        if let Err(error) =
+                            record_finished_downloads(state.inner(), previous_downloads, &current_downloads)
+                        {
+                            eprintln!("Failed to record finished downloads: {error}");
+                        }
+                    }
+                    previous_downloads = Some(current_downloads);
+                }
+                if let Err(error) = dispatch_pending_notifications(&app_handle, state.inner()) {
+                    eprintln!("Failed to dispatch notifications: {error}");
+                }
+
+                thread::sleep(NOTIFICATION_MONITOR_POLL_INTERVAL);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notifications_are_dispatched_once_and_can_be_marked_read() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+
+        let notification = create_notification(
+            &connection,
+            &user.id,
+            NOTIFICATION_KIND_DOWNLOAD_FINISHED,
+            "Download finished",
+            "Portal 2 is ready to play.",
+            Some(("steam", "620")),
+        )
+        .expect("create notification");
+
+        assert_eq!(take_undispatched_notifications(&connection, &user.id).expect("take").len(), 1);
+        assert!(take_undispatched_notifications(&connection, &user.id).expect("take").is_empty());
+        assert_eq!(
+            mark_notifications_read(&connection, &user.id, Some(&notification.id)).expect("mark read"),
+            1
+        );
+        assert!(list_notifications(&connection, &user.id, true, None).expect("list").is_empty());
+        assert_eq!(list_notifications(&connection, &user.id, false, None).expect("list").len(), 1);
+    }
+
+    fn pending_download(external_id: &str, name: &str) -> SteamDownloadProgressResponse {
+        SteamDownloadProgressResponse {
+            game_id: format!("steam:{external_id}"),
+            provider: String::from("steam"),
+            external_id: external_id.to_owned(),
+            name: name.to_owned(),
+            state: String::from("downloading"),
+            bytes_downloaded: None,
+            bytes_total: None,
+            progress_percent: None,
+            progress_source: None,
+            paused: false,
+            queue_position: None,
+        }
+    }
+
+    #[test]
+    fn create_notification_keeps_only_the_newest_notifications() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let other_user = create_user(connection, "other@example.com", "hash", None).expect("create user");
+        create_notification(connection, &other_user.id, NOTIFICATION_KIND_SYNC_COMPLETED, "Sync", "Done", None)
+            .expect("create other notification");
+
+        for index in 0..NOTIFICATION_RETENTION_LIMIT + 5 {
+            create_notification(
+                connection,
+                &user.id,
+                NOTIFICATION_KIND_SYNC_COMPLETED,
+                "Steam sync finished",
+                &format!("Sync {index}"),
+                None,
+            )
+            .expect("create notification");
+        }
+
+        let notifications = list_notifications(connection, &user.id, false, Some(usize::MAX)).expect("list");
+        assert_eq!(notifications.len(), NOTIFICATION_RETENTION_LIMIT as usize);
+        assert_eq!(list_notifications(connection, &user.id, false, Some(0)).expect("list").len(), 1);
+        assert_eq!(list_notifications(connection, &other_user.id, false, None).expect("list").len(), 1);
+        // Marking everything read stays within the user's own notifications.
+        assert_eq!(
+            mark_notifications_read(connection, &user.id, None).expect("mark all read"),
+            NOTIFICATION_RETENTION_LIMIT as usize
+        );
+        assert_eq!(list_notifications(connection, &other_user.id, true, None).expect("list").len(), 1);
+    }
+
+    #[test]
+    fn record_finished_downloads_skips_downloads_that_did_not_install() {
+        let database = test_support::test_database();
+        database.write_steam_app_manifest(620, "Portal 2");
+        let state = database.signed_in_state_with_steam_root();
+        let previous_downloads = HashMap::from([
+            (String::from("620"), pending_download("620", "Portal 2")),
+            (String::from("400"), pending_download("400", "Portal")),
+            (String::from("70"), pending_download("70", "Half-Life")),
+        ]);
+        let current_downloads = HashMap::from([(String::from("70"), pending_download("70", "Half-Life"))]);
+
+        record_finished_downloads(&state, &previous_downloads, &current_downloads).expect("record downloads");
+
+        let notifications = list_notifications(&database.connection, &database.user.id, false, None).expect("list");
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].kind, NOTIFICATION_KIND_DOWNLOAD_FINISHED);
+        assert_eq!(notifications[0].external_id.as_deref(), Some("620"));
+        assert_eq!(notifications[0].body, "Portal 2 is ready to play.");
+    }
+}
@@ -0,0 +1,608 @@
+use crate::*;
+
+pub(crate) const PLAYTIME_TIMELINE_GRANULARITY_WEEK: &str = "week";
+pub(crate) const PLAYTIME_TIMELINE_GRANULARITY_MONTH: &str = "month";
+const PLAY_SESSION_SOURCE_SESSION: &str = "session";
+const PLAY_SESSION_SOURCE_LAST_PLAYED: &str = "last_played";
+const GAME_SESSIONS_DEFAULT_LIMIT: usize = 50;
+const PLAY_SESSION_NOTES_MAX_CHARS: usize = 4000;
+const PLAY_SESSION_RATING_RANGE: std::ops::RangeInclusive<i64> = 1..=5;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlaytimeTimelineResponse {
+    pub granularity: String,
+    pub year: Option<i32>,
+    pub total_playtime_minutes: i64,
+    pub buckets: Vec<PlaytimeTimelineBucket>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlaytimeTimelineBucket {
+    /// `YYYY-MM` for months, `YYYY-Www` (Monday-based week of the year) for weeks.
+    pub period: String,
+    pub playtime_minutes: i64,
+    pub session_count: usize,
+    pub games: Vec<PlaytimeTimelineGameEntry>,
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PlaytimeTimelineGameEntry {
+    pub provider: String,
+    pub external_id: String,
+    pub name: String,
+    pub playtime_minutes: i64,
+    pub session_count: usize,
+    /// `session` for tracked play sessions, `last_played` for games seeded from the provider's
+    /// last played date and lifetime playtime.
+    pub source: String,
+}
+
+/// A finished play session with the user's journal entry and the screenshots taken while it ran.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GameSessionResponse {
+    pub id: String,
+    pub provider: String,
+    pub external_id: String,
+    pub started_at: String,
+    pub ended_at: String,
+    pub duration_minutes: i64,
+    pub notes: Option<String>,
+    /// 1 to 5.
+    pub rating: Option<i64>,
+    pub annotated_at: Option<String>,
+    pub screenshots: Vec<screenshots::GameScreenshotResponse>,
+}
+
+fn map_game_session_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<GameSessionResponse> {
+    Ok(GameSessionResponse {
+        id: row.get(0)?,
+        provider: row.get(1)?,
+        external_id: row.get(2)?,
+        started_at: row.get(3)?,
+        ended_at: row.get(4)?,
+        duration_minutes: row.get(5)?,
+        notes: row.get(6)?,
+        rating: row.get(7)?,
+        annotated_at: row.get(8)?,
+        screenshots: Vec::new(),
+    })
+}
+
+/// Finished sessions of a game, newest first. Screenshots are attached separately by
+/// `attach_session_screenshots`.
+pub(crate) fn list_game_sessions(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    limit: Option<usize>,
+) -> Result<Vec<GameSessionResponse>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT id, provider, external_id, started_at, ended_at, duration_minutes, notes, rating, annotated_at
+            FROM play_sessions
+            WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3 AND ended_at IS NOT NULL
+            ORDER BY started_at DESC
+            LIMIT ?4
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare game session query: {error}"))?;
+    let limit = limit.unwrap_or(GAME_SESSIONS_DEFAULT_LIMIT) as i64;
+    let sessions = statement
+        .query_map(params![user_id, provider, external_id, limit], map_game_session_row)
+        .map_err(|error| format!("Failed to query game sessions: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode game session row: {error}"))?;
+    Ok(sessions)
+}
+
+/// Saves the user's notes and rating for a finished session. Empty notes and a missing rating
+/// clear them.
+pub(crate) fn annotate_play_session(
+    connection: &Connection,
+    user_id: &str,
+    session_id: &str,
+    notes: Option<&str>,
+    rating: Option<i64>,
+) -> Result<GameSessionResponse, String> {
+    let notes = notes.map(str::trim).filter(|notes| !notes.is_empty());
+    if notes.is_some_and(|notes| notes.chars().count() > PLAY_SESSION_NOTES_MAX_CHARS) {
+        return Err(format!(
+            "Session notes must be at most {PLAY_SESSION_NOTES_MAX_CHARS} characters"
+        ));
+    }
+    if rating.is_some_and(|rating| !PLAY_SESSION_RATING_RANGE.contains(&rating)) {
+        return Err(format!(
+            "Session rating must be between {} and {}",
+            PLAY_SESSION_RATING_RANGE.start(),
+            PLAY_SESSION_RATING_RANGE.end()
+        ));
+    }
+
+    let annotated_at = (notes.is_some() || rating.is_some()).then(|| Utc::now().to_rfc3339());
+    let updated = connection
+        .execute(
+            "
+            UPDATE play_sessions
+            SET notes = ?3, rating = ?4, annotated_at = ?5
+            WHERE user_id = ?1 AND id = ?2 AND ended_at IS NOT NULL
+            ",
+            params![user_id, session_id, notes, rating, annotated_at],
+        )
+        .map_err(|error| format!("Failed to save session notes: {error}"))?;
+    if updated == 0 {
+        return Err(format!("Play session {session_id} not found"));
+    }
+    connection
+        .query_row(
+            "
+            SELECT id, provider, external_id, started_at, ended_at, duration_minutes, notes, rating, annotated_at
+            FROM play_sessions
+            WHERE user_id = ?1 AND id = ?2
+            ",
+            params![user_id, session_id],
+            map_game_session_row,
+        )
+        .map_err(|error| format!("Failed to load play session: {error}"))
+}
+
+/// Moves each screenshot into the session it was taken during. The window is widened by a
+/// watcher poll on both ends since sessions start and stop when the watcher notices them.
+/// Screenshots outside every session are dropped.
+pub(crate) fn attach_session_screenshots(
+    sessions: &mut [GameSessionResponse],
+    game_screenshots: Vec<screenshots::GameScreenshotResponse>,
+) {
+    let slack = ChronoDuration::from_std(GAME_SESSION_POLL_INTERVAL).unwrap_or_else(|_| ChronoDuration::zero());
+    let parse = |value: &str| chrono::DateTime::parse_from_rfc3339(value).ok().map(|time| time.with_timezone(&Utc));
+    let windows = sessions
+        .iter()
+        .map(|session| {
+            Some((
+                parse(&session.started_at)? - slack,
+                parse(&session.ended_at)? + slack,
+            ))
+        })
+        .collect::<Vec<_>>();
+    for screenshot in game_screenshots {
+        let Some(taken_at) = screenshot.created_at.as_deref().and_then(parse) else {
+            continue;
+        };
+        let session_index = windows.iter().position(|window| {
+            window.is_some_and(|(start, end)| (start..=end).contains(&taken_at))
+        });
+        if let Some(session_index) = session_index {
+            sessions[session_index].screenshots.push(screenshot);
+        }
+    }
+    for session in sessions {
+        screenshots::sort_screenshots_newest_first(&mut session.screenshots);
+    }
+}
+
+/// Opens a session for every user who owns the game, since the watcher doesn't know which
+/// account launched it.
+pub(crate) fn start_play_session(connection: &Connection, provider: &str, external_id: &str) -> Result<(), String> {
+    let mut statement = connection
+        .prepare("SELECT user_id FROM games WHERE provider = ?1 AND external_id = ?2")
+        .map_err(|error| format!("Failed to prepare game owner query: {error}"))?;
+    let user_ids = statement
+        .query_map(params![provider, external_id], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to query game owners: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode game owner row: {error}"))?;
+
+    let started_at = Utc::now().to_rfc3339();
+    for user_id in user_ids {
+        connection
+            .execute(
+                "
+                INSERT INTO play_sessions (id, user_id, provider, external_id, started_at, ended_at, duration_minutes)
+                VALUES (?1, ?2, ?3, ?4, ?5, NULL, 0)
+                ",
+                params![Uuid::new_v4().to_string(), user_id, provider, external_id, started_at],
+            )
+            .map_err(|error| format!("Failed to record play session: {error}"))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn end_play_session(connection: &Connection, provider: &str, external_id: &str) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            UPDATE play_sessions
+            SET ended_at = ?3,
+                duration_minutes = MAX(CAST(ROUND((julianday(?3) - julianday(started_at)) * 1440) AS INTEGER), 0)
+            WHERE provider = ?1 AND external_id = ?2 AND ended_at IS NULL
+            ",
+            params![provider, external_id, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to close play session: {error}"))?;
+    Ok(())
+}
+
+/// Sessions still open at startup were cut short by Catalyst exiting; when the game actually
+/// stopped is unknown, so they are dropped rather than counted.
+pub(crate) fn discard_interrupted_play_sessions(connection: &Connection) -> Result<(), String> {
+    connection
+        .execute("DELETE FROM play_sessions WHERE ended_at IS NULL", [])
+        .map_err(|error| format!("Failed to discard interrupted play sessions: {error}"))?;
+    Ok(())
+}
+
+/// Buckets playtime by week or month and by game. Games without any tracked session fall back
+/// to their last played date and lifetime playtime (Steam's `rtime_last_played` and
+/// `playtime_forever`), so the timeline has history from before session tracking started.
+/// With `exclude_private`, games marked private are left out entirely.
+pub(crate) fn compute_playtime_timeline(
+    connection: &Connection,
+    user_id: &str,
+    granularity: &str,
+    year: Option<i32>,
+    exclude_private: bool,
+) -> Result<PlaytimeTimelineResponse, String> {
+    let period_format = if granularity == PLAYTIME_TIMELINE_GRANULARITY_WEEK {
+        "%Y-W%W"
+    } else {
+        "%Y-%m"
+    };
+    let year_filter = year.map(|year| format!("{year:04}"));
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT period, provider, external_id, name, playtime_minutes, session_count, source
+            FROM (
+              SELECT
+                strftime(?2, session.started_at) AS period,
+                session.provider AS provider,
+                session.external_id AS external_id,
+                COALESCE(game.name, session.external_id) AS name,
+                SUM(session.duration_minutes) AS playtime_minutes,
+                COUNT(*) AS session_count,
+                ?4 AS source
+              FROM play_sessions session
+              LEFT JOIN games game
+                ON game.user_id = session.user_id
+               AND game.provider = session.provider
+               AND game.external_id = session.external_id
+              WHERE session.user_id = ?1
+                AND session.ended_at IS NOT NULL
+                AND (?3 IS NULL OR substr(session.started_at, 1, 4) = ?3)
+                AND (?6 = 0 OR NOT EXISTS (
+                  SELECT 1 FROM game_privacy_settings privacy
+                  WHERE privacy.user_id = session.user_id
+                    AND privacy.provider = session.provider
+                    AND privacy.external_id = session.external_id
+                    AND privacy.mark_as_private != 0
+                ))
+              GROUP BY period, session.provider, session.external_id
+
+              UNION ALL
+
+              SELECT
+                strftime(?2, game.last_played_at),
+                game.provider,
+                game.external_id,
+                game.name,
+                game.playtime_minutes,
+                0,
+                ?5
+              FROM games game
+              WHERE game.user_id = ?1
+                AND game.last_played_at IS NOT NULL
+                AND game.playtime_minutes > 0
+                AND (?3 IS NULL OR substr(game.last_played_at, 1, 4) = ?3)
+                AND (?6 = 0 OR NOT EXISTS (
+                  SELECT 1 FROM game_privacy_settings privacy
+                  WHERE privacy.user_id = game.user_id
+                    AND privacy.provider = game.provider
+                    AND privacy.external_id = game.external_id
+                    AND privacy.mark_as_private != 0
+                ))
+                AND NOT EXISTS (
+                  SELECT 1 FROM play_sessions session
+                  WHERE session.user_id = game.user_id
+                    AND session.provider = game.provider
+                    AND session.external_id = game.external_id
+                )
+            )
+            WHERE period IS NOT NULL
+            ORDER BY period ASC, playtime_minutes DESC, name COLLATE NOCASE ASC
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare playtime timeline query: {error}"))?;
+    let rows = statement
+        .query_map(
+            params![
+                user_id,
+                period_format,
+                year_filter,
+                PLAY_SESSION_SOURCE_SESSION,
+                PLAY_SESSION_SOURCE_LAST_PLAYED,
+                exclude_private
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    PlaytimeTimelineGameEntry {
+                        provider: row.get(1)?,
+                        external_id: row.get(2)?,
+                        name: row.get(3)?,
+                        playtime_minutes: row.get(4)?,
+                        session_count: row.get::<_, i64>(5)?.max(0) as usize,
+                        source: row.get(6)?,
+                    },
+                ))
+            },
+        )
+        .map_err(|error| format!("Failed to query playtime timeline: {error}"))?;
+
+    let mut buckets: Vec<PlaytimeTimelineBucket> = Vec::new();
+    for row in rows {
+        let (period, game) = row.map_err(|error| format!("Failed to decode playtime timeline row: {error}"))?;
+        if buckets.last().map(|bucket| bucket.period != period).unwrap_or(true) {
+            buckets.push(PlaytimeTimelineBucket {
+                period,
+                playtime_minutes: 0,
+                session_count: 0,
+                games: Vec::new(),
+            });
+        }
+        let bucket = buckets.last_mut().expect("bucket was just pushed");
+        bucket.playtime_minutes += game.playtime_minutes;
+        bucket.session_count += game.session_count;
+        bucket.games.push(game);
+    }
+
+    Ok(PlaytimeTimelineResponse {
+        granularity: granularity.to_owned(),
+        year,
+        total_playtime_minutes: buckets.iter().map(|bucket| bucket.playtime_minutes).sum(),
+        buckets,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_playtime_timeline_buckets_sessions_and_seeds_last_played() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for (external_id, name, playtime_minutes, last_played_at) in [
+            ("620", "Portal 2", 600, "2024-03-20T10:00:00Z"),
+            ("400", "Portal", 300, "2024-02-01T10:00:00Z"),
+        ] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, last_synced_at, last_played_at)
+                    VALUES (?1, 'steam', ?2, ?3, 'game', ?4, ?5, ?5)
+                    ",
+                    params![user.id, external_id, name, playtime_minutes, last_played_at],
+                )
+                .expect("insert game");
+        }
+        for (started_at, duration_minutes) in [("2024-03-02T18:00:00Z", 90), ("2024-03-09T18:00:00Z", 30)] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO play_sessions (id, user_id, provider, external_id, started_at, ended_at, duration_minutes)
+                    VALUES (?1, ?2, 'steam', '620', ?3, ?3, ?4)
+                    ",
+                    params![Uuid::new_v4().to_string(), user.id, started_at, duration_minutes],
+                )
+                .expect("insert session");
+        }
+
+        let timeline = compute_playtime_timeline(&connection, &user.id, PLAYTIME_TIMELINE_GRANULARITY_MONTH, Some(2024), false)
+            .expect("compute timeline");
+
+        assert_eq!(timeline.total_playtime_minutes, 420);
+        assert_eq!(
+            timeline
+                .buckets
+                .iter()
+                .map(|bucket| (bucket.period.as_str(), bucket.playtime_minutes, bucket.session_count))
+                .collect::<Vec<_>>(),
+            vec![("2024-02", 300, 0), ("2024-03", 120, 2)]
+        );
+        assert_eq!(timeline.buckets[0].games[0].source, PLAY_SESSION_SOURCE_LAST_PLAYED);
+        assert_eq!(timeline.buckets[1].games[0].source, PLAY_SESSION_SOURCE_SESSION);
+    }
+
+    #[test]
+    fn game_sessions_keep_annotations_and_collect_screenshots_taken_during_them() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        for (id, started_at, ended_at) in [
+            ("early", "2024-03-02T18:00:00+00:00", "2024-03-02T19:00:00+00:00"),
+            ("late", "2024-03-09T18:00:00+00:00", "2024-03-09T18:30:00+00:00"),
+        ] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO play_sessions (id, user_id, provider, external_id, started_at, ended_at, duration_minutes)
+                    VALUES (?1, ?2, 'steam', '620', ?3, ?4, 0)
+                    ",
+                    params![id, user.id, started_at, ended_at],
+                )
+                .expect("insert session");
+        }
+
+        let session = annotate_play_session(&connection, &user.id, "early", Some("  Beat chapter 3 "), Some(4))
+            .expect("annotate session");
+        assert_eq!((session.notes.as_deref(), session.rating), (Some("Beat chapter 3"), Some(4)));
+        assert!(annotate_play_session(&connection, &user.id, "early", None, Some(6)).is_err());
+        assert!(annotate_play_session(&connection, &user.id, "missing", None, None).is_err());
+
+        let mut sessions = list_game_sessions(&connection, &user.id, "steam", "620", None).expect("list sessions");
+        assert_eq!(sessions.iter().map(|session| session.id.as_str()).collect::<Vec<_>>(), vec!["late", "early"]);
+        let screenshot = |created_at: &str| screenshots::GameScreenshotResponse {
+            path: format!("/shots/{created_at}.png"),
+            thumbnail_path: None,
+            created_at: Some(created_at.to_owned()),
+            caption: None,
+            source: String::from("steam"),
+        };
+        attach_session_screenshots(
+            &mut sessions,
+            vec![
+                screenshot("2024-03-02T18:10:00+00:00"),
+                screenshot("2024-03-02T19:00:03+00:00"),
+                screenshot("2024-03-05T12:00:00+00:00"),
+                screenshot("2024-03-09T18:15:00+00:00"),
+            ],
+        );
+        assert_eq!(sessions[0].screenshots.len(), 1);
+        assert_eq!(
+            sessions[1]
+                .screenshots
+                .iter()
+                .map(|screenshot| screenshot.created_at.as_deref())
+                .collect::<Vec<_>>(),
+            vec![Some("2024-03-02T19:00:03+00:00"), Some("2024-03-02T18:10:00+00:00")]
+        );
+        assert_eq!(sessions[1].notes.as_deref(), Some("Beat chapter 3"));
+    }
+
+    #[test]
+    fn play_sessions_open_for_every_owner_and_drop_when_interrupted() {
+        let database = test_support::test_database();
+        let connection = &database.connection;
+        database.insert_game("steam", "620", "Portal 2");
+        let other_user = create_user(connection, "other@example.com", "hash", None).expect("create user");
+        connection
+            .execute(
+                "
+                INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, last_synced_at)
+                VALUES (?1, 'steam', '620', 'Portal 2', 'game', 0, '2024-01-01T00:00:00Z')
+                ",
+                params![other_user.id],
+            )
+            .expect("insert game");
+        let session_count = |condition: &str| {
+            connection
+                .query_row(&format!("SELECT COUNT(*) FROM play_sessions WHERE {condition}"), [], |row| {
+                    row.get::<_, i64>(0)
+                })
+                .expect("count sessions")
+        };
+
+        start_play_session(connection, "steam", "620").expect("start session");
+        start_play_session(connection, "steam", "999").expect("start unowned session");
+        assert_eq!(session_count("ended_at IS NULL"), 2);
+        // Open sessions are not part of the timeline yet.
+        let timeline = compute_playtime_timeline(connection, &database.user.id, PLAYTIME_TIMELINE_GRANULARITY_WEEK, None, false)
+            .expect("compute timeline");
+        assert!(timeline.buckets.is_empty());
+
+        end_play_session(connection, "steam", "620").expect("end session");
+        assert_eq!(session_count("ended_at IS NOT NULL AND duration_minutes >= 0"), 2);
+        start_play_session(connection, "steam", "620").expect("start another session");
+        discard_interrupted_play_sessions(connection).expect("discard interrupted");
+        assert_eq!(session_count("1 = 1"), 2);
+    }
+
+    #[test]
+    fn compute_playtime_timeline_filters_by_year_and_buckets_by_week() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        database.insert_game("steam", "620", "Portal 2");
+        for (started_at, duration_minutes) in [
+            ("2023-12-31T18:00:00Z", 60),
+            ("2024-01-01T18:00:00Z", 30),
+            ("2024-01-03T18:00:00Z", 15),
+            ("2024-01-08T18:00:00Z", 45),
+        ] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO play_sessions (id, user_id, provider, external_id, started_at, ended_at, duration_minutes)
+                    VALUES (?1, ?2, 'steam', '620', ?3, ?3, ?4)
+                    ",
+                    params![Uuid::new_v4().to_string(), user.id, started_at, duration_minutes],
+                )
+                .expect("insert session");
+        }
+
+        let timeline = compute_playtime_timeline(connection, &user.id, PLAYTIME_TIMELINE_GRANULARITY_WEEK, Some(2024), false)
+            .expect("compute timeline");
+        assert_eq!(
+            timeline
+                .buckets
+                .iter()
+                .map(|bucket| (bucket.period.as_str(), bucket.playtime_minutes, bucket.session_count))
+                .collect::<Vec<_>>(),
+            vec![("2024-W01", 45, 2), ("2024-W02", 45, 1)]
+        );
+        assert!(compute_playtime_timeline(connection, &user.id, PLAYTIME_TIMELINE_GRANULARITY_MONTH, Some(2022), false)
+            .expect("compute empty year")
+            .buckets
+            .is_empty());
+    }
+
+    #[test]
+    fn annotate_play_session_validates_and_clears_and_skips_open_or_foreign_sessions() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let friend = create_user(connection, "friend@example.com", "hash", None).expect("create user");
+        for (id, user_id, started_at, ended_at) in [
+            ("first", user.id.as_str(), "2024-03-01T18:00:00+00:00", Some("2024-03-01T19:00:00+00:00")),
+            ("second", user.id.as_str(), "2024-03-02T18:00:00+00:00", Some("2024-03-02T19:00:00+00:00")),
+            ("running", user.id.as_str(), "2024-03-03T18:00:00+00:00", None),
+            ("friends", friend.id.as_str(), "2024-03-01T18:00:00+00:00", Some("2024-03-01T19:00:00+00:00")),
+        ] {
+            connection
+                .execute(
+                    "
+                    INSERT INTO play_sessions (id, user_id, provider, external_id, started_at, ended_at, duration_minutes)
+                    VALUES (?1, ?2, 'steam', '620', ?3, ?4, 60)
+                    ",
+                    params![id, user_id, started_at, ended_at],
+                )
+                .expect("insert session");
+        }
+
+        let longest_notes = "a".repeat(PLAY_SESSION_NOTES_MAX_CHARS);
+        let session = annotate_play_session(connection, &user.id, "first", Some(&longest_notes), Some(1))
+            .expect("annotate session");
+        assert!(session.annotated_at.is_some());
+        let too_long_notes = "a".repeat(PLAY_SESSION_NOTES_MAX_CHARS + 1);
+        assert!(annotate_play_session(connection, &user.id, "first", Some(&too_long_notes), None).is_err());
+        assert!(annotate_play_session(connection, &user.id, "first", None, Some(0)).is_err());
+
+        // Blank notes and no rating clear the journal entry.
+        let session = annotate_play_session(connection, &user.id, "first", Some("   "), None).expect("clear session");
+        assert_eq!((session.notes, session.rating, session.annotated_at), (None, None, None));
+
+        assert!(annotate_play_session(connection, &user.id, "running", Some("Still playing"), None).is_err());
+        assert!(annotate_play_session(connection, &user.id, "friends", Some("Not mine"), None).is_err());
+        let friends_sessions = list_game_sessions(connection, &friend.id, "steam", "620", None).expect("friend's sessions");
+        assert!(friends_sessions[0].notes.is_none());
+
+        let sessions = list_game_sessions(connection, &user.id, "steam", "620", Some(1)).expect("list sessions");
+        assert_eq!(sessions.iter().map(|session| session.id.as_str()).collect::<Vec<_>>(), vec!["second"]);
+        assert_eq!(list_game_sessions(connection, &user.id, "steam", "620", None).expect("list sessions").len(), 2);
+
+        let mut sessions = list_game_sessions(connection, &user.id, "steam", "620", None).expect("list sessions");
+        attach_session_screenshots(
+            &mut sessions,
+            vec![screenshots::GameScreenshotResponse {
+                path: String::from("/shots/undated.png"),
+                thumbnail_path: None,
+                created_at: None,
+                caption: None,
+                source: String::from("custom"),
+            }],
+        );
+        assert!(sessions.iter().all(|session| session.screenshots.is_empty()));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::*;
+
+/// How long private games stay visible after `unlock_private_games` before they lock again.
+pub(crate) const PRIVATE_GAMES_RELOCK_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+const PRIVATE_GAMES_PIN_MIN_DIGITS: usize = 4;
+const PRIVATE_GAMES_PIN_MAX_DIGITS: usize = 12;
+/// Wrong PINs allowed in a row before checks pause for `PRIVATE_GAMES_PIN_COOLDOWN`.
+const PRIVATE_GAMES_PIN_MAX_ATTEMPTS: i64 = 5;
+pub(crate) const PRIVATE_GAMES_PIN_COOLDOWN: Duration = Duration::from_secs(5 * 60);
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PrivateGamesLockStatusResponse {
+    pub pin_set: bool,
+    pub unlocked: bool,
+    pub relocks_in_seconds: Option<u64>,
+}
+
+/// Private games unlocked with the PIN, kept in `AppState` so a restart always locks them again.
+#[derive(Debug, Clone)]
+pub(crate) struct PrivateGamesUnlock {
+    pub user_id: String,
+    pub expires_at: Instant,
+}
+
+pub(crate) fn normalize_private_games_pin(pin: &str) -> Result<String, String> {
+    let pin = pin.trim();
+    if pin.len() < PRIVATE_GAMES_PIN_MIN_DIGITS
+        || pin.len() > PRIVATE_GAMES_PIN_MAX_DIGITS
+        || !pin.chars().all(|character| character.is_ascii_digit())
+    {
+        return Err(format!(
+            "PIN must be {PRIVATE_GAMES_PIN_MIN_DIGITS} to {PRIVATE_GAMES_PIN_MAX_DIGITS} digits"
+        ));
+    }
+    Ok(pin.to_owned())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PrivateGamesPinCheck {
+    Accepted,
+    Rejected,
+    CoolingDown { retry_after: Duration },
+}
+
+struct StoredPrivateGamesPin {
+    pin_hash: String,
+    failed_attempts: i64,
+    locked_until: Option<String>,
+}
+
+fn find_private_games_pin(connection: &Connection, user_id: &str) -> Result<Option<StoredPrivateGamesPin>, String> {
+    connection
+        .query_row(
+            "SELECT pin_hash, failed_attempts, locked_until FROM private_games_pins WHERE user_id = ?1",
+            params![user_id],
+            |row| {
+                Ok(StoredPrivateGamesPin {
+                    pin_hash: row.get(0)?,
+                    failed_attempts: row.get(1)?,
+                    locked_until: row.get(2)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query private games PIN: {error}"))
+}
+
+pub(crate) fn has_private_games_pin(connection: &Connection, user_id: &str) -> Result<bool, String> {
+    Ok(find_private_games_pin(connection, user_id)?.is_some())
+}
+
+fn save_private_games_pin_attempts(
+    connection: &Connection,
+    user_id: &str,
+    failed_attempts: i64,
+    locked_until: Option<String>,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "UPDATE private_games_pins SET failed_attempts = ?2, locked_until = ?3 WHERE user_id = ?1",
+            params![user_id, failed_attempts, locked_until],
+        )
+        .map_err(|error| format!("Failed to record private games PIN attempt: {error}"))?;
+    Ok(())
+}
+
+/// Without a PIN there is nothing to check against, so any PIN is accepted. Wrong PINs are
+/// counted in the database, so restarting the app does not reset the cooldown.
+pub(crate) fn check_private_games_pin(
+    connection: &Connection,
+    user_id: &str,
+    pin: &str,
+) -> Result<PrivateGamesPinCheck, String> {
+    let Some(stored) = find_private_games_pin(connection, user_id)? else {
+        return Ok(PrivateGamesPinCheck::Accepted);
+    };
+
+    let now = Utc::now();
+    let locked_until = stored
+        .locked_until
+        .as_deref()
+        .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+        .map(|value| value.with_timezone(&Utc));
+    if let Some(retry_after) = locked_until.and_then(|locked_until| (locked_until - now).to_std().ok()) {
+        if !retry_after.is_zero() {
+            return Ok(PrivateGamesPinCheck::CoolingDown { retry_after });
+        }
+    }
+
+    if verify(pin.trim(), &stored.pin_hash).unwrap_or(false) {
+        if stored.failed_attempts != 0 || stored.locked_until.is_some() {
+            save_private_games_pin_attempts(connection, user_id, 0, None)?;
+        }
+        return Ok(PrivateGamesPinCheck::Accepted);
+    }
+
+    // An expired cooldown starts a fresh round of attempts.
+    let failed_attempts = if locked_until.is_some() { 1 } else { stored.failed_attempts + 1 };
+    if failed_attempts >= PRIVATE_GAMES_PIN_MAX_ATTEMPTS {
+        let cooldown = ChronoDuration::from_std(PRIVATE_GAMES_PIN_COOLDOWN)
+            .map_err(|error| format!("Invalid private games PIN cooldown: {error}"))?;
+        save_private_games_pin_attempts(connection, user_id, 0, Some((now + cooldown).to_rfc3339()))?;
+    } else {
+        save_private_games_pin_attempts(connection, user_id, failed_attempts, None)?;
+    }
+    Ok(PrivateGamesPinCheck::Rejected)
+}
+
+pub(crate) fn save_private_games_pin(connection: &Connection, user_id: &str, pin: &str) -> Result<(), String> {
+    let pin = normalize_private_games_pin(pin)?;
+    let pin_hash = hash(pin, DEFAULT_COST).map_err(|error| format!("Failed to hash PIN: {error}"))?;
+    let now = Utc::now().to_rfc3339();
+    connection
+        .execute(
+            "
+            INSERT INTO private_games_pins (user_id, pin_hash, created_at, updated_at)
+            VALUES (?1, ?2, ?3, ?3)
+            ON CONFLICT(user_id) DO UPDATE SET
+              pin_hash = excluded.pin_hash,
+              failed_attempts = 0,
+              locked_until = NULL,
+              updated_at = excluded.updated_at
+            ",
+            params![user_id, pin_hash, now],
+        )
+        .map_err(|error| format!("Failed to save private games PIN: {error}"))?;
+    Ok(())
+}
+
+pub(crate) fn delete_private_games_pin(connection: &Connection, user_id: &str) -> Result<(), String> {
+    connection
+        .execute("DELETE FROM private_games_pins WHERE user_id = ?1", params![user_id])
+        .map_err(|error| format!("Failed to remove private games PIN: {error}"))?;
+    Ok(())
+}
+
+pub(crate) fn unlock_private_games(state: &AppState, user_id: &str) {
+    if let Ok(mut unlock) = state.private_games_unlock.lock() {
+        *unlock = Some(PrivateGamesUnlock {
+            user_id: user_id.to_owned(),
+            expires_at: Instant::now() + PRIVATE_GAMES_RELOCK_TIMEOUT,
+        });
+    }
+}
+
+pub(crate) fn lock_private_games(state: &AppState) {
+    if let Ok(mut unlock) = state.private_games_unlock.lock() {
+        *unlock = None;
+    }
+}
+
+/// Time left before private games lock again, or `None` when they are locked.
+fn private_games_unlock_remaining(state: &AppState, user_id: &str) -> Option<Duration> {
+    let mut unlock = state.private_games_unlock.lock().ok()?;
+    let remaining = unlock
+        .as_ref()
+        .filter(|unlock| unlock.user_id == user_id)
+        .and_then(|unlock| unlock.expires_at.checked_duration_since(Instant::now()))
+        .filter(|remaining| !remaining.is_zero());
+    if remaining.is_none() {
+        *unlock = None;
+    }
+    remaining
+}
+
+pub(crate) fn private_games_lock_status(
+    state: &AppState,
+    connection: &Connection,
+    user_id: &str,
+) -> Result<PrivateGamesLockStatusResponse, String> {
+    let pin_set = has_private_games_pin(connection, user_id)?;
+    let remaining = if pin_set {
+        private_games_unlock_remaining(state, user_id)
+    } else {
+        None
+    };
+    Ok(PrivateGamesLockStatusResponse {
+        pin_set,
+        unlocked: !pin_set || remaining.is_some(),
+        relocks_in_seconds: remaining.map(|remaining| remaining.as_secs()),
+    })
+}
+
+/// Private games only need unlocking once a PIN has been set.
+fn private_games_locked(state: &AppState, connection: &Connection, user_id: &str) -> Result<bool, String> {
+    Ok(!private_games_lock_status(state, connection, user_id)?.unlocked)
+}
+
+fn load_private_game_keys(connection: &Connection, user_id: &str) -> Result<HashSet<String>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT provider, external_id
+            FROM game_privacy_settings
+            WHERE user_id = ?1 AND mark_as_private != 0
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare private games query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok(game_membership_key(&row.get::<_, String>(0)?, &row.get::<_, String>(1)?))
+        })
+        .map_err(|error| format!("Failed to query private games: {error}"))?;
+
+    rows.collect::<Result<HashSet<_>, _>>()
+        .map_err(|error| format!("Failed to decode private game row: {error}"))
+}
+
+/// Drops games marked private from a response while private games are locked.
+pub(crate) fn retain_unlocked_games(
+    state: &AppState,
+    connection: &Connection,
+    user_id: &str,
+    games: &mut Vec<GameResponse>,
+) -> Result<(), String> {
+    if !private_games_locked(state, connection, user_id)? {
+        return Ok(());
+    }
+    let private_game_keys = load_private_game_keys(connection, user_id)?;
+    games.retain(|game| !private_game_keys.contains(&game_membership_key(&game.provider, &game.external_id)));
+    Ok(())
+}
+
+pub(crate) fn is_locked_private_game(
+    state: &AppState,
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<bool, String> {
+    Ok(private_games_locked(state, connection, user_id)?
+        && load_private_game_keys(connection, user_id)?.contains(&game_membership_key(provider, external_id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_private_games_pin_requires_digits() {
+        assert_eq!(normalize_private_games_pin(" 1234 "), Ok(String::from("1234")));
+        assert!(normalize_private_games_pin("123").is_err());
+        assert!(normalize_private_games_pin("12a4").is_err());
+        assert!(normalize_private_games_pin("1234567890123").is_err());
+    }
+
+    #[test]
+    fn check_private_games_pin_cools_down_after_repeated_wrong_pins() {
+        let database = test_support::test_database();
+        let (connection, user_id) = (&database.connection, database.user.id.as_str());
+        assert_eq!(check_private_games_pin(connection, user_id, "0000"), Ok(PrivateGamesPinCheck::Accepted));
+        save_private_games_pin(connection, user_id, "1234").expect("save PIN");
+
+        // A correct PIN resets the count, so only wrong PINs in a row lead to a cooldown.
+        for _ in 0..PRIVATE_GAMES_PIN_MAX_ATTEMPTS - 1 {
+            assert_eq!(check_private_games_pin(connection, user_id, "0000"), Ok(PrivateGamesPinCheck::Rejected));
+        }
+        assert_eq!(check_private_games_pin(connection, user_id, "1234"), Ok(PrivateGamesPinCheck::Accepted));
+        for _ in 0..PRIVATE_GAMES_PIN_MAX_ATTEMPTS {
+            assert_eq!(check_private_games_pin(connection, user_id, "0000"), Ok(PrivateGamesPinCheck::Rejected));
+        }
+        match check_private_games_pin(connection, user_id, "1234") {
+            Ok(PrivateGamesPinCheck::CoolingDown { retry_after }) => {
+                assert!(retry_after <= PRIVATE_GAMES_PIN_COOLDOWN && !retry_after.is_zero());
+            }
+            other => panic!("expected a cooldown, got {other:?}"),
+        }
+
+        connection
+            .execute(
+                "UPDATE private_games_pins SET locked_until = ?2 WHERE user_id = ?1",
+                params![user_id, (Utc::now() - ChronoDuration::seconds(1)).to_rfc3339()],
+            )
+            .expect("expire cooldown");
+        assert_eq!(check_private_games_pin(connection, user_id, "1234"), Ok(PrivateGamesPinCheck::Accepted));
+        let (failed_attempts, locked_until) = connection
+            .query_row(
+                "SELECT failed_attempts, locked_until FROM private_games_pins WHERE user_id = ?1",
+                params![user_id],
+                |row| Ok((row.get::<_, i64>(0)?, row.get::<_, Option<String>>(1)?)),
+            )
+            .expect("PIN attempts");
+        assert_eq!((failed_attempts, locked_until), (0, None));
+    }
+}
@@ -0,0 +1,413 @@
+use crate::*;
+
+pub(crate) const GAME_STARTED_EVENT: &str = "game-started";
+pub(crate) const GAME_STOPPED_EVENT: &str = "game-stopped";
+const INSTALL_EXECUTABLES_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+const INSTALL_EXECUTABLE_SCAN_DEPTH: usize = 2;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GameRunningEventPayload {
+    pub game_id: String,
+    pub provider: String,
+    pub external_id: String,
+}
+
+/// Which games are currently running, plus the PIDs of processes Catalyst spawned itself.
+/// `play_game` records PIDs; the watcher thread owns the running set.
+#[derive(Default)]
+pub(crate) struct RunningGames {
+    running_game_ids: Mutex<HashSet<String>>,
+    tracked_processes: Mutex<HashMap<String, u32>>,
+}
+
+impl RunningGames {
+    pub(crate) fn is_running(&self, game_id: &str) -> bool {
+        self.running_game_ids
+            .lock()
+            .map(|running_game_ids| running_game_ids.contains(game_id))
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn track_process(&self, game_id: &str, pid: u32) {
+        if let Ok(mut tracked_processes) = self.tracked_processes.lock() {
+            tracked_processes.insert(game_id.to_owned(), pid);
+        }
+        if let Ok(mut running_game_ids) = self.running_game_ids.lock() {
+            running_game_ids.insert(game_id.to_owned());
+        }
+    }
+
+    pub(crate) fn tracked_process(&self, game_id: &str) -> Option<u32> {
+        self.tracked_processes
+            .lock()
+            .ok()
+            .and_then(|tracked_processes| tracked_processes.get(game_id).copied())
+    }
+
+    fn tracked_processes(&self) -> HashMap<String, u32> {
+        self.tracked_processes
+            .lock()
+            .map(|tracked_processes| tracked_processes.clone())
+            .unwrap_or_default()
+    }
+
+    fn untrack_process(&self, game_id: &str) {
+        if let Ok(mut tracked_processes) = self.tracked_processes.lock() {
+            tracked_processes.remove(game_id);
+        }
+    }
+
+    /// Swaps in the latest running set and returns the game IDs that started and stopped.
+    fn replace(&self, next_running_game_ids: HashSet<String>) -> (Vec<String>, Vec<String>) {
+        let Ok(mut running_game_ids) = self.running_game_ids.lock() else {
+            return (Vec::new(), Vec::new());
+        };
+        let started = next_running_game_ids
+            .difference(&running_game_ids)
+            .cloned()
+            .collect();
+        let stopped = running_game_ids
+            .difference(&next_running_game_ids)
+            .cloned()
+            .collect();
+        *running_game_ids = next_running_game_ids;
+        (started, stopped)
+    }
+}
+
+pub(crate) fn mark_running_games(running_games: &RunningGames, games: &mut [GameResponse]) {
+    let Ok(running_game_ids) = running_games.running_game_ids.lock() else {
+        return;
+    };
+    for game in games {
+        game.running = running_game_ids.contains(&game.id);
+    }
+}
+
+struct RunningProcess {
+    pid: u32,
+    name: String,
+    arguments: Vec<String>,
+}
+
+/// Lists running processes with lowercase executable file names. Linux reads `/proc`, Windows
+/// uses `tasklist`, and macOS falls back to `ps`.
+fn list_running_processes() -> Vec<RunningProcess> {
+    if cfg!(target_os = "windows") {
+        let Ok(output) = Command::new("tasklist").args(["/FO", "CSV", "/NH"]).output() else {
+            return Vec::new();
+        };
+        return String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| {
+                let mut columns = line.split("\",\"").map(|column| column.trim_matches('"'));
+                let name = columns.next()?.to_ascii_lowercase();
+                let pid = columns.next()?.parse::<u32>().ok()?;
+                Some(RunningProcess {
+                    pid,
+                    name,
+                    arguments: Vec::new(),
+                })
+            })
+            .collect();
+    }
+
+    if cfg!(target_os = "linux") {
+        let Ok(entries) = fs::read_dir("/proc") else {
+            return Vec::new();
+        };
+        return entries
+            .flatten()
+            .filter_map(|entry| {
+                let pid = entry.file_name().to_str()?.parse::<u32>().ok()?;
+                let arguments = fs::read(entry.path().join("cmdline"))
+                    .map(|contents| {
+                        contents
+                            .split(|byte| *byte == 0)
+                            .filter(|argument| !argument.is_empty())
+                            .map(|argument| String::from_utf8_lossy(argument).into_owned())
+                            .collect::<Vec<_>>()
+                    })
+                    .unwrap_or_default();
+                // Wine keeps the Windows path of the game in argv[0], so prefer it over `exe`,
+                // which points at the Wine loader for Proton games.
+                let name = arguments
+                    .first()
+                    .and_then(|argument| argument.rsplit(['/', '\\']).next())
+                    .filter(|name| !name.is_empty())
+                    .map(str::to_owned)
+                    .or_else(|| {
+                        fs::read_link(entry.path().join("exe"))
+                            .ok()
+                            .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+                    })
+                    .or_else(|| fs::read_to_string(entry.path().join("comm")).ok())?;
+                Some(RunningProcess {
+                    pid,
+                    name: name.trim().to_ascii_lowercase(),
+                    arguments,
+                })
+            })
+            .collect();
+    }
+
+    let Ok(output) = Command::new("ps").args(["-axo", "pid=,comm="]).output() else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pid, command) = line.trim().split_once(char::is_whitespace)?;
+            let name = Path::new(command.trim()).file_name()?.to_string_lossy().to_ascii_lowercase();
+            Some(RunningProcess {
+                pid: pid.parse().ok()?,
+                name,
+                arguments: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Collects lowercase `.exe` names near the top of an install folder. Launchers start the
+/// game from there, so matching by name catches it without knowing the exact entry point.
+/// Whether the Steam client itself is running, as opposed to a game launched through it.
+pub(crate) fn is_steam_client_running() -> bool {
+    list_running_processes()
+        .iter()
+        .any(|process| matches!(process.name.as_str(), "steam.exe" | "steam" | "steam_osx"))
+}
+
+fn collect_install_executable_names(install_path: &Path) -> HashSet<String> {
+    let mut executable_names = HashSet::new();
+    let mut pending_directories = vec![(install_path.to_path_buf(), 0_usize)];
+    while let Some((directory, depth)) = pending_directories.pop() {
+        let Ok(entries) = fs::read_dir(&directory) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                if depth + 1 < INSTALL_EXECUTABLE_SCAN_DEPTH {
+                    pending_directories.push((path, depth + 1));
+                }
+                continue;
+            }
+            let Some(file_name) = path.file_name().map(|name| name.to_string_lossy().to_ascii_lowercase()) else {
+                continue;
+            };
+            if file_name.ends_with(".exe") && !file_name.starts_with("unins") {
+                executable_names.insert(file_name);
+            }
+        }
+    }
+    executable_names
+}
+
+/// Finds processes started from an install folder, either by executable name or, where the
+/// command line is available, by an argument pointing inside the folder.
+pub(crate) fn find_install_directory_process_ids(install_directory: &Path) -> Vec<u32> {
+    let executable_names = collect_install_executable_names(install_directory);
+    let own_pid = std::process::id();
+    list_running_processes()
+        .into_iter()
+        .filter(|process| process.pid != own_pid)
+        .filter(|process| {
+            executable_names.contains(&process.name)
+                || process
+                    .arguments
+                    .iter()
+                    .any(|argument| Path::new(argument).starts_with(install_directory))
+        })
+        .map(|process| process.pid)
+        .collect()
+}
+
+/// Asks processes to exit, or kills them outright when `force` is set.
+pub(crate) fn terminate_processes(pids: &[u32], force: bool) -> Result<(), String> {
+    if pids.is_empty() {
+        return Ok(());
+    }
+
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = Command::new("taskkill");
+        if force {
+            command.arg("/F");
+        }
+        command.arg("/T");
+        for pid in pids {
+            command.arg("/PID").arg(pid.to_string());
+        }
+        command
+    } else {
+        let mut command = Command::new("kill");
+        command.arg(if force { "-KILL" } else { "-TERM" });
+        command.args(pids.iter().map(u32::to_string));
+        command
+    };
+    let output = command
+        .output()
+        .map_err(|error| format!("Failed to stop game processes: {error}"))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to stop game processes: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(())
+}
+
+fn collect_launcher_game_executables() -> Vec<(String, HashSet<String>)> {
+    let detected_by_provider = [
+        ("ea", store_providers::detect_ea_installed_games()),
+        ("ubisoft", store_providers::detect_ubisoft_installed_games()),
+    ];
+    let mut game_executables = Vec::new();
+    for (provider, detected_games) in detected_by_provider {
+        for game in detected_games.unwrap_or_default() {
+            let Some(install_path) = game.install_path.as_ref() else {
+                continue;
+            };
+            let executable_names = collect_install_executable_names(install_path);
+            if !executable_names.is_empty() {
+                game_executables.push((format!("{provider}:{}", game.external_id), executable_names));
+            }
+        }
+    }
+    game_executables
+}
+
+fn detect_running_game_ids(
+    running_games: &RunningGames,
+    launcher_game_executables: &[(String, HashSet<String>)],
+) -> HashSet<String> {
+    let mut running_game_ids = HashSet::new();
+    if let Some(app_id) = detect_running_steam_app_id() {
+        running_game_ids.insert(format!("steam:{app_id}"));
+    }
+
+    let tracked_processes = running_games.tracked_processes();
+    if tracked_processes.is_empty() && launcher_game_executables.is_empty() {
+        return running_game_ids;
+    }
+
+    let processes = list_running_processes();
+    let running_pids = processes.iter().map(|process| process.pid).collect::<HashSet<_>>();
+    let running_names = processes
+        .iter()
+        .map(|process| process.name.as_str())
+        .collect::<HashSet<_>>();
+    for (game_id, pid) in tracked_processes {
+        if running_pids.contains(&pid) {
+            running_game_ids.insert(game_id);
+        } else {
+            running_games.untrack_process(&game_id);
+        }
+    }
+    for (game_id, executable_names) in launcher_game_executables {
+        if executable_names
+            .iter()
+            .any(|name| running_names.contains(name.as_str()))
+        {
+            running_game_ids.insert(game_id.clone());
+        }
+    }
+
+    running_game_ids
+}
+
+fn record_play_sessions(db_path: &Path, started: &[String], stopped: &[String]) {
+    let connection = match open_connection(db_path) {
+        Ok(connection) => connection,
+        Err(error) => {
+            eprintln!("Failed to open database for play sessions: {error}");
+            return;
+        }
+    };
+    for game_id in stopped {
+        if let Some((provider, external_id)) = game_id.split_once(':') {
+            if let Err(error) = play_sessions::end_play_session(&connection, provider, external_id) {
+                eprintln!("{error}");
+            }
+        }
+    }
+    for game_id in started {
+        if let Some((provider, external_id)) = game_id.split_once(':') {
+            if let Err(error) = play_sessions::start_play_session(&connection, provider, external_id) {
+                eprintln!("{error}");
+            }
+        }
+    }
+}
+
+/// Polls for running games, records play sessions and emits `game-started` / `game-stopped`
+/// whenever the set changes.
+pub(crate) fn spawn_running_game_watcher(app_handle: tauri::AppHandle) {
+    use tauri::{Emitter, Manager};
+
+    let _ = thread::Builder::new()
+        .name("running-game-watcher".into())
+        .spawn(move || {
+            match open_connection(&app_handle.state::<AppState>().db_path) {
+                Ok(connection) => {
+                    if let Err(error) = play_sessions::discard_interrupted_play_sessions(&connection) {
+                        eprintln!("{error}");
+                    }
+                }
+                Err(error) => eprintln!("Failed to open database for play sessions: {error}"),
+            }
+            let mut launcher_game_executables = Vec::new();
+            let mut executables_refreshed_at: Option<Instant> = None;
+            loop {
+                if executables_refreshed_at
+                    .map(|refreshed_at| refreshed_at.elapsed() >= INSTALL_EXECUTABLES_REFRESH_INTERVAL)
+                    .unwrap_or(true)
+                {
+                    launcher_game_executables = collect_launcher_game_executables();
+                    executables_refreshed_at = Some(Instant::now());
+                }
+
+                let state = app_handle.state::<AppState>();
+                let running_game_ids =
+                    detect_running_game_ids(&state.running_games, &launcher_game_executables);
+                let (started, stopped) = state.running_games.replace(running_game_ids);
+                if !started.is_empty() || !stopped.is_empty() {
+                    record_play_sessions(&state.db_path, &started, &stopped);
+                }
+                for (event, game_ids) in [(GAME_STARTED_EVENT, started), (GAME_STOPPED_EVENT, stopped)] {
+                    for game_id in game_ids {
+                        let Some((provider, external_id)) = game_id.split_once(':') else {
+                            continue;
+                        };
+                        let payload = GameRunningEventPayload {
+                            provider: provider.to_owned(),
+                            external_id: external_id.to_owned(),
+                            game_id: game_id.clone(),
+                        };
+                        let _ = app_handle.emit(event, payload);
+                    }
+                }
+
+                thread::sleep(GAME_SESSION_POLL_INTERVAL);
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_games_replace_reports_started_and_stopped_games() {
+        let running_games = RunningGames::default();
+        running_games.replace(HashSet::from([String::from("steam:620"), String::from("rom:abc")]));
+
+        let (started, stopped) =
+            running_games.replace(HashSet::from([String::from("rom:abc"), String::from("ea:OFB-1")]));
+
+        assert_eq!(started, vec![String::from("ea:OFB-1")]);
+        assert_eq!(stopped, vec![String::from("steam:620")]);
+        assert!(running_games.is_running("rom:abc"));
+        assert!(!running_games.is_running("steam:620"));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::*;
+
+const PROTON_PREFIX_OUTPUT_TAIL_LINES: usize = 40;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProtonPrefixToolResponse {
+    /// `protontricks` or `winetricks`, whichever ran the verbs.
+    pub tool: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    /// The last lines the tool printed, to show why a verb failed.
+    pub output: String,
+}
+
+/// The Wine prefix Proton creates at `compatdata/<appid>/pfx` on the game's first launch.
+pub(crate) fn resolve_proton_prefix_directory(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+) -> Result<PathBuf, String> {
+    if !cfg!(target_os = "linux") {
+        return Err(String::from("Proton prefixes only exist on Linux"));
+    }
+    let prefix_directory = resolve_steam_app_storage_paths(steam_root_override, app_id)?
+        .compatdata_directory
+        .join("pfx");
+    if !prefix_directory.is_dir() {
+        return Err(String::from("This game has no Proton prefix yet; launch it once through Proton first"));
+    }
+    Ok(prefix_directory)
+}
+
+/// Winetricks verbs are plain identifiers such as `vcrun2019` or `dxvk=2.3`; anything else could
+/// smuggle options to the tool.
+fn validate_winetricks_verbs(verbs: &[String]) -> Result<Vec<String>, String> {
+    let verbs = verbs
+        .iter()
+        .map(|verb| verb.trim())
+        .filter(|verb| !verb.is_empty())
+        .map(str::to_owned)
+        .collect::<Vec<_>>();
+    if verbs.is_empty() {
+        return Err(String::from("Enter at least one winetricks verb"));
+    }
+    if let Some(invalid) = verbs.iter().find(|verb| {
+        verb.starts_with('-')
+            || !verb
+                .chars()
+                .all(|character| character.is_ascii_alphanumeric() || "_-.=".contains(character))
+    }) {
+        return Err(format!("'{invalid}' is not a winetricks verb"));
+    }
+    Ok(verbs)
+}
+
+/// Prefers `protontricks`, which also points winetricks at the game's Proton build, and falls
+/// back to plain winetricks against the prefix.
+fn build_prefix_tool_command(
+    protontricks_path: Option<&Path>,
+    winetricks_path: Option<&Path>,
+    app_id: u64,
+    prefix_directory: &Path,
+    verbs: &[String],
+) -> Result<(&'static str, Command), String> {
+    if let Some(protontricks_path) = protontricks_path {
+        let mut command = Command::new(protontricks_path);
+        command.arg(app_id.to_string()).arg("-q").args(verbs);
+        return Ok(("protontricks", command));
+    }
+    let winetricks_path = winetricks_path.ok_or_else(|| String::from("Install protontricks or winetricks first"))?;
+    let mut command = Command::new(winetricks_path);
+    command.env("WINEPREFIX", prefix_directory).arg("-q").args(verbs);
+    Ok(("winetricks", command))
+}
+
+pub(crate) fn run_proton_prefix_tool(
+    app_id: u64,
+    prefix_directory: &Path,
+    verbs: &[String],
+) -> Result<ProtonPrefixToolResponse, String> {
+    let verbs = validate_winetricks_verbs(verbs)?;
+    let protontricks_path = find_executable_in_path("protontricks");
+    let winetricks_path = find_executable_in_path("winetricks");
+    let (tool, mut command) = build_prefix_tool_command(
+        protontricks_path.as_deref(),
+        winetricks_path.as_deref(),
+        app_id,
+        prefix_directory,
+        &verbs,
+    )?;
+    let output = command
+        .output()
+        .map_err(|error| format!("Failed to run {tool}: {error}"))?;
+
+    let combined_output = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let lines = combined_output.lines().collect::<Vec<_>>();
+    let tail_start = lines.len().saturating_sub(PROTON_PREFIX_OUTPUT_TAIL_LINES);
+    Ok(ProtonPrefixToolResponse {
+        tool: tool.to_owned(),
+        success: output.status.success(),
+        exit_code: output.status.code(),
+        output: lines[tail_start..].join("\n"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_prefix_tool_command_prefers_protontricks() {
+        let verbs = validate_winetricks_verbs(&[String::from(" vcrun2019 "), String::from("dxvk=2.3")])
+            .expect("valid verbs");
+        let prefix_directory = Path::new("/games/steamapps/compatdata/367520/pfx");
+
+        let (tool, command) = build_prefix_tool_command(
+            Some(Path::new("/usr/bin/protontricks")),
+            Some(Path::new("/usr/bin/winetricks")),
+            367520,
+            prefix_directory,
+            &verbs,
+        )
+        .expect("build command");
+        assert_eq!(tool, "protontricks");
+        assert_eq!(
+            command.get_args().collect::<Vec<_>>(),
+            ["367520", "-q", "vcrun2019", "dxvk=2.3"]
+        );
+
+        let (tool, command) =
+            build_prefix_tool_command(None, Some(Path::new("/usr/bin/winetricks")), 367520, prefix_directory, &verbs)
+                .expect("build command");
+        assert_eq!(tool, "winetricks");
+        assert!(command
+            .get_envs()
+            .any(|(name, value)| name == "WINEPREFIX" && value == Some(prefix_directory.as_os_str())));
+        assert!(validate_winetricks_verbs(&[String::from("--force")]).is_err());
+        assert!(validate_winetricks_verbs(&[String::from("vcrun2019; rm -rf ~")]).is_err());
+    }
+}
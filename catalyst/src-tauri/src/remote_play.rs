@@ -0,0 +1,109 @@
+use crate::*;
+
+/// A machine Steam has seen on the local network that can stream games to this one.
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RemotePlayHostResponse {
+    pub client_id: String,
+    pub hostname: String,
+    pub ip_address: Option<String>,
+    pub last_seen_at: Option<String>,
+}
+
+fn resolve_remote_clients_path(steam_root_override: Option<&str>) -> Option<PathBuf> {
+    let path = resolve_steam_root_path(steam_root_override)?
+        .join("config")
+        .join("remoteclients.vdf");
+    path.is_file().then_some(path)
+}
+
+/// Parses `config/remoteclients.vdf`, where Steam caches the Remote Play hosts it discovered,
+/// keyed by client ID. Entries without a hostname are stale discovery records and skipped.
+fn parse_remote_play_hosts(contents: &str) -> Result<Vec<RemotePlayHostResponse>, String> {
+    let document = parse_vdf_document(contents)?;
+    let VdfValue::Object(roots) = &document else {
+        return Ok(Vec::new());
+    };
+    let mut hosts = roots
+        .iter()
+        .filter_map(|(_, root)| match root {
+            VdfValue::Object(entries) => Some(entries),
+            VdfValue::Text(_) => None,
+        })
+        .flatten()
+        .filter_map(|(client_id, client)| {
+            let hostname = vdf_get_text_entry(client, "hostname")?.trim();
+            if hostname.is_empty() {
+                return None;
+            }
+            let ip_address = ["ipaddress", "ip"]
+                .iter()
+                .find_map(|key| vdf_get_text_entry(client, key))
+                .map(|value| value.trim().to_owned())
+                .filter(|value| !value.is_empty());
+            let last_seen_at = ["lastseen", "timestamp"]
+                .iter()
+                .find_map(|key| vdf_get_text_entry(client, key))
+                .and_then(|value| value.trim().parse::<i64>().ok())
+                .and_then(|seconds| Utc.timestamp_opt(seconds, 0).single())
+                .map(|seen_at| seen_at.to_rfc3339());
+            Some(RemotePlayHostResponse {
+                client_id: client_id.clone(),
+                hostname: hostname.to_owned(),
+                ip_address,
+                last_seen_at,
+            })
+        })
+        .collect::<Vec<_>>();
+    hosts.sort_by(|left, right| right.last_seen_at.cmp(&left.last_seen_at));
+    Ok(hosts)
+}
+
+pub(crate) fn read_remote_play_hosts(steam_root_override: Option<&str>) -> Result<Vec<RemotePlayHostResponse>, String> {
+    let Some(path) = resolve_remote_clients_path(steam_root_override) else {
+        return Ok(Vec::new());
+    };
+    let contents = fs::read_to_string(&path).map_err(|error| format!("Failed to read {}: {error}", path.display()))?;
+    parse_remote_play_hosts(&contents)
+}
+
+/// `rungameid` runs the library's primary action, which for a game that isn't installed here
+/// but is on a host in the same Steam account is "Stream" rather than "Install".
+pub(crate) fn launch_steam_remote_play(app_id: u64) -> Result<(), String> {
+    launch_steam_uri(&format!("steam://rungameid/{app_id}"), "play-remote")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_remote_play_hosts_reads_cached_clients() {
+        let hosts = parse_remote_play_hosts(
+            r#""RemoteClientCache"
+{
+    "1234567890"
+    {
+        "hostname"  "GAMING-PC"
+        "ipaddress" "192.168.1.20"
+        "lastseen"  "1700000000"
+    }
+    "987"
+    {
+        "hostname"  ""
+    }
+}"#,
+        )
+        .expect("parse hosts");
+
+        assert_eq!(
+            hosts,
+            vec![RemotePlayHostResponse {
+                client_id: String::from("1234567890"),
+                hostname: String::from("GAMING-PC"),
+                ip_address: Some(String::from("192.168.1.20")),
+                last_seen_at: Some(String::from("2023-11-14T22:13:20+00:00")),
+            }]
+        );
+    }
+}
@@ -0,0 +1,536 @@
+use crate::*;
+
+const ROM_SCAN_MAX_DEPTH: usize = 6;
+const ROM_ARGS_PATH_PLACEHOLDER: &str = "{rom}";
+const ROM_HASH_BUFFER_BYTES: usize = 1024 * 1024;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct EmulatorProfileResponse {
+    pub id: String,
+    pub name: String,
+    pub executable_path: String,
+    pub args_template: String,
+    pub extensions: Vec<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RomDirectoryResponse {
+    pub id: String,
+    pub path: String,
+    pub profile_id: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RomScanResponse {
+    pub scanned_files: usize,
+    pub synced_games: usize,
+    pub added_games: usize,
+    pub updated_games: usize,
+    pub uninstalled_games: usize,
+}
+
+pub(crate) struct ScannedRomFile {
+    pub hash: String,
+    pub name: String,
+    pub path: PathBuf,
+    pub profile_id: String,
+    pub size_bytes: u64,
+}
+
+fn normalize_rom_extensions(extensions: &[String]) -> Vec<String> {
+    let mut normalized = extensions
+        .iter()
+        .map(|extension| extension.trim().trim_start_matches('.').to_ascii_lowercase())
+        .filter(|extension| !extension.is_empty())
+        .collect::<Vec<_>>();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+pub(crate) fn list_emulator_profiles(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<Vec<EmulatorProfileResponse>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT id, name, executable_path, args_template, extensions_json
+            FROM emulator_profiles
+            WHERE user_id = ?1
+            ORDER BY name COLLATE NOCASE
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare emulator profile query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            let extensions_json = row.get::<_, String>(4)?;
+            Ok(EmulatorProfileResponse {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                executable_path: row.get(2)?,
+                args_template: row.get(3)?,
+                extensions: serde_json::from_str(&extensions_json).unwrap_or_default(),
+            })
+        })
+        .map_err(|error| format!("Failed to query emulator profiles: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode emulator profile row: {error}"))
+}
+
+pub(crate) fn save_emulator_profile(
+    connection: &Connection,
+    user_id: &str,
+    profile_id: Option<&str>,
+    name: &str,
+    executable_path: &str,
+    args_template: &str,
+    extensions: &[String],
+) -> Result<EmulatorProfileResponse, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(String::from("Emulator profile name is required"));
+    }
+    let executable_path = executable_path.trim();
+    if executable_path.is_empty() {
+        return Err(String::from("Emulator executable path is required"));
+    }
+    let extensions = normalize_rom_extensions(extensions);
+    if extensions.is_empty() {
+        return Err(String::from("At least one ROM file extension is required"));
+    }
+
+    let extensions_json = serde_json::to_string(&extensions)
+        .map_err(|error| format!("Failed to serialize ROM extensions: {error}"))?;
+    let now = Utc::now().to_rfc3339();
+    let profile_id = match profile_id.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(existing_id) => {
+            let updated_rows = connection
+                .execute(
+                    "
+                    UPDATE emulator_profiles
+                    SET name = ?1, executable_path = ?2, args_template = ?3, extensions_json = ?4, updated_at = ?5
+                    WHERE id = ?6 AND user_id = ?7
+                    ",
+                    params![name, executable_path, args_template.trim(), extensions_json, now, existing_id, user_id],
+                )
+                .map_err(|error| format!("Failed to update emulator profile: {error}"))?;
+            if updated_rows == 0 {
+                return Err(String::from("Emulator profile not found"));
+            }
+            existing_id.to_owned()
+        }
+        None => {
+            let new_id = Uuid::new_v4().to_string();
+            connection
+                .execute(
+                    "
+                    INSERT INTO emulator_profiles (id, user_id, name, executable_path, args_template, extensions_json, created_at, updated_at)
+                    VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?7)
+                    ",
+                    params![new_id, user_id, name, executable_path, args_template.trim(), extensions_json, now],
+                )
+                .map_err(|error| format!("Failed to create emulator profile: {error}"))?;
+            new_id
+        }
+    };
+
+    Ok(EmulatorProfileResponse {
+        id: profile_id,
+        name: name.to_owned(),
+        executable_path: executable_path.to_owned(),
+        args_template: args_template.trim().to_owned(),
+        extensions,
+    })
+}
+
+pub(crate) fn delete_emulator_profile(
+    connection: &Connection,
+    user_id: &str,
+    profile_id: &str,
+) -> Result<(), String> {
+    let deleted_rows = connection
+        .execute(
+            "DELETE FROM emulator_profiles WHERE id = ?1 AND user_id = ?2",
+            params![profile_id, user_id],
+        )
+        .map_err(|error| format!("Failed to delete emulator profile: {error}"))?;
+    if deleted_rows == 0 {
+        return Err(String::from("Emulator profile not found"));
+    }
+    Ok(())
+}
+
+pub(crate) fn list_rom_directories(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<Vec<RomDirectoryResponse>, String> {
+    let mut statement = connection
+        .prepare("SELECT id, path, profile_id FROM rom_directories WHERE user_id = ?1 ORDER BY path")
+        .map_err(|error| format!("Failed to prepare ROM directory query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| {
+            Ok(RomDirectoryResponse {
+                id: row.get(0)?,
+                path: row.get(1)?,
+                profile_id: row.get(2)?,
+            })
+        })
+        .map_err(|error| format!("Failed to query ROM directories: {error}"))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode ROM directory row: {error}"))
+}
+
+pub(crate) fn add_rom_directory(
+    connection: &Connection,
+    user_id: &str,
+    path: &str,
+    profile_id: &str,
+) -> Result<RomDirectoryResponse, String> {
+    let path = path.trim();
+    if !Path::new(path).is_dir() {
+        return Err(String::from("ROM directory must be an existing directory"));
+    }
+    let profile_exists = connection
+        .query_row(
+            "SELECT 1 FROM emulator_profiles WHERE id = ?1 AND user_id = ?2",
+            params![profile_id, user_id],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to validate emulator profile: {error}"))?
+        .is_some();
+    if !profile_exists {
+        return Err(String::from("Emulator profile not found"));
+    }
+
+    let directory_id = Uuid::new_v4().to_string();
+    connection
+        .execute(
+            "
+            INSERT INTO rom_directories (id, user_id, path, profile_id, created_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ",
+            params![directory_id, user_id, path, profile_id, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| {
+            if error.to_string().contains("UNIQUE constraint failed") {
+                String::from("ROM directory already exists")
+            } else {
+                format!("Failed to add ROM directory: {error}")
+            }
+        })?;
+
+    Ok(RomDirectoryResponse {
+        id: directory_id,
+        path: path.to_owned(),
+        profile_id: profile_id.to_owned(),
+    })
+}
+
+pub(crate) fn remove_rom_directory(
+    connection: &Connection,
+    user_id: &str,
+    directory_id: &str,
+) -> Result<(), String> {
+    let deleted_rows = connection
+        .execute(
+            "DELETE FROM rom_directories WHERE id = ?1 AND user_id = ?2",
+            params![directory_id, user_id],
+        )
+        .map_err(|error| format!("Failed to remove ROM directory: {error}"))?;
+    if deleted_rows == 0 {
+        return Err(String::from("ROM directory not found"));
+    }
+    Ok(())
+}
+
+/// Totals the scanned ROM files that live under `directory`. Paths are compared by component,
+/// so `/roms` does not count the files of a sibling `/roms-snes`.
+pub(crate) fn summarize_rom_directory_usage(
+    connection: &Connection,
+    user_id: &str,
+    directory: &str,
+) -> Result<(u64, usize), String> {
+    let mut statement = connection
+        .prepare("SELECT path, size_bytes FROM rom_files WHERE user_id = ?1")
+        .map_err(|error| format!("Failed to prepare ROM usage query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?)))
+        .map_err(|error| format!("Failed to summarize ROM directory usage: {error}"))?;
+
+    let mut used_bytes = 0_u64;
+    let mut file_count = 0_usize;
+    for row in rows {
+        let (path, size_bytes) = row.map_err(|error| format!("Failed to decode ROM file row: {error}"))?;
+        if Path::new(&path).starts_with(directory) {
+            used_bytes = used_bytes.saturating_add(size_bytes.max(0) as u64);
+            file_count += 1;
+        }
+    }
+    Ok((used_bytes, file_count))
+}
+
+/// Walks every configured ROM directory and hashes files matching the directory's emulator
+/// profile. The SHA-256 of the file contents is the game's stable external ID, so renamed or
+/// moved ROMs keep their favorites and collections.
+pub(crate) fn scan_rom_directories(
+    connection: &Connection,
+    user_id: &str,
+) -> Result<Vec<ScannedRomFile>, String> {
+    let profiles_by_id = list_emulator_profiles(connection, user_id)?
+        .into_iter()
+        .map(|profile| (profile.id.clone(), profile))
+        .collect::<HashMap<_, _>>();
+
+    let mut scanned_files = Vec::new();
+    let mut seen_hashes = HashSet::new();
+    for directory in list_rom_directories(connection, user_id)? {
+        let Some(profile) = profiles_by_id.get(&directory.profile_id) else {
+            continue;
+        };
+        let mut candidate_paths = Vec::new();
+        collect_rom_file_paths(Path::new(&directory.path), &profile.extensions, 0, &mut candidate_paths);
+
+        for path in candidate_paths {
+            let (hash, size_bytes) = match hash_rom_file(&path) {
+                Ok(result) => result,
+                Err(error) => {
+                    eprintln!("Skipping ROM {}: {error}", path.display());
+                    continue;
+                }
+            };
+            if !seen_hashes.insert(hash.clone()) {
+                continue;
+            }
+            let name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().trim().to_owned())
+                .filter(|stem| !stem.is_empty())
+                .unwrap_or_else(|| hash.clone());
+            scanned_files.push(ScannedRomFile {
+                hash,
+                name,
+                path,
+                profile_id: profile.id.clone(),
+                size_bytes,
+            });
+        }
+    }
+
+    Ok(scanned_files)
+}
+
+fn collect_rom_file_paths(directory: &Path, extensions: &[String], depth: usize, paths: &mut Vec<PathBuf>) {
+    if depth > ROM_SCAN_MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(directory) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_rom_file_paths(&path, extensions, depth + 1, paths);
+            continue;
+        }
+        let matches_extension = path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+            .map(|extension| extensions.contains(&extension))
+            .unwrap_or(false);
+        if matches_extension {
+            paths.push(path);
+        }
+    }
+}
+
+fn hash_rom_file(path: &Path) -> Result<(String, u64), String> {
+    let mut file = fs::File::open(path).map_err(|error| format!("Failed to open ROM: {error}"))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0_u8; ROM_HASH_BUFFER_BYTES];
+    let mut size_bytes = 0_u64;
+    loop {
+        let read_bytes = file
+            .read(&mut buffer)
+            .map_err(|error| format!("Failed to read ROM: {error}"))?;
+        if read_bytes == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read_bytes]);
+        size_bytes += read_bytes as u64;
+    }
+    Ok((format!("{:x}", hasher.finalize()), size_bytes))
+}
+
+pub(crate) fn save_scanned_rom_files(
+    connection: &Connection,
+    user_id: &str,
+    scanned_files: &[ScannedRomFile],
+) -> Result<(), String> {
+    let scanned_at = Utc::now().to_rfc3339();
+    for rom_file in scanned_files {
+        connection
+            .execute(
+                "
+                INSERT INTO rom_files (user_id, external_id, path, profile_id, size_bytes, scanned_at)
+                VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+                ON CONFLICT(user_id, external_id) DO UPDATE SET
+                  path = excluded.path,
+                  profile_id = excluded.profile_id,
+                  size_bytes = excluded.size_bytes,
+                  scanned_at = excluded.scanned_at
+                ",
+                params![
+                    user_id,
+                    rom_file.hash,
+                    rom_file.path.display().to_string(),
+                    rom_file.profile_id,
+                    rom_file.size_bytes as i64,
+                    scanned_at
+                ],
+            )
+            .map_err(|error| format!("Failed to save ROM file: {error}"))?;
+    }
+    Ok(())
+}
+
+fn load_rom_launch_details(
+    connection: &Connection,
+    user_id: &str,
+    external_id: &str,
+) -> Result<(String, String, String), String> {
+    let (rom_path, executable_path, args_template) = connection
+        .query_row(
+            "
+            SELECT rom_files.path, emulator_profiles.executable_path, emulator_profiles.args_template
+            FROM rom_files
+            INNER JOIN emulator_profiles ON emulator_profiles.id = rom_files.profile_id
+            WHERE rom_files.user_id = ?1 AND rom_files.external_id = ?2
+            ",
+            params![user_id, external_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?)),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query ROM launch details: {error}"))?
+        .ok_or_else(|| String::from("ROM file or emulator profile not found; rescan the ROM library"))?;
+    if !Path::new(&rom_path).is_file() {
+        return Err(format!("ROM file not found at {rom_path}; rescan the ROM library"));
+    }
+    Ok((rom_path, executable_path, args_template))
+}
+
+/// Spawns the emulator configured for a scanned ROM. `{rom}` in the profile's argument template
+/// is replaced with the ROM path; templates without it get the path appended.
+pub(crate) fn launch_rom_game(
+    connection: &Connection,
+    user_id: &str,
+    external_id: &str,
+    launch_options: Option<&str>,
+    environment_variables: &BTreeMap<String, String>,
+) -> Result<std::process::Child, String> {
+    let (rom_path, executable_path, args_template) = load_rom_launch_details(connection, user_id, external_id)?;
+    let args = build_rom_launch_args(&args_template, &rom_path, launch_options);
+    Command::new(&executable_path)
+        .args(&args)
+        .envs(environment_variables)
+        .spawn()
+        .map_err(|error| format!("Failed to start emulator {executable_path}: {error}"))
+}
+
+/// Returns the emulator executable and a quoted argument string for launching a ROM from
+/// outside Catalyst, e.g. through a Steam shortcut.
+pub(crate) fn rom_external_launch_command(
+    connection: &Connection,
+    user_id: &str,
+    external_id: &str,
+) -> Result<(String, String), String> {
+    let (rom_path, executable_path, args_template) = load_rom_launch_details(connection, user_id, external_id)?;
+    let args = build_rom_launch_args(&args_template, &rom_path, None)
+        .into_iter()
+        .map(|arg| {
+            if arg.chars().any(char::is_whitespace) {
+                format!("\"{arg}\"")
+            } else {
+                arg
+            }
+        })
+        .collect::<Vec<_>>();
+    Ok((executable_path, args.join(" ")))
+}
+
+fn build_rom_launch_args(args_template: &str, rom_path: &str, launch_options: Option<&str>) -> Vec<String> {
+    let mut args = args_template
+        .split_whitespace()
+        .map(|arg| arg.replace(ROM_ARGS_PATH_PLACEHOLDER, rom_path))
+        .collect::<Vec<_>>();
+    if !args_template.contains(ROM_ARGS_PATH_PLACEHOLDER) {
+        args.push(rom_path.to_owned());
+    }
+    if let Some(launch_options) = launch_options {
+        args.extend(launch_options.split_whitespace().map(str::to_owned));
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_rom_launch_args_substitutes_rom_path() {
+        assert_eq!(
+            build_rom_launch_args("-f -L core.so {rom}", "/roms/Game One.sfc", None),
+            vec!["-f", "-L", "core.so", "/roms/Game One.sfc"]
+        );
+        assert_eq!(
+            build_rom_launch_args("--fullscreen", "/roms/game.iso", Some("--debug")),
+            vec!["--fullscreen", "/roms/game.iso", "--debug"]
+        );
+    }
+
+    #[test]
+    fn summarize_rom_directory_usage_ignores_sibling_directories() {
+        let database = test_support::test_database();
+        database
+            .connection
+            .execute(
+                "
+                INSERT INTO emulator_profiles (id, user_id, name, executable_path, args_template, extensions_json, created_at, updated_at)
+                VALUES ('snes', ?1, 'SNES', '/usr/bin/snes9x', '{rom}', '[\"sfc\"]', '2024-01-01T00:00:00Z', '2024-01-01T00:00:00Z')
+                ",
+                params![database.user.id],
+            )
+            .expect("insert profile");
+        for (external_id, path, size_bytes) in [
+            ("a", "/roms/Game One.sfc", 100),
+            ("b", "/roms/hacks/Game Two.sfc", 50),
+            ("c", "/roms-snes/Game Three.sfc", 400),
+        ] {
+            database
+                .connection
+                .execute(
+                    "
+                    INSERT INTO rom_files (user_id, external_id, path, profile_id, size_bytes, scanned_at)
+                    VALUES (?1, ?2, ?3, 'snes', ?4, '2024-01-01T00:00:00Z')
+                    ",
+                    params![database.user.id, external_id, path, size_bytes],
+                )
+                .expect("insert rom file");
+        }
+
+        let usage = |directory: &str| {
+            summarize_rom_directory_usage(&database.connection, &database.user.id, directory).expect("summarize")
+        };
+        assert_eq!(usage("/roms"), (150, 2));
+        assert_eq!(usage("/roms/"), (150, 2));
+        assert_eq!(usage("/roms-snes"), (400, 1));
+        assert_eq!(usage("/missing"), (0, 0));
+    }
+}
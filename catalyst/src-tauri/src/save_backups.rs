@@ -0,0 +1,466 @@
+use crate::*;
+
+const SAVE_BACKUPS_DIRECTORY: &str = "save-backups";
+const SAVE_BACKUP_MANIFEST_ENTRY: &str = "catalyst-backup.json";
+const SAVE_BACKUP_EXTENSION: &str = "zip";
+const SAVE_BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+const PROTON_STEAM_USER_DIRECTORY: &str = "pfx/drive_c/users/steamuser";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SaveBackupResponse {
+    pub id: String,
+    pub provider: String,
+    pub external_id: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+    pub file_count: usize,
+    pub path: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SaveRestoreResponse {
+    pub backup_id: String,
+    pub restored_files: usize,
+    pub locations: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveBackupManifest {
+    provider: String,
+    external_id: String,
+    created_at: String,
+    file_count: usize,
+    locations: Vec<SaveBackupManifestLocation>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SaveBackupManifestLocation {
+    template: String,
+    path: String,
+}
+
+/// Everything a PCGamingWiki-style `{{p|...}}` path template can refer to. For Steam games run
+/// through Proton the Windows profile tokens resolve inside the game's compatdata prefix.
+#[derive(Default)]
+pub(crate) struct SaveLocationContext {
+    pub home_directory: Option<PathBuf>,
+    pub windows_profile_directory: Option<PathBuf>,
+    pub windows_program_data_directory: Option<PathBuf>,
+    pub game_directory: Option<PathBuf>,
+    pub steam_root: Option<PathBuf>,
+    pub steam_uid: Option<String>,
+}
+
+pub(crate) fn list_save_location_templates(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<Vec<String>, String> {
+    let mut statement = connection
+        .prepare(
+            "
+            SELECT path_template
+            FROM game_save_locations
+            WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3
+            ORDER BY path_template
+            ",
+        )
+        .map_err(|error| format!("Failed to prepare save location query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id, provider, external_id], |row| row.get::<_, String>(0))
+        .map_err(|error| format!("Failed to query save locations: {error}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode save location row: {error}"))
+}
+
+pub(crate) fn replace_save_location_templates(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    templates: &[String],
+) -> Result<Vec<String>, String> {
+    let mut normalized_templates = templates
+        .iter()
+        .map(|template| template.trim().to_owned())
+        .filter(|template| !template.is_empty())
+        .collect::<Vec<_>>();
+    normalized_templates.sort();
+    normalized_templates.dedup();
+
+    connection
+        .execute(
+            "DELETE FROM game_save_locations WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+            params![user_id, provider, external_id],
+        )
+        .map_err(|error| format!("Failed to clear save locations: {error}"))?;
+    let updated_at = Utc::now().to_rfc3339();
+    for template in &normalized_templates {
+        connection
+            .execute(
+                "
+                INSERT INTO game_save_locations (user_id, provider, external_id, path_template, updated_at)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ",
+                params![user_id, provider, external_id, template, updated_at],
+            )
+            .map_err(|error| format!("Failed to save save location: {error}"))?;
+    }
+
+    Ok(normalized_templates)
+}
+
+/// Steam games always get their local Steam Cloud folder in addition to any registered templates.
+pub(crate) fn default_save_location_templates(provider: &str, external_id: &str) -> Vec<String> {
+    if provider == "steam" {
+        vec![format!("{{{{p|steam}}}}/userdata/{{{{p|uid}}}}/{external_id}/remote")]
+    } else {
+        Vec::new()
+    }
+}
+
+pub(crate) fn build_save_location_context(
+    steam_root_override: Option<&str>,
+    steam_id: Option<&str>,
+    provider: &str,
+    external_id: &str,
+) -> SaveLocationContext {
+    let home_directory = std::env::var("USERPROFILE")
+        .ok()
+        .filter(|_| cfg!(target_os = "windows"))
+        .or_else(|| std::env::var("HOME").ok())
+        .map(PathBuf::from);
+    let mut context = SaveLocationContext {
+        home_directory: home_directory.clone(),
+        ..SaveLocationContext::default()
+    };
+    if cfg!(target_os = "windows") {
+        context.windows_profile_directory = home_directory;
+        context.windows_program_data_directory = std::env::var("PROGRAMDATA").ok().map(PathBuf::from);
+    }
+    if provider != "steam" {
+        return context;
+    }
+
+    context.steam_root = resolve_steam_root_path(steam_root_override);
+    if let (Some(steam_root), Some(steam_id)) = (context.steam_root.as_ref(), steam_id) {
+        context.steam_uid = resolve_steam_userdata_directory(steam_root, steam_id)
+            .ok()
+            .and_then(|directory| directory.file_name().map(|name| name.to_string_lossy().into_owned()));
+    }
+    let Ok(app_id) = external_id.parse::<u64>() else {
+        return context;
+    };
+    context.game_directory = resolve_steam_install_directory_for_app_id(steam_root_override, app_id).ok();
+    if !cfg!(target_os = "windows") {
+        let compatdata_directory = resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)
+            .ok()
+            .and_then(|manifest_path| manifest_path.parent().map(Path::to_path_buf))
+            .map(|steamapps_directory| steamapps_directory.join("compatdata").join(app_id.to_string()))
+            .filter(|directory| directory.is_dir());
+        if let Some(compatdata_directory) = compatdata_directory {
+            context.windows_profile_directory = Some(compatdata_directory.join(PROTON_STEAM_USER_DIRECTORY));
+            context.windows_program_data_directory =
+                Some(compatdata_directory.join("pfx").join("drive_c").join("ProgramData"));
+        }
+    }
+    context
+}
+
+/// Expands a PCGamingWiki path template. Returns `None` when the template references a location
+/// that does not exist on this machine, e.g. a Windows profile path for a native Linux game.
+pub(crate) fn expand_save_location_template(template: &str, context: &SaveLocationContext) -> Option<PathBuf> {
+    let token_pattern = Regex::new(r"(?i)\{\{p\|([^}]+)\}\}").ok()?;
+    let mut expanded = String::new();
+    let mut last_end = 0;
+    for capture in token_pattern.captures_iter(template) {
+        let whole_match = capture.get(0)?;
+        let token = capture.get(1)?.as_str().trim().replace('\\', "/").to_ascii_lowercase();
+        let windows_profile = || context.windows_profile_directory.clone();
+        let resolved = match token.as_str() {
+            "userprofile" => windows_profile(),
+            "userprofile/documents" => windows_profile().map(|directory| directory.join("Documents")),
+            "appdata" => windows_profile().map(|directory| directory.join("AppData").join("Roaming")),
+            "localappdata" => windows_profile().map(|directory| directory.join("AppData").join("Local")),
+            "localappdatalow" => windows_profile().map(|directory| directory.join("AppData").join("LocalLow")),
+            "programdata" => context.windows_program_data_directory.clone(),
+            "game" => context.game_directory.clone(),
+            "steam" => context.steam_root.clone(),
+            "uid" => context.steam_uid.clone().map(PathBuf::from),
+            "linuxhome" | "home" => context.home_directory.clone(),
+            "xdgdatahome" => std::env::var("XDG_DATA_HOME")
+                .ok()
+                .map(PathBuf::from)
+                .or_else(|| context.home_directory.as_ref().map(|home| home.join(".local").join("share"))),
+            "xdgconfighome" => std::env::var("XDG_CONFIG_HOME")
+                .ok()
+                .map(PathBuf::from)
+                .or_else(|| context.home_directory.as_ref().map(|home| home.join(".config"))),
+            _ => None,
+        }?;
+        expanded.push_str(&template[last_end..whole_match.start()]);
+        expanded.push_str(&resolved.to_string_lossy());
+        last_end = whole_match.end();
+    }
+    expanded.push_str(&template[last_end..]);
+
+    let normalized = if cfg!(target_os = "windows") {
+        expanded.replace('/', "\\")
+    } else {
+        expanded.replace('\\', "/")
+    };
+    Some(PathBuf::from(normalized))
+}
+
+pub(crate) fn save_backups_directory(db_path: &Path, provider: &str, external_id: &str) -> PathBuf {
+    let sanitized_external_id = external_id
+        .chars()
+        .map(|character| if character.is_ascii_alphanumeric() { character } else { '_' })
+        .collect::<String>();
+    db_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(SAVE_BACKUPS_DIRECTORY)
+        .join(format!("{provider}-{sanitized_external_id}"))
+}
+
+/// Zips every existing save location into a timestamped archive. Each location is stored under
+/// its index so that restores can map entries back to the original folder.
+pub(crate) fn create_save_backup(
+    backups_directory: &Path,
+    provider: &str,
+    external_id: &str,
+    locations: &[(String, PathBuf)],
+) -> Result<SaveBackupResponse, String> {
+    let existing_locations = locations
+        .iter()
+        .filter(|(_, path)| path.exists())
+        .collect::<Vec<_>>();
+    if existing_locations.is_empty() {
+        return Err(String::from("No save files were found for this game"));
+    }
+
+    fs::create_dir_all(backups_directory)
+        .map_err(|error| format!("Failed to create save backup directory: {error}"))?;
+    let created_at = Utc::now();
+    let backup_id = created_at.format(SAVE_BACKUP_TIMESTAMP_FORMAT).to_string();
+    let archive_path = backups_directory.join(format!("{backup_id}.{SAVE_BACKUP_EXTENSION}"));
+    let archive_file = fs::File::create(&archive_path)
+        .map_err(|error| format!("Failed to create save backup {}: {error}", archive_path.display()))?;
+    let mut writer = zip::ZipWriter::new(archive_file);
+    let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut file_count = 0;
+    let mut manifest_locations = Vec::new();
+    for (index, (template, location_path)) in existing_locations.iter().enumerate() {
+        let mut files = Vec::new();
+        collect_save_files(location_path, location_path, &mut files);
+        for (relative_path, file_path) in files {
+            let entry_name = if relative_path.is_empty() {
+                format!("{index}")
+            } else {
+                format!("{index}/{relative_path}")
+            };
+            writer
+                .start_file(entry_name, options)
+                .map_err(|error| format!("Failed to add save file to backup: {error}"))?;
+            let mut source_file = fs::File::open(&file_path)
+                .map_err(|error| format!("Failed to open save file {}: {error}", file_path.display()))?;
+            std::io::copy(&mut source_file, &mut writer)
+                .map_err(|error| format!("Failed to write save file {}: {error}", file_path.display()))?;
+            file_count += 1;
+        }
+        manifest_locations.push(SaveBackupManifestLocation {
+            template: template.clone(),
+            path: location_path.display().to_string(),
+        });
+    }
+
+    let manifest = SaveBackupManifest {
+        provider: provider.to_owned(),
+        external_id: external_id.to_owned(),
+        created_at: created_at.to_rfc3339(),
+        file_count,
+        locations: manifest_locations,
+    };
+    let manifest_json = serde_json::to_vec_pretty(&manifest)
+        .map_err(|error| format!("Failed to serialize save backup manifest: {error}"))?;
+    writer
+        .start_file(SAVE_BACKUP_MANIFEST_ENTRY, options)
+        .map_err(|error| format!("Failed to add save backup manifest: {error}"))?;
+    writer
+        .write_all(&manifest_json)
+        .map_err(|error| format!("Failed to write save backup manifest: {error}"))?;
+    writer
+        .finish()
+        .map_err(|error| format!("Failed to finish save backup: {error}"))?;
+
+    let size_bytes = fs::metadata(&archive_path).map(|metadata| metadata.len()).unwrap_or(0);
+    Ok(SaveBackupResponse {
+        id: backup_id,
+        provider: provider.to_owned(),
+        external_id: external_id.to_owned(),
+        created_at: manifest.created_at,
+        size_bytes,
+        file_count,
+        path: archive_path.display().to_string(),
+    })
+}
+
+fn collect_save_files(root: &Path, path: &Path, files: &mut Vec<(String, PathBuf)>) {
+    if path.is_file() {
+        let relative_path = path
+            .strip_prefix(root)
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_default();
+        files.push((relative_path, path.to_path_buf()));
+        return;
+    }
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        collect_save_files(root, &entry.path(), files);
+    }
+}
+
+fn read_save_backup_manifest(archive_path: &Path) -> Result<SaveBackupManifest, String> {
+    let archive_file = fs::File::open(archive_path)
+        .map_err(|error| format!("Failed to open save backup {}: {error}", archive_path.display()))?;
+    let mut archive =
+        zip::ZipArchive::new(archive_file).map_err(|error| format!("Failed to read save backup: {error}"))?;
+    let mut manifest_entry = archive
+        .by_name(SAVE_BACKUP_MANIFEST_ENTRY)
+        .map_err(|_| String::from("Save backup is missing its manifest"))?;
+    let mut manifest_json = String::new();
+    manifest_entry
+        .read_to_string(&mut manifest_json)
+        .map_err(|error| format!("Failed to read save backup manifest: {error}"))?;
+    serde_json::from_str(&manifest_json).map_err(|error| format!("Failed to parse save backup manifest: {error}"))
+}
+
+pub(crate) fn list_save_backups(backups_directory: &Path) -> Result<Vec<SaveBackupResponse>, String> {
+    let Ok(entries) = fs::read_dir(backups_directory) else {
+        return Ok(Vec::new());
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries.flatten() {
+        let archive_path = entry.path();
+        let is_backup = archive_path
+            .extension()
+            .map(|extension| extension.eq_ignore_ascii_case(SAVE_BACKUP_EXTENSION))
+            .unwrap_or(false);
+        if !is_backup {
+            continue;
+        }
+        let Ok(manifest) = read_save_backup_manifest(&archive_path) else {
+            continue;
+        };
+        backups.push(SaveBackupResponse {
+            id: archive_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            provider: manifest.provider,
+            external_id: manifest.external_id,
+            created_at: manifest.created_at,
+            size_bytes: entry.metadata().map(|metadata| metadata.len()).unwrap_or(0),
+            file_count: manifest.file_count,
+            path: archive_path.display().to_string(),
+        });
+    }
+    backups.sort_by(|left, right| right.created_at.cmp(&left.created_at));
+    Ok(backups)
+}
+
+/// Extracts a backup over the save locations recorded in its manifest. Existing files are
+/// overwritten; files that are not in the backup are left alone.
+pub(crate) fn restore_save_backup(backups_directory: &Path, backup_id: &str) -> Result<SaveRestoreResponse, String> {
+    let backup_id = backup_id.trim();
+    if backup_id.is_empty() || backup_id.contains(['/', '\\', '.']) {
+        return Err(String::from("Save backup ID is invalid"));
+    }
+    let archive_path = backups_directory.join(format!("{backup_id}.{SAVE_BACKUP_EXTENSION}"));
+    if !archive_path.is_file() {
+        return Err(String::from("Save backup not found"));
+    }
+    let manifest = read_save_backup_manifest(&archive_path)?;
+
+    let archive_file = fs::File::open(&archive_path)
+        .map_err(|error| format!("Failed to open save backup {}: {error}", archive_path.display()))?;
+    let mut archive =
+        zip::ZipArchive::new(archive_file).map_err(|error| format!("Failed to read save backup: {error}"))?;
+    let mut restored_files = 0;
+    for entry_index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(entry_index)
+            .map_err(|error| format!("Failed to read save backup entry: {error}"))?;
+        if entry.is_dir() || entry.name() == SAVE_BACKUP_MANIFEST_ENTRY {
+            continue;
+        }
+        let Some(enclosed_path) = entry.enclosed_name() else {
+            continue;
+        };
+        let mut components = enclosed_path.components();
+        let Some(location) = components
+            .next()
+            .and_then(|component| component.as_os_str().to_str())
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| manifest.locations.get(index))
+        else {
+            continue;
+        };
+        let relative_path = components.as_path();
+        let destination_path = if relative_path.as_os_str().is_empty() {
+            PathBuf::from(&location.path)
+        } else {
+            Path::new(&location.path).join(relative_path)
+        };
+        if let Some(parent_directory) = destination_path.parent() {
+            fs::create_dir_all(parent_directory)
+                .map_err(|error| format!("Failed to create {}: {error}", parent_directory.display()))?;
+        }
+        let mut destination_file = fs::File::create(&destination_path)
+            .map_err(|error| format!("Failed to write {}: {error}", destination_path.display()))?;
+        std::io::copy(&mut entry, &mut destination_file)
+            .map_err(|error| format!("Failed to restore {}: {error}", destination_path.display()))?;
+        restored_files += 1;
+    }
+
+    Ok(SaveRestoreResponse {
+        backup_id: backup_id.to_owned(),
+        restored_files,
+        locations: manifest.locations.into_iter().map(|location| location.path).collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(target_os = "windows"))]
+    fn expand_save_location_template_resolves_proton_profile_tokens() {
+        let context = SaveLocationContext {
+            home_directory: Some(PathBuf::from("/home/deck")),
+            windows_profile_directory: Some(PathBuf::from(
+                "/games/steamapps/compatdata/367520/pfx/drive_c/users/steamuser",
+            )),
+            ..SaveLocationContext::default()
+        };
+
+        assert_eq!(
+            expand_save_location_template(r"{{p|appdata}}\Hollow Knight", &context),
+            Some(PathBuf::from(
+                "/games/steamapps/compatdata/367520/pfx/drive_c/users/steamuser/AppData/Roaming/Hollow Knight"
+            ))
+        );
+        assert_eq!(expand_save_location_template("{{p|game}}/saves", &context), None);
+    }
+}
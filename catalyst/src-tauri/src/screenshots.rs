@@ -0,0 +1,247 @@
+use crate::*;
+
+const STEAM_SCREENSHOTS_APP_ID: &str = "760";
+const SCREENSHOT_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "webp", "bmp"];
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GameScreenshotResponse {
+    pub path: String,
+    pub thumbnail_path: Option<String>,
+    pub created_at: Option<String>,
+    pub caption: Option<String>,
+    pub source: String,
+}
+
+pub(crate) fn find_game_screenshot_directory(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+) -> Result<Option<String>, String> {
+    connection
+        .query_row(
+            "
+            SELECT directory
+            FROM game_screenshot_directories
+            WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3
+            ",
+            params![user_id, provider, external_id],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query screenshot directory: {error}"))
+}
+
+pub(crate) fn save_game_screenshot_directory(
+    connection: &Connection,
+    user_id: &str,
+    provider: &str,
+    external_id: &str,
+    directory: Option<&str>,
+) -> Result<(), String> {
+    let Some(directory) = directory.map(str::trim).filter(|value| !value.is_empty()) else {
+        connection
+            .execute(
+                "DELETE FROM game_screenshot_directories WHERE user_id = ?1 AND provider = ?2 AND external_id = ?3",
+                params![user_id, provider, external_id],
+            )
+            .map_err(|error| format!("Failed to clear screenshot directory: {error}"))?;
+        return Ok(());
+    };
+    if !Path::new(directory).is_dir() {
+        return Err(String::from("Screenshot directory must be an existing directory"));
+    }
+
+    connection
+        .execute(
+            "
+            INSERT INTO game_screenshot_directories (user_id, provider, external_id, directory, updated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT(user_id, provider, external_id) DO UPDATE SET
+              directory = excluded.directory,
+              updated_at = excluded.updated_at
+            ",
+            params![user_id, provider, external_id, directory, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to save screenshot directory: {error}"))?;
+    Ok(())
+}
+
+/// Steam keeps screenshots under `userdata/<id>/760/remote/<appid>/screenshots`, indexed by
+/// `760/screenshots.vdf`.
+pub(crate) fn resolve_steam_screenshots_directory(
+    steam_root_override: Option<&str>,
+    steam_id: &str,
+    app_id: u64,
+) -> Result<PathBuf, String> {
+    let steam_root = resolve_steam_root_path(steam_root_override)
+        .ok_or_else(|| String::from("Could not locate local Steam installation"))?;
+    let userdata_directory = resolve_steam_userdata_directory(&steam_root, steam_id)?;
+    Ok(userdata_directory
+        .join(STEAM_SCREENSHOTS_APP_ID)
+        .join("remote")
+        .join(app_id.to_string())
+        .join("screenshots"))
+}
+
+/// Lists Steam screenshots for an app. Entries from `screenshots.vdf` carry captions and
+/// capture times; image files missing from the index are still returned using their mtime.
+pub(crate) fn list_steam_screenshots(
+    steam_root_override: Option<&str>,
+    steam_id: &str,
+    app_id: u64,
+) -> Result<Vec<GameScreenshotResponse>, String> {
+    let screenshots_directory = resolve_steam_screenshots_directory(steam_root_override, steam_id, app_id)?;
+    let Some(remote_directory) = screenshots_directory.parent().and_then(Path::parent) else {
+        return Ok(Vec::new());
+    };
+    let index_path = remote_directory
+        .parent()
+        .map(|directory| directory.join("screenshots.vdf"));
+
+    let mut screenshots = Vec::new();
+    let mut indexed_paths = HashSet::new();
+    if let Some(index_contents) = index_path.and_then(|path| fs::read_to_string(path).ok()) {
+        for screenshot in parse_steam_screenshots_index(&index_contents, app_id, remote_directory)? {
+            if Path::new(&screenshot.path).is_file() {
+                indexed_paths.insert(screenshot.path.clone());
+                screenshots.push(screenshot);
+            }
+        }
+    }
+
+    for mut screenshot in list_directory_screenshots(&screenshots_directory, "steam") {
+        if indexed_paths.contains(&screenshot.path) {
+            continue;
+        }
+        let thumbnail_path = Path::new(&screenshot.path)
+            .file_name()
+            .map(|file_name| screenshots_directory.join("thumbnails").join(file_name))
+            .filter(|path| path.is_file());
+        screenshot.thumbnail_path = thumbnail_path.map(|path| path.display().to_string());
+        screenshots.push(screenshot);
+    }
+
+    sort_screenshots_newest_first(&mut screenshots);
+    Ok(screenshots)
+}
+
+fn parse_steam_screenshots_index(
+    contents: &str,
+    app_id: u64,
+    remote_directory: &Path,
+) -> Result<Vec<GameScreenshotResponse>, String> {
+    let document = parse_vdf_document(contents)?;
+    let Some(VdfValue::Object(entries)) = vdf_find_object_value(&document, "Screenshots")
+        .and_then(|screenshots| vdf_find_object_value(screenshots, &app_id.to_string()))
+    else {
+        return Ok(Vec::new());
+    };
+
+    let resolve_path = |relative_path: &str| remote_directory.join(relative_path).display().to_string();
+    Ok(entries
+        .iter()
+        .filter_map(|(_, entry)| {
+            let filename = vdf_get_text_entry(entry, "filename")?;
+            Some(GameScreenshotResponse {
+                path: resolve_path(filename),
+                thumbnail_path: vdf_get_text_entry(entry, "thumbnail")
+                    .filter(|value| !value.is_empty())
+                    .map(resolve_path),
+                created_at: vdf_get_text_entry(entry, "creation")
+                    .and_then(|value| value.parse::<i64>().ok())
+                    .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+                    .map(|timestamp| timestamp.to_rfc3339()),
+                caption: vdf_get_text_entry(entry, "caption")
+                    .map(str::trim)
+                    .filter(|value| !value.is_empty())
+                    .map(str::to_owned),
+                source: String::from("steam"),
+            })
+        })
+        .collect())
+}
+
+pub(crate) fn list_directory_screenshots(directory: &Path, source: &str) -> Vec<GameScreenshotResponse> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+
+    let mut screenshots = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+                    .map(|extension| SCREENSHOT_EXTENSIONS.contains(&extension.as_str()))
+                    .unwrap_or(false)
+        })
+        .map(|path| GameScreenshotResponse {
+            created_at: fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .map(|modified| chrono::DateTime::<Utc>::from(modified).to_rfc3339()),
+            path: path.display().to_string(),
+            thumbnail_path: None,
+            caption: None,
+            source: source.to_owned(),
+        })
+        .collect::<Vec<_>>();
+    sort_screenshots_newest_first(&mut screenshots);
+    screenshots
+}
+
+pub(crate) fn sort_screenshots_newest_first(screenshots: &mut [GameScreenshotResponse]) {
+    screenshots.sort_by(|left, right| {
+        right
+            .created_at
+            .cmp(&left.created_at)
+            .then_with(|| left.path.cmp(&right.path))
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steam_screenshots_index_reads_app_entries() {
+        let contents = r#"
+"Screenshots"
+{
+	"620"
+	{
+		"0"
+		{
+			"type"		"1"
+			"filename"		"620/screenshots/20240101120000_1.jpg"
+			"thumbnail"		"620/screenshots/thumbnails/20240101120000_1.jpg"
+			"creation"		"1704110400"
+			"caption"		"Cake"
+		}
+	}
+	"400"
+	{
+		"0"
+		{
+			"filename"		"400/screenshots/20230101120000_1.jpg"
+		}
+	}
+}
+"#;
+
+        let screenshots =
+            parse_steam_screenshots_index(contents, 620, Path::new("remote")).expect("index should parse");
+
+        assert_eq!(screenshots.len(), 1);
+        assert_eq!(screenshots[0].caption.as_deref(), Some("Cake"));
+        assert_eq!(
+            screenshots[0].created_at.as_deref(),
+            Some("2024-01-01T12:00:00+00:00")
+        );
+        assert!(screenshots[0].path.ends_with("20240101120000_1.jpg"));
+    }
+}
@@ -0,0 +1,91 @@
+use crate::*;
+
+const KEYCHAIN_SERVICE: &str = "catalyst";
+pub(crate) const STEAM_API_KEY_ACCOUNT: &str = "steam-api-key";
+
+/// Reads a secret from the OS keychain (Windows Credential Manager, macOS Keychain or the
+/// Secret Service on Linux). `Ok(None)` means the keychain works but holds no such entry; an
+/// error means the keychain itself is unavailable.
+#[cfg(not(test))]
+pub(crate) fn read_secret(account: &str) -> Result<Option<String>, String> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, account)
+        .map_err(|error| format!("OS keychain is unavailable: {error}"))?;
+    match entry.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(error) => Err(format!("OS keychain is unavailable: {error}")),
+    }
+}
+
+#[cfg(not(test))]
+pub(crate) fn write_secret(account: &str, value: &str) -> Result<(), String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, account)
+        .and_then(|entry| entry.set_password(value))
+        .map_err(|error| format!("Failed to write to the OS keychain: {error}"))
+}
+
+#[cfg(not(test))]
+pub(crate) fn delete_secret(account: &str) -> Result<(), String> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, account)
+        .map_err(|error| format!("OS keychain is unavailable: {error}"))?;
+    match entry.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(error) => Err(format!("Failed to remove secret from the OS keychain: {error}")),
+    }
+}
+
+// Tests must never read or write the developer's real keychain. Tests share one process, so
+// each should use its own account names.
+#[cfg(test)]
+static TEST_SECRETS: once_cell::sync::Lazy<Mutex<HashMap<String, String>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[cfg(test)]
+static TEST_UNAVAILABLE_ACCOUNTS: once_cell::sync::Lazy<Mutex<HashSet<String>>> =
+    once_cell::sync::Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Makes every keychain call for `account` fail, as a locked or missing keychain would.
+#[cfg(test)]
+pub(crate) fn set_test_secret_unavailable(account: &str, unavailable: bool) {
+    let mut accounts = TEST_UNAVAILABLE_ACCOUNTS.lock().expect("test keychain lock");
+    if unavailable {
+        accounts.insert(account.to_owned());
+    } else {
+        accounts.remove(account);
+    }
+}
+
+#[cfg(test)]
+fn ensure_test_secret_available(account: &str) -> Result<(), String> {
+    if TEST_UNAVAILABLE_ACCOUNTS
+        .lock()
+        .map_err(|error| error.to_string())?
+        .contains(account)
+    {
+        return Err(String::from("OS keychain is unavailable: test keychain is locked"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) fn read_secret(account: &str) -> Result<Option<String>, String> {
+    ensure_test_secret_available(account)?;
+    Ok(TEST_SECRETS.lock().map_err(|error| error.to_string())?.get(account).cloned())
+}
+
+#[cfg(test)]
+pub(crate) fn write_secret(account: &str, value: &str) -> Result<(), String> {
+    ensure_test_secret_available(account)?;
+    TEST_SECRETS
+        .lock()
+        .map_err(|error| error.to_string())?
+        .insert(account.to_owned(), value.to_owned());
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) fn delete_secret(account: &str) -> Result<(), String> {
+    ensure_test_secret_available(account)?;
+    TEST_SECRETS.lock().map_err(|error| error.to_string())?.remove(account);
+    Ok(())
+}
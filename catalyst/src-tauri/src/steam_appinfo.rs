@@ -0,0 +1,270 @@
+use crate::*;
+use crate::steam_shortcuts::{
+    parse_binary_vdf_with_key_table, read_binary_vdf_bytes, read_binary_vdf_string, BinaryVdfValue,
+};
+
+const APPINFO_MAGIC_V27: u32 = 0x0756_4427;
+const APPINFO_MAGIC_V28: u32 = 0x0756_4428;
+const APPINFO_MAGIC_V29: u32 = 0x0756_4429;
+/// info_state, last_updated, pics_token, text SHA-1 and change_number precede every entry's data.
+const APPINFO_ENTRY_HEADER_LEN: usize = 4 + 4 + 8 + 20 + 4;
+const APPINFO_BINARY_SHA1_LEN: usize = 20;
+
+fn resolve_steam_appinfo_path(steam_root_override: Option<&str>) -> Option<PathBuf> {
+    let path = resolve_steam_root_path(steam_root_override)?
+        .join("appcache")
+        .join("appinfo.vdf");
+    path.is_file().then_some(path)
+}
+
+fn read_u32(bytes: &[u8], cursor: &mut usize) -> Result<u32, String> {
+    Ok(u32::from_le_bytes(read_binary_vdf_bytes::<4>(bytes, cursor)?))
+}
+
+/// v29 replaced the inline keys of every entry with indices into a table at the end of the file.
+fn read_appinfo_key_table(bytes: &[u8], offset: usize) -> Result<Vec<String>, String> {
+    let mut cursor = offset;
+    let count = read_u32(bytes, &mut cursor)? as usize;
+    let mut keys = Vec::with_capacity(count.min(bytes.len()));
+    for _ in 0..count {
+        keys.push(read_binary_vdf_string(bytes, &mut cursor)?);
+    }
+    Ok(keys)
+}
+
+/// Finds an app in the contents of Steam's `appinfo.vdf` product info cache and parses its
+/// KeyValues. Returns `None` when the app isn't cached.
+pub(crate) fn parse_steam_appinfo_entry(
+    bytes: &[u8],
+    app_id: u32,
+) -> Result<Option<Vec<(String, BinaryVdfValue)>>, String> {
+    let mut cursor = 0;
+    let magic = read_u32(bytes, &mut cursor)?;
+    let _universe = read_u32(bytes, &mut cursor)?;
+    let key_table = match magic {
+        APPINFO_MAGIC_V27 | APPINFO_MAGIC_V28 => None,
+        APPINFO_MAGIC_V29 => {
+            let offset = i64::from_le_bytes(read_binary_vdf_bytes::<8>(bytes, &mut cursor)?);
+            let offset = usize::try_from(offset).map_err(|_| String::from("Invalid appinfo.vdf key table offset"))?;
+            Some(read_appinfo_key_table(bytes, offset)?)
+        }
+        _ => return Err(format!("Unsupported appinfo.vdf version {magic:#010x}")),
+    };
+    let data_offset = match magic {
+        APPINFO_MAGIC_V27 => APPINFO_ENTRY_HEADER_LEN,
+        _ => APPINFO_ENTRY_HEADER_LEN + APPINFO_BINARY_SHA1_LEN,
+    };
+
+    loop {
+        let entry_app_id = read_u32(bytes, &mut cursor)?;
+        if entry_app_id == 0 {
+            return Ok(None);
+        }
+        let size = read_u32(bytes, &mut cursor)? as usize;
+        let entry = bytes
+            .get(cursor..cursor + size)
+            .ok_or_else(|| String::from("Unexpected end of appinfo.vdf"))?;
+        cursor += size;
+        if entry_app_id != app_id {
+            continue;
+        }
+
+        let data = entry
+            .get(data_offset..)
+            .ok_or_else(|| format!("appinfo.vdf entry for app {app_id} is truncated"))?;
+        let entries = match key_table.as_deref() {
+            Some(key_table) => parse_binary_vdf_with_key_table(data, key_table)?,
+            None => steam_shortcuts::parse_binary_vdf(data)?,
+        };
+        return Ok(Some(entries));
+    }
+}
+
+fn binary_vdf_to_json(value: &BinaryVdfValue) -> serde_json::Value {
+    match value {
+        BinaryVdfValue::Map(entries) => serde_json::Value::Object(
+            entries
+                .iter()
+                .map(|(key, value)| (key.clone(), binary_vdf_to_json(value)))
+                .collect(),
+        ),
+        BinaryVdfValue::String(text) => serde_json::Value::String(text.clone()),
+        BinaryVdfValue::Int32(number) => serde_json::Value::from(*number),
+        BinaryVdfValue::UInt64(number) => serde_json::Value::from(*number),
+    }
+}
+
+/// The app's section of the local `appinfo.vdf`, or `None` when Steam hasn't cached it.
+fn read_local_steam_appinfo_json(steam_root_override: Option<&str>, app_id: u64) -> Result<Option<serde_json::Value>, String> {
+    let Some(appinfo_path) = resolve_steam_appinfo_path(steam_root_override) else {
+        return Ok(None);
+    };
+    let Ok(numeric_app_id) = u32::try_from(app_id) else {
+        return Ok(None);
+    };
+    let bytes = fs::read(&appinfo_path)
+        .map_err(|error| format!("Failed to read {}: {error}", appinfo_path.display()))?;
+    let Some(entries) = parse_steam_appinfo_entry(&bytes, numeric_app_id)? else {
+        return Ok(None);
+    };
+
+    // The entry's root is an "appinfo" map with the same shape as the web appdetails payload.
+    Ok(Some(
+        entries
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("appinfo"))
+            .map(|(_, value)| binary_vdf_to_json(value))
+            .unwrap_or_else(|| binary_vdf_to_json(&BinaryVdfValue::Map(entries))),
+    ))
+}
+
+/// The folder name under `steamapps/common` Steam installs the app into.
+pub(crate) fn read_local_steam_app_install_dir(steam_root_override: Option<&str>, app_id: u64) -> Result<Option<String>, String> {
+    Ok(read_local_steam_appinfo_json(steam_root_override, app_id)?
+        .as_ref()
+        .and_then(|payload| payload.pointer("/config/installdir"))
+        .and_then(serde_json::Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_owned))
+}
+
+/// Reads an app's beta branches from the local product info cache, which Steam keeps current
+/// for every app it has seen. Returns `None` when the cache or the app entry is missing, so the
+/// caller can fall back to the web.
+pub(crate) fn read_local_steam_beta_branches(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+) -> Result<Option<Vec<GameVersionBetaOptionResponse>>, String> {
+    let Some(payload) = read_local_steam_appinfo_json(steam_root_override, app_id)? else {
+        return Ok(None);
+    };
+    let Some(depots) = payload.get("depots") else {
+        return Ok(None);
+    };
+    let payload = serde_json::json!({ "data": { "depots": depots } });
+    let options = parse_steam_game_version_betas_payload(&payload, app_id);
+    Ok((!options.is_empty()).then(|| normalize_game_version_beta_options(&options)))
+}
+
+/// The manifest a branch currently ships for one depot, with its installed and compressed sizes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SteamDepotBranchManifest {
+    pub manifest_id: String,
+    pub size_bytes: Option<u64>,
+    pub download_bytes: Option<u64>,
+}
+
+fn appinfo_json_u64(value: Option<&serde_json::Value>) -> Option<u64> {
+    match value? {
+        serde_json::Value::String(text) => text.trim().parse().ok(),
+        value => value.as_u64(),
+    }
+}
+
+/// Parses `depots/<id>/manifests/<branch>` of an appinfo section. Newer caches store an object
+/// with `gid`, `size` and `download`; older ones only the manifest ID, with the depot's
+/// `maxsize` as the best size available.
+pub(crate) fn parse_steam_depot_branch_manifests(
+    payload: &serde_json::Value,
+    branch: &str,
+) -> HashMap<String, SteamDepotBranchManifest> {
+    let Some(depots) = payload.get("depots").and_then(serde_json::Value::as_object) else {
+        return HashMap::new();
+    };
+    depots
+        .iter()
+        .filter_map(|(depot_id, depot)| {
+            let manifest = depot.get("manifests")?.get(branch)?;
+            let manifest = match manifest {
+                serde_json::Value::String(manifest_id) => SteamDepotBranchManifest {
+                    manifest_id: manifest_id.clone(),
+                    size_bytes: appinfo_json_u64(depot.get("maxsize")),
+                    download_bytes: None,
+                },
+                manifest => SteamDepotBranchManifest {
+                    manifest_id: manifest.get("gid")?.as_str()?.to_owned(),
+                    size_bytes: appinfo_json_u64(manifest.get("size")),
+                    download_bytes: appinfo_json_u64(manifest.get("download")),
+                },
+            };
+            Some((depot_id.clone(), manifest))
+        })
+        .collect()
+}
+
+/// Depot manifests `branch` ships according to the local product info cache, or `None` when
+/// Steam hasn't cached the app.
+pub(crate) fn read_local_steam_depot_branch_manifests(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+    branch: &str,
+) -> Result<Option<HashMap<String, SteamDepotBranchManifest>>, String> {
+    Ok(read_local_steam_appinfo_json(steam_root_override, app_id)?
+        .map(|payload| parse_steam_depot_branch_manifests(&payload, branch)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push_key(bytes: &mut Vec<u8>, value_type: u8, key_index: u32) {
+        bytes.push(value_type);
+        bytes.extend_from_slice(&key_index.to_le_bytes());
+    }
+
+    #[test]
+    fn read_local_steam_beta_branches_parses_v29_appinfo() {
+        let keys = ["appinfo", "depots", "branches", "public", "beta", "buildid", "pwdrequired", "timeupdated"];
+        let mut data = Vec::new();
+        push_key(&mut data, 0x00, 0);
+        push_key(&mut data, 0x00, 1);
+        push_key(&mut data, 0x00, 2);
+        push_key(&mut data, 0x00, 3);
+        push_key(&mut data, 0x01, 5);
+        data.extend_from_slice(b"100\0");
+        data.push(0x08);
+        push_key(&mut data, 0x00, 4);
+        push_key(&mut data, 0x01, 5);
+        data.extend_from_slice(b"200\0");
+        push_key(&mut data, 0x01, 6);
+        data.extend_from_slice(b"1\0");
+        push_key(&mut data, 0x02, 7);
+        data.extend_from_slice(&1_700_000_000_i32.to_le_bytes());
+        data.extend_from_slice(&[0x08, 0x08, 0x08, 0x08, 0x08]);
+
+        let mut entry = vec![0_u8; APPINFO_ENTRY_HEADER_LEN + APPINFO_BINARY_SHA1_LEN];
+        entry.extend_from_slice(&data);
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&APPINFO_MAGIC_V29.to_le_bytes());
+        bytes.extend_from_slice(&1_u32.to_le_bytes());
+        let key_table_offset_position = bytes.len();
+        bytes.extend_from_slice(&0_i64.to_le_bytes());
+        bytes.extend_from_slice(&620_u32.to_le_bytes());
+        bytes.extend_from_slice(&(entry.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&entry);
+        bytes.extend_from_slice(&0_u32.to_le_bytes());
+        let key_table_offset = bytes.len() as i64;
+        bytes[key_table_offset_position..key_table_offset_position + 8]
+            .copy_from_slice(&key_table_offset.to_le_bytes());
+        bytes.extend_from_slice(&(keys.len() as u32).to_le_bytes());
+        for key in keys {
+            bytes.extend_from_slice(key.as_bytes());
+            bytes.push(0);
+        }
+
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        fs::create_dir_all(dir.path().join("appcache")).expect("create appcache");
+        fs::write(dir.path().join("appcache").join("appinfo.vdf"), &bytes).expect("write appinfo");
+        let steam_root = dir.path().to_string_lossy().into_owned();
+
+        let options = read_local_steam_beta_branches(Some(&steam_root), 620)
+            .expect("read appinfo")
+            .expect("app should be cached");
+        let beta = options.iter().find(|option| option.id == "beta").expect("beta branch");
+        assert_eq!(beta.build_id.as_deref(), Some("200"));
+        assert!(beta.requires_access_code);
+        assert!(options.iter().any(|option| option.id == "public" && option.is_default));
+        assert!(read_local_steam_beta_branches(Some(&steam_root), 440).expect("read appinfo").is_none());
+    }
+}
@@ -0,0 +1,340 @@
+use crate::*;
+
+const STEAM_GET_BADGES_ENDPOINT: &str = "https://api.steampowered.com/IPlayerService/GetBadges/v1/";
+const STEAM_COMMUNITY_PROFILES_URL: &str = "https://steamcommunity.com/profiles";
+/// The store category of games that drop trading cards.
+const STEAM_TRADING_CARDS_CATEGORY: &str = "Steam Trading Cards";
+pub(crate) const STEAM_BADGES_CACHE_TTL_SECS: i64 = 15 * 60;
+pub(crate) const STEAM_GAME_CARD_STATUS_CACHE_TTL_SECS: i64 = 15 * 60;
+/// Regular game badges top out at level 5; foil badges have a single level.
+const STEAM_GAME_BADGE_MAX_LEVEL: u64 = 5;
+
+static CARD_DROPS_REMAINING_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+    Regex::new(r"(?i)(\d+|no) card drops? remaining").expect("card drops remaining pattern should compile")
+});
+
+/// One badge from `IPlayerService/GetBadges`. Game badges have an app ID; the rest are Steam's
+/// own badges such as years of service.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamBadge {
+    pub badge_id: u64,
+    pub app_id: Option<u64>,
+    pub level: u64,
+    pub xp: u64,
+    /// Game badges crafted from foil cards.
+    pub foil: bool,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamPlayerBadges {
+    pub player_level: u64,
+    pub player_xp: u64,
+    pub xp_needed_to_level_up: u64,
+    pub xp_needed_current_level: u64,
+    pub badges: Vec<SteamBadge>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct GameCardStatusResponse {
+    pub has_trading_cards: bool,
+    /// 0 until the first badge level is crafted, `STEAM_GAME_BADGE_MAX_LEVEL` when maxed.
+    pub badge_level: u64,
+    pub badge_xp: u64,
+    pub foil_badge_crafted: bool,
+    /// Steam only shows this on the badge page while it is public and the drops are known;
+    /// `None` when it couldn't be read.
+    pub card_drops_remaining: Option<u64>,
+    pub warning: Option<String>,
+    pub stale: bool,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamBadgeEntry {
+    pub badge_id: u64,
+    pub app_id: Option<u64>,
+    /// The game's name for game badges the user owns.
+    pub game_name: Option<String>,
+    pub level: u64,
+    pub xp: u64,
+    pub foil: bool,
+    pub completed_at: Option<String>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamBadgesResponse {
+    pub player_level: u64,
+    pub player_xp: u64,
+    pub xp_needed_to_level_up: u64,
+    pub xp_needed_current_level: u64,
+    pub game_badge_count: usize,
+    pub foil_badge_count: usize,
+    /// Game badges below the regular badge's last level.
+    pub unfinished_game_badge_count: usize,
+    /// Most recently earned first.
+    pub badges: Vec<SteamBadgeEntry>,
+    pub warning: Option<String>,
+    pub stale: bool,
+}
+
+impl GameCardStatusResponse {
+    pub(crate) fn without_cards(warning: Option<String>) -> Self {
+        Self {
+            has_trading_cards: false,
+            badge_level: 0,
+            badge_xp: 0,
+            foil_badge_crafted: false,
+            card_drops_remaining: None,
+            warning,
+            stale: false,
+        }
+    }
+}
+
+pub(crate) fn fetch_steam_badges(client: &Client, api_key: &str, steam_id: &str) -> Result<SteamPlayerBadges, String> {
+    let mut request_url = Url::parse(STEAM_GET_BADGES_ENDPOINT)
+        .map_err(|error| format!("Failed to parse Steam badges endpoint: {error}"))?;
+    request_url
+        .query_pairs_mut()
+        .append_pair("key", api_key)
+        .append_pair("steamid", steam_id);
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam badges request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam badges request failed with status {}", response.status()));
+    }
+    let payload = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam badges response: {error}"))?;
+    parse_steam_badges_payload(&payload)
+}
+
+/// A private profile answers with an empty `response`, which is reported rather than shown as
+/// level 0.
+fn parse_steam_badges_payload(payload: &serde_json::Value) -> Result<SteamPlayerBadges, String> {
+    let response = payload
+        .get("response")
+        .filter(|response| response.get("player_level").is_some())
+        .ok_or_else(|| String::from("Steam returned no badges; the Steam profile may be private"))?;
+    let badges = response
+        .get("badges")
+        .and_then(serde_json::Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|badge| {
+            Some(SteamBadge {
+                badge_id: parse_json_u64_value(badge.get("badgeid"))?,
+                app_id: parse_json_u64_value(badge.get("appid")),
+                level: parse_json_u64_value(badge.get("level")).unwrap_or(0),
+                xp: parse_json_u64_value(badge.get("xp")).unwrap_or(0),
+                foil: parse_json_u64_value(badge.get("border_color")).is_some_and(|border| border != 0),
+                completed_at: badge
+                    .get("completion_time")
+                    .and_then(serde_json::Value::as_i64)
+                    .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+                    .map(|timestamp| timestamp.to_rfc3339()),
+            })
+        })
+        .collect();
+    Ok(SteamPlayerBadges {
+        player_level: parse_json_u64_value(response.get("player_level")).unwrap_or(0),
+        player_xp: parse_json_u64_value(response.get("player_xp")).unwrap_or(0),
+        xp_needed_to_level_up: parse_json_u64_value(response.get("player_xp_needed_to_level_up")).unwrap_or(0),
+        xp_needed_current_level: parse_json_u64_value(response.get("player_xp_needed_current_level")).unwrap_or(0),
+        badges,
+    })
+}
+
+fn parse_card_drops_remaining(html: &str) -> Option<u64> {
+    let captures = CARD_DROPS_REMAINING_PATTERN.captures(html)?;
+    if captures[1].eq_ignore_ascii_case("no") {
+        return Some(0);
+    }
+    captures[1].parse().ok()
+}
+
+/// Reads the drops remaining from the game's badge page on the community site.
+pub(crate) fn fetch_card_drops_remaining(client: &Client, steam_id: &str, app_id: u64) -> Result<Option<u64>, String> {
+    let request_url = Url::parse(&format!("{STEAM_COMMUNITY_PROFILES_URL}/{steam_id}/gamecards/{app_id}/"))
+        .map_err(|error| format!("Failed to parse Steam badge page URL: {error}"))?;
+    let response = send_steam_get_request(client, request_url)
+        .map_err(|error| format!("Steam badge page request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!("Steam badge page request failed with status {}", response.status()));
+    }
+    let html = response
+        .text()
+        .map_err(|error| format!("Failed to read Steam badge page: {error}"))?;
+    Ok(parse_card_drops_remaining(&html))
+}
+
+/// Whether the store lists the app under Steam Trading Cards, from the metadata cache. Stale or
+/// missing metadata is fetched unless `allow_fetch` is false, as in offline mode.
+pub(crate) fn app_has_trading_cards(
+    connection: &Connection,
+    client: &Client,
+    app_id: u64,
+    allow_fetch: bool,
+) -> Result<bool, String> {
+    let stale_before = Utc::now() - ChronoDuration::hours(STEAM_APP_METADATA_CACHE_TTL_HOURS);
+    let cached = connection
+        .query_row(
+            "SELECT categories_json, fetched_at FROM steam_app_metadata WHERE app_id = ?1",
+            params![app_id.to_string()],
+            |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, String>(1)?)),
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query cached Steam app metadata: {error}"))?;
+    let has_category = |categories: &[String]| {
+        categories
+            .iter()
+            .any(|category| category.eq_ignore_ascii_case(STEAM_TRADING_CARDS_CATEGORY))
+    };
+    if let Some((Some(categories_json), fetched_at)) = cached {
+        let is_fresh = chrono::DateTime::parse_from_rfc3339(&fetched_at)
+            .is_ok_and(|timestamp| timestamp.with_timezone(&Utc) >= stale_before);
+        if is_fresh || !allow_fetch {
+            let categories = serde_json::from_str::<Vec<String>>(&categories_json).unwrap_or_default();
+            return Ok(has_category(&categories));
+        }
+    }
+    if !allow_fetch {
+        return Ok(false);
+    }
+
+    let Some(metadata) = fetch_steam_app_metadata_batch(client, &[app_id])?.remove(&app_id) else {
+        return Ok(false);
+    };
+    cache_steam_app_metadata(connection, app_id, &metadata)?;
+    Ok(has_category(&metadata.categories))
+}
+
+/// Level, XP and badge counts for the profile stats panel. `game_names` names game badges by
+/// app ID.
+pub(crate) fn summarize_steam_badges(
+    badges: SteamPlayerBadges,
+    game_names: &HashMap<u64, String>,
+    stale: bool,
+) -> SteamBadgesResponse {
+    let game_badges = badges.badges.iter().filter(|badge| badge.app_id.is_some());
+    let game_badge_count = game_badges.clone().count();
+    let foil_badge_count = game_badges.clone().filter(|badge| badge.foil).count();
+    let unfinished_game_badge_count = game_badges
+        .filter(|badge| !badge.foil && badge.level < STEAM_GAME_BADGE_MAX_LEVEL)
+        .count();
+
+    let mut entries = badges
+        .badges
+        .into_iter()
+        .map(|badge| SteamBadgeEntry {
+            game_name: badge.app_id.and_then(|app_id| game_names.get(&app_id).cloned()),
+            badge_id: badge.badge_id,
+            app_id: badge.app_id,
+            level: badge.level,
+            xp: badge.xp,
+            foil: badge.foil,
+            completed_at: badge.completed_at,
+        })
+        .collect::<Vec<_>>();
+    entries.sort_by(|left, right| {
+        right
+            .completed_at
+            .cmp(&left.completed_at)
+            .then(right.xp.cmp(&left.xp))
+    });
+
+    SteamBadgesResponse {
+        player_level: badges.player_level,
+        player_xp: badges.player_xp,
+        xp_needed_to_level_up: badges.xp_needed_to_level_up,
+        xp_needed_current_level: badges.xp_needed_current_level,
+        game_badge_count,
+        foil_badge_count,
+        unfinished_game_badge_count,
+        badges: entries,
+        warning: None,
+        stale,
+    }
+}
+
+/// Combines the game's badge from `badges` with the drops read from its badge page.
+pub(crate) fn game_card_status(
+    app_id: u64,
+    badges: Option<&SteamPlayerBadges>,
+    card_drops_remaining: Option<u64>,
+    warning: Option<String>,
+) -> GameCardStatusResponse {
+    let game_badges = badges
+        .map(|badges| {
+            badges
+                .badges
+                .iter()
+                .filter(|badge| badge.app_id == Some(app_id))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let regular_badge = game_badges.iter().find(|badge| !badge.foil);
+    GameCardStatusResponse {
+        has_trading_cards: true,
+        badge_level: regular_badge.map_or(0, |badge| badge.level),
+        badge_xp: game_badges.iter().map(|badge| badge.xp).sum(),
+        foil_badge_crafted: game_badges.iter().any(|badge| badge.foil),
+        card_drops_remaining,
+        warning,
+        stale: false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steam_badges_and_card_drops() {
+        let payload = serde_json::json!({
+            "response": {
+                "badges": [
+                    { "badgeid": 1, "level": 7, "xp": 350, "completion_time": 1700000000, "scarcity": 1000 },
+                    { "badgeid": 1, "appid": 620, "level": 3, "xp": 300, "border_color": 0, "completion_time": 1700000000 },
+                    { "badgeid": 1, "appid": 620, "level": 1, "xp": 100, "border_color": 1, "completion_time": 1700000000 }
+                ],
+                "player_xp": 750,
+                "player_level": 7,
+                "player_xp_needed_to_level_up": 50,
+                "player_xp_needed_current_level": 700
+            }
+        });
+        let badges = parse_steam_badges_payload(&payload).expect("parse badges");
+        assert_eq!((badges.player_level, badges.player_xp, badges.badges.len()), (7, 750, 3));
+        assert!(parse_steam_badges_payload(&serde_json::json!({ "response": {} })).is_err());
+
+        let status = game_card_status(620, Some(&badges), Some(2), None);
+        assert_eq!(
+            (status.badge_level, status.badge_xp, status.foil_badge_crafted),
+            (3, 400, true)
+        );
+        assert_eq!(game_card_status(400, Some(&badges), None, None).badge_level, 0);
+
+        let game_names = HashMap::from([(620, String::from("Portal 2"))]);
+        let summary = summarize_steam_badges(badges, &game_names, false);
+        assert_eq!(
+            (summary.game_badge_count, summary.foil_badge_count, summary.unfinished_game_badge_count),
+            (2, 1, 1)
+        );
+        assert_eq!(summary.badges[0].xp, 350);
+        assert_eq!(summary.badges[1].game_name.as_deref(), Some("Portal 2"));
+
+        assert_eq!(
+            parse_card_drops_remaining(r#"<span class="progress_info_bold">3 card drops remaining</span>"#),
+            Some(3)
+        );
+        assert_eq!(parse_card_drops_remaining("No card drops remaining"), Some(0));
+        assert_eq!(parse_card_drops_remaining("<div class=\"badge_title\">Portal 2 Badge</div>"), None);
+    }
+}
@@ -0,0 +1,246 @@
+use crate::*;
+
+/// Collections whose ids Steam reserves for flags Catalyst syncs separately.
+const RESERVED_COLLECTION_IDS: [&str; 1] = ["hidden"];
+
+/// The filters of a Steam dynamic collection that Catalyst can evaluate itself. Steam's filter
+/// groups (play state, genres, features, ...) are keyed by client-internal option ids, so only
+/// a name search is representable.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SteamDynamicCollectionFilter {
+    pub search_text: Option<String>,
+    /// Set when the filter uses groups Catalyst can't map; such collections are skipped.
+    pub has_unsupported_groups: bool,
+}
+
+/// A collection from `cloud-storage-namespace-1.json`, which Steam has used for collections since
+/// the library redesign; `sharedconfig.vdf` only keeps the categories of older clients.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SteamCloudCollection {
+    pub id: String,
+    pub name: String,
+    pub added: Vec<u64>,
+    pub removed: HashSet<u64>,
+    pub filter: Option<SteamDynamicCollectionFilter>,
+}
+
+fn parse_app_id_list(value: Option<&serde_json::Value>) -> Vec<u64> {
+    value
+        .and_then(serde_json::Value::as_array)
+        .map(|app_ids| {
+            app_ids
+                .iter()
+                .filter_map(|app_id| {
+                    app_id
+                        .as_u64()
+                        .or_else(|| app_id.as_str().and_then(|app_id| app_id.trim().parse::<u64>().ok()))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn parse_dynamic_collection_filter(filter_spec: &serde_json::Value) -> SteamDynamicCollectionFilter {
+    let has_unsupported_groups = filter_spec
+        .get("filterGroups")
+        .and_then(serde_json::Value::as_array)
+        .is_some_and(|groups| {
+            groups.iter().any(|group| {
+                group
+                    .get("rgOptions")
+                    .and_then(serde_json::Value::as_array)
+                    .is_some_and(|options| !options.is_empty())
+            })
+        });
+    SteamDynamicCollectionFilter {
+        search_text: filter_spec
+            .get("strSearchText")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(str::to_owned),
+        has_unsupported_groups,
+    }
+}
+
+/// Reads the `user-collections.*` entries of a cloud storage namespace file. Each entry is a
+/// `[key, { value, is_deleted, ... }]` pair whose `value` is the collection as a JSON string.
+pub(crate) fn parse_steam_cloud_collections(contents: &str) -> Result<Vec<SteamCloudCollection>, String> {
+    let namespace_value = serde_json::from_str::<serde_json::Value>(contents)
+        .map_err(|error| format!("Failed to parse Steam cloudstorage namespace JSON: {error}"))?;
+    let Some(namespace_entries) = namespace_value.as_array() else {
+        return Err(String::from("Steam cloudstorage namespace data must be a JSON array"));
+    };
+
+    let mut collections = Vec::new();
+    for namespace_entry in namespace_entries {
+        let Some([key, metadata, ..]) = namespace_entry.as_array().map(Vec::as_slice) else {
+            continue;
+        };
+        if !key
+            .as_str()
+            .is_some_and(|key| key.starts_with("user-collections."))
+        {
+            continue;
+        }
+        if metadata
+            .get("is_deleted")
+            .and_then(serde_json::Value::as_bool)
+            .unwrap_or(false)
+        {
+            continue;
+        }
+        let Some(collection_value) = metadata
+            .get("value")
+            .and_then(serde_json::Value::as_str)
+            .and_then(|value| serde_json::from_str::<serde_json::Value>(value).ok())
+        else {
+            continue;
+        };
+
+        let id = collection_value
+            .get("id")
+            .and_then(serde_json::Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        if RESERVED_COLLECTION_IDS.contains(&id.as_str()) {
+            continue;
+        }
+        // Named by locale in the cloud, but imported like the `favorite` tag of sharedconfig.vdf.
+        let name = if id == STEAM_FAVORITES_COLLECTION {
+            Some(String::from(STEAM_FAVORITES_COLLECTION))
+        } else {
+            collection_value
+                .get("name")
+                .and_then(serde_json::Value::as_str)
+                .and_then(parse_collection_name_candidate)
+        };
+        let Some(name) = name else {
+            continue;
+        };
+        collections.push(SteamCloudCollection {
+            id,
+            name,
+            added: parse_app_id_list(collection_value.get("added")),
+            removed: parse_app_id_list(collection_value.get("removed")).into_iter().collect(),
+            filter: collection_value
+                .get("filterSpec")
+                .filter(|filter_spec| filter_spec.is_object())
+                .map(parse_dynamic_collection_filter),
+        });
+    }
+    Ok(collections)
+}
+
+/// Resolves cloud collections into the `app ID -> collection names` shape the import uses.
+/// Dynamic collections are matched against `owned_game_names` (app ID, name); those with filters
+/// Catalyst can't evaluate are left out and counted in the second value.
+pub(crate) fn resolve_steam_cloud_collection_memberships(
+    collections: &[SteamCloudCollection],
+    owned_game_names: &[(String, String)],
+) -> (HashMap<String, HashSet<String>>, usize) {
+    let mut collections_by_app_id: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut skipped_dynamic_collections = 0usize;
+    for collection in collections {
+        let mut app_ids = collection
+            .added
+            .iter()
+            .map(u64::to_string)
+            .collect::<HashSet<String>>();
+        match &collection.filter {
+            Some(filter) if filter.has_unsupported_groups => {
+                skipped_dynamic_collections += 1;
+                continue;
+            }
+            Some(filter) => {
+                let search_text = filter.search_text.as_deref().map(str::to_lowercase);
+                app_ids.extend(
+                    owned_game_names
+                        .iter()
+                        .filter(|(_, name)| {
+                            search_text
+                                .as_deref()
+                                .map_or(true, |search_text| name.to_lowercase().contains(search_text))
+                        })
+                        .map(|(external_id, _)| external_id.clone()),
+                );
+            }
+            None => {}
+        }
+        for app_id in app_ids {
+            if app_id
+                .parse::<u64>()
+                .is_ok_and(|app_id| collection.removed.contains(&app_id))
+            {
+                continue;
+            }
+            collections_by_app_id
+                .entry(app_id)
+                .or_default()
+                .insert(collection.name.clone());
+        }
+    }
+    (collections_by_app_id, skipped_dynamic_collections)
+}
+
+pub(crate) fn load_steam_game_names(connection: &Connection, user_id: &str) -> Result<Vec<(String, String)>, String> {
+    let mut statement = connection
+        .prepare("SELECT external_id, name FROM games WHERE user_id = ?1 AND provider = 'steam'")
+        .map_err(|error| format!("Failed to prepare Steam game name query: {error}"))?;
+    let rows = statement
+        .query_map(params![user_id], |row| Ok((row.get(0)?, row.get(1)?)))
+        .map_err(|error| format!("Failed to query Steam game names: {error}"))?;
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode Steam game names: {error}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steam_cloud_collections_resolves_static_and_dynamic_collections() {
+        let collection = |value: serde_json::Value| {
+            serde_json::json!({ "key": "user-collections.x", "value": value.to_string(), "version": "3" })
+        };
+        let contents = serde_json::json!([
+            ["user-collections.uc-1", collection(serde_json::json!({
+                "id": "uc-1", "name": "Co-op Nights", "added": [620, 550], "removed": [550]
+            }))],
+            ["user-collections.uc-2", collection(serde_json::json!({
+                "id": "uc-2", "name": "Portals", "added": [], "removed": [],
+                "filterSpec": { "nFormatVersion": 2, "strSearchText": "portal", "filterGroups": [{ "rgOptions": [], "bAcceptUnion": false }] }
+            }))],
+            ["user-collections.uc-3", collection(serde_json::json!({
+                "id": "uc-3", "name": "Installed RPGs", "added": [], "removed": [],
+                "filterSpec": { "nFormatVersion": 2, "filterGroups": [{ "rgOptions": [1], "bAcceptUnion": false }] }
+            }))],
+            ["user-collections.uc-4", { "key": "user-collections.uc-4", "is_deleted": true }],
+            ["user-collections.hidden", collection(serde_json::json!({ "id": "hidden", "name": "Hidden", "added": [400] }))],
+            ["showcases.1", { "key": "showcases.1", "value": "{}" }]
+        ])
+        .to_string();
+
+        let collections = parse_steam_cloud_collections(&contents).expect("parse namespace");
+        assert_eq!(
+            collections.iter().map(|collection| collection.name.as_str()).collect::<Vec<_>>(),
+            vec!["Co-op Nights", "Portals", "Installed RPGs"]
+        );
+
+        let owned_game_names = vec![
+            (String::from("400"), String::from("Portal")),
+            (String::from("620"), String::from("Portal 2")),
+            (String::from("570"), String::from("Dota 2")),
+        ];
+        let (collections_by_app_id, skipped) =
+            resolve_steam_cloud_collection_memberships(&collections, &owned_game_names);
+        assert_eq!(skipped, 1);
+        assert_eq!(
+            collections_by_app_id.get("620"),
+            Some(&HashSet::from([String::from("Co-op Nights"), String::from("Portals")]))
+        );
+        assert_eq!(collections_by_app_id.get("400"), Some(&HashSet::from([String::from("Portals")])));
+        assert!(!collections_by_app_id.contains_key("550"));
+        assert!(!collections_by_app_id.contains_key("570"));
+    }
+}
@@ -0,0 +1,303 @@
+use crate::*;
+use once_cell::sync::OnceCell;
+use std::io::Write;
+
+const STEAM_CONFIG_BACKUP_LIMIT: usize = 10;
+const STEAM_CONFIG_BACKUP_ORIGINAL_PATH_FILE: &str = "original-path.txt";
+const PENDING_STEAM_CONFIG_WRITE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+static STEAM_CONFIG_BACKUP_DIRECTORY: OnceCell<PathBuf> = OnceCell::new();
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamConfigBackupResponse {
+    pub id: String,
+    pub original_path: String,
+    pub created_at: String,
+    pub size_bytes: u64,
+}
+
+/// Sets where backups of Steam config files are kept. Without it, files are still written
+/// atomically but no backups are taken.
+pub(crate) fn configure_steam_config_backups(directory: PathBuf) {
+    let _ = STEAM_CONFIG_BACKUP_DIRECTORY.set(directory);
+}
+
+/// Replaces a Steam config file without leaving it half-written: the new contents go to a
+/// temporary file in the same directory, are flushed to disk and then renamed over the
+/// original. The previous version is backed up first.
+pub(crate) fn write_steam_config_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    let path = path.as_ref();
+    if path.is_file() {
+        if let Some(backup_directory) = STEAM_CONFIG_BACKUP_DIRECTORY.get() {
+            backup_steam_config_file(backup_directory, path)?;
+        }
+    }
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Config path has no file name"))?;
+    let temp_path = path.with_file_name(format!(".{file_name}.catalyst-tmp"));
+    let write_result = (|| {
+        let mut temp_file = fs::File::create(&temp_path)?;
+        temp_file.write_all(contents.as_ref())?;
+        temp_file.sync_all()?;
+        fs::rename(&temp_path, path)
+    })();
+    if write_result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    write_result
+}
+
+/// Backups of one file live in a directory named after a hash of its path, so files with the
+/// same name from different Steam accounts stay apart.
+fn backup_directory_for(backup_root: &Path, path: &Path) -> PathBuf {
+    let digest = Sha256::digest(path.to_string_lossy().as_bytes());
+    let key = digest.iter().take(8).map(|byte| format!("{byte:02x}")).collect::<String>();
+    backup_root.join(key)
+}
+
+fn backup_steam_config_file(backup_root: &Path, path: &Path) -> std::io::Result<()> {
+    let directory = backup_directory_for(backup_root, path);
+    fs::create_dir_all(&directory)?;
+    fs::write(
+        directory.join(STEAM_CONFIG_BACKUP_ORIGINAL_PATH_FILE),
+        path.to_string_lossy().as_bytes(),
+    )?;
+    let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("config.vdf");
+    let timestamp = Utc::now().format("%Y%m%dT%H%M%S%.3fZ");
+    fs::copy(path, directory.join(format!("{timestamp}-{file_name}")))?;
+
+    let mut backups = list_backup_files(&directory)?;
+    while backups.len() > STEAM_CONFIG_BACKUP_LIMIT {
+        let oldest = backups.remove(0);
+        fs::remove_file(oldest)?;
+    }
+    Ok(())
+}
+
+/// Backup files of one config file, oldest first. The timestamp prefix sorts chronologically.
+fn list_backup_files(directory: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut backups = fs::read_dir(directory)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path.file_name().and_then(|name| name.to_str()) != Some(STEAM_CONFIG_BACKUP_ORIGINAL_PATH_FILE)
+        })
+        .collect::<Vec<_>>();
+    backups.sort();
+    Ok(backups)
+}
+
+fn backup_root() -> Result<&'static PathBuf, String> {
+    STEAM_CONFIG_BACKUP_DIRECTORY
+        .get()
+        .ok_or_else(|| String::from("Steam config backups are not configured"))
+}
+
+/// Lists backups of every Steam config file Catalyst has rewritten, newest first.
+pub(crate) fn list_steam_config_backups() -> Result<Vec<SteamConfigBackupResponse>, String> {
+    let backup_root = backup_root()?;
+    let Ok(directories) = fs::read_dir(backup_root) else {
+        return Ok(Vec::new());
+    };
+
+    let mut backups = Vec::new();
+    for directory in directories.filter_map(Result::ok).map(|entry| entry.path()) {
+        let Ok(original_path) = fs::read_to_string(directory.join(STEAM_CONFIG_BACKUP_ORIGINAL_PATH_FILE)) else {
+            continue;
+        };
+        let Some(directory_name) = directory.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        for backup_path in list_backup_files(&directory).unwrap_or_default() {
+            let Some(backup_name) = backup_path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let created_at = backup_name
+                .split_once('-')
+                .and_then(|(timestamp, _)| {
+                    chrono::NaiveDateTime::parse_from_str(timestamp, "%Y%m%dT%H%M%S%.3fZ").ok()
+                })
+                .map(|timestamp| timestamp.and_utc().to_rfc3339())
+                .unwrap_or_default();
+            backups.push(SteamConfigBackupResponse {
+                id: format!("{directory_name}/{backup_name}"),
+                original_path: original_path.clone(),
+                created_at,
+                size_bytes: fs::metadata(&backup_path).map(|metadata| metadata.len()).unwrap_or(0),
+            });
+        }
+    }
+    backups.sort_by(|left, right| right.created_at.cmp(&left.created_at));
+    Ok(backups)
+}
+
+/// Restores a backup over the file it was taken from. The current file is backed up first, so
+/// a restore can itself be undone.
+pub(crate) fn restore_steam_config_backup(backup_id: &str) -> Result<SteamConfigBackupResponse, String> {
+    let backup_root = backup_root()?;
+    let (directory_name, backup_name) = backup_id
+        .split_once('/')
+        .filter(|(directory_name, backup_name)| {
+            [directory_name, backup_name].iter().all(|part| {
+                !part.is_empty() && !part.contains(['/', '\\']) && **part != ".." && **part != "."
+            })
+        })
+        .ok_or_else(|| String::from("Invalid backup ID"))?;
+    let directory = backup_root.join(directory_name);
+    let backup_path = directory.join(backup_name);
+    if !backup_path.is_file() || backup_name == STEAM_CONFIG_BACKUP_ORIGINAL_PATH_FILE {
+        return Err(String::from("Backup not found"));
+    }
+    let original_path = fs::read_to_string(directory.join(STEAM_CONFIG_BACKUP_ORIGINAL_PATH_FILE))
+        .map_err(|error| format!("Failed to read backup metadata: {error}"))?;
+    let contents = fs::read(&backup_path).map_err(|error| format!("Failed to read backup: {error}"))?;
+    write_steam_config_file(&original_path, &contents)
+        .map_err(|error| format!("Failed to restore {original_path}: {error}"))?;
+
+    Ok(SteamConfigBackupResponse {
+        id: backup_id.to_owned(),
+        original_path,
+        created_at: Utc::now().to_rfc3339(),
+        size_bytes: contents.len() as u64,
+    })
+}
+
+/// Steam keeps localconfig.vdf in memory and rewrites it on exit, so changes written while it
+/// runs are lost. Such writes are queued and replayed from the stored settings once Steam exits.
+pub(crate) fn queue_pending_steam_config_write(
+    connection: &Connection,
+    user_id: &str,
+    app_id: u64,
+) -> Result<(), String> {
+    connection
+        .execute(
+            "
+            INSERT INTO pending_steam_config_writes (user_id, app_id, queued_at)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT(user_id, app_id) DO UPDATE SET queued_at = excluded.queued_at
+            ",
+            params![user_id, app_id.to_string(), Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to queue Steam config write: {error}"))?;
+    Ok(())
+}
+
+fn flush_pending_steam_config_writes(state: &AppState) -> Result<(), String> {
+    let connection = open_connection(&state.db_path)?;
+    let mut statement = connection
+        .prepare("SELECT user_id, app_id FROM pending_steam_config_writes ORDER BY queued_at ASC")
+        .map_err(|error| format!("Failed to prepare pending Steam config query: {error}"))?;
+    let pending = statement
+        .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))
+        .map_err(|error| format!("Failed to query pending Steam config writes: {error}"))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|error| format!("Failed to decode pending Steam config row: {error}"))?;
+    if pending.is_empty() || process_watcher::is_steam_client_running() {
+        return Ok(());
+    }
+
+    for (user_id, app_id) in pending {
+        let applied = match (find_user_by_id(&connection, &user_id)?, app_id.parse::<u64>()) {
+            (Some(user), Ok(numeric_app_id)) => {
+                let settings = load_game_properties_settings(&connection, &user_id, "steam", &app_id)?;
+                apply_steam_game_properties_settings(state, &user, numeric_app_id, &settings)
+            }
+            _ => Ok(()),
+        };
+        if let Err(error) = applied {
+            eprintln!("Could not apply queued Steam settings for app {app_id}: {error}");
+        }
+        connection
+            .execute(
+                "DELETE FROM pending_steam_config_writes WHERE user_id = ?1 AND app_id = ?2",
+                params![user_id, app_id],
+            )
+            .map_err(|error| format!("Failed to clear pending Steam config write: {error}"))?;
+    }
+    Ok(())
+}
+
+/// Replays queued game settings into Steam's config files after the Steam client exits.
+pub(crate) fn spawn_pending_steam_config_writer(app_handle: tauri::AppHandle) {
+    use tauri::Manager;
+
+    let _ = thread::Builder::new()
+        .name("steam-config-writer".into())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+            if let Err(error) = flush_pending_steam_config_writes(state.inner()) {
+                eprintln!("Failed to apply queued Steam config writes: {error}");
+            }
+            thread::sleep(PENDING_STEAM_CONFIG_WRITE_POLL_INTERVAL);
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_steam_config_file_backs_up_and_restores() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        configure_steam_config_backups(dir.path().join("backups"));
+        let config_path = dir.path().join("localconfig.vdf");
+        fs::write(&config_path, "\"UserLocalConfigStore\" { }").expect("seed config");
+
+        write_steam_config_file(&config_path, "\"UserLocalConfigStore\" { \"a\" \"1\" }").expect("write config");
+
+        let backups = list_steam_config_backups().expect("list backups");
+        let backup = backups
+            .iter()
+            .find(|backup| backup.original_path == config_path.to_string_lossy())
+            .expect("backup should exist");
+        restore_steam_config_backup(&backup.id).expect("restore backup");
+        assert_eq!(
+            fs::read_to_string(&config_path).expect("read config"),
+            "\"UserLocalConfigStore\" { }"
+        );
+        assert!(restore_steam_config_backup("../escape").is_err());
+    }
+
+    #[test]
+    fn backup_steam_config_file_keeps_only_the_newest_backups() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let backup_root = dir.path().join("backups");
+        let config_path = dir.path().join("localconfig.vdf");
+        for revision in 0..STEAM_CONFIG_BACKUP_LIMIT + 2 {
+            fs::write(&config_path, format!("revision {revision}")).expect("write config");
+            backup_steam_config_file(&backup_root, &config_path).expect("back up config");
+            // Backup names carry millisecond timestamps.
+            thread::sleep(Duration::from_millis(2));
+        }
+
+        let backups = list_backup_files(&backup_directory_for(&backup_root, &config_path)).expect("list backups");
+        assert_eq!(backups.len(), STEAM_CONFIG_BACKUP_LIMIT);
+        assert_eq!(fs::read_to_string(&backups[0]).expect("read oldest"), "revision 2");
+        assert_eq!(
+            fs::read_to_string(backups.last().expect("newest")).expect("read newest"),
+            format!("revision {}", STEAM_CONFIG_BACKUP_LIMIT + 1)
+        );
+    }
+
+    #[test]
+    fn write_steam_config_file_leaves_no_partial_file_behind() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let missing_directory_path = dir.path().join("missing").join("localconfig.vdf");
+        assert!(write_steam_config_file(&missing_directory_path, "contents").is_err());
+        assert!(!missing_directory_path.exists());
+
+        // A directory in the file's place makes the final rename fail; the temp file is removed.
+        let config_path = dir.path().join("localconfig.vdf");
+        fs::create_dir(&config_path).expect("create blocking directory");
+        assert!(write_steam_config_file(&config_path, "contents").is_err());
+        assert_eq!(fs::read_dir(dir.path()).expect("list dir").count(), 1);
+
+        let error = write_steam_config_file(Path::new("/"), "contents").expect_err("no file name");
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,228 @@
+use crate::*;
+
+/// Set in `StateFlags` once an app is fully installed.
+const STEAM_STATE_FLAG_FULLY_INSTALLED: u64 = 4;
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamManifestCopyResponse {
+    pub manifest_path: String,
+    pub library_path: String,
+    pub size_on_disk_bytes: Option<u64>,
+    pub last_updated_at: Option<String>,
+    /// The copy Steam launches and updates; the others are leftovers.
+    pub active: bool,
+}
+
+/// An app whose manifest exists in more than one Steam library folder, e.g. after moving a
+/// drive without letting Steam clean up the old library.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamLibraryConflictResponse {
+    pub app_id: u64,
+    pub name: Option<String>,
+    pub copies: Vec<SteamManifestCopyResponse>,
+}
+
+#[derive(Debug)]
+struct SteamManifestCandidate {
+    manifest_path: PathBuf,
+    listed_in_library_folders: bool,
+    fully_installed: bool,
+    last_updated: Option<u64>,
+    size_on_disk_bytes: Option<u64>,
+    name: Option<String>,
+}
+
+/// Reads the `apps` block Steam keeps per library in `libraryfolders.vdf`, which names the library
+/// each app is installed to, keyed by the library's `steamapps` directory.
+fn parse_library_folder_app_ids(contents: &str) -> Result<HashMap<PathBuf, HashSet<u64>>, String> {
+    let document = parse_vdf_document(contents)?;
+    let Some(VdfValue::Object(folders)) = vdf_find_object_value(&document, "libraryfolders") else {
+        return Ok(HashMap::new());
+    };
+    Ok(folders
+        .iter()
+        .filter_map(|(_, folder)| {
+            let library_path = vdf_get_text_entry(folder, "path")?.trim();
+            let app_ids = match vdf_find_object_value(folder, "apps") {
+                Some(VdfValue::Object(apps)) => apps
+                    .iter()
+                    .filter_map(|(app_id, _)| app_id.trim().parse::<u64>().ok())
+                    .collect(),
+                _ => HashSet::new(),
+            };
+            (!library_path.is_empty()).then(|| (Path::new(library_path).join("steamapps"), app_ids))
+        })
+        .collect())
+}
+
+fn read_library_folder_app_ids(steam_root: &Path) -> HashMap<PathBuf, HashSet<u64>> {
+    let library_folders_path = steam_root.join("steamapps").join("libraryfolders.vdf");
+    let Ok(contents) = fs::read_to_string(&library_folders_path) else {
+        return HashMap::new();
+    };
+    match parse_library_folder_app_ids(&contents) {
+        Ok(app_ids) => app_ids
+            .into_iter()
+            .map(|(steamapps_directory, app_ids)| (steam_path_dedupe_key(&steamapps_directory), app_ids))
+            .collect(),
+        Err(error) => {
+            eprintln!("Could not parse {}: {error}", library_folders_path.display());
+            HashMap::new()
+        }
+    }
+}
+
+fn collect_steam_manifest_candidates(steam_root_override: Option<&str>) -> BTreeMap<u64, Vec<SteamManifestCandidate>> {
+    let mut candidates = BTreeMap::<u64, Vec<SteamManifestCandidate>>::new();
+    let mut seen_directories = HashSet::new();
+    for steam_root in resolve_steam_root_paths(steam_root_override) {
+        let library_app_ids = read_library_folder_app_ids(&steam_root);
+        let Ok(steamapps_directories) = resolve_steamapps_directories(&steam_root) else {
+            continue;
+        };
+        for steamapps_directory in steamapps_directories {
+            let normalized_directory = steam_path_dedupe_key(&steamapps_directory);
+            if !seen_directories.insert(normalized_directory.clone()) {
+                continue;
+            }
+            let Ok(entries) = fs::read_dir(&steamapps_directory) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let Some(app_id) = parse_steam_manifest_app_id(&entry.file_name().to_string_lossy()) else {
+                    continue;
+                };
+                let Ok(manifest_contents) = fs::read_to_string(entry.path()) else {
+                    continue;
+                };
+                candidates.entry(app_id).or_default().push(SteamManifestCandidate {
+                    manifest_path: entry.path(),
+                    listed_in_library_folders: library_app_ids
+                        .get(&normalized_directory)
+                        .is_some_and(|app_ids| app_ids.contains(&app_id)),
+                    fully_installed: parse_steam_manifest_u64_field(&manifest_contents, "StateFlags")
+                        .is_some_and(|flags| flags & STEAM_STATE_FLAG_FULLY_INSTALLED != 0),
+                    last_updated: parse_steam_manifest_u64_field(&manifest_contents, "LastUpdated"),
+                    size_on_disk_bytes: parse_steam_manifest_size_on_disk_bytes(&manifest_contents),
+                    name: parse_steam_manifest_string_field(&manifest_contents, "name"),
+                });
+            }
+        }
+    }
+    candidates
+}
+
+/// Picks the manifest Steam treats as the real install: the library `libraryfolders.vdf` assigns
+/// the app to, then a fully installed copy, then the most recently updated one.
+fn select_active_manifest(candidates: &[SteamManifestCandidate]) -> Option<usize> {
+    candidates
+        .iter()
+        .enumerate()
+        .max_by_key(|(index, candidate)| {
+            (
+                candidate.listed_in_library_folders,
+                candidate.fully_installed,
+                candidate.last_updated.unwrap_or(0),
+                std::cmp::Reverse(*index),
+            )
+        })
+        .map(|(index, _)| index)
+}
+
+/// Manifests that duplicate an app installed in another library. Scans that walk every library
+/// skip these so the app is only counted once.
+pub(crate) fn collect_inactive_duplicate_manifest_paths(steam_root_override: Option<&str>) -> HashSet<PathBuf> {
+    collect_steam_manifest_candidates(steam_root_override)
+        .into_values()
+        .filter(|candidates| candidates.len() > 1)
+        .flat_map(|candidates| {
+            let active_index = select_active_manifest(&candidates);
+            candidates
+                .into_iter()
+                .enumerate()
+                .filter(move |(index, _)| Some(*index) != active_index)
+                .map(|(_, candidate)| candidate.manifest_path)
+        })
+        .collect()
+}
+
+pub(crate) fn find_active_steam_manifest_path(steam_root_override: Option<&str>, app_id: u64) -> Option<PathBuf> {
+    let mut candidates = collect_steam_manifest_candidates(steam_root_override).remove(&app_id)?;
+    let active_index = select_active_manifest(&candidates)?;
+    Some(candidates.swap_remove(active_index).manifest_path)
+}
+
+pub(crate) fn collect_steam_library_conflicts(steam_root_override: Option<&str>) -> Vec<SteamLibraryConflictResponse> {
+    collect_steam_manifest_candidates(steam_root_override)
+        .into_iter()
+        .filter(|(_, candidates)| candidates.len() > 1)
+        .map(|(app_id, candidates)| {
+            let active_index = select_active_manifest(&candidates);
+            let name = candidates.iter().find_map(|candidate| candidate.name.clone());
+            let copies = candidates
+                .into_iter()
+                .enumerate()
+                .map(|(index, candidate)| SteamManifestCopyResponse {
+                    library_path: candidate
+                        .manifest_path
+                        .parent()
+                        .and_then(Path::parent)
+                        .map(|library_path| library_path.display().to_string())
+                        .unwrap_or_default(),
+                    manifest_path: candidate.manifest_path.display().to_string(),
+                    size_on_disk_bytes: candidate.size_on_disk_bytes,
+                    last_updated_at: candidate
+                        .last_updated
+                        .and_then(|seconds| Utc.timestamp_opt(seconds as i64, 0).single())
+                        .map(|updated_at| updated_at.to_rfc3339()),
+                    active: Some(index) == active_index,
+                })
+                .collect();
+            SteamLibraryConflictResponse { app_id, name, copies }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(path: &str, listed: bool, fully_installed: bool, last_updated: u64) -> SteamManifestCandidate {
+        SteamManifestCandidate {
+            manifest_path: PathBuf::from(path),
+            listed_in_library_folders: listed,
+            fully_installed,
+            last_updated: Some(last_updated),
+            size_on_disk_bytes: None,
+            name: None,
+        }
+    }
+
+    #[test]
+    fn select_active_manifest_prefers_library_folders_assignment() {
+        let app_ids = parse_library_folder_app_ids(
+            r#""libraryfolders"
+{
+    "0" { "path" "/home/user/.steam/steam" "apps" { "228980" "1024" } }
+    "1" { "path" "/mnt/games" "apps" { "620" "2048" } }
+}"#,
+        )
+        .expect("parse library folders");
+        assert!(app_ids[Path::new("/mnt/games/steamapps")].contains(&620));
+
+        let candidates = [
+            candidate("/old/steamapps/appmanifest_620.acf", false, true, 1_700_000_000),
+            candidate("/mnt/games/steamapps/appmanifest_620.acf", true, true, 1_600_000_000),
+        ];
+        assert_eq!(select_active_manifest(&candidates), Some(1));
+
+        let unlisted = [
+            candidate("/a/steamapps/appmanifest_620.acf", false, false, 1_700_000_000),
+            candidate("/b/steamapps/appmanifest_620.acf", false, true, 1_600_000_000),
+            candidate("/c/steamapps/appmanifest_620.acf", false, true, 1_650_000_000),
+        ];
+        assert_eq!(select_active_manifest(&unlisted), Some(2));
+    }
+}
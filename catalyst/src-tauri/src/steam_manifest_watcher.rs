@@ -0,0 +1,274 @@
+use crate::*;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::sync::mpsc;
+use tauri::Emitter;
+
+pub(crate) const STEAM_LIBRARY_CHANGED_EVENT: &str = "steam-library-changed";
+/// Steam rewrites a manifest several times while it starts or finishes a transfer, so events
+/// are batched for this long before rescanning.
+const MANIFEST_EVENT_DEBOUNCE: Duration = Duration::from_millis(300);
+/// How often the watched libraries are re-resolved, picking up new library folders and a
+/// changed Steam root override.
+const WATCHED_LIBRARIES_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Default, Debug, PartialEq)]
+pub(crate) struct SteamManifestSnapshot {
+    pub installed_app_ids: HashSet<u64>,
+    /// Apps Steam is transferring, by manifest state or a `downloading/<appid>` folder.
+    pub downloading_app_ids: HashSet<u64>,
+    /// Installed apps whose manifest mentions OpenVR, for titles missing VR store categories.
+    pub openvr_app_ids: HashSet<u64>,
+}
+
+/// What the manifest watcher last saw in the Steam libraries. Empty until the first scan, and
+/// in the CLI, where callers scan on demand instead.
+#[derive(Default)]
+pub(crate) struct SteamManifestIndex {
+    snapshot: Mutex<Option<SteamManifestSnapshot>>,
+}
+
+impl SteamManifestIndex {
+    /// `Some(false)` lets download polling skip reading every manifest while nothing transfers.
+    pub(crate) fn has_active_downloads(&self) -> Option<bool> {
+        self.snapshot
+            .lock()
+            .ok()
+            .and_then(|snapshot| snapshot.as_ref().map(|snapshot| !snapshot.downloading_app_ids.is_empty()))
+    }
+
+    /// Returns whether the snapshot changed.
+    fn replace(&self, next_snapshot: SteamManifestSnapshot) -> bool {
+        let Ok(mut snapshot) = self.snapshot.lock() else {
+            return false;
+        };
+        if snapshot.as_ref() == Some(&next_snapshot) {
+            return false;
+        }
+        *snapshot = Some(next_snapshot);
+        true
+    }
+}
+
+fn resolve_watched_steamapps_directories(steam_root_override: Option<&str>) -> Vec<PathBuf> {
+    let mut seen_directories = HashSet::new();
+    resolve_steam_root_paths(steam_root_override)
+        .iter()
+        .filter_map(|steam_root| resolve_steamapps_directories(steam_root).ok())
+        .flatten()
+        .filter(|steamapps_directory| steamapps_directory.is_dir())
+        .filter(|steamapps_directory| seen_directories.insert(steam_path_dedupe_key(steamapps_directory)))
+        .collect()
+}
+
+fn scan_steamapps_directories(steamapps_directories: &[PathBuf]) -> SteamManifestSnapshot {
+    let mut snapshot = SteamManifestSnapshot::default();
+    for steamapps_directory in steamapps_directories {
+        if let Err(error) =
+            collect_installed_app_ids_from_steamapps_dir(steamapps_directory, &mut snapshot.installed_app_ids)
+        {
+            eprintln!("Could not collect installed Steam app IDs from {}: {error}", steamapps_directory.display());
+        }
+        let Ok(entries) = fs::read_dir(steamapps_directory) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Some(app_id) = parse_steam_manifest_app_id(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+            let contents = fs::read_to_string(entry.path()).unwrap_or_default();
+            let state_flags = parse_steam_manifest_u64_field(&contents, "StateFlags").unwrap_or(0);
+            if manifest_mentions_openvr(&contents) {
+                snapshot.openvr_app_ids.insert(app_id);
+            }
+            let has_download_directory = steamapps_directory.join("downloading").join(app_id.to_string()).is_dir();
+            if has_download_directory
+                || state_flags & (STEAM_APP_STATE_DOWNLOADING | STEAM_APP_STATE_PREALLOCATING) != 0
+            {
+                snapshot.downloading_app_ids.insert(app_id);
+            }
+        }
+    }
+    snapshot
+}
+
+/// Steam records the launch and depot configuration it installed in the manifest; VR titles
+/// carry an `openvr` entry there, e.g. a `"vrmode" "openvr"` launch config.
+fn manifest_mentions_openvr(contents: &str) -> bool {
+    contents.to_ascii_lowercase().contains("openvr")
+}
+
+fn replace_steam_local_vr_apps(connection: &Connection, openvr_app_ids: &HashSet<u64>) -> Result<(), String> {
+    let transaction = connection
+        .unchecked_transaction()
+        .map_err(|error| format!("Failed to start local VR app update: {error}"))?;
+    transaction
+        .execute("DELETE FROM steam_local_vr_apps", [])
+        .map_err(|error| format!("Failed to clear local VR apps: {error}"))?;
+    for app_id in openvr_app_ids {
+        transaction
+            .execute(
+                "INSERT INTO steam_local_vr_apps (app_id) VALUES (?1)",
+                params![app_id.to_string()],
+            )
+            .map_err(|error| format!("Failed to store local VR app: {error}"))?;
+    }
+    transaction
+        .commit()
+        .map_err(|error| format!("Failed to commit local VR apps: {error}"))
+}
+
+/// Only manifests, the library list and per-app download folders affect the snapshot; Steam
+/// also churns through `temp`, `shadercache` and friends.
+fn is_relevant_manifest_event(event: &notify::Event) -> bool {
+    event.paths.iter().any(|path| {
+        let file_name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+        file_name.starts_with("appmanifest_")
+            || file_name == "libraryfolders.vdf"
+            || path
+                .parent()
+                .and_then(Path::file_name)
+                .is_some_and(|parent| parent == "downloading")
+    })
+}
+
+fn watch_steamapps_directories(watcher: &mut RecommendedWatcher, steamapps_directories: &[PathBuf]) -> Vec<PathBuf> {
+    let mut watched_paths = Vec::new();
+    for steamapps_directory in steamapps_directories {
+        for path in [steamapps_directory.clone(), steamapps_directory.join("downloading")] {
+            if path.is_dir() && watcher.watch(&path, RecursiveMode::NonRecursive).is_ok() {
+                watched_paths.push(path);
+            }
+        }
+    }
+    watched_paths
+}
+
+/// Applies a changed snapshot: install flags of the signed-in user's Steam games and the
+/// locally detected VR apps follow it, and the frontend is told to reload.
+fn publish_snapshot(app_handle: &tauri::AppHandle, snapshot: SteamManifestSnapshot) {
+    let state = app_handle.state::<AppState>();
+    let installed_app_ids = snapshot.installed_app_ids.clone();
+    let openvr_app_ids = snapshot.openvr_app_ids.clone();
+    if !state.steam_manifest_index.replace(snapshot) {
+        return;
+    }
+    if let Err(error) = open_connection(&state.db_path)
+        .and_then(|connection| replace_steam_local_vr_apps(&connection, &openvr_app_ids))
+    {
+        eprintln!("Could not refresh local Steam VR apps: {error}");
+    }
+    let _ = serde_json::to_value(installed_app_ids.iter().copied().collect::<Vec<u64>>())
+        .map(|value| cache::set_cached("local_installed_app_ids", value));
+    if state.steam_local_install_detection() {
+        let result = open_connection(&state.db_path).and_then(|connection| {
+            match get_authenticated_user(state.inner(), &connection) {
+                Ok(user) => refresh_provider_installed_flags(&connection, &user.id, "steam", &installed_app_ids),
+                // Nobody is signed in; the flags are refreshed on the next sync instead.
+                Err(_) => Ok(()),
+            }
+        });
+        if let Err(error) = result {
+            eprintln!("Could not refresh Steam install flags: {error}");
+        }
+    }
+    let _ = app_handle.emit(STEAM_LIBRARY_CHANGED_EVENT, ());
+}
+
+/// Watches every Steam library's `steamapps` directory and keeps `AppState`'s manifest index
+/// current, so install flags and download polling don't have to rescan on demand.
+pub(crate) fn spawn_steam_manifest_watcher(app_handle: tauri::AppHandle) {
+    let _ = thread::Builder::new()
+        .name("steam-manifest-watcher".into())
+        .spawn(move || {
+            let (sender, receiver) = mpsc::channel::<notify::Result<notify::Event>>();
+            let mut watcher = match notify::recommended_watcher(sender) {
+                Ok(watcher) => watcher,
+                Err(error) => {
+                    eprintln!("Steam manifest watcher unavailable: {error}");
+                    return;
+                }
+            };
+            let mut steamapps_directories = Vec::new();
+            let mut watched_paths = Vec::new();
+            let mut libraries_refreshed_at: Option<Instant> = None;
+            let mut rescan_at = Some(Instant::now());
+            loop {
+                if libraries_refreshed_at.map_or(true, |refreshed_at| {
+                    refreshed_at.elapsed() >= WATCHED_LIBRARIES_REFRESH_INTERVAL
+                }) {
+                    let steam_root_override = app_handle.state::<AppState>().steam_root_override();
+                    let next_directories = resolve_watched_steamapps_directories(steam_root_override.as_deref());
+                    if next_directories != steamapps_directories {
+                        for path in watched_paths.drain(..) {
+                            let _ = watcher.unwatch(&path);
+                        }
+                        watched_paths = watch_steamapps_directories(&mut watcher, &next_directories);
+                        steamapps_directories = next_directories;
+                        rescan_at.get_or_insert_with(Instant::now);
+                    }
+                    libraries_refreshed_at = Some(Instant::now());
+                }
+
+                let timeout = rescan_at
+                    .map(|rescan_at| rescan_at.saturating_duration_since(Instant::now()))
+                    .unwrap_or(WATCHED_LIBRARIES_REFRESH_INTERVAL);
+                match receiver.recv_timeout(timeout) {
+                    Ok(Ok(event)) if is_relevant_manifest_event(&event) => {
+                        rescan_at.get_or_insert_with(|| Instant::now() + MANIFEST_EVENT_DEBOUNCE);
+                        // Library folders can change with the list itself.
+                        if event
+                            .paths
+                            .iter()
+                            .any(|path| path.file_name().is_some_and(|name| name == "libraryfolders.vdf"))
+                        {
+                            libraries_refreshed_at = None;
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(mpsc::RecvTimeoutError::Timeout) => {}
+                    Err(mpsc::RecvTimeoutError::Disconnected) => return,
+                }
+
+                if rescan_at.is_some_and(|rescan_at| Instant::now() >= rescan_at) {
+                    rescan_at = None;
+                    publish_snapshot(&app_handle, scan_steamapps_directories(&steamapps_directories));
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_steamapps_directories_tracks_installed_and_downloading_apps() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let steamapps = dir.path().join("steamapps");
+        fs::create_dir_all(steamapps.join("common").join("Portal").join("bin")).expect("create install dir");
+        fs::create_dir_all(steamapps.join("downloading").join("570")).expect("create download dir");
+        fs::write(
+            steamapps.join("appmanifest_400.acf"),
+            "\"AppState\"\n{\n\t\"appid\"\t\"400\"\n\t\"StateFlags\"\t\"4\"\n\t\"installdir\"\t\"Portal\"\n}\n",
+        )
+        .expect("write installed manifest");
+        fs::create_dir_all(steamapps.join("common").join("Half-Life Alyx").join("bin")).expect("create vr install dir");
+        fs::write(
+            steamapps.join("appmanifest_546560.acf"),
+            "\"AppState\"\n{\n\t\"appid\"\t\"546560\"\n\t\"StateFlags\"\t\"4\"\n\t\"installdir\"\t\"Half-Life Alyx\"\n\t\"UserConfig\"\n\t{\n\t\t\"vrmode\"\t\"openvr\"\n\t}\n}\n",
+        )
+        .expect("write vr manifest");
+        fs::write(
+            steamapps.join("appmanifest_570.acf"),
+            "\"AppState\"\n{\n\t\"appid\"\t\"570\"\n\t\"StateFlags\"\t\"1026\"\n\t\"installdir\"\t\"dota 2 beta\"\n}\n",
+        )
+        .expect("write downloading manifest");
+
+        let snapshot = scan_steamapps_directories(&[steamapps]);
+
+        assert!(snapshot.installed_app_ids.contains(&400));
+        assert!(!snapshot.installed_app_ids.contains(&570));
+        assert_eq!(snapshot.downloading_app_ids, HashSet::from([570]));
+        assert_eq!(snapshot.openvr_app_ids, HashSet::from([546560]));
+    }
+}
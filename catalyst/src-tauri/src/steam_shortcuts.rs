@@ -0,0 +1,353 @@
+use crate::*;
+
+const BINARY_VDF_TYPE_MAP: u8 = 0x00;
+const BINARY_VDF_TYPE_STRING: u8 = 0x01;
+const BINARY_VDF_TYPE_INT32: u8 = 0x02;
+const BINARY_VDF_TYPE_UINT64: u8 = 0x07;
+const BINARY_VDF_TYPE_MAP_END: u8 = 0x08;
+const STEAM_SHORTCUTS_ROOT_KEY: &str = "shortcuts";
+const STEAM_SHORTCUT_APP_ID_FLAG: u32 = 0x8000_0000;
+
+/// A node of Steam's binary KeyValues format as used by `shortcuts.vdf` and `appinfo.vdf`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum BinaryVdfValue {
+    Map(Vec<(String, BinaryVdfValue)>),
+    String(String),
+    Int32(i32),
+    UInt64(u64),
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamShortcutResponse {
+    pub app_id: u32,
+    pub shortcuts_path: String,
+    pub created: bool,
+    pub grid_images: usize,
+}
+
+/// What a Steam shortcut should launch. `launch_options` is passed verbatim to Steam.
+pub(crate) struct SteamShortcutTarget {
+    pub name: String,
+    pub executable_path: String,
+    pub start_directory: String,
+    pub launch_options: String,
+    pub icon_path: Option<String>,
+}
+
+pub(crate) fn parse_binary_vdf(bytes: &[u8]) -> Result<Vec<(String, BinaryVdfValue)>, String> {
+    let mut cursor = 0;
+    let entries = parse_binary_vdf_map(bytes, &mut cursor, None)?;
+    Ok(entries)
+}
+
+/// Parses binary KeyValues whose keys are `u32` indices into `key_table`, as in appinfo.vdf
+/// v29 and later.
+pub(crate) fn parse_binary_vdf_with_key_table(
+    bytes: &[u8],
+    key_table: &[String],
+) -> Result<Vec<(String, BinaryVdfValue)>, String> {
+    let mut cursor = 0;
+    parse_binary_vdf_map(bytes, &mut cursor, Some(key_table))
+}
+
+fn parse_binary_vdf_map(
+    bytes: &[u8],
+    cursor: &mut usize,
+    key_table: Option<&[String]>,
+) -> Result<Vec<(String, BinaryVdfValue)>, String> {
+    let mut entries = Vec::new();
+    loop {
+        let Some(&value_type) = bytes.get(*cursor) else {
+            // Top-level maps in Steam's files may omit the final terminator.
+            return Ok(entries);
+        };
+        *cursor += 1;
+        if value_type == BINARY_VDF_TYPE_MAP_END {
+            return Ok(entries);
+        }
+
+        let key = match key_table {
+            Some(key_table) => {
+                let index = u32::from_le_bytes(read_binary_vdf_bytes::<4>(bytes, cursor)?) as usize;
+                key_table
+                    .get(index)
+                    .cloned()
+                    .ok_or_else(|| format!("Binary VDF key index {index} is out of range"))?
+            }
+            None => read_binary_vdf_string(bytes, cursor)?,
+        };
+        let value = match value_type {
+            BINARY_VDF_TYPE_MAP => BinaryVdfValue::Map(parse_binary_vdf_map(bytes, cursor, key_table)?),
+            BINARY_VDF_TYPE_STRING => BinaryVdfValue::String(read_binary_vdf_string(bytes, cursor)?),
+            BINARY_VDF_TYPE_INT32 => {
+                let raw = read_binary_vdf_bytes::<4>(bytes, cursor)?;
+                BinaryVdfValue::Int32(i32::from_le_bytes(raw))
+            }
+            BINARY_VDF_TYPE_UINT64 => {
+                let raw = read_binary_vdf_bytes::<8>(bytes, cursor)?;
+                BinaryVdfValue::UInt64(u64::from_le_bytes(raw))
+            }
+            other => {
+                return Err(format!(
+                    "Unsupported binary VDF value type 0x{other:02x} for key {key}"
+                ))
+            }
+        };
+        entries.push((key, value));
+    }
+}
+
+pub(crate) fn read_binary_vdf_string(bytes: &[u8], cursor: &mut usize) -> Result<String, String> {
+    let remaining = bytes.get(*cursor..).unwrap_or_default();
+    let terminator = remaining
+        .iter()
+        .position(|byte| *byte == 0)
+        .ok_or_else(|| String::from("Binary VDF string is not terminated"))?;
+    let value = String::from_utf8_lossy(&remaining[..terminator]).into_owned();
+    *cursor += terminator + 1;
+    Ok(value)
+}
+
+pub(crate) fn read_binary_vdf_bytes<const N: usize>(bytes: &[u8], cursor: &mut usize) -> Result<[u8; N], String> {
+    let raw = bytes
+        .get(*cursor..*cursor + N)
+        .ok_or_else(|| String::from("Binary VDF value is truncated"))?;
+    *cursor += N;
+    let mut value = [0_u8; N];
+    value.copy_from_slice(raw);
+    Ok(value)
+}
+
+pub(crate) fn write_binary_vdf(entries: &[(String, BinaryVdfValue)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_binary_vdf_map(entries, &mut bytes);
+    bytes.push(BINARY_VDF_TYPE_MAP_END);
+    bytes
+}
+
+fn write_binary_vdf_map(entries: &[(String, BinaryVdfValue)], bytes: &mut Vec<u8>) {
+    for (key, value) in entries {
+        let value_type = match value {
+            BinaryVdfValue::Map(_) => BINARY_VDF_TYPE_MAP,
+            BinaryVdfValue::String(_) => BINARY_VDF_TYPE_STRING,
+            BinaryVdfValue::Int32(_) => BINARY_VDF_TYPE_INT32,
+            BinaryVdfValue::UInt64(_) => BINARY_VDF_TYPE_UINT64,
+        };
+        bytes.push(value_type);
+        bytes.extend_from_slice(key.as_bytes());
+        bytes.push(0);
+        match value {
+            BinaryVdfValue::Map(children) => {
+                write_binary_vdf_map(children, bytes);
+                bytes.push(BINARY_VDF_TYPE_MAP_END);
+            }
+            BinaryVdfValue::String(text) => {
+                bytes.extend_from_slice(text.as_bytes());
+                bytes.push(0);
+            }
+            BinaryVdfValue::Int32(number) => bytes.extend_from_slice(&number.to_le_bytes()),
+            BinaryVdfValue::UInt64(number) => bytes.extend_from_slice(&number.to_le_bytes()),
+        }
+    }
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFF_u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Steam derives non-Steam shortcut IDs from the quoted executable and the app name; grid
+/// artwork files in `userdata/<id>/config/grid` are keyed by this ID.
+pub(crate) fn steam_shortcut_app_id(quoted_executable: &str, name: &str) -> u32 {
+    crc32(format!("{quoted_executable}{name}").as_bytes()) | STEAM_SHORTCUT_APP_ID_FLAG
+}
+
+fn quote_shortcut_path(path: &str) -> String {
+    format!("\"{}\"", path.trim_matches('"'))
+}
+
+fn build_shortcut_entry(app_id: u32, target: &SteamShortcutTarget) -> Vec<(String, BinaryVdfValue)> {
+    let string = |value: &str| BinaryVdfValue::String(value.to_owned());
+    vec![
+        (String::from("appid"), BinaryVdfValue::Int32(app_id as i32)),
+        (String::from("AppName"), string(&target.name)),
+        (String::from("Exe"), string(&quote_shortcut_path(&target.executable_path))),
+        (String::from("StartDir"), string(&quote_shortcut_path(&target.start_directory))),
+        (String::from("icon"), string(target.icon_path.as_deref().unwrap_or_default())),
+        (String::from("ShortcutPath"), string("")),
+        (String::from("LaunchOptions"), string(&target.launch_options)),
+        (String::from("IsHidden"), BinaryVdfValue::Int32(0)),
+        (String::from("AllowDesktopConfig"), BinaryVdfValue::Int32(1)),
+        (String::from("AllowOverlay"), BinaryVdfValue::Int32(1)),
+        (String::from("OpenVR"), BinaryVdfValue::Int32(0)),
+        (String::from("Devkit"), BinaryVdfValue::Int32(0)),
+        (String::from("DevkitGameID"), string("")),
+        (String::from("DevkitOverrideAppID"), BinaryVdfValue::Int32(0)),
+        (String::from("LastPlayTime"), BinaryVdfValue::Int32(0)),
+        (String::from("FlatpakAppID"), string("")),
+        (String::from("tags"), BinaryVdfValue::Map(vec![(String::from("0"), string("Catalyst"))])),
+    ]
+}
+
+fn shortcut_entry_app_id(entry: &BinaryVdfValue) -> Option<u32> {
+    let BinaryVdfValue::Map(fields) = entry else {
+        return None;
+    };
+    fields.iter().find_map(|(key, value)| match value {
+        BinaryVdfValue::Int32(app_id) if key.eq_ignore_ascii_case("appid") => Some(*app_id as u32),
+        _ => None,
+    })
+}
+
+/// Inserts or replaces the shortcut for `target` in the parsed `shortcuts.vdf` document and
+/// returns its app ID together with whether a new entry was appended.
+pub(crate) fn upsert_steam_shortcut(
+    document: &mut Vec<(String, BinaryVdfValue)>,
+    target: &SteamShortcutTarget,
+) -> (u32, bool) {
+    let app_id = steam_shortcut_app_id(&quote_shortcut_path(&target.executable_path), &target.name);
+    let shortcut_entry = BinaryVdfValue::Map(build_shortcut_entry(app_id, target));
+
+    if !document
+        .iter()
+        .any(|(key, _)| key.eq_ignore_ascii_case(STEAM_SHORTCUTS_ROOT_KEY))
+    {
+        document.push((String::from(STEAM_SHORTCUTS_ROOT_KEY), BinaryVdfValue::Map(Vec::new())));
+    }
+    let shortcuts = document
+        .iter_mut()
+        .find_map(|(key, value)| match value {
+            BinaryVdfValue::Map(entries) if key.eq_ignore_ascii_case(STEAM_SHORTCUTS_ROOT_KEY) => Some(entries),
+            _ => None,
+        });
+    let Some(shortcuts) = shortcuts else {
+        // The root key exists but is not a map; replace it with a fresh shortcuts list.
+        document.retain(|(key, _)| !key.eq_ignore_ascii_case(STEAM_SHORTCUTS_ROOT_KEY));
+        document.push((
+            String::from(STEAM_SHORTCUTS_ROOT_KEY),
+            BinaryVdfValue::Map(vec![(String::from("0"), shortcut_entry)]),
+        ));
+        return (app_id, true);
+    };
+
+    if let Some((_, existing_entry)) = shortcuts
+        .iter_mut()
+        .find(|(_, entry)| shortcut_entry_app_id(entry) == Some(app_id))
+    {
+        *existing_entry = shortcut_entry;
+        return (app_id, false);
+    }
+
+    let next_index = shortcuts
+        .iter()
+        .filter_map(|(key, _)| key.parse::<usize>().ok())
+        .max()
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    shortcuts.push((next_index.to_string(), shortcut_entry));
+    (app_id, true)
+}
+
+/// Adds `target` to the user's `shortcuts.vdf` and copies any supplied grid artwork. Steam only
+/// reads the file on startup and rewrites it on exit, so changes show up after a Steam restart.
+pub(crate) fn add_steam_shortcut(
+    steam_root_override: Option<&str>,
+    steam_id: &str,
+    target: &SteamShortcutTarget,
+    grid_images: &[(&str, &Path)],
+) -> Result<SteamShortcutResponse, String> {
+    let steam_root = resolve_steam_root_path(steam_root_override)
+        .ok_or_else(|| String::from("Could not locate local Steam installation"))?;
+    let config_directory = resolve_steam_userdata_directory(&steam_root, steam_id)?.join("config");
+    fs::create_dir_all(&config_directory).map_err(|error| {
+        format!(
+            "Failed to create Steam config directory {}: {error}",
+            config_directory.display()
+        )
+    })?;
+
+    let shortcuts_path = config_directory.join("shortcuts.vdf");
+    let mut document = match fs::read(&shortcuts_path) {
+        Ok(bytes) if !bytes.is_empty() => parse_binary_vdf(&bytes)?,
+        Ok(_) => Vec::new(),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+        Err(error) => {
+            return Err(format!(
+                "Failed to read Steam shortcuts at {}: {error}",
+                shortcuts_path.display()
+            ))
+        }
+    };
+    let (app_id, created) = upsert_steam_shortcut(&mut document, target);
+    steam_config_writer::write_steam_config_file(&shortcuts_path, write_binary_vdf(&document)).map_err(|error| {
+        format!(
+            "Failed to write Steam shortcuts at {}: {error}",
+            shortcuts_path.display()
+        )
+    })?;
+
+    let grid_directory = config_directory.join("grid");
+    let mut copied_grid_images = 0;
+    for (suffix, source_path) in grid_images {
+        let Some(extension) = source_path
+            .extension()
+            .map(|extension| extension.to_string_lossy().to_ascii_lowercase())
+            .filter(|extension| extension_priority_rank(extension) != usize::MAX)
+        else {
+            continue;
+        };
+        fs::create_dir_all(&grid_directory)
+            .map_err(|error| format!("Failed to create Steam grid directory: {error}"))?;
+        let destination_path = grid_directory.join(format!("{app_id}{suffix}.{extension}"));
+        fs::copy(source_path, &destination_path).map_err(|error| {
+            format!(
+                "Failed to copy grid artwork {} to {}: {error}",
+                source_path.display(),
+                destination_path.display()
+            )
+        })?;
+        copied_grid_images += 1;
+    }
+
+    Ok(SteamShortcutResponse {
+        app_id,
+        shortcuts_path: shortcuts_path.display().to_string(),
+        created,
+        grid_images: copied_grid_images,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upsert_steam_shortcut_round_trips_through_binary_vdf() {
+        let target = SteamShortcutTarget {
+            name: String::from("Chrono Trigger"),
+            executable_path: String::from("/usr/bin/retroarch"),
+            start_directory: String::from("/usr/bin"),
+            launch_options: String::from("\"/roms/Chrono Trigger.sfc\""),
+            icon_path: None,
+        };
+        let mut document = Vec::new();
+
+        let (app_id, created) = upsert_steam_shortcut(&mut document, &target);
+        let reparsed = parse_binary_vdf(&write_binary_vdf(&document)).expect("shortcuts should parse");
+        let (second_app_id, second_created) = upsert_steam_shortcut(&mut document.clone(), &target);
+
+        assert!(created);
+        assert!(!second_created);
+        assert_eq!(app_id, second_app_id);
+        assert_ne!(app_id & STEAM_SHORTCUT_APP_ID_FLAG, 0);
+        assert_eq!(reparsed, document);
+    }
+}
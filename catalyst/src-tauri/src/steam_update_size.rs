@@ -0,0 +1,220 @@
+use crate::*;
+use crate::steam_appinfo::SteamDepotBranchManifest;
+
+const STEAM_DEFAULT_BRANCH: &str = "public";
+pub(crate) const PENDING_UPDATE_SIZE_SOURCE_APP_MANIFEST: &str = "app_manifest";
+pub(crate) const PENDING_UPDATE_SIZE_SOURCE_DEPOT_MANIFESTS: &str = "depot_manifests";
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingUpdateSizeResponse {
+    pub branch: String,
+    pub installed_build_id: Option<u64>,
+    pub target_build_id: Option<u64>,
+    pub update_pending: bool,
+    /// Bytes left to download. From depot manifests this is the compressed size of every changed
+    /// depot, an upper bound since Steam only fetches the chunks that changed.
+    pub download_bytes: Option<u64>,
+    /// How much the install grows (or shrinks, when negative) once updated.
+    pub disk_delta_bytes: Option<i64>,
+    /// `app_manifest` when Steam already queued the update and sized it, `depot_manifests` when
+    /// estimated by comparing installed depot manifests with the product info cache.
+    pub source: Option<String>,
+    pub changed_depots: Vec<PendingDepotUpdateResponse>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct PendingDepotUpdateResponse {
+    pub depot_id: String,
+    pub installed_manifest_id: String,
+    pub target_manifest_id: String,
+    pub installed_size_bytes: Option<u64>,
+    pub target_size_bytes: Option<u64>,
+    pub download_bytes: Option<u64>,
+}
+
+struct InstalledSteamDepot {
+    depot_id: String,
+    manifest_id: String,
+    size_bytes: Option<u64>,
+}
+
+/// The branch the install tracks, from `UserConfig/BetaKey` of its app manifest.
+pub(crate) fn parse_steam_manifest_branch(manifest: &VdfValue) -> String {
+    vdf_find_object_value(manifest, "AppState")
+        .and_then(|app_state| vdf_find_object_value(app_state, "UserConfig"))
+        .and_then(|user_config| vdf_get_text_entry(user_config, "BetaKey"))
+        .map(str::trim)
+        .filter(|branch| !branch.is_empty())
+        .unwrap_or(STEAM_DEFAULT_BRANCH)
+        .to_owned()
+}
+
+fn parse_installed_steam_depots(app_state: &VdfValue) -> Vec<InstalledSteamDepot> {
+    let Some(VdfValue::Object(depots)) = vdf_find_object_value(app_state, "InstalledDepots") else {
+        return Vec::new();
+    };
+    depots
+        .iter()
+        .filter_map(|(depot_id, depot)| {
+            Some(InstalledSteamDepot {
+                depot_id: depot_id.clone(),
+                manifest_id: vdf_get_text_entry(depot, "manifest")?.trim().to_owned(),
+                size_bytes: vdf_get_text_entry(depot, "size").and_then(|size| size.trim().parse().ok()),
+            })
+        })
+        .collect()
+}
+
+fn sum_known<I>(values: I) -> Option<i64>
+where
+    I: IntoIterator<Item = Option<i64>>,
+{
+    values
+        .into_iter()
+        .try_fold(0_i64, |total, value| value.map(|value| total.saturating_add(value)))
+}
+
+/// Sizes the update pending for an installed app. When Steam already queued it
+/// (`TargetBuildID` ahead of `buildid`), the manifest's own `BytesToDownload` is used; otherwise
+/// the installed depot manifests are compared with the ones `depot_targets` says the branch
+/// ships now, the way DepotDownloader decides what to fetch.
+pub(crate) fn compute_pending_update_size(
+    manifest: &VdfValue,
+    depot_targets: Option<&HashMap<String, SteamDepotBranchManifest>>,
+) -> PendingUpdateSizeResponse {
+    let branch = parse_steam_manifest_branch(manifest);
+    let app_state = vdf_find_object_value(manifest, "AppState").unwrap_or(manifest);
+    let read_u64 = |key: &str| vdf_get_text_entry(app_state, key).and_then(|value| value.trim().parse::<u64>().ok());
+    let installed_build_id = read_u64("buildid");
+    let target_build_id = read_u64("TargetBuildID").filter(|target| *target != 0);
+
+    let changed_depots = depot_targets
+        .map(|depot_targets| {
+            parse_installed_steam_depots(app_state)
+                .into_iter()
+                .filter_map(|installed| {
+                    let target = depot_targets.get(&installed.depot_id)?;
+                    (target.manifest_id != installed.manifest_id).then(|| PendingDepotUpdateResponse {
+                        depot_id: installed.depot_id,
+                        installed_manifest_id: installed.manifest_id,
+                        target_manifest_id: target.manifest_id.clone(),
+                        installed_size_bytes: installed.size_bytes,
+                        target_size_bytes: target.size_bytes,
+                        download_bytes: target.download_bytes,
+                    })
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    let depot_disk_delta = (!changed_depots.is_empty())
+        .then(|| {
+            sum_known(changed_depots.iter().map(|depot| {
+                Some(depot.target_size_bytes? as i64 - depot.installed_size_bytes? as i64)
+            }))
+        })
+        .flatten();
+
+    let queued_by_steam = matches!(
+        (installed_build_id, target_build_id),
+        (Some(installed), Some(target)) if target != installed
+    );
+    let queued_download_bytes = read_u64("BytesToDownload")
+        .filter(|bytes| *bytes > 0)
+        .map(|bytes| bytes.saturating_sub(read_u64("BytesDownloaded").unwrap_or(0)));
+
+    let (download_bytes, source) = match queued_download_bytes {
+        Some(bytes) if queued_by_steam => (Some(bytes), Some(PENDING_UPDATE_SIZE_SOURCE_APP_MANIFEST)),
+        _ if !changed_depots.is_empty() => (
+            sum_known(
+                changed_depots
+                    .iter()
+                    .map(|depot| depot.download_bytes.map(|bytes| bytes as i64)),
+            )
+            .map(|bytes| bytes.max(0) as u64),
+            Some(PENDING_UPDATE_SIZE_SOURCE_DEPOT_MANIFESTS),
+        ),
+        _ => (None, None),
+    };
+
+    PendingUpdateSizeResponse {
+        branch,
+        installed_build_id,
+        target_build_id,
+        update_pending: queued_by_steam || !changed_depots.is_empty(),
+        download_bytes,
+        disk_delta_bytes: depot_disk_delta,
+        source: source.map(str::to_owned),
+        changed_depots,
+    }
+}
+
+/// Reads the app's manifest and the local product info cache to size its pending update.
+pub(crate) fn read_pending_steam_update_size(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+) -> Result<PendingUpdateSizeResponse, String> {
+    let manifest_path = resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)?;
+    let manifest_contents = fs::read_to_string(&manifest_path)
+        .map_err(|error| format!("Failed to read {}: {error}", manifest_path.display()))?;
+    let manifest = parse_vdf_document(&manifest_contents)?;
+    let branch = parse_steam_manifest_branch(&manifest);
+    let depot_targets =
+        steam_appinfo::read_local_steam_depot_branch_manifests(steam_root_override, app_id, &branch)
+            .unwrap_or_default();
+    Ok(compute_pending_update_size(&manifest, depot_targets.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_pending_update_size_diffs_depot_manifests() {
+        let manifest = parse_vdf_document(
+            r#""AppState"
+{
+    "appid"     "620"
+    "buildid"   "100"
+    "TargetBuildID"     "0"
+    "BytesToDownload"   "0"
+    "InstalledDepots"
+    {
+        "621" { "manifest" "111" "size" "1000" }
+        "622" { "manifest" "222" "size" "500" }
+    }
+    "UserConfig" { "BetaKey" "beta" }
+}"#,
+        )
+        .expect("parse manifest");
+        let depot_targets = HashMap::from([
+            (
+                String::from("621"),
+                SteamDepotBranchManifest {
+                    manifest_id: String::from("333"),
+                    size_bytes: Some(1200),
+                    download_bytes: Some(400),
+                },
+            ),
+            (
+                String::from("622"),
+                SteamDepotBranchManifest {
+                    manifest_id: String::from("222"),
+                    size_bytes: Some(500),
+                    download_bytes: Some(200),
+                },
+            ),
+        ]);
+
+        let pending = compute_pending_update_size(&manifest, Some(&depot_targets));
+
+        assert_eq!(pending.branch, "beta");
+        assert!(pending.update_pending);
+        assert_eq!(pending.download_bytes, Some(400));
+        assert_eq!(pending.disk_delta_bytes, Some(200));
+        assert_eq!(pending.source.as_deref(), Some(PENDING_UPDATE_SIZE_SOURCE_DEPOT_MANIFESTS));
+        assert_eq!(pending.changed_depots.len(), 1);
+        assert_eq!(pending.changed_depots[0].depot_id, "621");
+    }
+}
@@ -0,0 +1,422 @@
+use crate::*;
+use std::process::{Child, Stdio};
+
+pub(crate) const STEAMCMD_INSTALL_PROGRESS_EVENT: &str = "steamcmd-install-progress";
+pub(crate) const STEAMCMD_STEAM_GUARD_REQUIRED_MESSAGE: &str =
+    "Steam Guard needs a code for this login. Enter the code from your email or the Steam Mobile app and try again.";
+/// `StateFlags` value of a fully installed app.
+const STEAM_APP_STATE_FLAGS_INSTALLED: u64 = 4;
+
+/// Where steamcmd lives and which account it logs in with. Without a username steamcmd logs in
+/// anonymously, which only works for free and dedicated-server apps.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamCmdSettings {
+    pub path: Option<String>,
+    pub username: Option<String>,
+}
+
+/// Install options for a single steamcmd run. The password and Steam Guard code are only
+/// passed through to steamcmd and never stored; once steamcmd has cached the login they can be
+/// left out.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamCmdInstallOptions {
+    pub library_path: String,
+    pub branch: Option<String>,
+    pub branch_password: Option<String>,
+    /// Downloads only these depots instead of everything the branch ships for this platform.
+    pub depot_ids: Option<Vec<u64>>,
+    pub password: Option<String>,
+    pub steam_guard_code: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamCmdInstallProgressPayload {
+    pub app_id: String,
+    /// `starting`, `downloading`, `verifying`, `registering` or `complete`.
+    pub phase: String,
+    pub progress_percent: Option<f64>,
+    pub bytes_done: Option<u64>,
+    pub bytes_total: Option<u64>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamCmdInstallResponse {
+    pub app_id: String,
+    pub install_path: String,
+    pub manifest_path: String,
+    pub build_id: Option<u64>,
+    pub size_on_disk: u64,
+}
+
+#[derive(Debug, PartialEq)]
+enum SteamCmdOutputLine {
+    Progress {
+        verifying: bool,
+        percent: f64,
+        bytes_done: u64,
+        bytes_total: u64,
+    },
+    DepotDownloaded(PathBuf),
+    SteamGuardRequired,
+    Failed(String),
+}
+
+pub(crate) fn load_steamcmd_settings(connection: &Connection) -> Result<SteamCmdSettings, String> {
+    let settings = load_app_settings(connection)?;
+    match settings.get(APP_SETTING_STEAMCMD) {
+        Some(value) => serde_json::from_str(value)
+            .map_err(|error| format!("Stored steamcmd settings are invalid: {error}")),
+        None => Ok(SteamCmdSettings::default()),
+    }
+}
+
+pub(crate) fn save_steamcmd_settings(connection: &Connection, settings: &SteamCmdSettings) -> Result<(), String> {
+    let serialized =
+        serde_json::to_string(settings).map_err(|error| format!("Failed to serialize steamcmd settings: {error}"))?;
+    save_app_setting(connection, APP_SETTING_STEAMCMD, Some(&serialized))
+}
+
+/// Uses the configured path, falling back to a `steamcmd` on `PATH`.
+pub(crate) fn resolve_steamcmd_executable(settings: &SteamCmdSettings) -> Result<PathBuf, String> {
+    if let Some(path) = settings.path.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        let path = PathBuf::from(path);
+        return if path.is_file() {
+            Ok(path)
+        } else {
+            Err(format!("steamcmd was not found at {}", path.display()))
+        };
+    }
+
+    let executable_names: &[&str] = if cfg!(target_os = "windows") {
+        &["steamcmd.exe"]
+    } else {
+        &["steamcmd", "steamcmd.sh"]
+    };
+    std::env::var_os("PATH")
+        .iter()
+        .flat_map(std::env::split_paths)
+        .flat_map(|directory| executable_names.iter().map(move |name| directory.join(name)))
+        .find(|candidate| candidate.is_file())
+        .ok_or_else(|| String::from("steamcmd was not found; set its path in the steamcmd settings"))
+}
+
+fn parse_steamcmd_output_line(line: &str) -> Option<SteamCmdOutputLine> {
+    static PROGRESS_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+        Regex::new(r"Update state \(0x[0-9a-fA-F]+\) ([a-z ]+), progress: ([0-9.]+) \((\d+) / (\d+)\)")
+            .expect("steamcmd progress pattern is valid")
+    });
+    static DEPOT_PATTERN: once_cell::sync::Lazy<Regex> = once_cell::sync::Lazy::new(|| {
+        Regex::new(r#"Depot download complete : "([^"]+)""#).expect("steamcmd depot pattern is valid")
+    });
+
+    let line = line.trim();
+    if let Some(captures) = PROGRESS_PATTERN.captures(line) {
+        return Some(SteamCmdOutputLine::Progress {
+            verifying: captures[1].contains("verifying"),
+            percent: captures[2].parse().unwrap_or(0.0),
+            bytes_done: captures[3].parse().unwrap_or(0),
+            bytes_total: captures[4].parse().unwrap_or(0),
+        });
+    }
+    if let Some(captures) = DEPOT_PATTERN.captures(line) {
+        return Some(SteamCmdOutputLine::DepotDownloaded(PathBuf::from(&captures[1])));
+    }
+
+    let normalized = line.to_ascii_lowercase();
+    if normalized.contains("steam guard code")
+        || normalized.contains("two-factor code")
+        || normalized.contains("account logon denied")
+        || normalized.contains("invalid login auth code")
+    {
+        return Some(SteamCmdOutputLine::SteamGuardRequired);
+    }
+    if normalized.starts_with("error!") || normalized.contains("login failure") || normalized.contains("failed (") {
+        return Some(SteamCmdOutputLine::Failed(line.to_owned()));
+    }
+    None
+}
+
+fn build_steamcmd_arguments(
+    app_id: u64,
+    install_directory: &Path,
+    username: Option<&str>,
+    options: &SteamCmdInstallOptions,
+) -> Vec<String> {
+    let mut arguments = vec![
+        String::from("+@ShutdownOnFailedCommand"),
+        String::from("1"),
+        String::from("+@NoPromptForPassword"),
+        String::from("1"),
+        String::from("+force_install_dir"),
+        install_directory.to_string_lossy().into_owned(),
+    ];
+    if let Some(code) = options.steam_guard_code.as_deref().map(str::trim).filter(|value| !value.is_empty()) {
+        arguments.extend([String::from("+set_steam_guard_code"), code.to_owned()]);
+    }
+    arguments.push(String::from("+login"));
+    match username {
+        Some(username) => {
+            arguments.push(username.to_owned());
+            if let Some(password) = options.password.as_deref().filter(|value| !value.is_empty()) {
+                arguments.push(password.to_owned());
+            }
+        }
+        None => arguments.push(String::from("anonymous")),
+    }
+
+    let branch = options.branch.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    match options.depot_ids.as_deref().filter(|depot_ids| !depot_ids.is_empty()) {
+        Some(depot_ids) => {
+            if let Some(branch) = branch {
+                arguments.extend([String::from("+download_depot_branch"), branch.to_owned()]);
+            }
+            for depot_id in depot_ids {
+                arguments.extend([String::from("+download_depot"), app_id.to_string(), depot_id.to_string()]);
+            }
+        }
+        None => {
+            arguments.extend([String::from("+app_update"), app_id.to_string()]);
+            if let Some(branch) = branch {
+                arguments.extend([String::from("-beta"), branch.to_owned()]);
+                if let Some(branch_password) = options.branch_password.as_deref().filter(|value| !value.is_empty()) {
+                    arguments.extend([String::from("-betapassword"), branch_password.to_owned()]);
+                }
+            }
+            arguments.push(String::from("validate"));
+        }
+    }
+    arguments.push(String::from("+quit"));
+    arguments
+}
+
+/// Feeds steamcmd's output to `on_line` line by line. steamcmd redraws progress with `\r`, so
+/// both line endings split lines.
+fn read_steamcmd_output(
+    child: &mut Child,
+    on_line: &mut dyn FnMut(&str) -> bool,
+) -> Result<(), String> {
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| String::from("steamcmd output is unavailable"))?;
+    let mut buffer = [0_u8; 8192];
+    let mut pending = Vec::new();
+    loop {
+        let read_bytes = stdout
+            .read(&mut buffer)
+            .map_err(|error| format!("Failed to read steamcmd output: {error}"))?;
+        if read_bytes == 0 {
+            break;
+        }
+        for byte in &buffer[..read_bytes] {
+            if *byte != b'\n' && *byte != b'\r' {
+                pending.push(*byte);
+                continue;
+            }
+            let line = String::from_utf8_lossy(&pending).into_owned();
+            pending.clear();
+            if !line.trim().is_empty() && !on_line(&line) {
+                return Ok(());
+            }
+        }
+    }
+    if !pending.is_empty() {
+        on_line(&String::from_utf8_lossy(&pending));
+    }
+    Ok(())
+}
+
+fn build_steam_appmanifest(
+    app_id: u64,
+    name: &str,
+    install_dir: &str,
+    build_id: Option<u64>,
+    size_on_disk: u64,
+    branch: Option<&str>,
+) -> String {
+    let mut document = VdfValue::Object(Vec::new());
+    let app_state = vdf_ensure_object_path_mut(&mut document, &["AppState"]);
+    let now = Utc::now().timestamp().to_string();
+    for (key, value) in [
+        ("appid", app_id.to_string()),
+        ("Universe", String::from("1")),
+        ("name", name.to_owned()),
+        ("StateFlags", STEAM_APP_STATE_FLAGS_INSTALLED.to_string()),
+        ("installdir", install_dir.to_owned()),
+        ("LastUpdated", now),
+        ("SizeOnDisk", size_on_disk.to_string()),
+        ("buildid", build_id.unwrap_or(0).to_string()),
+        ("BytesToDownload", String::from("0")),
+        ("BytesDownloaded", String::from("0")),
+        ("AutoUpdateBehavior", String::from("0")),
+    ] {
+        vdf_set_text_entry(app_state, key, &value);
+    }
+    for section in ["UserConfig", "MountedConfig"] {
+        let config = vdf_ensure_object_path_mut(app_state, &[section]);
+        if let Some(branch) = branch {
+            vdf_set_text_entry(config, "BetaKey", branch);
+        }
+    }
+    serialize_vdf_document(&document)
+}
+
+/// Moves what `download_depot` staged into the install folder and removes the staging copy.
+fn merge_downloaded_depot(depot_directory: &Path, install_directory: &Path) -> Result<(), String> {
+    copy_downloaded_files(depot_directory, install_directory)?;
+    fs::remove_dir_all(depot_directory)
+        .map_err(|error| format!("Failed to remove {}: {error}", depot_directory.display()))
+}
+
+fn copy_downloaded_files(source_directory: &Path, destination_directory: &Path) -> Result<(), String> {
+    install_jobs::copy_directory_with_progress(source_directory, destination_directory, &mut |_| {})
+}
+
+/// Downloads the app (or the chosen depots) with steamcmd into `<library>/steamapps/common`,
+/// then writes an appmanifest so the Steam client picks the install up on its next start.
+pub(crate) fn install_steam_app_with_steamcmd(
+    steam_root_override: Option<&str>,
+    settings: &SteamCmdSettings,
+    app_id: u64,
+    name: &str,
+    options: &SteamCmdInstallOptions,
+    on_progress: &dyn Fn(SteamCmdInstallProgressPayload),
+) -> Result<SteamCmdInstallResponse, String> {
+    let executable = resolve_steamcmd_executable(settings)?;
+    let library_path = Path::new(options.library_path.trim());
+    let steamapps_directory = resolve_known_steam_library_steamapps(steam_root_override, library_path)?;
+    let install_dir = steam_appinfo::read_local_steam_app_install_dir(steam_root_override, app_id)
+        .unwrap_or_default()
+        .unwrap_or_else(|| sanitize_desktop_shortcut_name(name));
+    let install_directory = steamapps_directory.join("common").join(&install_dir);
+    fs::create_dir_all(&install_directory)
+        .map_err(|error| format!("Failed to create {}: {error}", install_directory.display()))?;
+
+    let app_id_text = app_id.to_string();
+    let emit = |phase: &str, progress: Option<(f64, u64, u64)>| {
+        on_progress(SteamCmdInstallProgressPayload {
+            app_id: app_id_text.clone(),
+            phase: phase.to_owned(),
+            progress_percent: progress.map(|(percent, _, _)| percent),
+            bytes_done: progress.map(|(_, done, _)| done),
+            bytes_total: progress.map(|(_, _, total)| total),
+        });
+    };
+    emit("starting", None);
+
+    let username = settings.username.as_deref().map(str::trim).filter(|value| !value.is_empty());
+    let mut child = Command::new(&executable)
+        .args(build_steamcmd_arguments(app_id, &install_directory, username, options))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| format!("Failed to start steamcmd at {}: {error}", executable.display()))?;
+
+    let mut depot_directories = Vec::new();
+    let mut failure = None;
+    let read_result = read_steamcmd_output(&mut child, &mut |line| {
+        match parse_steamcmd_output_line(line) {
+            Some(SteamCmdOutputLine::Progress {
+                verifying,
+                percent,
+                bytes_done,
+                bytes_total,
+            }) => emit(
+                if verifying { "verifying" } else { "downloading" },
+                Some((percent, bytes_done, bytes_total)),
+            ),
+            Some(SteamCmdOutputLine::DepotDownloaded(path)) => depot_directories.push(path),
+            Some(SteamCmdOutputLine::SteamGuardRequired) => {
+                failure = Some(String::from(STEAMCMD_STEAM_GUARD_REQUIRED_MESSAGE));
+                return false;
+            }
+            Some(SteamCmdOutputLine::Failed(message)) => failure = Some(message),
+            None => {}
+        }
+        true
+    });
+    if failure.is_some() {
+        let _ = child.kill();
+    }
+    let status = child
+        .wait()
+        .map_err(|error| format!("Failed to wait for steamcmd: {error}"))?;
+    read_result?;
+    if let Some(failure) = failure {
+        return Err(if failure == STEAMCMD_STEAM_GUARD_REQUIRED_MESSAGE {
+            failure
+        } else {
+            format!("steamcmd failed: {failure}")
+        });
+    }
+    if !status.success() {
+        return Err(format!("steamcmd exited with {status}"));
+    }
+
+    emit("registering", None);
+    for depot_directory in &depot_directories {
+        merge_downloaded_depot(depot_directory, &install_directory)?;
+    }
+    // steamcmd keeps its own manifest under the forced install folder; take the build ID from
+    // it and drop the nested `steamapps` so it doesn't end up inside the game folder.
+    let nested_steamapps = install_directory.join("steamapps");
+    let build_id = fs::read_to_string(nested_steamapps.join(format!("appmanifest_{app_id}.acf")))
+        .ok()
+        .and_then(|contents| parse_steam_manifest_u64_field(&contents, "buildid"));
+    if nested_steamapps.is_dir() {
+        fs::remove_dir_all(&nested_steamapps)
+            .map_err(|error| format!("Failed to remove {}: {error}", nested_steamapps.display()))?;
+    }
+
+    let size_on_disk = install_jobs::measure_directory_size(&install_directory);
+    let branch = options.branch.as_deref().map(str::trim).filter(|value| !value.is_empty() && *value != "public");
+    let manifest_path = steamapps_directory.join(format!("appmanifest_{app_id}.acf"));
+    fs::write(
+        &manifest_path,
+        build_steam_appmanifest(app_id, name, &install_dir, build_id, size_on_disk, branch),
+    )
+    .map_err(|error| format!("Failed to write {}: {error}", manifest_path.display()))?;
+    emit("complete", Some((100.0, size_on_disk, size_on_disk)));
+
+    Ok(SteamCmdInstallResponse {
+        app_id: app_id_text.clone(),
+        install_path: install_directory.display().to_string(),
+        manifest_path: manifest_path.display().to_string(),
+        build_id,
+        size_on_disk,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steamcmd_output_line_reads_progress_and_steam_guard_prompts() {
+        assert_eq!(
+            parse_steamcmd_output_line(" Update state (0x61) downloading, progress: 45.20 (1130 / 2500)"),
+            Some(SteamCmdOutputLine::Progress {
+                verifying: false,
+                percent: 45.2,
+                bytes_done: 1130,
+                bytes_total: 2500,
+            })
+        );
+        assert_eq!(
+            parse_steamcmd_output_line(r#"Depot download complete : "/tmp/steamapps/content/app_620/depot_621" (12 files, manifest 1)"#),
+            Some(SteamCmdOutputLine::DepotDownloaded(PathBuf::from(
+                "/tmp/steamapps/content/app_620/depot_621"
+            )))
+        );
+        assert_eq!(
+            parse_steamcmd_output_line("Steam Guard code:"),
+            Some(SteamCmdOutputLine::SteamGuardRequired)
+        );
+        assert_eq!(parse_steamcmd_output_line("Loading Steam API...OK"), None);
+    }
+}
@@ -0,0 +1,266 @@
+use crate::*;
+
+const EA_LOCAL_CONTENT_DIRECTORY: &str = r"Origin\LocalContent";
+const EA_MANIFEST_EXTENSION: &str = "mfst";
+const UBISOFT_INSTALLS_REGISTRY_KEY: &str = r"HKLM\SOFTWARE\WOW6432Node\Ubisoft\Launcher\Installs";
+
+/// A game found through a third-party launcher's local install data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LocalProviderGame {
+    pub external_id: String,
+    pub name: String,
+    pub install_path: Option<PathBuf>,
+}
+
+/// Detects EA App / Origin installs from the `.mfst` manifests under
+/// `%PROGRAMDATA%\Origin\LocalContent`. The EA App still writes these for every installed title.
+pub(crate) fn detect_ea_installed_games() -> Result<Vec<LocalProviderGame>, String> {
+    if !cfg!(target_os = "windows") {
+        return Ok(Vec::new());
+    }
+
+    let Ok(program_data) = std::env::var("PROGRAMDATA") else {
+        return Ok(Vec::new());
+    };
+    let local_content_directory = PathBuf::from(program_data).join(EA_LOCAL_CONTENT_DIRECTORY);
+    let Ok(game_directories) = fs::read_dir(&local_content_directory) else {
+        return Ok(Vec::new());
+    };
+
+    let mut games = Vec::new();
+    let mut seen_ids = HashSet::new();
+    for game_directory in game_directories.flatten() {
+        let Ok(manifest_entries) = fs::read_dir(game_directory.path()) else {
+            continue;
+        };
+        for manifest_entry in manifest_entries.flatten() {
+            let manifest_path = manifest_entry.path();
+            let is_manifest = manifest_path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map(|extension| extension.eq_ignore_ascii_case(EA_MANIFEST_EXTENSION))
+                .unwrap_or(false);
+            if !is_manifest {
+                continue;
+            }
+
+            let Ok(contents) = fs::read_to_string(&manifest_path) else {
+                continue;
+            };
+            if let Some(game) = parse_ea_manifest(&contents) {
+                if seen_ids.insert(game.external_id.clone()) {
+                    games.push(game);
+                }
+            }
+        }
+    }
+
+    Ok(games)
+}
+
+/// Parses an EA `.mfst` manifest, which is a URL query string carrying the offer ID and
+/// install path. Manifests without an install path belong to titles that are not installed.
+fn parse_ea_manifest(contents: &str) -> Option<LocalProviderGame> {
+    let query = contents.trim().trim_start_matches('?');
+    let parsed_url = Url::parse(&format!("https://localhost/?{query}")).ok()?;
+    let mut offer_id = None;
+    let mut install_path = None;
+    for (key, value) in parsed_url.query_pairs() {
+        match key.as_ref() {
+            "id" => offer_id = Some(value.split('@').next().unwrap_or_default().to_owned()),
+            "dipinstallpath" => install_path = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    let offer_id = offer_id.filter(|value| !value.is_empty())?;
+    let install_path = PathBuf::from(install_path.filter(|value| !value.trim().is_empty())?);
+    let name = read_ea_installer_game_title(&install_path).or_else(|| {
+        install_path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned())
+    })?;
+
+    Some(LocalProviderGame {
+        external_id: offer_id,
+        name,
+        install_path: Some(install_path),
+    })
+}
+
+fn read_ea_installer_game_title(install_path: &Path) -> Option<String> {
+    let installer_data = fs::read_to_string(install_path.join("__Installer").join("installerdata.xml")).ok()?;
+    let title_pattern = Regex::new(r#"<gameTitle[^>]*locale="en_US"[^>]*>([^<]+)</gameTitle>"#).ok()?;
+    title_pattern
+        .captures(&installer_data)
+        .and_then(|capture| capture.get(1))
+        .map(|matched| matched.as_str().trim().to_owned())
+        .filter(|title| !title.is_empty())
+}
+
+/// Detects Ubisoft Connect installs from the launcher's `Installs` registry key.
+pub(crate) fn detect_ubisoft_installed_games() -> Result<Vec<LocalProviderGame>, String> {
+    if !cfg!(target_os = "windows") {
+        return Ok(Vec::new());
+    }
+
+    let output = match Command::new("reg")
+        .args(["query", UBISOFT_INSTALLS_REGISTRY_KEY, "/s"])
+        .output()
+    {
+        Ok(output) if output.status.success() => output,
+        _ => return Ok(Vec::new()),
+    };
+
+    Ok(parse_ubisoft_installs_registry_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+fn parse_ubisoft_installs_registry_output(output: &str) -> Vec<LocalProviderGame> {
+    let mut games = Vec::new();
+    let mut current_id = None;
+    for line in output.lines() {
+        let trimmed_line = line.trim();
+        if trimmed_line.to_ascii_uppercase().starts_with("HKEY_") {
+            current_id = trimmed_line
+                .rsplit('\\')
+                .next()
+                .filter(|segment| segment.chars().all(|character| character.is_ascii_digit()))
+                .map(str::to_owned);
+            continue;
+        }
+
+        let Some(game_id) = current_id.as_ref() else {
+            continue;
+        };
+        let mut parts = trimmed_line.splitn(3, "    ").map(str::trim);
+        let (Some("InstallDir"), Some(_), Some(install_dir)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        // An empty or bare-root install dir doesn't point at an install.
+        let install_dir = install_dir.trim_end_matches(['/', '\\']).trim();
+        if install_dir.is_empty() {
+            continue;
+        }
+        let install_path = PathBuf::from(install_dir);
+        let name = install_path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().into_owned())
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| format!("Ubisoft game {game_id}"));
+        games.push(LocalProviderGame {
+            external_id: game_id.clone(),
+            name,
+            install_path: Some(install_path),
+        });
+    }
+
+    games
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ubisoft_installs_registry_output_reads_install_dirs() {
+        let output = "\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\WOW6432Node\\Ubisoft\\Launcher\\Installs\\635\r\n    InstallDir    REG_SZ    C:/Program Files (x86)/Ubisoft/Ubisoft Game Launcher/games/Far Cry 5/\r\n    Language    REG_SZ    en-US\r\n\r\nHKEY_LOCAL_MACHINE\\SOFTWARE\\WOW6432Node\\Ubisoft\\Launcher\\Installs\\4311\r\n    InstallDir    REG_SZ    D:/Games/Anno 1800/\r\n";
+
+        let games = parse_ubisoft_installs_registry_output(output);
+
+        assert_eq!(
+            games,
+            vec![
+                LocalProviderGame {
+                    external_id: String::from("635"),
+                    name: String::from("Far Cry 5"),
+                    install_path: Some(PathBuf::from(
+                        "C:/Program Files (x86)/Ubisoft/Ubisoft Game Launcher/games/Far Cry 5"
+                    )),
+                },
+                LocalProviderGame {
+                    external_id: String::from("4311"),
+                    name: String::from("Anno 1800"),
+                    install_path: Some(PathBuf::from("D:/Games/Anno 1800")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_ubisoft_installs_registry_output_skips_unkeyed_values() {
+        let output = "    InstallDir    REG_SZ    C:/Orphan/\r\n\
+            HKEY_LOCAL_MACHINE\\SOFTWARE\\Ubisoft\\Launcher\\Installs\\Backup\r\n\
+            \x20   InstallDir    REG_SZ    C:/Backup/\r\n\
+            HKEY_LOCAL_MACHINE\\SOFTWARE\\Ubisoft\\Launcher\\Installs\\77\r\n\
+            \x20   InstallDir    REG_SZ\r\n\
+            \x20   InstallDir    REG_SZ    /\r\n\
+            \x20   InstallDir    REG_SZ    \x20 \r\n";
+
+        assert!(parse_ubisoft_installs_registry_output(output).is_empty());
+        assert!(parse_ubisoft_installs_registry_output("").is_empty());
+    }
+
+    fn ea_manifest(pairs: &[(&str, &str)]) -> String {
+        let query = pairs
+            .iter()
+            .map(|(key, value)| {
+                let encoded_value = url::form_urlencoded::byte_serialize(value.as_bytes()).collect::<String>();
+                format!("{key}={encoded_value}")
+            })
+            .collect::<Vec<_>>()
+            .join("&");
+        format!("?{query}")
+    }
+
+    #[test]
+    fn parse_ea_manifest_reads_the_offer_and_title() {
+        let dir = tempfile::tempdir().expect("temp dir should be created");
+        let install_path = dir.path().join("Battlefield 1");
+        fs::create_dir_all(install_path.join("__Installer")).expect("create installer dir");
+        fs::write(
+            install_path.join("__Installer").join("installerdata.xml"),
+            r#"<DiPManifest><gameTitles><gameTitle locale="en_US"> Battlefield™ 1 </gameTitle></gameTitles></DiPManifest>"#,
+        )
+        .expect("write installer data");
+        let install_dir = install_path.to_string_lossy().into_owned();
+
+        let game = parse_ea_manifest(&ea_manifest(&[
+            ("id", "Origin.OFR.50.0000557@steam"),
+            ("dipinstallpath", install_dir.as_str()),
+            ("previousstate", "kReadyToStart"),
+        ]))
+        .expect("installed game");
+        assert_eq!(
+            game,
+            LocalProviderGame {
+                external_id: String::from("Origin.OFR.50.0000557"),
+                name: String::from("Battlefield™ 1"),
+                install_path: Some(install_path.clone()),
+            }
+        );
+
+        // Without installer data the install folder names the game.
+        let folder_path = dir.path().join("Mass Effect").to_string_lossy().into_owned();
+        let game = parse_ea_manifest(&ea_manifest(&[
+            ("id", "Origin.OFR.50.0001234"),
+            ("dipinstallpath", folder_path.as_str()),
+        ]))
+        .expect("installed game");
+        assert_eq!(game.name, "Mass Effect");
+    }
+
+    #[test]
+    fn parse_ea_manifest_skips_manifests_missing_the_offer_or_install_path() {
+        assert_eq!(parse_ea_manifest(&ea_manifest(&[("id", "Origin.OFR.50.0001234")])), None);
+        assert_eq!(
+            parse_ea_manifest(&ea_manifest(&[("id", "Origin.OFR.50.0001234"), ("dipinstallpath", "  ")])),
+            None
+        );
+        assert_eq!(parse_ea_manifest(&ea_manifest(&[("dipinstallpath", "C:\\Games\\Mass Effect")])), None);
+        assert_eq!(
+            parse_ea_manifest(&ea_manifest(&[("id", "@steam"), ("dipinstallpath", "C:\\Games\\Mass Effect")])),
+            None
+        );
+        assert_eq!(parse_ea_manifest(""), None);
+    }
+}
@@ -0,0 +1,89 @@
+use crate::*;
+
+/// A migrated database in a temporary directory with one user, `player@example.com`. The
+/// directory is removed when the fixture is dropped.
+pub(crate) struct TestDatabase {
+    pub connection: Connection,
+    pub user: UserRow,
+    pub db_path: PathBuf,
+    pub dir: tempfile::TempDir,
+}
+
+pub(crate) fn test_database() -> TestDatabase {
+    let dir = tempfile::tempdir().expect("temp dir should be created");
+    let db_path = dir.path().join("catalyst.db");
+    initialize_database(&db_path).expect("init db");
+    let connection = open_connection(&db_path).expect("open conn");
+    let user = create_user(&connection, "player@example.com", "hash", None).expect("create user");
+    TestDatabase {
+        connection,
+        user,
+        db_path,
+        dir,
+    }
+}
+
+impl TestDatabase {
+    /// Adds an uninstalled, unplayed game to the user's library.
+    pub(crate) fn insert_game(&self, provider: &str, external_id: &str, name: &str) {
+        self.connection
+            .execute(
+                "
+                INSERT INTO games (user_id, provider, external_id, name, kind, playtime_minutes, last_synced_at)
+                VALUES (?1, ?2, ?3, ?4, 'game', 0, '2024-01-01T00:00:00Z')
+                ",
+                params![self.user.id, provider, external_id, name],
+            )
+            .expect("insert game");
+    }
+
+    /// A Steam root inside the fixture's directory, `<dir>/Steam`. It is created by
+    /// `write_steam_app_manifest`.
+    pub(crate) fn steam_root(&self) -> PathBuf {
+        self.dir.path().join("Steam")
+    }
+
+    /// Writes a fully installed manifest for `app_id` into the fixture's Steam root and returns
+    /// the app's install directory, which is created empty.
+    pub(crate) fn write_steam_app_manifest(&self, app_id: u64, install_dir: &str) -> PathBuf {
+        let steamapps_directory = self.steam_root().join("steamapps");
+        let install_directory = steamapps_directory.join("common").join(install_dir);
+        fs::create_dir_all(&install_directory).expect("create install dir");
+        fs::write(
+            steamapps_directory.join(format!("appmanifest_{app_id}.acf")),
+            format!(
+                "\"AppState\"\n{{\n\t\"appid\"\t\"{app_id}\"\n\t\"StateFlags\"\t\"4\"\n\t\"installdir\"\t\"{install_dir}\"\n}}\n"
+            ),
+        )
+        .expect("write manifest");
+        install_directory
+    }
+
+    /// An `AppState` on this database with no session.
+    pub(crate) fn app_state(&self) -> AppState {
+        AppState::new(
+            self.db_path.clone(),
+            self.dir.path().join("session"),
+            None,
+            false,
+            false,
+            None,
+        )
+    }
+
+    /// An `AppState` on this database, signed in as the fixture's user.
+    pub(crate) fn signed_in_state(&self) -> AppState {
+        let state = self.app_state();
+        let token = create_session(&self.connection, &self.user.id).expect("create session");
+        *state.current_session_token.lock().expect("session lock") = Some(token);
+        state
+    }
+
+    /// Like `signed_in_state`, reading Steam's files from `steam_root` only.
+    pub(crate) fn signed_in_state_with_steam_root(&self) -> AppState {
+        let state = self.signed_in_state();
+        *state.steam_root_override.lock().expect("steam root lock") =
+            Some(self.steam_root().to_string_lossy().into_owned());
+        state
+    }
+}
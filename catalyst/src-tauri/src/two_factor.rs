@@ -0,0 +1,296 @@
+use crate::*;
+use totp_rs::{Algorithm, Secret, TOTP};
+
+const TOTP_ISSUER: &str = "Catalyst";
+const TOTP_DIGITS: usize = 6;
+const TOTP_STEP_SECONDS: u64 = 30;
+// Accept the previous and next code as well to tolerate clock drift between devices.
+const TOTP_SKEW_STEPS: u8 = 1;
+const RECOVERY_CODE_COUNT: usize = 8;
+pub(crate) const TWO_FACTOR_LOGIN_TTL: Duration = Duration::from_secs(5 * 60);
+pub(crate) const TWO_FACTOR_LOGIN_MAX_ATTEMPTS: u32 = 5;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TwoFactorSetupResponse {
+    pub secret: String,
+    pub otpauth_url: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TwoFactorRecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// A Steam sign-in that succeeded for an account with 2FA enabled and is waiting for a code
+/// before a session is issued.
+#[derive(Debug, Clone)]
+pub(crate) struct PendingTwoFactorLogin {
+    pub user_id: String,
+    pub synced_games: usize,
+    pub expires_at: Instant,
+    pub failed_attempts: u32,
+}
+
+struct TwoFactorRow {
+    encrypted_secret: String,
+    recovery_code_hashes: Vec<String>,
+    enabled: bool,
+    /// Time step of the last accepted authenticator code; codes up to it are replays.
+    last_used_step: Option<u64>,
+}
+
+fn find_two_factor_row(connection: &Connection, user_id: &str) -> Result<Option<TwoFactorRow>, String> {
+    let row = connection
+        .query_row(
+            "
+            SELECT encrypted_secret, recovery_code_hashes, enabled_at, last_used_step
+            FROM user_two_factor
+            WHERE user_id = ?1
+            ",
+            params![user_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, Option<i64>>(3)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(|error| format!("Failed to query two-factor settings: {error}"))?;
+
+    let Some((encrypted_secret, recovery_code_hashes, enabled_at, last_used_step)) = row else {
+        return Ok(None);
+    };
+    let recovery_code_hashes = serde_json::from_str::<Vec<String>>(&recovery_code_hashes)
+        .map_err(|error| format!("Failed to decode two-factor recovery codes: {error}"))?;
+
+    Ok(Some(TwoFactorRow {
+        encrypted_secret,
+        recovery_code_hashes,
+        enabled: enabled_at.is_some(),
+        last_used_step: last_used_step.and_then(|step| u64::try_from(step).ok()),
+    }))
+}
+
+pub(crate) fn is_two_factor_enabled(connection: &Connection, user_id: &str) -> Result<bool, String> {
+    Ok(find_two_factor_row(connection, user_id)?
+        .map(|row| row.enabled)
+        .unwrap_or(false))
+}
+
+pub(crate) fn has_pending_two_factor_setup(connection: &Connection, user_id: &str) -> Result<bool, String> {
+    Ok(find_two_factor_row(connection, user_id)?
+        .map(|row| !row.enabled)
+        .unwrap_or(false))
+}
+
+fn build_totp(secret: Vec<u8>, account_name: &str) -> Result<TOTP, String> {
+    // otpauth labels use ':' to separate issuer and account, so it cannot appear in the name.
+    let account_name = account_name.replace(':', " ");
+    TOTP::new(
+        Algorithm::SHA1,
+        TOTP_DIGITS,
+        TOTP_SKEW_STEPS,
+        TOTP_STEP_SECONDS,
+        secret,
+        Some(String::from(TOTP_ISSUER)),
+        account_name,
+    )
+    .map_err(|error| format!("Failed to configure two-factor secret: {error}"))
+}
+
+/// Generates a new secret for the user and stores it, encrypted, as a pending setup. A setup
+/// that was never confirmed is replaced; 2FA is only enforced after `enable_two_factor`.
+pub(crate) fn begin_two_factor_setup(
+    connection: &Connection,
+    user_id: &str,
+    account_name: &str,
+) -> Result<TwoFactorSetupResponse, String> {
+    let secret = Secret::generate_secret()
+        .to_bytes()
+        .map_err(|error| format!("Failed to generate two-factor secret: {error}"))?;
+    let totp = build_totp(secret, account_name)?;
+    let secret_base32 = totp.get_secret_base32();
+    let encrypted_secret = field_encryption::encrypt_field(&secret_base32)?;
+
+    connection
+        .execute(
+            "
+            INSERT INTO user_two_factor (user_id, encrypted_secret, recovery_code_hashes, enabled_at, created_at)
+            VALUES (?1, ?2, '[]', NULL, ?3)
+            ON CONFLICT(user_id) DO UPDATE SET
+              encrypted_secret = excluded.encrypted_secret,
+              recovery_code_hashes = '[]',
+              enabled_at = NULL,
+              last_used_step = NULL,
+              created_at = excluded.created_at
+            ",
+            params![user_id, encrypted_secret, Utc::now().to_rfc3339()],
+        )
+        .map_err(|error| format!("Failed to store two-factor secret: {error}"))?;
+
+    Ok(TwoFactorSetupResponse {
+        otpauth_url: totp.get_url(),
+        secret: secret_base32,
+    })
+}
+
+/// Checks a code from the authenticator app. Each authenticator code is accepted once: a code
+/// from the time step of the last accepted one, or an earlier step, is a replay. When
+/// `accept_recovery_code` is set, an unused recovery code is also accepted and is consumed so it
+/// cannot be used again.
+pub(crate) fn check_two_factor_code(
+    connection: &Connection,
+    user_id: &str,
+    code: &str,
+    accept_recovery_code: bool,
+) -> Result<bool, String> {
+    let Some(row) = find_two_factor_row(connection, user_id)? else {
+        return Ok(false);
+    };
+    let code = code.trim();
+
+    let digits = code.chars().filter(|character| !character.is_whitespace()).collect::<String>();
+    if digits.len() == TOTP_DIGITS && digits.chars().all(|character| character.is_ascii_digit()) {
+        let secret_base32 = field_encryption::decrypt_field(&row.encrypted_secret)?;
+        let secret = Secret::Encoded(secret_base32)
+            .to_bytes()
+            .map_err(|error| format!("Stored two-factor secret is invalid: {error}"))?;
+        let totp = build_totp(secret, user_id)?;
+        let current_step = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|error| format!("Failed to read system time: {error}"))?
+            .as_secs()
+            / TOTP_STEP_SECONDS;
+        let skew_steps = u64::from(TOTP_SKEW_STEPS);
+        let matched_step = (current_step.saturating_sub(skew_steps)..=current_step + skew_steps)
+            .filter(|step| row.last_used_step.is_none_or(|last_used_step| *step > last_used_step))
+            .find(|step| totp.generate(step * TOTP_STEP_SECONDS) == digits);
+        let Some(matched_step) = matched_step else {
+            return Ok(false);
+        };
+        connection
+            .execute(
+                "UPDATE user_two_factor SET last_used_step = ?1 WHERE user_id = ?2",
+                params![matched_step as i64, user_id],
+            )
+            .map_err(|error| format!("Failed to record two-factor code use: {error}"))?;
+        return Ok(true);
+    }
+
+    if !accept_recovery_code || !row.enabled {
+        return Ok(false);
+    }
+    let code_hash = hash_recovery_code(code);
+    let Some(position) = row.recovery_code_hashes.iter().position(|hash| *hash == code_hash) else {
+        return Ok(false);
+    };
+    let mut remaining_hashes = row.recovery_code_hashes;
+    remaining_hashes.remove(position);
+    store_recovery_code_hashes(connection, user_id, &remaining_hashes)?;
+    Ok(true)
+}
+
+/// Marks the pending setup as enabled and issues a fresh set of recovery codes. Only hashes are
+/// stored, so the returned codes cannot be shown again.
+pub(crate) fn enable_two_factor(connection: &Connection, user_id: &str) -> Result<Vec<String>, String> {
+    let recovery_codes = (0..RECOVERY_CODE_COUNT)
+        .map(|_| {
+            let random = Uuid::new_v4().simple().to_string();
+            format!("{}-{}", &random[..5], &random[5..10])
+        })
+        .collect::<Vec<_>>();
+    let recovery_code_hashes = recovery_codes
+        .iter()
+        .map(|code| hash_recovery_code(code))
+        .collect::<Vec<_>>();
+
+    store_recovery_code_hashes(connection, user_id, &recovery_code_hashes)?;
+    connection
+        .execute(
+            "UPDATE user_two_factor SET enabled_at = ?1 WHERE user_id = ?2",
+            params![Utc::now().to_rfc3339(), user_id],
+        )
+        .map_err(|error| format!("Failed to enable two-factor authentication: {error}"))?;
+
+    Ok(recovery_codes)
+}
+
+pub(crate) fn disable_two_factor(connection: &Connection, user_id: &str) -> Result<(), String> {
+    connection
+        .execute("DELETE FROM user_two_factor WHERE user_id = ?1", params![user_id])
+        .map_err(|error| format!("Failed to disable two-factor authentication: {error}"))?;
+    Ok(())
+}
+
+fn store_recovery_code_hashes(connection: &Connection, user_id: &str, hashes: &[String]) -> Result<(), String> {
+    let encoded = serde_json::to_string(hashes)
+        .map_err(|error| format!("Failed to encode two-factor recovery codes: {error}"))?;
+    connection
+        .execute(
+            "UPDATE user_two_factor SET recovery_code_hashes = ?1 WHERE user_id = ?2",
+            params![encoded, user_id],
+        )
+        .map_err(|error| format!("Failed to store two-factor recovery codes: {error}"))?;
+    Ok(())
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    let normalized = code.trim().to_ascii_lowercase().replace([' ', '-'], "");
+    let digest = Sha256::digest(normalized.as_bytes());
+    format!("{digest:x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovery_codes_are_single_use() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+
+        begin_two_factor_setup(&connection, &user.id, &user.email).expect("begin setup");
+        assert!(!is_two_factor_enabled(&connection, &user.id).expect("query status"));
+        let recovery_codes = enable_two_factor(&connection, &user.id).expect("enable");
+        assert!(is_two_factor_enabled(&connection, &user.id).expect("query status"));
+
+        let code = recovery_codes[0].to_uppercase();
+        assert!(check_two_factor_code(&connection, &user.id, &code, true).expect("check code"));
+        assert!(!check_two_factor_code(&connection, &user.id, &code, true).expect("check code"));
+        assert!(!check_two_factor_code(&connection, &user.id, &recovery_codes[1], false)
+            .expect("check code"));
+    }
+
+    #[test]
+    fn authenticator_codes_cannot_be_replayed() {
+        let database = test_support::test_database();
+        let (connection, user) = (&database.connection, &database.user);
+        let setup = begin_two_factor_setup(connection, &user.id, &user.email).expect("begin setup");
+        let secret = Secret::Encoded(setup.secret).to_bytes().expect("decode secret");
+        let totp = build_totp(secret, &user.id).expect("build totp");
+        let now = SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .expect("system time")
+            .as_secs();
+
+        let next_code = totp.generate(now + TOTP_STEP_SECONDS);
+        assert!(check_two_factor_code(connection, &user.id, &next_code, false).expect("check code"));
+        assert!(!check_two_factor_code(connection, &user.id, &next_code, false).expect("replayed code"));
+        // A code from an earlier step than the last accepted one is stale as well.
+        let current_code = totp.generate(now);
+        if current_code != next_code {
+            assert!(!check_two_factor_code(connection, &user.id, &current_code, false).expect("stale code"));
+        }
+
+        // A fresh setup starts over.
+        let setup = begin_two_factor_setup(connection, &user.id, &user.email).expect("restart setup");
+        let secret = Secret::Encoded(setup.secret).to_bytes().expect("decode secret");
+        let code = build_totp(secret, &user.id).expect("build totp").generate(now);
+        assert!(check_two_factor_code(connection, &user.id, &code, false).expect("check new code"));
+    }
+}
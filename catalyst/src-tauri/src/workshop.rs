@@ -0,0 +1,247 @@
+use crate::*;
+
+const STEAM_PUBLISHED_FILE_DETAILS_ENDPOINT: &str =
+    "https://api.steampowered.com/ISteamRemoteStorage/GetPublishedFileDetails/v1/";
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SteamWorkshopItemResponse {
+    pub published_file_id: String,
+    pub title: Option<String>,
+    pub size_bytes: Option<u64>,
+    pub installed: bool,
+    pub needs_update: bool,
+    pub updated_at: Option<String>,
+    pub latest_updated_at: Option<String>,
+    pub path: Option<String>,
+}
+
+/// Reads subscribed Workshop items for an app from `steamapps/workshop/appworkshop_<appid>.acf`
+/// in the library that holds the app. Items whose content folder is missing are reported as
+/// not installed, which is how Steam represents subscriptions that are still downloading.
+pub(crate) fn read_steam_workshop_items(
+    steam_root_override: Option<&str>,
+    app_id: u64,
+) -> Result<Vec<SteamWorkshopItemResponse>, String> {
+    let manifest_path = resolve_steam_manifest_path_for_app_id(steam_root_override, app_id)?;
+    let Some(steamapps_directory) = manifest_path.parent() else {
+        return Ok(Vec::new());
+    };
+    let workshop_directory = steamapps_directory.join("workshop");
+    let workshop_manifest_path = workshop_directory.join(format!("appworkshop_{app_id}.acf"));
+    let contents = match fs::read_to_string(&workshop_manifest_path) {
+        Ok(contents) => contents,
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(format!(
+                "Failed to read Steam workshop manifest at {}: {error}",
+                workshop_manifest_path.display()
+            ))
+        }
+    };
+
+    let content_directory = workshop_directory.join("content").join(app_id.to_string());
+    parse_steam_workshop_manifest(&contents, &content_directory)
+}
+
+fn parse_steam_workshop_manifest(
+    contents: &str,
+    content_directory: &Path,
+) -> Result<Vec<SteamWorkshopItemResponse>, String> {
+    let document = parse_vdf_document(contents)?;
+    let Some(app_workshop) = vdf_find_object_value(&document, "AppWorkshop") else {
+        return Ok(Vec::new());
+    };
+    let installed_items = vdf_find_object_value(app_workshop, "WorkshopItemsInstalled");
+    let item_details = vdf_find_object_value(app_workshop, "WorkshopItemDetails");
+
+    let mut published_file_ids = Vec::new();
+    for section in [installed_items, item_details].into_iter().flatten() {
+        let VdfValue::Object(entries) = section else {
+            continue;
+        };
+        for (published_file_id, _) in entries {
+            if !published_file_ids.contains(published_file_id) {
+                published_file_ids.push(published_file_id.clone());
+            }
+        }
+    }
+
+    let format_timestamp = |value: Option<i64>| {
+        value
+            .filter(|timestamp| *timestamp > 0)
+            .and_then(|timestamp| Utc.timestamp_opt(timestamp, 0).single())
+            .map(|timestamp| timestamp.to_rfc3339())
+    };
+    let read_number = |section: Option<&VdfValue>, published_file_id: &str, key: &str| {
+        section
+            .and_then(|value| vdf_find_object_value(value, published_file_id))
+            .and_then(|item| vdf_get_text_entry(item, key))
+            .and_then(|value| value.trim().parse::<i64>().ok())
+    };
+    let read_text = |section: Option<&VdfValue>, published_file_id: &str, key: &str| {
+        section
+            .and_then(|value| vdf_find_object_value(value, published_file_id))
+            .and_then(|item| vdf_get_text_entry(item, key))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_owned)
+    };
+
+    Ok(published_file_ids
+        .into_iter()
+        .map(|published_file_id| {
+            let item_path = content_directory.join(&published_file_id);
+            let installed = installed_items
+                .and_then(|value| vdf_find_object_value(value, &published_file_id))
+                .is_some()
+                && item_path.is_dir();
+            let time_updated = read_number(installed_items, &published_file_id, "timeupdated")
+                .or_else(|| read_number(item_details, &published_file_id, "timeupdated"));
+            let latest_time_updated = read_number(item_details, &published_file_id, "latest_timeupdated");
+            let manifest = read_text(item_details, &published_file_id, "manifest");
+            let latest_manifest = read_text(item_details, &published_file_id, "latest_manifest");
+            let needs_update = !installed
+                || matches!(
+                    (time_updated, latest_time_updated),
+                    (Some(current), Some(latest)) if latest > current
+                )
+                || matches!(
+                    (&manifest, &latest_manifest),
+                    (Some(current), Some(latest)) if current != latest
+                );
+
+            SteamWorkshopItemResponse {
+                title: None,
+                size_bytes: read_number(installed_items, &published_file_id, "size")
+                    .map(|size| size.max(0) as u64),
+                installed,
+                needs_update,
+                updated_at: format_timestamp(time_updated),
+                latest_updated_at: format_timestamp(latest_time_updated),
+                path: installed.then(|| item_path.display().to_string()),
+                published_file_id,
+            }
+        })
+        .collect())
+}
+
+/// Looks up Workshop item titles. The endpoint is public, so no Web API key is required.
+pub(crate) fn fetch_steam_workshop_titles(
+    client: &Client,
+    published_file_ids: &[String],
+) -> Result<HashMap<String, String>, String> {
+    if published_file_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut form = vec![(String::from("itemcount"), published_file_ids.len().to_string())];
+    for (index, published_file_id) in published_file_ids.iter().enumerate() {
+        form.push((format!("publishedfileids[{index}]"), published_file_id.clone()));
+    }
+    let request_url = Url::parse(STEAM_PUBLISHED_FILE_DETAILS_ENDPOINT)
+        .map_err(|error| format!("Failed to build Steam workshop details URL: {error}"))?;
+    let response = send_limited_request(&request_url, || client.post(request_url.clone()).form(&form))
+        .map_err(|error| format!("Steam workshop details request failed: {error}"))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Steam workshop details request failed with status {}",
+            response.status()
+        ));
+    }
+    let payload = response
+        .json::<serde_json::Value>()
+        .map_err(|error| format!("Failed to decode Steam workshop details: {error}"))?;
+
+    let mut titles = HashMap::new();
+    let details = payload
+        .pointer("/response/publishedfiledetails")
+        .and_then(serde_json::Value::as_array);
+    for detail in details.into_iter().flatten() {
+        let published_file_id = detail.get("publishedfileid").and_then(serde_json::Value::as_str);
+        let title = detail
+            .get("title")
+            .and_then(serde_json::Value::as_str)
+            .map(str::trim)
+            .filter(|title| !title.is_empty());
+        if let (Some(published_file_id), Some(title)) = (published_file_id, title) {
+            titles.insert(published_file_id.to_owned(), title.to_owned());
+        }
+    }
+
+    Ok(titles)
+}
+
+/// Opens an item page, or the app's Workshop hub when no item is given, in the Steam client and
+/// falls back to the community website when Steam cannot handle the URI.
+pub(crate) fn open_steam_workshop_page(app_id: u64, published_file_id: Option<&str>) -> Result<(), String> {
+    let (steam_uri, web_url) = match published_file_id {
+        Some(published_file_id) => (
+            format!("steam://url/CommunityFilePage/{published_file_id}"),
+            format!("https://steamcommunity.com/sharedfiles/filedetails/?id={published_file_id}"),
+        ),
+        None => (
+            format!("steam://url/SteamWorkshopPage/{app_id}"),
+            format!("https://steamcommunity.com/app/{app_id}/workshop/"),
+        ),
+    };
+
+    launch_steam_uri(&steam_uri, "open-workshop").or_else(|steam_error| {
+        webbrowser::open(&web_url)
+            .map(|_| ())
+            .map_err(|error| format!("{steam_error}; webbrowser::open {web_url}: {error}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_steam_workshop_manifest_reports_install_and_update_state() {
+        let content_directory = tempfile::tempdir().expect("temp dir should be created");
+        fs::create_dir(content_directory.path().join("111")).expect("item dir should be created");
+        let contents = r#"
+"AppWorkshop"
+{
+	"appid"		"294100"
+	"WorkshopItemsInstalled"
+	{
+		"111"
+		{
+			"size"		"2048"
+			"timeupdated"		"1700000000"
+			"manifest"		"55"
+		}
+	}
+	"WorkshopItemDetails"
+	{
+		"111"
+		{
+			"manifest"		"55"
+			"timeupdated"		"1700000000"
+			"latest_timeupdated"		"1700000500"
+			"latest_manifest"		"56"
+		}
+		"222"
+		{
+			"manifest"		"77"
+			"timeupdated"		"1690000000"
+		}
+	}
+}
+"#;
+
+        let items = parse_steam_workshop_manifest(contents, content_directory.path())
+            .expect("workshop manifest should parse");
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].published_file_id, "111");
+        assert!(items[0].installed);
+        assert!(items[0].needs_update);
+        assert_eq!(items[0].size_bytes, Some(2048));
+        assert_eq!(items[1].published_file_id, "222");
+        assert!(!items[1].installed);
+        assert_eq!(items[1].path, None);
+    }
+}
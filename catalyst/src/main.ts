@@ -116,7 +116,16 @@ const startSteamLogin = async (): Promise<void> => {
   try {
     setPendingState(true);
 
-    const result = await ipcService.startSteamAuth();
+    let result = await ipcService.startSteamAuth();
+    if (result.twoFactorRequired) {
+      const code = window.prompt("Enter the code from your authenticator app or a recovery code.");
+      if (!code) {
+        setStatusMessage("Sign-in cancelled.");
+        setPendingState(false);
+        return;
+      }
+      result = await ipcService.verifyTwoFactorLogin({ code });
+    }
     const steamId = result.user.steamId ?? "unknown";
     window.history.replaceState(
       {},
@@ -126,6 +126,8 @@ const FILTER_TEMPLATE = `
             <button type="button" class="filter-select-option" role="option" data-value="alphabetical-reverse">Alphabetical (reverse)</button>
             <button type="button" class="filter-select-option" role="option" data-value="least-played">Least Played</button>
             <button type="button" class="filter-select-option" role="option" data-value="most-played">Most Played</button>
+            <button type="button" class="filter-select-option" role="option" data-value="recently-played">Recently Played</button>
+            <button type="button" class="filter-select-option" role="option" data-value="review-score">Review Score</button>
           </div>
         </div>
       </div>
@@ -196,6 +198,9 @@ const FILTER_TEMPLATE = `
                 <button type="button" class="filter-select-option" role="option" data-value="favorites">Favorites</button>
                 <button type="button" class="filter-select-option" role="option" data-value="recently-played">Recently Played</button>
                 <button type="button" class="filter-select-option" role="option" data-value="never-played">Never Played</button>
+                <button type="button" class="filter-select-option" role="option" data-value="completed">Completed</button>
+                <button type="button" class="filter-select-option" role="option" data-value="controller-support">Controller Support</button>
+                <button type="button" class="filter-select-option" role="option" data-value="vr">VR</button>
               </div>
             </div>
           </div>
@@ -289,6 +294,10 @@ const FILTER_TEMPLATE = `
                 <button type="button" class="filter-select-option" role="option" data-value="game">Games</button>
                 <button type="button" class="filter-select-option" role="option" data-value="demo">Demos</button>
                 <button type="button" class="filter-select-option" role="option" data-value="dlc">DLCs</button>
+                <button type="button" class="filter-select-option" role="option" data-value="music">Soundtracks</button>
+                <button type="button" class="filter-select-option" role="option" data-value="tool">Tools</button>
+                <button type="button" class="filter-select-option" role="option" data-value="application">Applications</button>
+                <button type="button" class="filter-select-option" role="option" data-value="video">Videos</button>
                 <button type="button" class="filter-select-option" role="option" data-value="unknown">Unknown</button>
               </div>
             </div>
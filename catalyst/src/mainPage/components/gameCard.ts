@@ -41,6 +41,18 @@ const formatKind = (kind: GameResponse["kind"]): string => {
   if (kind === "dlc") {
     return "DLC";
   }
+  if (kind === "music") {
+    return "Soundtrack";
+  }
+  if (kind === "tool") {
+    return "Tool";
+  }
+  if (kind === "application") {
+    return "Application";
+  }
+  if (kind === "video") {
+    return "Video";
+  }
   if (kind === "unknown") {
     return "Unknown";
   }
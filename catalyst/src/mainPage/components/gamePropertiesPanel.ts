@@ -46,6 +46,8 @@ export interface GameCompatibilityToolOption {
 export interface GameBetaAccessCodeValidationResult {
   valid: boolean;
   message: string;
+  messageCode?: string;
+  messageParams?: Record<string, string>;
   branchId?: string;
   branchName?: string;
 }
@@ -82,6 +84,22 @@ export interface GameGeneralSettings {
   language: string;
   launchOptions: string;
   steamOverlayEnabled: boolean;
+  discordPresenceEnabled: boolean;
+  preLaunchCommand: string;
+  postExitCommand: string;
+  launchHooksConfirmed: boolean;
+  launchWrappers: GameLaunchWrappersSettings;
+  environmentVariables: Record<string, string>;
+}
+
+export interface GameLaunchWrappersSettings {
+  gamemode: boolean;
+  mangohud: boolean;
+  gamescope: boolean;
+  gamescopeWidth: number | null;
+  gamescopeHeight: number | null;
+  gamescopeRefreshRate: number | null;
+  gamescopeFullscreen: boolean;
 }
 
 export interface GameCompatibilitySettings {
@@ -192,6 +210,20 @@ const DEFAULT_GENERAL_SETTINGS: GameGeneralSettings = {
   language: "English",
   launchOptions: "",
   steamOverlayEnabled: true,
+  discordPresenceEnabled: true,
+  preLaunchCommand: "",
+  postExitCommand: "",
+  launchHooksConfirmed: false,
+  launchWrappers: {
+    gamemode: false,
+    mangohud: false,
+    gamescope: false,
+    gamescopeWidth: null,
+    gamescopeHeight: null,
+    gamescopeRefreshRate: null,
+    gamescopeFullscreen: false,
+  },
+  environmentVariables: {},
 };
 const DEFAULT_COMPATIBILITY_SETTINGS: GameCompatibilitySettings = {
   forceSteamPlayCompatibilityTool: false,
@@ -287,7 +319,11 @@ const DEFAULT_CUSTOMIZATION_SETTINGS: GameCustomizationSettings = {
 };
 
 const cloneGeneralSettings = (settings: GameGeneralSettings): GameGeneralSettings => {
-  return { ...settings };
+  return {
+    ...settings,
+    launchWrappers: { ...settings.launchWrappers },
+    environmentVariables: { ...settings.environmentVariables },
+  };
 };
 
 const cloneCompatibilitySettings = (settings: GameCompatibilitySettings): GameCompatibilitySettings => {
@@ -348,17 +384,86 @@ const toRecord = (value: unknown): Record<string, unknown> | null => {
   return value as Record<string, unknown>;
 };
 
+const parseLaunchWrappersSettings = (record: Record<string, unknown> | null): GameLaunchWrappersSettings => {
+  const defaults = DEFAULT_GENERAL_SETTINGS.launchWrappers;
+  if (!record) {
+    return { ...defaults };
+  }
+
+  const readFlag = (key: keyof GameLaunchWrappersSettings): boolean => {
+    return typeof record[key] === "boolean" ? (record[key] as boolean) : false;
+  };
+  const readNumber = (key: keyof GameLaunchWrappersSettings): number | null => {
+    return typeof record[key] === "number" ? (record[key] as number) : null;
+  };
+  return {
+    gamemode: readFlag("gamemode"),
+    mangohud: readFlag("mangohud"),
+    gamescope: readFlag("gamescope"),
+    gamescopeWidth: readNumber("gamescopeWidth"),
+    gamescopeHeight: readNumber("gamescopeHeight"),
+    gamescopeRefreshRate: readNumber("gamescopeRefreshRate"),
+    gamescopeFullscreen: readFlag("gamescopeFullscreen"),
+  };
+};
+
+const parseEnvironmentVariables = (record: Record<string, unknown> | null): Record<string, string> => {
+  const variables: Record<string, string> = {};
+  if (!record) {
+    return variables;
+  }
+
+  for (const [name, value] of Object.entries(record)) {
+    if (typeof value === "string") {
+      variables[name] = value;
+    }
+  }
+  return variables;
+};
+
+const formatEnvironmentVariables = (variables: Record<string, string>): string => {
+  return Object.entries(variables)
+    .map(([name, value]) => `${name}=${value}`)
+    .join("\n");
+};
+
+const parseEnvironmentVariablesText = (text: string): Record<string, string> => {
+  const variables: Record<string, string> = {};
+  for (const line of text.split("\n")) {
+    const separatorIndex = line.indexOf("=");
+    const name = (separatorIndex < 0 ? line : line.slice(0, separatorIndex)).trim();
+    if (name.length > 0) {
+      variables[name] = separatorIndex < 0 ? "" : line.slice(separatorIndex + 1).trim();
+    }
+  }
+  return variables;
+};
+
 const parseGeneralSettings = (record: Record<string, unknown>): GameGeneralSettings => {
   return {
     steamOverlayEnabled: typeof record["steamOverlayEnabled"] === "boolean"
       ? (record["steamOverlayEnabled"] as boolean)
       : DEFAULT_GENERAL_SETTINGS.steamOverlayEnabled,
+    discordPresenceEnabled: typeof record["discordPresenceEnabled"] === "boolean"
+      ? (record["discordPresenceEnabled"] as boolean)
+      : DEFAULT_GENERAL_SETTINGS.discordPresenceEnabled,
     language: typeof record["language"] === "string" && (record["language"] as string).trim().length > 0
       ? (record["language"] as string)
       : DEFAULT_GENERAL_SETTINGS.language,
     launchOptions: typeof record["launchOptions"] === "string"
       ? (record["launchOptions"] as string)
       : DEFAULT_GENERAL_SETTINGS.launchOptions,
+    preLaunchCommand: typeof record["preLaunchCommand"] === "string"
+      ? (record["preLaunchCommand"] as string)
+      : DEFAULT_GENERAL_SETTINGS.preLaunchCommand,
+    postExitCommand: typeof record["postExitCommand"] === "string"
+      ? (record["postExitCommand"] as string)
+      : DEFAULT_GENERAL_SETTINGS.postExitCommand,
+    launchHooksConfirmed: typeof record["launchHooksConfirmed"] === "boolean"
+      ? (record["launchHooksConfirmed"] as boolean)
+      : DEFAULT_GENERAL_SETTINGS.launchHooksConfirmed,
+    launchWrappers: parseLaunchWrappersSettings(toRecord(record["launchWrappers"])),
+    environmentVariables: parseEnvironmentVariables(toRecord(record["environmentVariables"])),
   };
 };
 
@@ -1163,6 +1268,39 @@ export const createGamePropertiesPanel = (): GamePropertiesPanelController => {
     overlayToggle.append(overlayInput, overlayTrack);
     overlayField.append(overlayCopy, overlayToggle);
 
+    const discordPresenceField = document.createElement("div");
+    discordPresenceField.className = "game-properties-field game-properties-switch-field";
+
+    const discordPresenceCopy = document.createElement("div");
+    discordPresenceCopy.className = "game-properties-switch-copy";
+
+    const discordPresenceLabelText = document.createElement("p");
+    discordPresenceLabelText.className = "game-properties-switch-label";
+    discordPresenceLabelText.textContent = "Show this game in Discord";
+
+    const discordPresenceHint = document.createElement("p");
+    discordPresenceHint.className = "game-properties-field-hint";
+    discordPresenceHint.textContent = "Shares the game name and play time when Discord Rich Presence is enabled.";
+
+    discordPresenceCopy.append(discordPresenceLabelText, discordPresenceHint);
+
+    const discordPresenceToggle = document.createElement("label");
+    discordPresenceToggle.className = "game-properties-switch-control";
+
+    const discordPresenceInput = document.createElement("input");
+    discordPresenceInput.id = "game-properties-discord-presence-toggle";
+    discordPresenceInput.className = "game-properties-switch-input";
+    discordPresenceInput.type = "checkbox";
+    discordPresenceInput.checked = currentGeneralSettings.discordPresenceEnabled;
+    discordPresenceInput.setAttribute("aria-label", "Show this game in Discord");
+
+    const discordPresenceTrack = document.createElement("span");
+    discordPresenceTrack.className = "game-properties-switch-track";
+    discordPresenceTrack.setAttribute("aria-hidden", "true");
+
+    discordPresenceToggle.append(discordPresenceInput, discordPresenceTrack);
+    discordPresenceField.append(discordPresenceCopy, discordPresenceToggle);
+
     const languageField = document.createElement("div");
     languageField.className = "game-properties-field";
 
@@ -1399,7 +1537,79 @@ export const createGamePropertiesPanel = (): GamePropertiesPanelController => {
     launchOptionsHint.textContent = "Add command-line arguments to apply whenever this game launches.";
 
     launchOptionsField.append(launchOptionsLabel, launchOptions, launchOptionsHint);
-    form.append(overlayField, languageField, launchOptionsField);
+
+    const environmentVariablesField = document.createElement("div");
+    environmentVariablesField.className = "game-properties-field";
+
+    const environmentVariablesLabel = document.createElement("label");
+    environmentVariablesLabel.className = "game-properties-field-label";
+    environmentVariablesLabel.setAttribute("for", "game-properties-environment-variables");
+    environmentVariablesLabel.textContent = "Environment Variables";
+
+    const environmentVariables = document.createElement("textarea");
+    environmentVariables.id = "game-properties-environment-variables";
+    environmentVariables.className = "game-properties-launch-options text-input";
+    environmentVariables.placeholder = "e.g. PROTON_ENABLE_NVAPI=1";
+    environmentVariables.value = formatEnvironmentVariables(currentGeneralSettings.environmentVariables);
+    environmentVariables.rows = 3;
+
+    const environmentVariablesHint = document.createElement("p");
+    environmentVariablesHint.className = "game-properties-field-hint";
+    environmentVariablesHint.textContent = "One NAME=value per line, set whenever this game launches.";
+
+    environmentVariablesField.append(environmentVariablesLabel, environmentVariables, environmentVariablesHint);
+
+    const createLaunchHookField = (
+      id: string,
+      labelText: string,
+      placeholder: string,
+      value: string,
+    ): { field: HTMLDivElement; input: HTMLInputElement } => {
+      const field = document.createElement("div");
+      field.className = "game-properties-field";
+
+      const label = document.createElement("label");
+      label.className = "game-properties-field-label";
+      label.setAttribute("for", id);
+      label.textContent = labelText;
+
+      const input = document.createElement("input");
+      input.id = id;
+      input.className = "text-input";
+      input.type = "text";
+      input.placeholder = placeholder;
+      input.value = value;
+
+      field.append(label, input);
+      return { field, input };
+    };
+
+    const preLaunchHook = createLaunchHookField(
+      "game-properties-pre-launch-command",
+      "Before Launch",
+      "e.g. mod-manager --deploy %APPID%",
+      currentGeneralSettings.preLaunchCommand,
+    );
+    const postExitHook = createLaunchHookField(
+      "game-properties-post-exit-command",
+      "After Exit",
+      "e.g. notify-send \"%GAMENAME% closed\"",
+      currentGeneralSettings.postExitCommand,
+    );
+    const launchHooksHint = document.createElement("p");
+    launchHooksHint.className = "game-properties-field-hint";
+    launchHooksHint.textContent = "Shell commands run around each session. %APPID%, %PROVIDER% and %GAMENAME% are replaced.";
+    postExitHook.field.append(launchHooksHint);
+
+    form.append(
+      overlayField,
+      discordPresenceField,
+      languageField,
+      launchOptionsField,
+      environmentVariablesField,
+      preLaunchHook.field,
+      postExitHook.field,
+    );
     tabPanel.append(heading, description, form);
 
     overlayInput.addEventListener("change", () => {
@@ -1410,6 +1620,14 @@ export const createGamePropertiesPanel = (): GamePropertiesPanelController => {
       persistCurrentSettings();
     });
 
+    discordPresenceInput.addEventListener("change", () => {
+      currentGeneralSettings = {
+        ...currentGeneralSettings,
+        discordPresenceEnabled: discordPresenceInput.checked,
+      };
+      persistCurrentSettings();
+    });
+
     launchOptions.addEventListener("input", () => {
       currentGeneralSettings = {
         ...currentGeneralSettings,
@@ -1417,6 +1635,41 @@ export const createGamePropertiesPanel = (): GamePropertiesPanelController => {
       };
       persistCurrentSettings();
     });
+
+    environmentVariables.addEventListener("change", () => {
+      currentGeneralSettings = {
+        ...currentGeneralSettings,
+        environmentVariables: parseEnvironmentVariablesText(environmentVariables.value),
+      };
+      persistCurrentSettings();
+    });
+
+    const handleLaunchHookChange = (): void => {
+      const preLaunchCommand = preLaunchHook.input.value.trim();
+      const postExitCommand = postExitHook.input.value.trim();
+      const hasLaunchHooks = preLaunchCommand.length > 0 || postExitCommand.length > 0;
+      let launchHooksConfirmed = currentGeneralSettings.launchHooksConfirmed;
+      if (hasLaunchHooks && !launchHooksConfirmed) {
+        launchHooksConfirmed = window.confirm(
+          "Launch hooks run these commands on your computer every time the game starts or exits. Enable them?",
+        );
+        if (!launchHooksConfirmed) {
+          preLaunchHook.input.value = currentGeneralSettings.preLaunchCommand;
+          postExitHook.input.value = currentGeneralSettings.postExitCommand;
+          return;
+        }
+      }
+
+      currentGeneralSettings = {
+        ...currentGeneralSettings,
+        preLaunchCommand,
+        postExitCommand,
+        launchHooksConfirmed,
+      };
+      persistCurrentSettings();
+    };
+    preLaunchHook.input.addEventListener("change", handleLaunchHookChange);
+    postExitHook.input.addEventListener("change", handleLaunchHookChange);
   };
 
   const renderCompatibilityTab = (): void => {
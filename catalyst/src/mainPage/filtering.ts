@@ -39,6 +39,13 @@ const hasExactTag = (values: string[] | undefined, expected: string): boolean =>
   return values.some((value) => canonicalizeTag(value) === target);
 };
 
+const getCollectionPosition = (game: GameResponse, collection: string): number | undefined => {
+  const target = canonicalizeTag(collection);
+  const entry = Object.entries(game.collectionPositions ?? {})
+    .find(([name]) => canonicalizeTag(name) === target);
+  return entry?.[1];
+};
+
 const includesTagText = (values: string[] | undefined, expected: string): boolean => {
   if (expected.trim().length === 0) {
     return true;
@@ -75,15 +82,18 @@ export const applyLibraryFilters = (
 
     const source = getSourceFromProvider(game.provider);
     const lastPlayedAt = game.lastPlayedAt ? parseDate(game.lastPlayedAt) : 0;
-    const fallbackRecentlyPlayed = game.playtimeMinutes > 0 && parseDate(game.lastSyncedAt) >= thirtyDaysAgo;
+    const fallbackRecentlyPlayed = (game.playtime2WeeksMinutes ?? 0) > 0
+      || (game.playtimeMinutes > 0 && parseDate(game.lastSyncedAt) >= thirtyDaysAgo);
     const recentlyPlayed = lastPlayedAt > 0 ? lastPlayedAt >= thirtyDaysAgo : fallbackRecentlyPlayed;
 
     if (
       searchTerm.length > 0
       && !normalize(game.name).includes(searchTerm)
+      && !normalize(game.originalName ?? "").includes(searchTerm)
       && !normalize(game.provider).includes(searchTerm)
       && !normalize(game.kind).includes(searchTerm)
       && !includesTagText(game.steamTags, searchTerm)
+      && !includesTagText(game.userTags, searchTerm)
       && !includesTagText(game.collections, searchTerm)
     ) {
       return false;
@@ -115,6 +125,18 @@ export const applyLibraryFilters = (
       return false;
     }
 
+    if (filters.filterBy === "completed" && game.completed !== true) {
+      return false;
+    }
+
+    if (filters.filterBy === "controller-support" && game.controllerSupport !== "Full") {
+      return false;
+    }
+
+    if (filters.filterBy === "vr" && !game.vrSupport) {
+      return false;
+    }
+
     if (filters.platform !== "all" && !hasTag(game.platforms, filters.platform)) {
       return false;
     }
@@ -151,10 +173,37 @@ export const applyLibraryFilters = (
       return left.playtimeMinutes - right.playtimeMinutes;
     }
 
+    if (filters.sortBy === "recently-played") {
+      const lastPlayed = (game: GameResponse) => (game.lastPlayedAt ? parseDate(game.lastPlayedAt) : 0);
+      const recencyDifference = lastPlayed(right) - lastPlayed(left)
+        || (right.playtime2WeeksMinutes ?? 0) - (left.playtime2WeeksMinutes ?? 0);
+      if (recencyDifference !== 0) {
+        return recencyDifference;
+      }
+    }
+
+    if (filters.sortBy === "review-score") {
+      // Metacritic when available, otherwise the Steam user review percentage; unrated games sink.
+      const reviewScore = (game: GameResponse) => game.metacriticScore ?? game.reviewPositivePercent ?? -1;
+      const scoreDifference = reviewScore(right) - reviewScore(left);
+      if (scoreDifference !== 0) {
+        return scoreDifference;
+      }
+    }
+
     if (filters.sortBy === "alphabetical-reverse") {
       return right.name.localeCompare(left.name, undefined, { sensitivity: "base" });
     }
 
+    // A collection ordered by hand keeps that order; games without a position follow by name.
+    if (filters.sortBy === "alphabetical" && collectionFilter.length > 0 && !showOnlyHiddenGames) {
+      const leftPosition = getCollectionPosition(left, collectionFilter) ?? Number.POSITIVE_INFINITY;
+      const rightPosition = getCollectionPosition(right, collectionFilter) ?? Number.POSITIVE_INFINITY;
+      if (leftPosition !== rightPosition) {
+        return leftPosition - rightPosition;
+      }
+    }
+
     return left.name.localeCompare(right.name, undefined, { sensitivity: "base" });
   });
 };
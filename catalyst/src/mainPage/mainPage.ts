@@ -20,7 +20,9 @@ import {
 } from "./types";
 import { ipcService } from "../shared/ipc/client";
 import { normalizeAppError } from "../shared/ipc/errors";
+import { listen } from "@tauri-apps/api/event";
 import type {
+  DeepLinkActionPayload,
   GameCustomizationArtworkPayload,
   GameInstallLocationPayload,
   GameInstallationDetailsPayload,
@@ -141,6 +143,7 @@ const GRID_WHEEL_SMOOTHING_LERP = 0.16;
 const GRID_WHEEL_SMOOTHING_MAX_STEP_PX = 180;
 const GRID_WHEEL_SMOOTHING_MIN_WHEEL_DELTA_PX = 8;
 const GRID_CARD_WIDTH_STORAGE_KEY = "catalyst.library.gridCardMinWidthPx";
+const IMPORT_STEAM_FAVORITES_STORAGE_KEY = "catalyst.library.importSteamFavorites";
 const APP_NAME = "Catalyst";
 const DOWNLOAD_POLL_INTERVAL_MS = 2500;
 const DOWNLOAD_ETA_SMOOTHING_FACTOR = 0.35;
@@ -877,6 +880,16 @@ const readStoredGridCardWidthPx = (): number | null => {
   }
 };
 
+// Maps Steam's Favorites collection onto Catalyst favorites during collection import; off unless
+// set to "true".
+const readImportSteamFavorites = (): boolean => {
+  try {
+    return localStorage.getItem(IMPORT_STEAM_FAVORITES_STORAGE_KEY) === "true";
+  } catch {
+    return false;
+  }
+};
+
 const persistGridCardWidthPx = (value: number): void => {
   try {
     localStorage.setItem(GRID_CARD_WIDTH_STORAGE_KEY, `${value}`);
@@ -2663,7 +2676,10 @@ const refreshLibrary = async (syncBeforeLoad = false, importSteamCollections = f
 
     if (syncBeforeLoad && store.steamLinked) {
       try {
-        await ipcService.syncSteamLibrary();
+        const syncResult = await ipcService.syncSteamLibrary();
+        if (syncResult.warning) {
+          console.warn(`[library/sync] ${syncResult.warning}`);
+        }
       } catch (error) {
         const appError = normalizeAppError(error, "Steam sync failed. Loading cached library.");
         console.error(`[library/sync] ${appError.kind}:${appError.code} ${appError.message}`);
@@ -2671,7 +2687,12 @@ const refreshLibrary = async (syncBeforeLoad = false, importSteamCollections = f
 
       if (importSteamCollections) {
         try {
-          await ipcService.importSteamCollections();
+          const importResult = await ipcService.importSteamCollections({
+            importFavorites: readImportSteamFavorites(),
+          });
+          if (importResult.favoritesLinked > 0) {
+            console.debug(`[collections/import_steam] linked ${importResult.favoritesLinked} Steam favorites`);
+          }
         } catch (error) {
           const appError = normalizeAppError(error, "Steam collection import failed.");
           console.error(`[collections/import_steam] ${appError.kind}:${appError.code} ${appError.message}`);
@@ -2688,7 +2709,7 @@ const refreshLibrary = async (syncBeforeLoad = false, importSteamCollections = f
     }
 
     const [library, collections] = await Promise.all([
-      ipcService.getLibrary(),
+      ipcService.getLibrary({ includeHidden: true }),
       listCollectionsForUser().catch(() => []),
     ]);
     // (removed debug log)
@@ -2740,6 +2761,42 @@ refreshLibraryButton.addEventListener("click", () => {
   void refreshLibrary(true, true);
 });
 
+// Any page can open a catalyst:// link, so a `play` link only launches once the user confirms it.
+const handleDeepLinkAction = async (link: DeepLinkActionPayload): Promise<void> => {
+  const game = store.allGames.find(
+    candidate => candidate.provider === link.provider && candidate.externalId === link.externalId,
+  );
+  if (link.action === "open_game") {
+    if (game) {
+      openGameDetails(game.id, true);
+    }
+    return;
+  }
+
+  const shouldPlay = await confirmationDialog.open({
+    title: "Launch Game",
+    description: `A link asked Catalyst to launch "${game?.name ?? link.externalId}". Play it now?`,
+    confirmLabel: "Play",
+  });
+  if (!shouldPlay) {
+    return;
+  }
+
+  try {
+    await ipcService.playGame({ provider: link.provider, externalId: link.externalId });
+  } catch (error) {
+    const appError = normalizeAppError(error, "Could not launch game.");
+    showLauncherToast(appError.message, "error");
+    console.error(`[deep-link/play] ${appError.kind}:${appError.code} ${appError.message}`);
+  }
+};
+
+void listen<DeepLinkActionPayload>("deep-link", event => {
+  void handleDeepLinkAction(event.payload);
+}).catch(error => {
+  console.warn("Could not register the deep link listener:", error);
+});
+
 window.addEventListener("resize", applyLibraryAspectSoftLock);
 window.addEventListener("beforeunload", stopDownloadPolling);
 window.addEventListener("beforeunload", stopLibraryLastUpdatedTimer);
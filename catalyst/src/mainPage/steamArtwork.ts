@@ -1,3 +1,4 @@
+import { convertFileSrc } from "@tauri-apps/api/core";
 import type { GameResponse } from "./types";
 
 export type SteamLibraryArtworkKind = "cover" | "background" | "logo" | "wide-cover";
@@ -45,6 +46,11 @@ export const getSteamArtworkCandidates = (
   const normalizedProvider = game.provider.trim().toLowerCase();
   const normalizedExternalId = game.externalId.trim();
 
+  const customArtworkPath = game.customArtworkPath?.trim();
+  if (customArtworkPath && kind !== "logo") {
+    addUniqueCandidate(convertFileSrc(customArtworkPath), seen, candidates);
+  }
+
   if (isSteamAppGame(game)) {
     const appId = normalizedExternalId;
     if (kind === "cover") {
@@ -5,24 +5,40 @@ export interface PublicUser {
   email: string;
   steamLinked: boolean;
   steamId?: string;
+  steamPersonaName?: string;
+  steamAvatarUrl?: string;
+  steamProfileUrl?: string;
 }
 
 export interface GameResponse {
   id: string;
   provider: string;
   externalId: string;
+  /** The user's display title when set, otherwise the synced name. */
   name: string;
+  /** The synced name, only set when a display title overrides it. */
+  originalName?: string | null;
   kind: GameKind;
   playtimeMinutes: number;
+  playtime2WeeksMinutes?: number;
   artworkUrl?: string;
+  /** Local path of artwork the user uploaded; takes precedence over provider artwork. */
+  customArtworkPath?: string | null;
   lastSyncedAt: string;
   installed: boolean;
   favorite: boolean;
   lastPlayedAt?: string;
+  addedAt?: string;
+  running?: boolean;
   platforms?: string[];
   genres?: string[];
+  categories?: string[];
   steamTags?: string[];
+  /** Personal tags the user added; unlike `steamTags`, any provider's games can have them. */
+  userTags?: string[];
   collections?: string[];
+  /** Positions in collections ordered by hand, keyed by collection name. */
+  collectionPositions?: Record<string, number>;
   hideInLibrary?: boolean;
   // Enriched store metadata (optional)
   developers?: string[];
@@ -34,9 +50,17 @@ export interface GameResponse {
   features?: Feature[];
   hasAchievements?: boolean;
   achievementsCount?: number | null;
+  achievementsUnlocked?: number | null;
+  completed?: boolean;
   hasCloudSaves?: boolean;
   cloudDetails?: string | null;
   controllerSupport?: string | null;
+  vrSupport?: "Only" | "Supported" | null;
+  metacriticScore?: number | null;
+  recommendationsTotal?: number | null;
+  reviewScoreDescription?: string | null;
+  reviewPositivePercent?: number | null;
+  reviewTotal?: number | null;
 }
 
 export interface Feature {
@@ -51,12 +75,26 @@ export interface CollectionResponse {
   name: string;
   gameCount: number;
   containsGame: boolean;
+  coverImagePath?: string | null;
+  /** `#rrggbb` */
+  accentColor?: string | null;
+}
+
+export interface MergedGameResponse {
+  groupKey: string;
+  name: string;
+  preferredProvider: string;
+  preferredGameId: string;
+  installed: boolean;
+  playtimeMinutes: number;
+  games: GameResponse[];
 }
 
 export interface LibraryResponse {
   userId: string;
   total: number;
   games: GameResponse[];
+  merged: MergedGameResponse[] | null;
 }
 
 export type FilterByOption =
@@ -65,10 +103,13 @@ export type FilterByOption =
   | "not-installed"
   | "favorites"
   | "recently-played"
-  | "never-played";
+  | "never-played"
+  | "completed"
+  | "controller-support"
+  | "vr";
 export type PlatformFilter = "all" | "windows" | "macos" | "linux";
 export type SourceFilter = "all" | "steam" | "epic-games";
-export type GameKind = "game" | "demo" | "dlc" | "unknown";
+export type GameKind = "game" | "demo" | "dlc" | "music" | "tool" | "application" | "video" | "unknown";
 export type GameKindFilter = "all" | GameKind;
 export type GenreFilter =
   | "all"
@@ -86,7 +127,9 @@ export type SortOption =
   | "alphabetical"
   | "alphabetical-reverse"
   | "least-played"
-  | "most-played";
+  | "most-played"
+  | "recently-played"
+  | "review-score";
 
 export interface LibraryFilters {
   search: string;
@@ -2,17 +2,69 @@ import { invoke } from "@tauri-apps/api/core";
 import { IpcError, normalizeAppError } from "./errors";
 import type {
   AddGameToCollectionRequest,
+  AnnotateGameSessionRequest,
+  BulkUpdatePolicyRequest,
+  ClearPrivateGamesPinRequest,
   CreateCollectionRequest,
+  CreateGameShortcutRequest,
   DeleteCollectionRequest,
+  DeleteGameCompatdataRequest,
+  FollowDiscoverySourceRequest,
+  GameUserTagRequest,
+  GetDeveloperReleasesRequest,
+  GetDiscoveryFeedRequest,
+  GetDownloadHistoryRequest,
+  GetGameSessionsRequest,
+  GetLibraryRequest,
+  GetLibraryStatsRequest,
+  GetPlaytimeTimelineRequest,
+  GetSteamBadgesRequest,
+  ImportLutrisLibraryRequest,
+  ImportPlayniteLibraryRequest,
+  ImportSteamCollectionsRequest,
   InstallGameRequest,
+  InstallGameWithSteamCmdRequest,
   IpcCommandName,
   IpcContracts,
+  LinkLibraryGamesRequest,
   ListCollectionsForGameRequest,
+  ListCollectionsRequest,
+  ListNotificationsRequest,
+  ListSteamTagsRequest,
+  MarkNotificationReadRequest,
+  PlayGameRemoteRequest,
   ProviderExternalIdRequest,
+  RefreshAchievementsRequest,
   RenameCollectionRequest,
+  ReorderDownloadQueueRequest,
+  RestoreSteamConfigBackupRequest,
+  RunGamePrefixToolRequest,
+  SaveGameNotesRequest,
+  SearchLibraryRequest,
+  SetApiServerSettingsRequest,
+  SetCollectionAccentColorRequest,
+  SetCollectionCoverImageRequest,
+  SetCollectionGameOrderRequest,
+  SetDownloadRateLimitRequest,
+  SetDownloadScheduleRequest,
+  SetGameCustomArtworkRequest,
+  SetGameDisplayNameRequest,
   SetGameFavoriteRequest,
   SetGamePrivacySettingsRequest,
   SetGamePropertiesSettingsRequest,
+  SetGamesFavoriteRequest,
+  SetGamesHiddenRequest,
+  SetGamingPcSettingsRequest,
+  SetHttpProxySettingsRequest,
+  SetLocaleRequest,
+  SetPrivateGamesPinRequest,
+  SetSteamCmdSettingsRequest,
+  SteamDownloadRequest,
+  SuggestGameRequest,
+  TestProxyConnectionRequest,
+  TwoFactorCodeRequest,
+  UnfollowDiscoverySourceRequest,
+  UnlockPrivateGamesRequest,
   ValidateGameBetaAccessCodeRequest,
 } from "./contracts";
 
@@ -39,49 +91,136 @@ export const callCommand = async <K extends IpcCommandName>(
 export const ipcService = {
   getSession: () => callCommand("get_session"),
   startSteamAuth: () => callCommand("start_steam_auth"),
+  cancelSteamAuth: () => callCommand("cancel_steam_auth"),
+  verifyTwoFactorLogin: (payload: TwoFactorCodeRequest) => callCommand("verify_2fa_login", payload),
   startLocalSteamScan: () => callCommand("start_local_steam_scan"),
   logout: () => callCommand("logout"),
   syncSteamLibrary: () => callCommand("sync_steam_library"),
-  importSteamCollections: () => callCommand("import_steam_collections"),
-  getLibrary: () => callCommand("get_library"),
-  listCollections: (payload?: ListCollectionsForGameRequest) => callCommand("list_collections", payload),
+  getLibraryConflicts: () => callCommand("get_library_conflicts"),
+  refreshAchievements: (payload?: RefreshAchievementsRequest) => callCommand("refresh_achievements", payload),
+  searchLibrary: (payload: SearchLibraryRequest) => callCommand("search_library", payload),
+  getGameNotes: (payload: ProviderExternalIdRequest) => callCommand("get_game_notes", payload),
+  saveGameNotes: (payload: SaveGameNotesRequest) => callCommand("save_game_notes", payload),
+  setGameDisplayName: (payload: SetGameDisplayNameRequest) => callCommand("set_game_display_name", payload),
+  addGameUserTag: (payload: GameUserTagRequest) => callCommand("add_game_user_tag", payload),
+  removeGameUserTag: (payload: GameUserTagRequest) => callCommand("remove_game_user_tag", payload),
+  importSteamCollections: (payload?: ImportSteamCollectionsRequest) => callCommand("import_steam_collections", payload),
+  getLibrary: (payload?: GetLibraryRequest) => callCommand("get_library", payload),
+  listSteamTags: (payload?: ListSteamTagsRequest) => callCommand("list_steam_tags", payload),
+  listHiddenGames: () => callCommand("list_hidden_games"),
+  linkLibraryGames: (payload: LinkLibraryGamesRequest) => callCommand("link_library_games", payload),
+  unlinkLibraryGame: (payload: ProviderExternalIdRequest) => callCommand("unlink_library_game", payload),
+  setPreferredLaunchProvider: (payload: ProviderExternalIdRequest) =>
+    callCommand("set_preferred_launch_provider", payload),
+  importPlayniteLibrary: (payload: ImportPlayniteLibraryRequest) => callCommand("import_playnite_library", payload),
+  importLutrisLibrary: (payload?: ImportLutrisLibraryRequest) => callCommand("import_lutris_library", payload),
+  getLibraryStats: (payload?: GetLibraryStatsRequest) => callCommand("get_library_stats", payload),
+  getPlaytimeTimeline: (payload?: GetPlaytimeTimelineRequest) => callCommand("get_playtime_timeline", payload),
+  suggestGame: (payload?: SuggestGameRequest) => callCommand("suggest_game", payload),
+  listNotifications: (payload?: ListNotificationsRequest) => callCommand("list_notifications", payload),
+  markNotificationRead: (payload?: MarkNotificationReadRequest) => callCommand("mark_notification_read", payload),
+  listCollections: (payload?: ListCollectionsRequest | ListCollectionsForGameRequest) =>
+    callCommand("list_collections", payload),
   createCollection: (payload: CreateCollectionRequest) => callCommand("create_collection", payload),
   renameCollection: (payload: RenameCollectionRequest) => callCommand("rename_collection", payload),
+  setCollectionCoverImage: (payload: SetCollectionCoverImageRequest) =>
+    callCommand("set_collection_cover_image", payload),
+  setCollectionAccentColor: (payload: SetCollectionAccentColorRequest) =>
+    callCommand("set_collection_accent_color", payload),
   deleteCollection: (payload: DeleteCollectionRequest) => callCommand("delete_collection", payload),
   addGameToCollection: (payload: AddGameToCollectionRequest) => callCommand("add_game_to_collection", payload),
+  setCollectionGameOrder: (payload: SetCollectionGameOrderRequest) =>
+    callCommand("set_collection_game_order", payload),
   listGameLanguages: (payload: ProviderExternalIdRequest) => callCommand("list_game_languages", payload),
   listGameCompatibilityTools: (payload: ProviderExternalIdRequest) =>
     callCommand("list_game_compatibility_tools", payload),
   listGameVersionBetas: (payload: ProviderExternalIdRequest) => callCommand("list_game_versions_betas", payload),
   validateGameBetaAccessCode: (payload: ValidateGameBetaAccessCodeRequest) =>
     callCommand("validate_game_beta_access_code", payload),
+  listSteamConfigBackups: () => callCommand("list_steam_config_backups"),
+  restoreSteamConfigBackup: (payload: RestoreSteamConfigBackupRequest) =>
+    callCommand("restore_steam_config_backup", payload),
   getGamePrivacySettings: (payload: ProviderExternalIdRequest) => callCommand("get_game_privacy_settings", payload),
   setGamePrivacySettings: (payload: SetGamePrivacySettingsRequest) => callCommand("set_game_privacy_settings", payload),
+  setGamesHidden: (payload: SetGamesHiddenRequest) => callCommand("set_games_hidden", payload),
+  getPrivateGamesLockStatus: () => callCommand("get_private_games_lock_status"),
+  setPrivateGamesPin: (payload: SetPrivateGamesPinRequest) => callCommand("set_private_games_pin", payload),
+  clearPrivateGamesPin: (payload: ClearPrivateGamesPinRequest) => callCommand("clear_private_games_pin", payload),
+  unlockPrivateGames: (payload: UnlockPrivateGamesRequest) => callCommand("unlock_private_games", payload),
+  lockPrivateGames: () => callCommand("lock_private_games"),
   clearGameOverlayData: (payload: ProviderExternalIdRequest) => callCommand("clear_game_overlay_data", payload),
   getGameInstallationDetails: (payload: ProviderExternalIdRequest) =>
     callCommand("get_game_installation_details", payload),
   getGameCustomizationArtwork: (payload: ProviderExternalIdRequest) =>
     callCommand("get_game_customization_artwork", payload),
+  setGameCustomArtwork: (payload: SetGameCustomArtworkRequest) => callCommand("set_game_custom_artwork", payload),
+  clearGameCustomArtwork: (payload: ProviderExternalIdRequest) => callCommand("clear_game_custom_artwork", payload),
   getGameStoreMetadata: (payload: ProviderExternalIdRequest) =>
     callCommand("get_game_store_metadata", payload),
   listGameInstallLocations: (payload: ProviderExternalIdRequest) => callCommand("list_game_install_locations", payload),
   getGameInstallSizeEstimate: (payload: ProviderExternalIdRequest) =>
     callCommand("get_game_install_size_estimate", payload),
+  getPendingUpdateSize: (payload: ProviderExternalIdRequest) => callCommand("get_pending_update_size", payload),
   listSteamDownloads: () => callCommand("list_steam_downloads"),
+  pauseDownload: (payload: SteamDownloadRequest) => callCommand("pause_download", payload),
+  resumeDownload: (payload: SteamDownloadRequest) => callCommand("resume_download", payload),
+  reorderDownloadQueue: (payload: ReorderDownloadQueueRequest) => callCommand("reorder_download_queue", payload),
+  getDownloadHistory: (payload?: GetDownloadHistoryRequest) => callCommand("get_download_history", payload),
+  getDownloadRateLimit: () => callCommand("get_download_rate_limit"),
+  setDownloadRateLimit: (payload: SetDownloadRateLimitRequest) => callCommand("set_download_rate_limit", payload),
+  getLocale: () => callCommand("get_locale"),
+  setLocale: (payload: SetLocaleRequest) => callCommand("set_locale", payload),
+  getDiscoveryFeed: (payload?: GetDiscoveryFeedRequest) => callCommand("get_discovery_feed", payload),
+  refreshDiscoveryFeed: () => callCommand("refresh_discovery_feed"),
+  followDiscoverySource: (payload: FollowDiscoverySourceRequest) => callCommand("follow_discovery_source", payload),
+  unfollowDiscoverySource: (payload: UnfollowDiscoverySourceRequest) =>
+    callCommand("unfollow_discovery_source", payload),
+  getDeveloperReleases: (payload?: GetDeveloperReleasesRequest) => callCommand("get_developer_releases", payload),
+  getDownloadSchedule: () => callCommand("get_download_schedule"),
+  setDownloadSchedule: (payload: SetDownloadScheduleRequest) => callCommand("set_download_schedule", payload),
+  getSteamCmdSettings: () => callCommand("get_steamcmd_settings"),
+  setSteamCmdSettings: (payload: SetSteamCmdSettingsRequest) => callCommand("set_steamcmd_settings", payload),
+  getApiServerSettings: () => callCommand("get_api_server_settings"),
+  setApiServerSettings: (payload: SetApiServerSettingsRequest) => callCommand("set_api_server_settings", payload),
+  regenerateApiServerToken: () => callCommand("regenerate_api_server_token"),
+  getGamingPcSettings: () => callCommand("get_gaming_pc_settings"),
+  setGamingPcSettings: (payload: SetGamingPcSettingsRequest) => callCommand("set_gaming_pc_settings", payload),
+  getHttpProxySettings: () => callCommand("get_http_proxy_settings"),
+  setHttpProxySettings: (payload: SetHttpProxySettingsRequest) => callCommand("set_http_proxy_settings", payload),
+  testProxyConnection: (payload?: TestProxyConnectionRequest) => callCommand("test_proxy_connection", payload),
   getGamePropertiesSettings: (payload: ProviderExternalIdRequest) =>
     callCommand("get_game_properties_settings", payload),
   setGamePropertiesSettings: (payload: SetGamePropertiesSettingsRequest) =>
     callCommand("set_game_properties_settings", payload),
+  bulkSetUpdatePolicy: (payload: BulkUpdatePolicyRequest) => callCommand("bulk_set_update_policy", payload),
   browseGameInstalledFiles: (payload: ProviderExternalIdRequest) =>
     callCommand("browse_game_installed_files", payload),
   backupGameFiles: (payload: ProviderExternalIdRequest) => callCommand("backup_game_files", payload),
   verifyGameFiles: (payload: ProviderExternalIdRequest) => callCommand("verify_game_files", payload),
+  getGameVerificationStatus: (payload: ProviderExternalIdRequest) =>
+    callCommand("get_game_verification_status", payload),
   addGameDesktopShortcut: (payload: ProviderExternalIdRequest) =>
     callCommand("add_game_desktop_shortcut", payload),
+  createGameShortcut: (payload: CreateGameShortcutRequest) => callCommand("create_game_shortcut", payload),
+  getGameSessions: (payload: GetGameSessionsRequest) => callCommand("get_game_sessions", payload),
+  annotateGameSession: (payload: AnnotateGameSessionRequest) => callCommand("annotate_game_session", payload),
+  getGameCardStatus: (payload: ProviderExternalIdRequest) => callCommand("get_game_card_status", payload),
+  getSteamBadges: (payload?: GetSteamBadgesRequest) => callCommand("get_steam_badges", payload),
   openGameRecordingSettings: (payload: ProviderExternalIdRequest) =>
     callCommand("open_game_recording_settings", payload),
   uninstallGame: (payload: ProviderExternalIdRequest) => callCommand("uninstall_game", payload),
   installGame: (payload: InstallGameRequest) => callCommand("install_game", payload),
+  installGameWithSteamCmd: (payload: InstallGameWithSteamCmdRequest) =>
+    callCommand("install_game_with_steamcmd", payload),
+  listInstallJobs: () => callCommand("list_install_jobs"),
   playGame: (payload: ProviderExternalIdRequest) => callCommand("play_game", payload),
+  playGameRemote: (payload: PlayGameRemoteRequest) => callCommand("play_game_remote", payload),
+  listRemotePlayHosts: () => callCommand("list_remote_play_hosts"),
+  wakeGamingPc: () => callCommand("wake_gaming_pc"),
+  startGamingPcDownload: (payload: ProviderExternalIdRequest) => callCommand("start_gaming_pc_download", payload),
+  openGameProtonPrefix: (payload: ProviderExternalIdRequest) => callCommand("open_game_proton_prefix", payload),
+  runGamePrefixTool: (payload: RunGamePrefixToolRequest) => callCommand("run_game_prefix_tool", payload),
+  deleteGameCompatdata: (payload: DeleteGameCompatdataRequest) => callCommand("delete_game_compatdata", payload),
   setGameFavorite: (payload: SetGameFavoriteRequest) => callCommand("set_game_favorite", payload),
+  setGamesFavorite: (payload: SetGamesFavoriteRequest) => callCommand("set_games_favorite", payload),
 };
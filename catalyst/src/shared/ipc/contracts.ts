@@ -4,7 +4,7 @@ import type {
   GamePropertiesPersistedSettings,
   GameVersionBetaOption,
 } from "../../mainPage/components/gamePropertiesPanel";
-import type { CollectionResponse, LibraryResponse, PublicUser } from "../../mainPage/types";
+import type { CollectionResponse, LibraryResponse, MergedGameResponse, PublicUser } from "../../mainPage/types";
 
 export type AppErrorKind = "validation" | "unauthorized" | "not_found" | "conflict" | "external" | "internal";
 
@@ -12,11 +12,27 @@ export interface AppErrorPayload {
   kind: AppErrorKind;
   code: string;
   message: string;
+  messageCode?: string;
+  messageParams?: Record<string, string>;
+}
+
+export interface LocaleResponse {
+  locale: string;
+  supportedLocales: string[];
+}
+
+export interface SetLocaleRequest {
+  locale: string;
 }
 
 export interface SteamAuthResponse {
   user: PublicUser;
   syncedGames: number;
+  twoFactorRequired: boolean;
+}
+
+export interface TwoFactorCodeRequest {
+  code: string;
 }
 
 export interface GamePrivacySettingsPayload {
@@ -50,6 +66,11 @@ export interface GameStoreMetadataPayload {
   cloudDetails?: string | null;
   controllerSupport?: string | null;
   features?: FeaturePayload[];
+  metacriticScore?: number | null;
+  recommendationsTotal?: number | null;
+  reviewScoreDescription?: string | null;
+  reviewPositivePercent?: number | null;
+  reviewTotal?: number | null;
 }
 
 export interface FeaturePayload {
@@ -74,6 +95,215 @@ export interface SteamDownloadProgressPayload {
   bytesTotal?: number;
   progressPercent?: number;
   progressSource?: string;
+  paused: boolean;
+  queuePosition?: number;
+}
+
+export interface SteamDownloadRequest {
+  externalId: string;
+}
+
+export interface ReorderDownloadQueueRequest {
+  externalIds: string[];
+}
+
+export interface DownloadRateLimitPayload {
+  kbps?: number;
+  steamConfigPath: string;
+}
+
+export interface DownloadHistoryEntryPayload {
+  appId: string;
+  name?: string | null;
+  startedAt: string;
+  finishedAt?: string | null;
+  bytesDownloaded: number;
+  bytesTotal: number;
+  status: "completed" | "canceled" | "in-progress";
+}
+
+export interface MonthlyBandwidthPayload {
+  /** `YYYY-MM`, local time. */
+  month: string;
+  bytesDownloaded: number;
+  downloads: number;
+}
+
+export interface DownloadHistoryPayload {
+  entries: DownloadHistoryEntryPayload[];
+  monthlyTotals: MonthlyBandwidthPayload[];
+}
+
+export interface GetDownloadHistoryRequest {
+  limit?: number;
+}
+
+export type DiscoverySourceKind = "curator" | "franchise";
+
+export interface DiscoveryFollowPayload {
+  kind: DiscoverySourceKind;
+  sourceId: string;
+  name: string;
+  followedAt: string;
+  lastFetchedAt?: string | null;
+}
+
+export interface DiscoveryFeedItemPayload {
+  kind: DiscoverySourceKind | "wishlist_discount" | "new_release";
+  /** The followed curator or franchise, or the owned genre a new release matched. */
+  sourceName?: string | null;
+  appId: string;
+  name: string;
+  detail?: string | null;
+  discountPercent?: number | null;
+  price?: string | null;
+  fetchedAt: string;
+}
+
+export interface DiscoveryFeedPayload {
+  items: DiscoveryFeedItemPayload[];
+  follows: DiscoveryFollowPayload[];
+  refreshedAt?: string | null;
+}
+
+export interface DiscoveryRefreshPayload {
+  refreshedSources: number;
+  failedSources: number;
+  items: number;
+  /** Games from owned developers and publishers that appeared since the last refresh. */
+  newDeveloperReleases: number;
+}
+
+export type DeveloperRole = "developer" | "publisher";
+
+export interface WatchedDeveloperPayload {
+  name: string;
+  role: DeveloperRole;
+  ownedGames: number;
+  lastScannedAt?: string | null;
+}
+
+export interface DeveloperReleasePayload {
+  appId: string;
+  name: string;
+  developer: string;
+  role: DeveloperRole;
+  /** As the store shows it, e.g. `12 Mar, 2025` or `Coming soon`. */
+  releaseDate?: string | null;
+  firstSeenAt: string;
+}
+
+export interface DeveloperReleasesPayload {
+  releases: DeveloperReleasePayload[];
+  watched: WatchedDeveloperPayload[];
+}
+
+export interface GetDeveloperReleasesRequest {
+  limit?: number;
+}
+
+export interface GetDiscoveryFeedRequest {
+  limit?: number;
+}
+
+export interface FollowDiscoverySourceRequest {
+  kind: DiscoverySourceKind;
+  /** A store URL or numeric ID for curators, a store URL or slug for franchises. */
+  source: string;
+  name?: string;
+}
+
+export interface UnfollowDiscoverySourceRequest {
+  kind: DiscoverySourceKind;
+  sourceId: string;
+}
+
+export interface SetDownloadRateLimitRequest {
+  kbps?: number;
+}
+
+export interface DownloadWindowPayload {
+  weekday: number;
+  start: string;
+  end: string;
+}
+
+export interface DownloadSchedulePayload {
+  enabled: boolean;
+  windows: DownloadWindowPayload[];
+}
+
+export interface SetDownloadScheduleRequest {
+  schedule: DownloadSchedulePayload;
+}
+
+export interface SteamCmdSettingsPayload {
+  path?: string | null;
+  username?: string | null;
+}
+
+export interface SetSteamCmdSettingsRequest {
+  settings: SteamCmdSettingsPayload;
+}
+
+export interface ApiServerSettingsPayload {
+  enabled: boolean;
+  port: number;
+  allowLan?: boolean;
+}
+
+export interface ApiServerSettingsResponse extends ApiServerSettingsPayload {
+  baseUrl: string;
+  token?: string | null;
+}
+
+export interface SetApiServerSettingsRequest {
+  settings: ApiServerSettingsPayload;
+}
+
+export interface GamingPcSettingsPayload {
+  name?: string | null;
+  macAddress: string;
+  broadcastAddress?: string | null;
+  apiUrl?: string | null;
+}
+
+export interface GamingPcSettingsResponse {
+  settings?: GamingPcSettingsPayload | null;
+  apiTokenConfigured: boolean;
+}
+
+export interface SetGamingPcSettingsRequest {
+  settings: GamingPcSettingsPayload | null;
+  apiToken?: string;
+}
+
+export interface HttpProxySettingsPayload {
+  kind: "http" | "socks5";
+  host: string;
+  port: number;
+  username?: string | null;
+}
+
+export interface HttpProxySettingsResponse {
+  settings?: HttpProxySettingsPayload | null;
+  passwordConfigured: boolean;
+}
+
+export interface SetHttpProxySettingsRequest {
+  settings: HttpProxySettingsPayload | null;
+  password?: string;
+}
+
+export interface TestProxyConnectionRequest {
+  settings?: HttpProxySettingsPayload | null;
+  password?: string;
+}
+
+export interface HttpProxyTestResponse {
+  success: boolean;
+  latencyMs?: number | null;
+  message?: string | null;
 }
 
 export interface GameVersionBetasPayload {
@@ -91,6 +321,25 @@ export interface SetGamePrivacySettingsRequest extends ProviderExternalIdRequest
   markAsPrivate: boolean;
 }
 
+export interface PrivateGamesLockStatusResponse {
+  pinSet: boolean;
+  unlocked: boolean;
+  relocksInSeconds: number | null;
+}
+
+export interface SetPrivateGamesPinRequest {
+  pin: string;
+  currentPin?: string;
+}
+
+export interface ClearPrivateGamesPinRequest {
+  currentPin: string;
+}
+
+export interface UnlockPrivateGamesRequest {
+  pin: string;
+}
+
 export interface SetGamePropertiesSettingsRequest extends ProviderExternalIdRequest {
   settings: GamePropertiesPersistedSettings;
 }
@@ -101,25 +350,540 @@ export interface AddGameToCollectionRequest extends ProviderExternalIdRequest {
 
 export interface InstallGameRequest extends ProviderExternalIdRequest {
   installPath: string;
+  sourcePath?: string;
   createDesktopShortcut: boolean;
   createApplicationShortcut: boolean;
 }
 
+export interface SteamCmdInstallOptions {
+  libraryPath: string;
+  branch?: string;
+  branchPassword?: string;
+  depotIds?: number[];
+  password?: string;
+  steamGuardCode?: string;
+}
+
+export interface InstallGameWithSteamCmdRequest extends ProviderExternalIdRequest {
+  options: SteamCmdInstallOptions;
+}
+
+export interface SteamCmdInstallResponse {
+  appId: string;
+  installPath: string;
+  manifestPath: string;
+  buildId?: number | null;
+  sizeOnDisk: number;
+}
+
+/** Emitted as `deep-link`. `confirm_play` launches nothing until the user confirms it. */
+export interface DeepLinkActionPayload {
+  action: "open_game" | "confirm_play";
+  provider: string;
+  externalId: string;
+}
+
+export interface SteamCmdInstallProgressPayload {
+  appId: string;
+  phase: "starting" | "downloading" | "verifying" | "registering" | "complete";
+  progressPercent?: number | null;
+  bytesDone?: number | null;
+  bytesTotal?: number | null;
+}
+
+export interface PendingDepotUpdateResponse {
+  depotId: string;
+  installedManifestId: string;
+  targetManifestId: string;
+  installedSizeBytes?: number | null;
+  targetSizeBytes?: number | null;
+  downloadBytes?: number | null;
+}
+
+export interface PendingUpdateSizeResponse {
+  branch: string;
+  installedBuildId?: number | null;
+  targetBuildId?: number | null;
+  updatePending: boolean;
+  downloadBytes?: number | null;
+  diskDeltaBytes?: number | null;
+  source?: "app_manifest" | "depot_manifests" | null;
+  changedDepots: PendingDepotUpdateResponse[];
+}
+
+export interface RemotePlayHostResponse {
+  clientId: string;
+  hostname: string;
+  ipAddress?: string | null;
+  lastSeenAt?: string | null;
+}
+
+export interface PlayGameRemoteRequest extends ProviderExternalIdRequest {
+  hostId?: string;
+}
+
+export interface RunGamePrefixToolRequest extends ProviderExternalIdRequest {
+  verbs: string[];
+}
+
+export interface ProtonPrefixToolResponse {
+  tool: "protontricks" | "winetricks";
+  success: boolean;
+  exitCode?: number | null;
+  output: string;
+}
+
+export interface DeleteGameCompatdataRequest extends ProviderExternalIdRequest {
+  confirm: boolean;
+}
+
+export interface CreateGameShortcutRequest extends ProviderExternalIdRequest {
+  location?: "desktop" | "applications";
+}
+
+export interface GameScreenshotPayload {
+  path: string;
+  thumbnailPath?: string | null;
+  createdAt?: string | null;
+  caption?: string | null;
+  source: "steam" | "custom";
+}
+
+export interface GameSessionPayload {
+  id: string;
+  provider: string;
+  externalId: string;
+  startedAt: string;
+  endedAt: string;
+  durationMinutes: number;
+  notes?: string | null;
+  /** 1 to 5. */
+  rating?: number | null;
+  annotatedAt?: string | null;
+  /** Screenshots taken while the session ran, newest first. */
+  screenshots: GameScreenshotPayload[];
+}
+
+export interface GetGameSessionsRequest extends ProviderExternalIdRequest {
+  limit?: number;
+}
+
+export interface AnnotateGameSessionRequest {
+  sessionId: string;
+  /** Empty or omitted clears the notes. */
+  notes?: string;
+  rating?: number;
+}
+
+export interface GameCardStatusPayload {
+  hasTradingCards: boolean;
+  /** 0 until the first level is crafted, 5 when the regular badge is maxed. */
+  badgeLevel: number;
+  badgeXp: number;
+  foilBadgeCrafted: boolean;
+  /** Unknown when the badge page doesn't show it. */
+  cardDropsRemaining?: number | null;
+  warning?: string | null;
+  stale: boolean;
+}
+
+export interface SteamBadgeEntryPayload {
+  badgeId: number;
+  appId?: number | null;
+  gameName?: string | null;
+  level: number;
+  xp: number;
+  foil: boolean;
+  completedAt?: string | null;
+}
+
+export interface SteamBadgesPayload {
+  playerLevel: number;
+  playerXp: number;
+  xpNeededToLevelUp: number;
+  xpNeededCurrentLevel: number;
+  gameBadgeCount: number;
+  foilBadgeCount: number;
+  unfinishedGameBadgeCount: number;
+  /** Most recently earned first. */
+  badges: SteamBadgeEntryPayload[];
+  warning?: string | null;
+  stale: boolean;
+}
+
+export interface GetSteamBadgesRequest {
+  refresh?: boolean;
+}
+
+export interface GameShortcutResponse {
+  shortcutPath: string;
+  targetUri: string;
+  iconPath: string | null;
+}
+
+export interface InstallJobResponse {
+  id: string;
+  provider: string;
+  externalId: string;
+  sourcePath: string;
+  installPath: string;
+  createDesktopShortcut: boolean;
+  createApplicationShortcut: boolean;
+  status: "queued" | "installing" | "completed" | "failed";
+  bytesTotal: number;
+  bytesDone: number;
+  error: string | null;
+  createdAt: string;
+  updatedAt: string;
+}
+
 export interface SetGameFavoriteRequest extends ProviderExternalIdRequest {
   favorite: boolean;
 }
 
+export interface SetGamesFavoriteRequest {
+  games: ProviderExternalIdRequest[];
+  favorite: boolean;
+}
+
+export interface SetGamesHiddenRequest {
+  games: ProviderExternalIdRequest[];
+  hidden: boolean;
+}
+
+export interface SetGamesHiddenResponse {
+  updatedGames: number;
+  /** Set when not every Steam game could be hidden in Steam's config files. */
+  steamConfigError?: string | null;
+}
+
 export interface ValidateGameBetaAccessCodeRequest extends ProviderExternalIdRequest {
   accessCode: string;
 }
 
-export interface ListCollectionsForGameRequest extends ProviderExternalIdRequest {}
+export interface ListCollectionsForGameRequest extends ProviderExternalIdRequest {
+  includeHidden?: boolean;
+}
+
+export interface ListCollectionsRequest {
+  includeHidden?: boolean;
+}
+
+export interface BulkUpdatePolicyRequest {
+  policy: {
+    games?: ProviderExternalIdRequest[];
+    filter?: LibraryQueryFilter;
+    installedOnly?: boolean;
+    automaticUpdatesMode?: "use-global-setting" | "wait-until-launch" | "let-steam-decide" | "immediately-download";
+    backgroundDownloadsMode?: "pause-while-playing-global" | "always-allow" | "never-allow";
+  };
+}
+
+export interface BulkUpdatePolicyResponse {
+  updatedGames: number;
+  steamConfigError?: string;
+  steamConfigQueued: boolean;
+}
+
+export interface GameVerificationStatusResponse {
+  provider: string;
+  externalId: string;
+  status: "idle" | "queued" | "validating" | "completed" | "timed_out";
+  startedAt: string | null;
+  lastVerifiedAt: string | null;
+}
+
+export interface SteamConfigBackupPayload {
+  id: string;
+  originalPath: string;
+  createdAt: string;
+  sizeBytes: number;
+}
+
+export interface RestoreSteamConfigBackupRequest {
+  backupId: string;
+}
+
+export interface LibraryQueryFilter {
+  genre?: string;
+  category?: string;
+  developer?: string;
+  publisher?: string;
+  completed?: boolean;
+  controllerSupport?: "full" | "partial";
+  vr?: boolean;
+  steamTag?: string;
+  userTag?: string;
+}
+
+export interface ListSteamTagsRequest {
+  includeHidden?: boolean;
+}
+
+export interface SteamTagCountResponse {
+  name: string;
+  gameCount: number;
+}
+
+export interface SteamManifestCopyResponse {
+  manifestPath: string;
+  libraryPath: string;
+  sizeOnDiskBytes?: number | null;
+  lastUpdatedAt?: string | null;
+  active: boolean;
+}
+
+export interface SteamLibraryConflictResponse {
+  appId: number;
+  name?: string | null;
+  copies: SteamManifestCopyResponse[];
+}
+
+export interface SearchLibraryRequest {
+  query: string;
+  limit?: number;
+}
+
+export type SteamCollectionsImportMode = "merge" | "replace" | "preview";
+
+export interface ImportSteamCollectionsRequest {
+  mode?: SteamCollectionsImportMode;
+  /** Link Steam's Favorites to Catalyst favorites instead of importing it as a collection. */
+  importFavorites?: boolean;
+}
+
+export interface SteamCollectionMembershipChange {
+  collectionName: string;
+  externalId: string;
+  removed: boolean;
+}
+
+export interface SteamCollectionsImportResponse {
+  mode: SteamCollectionsImportMode;
+  appsTagged: number;
+  collectionsCreated: number;
+  membershipsAdded: number;
+  membershipsRemoved: number;
+  favoritesLinked: number;
+  skippedGames: number;
+  tagsDiscovered: number;
+  dynamicCollectionsSkipped: number;
+  changes: SteamCollectionMembershipChange[];
+}
+
+export interface LibrarySearchMatch {
+  provider: string;
+  externalId: string;
+  name: string;
+  /** Best matching excerpt with matches wrapped in `<mark>`; the text itself is not HTML-escaped. */
+  snippet: string;
+  /** BM25 score; lower is a better match. */
+  rank: number;
+}
+
+export interface SaveGameNotesRequest extends ProviderExternalIdRequest {
+  notes: string;
+}
+
+export interface SetGameDisplayNameRequest extends ProviderExternalIdRequest {
+  /** Blank or omitted restores the synced name. */
+  displayName?: string | null;
+}
+
+export interface GameUserTagRequest extends ProviderExternalIdRequest {
+  tag: string;
+}
+
+export interface SetGameCustomArtworkRequest extends ProviderExternalIdRequest {
+  /** A local PNG, JPEG, GIF or WebP file; it is copied, so the original can be removed. */
+  path: string;
+}
+
+export interface RefreshAchievementsRequest {
+  provider?: string;
+  externalId?: string;
+}
+
+export interface AchievementsRefreshResponse {
+  refreshedGames: number;
+  completedGames: number;
+  failedGames: number;
+}
+
+export interface SteamSyncResponse {
+  userId: string;
+  provider: string;
+  syncedGames: number;
+  addedGames: number;
+  updatedGames: number;
+  removedGames: number;
+  warning: string | null;
+}
+
+export interface GetLibraryRequest {
+  merged?: boolean;
+  filter?: LibraryQueryFilter;
+  includeHidden?: boolean;
+}
+
+export interface ImportPlayniteLibraryRequest {
+  path: string;
+  dryRun?: boolean;
+}
+
+export interface ImportLutrisLibraryRequest {
+  path?: string;
+  dryRun?: boolean;
+}
+
+export interface LibraryImportResponse {
+  source: "playnite" | "lutris";
+  dryRun: boolean;
+  totalEntries: number;
+  gamesAdded: number;
+  gamesAlreadyInLibrary: number;
+  customGames: number;
+  skippedEntries: number;
+  collectionsCreated: number;
+  membershipsAdded: number;
+}
+
+export interface GetLibraryStatsRequest {
+  refresh?: boolean;
+}
+
+export interface PlaytimeBreakdownEntry {
+  name: string;
+  gameCount: number;
+  playtimeMinutes: number;
+}
+
+export interface MostPlayedYearEntry {
+  year: number;
+  provider: string;
+  externalId: string;
+  name: string;
+  playtimeMinutes: number;
+}
+
+export interface ProviderDiskUsageEntry {
+  provider: string;
+  gamesBytes: number;
+  gameCount: number;
+}
+
+export interface LibraryStatsResponse {
+  totalGames: number;
+  installedGames: number;
+  totalPlaytimeMinutes: number;
+  backlogGames: number;
+  playtimeByGenre: PlaytimeBreakdownEntry[];
+  playtimeByTag: PlaytimeBreakdownEntry[];
+  mostPlayedByYear: MostPlayedYearEntry[];
+  diskUsageByProvider: ProviderDiskUsageEntry[];
+  generatedAt: string;
+}
+
+export interface GetPlaytimeTimelineRequest {
+  granularity?: "week" | "month";
+  year?: number;
+}
+
+/** From 0 (ignored) to 10. Omitted weights keep their defaults. */
+export interface SuggestGameWeights {
+  short?: number;
+  reviewScore?: number;
+  installed?: number;
+  mood?: number;
+}
+
+export interface SuggestGameRequest {
+  weights?: SuggestGameWeights;
+  /** Store or personal tags to favor, e.g. `Cozy` or `Puzzle`. */
+  moods?: string[];
+  count?: number;
+}
+
+export interface GameSuggestionResponse {
+  provider: string;
+  externalId: string;
+  name: string;
+  playtimeMinutes: number;
+  installed: boolean;
+  score: number;
+  reasons: string[];
+}
+
+export interface PlaytimeTimelineGameEntry {
+  provider: string;
+  externalId: string;
+  name: string;
+  playtimeMinutes: number;
+  sessionCount: number;
+  source: "session" | "last_played";
+}
+
+export interface PlaytimeTimelineBucket {
+  period: string;
+  playtimeMinutes: number;
+  sessionCount: number;
+  games: PlaytimeTimelineGameEntry[];
+}
+
+export interface PlaytimeTimelineResponse {
+  granularity: "week" | "month";
+  year: number | null;
+  totalPlaytimeMinutes: number;
+  buckets: PlaytimeTimelineBucket[];
+}
+
+export interface NotificationResponse {
+  id: string;
+  kind: "download_finished" | "sync_completed" | "sync_failed";
+  title: string;
+  body: string;
+  provider: string | null;
+  externalId: string | null;
+  read: boolean;
+  createdAt: string;
+}
+
+export interface ListNotificationsRequest {
+  unreadOnly?: boolean;
+  limit?: number;
+}
+
+export interface MarkNotificationReadRequest {
+  notificationId?: string;
+}
+
+export interface LinkLibraryGamesRequest {
+  games: ProviderExternalIdRequest[];
+}
+
+export interface SetCollectionGameOrderRequest {
+  collectionId: string;
+  /** Members in display order; members left out are listed after these, by name. */
+  games: ProviderExternalIdRequest[];
+}
 
 export interface RenameCollectionRequest {
   collectionId: string;
   name: string;
 }
 
+export interface SetCollectionCoverImageRequest {
+  collectionId: string;
+  /** A local image file to copy in; omit to clear the cover. */
+  path?: string | null;
+}
+
+export interface SetCollectionAccentColorRequest {
+  collectionId: string;
+  /** `#rgb` or `#rrggbb`; omit to clear the color. */
+  accentColor?: string | null;
+}
+
 export interface DeleteCollectionRequest {
   collectionId: string;
 }
@@ -131,16 +895,41 @@ export interface CreateCollectionRequest {
 export interface IpcContracts {
   get_session: { req: void; res: PublicUser | null };
   start_steam_auth: { req: void; res: SteamAuthResponse };
+  cancel_steam_auth: { req: void; res: boolean };
+  verify_2fa_login: { req: TwoFactorCodeRequest; res: SteamAuthResponse };
   logout: { req: void; res: void };
   start_local_steam_scan: { req: void; res: void };
-  sync_steam_library: { req: void; res: void };
-  import_steam_collections: { req: void; res: void };
-  get_library: { req: void; res: LibraryResponse };
-  list_collections: { req: void | ListCollectionsForGameRequest; res: CollectionResponse[] };
+  sync_steam_library: { req: void; res: SteamSyncResponse };
+  get_library_conflicts: { req: void; res: SteamLibraryConflictResponse[] };
+  refresh_achievements: { req: void | RefreshAchievementsRequest; res: AchievementsRefreshResponse };
+  search_library: { req: SearchLibraryRequest; res: LibrarySearchMatch[] };
+  get_game_notes: { req: ProviderExternalIdRequest; res: string };
+  save_game_notes: { req: SaveGameNotesRequest; res: string };
+  set_game_display_name: { req: SetGameDisplayNameRequest; res: string | null };
+  add_game_user_tag: { req: GameUserTagRequest; res: string[] };
+  remove_game_user_tag: { req: GameUserTagRequest; res: string[] };
+  import_steam_collections: { req: void | ImportSteamCollectionsRequest; res: SteamCollectionsImportResponse };
+  get_library: { req: void | GetLibraryRequest; res: LibraryResponse };
+  list_steam_tags: { req: void | ListSteamTagsRequest; res: SteamTagCountResponse[] };
+  list_hidden_games: { req: void; res: GameResponse[] };
+  link_library_games: { req: LinkLibraryGamesRequest; res: MergedGameResponse[] };
+  unlink_library_game: { req: ProviderExternalIdRequest; res: MergedGameResponse[] };
+  set_preferred_launch_provider: { req: ProviderExternalIdRequest; res: MergedGameResponse };
+  import_playnite_library: { req: ImportPlayniteLibraryRequest; res: LibraryImportResponse };
+  import_lutris_library: { req: void | ImportLutrisLibraryRequest; res: LibraryImportResponse };
+  get_library_stats: { req: void | GetLibraryStatsRequest; res: LibraryStatsResponse };
+  get_playtime_timeline: { req: void | GetPlaytimeTimelineRequest; res: PlaytimeTimelineResponse };
+  suggest_game: { req: void | SuggestGameRequest; res: GameSuggestionResponse[] };
+  list_notifications: { req: void | ListNotificationsRequest; res: NotificationResponse[] };
+  mark_notification_read: { req: void | MarkNotificationReadRequest; res: number };
+  list_collections: { req: void | ListCollectionsRequest | ListCollectionsForGameRequest; res: CollectionResponse[] };
   create_collection: { req: CreateCollectionRequest; res: CollectionResponse };
   rename_collection: { req: RenameCollectionRequest; res: CollectionResponse };
+  set_collection_cover_image: { req: SetCollectionCoverImageRequest; res: CollectionResponse };
+  set_collection_accent_color: { req: SetCollectionAccentColorRequest; res: CollectionResponse };
   delete_collection: { req: DeleteCollectionRequest; res: void };
   add_game_to_collection: { req: AddGameToCollectionRequest; res: void };
+  set_collection_game_order: { req: SetCollectionGameOrderRequest; res: void };
   list_game_languages: { req: ProviderExternalIdRequest; res: string[] };
   list_game_compatibility_tools: { req: ProviderExternalIdRequest; res: GameCompatibilityToolOption[] };
   list_game_versions_betas: { req: ProviderExternalIdRequest; res: GameVersionBetasPayload };
@@ -148,26 +937,79 @@ export interface IpcContracts {
     req: ValidateGameBetaAccessCodeRequest;
     res: GameBetaAccessCodeValidationResult;
   };
+  list_steam_config_backups: { req: void; res: SteamConfigBackupPayload[] };
+  restore_steam_config_backup: { req: RestoreSteamConfigBackupRequest; res: SteamConfigBackupPayload };
   get_game_privacy_settings: { req: ProviderExternalIdRequest; res: GamePrivacySettingsPayload };
   set_game_privacy_settings: { req: SetGamePrivacySettingsRequest; res: void };
+  set_games_hidden: { req: SetGamesHiddenRequest; res: SetGamesHiddenResponse };
+  get_private_games_lock_status: { req: void; res: PrivateGamesLockStatusResponse };
+  set_private_games_pin: { req: SetPrivateGamesPinRequest; res: PrivateGamesLockStatusResponse };
+  clear_private_games_pin: { req: ClearPrivateGamesPinRequest; res: PrivateGamesLockStatusResponse };
+  unlock_private_games: { req: UnlockPrivateGamesRequest; res: PrivateGamesLockStatusResponse };
+  lock_private_games: { req: void; res: PrivateGamesLockStatusResponse };
   clear_game_overlay_data: { req: ProviderExternalIdRequest; res: void };
   get_game_installation_details: { req: ProviderExternalIdRequest; res: GameInstallationDetailsPayload };
   get_game_customization_artwork: { req: ProviderExternalIdRequest; res: GameCustomizationArtworkPayload };
+  set_game_custom_artwork: { req: SetGameCustomArtworkRequest; res: string };
+  clear_game_custom_artwork: { req: ProviderExternalIdRequest; res: void };
   get_game_store_metadata: { req: ProviderExternalIdRequest; res: GameStoreMetadataPayload };
   list_game_install_locations: { req: ProviderExternalIdRequest; res: GameInstallLocationPayload[] };
   get_game_install_size_estimate: { req: ProviderExternalIdRequest; res: number | null };
+  get_pending_update_size: { req: ProviderExternalIdRequest; res: PendingUpdateSizeResponse };
   list_steam_downloads: { req: void; res: SteamDownloadProgressPayload[] };
+  pause_download: { req: SteamDownloadRequest; res: void };
+  resume_download: { req: SteamDownloadRequest; res: void };
+  reorder_download_queue: { req: ReorderDownloadQueueRequest; res: SteamDownloadProgressPayload[] };
+  get_download_history: { req: void | GetDownloadHistoryRequest; res: DownloadHistoryPayload };
+  get_download_rate_limit: { req: void; res: DownloadRateLimitPayload };
+  set_download_rate_limit: { req: SetDownloadRateLimitRequest; res: DownloadRateLimitPayload };
+  get_locale: { req: void; res: LocaleResponse };
+  set_locale: { req: SetLocaleRequest; res: LocaleResponse };
+  get_discovery_feed: { req: void | GetDiscoveryFeedRequest; res: DiscoveryFeedPayload };
+  refresh_discovery_feed: { req: void; res: DiscoveryRefreshPayload };
+  follow_discovery_source: { req: FollowDiscoverySourceRequest; res: DiscoveryFollowPayload };
+  unfollow_discovery_source: { req: UnfollowDiscoverySourceRequest; res: void };
+  get_developer_releases: { req: void | GetDeveloperReleasesRequest; res: DeveloperReleasesPayload };
+  get_download_schedule: { req: void; res: DownloadSchedulePayload };
+  set_download_schedule: { req: SetDownloadScheduleRequest; res: DownloadSchedulePayload };
+  get_steamcmd_settings: { req: void; res: SteamCmdSettingsPayload };
+  set_steamcmd_settings: { req: SetSteamCmdSettingsRequest; res: SteamCmdSettingsPayload };
+  get_api_server_settings: { req: void; res: ApiServerSettingsResponse };
+  set_api_server_settings: { req: SetApiServerSettingsRequest; res: ApiServerSettingsResponse };
+  regenerate_api_server_token: { req: void; res: ApiServerSettingsResponse };
+  get_gaming_pc_settings: { req: void; res: GamingPcSettingsResponse };
+  set_gaming_pc_settings: { req: SetGamingPcSettingsRequest; res: GamingPcSettingsResponse };
+  get_http_proxy_settings: { req: void; res: HttpProxySettingsResponse };
+  set_http_proxy_settings: { req: SetHttpProxySettingsRequest; res: HttpProxySettingsResponse };
+  test_proxy_connection: { req: TestProxyConnectionRequest; res: HttpProxyTestResponse };
   get_game_properties_settings: { req: ProviderExternalIdRequest; res: GamePropertiesPersistedSettings };
   set_game_properties_settings: { req: SetGamePropertiesSettingsRequest; res: void };
+  bulk_set_update_policy: { req: BulkUpdatePolicyRequest; res: BulkUpdatePolicyResponse };
   browse_game_installed_files: { req: ProviderExternalIdRequest; res: void };
   backup_game_files: { req: ProviderExternalIdRequest; res: void };
   verify_game_files: { req: ProviderExternalIdRequest; res: void };
+  get_game_verification_status: { req: ProviderExternalIdRequest; res: GameVerificationStatusResponse };
   add_game_desktop_shortcut: { req: ProviderExternalIdRequest; res: void };
+  create_game_shortcut: { req: CreateGameShortcutRequest; res: GameShortcutResponse };
+  get_game_sessions: { req: GetGameSessionsRequest; res: GameSessionPayload[] };
+  annotate_game_session: { req: AnnotateGameSessionRequest; res: GameSessionPayload };
+  get_game_card_status: { req: ProviderExternalIdRequest; res: GameCardStatusPayload };
+  get_steam_badges: { req: void | GetSteamBadgesRequest; res: SteamBadgesPayload };
   open_game_recording_settings: { req: ProviderExternalIdRequest; res: void };
   uninstall_game: { req: ProviderExternalIdRequest; res: void };
-  install_game: { req: InstallGameRequest; res: void };
+  install_game: { req: InstallGameRequest; res: InstallJobResponse | null };
+  install_game_with_steamcmd: { req: InstallGameWithSteamCmdRequest; res: SteamCmdInstallResponse };
+  list_install_jobs: { req: void; res: InstallJobResponse[] };
   play_game: { req: ProviderExternalIdRequest; res: void };
+  play_game_remote: { req: PlayGameRemoteRequest; res: void };
+  list_remote_play_hosts: { req: void; res: RemotePlayHostResponse[] };
+  wake_gaming_pc: { req: void; res: void };
+  start_gaming_pc_download: { req: ProviderExternalIdRequest; res: void };
+  open_game_proton_prefix: { req: ProviderExternalIdRequest; res: void };
+  run_game_prefix_tool: { req: RunGamePrefixToolRequest; res: ProtonPrefixToolResponse };
+  delete_game_compatdata: { req: DeleteGameCompatdataRequest; res: void };
   set_game_favorite: { req: SetGameFavoriteRequest; res: void };
+  set_games_favorite: { req: SetGamesFavoriteRequest; res: number };
 }
 
 export type IpcCommandName = keyof IpcContracts;